@@ -1 +1,206 @@
 // pub mod ctx;
+
+pub mod request_ctx;
+pub mod service_ctx;
+
+pub use request_ctx::{RequestCtx, CORRELATION_ID_HEADER, REQUEST_ID_HEADER};
+pub use service_ctx::ServiceCtx;
+
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use lib_types::enums::UserRole;
+use lib_types::errors::AuthError;
+use lib_types::ids::{HospitalId, UserId};
+
+use crate::jwt::{DelegationClaim, HealthcareClaims};
+use crate::middleware::AuthRejection;
+use crate::rbac::Permissions;
+
+/// Authenticated caller context, resolved from the access token by the auth middleware
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ctx {
+    user_id: UserId,
+    role: UserRole,
+    hospital_id: HospitalId,
+    session_id: Uuid,
+    permissions: Permissions,
+    auth_time: DateTime<Utc>,
+    delegation: Option<DelegationClaim>,
+}
+
+impl Ctx {
+    /// Create a new context for a user who has just authenticated
+    pub fn new(user_id: UserId, role: UserRole, hospital_id: HospitalId, session_id: Uuid) -> Self {
+        Self {
+            user_id,
+            role,
+            hospital_id,
+            session_id,
+            permissions: Permissions::for_role(role),
+            auth_time: Utc::now(),
+            delegation: None,
+        }
+    }
+
+    /// Set when the user last entered their credentials
+    pub fn with_auth_time(mut self, auth_time: DateTime<Utc>) -> Self {
+        self.auth_time = auth_time;
+        self
+    }
+
+    /// Replace the permissions granted by the role
+    pub fn with_permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
+    /// Act with another user's role until the delegation expires
+    pub fn with_delegation(mut self, delegation: DelegationClaim) -> Self {
+        self.delegation = Some(delegation);
+        self
+    }
+
+    /// Drop the delegation (e.g. after it was revoked) and act with the user's own role
+    pub fn without_delegation(mut self) -> Self {
+        self.delegation = None;
+        self
+    }
+
+    /// Create context from verified token claims (expired delegations are ignored)
+    pub fn from_claims(claims: &HealthcareClaims) -> Self {
+        let auth_time = DateTime::from_timestamp(claims.auth_time, 0).unwrap_or_default();
+        let ctx = Self::new(claims.sub, claims.role, claims.hospital_id, claims.sid)
+            .with_permissions(claims.permissions)
+            .with_auth_time(auth_time);
+
+        match claims.active_delegation(Utc::now().timestamp()) {
+            Some(delegation) => ctx.with_delegation(delegation.clone()),
+            None => ctx,
+        }
+    }
+
+    pub fn user_id(&self) -> UserId {
+        self.user_id
+    }
+
+    /// Get the role used for permission checks: the delegated role while a
+    /// delegation is active, otherwise the user's own role
+    pub fn role(&self) -> UserRole {
+        self.delegation
+            .as_ref()
+            .map_or(self.role, |delegation| delegation.role)
+    }
+
+    /// Get the user's own role, ignoring any delegation
+    pub fn own_role(&self) -> UserRole {
+        self.role
+    }
+
+    pub fn hospital_id(&self) -> HospitalId {
+        self.hospital_id
+    }
+
+    pub fn session_id(&self) -> Uuid {
+        self.session_id
+    }
+
+    /// Get the caller's permissions, including those of a delegated role
+    pub fn permissions(&self) -> Permissions {
+        match &self.delegation {
+            Some(delegation) => self.permissions | Permissions::for_role(delegation.role),
+            None => self.permissions,
+        }
+    }
+
+    /// Check if the caller holds every permission in `required`
+    pub fn has_permission(&self, required: Permissions) -> bool {
+        self.permissions().contains(required)
+    }
+
+    pub fn auth_time(&self) -> DateTime<Utc> {
+        self.auth_time
+    }
+
+    pub fn delegation(&self) -> Option<&DelegationClaim> {
+        self.delegation.as_ref()
+    }
+
+    /// Check if the caller belongs to the given hospital
+    pub fn same_hospital(&self, hospital_id: HospitalId) -> bool {
+        self.hospital_id == hospital_id
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Ctx {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Ctx>()
+            .cloned()
+            .ok_or_else(|| AuthError::MissingToken.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ctx_from_claims() {
+        let claims = HealthcareClaims::builder(
+            UserId::new(),
+            "omar.paramedic",
+            UserRole::Paramedic,
+            HospitalId::new(),
+            Uuid::new_v4(),
+        )
+        .build("dubai-healthcare-emergency", "healthcare-staff", 0, 3600);
+
+        let ctx = Ctx::from_claims(&claims);
+        assert_eq!(ctx.user_id(), claims.sub);
+        assert_eq!(ctx.role(), UserRole::Paramedic);
+        assert_eq!(ctx.session_id(), claims.sid);
+        assert_eq!(ctx.permissions(), claims.permissions);
+        assert_eq!(ctx.auth_time().timestamp(), claims.auth_time);
+        assert!(ctx.same_hospital(claims.hospital_id));
+        assert!(!ctx.same_hospital(HospitalId::new()));
+    }
+
+    #[test]
+    fn test_delegated_role_applies_until_expiry() {
+        let now = Utc::now().timestamp();
+        let mut claims = HealthcareClaims::builder(
+            UserId::new(),
+            "layla.specialist",
+            UserRole::Specialist,
+            HospitalId::new(),
+            Uuid::new_v4(),
+        )
+        .delegation(Some(DelegationClaim {
+            id: Uuid::new_v4(),
+            delegator_id: UserId::new(),
+            role: UserRole::ErDirector,
+            exp: now + 600,
+        }))
+        .build("dubai-healthcare-emergency", "healthcare-staff", now, 3600);
+
+        let ctx = Ctx::from_claims(&claims);
+        assert_eq!(ctx.role(), UserRole::ErDirector);
+        assert_eq!(ctx.own_role(), UserRole::Specialist);
+        assert!(ctx.has_permission(Permissions::DELEGATE));
+        assert_eq!(ctx.clone().without_delegation().role(), UserRole::Specialist);
+
+        claims.delegation.as_mut().unwrap().exp = now - 1;
+        let ctx = Ctx::from_claims(&claims);
+        assert_eq!(ctx.role(), UserRole::Specialist);
+        assert!(ctx.delegation().is_none());
+        assert!(!ctx.has_permission(Permissions::DELEGATE));
+    }
+}
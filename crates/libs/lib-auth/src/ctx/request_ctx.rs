@@ -0,0 +1,273 @@
+use std::convert::Infallible;
+
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::header::ACCEPT_LANGUAGE;
+use axum::http::request::Parts;
+use axum::http::HeaderMap;
+use chrono::Utc;
+use uuid::Uuid;
+
+use lib_types::enums::{Locale, UserRole};
+use lib_types::ids::{HospitalId, UserId};
+
+use crate::rbac::BreakGlassGrant;
+
+use super::{Ctx, ServiceCtx};
+
+/// Header carrying the id used to correlate logs and audit rows of one request
+/// and of the calls it leads to, across services
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+/// Header carrying the id of a single request, e.g. one hop of a correlation
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+const DEFAULT_LOCALE: &str = "en";
+const MAX_CORRELATION_ID_LENGTH: usize = 128;
+const MAX_LOCALE_LENGTH: usize = 35;
+
+/// Per-request context passed down to the store layer: who is calling (if
+/// authenticated), the request and correlation ids and the preferred locale
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestCtx {
+    request_id: String,
+    correlation_id: String,
+    locale: String,
+    user: Option<Ctx>,
+    service: Option<ServiceCtx>,
+    break_glass: Option<BreakGlassGrant>,
+}
+
+impl RequestCtx {
+    /// Context that starts a correlation: the request id is the correlation id
+    pub fn new(correlation_id: impl Into<String>, locale: impl Into<String>) -> Self {
+        let correlation_id = correlation_id.into();
+        Self {
+            request_id: correlation_id.clone(),
+            correlation_id,
+            locale: locale.into(),
+            user: None,
+            service: None,
+            break_glass: None,
+        }
+    }
+
+    /// Context for work not triggered by a request (startup, background jobs)
+    pub fn system() -> Self {
+        Self::new(Uuid::new_v4().to_string(), DEFAULT_LOCALE)
+    }
+
+    /// Read the request id, correlation id and locale from request headers.
+    /// A request id the client did not send (or sent unusable) is generated;
+    /// without a correlation id the request starts a correlation of its own.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header_id = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|id| is_valid_correlation_id(id))
+                .map(str::to_string)
+        };
+        let request_id = header_id(REQUEST_ID_HEADER).unwrap_or_else(|| Uuid::new_v4().to_string());
+        let correlation_id = header_id(CORRELATION_ID_HEADER).unwrap_or_else(|| request_id.clone());
+
+        let locale = headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(preferred_locale)
+            .unwrap_or(DEFAULT_LOCALE);
+
+        Self {
+            request_id,
+            correlation_id,
+            locale: locale.to_string(),
+            user: None,
+            service: None,
+            break_glass: None,
+        }
+    }
+
+    /// Attach the authenticated caller
+    pub fn with_user(mut self, ctx: Ctx) -> Self {
+        self.user = Some(ctx);
+        self
+    }
+
+    /// Attach the authenticated service account
+    pub fn with_service(mut self, service: ServiceCtx) -> Self {
+        self.service = Some(service);
+        self
+    }
+
+    /// Attach the break-glass grant a read of another hospital's patient
+    /// was allowed under
+    pub fn with_break_glass(mut self, grant: BreakGlassGrant) -> Self {
+        self.break_glass = Some(grant);
+        self
+    }
+
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    pub fn correlation_id(&self) -> &str {
+        &self.correlation_id
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Language for user-facing messages: the preferred locale if it is
+    /// supported, otherwise English
+    pub fn message_locale(&self) -> Locale {
+        Locale::from_tag(&self.locale).unwrap_or_default()
+    }
+
+    pub fn user(&self) -> Option<&Ctx> {
+        self.user.as_ref()
+    }
+
+    pub fn service(&self) -> Option<&ServiceCtx> {
+        self.service.as_ref()
+    }
+
+    pub fn user_id(&self) -> Option<UserId> {
+        self.user.as_ref().map(Ctx::user_id)
+    }
+
+    pub fn role(&self) -> Option<UserRole> {
+        self.user.as_ref().map(Ctx::role)
+    }
+
+    pub fn hospital_id(&self) -> Option<HospitalId> {
+        self.user.as_ref().map(Ctx::hospital_id)
+    }
+
+    /// Hospital that queries must be restricted to: the caller's own, or the
+    /// one an attached break-glass grant covers while it lasts; none for system
+    /// admins, network-wide service accounts and unauthenticated requests
+    /// (e.g. login)
+    pub fn tenant_hospital_id(&self) -> Option<HospitalId> {
+        if let Some(grant) = &self.break_glass {
+            if grant.is_active_at(Utc::now()) {
+                return Some(grant.hospital_id);
+            }
+        }
+
+        match (&self.user, &self.service) {
+            (Some(ctx), _) => (ctx.role() != UserRole::Admin).then(|| ctx.hospital_id()),
+            (None, Some(service)) => service.hospital_id(),
+            (None, None) => None,
+        }
+    }
+}
+
+fn is_valid_correlation_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_CORRELATION_ID_LENGTH
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Get the first language tag of an `Accept-Language` header
+fn preferred_locale(header: &str) -> Option<&str> {
+    let tag = header.split(',').next()?.split(';').next()?.trim();
+    let valid = !tag.is_empty()
+        && tag.len() <= MAX_LOCALE_LENGTH
+        && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    valid.then_some(tag)
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for RequestCtx {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<RequestCtx>()
+            .cloned()
+            .unwrap_or_else(|| RequestCtx::from_headers(&parts.headers)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rbac::BreakGlassPolicy;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CORRELATION_ID_HEADER,
+            HeaderValue::from_static("ambulance-7f3a"),
+        );
+        headers.insert(
+            ACCEPT_LANGUAGE,
+            HeaderValue::from_static("ar-AE,ar;q=0.9,en;q=0.8"),
+        );
+
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("dispatch-1"));
+
+        let ctx = RequestCtx::from_headers(&headers);
+        assert_eq!(ctx.request_id(), "dispatch-1");
+        assert_eq!(ctx.correlation_id(), "ambulance-7f3a");
+        assert_eq!(ctx.locale(), "ar-AE");
+        assert_eq!(ctx.message_locale(), Locale::Ar);
+        assert_eq!(ctx.user_id(), None);
+    }
+
+    #[test]
+    fn test_defaults_for_missing_or_invalid_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CORRELATION_ID_HEADER, HeaderValue::from_static("bad id!"));
+        headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("*"));
+
+        let ctx = RequestCtx::from_headers(&headers);
+        assert!(Uuid::parse_str(ctx.request_id()).is_ok());
+        assert_eq!(ctx.correlation_id(), ctx.request_id());
+        assert_eq!(ctx.locale(), "en");
+        assert_eq!(ctx.message_locale(), Locale::En);
+    }
+
+    #[test]
+    fn test_tenant_follows_caller() {
+        let hospital_id = HospitalId::new();
+        let nurse = Ctx::new(UserId::new(), UserRole::Nurse, hospital_id, Uuid::new_v4());
+        let admin = Ctx::new(UserId::new(), UserRole::Admin, hospital_id, Uuid::new_v4());
+
+        assert_eq!(RequestCtx::system().tenant_hospital_id(), None);
+        assert_eq!(
+            RequestCtx::system().with_user(nurse).tenant_hospital_id(),
+            Some(hospital_id)
+        );
+        assert_eq!(
+            RequestCtx::system().with_user(admin).tenant_hospital_id(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tenant_follows_active_break_glass_grant() {
+        let nurse = Ctx::new(UserId::new(), UserRole::Nurse, HospitalId::new(), Uuid::new_v4());
+        let mut grant = BreakGlassPolicy::default()
+            .grant(&nurse, HospitalId::new(), "Disaster response, patient records needed", None)
+            .unwrap();
+        let granted = RequestCtx::system().with_user(nurse.clone());
+
+        assert_eq!(
+            granted.clone().with_break_glass(grant.clone()).tenant_hospital_id(),
+            Some(grant.hospital_id)
+        );
+
+        grant.expires_at = Utc::now() - chrono::Duration::seconds(1);
+        assert_eq!(
+            granted.with_break_glass(grant).tenant_hospital_id(),
+            Some(nurse.hospital_id())
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use uuid::Uuid;
+
+use lib_types::enums::ServiceScope;
+use lib_types::errors::AuthError;
+use lib_types::ids::HospitalId;
+
+use crate::jwt::ServiceClaims;
+use crate::middleware::AuthRejection;
+
+/// Authenticated service account context, resolved by `mw_require_service_auth`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceCtx {
+    account_id: Uuid,
+    client_id: String,
+    hospital_id: Option<HospitalId>,
+    scopes: Vec<ServiceScope>,
+}
+
+impl ServiceCtx {
+    /// Create context from verified service token claims
+    pub fn from_claims(claims: &ServiceClaims) -> Self {
+        Self {
+            account_id: claims.sub,
+            client_id: claims.client_id.clone(),
+            hospital_id: claims.hospital_id,
+            scopes: claims.scopes.clone(),
+        }
+    }
+
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Hospital the account is limited to, or `None` for network-wide services
+    pub fn hospital_id(&self) -> Option<HospitalId> {
+        self.hospital_id
+    }
+
+    pub fn scopes(&self) -> &[ServiceScope] {
+        &self.scopes
+    }
+
+    /// Check that the token was granted a scope
+    pub fn require_scope(&self, scope: ServiceScope) -> Result<(), AuthError> {
+        if self.scopes.contains(&scope) {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientPermissions)
+        }
+    }
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for ServiceCtx {
+    type Rejection = AuthRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<ServiceCtx>()
+            .cloned()
+            .ok_or_else(|| AuthError::MissingToken.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_require_scope() {
+        let claims = ServiceClaims {
+            sub: Uuid::new_v4(),
+            client_id: "reporting-jobs".to_string(),
+            hospital_id: None,
+            scopes: vec![ServiceScope::ReportsRead],
+            iss: "dubai-healthcare-emergency".to_string(),
+            aud: "healthcare-staff".to_string(),
+            iat: 0,
+            exp: 900,
+        };
+
+        let ctx = ServiceCtx::from_claims(&claims);
+        assert_eq!(ctx.client_id(), "reporting-jobs");
+        assert!(ctx.require_scope(ServiceScope::ReportsRead).is_ok());
+        assert_eq!(
+            ctx.require_scope(ServiceScope::DispatchWrite),
+            Err(AuthError::InsufficientPermissions)
+        );
+    }
+}
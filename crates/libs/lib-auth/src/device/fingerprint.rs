@@ -0,0 +1,75 @@
+use axum::http::header::USER_AGENT;
+use axum::http::HeaderMap;
+use sha2::{Digest, Sha256};
+
+/// Header carrying the stable identifier generated by the client app on install
+pub const DEVICE_ID_HEADER: &str = "x-device-id";
+
+/// Longest client-supplied device id that is taken into account
+const MAX_DEVICE_ID_LENGTH: usize = 128;
+
+/// Stable, non-reversible identifier of the device a login comes from.
+///
+/// Derived from the client's device id header and user agent, so the raw
+/// values never need to be stored to recognise a returning device.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeviceFingerprint(String);
+
+impl DeviceFingerprint {
+    /// Compute the fingerprint from the client-supplied identifiers
+    pub fn from_client(device_id: Option<&str>, user_agent: Option<&str>) -> Self {
+        let device_id = device_id
+            .map(str::trim)
+            .map(|id| match id.char_indices().nth(MAX_DEVICE_ID_LENGTH) {
+                Some((end, _)) => &id[..end],
+                None => id,
+            })
+            .unwrap_or_default();
+
+        let mut hasher = Sha256::new();
+        hasher.update(device_id.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(user_agent.unwrap_or_default().trim().as_bytes());
+        Self(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Compute the fingerprint of a request
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        Self::from_client(header(DEVICE_ID_HEADER), header(USER_AGENT.as_str()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLET_UA: &str = "DubaiEMS/2.4 (Android 13; SM-T636B)";
+
+    #[test]
+    fn test_fingerprint_is_stable_per_device() {
+        let first = DeviceFingerprint::from_client(Some("tablet-0042"), Some(TABLET_UA));
+        let again = DeviceFingerprint::from_client(Some(" tablet-0042 "), Some(TABLET_UA));
+        let other = DeviceFingerprint::from_client(Some("tablet-0043"), Some(TABLET_UA));
+
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+        assert_eq!(first.as_str().len(), 64);
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(DEVICE_ID_HEADER, "tablet-0042".parse().unwrap());
+        headers.insert(USER_AGENT, TABLET_UA.parse().unwrap());
+
+        assert_eq!(
+            DeviceFingerprint::from_headers(&headers),
+            DeviceFingerprint::from_client(Some("tablet-0042"), Some(TABLET_UA))
+        );
+    }
+}
@@ -0,0 +1,5 @@
+// pub mod device;
+
+pub mod fingerprint;
+
+pub use fingerprint::{DeviceFingerprint, DEVICE_ID_HEADER};
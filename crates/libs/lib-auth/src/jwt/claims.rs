@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_types::enums::{ServiceScope, UserRole};
+use lib_types::ids::{HospitalId, UserId};
+
+use crate::rbac::Permissions;
+
+/// Claims carried by access tokens issued to healthcare staff
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthcareClaims {
+    pub sub: UserId, // User ID
+    pub staff_id: String, // Staff username, so downstream logs need no user lookup
+    pub role: UserRole,
+    pub hospital_id: HospitalId,
+    pub permissions: Permissions,
+    pub sid: Uuid, // Server-side session ID
+    pub iss: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub auth_time: i64, // When the user last entered their credentials
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegation: Option<DelegationClaim>, // Role lent to this user by another
+}
+
+impl HealthcareClaims {
+    /// Start building claims for a user's session
+    pub fn builder(
+        user_id: UserId,
+        staff_id: impl Into<String>,
+        role: UserRole,
+        hospital_id: HospitalId,
+        session_id: Uuid,
+    ) -> HealthcareClaimsBuilder {
+        HealthcareClaimsBuilder {
+            sub: user_id,
+            staff_id: staff_id.into(),
+            role,
+            hospital_id,
+            sid: session_id,
+            permissions: None,
+            auth_time: None,
+            delegation: None,
+        }
+    }
+
+    /// Get the authenticated user ID
+    pub fn user_id(&self) -> UserId {
+        self.sub
+    }
+
+    /// Get the server-side session this token belongs to
+    pub fn session_id(&self) -> Uuid {
+        self.sid
+    }
+
+    /// Check if the token has expired at the given unix timestamp
+    pub fn is_expired_at(&self, now: i64) -> bool {
+        self.exp <= now
+    }
+
+    /// Check if the user authenticated no more than `max_age_seconds` before `now`
+    pub fn authenticated_within(&self, max_age_seconds: i64, now: i64) -> bool {
+        now - self.auth_time <= max_age_seconds
+    }
+
+    /// Get the delegation carried by the token if it has not expired at `now`
+    pub fn active_delegation(&self, now: i64) -> Option<&DelegationClaim> {
+        self.delegation.as_ref().filter(|delegation| delegation.exp > now)
+    }
+}
+
+/// Builder for `HealthcareClaims`; the issuer fills in the registered claims
+#[derive(Debug, Clone)]
+pub struct HealthcareClaimsBuilder {
+    sub: UserId,
+    staff_id: String,
+    role: UserRole,
+    hospital_id: HospitalId,
+    sid: Uuid,
+    permissions: Option<Permissions>,
+    auth_time: Option<i64>,
+    delegation: Option<DelegationClaim>,
+}
+
+impl HealthcareClaimsBuilder {
+    /// Override the permissions (defaults to those of the role)
+    pub fn permissions(mut self, permissions: Permissions) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    /// Set when the user last entered their credentials (defaults to the issue time)
+    pub fn auth_time(mut self, auth_time: i64) -> Self {
+        self.auth_time = Some(auth_time);
+        self
+    }
+
+    /// Attach a role delegated to the user
+    pub fn delegation(mut self, delegation: Option<DelegationClaim>) -> Self {
+        self.delegation = delegation;
+        self
+    }
+
+    /// Build the claims for a token issued at `issued_at` and valid for `lifetime_seconds`
+    pub fn build(
+        self,
+        issuer: impl Into<String>,
+        audience: impl Into<String>,
+        issued_at: i64,
+        lifetime_seconds: i64,
+    ) -> HealthcareClaims {
+        HealthcareClaims {
+            sub: self.sub,
+            staff_id: self.staff_id,
+            role: self.role,
+            hospital_id: self.hospital_id,
+            permissions: self
+                .permissions
+                .unwrap_or_else(|| Permissions::for_role(self.role)),
+            sid: self.sid,
+            iss: issuer.into(),
+            aud: audience.into(),
+            iat: issued_at,
+            exp: issued_at + lifetime_seconds,
+            auth_time: self.auth_time.unwrap_or(issued_at),
+            delegation: self.delegation,
+        }
+    }
+}
+
+/// A time-boxed delegation of another user's role, embedded in the delegate's tokens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DelegationClaim {
+    pub id: Uuid,
+    pub delegator_id: UserId,
+    pub role: UserRole, // Role whose powers are exercised under the delegation
+    pub exp: i64,
+}
+
+/// Claims carried by access tokens issued to service accounts.
+///
+/// These share no role or session fields with `HealthcareClaims`, so a service token
+/// never deserializes as a staff token (and vice versa).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceClaims {
+    pub sub: Uuid, // Service account ID
+    pub client_id: String,
+    pub hospital_id: Option<HospitalId>,
+    pub scopes: Vec<ServiceScope>,
+    pub iss: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+impl ServiceClaims {
+    /// Get the authenticated service account ID
+    pub fn account_id(&self) -> Uuid {
+        self.sub
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "dubai-healthcare-emergency";
+    const AUDIENCE: &str = "healthcare-staff";
+
+    fn claims(role: UserRole) -> HealthcareClaimsBuilder {
+        HealthcareClaims::builder(
+            UserId::new(),
+            "sarah.nurse",
+            role,
+            HospitalId::new(),
+            Uuid::new_v4(),
+        )
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let claims = claims(UserRole::Nurse).build(ISSUER, AUDIENCE, 1_000, 3_600);
+
+        assert_eq!(claims.staff_id, "sarah.nurse");
+        assert_eq!(claims.permissions, Permissions::for_role(UserRole::Nurse));
+        assert_eq!(claims.auth_time, 1_000);
+        assert_eq!(claims.iss, ISSUER);
+        assert!(!claims.is_expired_at(4_599));
+        assert!(claims.is_expired_at(4_600));
+    }
+
+    #[test]
+    fn test_auth_freshness() {
+        let claims = claims(UserRole::Specialist)
+            .auth_time(1_000)
+            .build(ISSUER, AUDIENCE, 2_000, 3_600);
+
+        assert!(claims.authenticated_within(300, 1_300));
+        assert!(!claims.authenticated_within(300, 1_301));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let claims = claims(UserRole::Paramedic)
+            .permissions(Permissions::VIEW_PATIENTS | Permissions::DISPATCH)
+            .build(ISSUER, AUDIENCE, 1_000, 3_600);
+
+        let json = serde_json::to_value(&claims).unwrap();
+        assert_eq!(json["role"], "paramedic");
+        assert_eq!(json["staff_id"], "sarah.nurse");
+        assert_eq!(json["permissions"], 9);
+        // Tokens without a delegation carry no delegation claim at all
+        assert!(json.get("delegation").is_none());
+        assert_eq!(
+            serde_json::from_value::<HealthcareClaims>(json).unwrap(),
+            claims
+        );
+    }
+
+    #[test]
+    fn test_delegation_claim_expiry_and_serde() {
+        let claims = claims(UserRole::Specialist)
+            .delegation(Some(DelegationClaim {
+                id: Uuid::new_v4(),
+                delegator_id: UserId::new(),
+                role: UserRole::ErDirector,
+                exp: 3_000,
+            }))
+            .build(ISSUER, AUDIENCE, 1_000, 3_600);
+
+        let json = serde_json::to_string(&claims).unwrap();
+        assert_eq!(
+            serde_json::from_str::<HealthcareClaims>(&json).unwrap(),
+            claims
+        );
+
+        assert!(claims.active_delegation(2_999).is_some());
+        assert!(claims.active_delegation(3_000).is_none());
+    }
+}
@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+/// Errors raised while loading signing keys or issuing tokens.
+///
+/// Token *verification* failures are reported as `AuthError` instead, since
+/// those are caused by the client rather than by server configuration.
+#[derive(Debug, Error)]
+pub enum JwtError {
+    #[error("Failed to read key file {path}: {source}")]
+    KeyIo {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid key '{kid}': {reason}")]
+    InvalidKey { kid: String, reason: String },
+
+    #[error("No private key found for signing key id '{kid}'")]
+    MissingSigningKey { kid: String },
+
+    #[error("Unsupported JWT algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Failed to sign token: {0}")]
+    Signing(String),
+}
@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters, Jwk,
+    JwkSet, KeyAlgorithm, PublicKeyUse, RSAKeyParameters,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
+use serde::{Deserialize, Serialize};
+
+use super::error::JwtError;
+
+/// Key id used for the shared HMAC secret
+pub const HMAC_KEY_ID: &str = "hs256";
+
+const PRIVATE_KEY_SUFFIX: &str = ".private.pem";
+const PUBLIC_KEY_SUFFIX: &str = ".public.pem";
+
+/// Token signing algorithms supported by the platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JwtAlgorithm {
+    #[serde(rename = "HS256")]
+    Hs256,
+    #[serde(rename = "RS256")]
+    Rs256,
+    #[serde(rename = "ES256")]
+    Es256,
+}
+
+impl JwtAlgorithm {
+    /// Get the algorithm name as used in JOSE headers
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JwtAlgorithm::Hs256 => "HS256",
+            JwtAlgorithm::Rs256 => "RS256",
+            JwtAlgorithm::Es256 => "ES256",
+        }
+    }
+
+    /// Check if the algorithm uses a public/private key pair
+    pub fn is_asymmetric(&self) -> bool {
+        !matches!(self, JwtAlgorithm::Hs256)
+    }
+
+    fn to_jsonwebtoken(self) -> Algorithm {
+        match self {
+            JwtAlgorithm::Hs256 => Algorithm::HS256,
+            JwtAlgorithm::Rs256 => Algorithm::RS256,
+            JwtAlgorithm::Es256 => Algorithm::ES256,
+        }
+    }
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = JwtError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "HS256" => Ok(JwtAlgorithm::Hs256),
+            "RS256" => Ok(JwtAlgorithm::Rs256),
+            "ES256" => Ok(JwtAlgorithm::Es256),
+            other => Err(JwtError::UnsupportedAlgorithm(other.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for JwtAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The set of keys used to sign and verify tokens.
+///
+/// Exactly one key (identified by `signing_kid`) signs new tokens, while every
+/// key in the ring is accepted for verification. Rotating keys is done by
+/// adding the new key pair, switching the active kid, and keeping the old
+/// public key around until all tokens signed with it have expired.
+pub struct JwtKeyRing {
+    algorithm: JwtAlgorithm,
+    signing_kid: String,
+    signing_key: EncodingKey,
+    verification_keys: HashMap<String, DecodingKey>,
+    public_jwks: Vec<Jwk>,
+}
+
+impl JwtKeyRing {
+    /// Create a key ring backed by a shared HMAC secret (HS256)
+    pub fn hmac(secret: &[u8]) -> Self {
+        let mut verification_keys = HashMap::new();
+        verification_keys.insert(HMAC_KEY_ID.to_string(), DecodingKey::from_secret(secret));
+
+        Self {
+            algorithm: JwtAlgorithm::Hs256,
+            signing_kid: HMAC_KEY_ID.to_string(),
+            signing_key: EncodingKey::from_secret(secret),
+            verification_keys,
+            public_jwks: Vec::new(), // Symmetric keys are never published
+        }
+    }
+
+    /// Create an asymmetric key ring from PEM-encoded keys.
+    ///
+    /// `public_keys` must contain the public half of the signing key as well as
+    /// any previously used keys that should still be accepted.
+    pub fn from_pem(
+        algorithm: JwtAlgorithm,
+        signing_kid: &str,
+        signing_private_pem: &[u8],
+        public_keys: &[(String, Vec<u8>)],
+    ) -> Result<Self, JwtError> {
+        if !algorithm.is_asymmetric() {
+            return Err(JwtError::UnsupportedAlgorithm(format!(
+                "{} requires a shared secret, not PEM keys",
+                algorithm
+            )));
+        }
+
+        let signing_key = match algorithm {
+            JwtAlgorithm::Rs256 => EncodingKey::from_rsa_pem(signing_private_pem),
+            _ => EncodingKey::from_ec_pem(signing_private_pem),
+        }
+        .map_err(|e| JwtError::InvalidKey {
+            kid: signing_kid.to_string(),
+            reason: e.to_string(),
+        })?;
+
+        let mut verification_keys = HashMap::new();
+        let mut public_jwks = Vec::new();
+
+        for (kid, pem) in public_keys {
+            let decoding_key = match algorithm {
+                JwtAlgorithm::Rs256 => DecodingKey::from_rsa_pem(pem),
+                _ => DecodingKey::from_ec_pem(pem),
+            }
+            .map_err(|e| JwtError::InvalidKey {
+                kid: kid.clone(),
+                reason: e.to_string(),
+            })?;
+
+            verification_keys.insert(kid.clone(), decoding_key);
+            public_jwks.push(public_jwk(algorithm, kid, pem)?);
+        }
+
+        if !verification_keys.contains_key(signing_kid) {
+            return Err(JwtError::InvalidKey {
+                kid: signing_kid.to_string(),
+                reason: "public key for the signing key is missing".to_string(),
+            });
+        }
+
+        // Keep the JWKS output stable regardless of file system ordering
+        public_jwks.sort_by(|a, b| a.common.key_id.cmp(&b.common.key_id));
+
+        Ok(Self {
+            algorithm,
+            signing_kid: signing_kid.to_string(),
+            signing_key,
+            verification_keys,
+            public_jwks,
+        })
+    }
+
+    /// Load an asymmetric key ring from a directory.
+    ///
+    /// Every `<kid>.public.pem` file becomes a verification key, and
+    /// `<active_kid>.private.pem` is used for signing new tokens.
+    pub fn load_dir(
+        algorithm: JwtAlgorithm,
+        dir: impl AsRef<Path>,
+        active_kid: &str,
+    ) -> Result<Self, JwtError> {
+        let dir = dir.as_ref();
+        let read = |path: &Path| {
+            std::fs::read(path).map_err(|source| JwtError::KeyIo {
+                path: path.display().to_string(),
+                source,
+            })
+        };
+
+        let entries = std::fs::read_dir(dir).map_err(|source| JwtError::KeyIo {
+            path: dir.display().to_string(),
+            source,
+        })?;
+
+        let mut public_keys = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|source| JwtError::KeyIo {
+                    path: dir.display().to_string(),
+                    source,
+                })?
+                .path();
+
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            if let Some(kid) = file_name.strip_suffix(PUBLIC_KEY_SUFFIX) {
+                public_keys.push((kid.to_string(), read(&path)?));
+            }
+        }
+
+        let private_path = dir.join(format!("{}{}", active_kid, PRIVATE_KEY_SUFFIX));
+        if !private_path.exists() {
+            return Err(JwtError::MissingSigningKey {
+                kid: active_kid.to_string(),
+            });
+        }
+        let private_pem = read(&private_path)?;
+
+        Self::from_pem(algorithm, active_kid, &private_pem, &public_keys)
+    }
+
+    /// Get the signing algorithm
+    pub fn algorithm(&self) -> JwtAlgorithm {
+        self.algorithm
+    }
+
+    /// Get the key id used to sign new tokens
+    pub fn signing_kid(&self) -> &str {
+        &self.signing_kid
+    }
+
+    /// Get all key ids accepted for verification
+    pub fn verification_kids(&self) -> Vec<&str> {
+        let mut kids: Vec<&str> = self.verification_keys.keys().map(|k| k.as_str()).collect();
+        kids.sort_unstable();
+        kids
+    }
+
+    /// Get the public keys as a JWK set (empty for HMAC key rings)
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.public_jwks.clone(),
+        }
+    }
+
+    pub(crate) fn jsonwebtoken_algorithm(&self) -> Algorithm {
+        self.algorithm.to_jsonwebtoken()
+    }
+
+    pub(crate) fn signing_key(&self) -> &EncodingKey {
+        &self.signing_key
+    }
+
+    pub(crate) fn decoding_key(&self, kid: &str) -> Option<&DecodingKey> {
+        self.verification_keys.get(kid)
+    }
+}
+
+/// Build the public JWK representation of a PEM-encoded public key
+fn public_jwk(algorithm: JwtAlgorithm, kid: &str, pem: &[u8]) -> Result<Jwk, JwtError> {
+    let invalid = |reason: String| JwtError::InvalidKey {
+        kid: kid.to_string(),
+        reason,
+    };
+    let pem = std::str::from_utf8(pem).map_err(|e| invalid(e.to_string()))?;
+
+    let (key_algorithm, parameters) = match algorithm {
+        JwtAlgorithm::Rs256 => {
+            let key = rsa::RsaPublicKey::from_public_key_pem(pem)
+                .map_err(|e| invalid(e.to_string()))?;
+            (
+                KeyAlgorithm::RS256,
+                AlgorithmParameters::RSA(RSAKeyParameters {
+                    n: URL_SAFE_NO_PAD.encode(key.n().to_bytes_be()),
+                    e: URL_SAFE_NO_PAD.encode(key.e().to_bytes_be()),
+                    ..Default::default()
+                }),
+            )
+        }
+        JwtAlgorithm::Es256 => {
+            let key = p256::PublicKey::from_public_key_pem(pem)
+                .map_err(|e| invalid(e.to_string()))?;
+            let point = key.to_encoded_point(false);
+            let (x, y) = match (point.x(), point.y()) {
+                (Some(x), Some(y)) => (x, y),
+                _ => return Err(invalid("EC point is not uncompressed".to_string())),
+            };
+            (
+                KeyAlgorithm::ES256,
+                AlgorithmParameters::EllipticCurve(EllipticCurveKeyParameters {
+                    curve: EllipticCurve::P256,
+                    x: URL_SAFE_NO_PAD.encode(x),
+                    y: URL_SAFE_NO_PAD.encode(y),
+                    ..Default::default()
+                }),
+            )
+        }
+        JwtAlgorithm::Hs256 => {
+            return Err(JwtError::UnsupportedAlgorithm(
+                "HS256 keys cannot be published".to_string(),
+            ))
+        }
+    };
+
+    Ok(Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm),
+            key_id: Some(kid.to_string()),
+            ..Default::default()
+        },
+        algorithm: parameters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/jwt");
+
+    #[test]
+    fn test_algorithm_parsing() {
+        assert_eq!("rs256".parse::<JwtAlgorithm>().unwrap(), JwtAlgorithm::Rs256);
+        assert_eq!("ES256".parse::<JwtAlgorithm>().unwrap(), JwtAlgorithm::Es256);
+        assert!("none".parse::<JwtAlgorithm>().is_err());
+        assert!(JwtAlgorithm::Rs256.is_asymmetric());
+        assert!(!JwtAlgorithm::Hs256.is_asymmetric());
+    }
+
+    #[test]
+    fn test_hmac_ring_publishes_nothing() {
+        let ring = JwtKeyRing::hmac(b"this-is-a-long-enough-secret-key-for-jwt");
+        assert_eq!(ring.signing_kid(), HMAC_KEY_ID);
+        assert!(ring.jwks().keys.is_empty());
+    }
+
+    #[test]
+    fn test_load_rsa_dir() {
+        let ring = JwtKeyRing::load_dir(
+            JwtAlgorithm::Rs256,
+            format!("{}/rs256", FIXTURES),
+            "2024-01",
+        )
+        .unwrap();
+
+        assert_eq!(ring.signing_kid(), "2024-01");
+        assert_eq!(ring.verification_kids(), vec!["2023-07", "2024-01"]);
+
+        let jwks = ring.jwks();
+        assert_eq!(jwks.keys.len(), 2);
+        let jwk = jwks.find("2024-01").unwrap();
+        match &jwk.algorithm {
+            AlgorithmParameters::RSA(params) => assert_eq!(params.e, "AQAB"),
+            other => panic!("Expected RSA parameters, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_load_ec_dir() {
+        let ring = JwtKeyRing::load_dir(
+            JwtAlgorithm::Es256,
+            format!("{}/es256", FIXTURES),
+            "2024-01",
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(ring.jwks()).unwrap();
+        let key = &json["keys"][1];
+        assert_eq!(key["kid"], "2024-01");
+        assert_eq!(key["kty"], "EC");
+        assert_eq!(key["crv"], "P-256");
+        assert_eq!(key["alg"], "ES256");
+        assert!(key.get("d").is_none()); // Private component never published
+    }
+
+    #[test]
+    fn test_missing_signing_key() {
+        let result = JwtKeyRing::load_dir(
+            JwtAlgorithm::Rs256,
+            format!("{}/rs256", FIXTURES),
+            "2099-01",
+        );
+        assert!(matches!(result, Err(JwtError::MissingSigningKey { .. })));
+    }
+
+    #[test]
+    fn test_mismatched_algorithm() {
+        let result = JwtKeyRing::load_dir(
+            JwtAlgorithm::Es256,
+            format!("{}/rs256", FIXTURES),
+            "2024-01",
+        );
+        assert!(matches!(result, Err(JwtError::InvalidKey { .. })));
+    }
+}
@@ -1 +1,11 @@
 // pub mod jwt;
+
+pub mod claims;
+pub mod error;
+pub mod keys;
+pub mod service;
+
+pub use claims::{DelegationClaim, HealthcareClaims, HealthcareClaimsBuilder, ServiceClaims};
+pub use error::JwtError;
+pub use keys::{JwtAlgorithm, JwtKeyRing};
+pub use service::JwtService;
@@ -0,0 +1,283 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::Utc;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, encode, Header, Validation};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+use lib_types::enums::ServiceScope;
+use lib_types::errors::AuthError;
+use lib_types::ids::HospitalId;
+
+use super::claims::{HealthcareClaims, HealthcareClaimsBuilder, ServiceClaims};
+use super::error::JwtError;
+use super::keys::JwtKeyRing;
+
+/// Issues and verifies access tokens against the active key ring
+pub struct JwtService {
+    keys: RwLock<Arc<JwtKeyRing>>,
+    issuer: String,
+    audience: String,
+    expiration_seconds: i64,
+    service_expiration_seconds: i64,
+}
+
+impl JwtService {
+    /// Create a new JWT service
+    pub fn new(
+        keys: JwtKeyRing,
+        issuer: impl Into<String>,
+        audience: impl Into<String>,
+        expiration_seconds: i64,
+    ) -> Self {
+        Self {
+            keys: RwLock::new(Arc::new(keys)),
+            issuer: issuer.into(),
+            audience: audience.into(),
+            expiration_seconds,
+            service_expiration_seconds: 900,
+        }
+    }
+
+    /// Set the lifetime of service account tokens (15 minutes by default)
+    pub fn with_service_expiration(mut self, seconds: i64) -> Self {
+        self.service_expiration_seconds = seconds;
+        self
+    }
+
+    /// Get the access token lifetime in seconds
+    pub fn expiration_seconds(&self) -> i64 {
+        self.expiration_seconds
+    }
+
+    /// Get the service account token lifetime in seconds
+    pub fn service_expiration_seconds(&self) -> i64 {
+        self.service_expiration_seconds
+    }
+
+    /// Replace the key ring (e.g. after a key rotation) without restarting
+    pub fn rotate(&self, keys: JwtKeyRing) {
+        let mut current = self.keys.write().unwrap_or_else(|e| e.into_inner());
+        *current = Arc::new(keys);
+    }
+
+    /// Get the public verification keys as a JWK set
+    pub fn jwks(&self) -> JwkSet {
+        self.current_keys().jwks()
+    }
+
+    /// Issue an access token for a staff session, filling in the issuer,
+    /// audience and lifetime
+    pub fn issue_token(&self, claims: HealthcareClaimsBuilder) -> Result<String, JwtError> {
+        let claims = claims.build(
+            self.issuer.clone(),
+            self.audience.clone(),
+            Utc::now().timestamp(),
+            self.expiration_seconds,
+        );
+        self.sign(&claims)
+    }
+
+    /// Issue an access token for a service account limited to the given scopes
+    pub fn issue_service_token(
+        &self,
+        account_id: Uuid,
+        client_id: &str,
+        hospital_id: Option<HospitalId>,
+        scopes: Vec<ServiceScope>,
+    ) -> Result<String, JwtError> {
+        let now = Utc::now().timestamp();
+        let claims = ServiceClaims {
+            sub: account_id,
+            client_id: client_id.to_string(),
+            hospital_id,
+            scopes,
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            iat: now,
+            exp: now + self.service_expiration_seconds,
+        };
+        self.sign(&claims)
+    }
+
+    /// Sign arbitrary claims with the active signing key
+    pub fn sign<T: Serialize>(&self, claims: &T) -> Result<String, JwtError> {
+        let keys = self.current_keys();
+        let mut header = Header::new(keys.jsonwebtoken_algorithm());
+        header.kid = Some(keys.signing_kid().to_string());
+
+        encode(&header, claims, keys.signing_key()).map_err(|e| JwtError::Signing(e.to_string()))
+    }
+
+    /// Verify a staff token and return its claims
+    pub fn verify(&self, token: &str) -> Result<HealthcareClaims, AuthError> {
+        self.decode_verified(token)
+    }
+
+    /// Verify a service account token and return its claims
+    pub fn verify_service_token(&self, token: &str) -> Result<ServiceClaims, AuthError> {
+        self.decode_verified(token)
+    }
+
+    fn decode_verified<T: DeserializeOwned>(&self, token: &str) -> Result<T, AuthError> {
+        let keys = self.current_keys();
+        let header = decode_header(token).map_err(|_| AuthError::InvalidToken)?;
+
+        // Reject algorithm substitution (e.g. an HS256 token signed with a public key)
+        if header.alg != keys.jsonwebtoken_algorithm() {
+            return Err(AuthError::InvalidToken);
+        }
+
+        let kid = header.kid.as_deref().unwrap_or_else(|| keys.signing_kid());
+        let decoding_key = keys.decoding_key(kid).ok_or(AuthError::InvalidToken)?;
+
+        let mut validation = Validation::new(keys.jsonwebtoken_algorithm());
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+
+        decode::<T>(token, decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|e| match e.kind() {
+                ErrorKind::ExpiredSignature => AuthError::TokenExpired,
+                _ => AuthError::InvalidToken,
+            })
+    }
+
+    fn current_keys(&self) -> Arc<JwtKeyRing> {
+        self.keys.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jwt::JwtAlgorithm;
+    use crate::rbac::Permissions;
+    use lib_types::enums::UserRole;
+    use lib_types::ids::UserId;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/jwt");
+    const SECRET: &[u8] = b"this-is-a-long-enough-secret-key-for-jwt";
+
+    fn ring(algorithm: JwtAlgorithm, kid: &str) -> JwtKeyRing {
+        let dir = match algorithm {
+            JwtAlgorithm::Rs256 => "rs256",
+            JwtAlgorithm::Es256 => "es256",
+            JwtAlgorithm::Hs256 => return JwtKeyRing::hmac(SECRET),
+        };
+        JwtKeyRing::load_dir(algorithm, format!("{}/{}", FIXTURES, dir), kid).unwrap()
+    }
+
+    fn service(keys: JwtKeyRing) -> JwtService {
+        JwtService::new(keys, "dubai-healthcare-emergency", "healthcare-staff", 3600)
+    }
+
+    fn staff(role: UserRole) -> HealthcareClaimsBuilder {
+        HealthcareClaims::builder(UserId::new(), "staff", role, HospitalId::new(), Uuid::new_v4())
+    }
+
+    #[test]
+    fn test_round_trip_all_algorithms() {
+        for algorithm in [JwtAlgorithm::Hs256, JwtAlgorithm::Rs256, JwtAlgorithm::Es256] {
+            let jwt = service(ring(algorithm, "2024-01"));
+            let user_id = UserId::new();
+            let hospital_id = HospitalId::new();
+
+            let session_id = Uuid::new_v4();
+
+            let token = jwt.issue_token(HealthcareClaims::builder(user_id, "sarah.nurse", UserRole::Nurse, hospital_id, session_id)).unwrap();
+            let claims = jwt.verify(&token).unwrap();
+
+            assert_eq!(claims.sub, user_id);
+            assert_eq!(claims.sid, session_id);
+            assert_eq!(claims.role, UserRole::Nurse);
+            assert_eq!(claims.hospital_id, hospital_id);
+            assert_eq!(claims.staff_id, "sarah.nurse");
+            assert_eq!(claims.permissions, Permissions::for_role(UserRole::Nurse));
+        }
+    }
+
+    #[test]
+    fn test_kid_header_is_set() {
+        let jwt = service(ring(JwtAlgorithm::Rs256, "2024-01"));
+        let token = jwt.issue_token(staff(UserRole::Paramedic)).unwrap();
+
+        let header = decode_header(&token).unwrap();
+        assert_eq!(header.kid.as_deref(), Some("2024-01"));
+    }
+
+    #[test]
+    fn test_rotation_keeps_old_tokens_valid() {
+        let jwt = service(ring(JwtAlgorithm::Es256, "2023-07"));
+        let old_token = jwt.issue_token(staff(UserRole::Nurse)).unwrap();
+
+        jwt.rotate(ring(JwtAlgorithm::Es256, "2024-01"));
+        let new_token = jwt.issue_token(staff(UserRole::Nurse)).unwrap();
+
+        assert!(jwt.verify(&old_token).is_ok());
+        assert!(jwt.verify(&new_token).is_ok());
+        assert_eq!(decode_header(&new_token).unwrap().kid.as_deref(), Some("2024-01"));
+    }
+
+    #[test]
+    fn test_unknown_kid_rejected() {
+        let issuer = service(ring(JwtAlgorithm::Rs256, "2024-01"));
+        let token = issuer.issue_token(staff(UserRole::Nurse)).unwrap();
+
+        let verifier = service(JwtKeyRing::hmac(SECRET));
+        assert_eq!(verifier.verify(&token), Err(AuthError::InvalidToken));
+    }
+
+    #[test]
+    fn test_expired_token() {
+        let jwt = service(JwtKeyRing::hmac(SECRET));
+        let now = Utc::now().timestamp();
+        let claims = staff(UserRole::Nurse).build(
+            "dubai-healthcare-emergency",
+            "healthcare-staff",
+            now - 7200,
+            3600,
+        );
+
+        let token = jwt.sign(&claims).unwrap();
+        assert_eq!(jwt.verify(&token), Err(AuthError::TokenExpired));
+    }
+
+    #[test]
+    fn test_wrong_audience_rejected() {
+        let issuer = JwtService::new(JwtKeyRing::hmac(SECRET), "dubai-healthcare-emergency", "other-app", 3600);
+        let token = issuer.issue_token(staff(UserRole::Nurse)).unwrap();
+
+        let verifier = service(JwtKeyRing::hmac(SECRET));
+        assert_eq!(verifier.verify(&token), Err(AuthError::InvalidToken));
+    }
+
+    #[test]
+    fn test_service_and_staff_tokens_not_interchangeable() {
+        let jwt = service(JwtKeyRing::hmac(SECRET)).with_service_expiration(600);
+        let account_id = Uuid::new_v4();
+
+        let service_token = jwt
+            .issue_service_token(account_id, "dispatch-optimizer", None, vec![ServiceScope::BedsRead])
+            .unwrap();
+        let claims = jwt.verify_service_token(&service_token).unwrap();
+        assert_eq!(claims.account_id(), account_id);
+        assert_eq!(claims.scopes, vec![ServiceScope::BedsRead]);
+        assert_eq!(claims.exp - claims.iat, 600);
+
+        let staff_token = jwt
+            .issue_token(staff(UserRole::Admin))
+            .unwrap();
+        assert_eq!(jwt.verify(&service_token), Err(AuthError::InvalidToken));
+        assert_eq!(jwt.verify_service_token(&staff_token), Err(AuthError::InvalidToken));
+    }
+
+    #[test]
+    fn test_garbage_token() {
+        let jwt = service(JwtKeyRing::hmac(SECRET));
+        assert_eq!(jwt.verify("not-a-jwt"), Err(AuthError::InvalidToken));
+    }
+}
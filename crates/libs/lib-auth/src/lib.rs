@@ -5,6 +5,10 @@ pub mod password;
 pub mod rbac;
 pub mod middleware;
 pub mod ctx;
+pub mod lockout;
+pub mod session;
+pub mod device;
+pub mod signed_url;
 
 // Re-exports for convenience
 pub use jwt::*;
@@ -12,3 +16,7 @@ pub use password::*;
 pub use rbac::*;
 pub use middleware::*;
 pub use ctx::*;
+pub use lockout::*;
+pub use session::*;
+pub use device::*;
+pub use signed_url::*;
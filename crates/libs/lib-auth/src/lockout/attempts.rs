@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use lib_types::errors::AppError;
+
+/// Thresholds for throttling and locking accounts after failed logins
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockoutPolicy {
+    pub max_failed_attempts: u32,
+    pub attempt_window: Duration, // Failures older than this are forgotten
+    pub lockout_duration: Duration,
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: 5,
+            attempt_window: Duration::from_secs(15 * 60),
+            lockout_duration: Duration::from_secs(15 * 60),
+            backoff_base: Duration::from_secs(1),
+            backoff_max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl LockoutPolicy {
+    /// Delay enforced before the next attempt after `failures` consecutive failures.
+    ///
+    /// Doubles with every failure, starting at `backoff_base` and capped at `backoff_max`.
+    pub fn backoff_after(&self, failures: u32) -> Duration {
+        if failures == 0 {
+            return Duration::ZERO;
+        }
+
+        let factor = 2u32.saturating_pow(failures - 1);
+        self.backoff_base
+            .checked_mul(factor)
+            .map_or(self.backoff_max, |delay| delay.min(self.backoff_max))
+    }
+
+    /// Check if the account should be locked after `failures` consecutive failures
+    pub fn should_lock(&self, failures: u32) -> bool {
+        failures >= self.max_failed_attempts
+    }
+}
+
+/// Result of recording a failed login
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginFailure {
+    /// Further attempts are delayed by `retry_after`
+    Delayed { failures: u32, retry_after: Duration },
+    /// The account is now locked
+    Locked,
+}
+
+/// Tracks failed logins per username
+#[async_trait]
+pub trait LoginAttemptStore: Send + Sync {
+    /// Reject the attempt if the account is locked (`AccountLocked`) or still
+    /// backing off after a recent failure (`RateLimit`)
+    async fn check(&self, username: &str) -> Result<(), AppError>;
+
+    /// Count a failed attempt, locking the account once the threshold is reached
+    async fn record_failure(&self, username: &str) -> Result<LoginFailure, AppError>;
+
+    /// Forget failed attempts after a successful login
+    async fn reset(&self, username: &str) -> Result<(), AppError>;
+
+    /// Lift a lockout early, returning whether the account was locked
+    async fn unlock(&self, username: &str) -> Result<bool, AppError>;
+
+    /// Apply new thresholds to subsequent failures, e.g. after a configuration
+    /// reload. Existing locks keep their original duration.
+    fn set_policy(&self, policy: LockoutPolicy);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progressive_backoff() {
+        let policy = LockoutPolicy::default();
+
+        assert_eq!(policy.backoff_after(0), Duration::ZERO);
+        assert_eq!(policy.backoff_after(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff_after(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff_after(4), Duration::from_secs(8));
+        assert_eq!(policy.backoff_after(6), Duration::from_secs(30));
+        assert_eq!(policy.backoff_after(200), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_lock_threshold() {
+        let policy = LockoutPolicy::default();
+
+        assert!(!policy.should_lock(4));
+        assert!(policy.should_lock(5));
+        assert!(policy.should_lock(6));
+    }
+}
@@ -0,0 +1,7 @@
+// pub mod lockout;
+
+pub mod attempts;
+pub mod redis_attempts;
+
+pub use attempts::{LockoutPolicy, LoginAttemptStore, LoginFailure};
+pub use redis_attempts::RedisLoginAttemptStore;
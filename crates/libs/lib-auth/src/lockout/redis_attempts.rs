@@ -0,0 +1,147 @@
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use redis::aio::{ConnectionLike, ConnectionManager};
+use redis::{AsyncCommands, RedisError};
+
+use lib_types::errors::{AppError, AuthError};
+
+use super::attempts::{LockoutPolicy, LoginAttemptStore, LoginFailure};
+
+const FAILURES_KEY_PREFIX: &str = "login_failures:";
+const LOCK_KEY_PREFIX: &str = "login_lock:";
+
+/// Failed-login tracking backed by Redis.
+///
+/// Consecutive failures are counted in a hash under `login_failures:{username}`
+/// (with the earliest time the next attempt is allowed), which expires after the
+/// attempt window. A lockout is a `login_lock:{username}` key whose TTL is the
+/// lockout duration, so locks lift on their own.
+#[derive(Clone)]
+pub struct RedisLoginAttemptStore<C = ConnectionManager> {
+    conn: C,
+    policy: Arc<RwLock<LockoutPolicy>>,
+}
+
+impl<C> RedisLoginAttemptStore<C> {
+    pub fn new(conn: C, policy: LockoutPolicy) -> Self {
+        Self {
+            conn,
+            policy: Arc::new(RwLock::new(policy)),
+        }
+    }
+
+    fn policy(&self) -> LockoutPolicy {
+        self.policy.read().unwrap().clone()
+    }
+
+    fn failures_key(username: &str) -> String {
+        format!("{}{}", FAILURES_KEY_PREFIX, username)
+    }
+
+    fn lock_key(username: &str) -> String {
+        format!("{}{}", LOCK_KEY_PREFIX, username)
+    }
+}
+
+fn redis_error(error: RedisError) -> AppError {
+    AppError::external_service_error("Redis", error.to_string())
+}
+
+#[async_trait]
+impl<C> LoginAttemptStore for RedisLoginAttemptStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    async fn check(&self, username: &str) -> Result<(), AppError> {
+        let (locked, retry_at): (bool, Option<i64>) = redis::pipe()
+            .exists(Self::lock_key(username))
+            .hget(Self::failures_key(username), "retry_at")
+            .query_async(&mut self.conn.clone())
+            .await
+            .map_err(redis_error)?;
+
+        if locked {
+            return Err(AuthError::AccountLocked.into());
+        }
+
+        let now = Utc::now().timestamp();
+        match retry_at {
+            Some(retry_at) if retry_at > now => Err(AppError::RateLimit {
+                retry_after: (retry_at - now) as u64,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    async fn record_failure(&self, username: &str) -> Result<LoginFailure, AppError> {
+        let mut conn = self.conn.clone();
+        let failures_key = Self::failures_key(username);
+
+        let failures: u32 = conn
+            .hincr(&failures_key, "count", 1)
+            .await
+            .map_err(redis_error)?;
+        let now = Utc::now().timestamp();
+        let policy = self.policy();
+
+        if policy.should_lock(failures) {
+            redis::pipe()
+                .atomic()
+                .set_ex(
+                    Self::lock_key(username),
+                    now,
+                    policy.lockout_duration.as_secs(),
+                )
+                .ignore()
+                .del(&failures_key)
+                .ignore()
+                .query_async::<()>(&mut conn)
+                .await
+                .map_err(redis_error)?;
+
+            return Ok(LoginFailure::Locked);
+        }
+
+        let retry_after = policy.backoff_after(failures);
+        redis::pipe()
+            .atomic()
+            .hset(&failures_key, "retry_at", now + retry_after.as_secs() as i64)
+            .ignore()
+            .expire(&failures_key, policy.attempt_window.as_secs() as i64)
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(redis_error)?;
+
+        Ok(LoginFailure::Delayed {
+            failures,
+            retry_after,
+        })
+    }
+
+    async fn reset(&self, username: &str) -> Result<(), AppError> {
+        self.conn
+            .clone()
+            .del(Self::failures_key(username))
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn unlock(&self, username: &str) -> Result<bool, AppError> {
+        let (removed, _): (u32, u32) = redis::pipe()
+            .atomic()
+            .del(Self::lock_key(username))
+            .del(Self::failures_key(username))
+            .query_async(&mut self.conn.clone())
+            .await
+            .map_err(redis_error)?;
+
+        Ok(removed > 0)
+    }
+
+    fn set_policy(&self, policy: LockoutPolicy) {
+        *self.policy.write().unwrap() = policy;
+    }
+}
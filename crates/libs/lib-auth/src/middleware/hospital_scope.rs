@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::{Path, Request, State};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::error;
+use uuid::Uuid;
+
+use lib_types::enums::UserRole;
+use lib_types::errors::AuthError;
+use lib_types::ids::HospitalId;
+
+use crate::ctx::{Ctx, RequestCtx};
+use crate::rbac::{audit_break_glass_access, BreakGlassGrant, BreakGlassStore};
+
+use super::rejection::AuthRejection;
+
+/// Kind of hospital-owned resource addressed by a route
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Hospital,
+    Patient,
+    Bed,
+    Staff,
+    Ambulance,
+}
+
+impl ResourceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceKind::Hospital => "hospital",
+            ResourceKind::Patient => "patient",
+            ResourceKind::Bed => "bed",
+            ResourceKind::Staff => "staff",
+            ResourceKind::Ambulance => "ambulance",
+        }
+    }
+
+    /// Check if system administrators may act on this resource across hospitals.
+    ///
+    /// Admins manage staff records network-wide but have no clinical access,
+    /// so patient and bed data stays restricted to the owning hospital.
+    pub fn allows_cross_hospital_admin(&self) -> bool {
+        matches!(self, ResourceKind::Hospital | ResourceKind::Staff)
+    }
+}
+
+/// Looks up which hospital owns a resource
+#[async_trait]
+pub trait HospitalResolver: Send + Sync {
+    /// Return the owning hospital, or `None` if the resource does not exist
+    async fn resolve_hospital(
+        &self,
+        kind: ResourceKind,
+        id: Uuid,
+    ) -> Result<Option<HospitalId>, AuthError>;
+}
+
+/// Configuration for `mw_require_hospital_scope` on a group of routes
+#[derive(Clone)]
+pub struct HospitalScope {
+    resolver: Arc<dyn HospitalResolver>,
+    kind: ResourceKind,
+    param: &'static str,
+    break_glass: Option<Arc<dyn BreakGlassStore>>,
+}
+
+impl HospitalScope {
+    /// Scope routes whose `:id` path parameter identifies a resource of `kind`
+    pub fn new(resolver: Arc<dyn HospitalResolver>, kind: ResourceKind) -> Self {
+        Self {
+            resolver,
+            kind,
+            param: "id",
+            break_glass: None,
+        }
+    }
+
+    /// Honor break-glass grants for reading patients of other hospitals
+    pub fn with_break_glass(mut self, store: Arc<dyn BreakGlassStore>) -> Self {
+        self.break_glass = Some(store);
+        self
+    }
+
+    /// Use a different path parameter name (e.g. `patient_id`)
+    pub fn with_param(mut self, param: &'static str) -> Self {
+        self.param = param;
+        self
+    }
+
+    /// Resolve the hospital owning the resource with the given id
+    pub async fn owning_hospital(&self, id: Uuid) -> Result<Option<HospitalId>, AuthError> {
+        match self.kind {
+            // Hospital routes are addressed by the hospital id itself
+            ResourceKind::Hospital => Ok(Some(id.into())),
+            kind => self.resolver.resolve_hospital(kind, id).await,
+        }
+    }
+
+    /// Find an active break-glass grant covering this request.
+    ///
+    /// Grants only cover reading patient records; lookup failures deny access.
+    async fn break_glass_grant(
+        &self,
+        ctx: &Ctx,
+        hospital_id: HospitalId,
+        id: Uuid,
+        method: &Method,
+    ) -> Option<BreakGlassGrant> {
+        let store = self.break_glass.as_ref()?;
+
+        if self.kind != ResourceKind::Patient || !method.is_safe() {
+            return None;
+        }
+
+        match store.active_grant(ctx.user_id(), hospital_id).await {
+            Ok(Some(grant)) => {
+                audit_break_glass_access(&grant, self.kind.as_str(), id);
+                Some(grant)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                error!("Break-glass lookup failed for user {}: {}", ctx.user_id(), e);
+                None
+            }
+        }
+    }
+}
+
+/// Check that the caller may access a resource owned by `hospital_id`
+pub fn ensure_hospital_access(
+    ctx: &Ctx,
+    kind: ResourceKind,
+    hospital_id: HospitalId,
+) -> Result<(), AuthError> {
+    if ctx.same_hospital(hospital_id) {
+        return Ok(());
+    }
+
+    if ctx.role() == UserRole::Admin && kind.allows_cross_hospital_admin() {
+        return Ok(());
+    }
+
+    Err(AuthError::HospitalAccessDenied { hospital_id })
+}
+
+/// Reject requests for resources owned by another hospital.
+///
+/// Must be installed with `route_layer` so the path parameters are available,
+/// and after `mw_require_auth` so the `Ctx` is present. Unknown resources are
+/// passed through so the handler can answer with its own 404.
+///
+/// A read allowed under a break-glass grant carries the grant in its
+/// `RequestCtx`, so the store layer reads from the granted hospital.
+pub async fn mw_require_hospital_scope(
+    State(scope): State<HospitalScope>,
+    ctx: Ctx,
+    Path(params): Path<HashMap<String, String>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AuthRejection> {
+    let id = params
+        .get(scope.param)
+        .and_then(|value| Uuid::parse_str(value).ok());
+
+    if let Some(id) = id {
+        if let Some(hospital_id) = scope.owning_hospital(id).await? {
+            if let Err(denied) = ensure_hospital_access(&ctx, scope.kind, hospital_id) {
+                let Some(grant) = scope
+                    .break_glass_grant(&ctx, hospital_id, id, req.method())
+                    .await
+                else {
+                    return Err(denied.into());
+                };
+
+                let request_ctx = req
+                    .extensions()
+                    .get::<RequestCtx>()
+                    .cloned()
+                    .unwrap_or_else(|| RequestCtx::from_headers(req.headers()).with_user(ctx));
+                req.extensions_mut()
+                    .insert(request_ctx.with_break_glass(grant));
+            }
+        }
+    }
+
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rbac::BreakGlassPolicy;
+    use lib_types::errors::AppError;
+    use lib_types::ids::UserId;
+
+    struct FixedResolver(Option<HospitalId>);
+
+    #[async_trait]
+    impl HospitalResolver for FixedResolver {
+        async fn resolve_hospital(
+            &self,
+            _kind: ResourceKind,
+            _id: Uuid,
+        ) -> Result<Option<HospitalId>, AuthError> {
+            Ok(self.0)
+        }
+    }
+
+    #[test]
+    fn test_same_hospital_access() {
+        let hospital_id = HospitalId::new();
+        let nurse = Ctx::new(UserId::new(), UserRole::Nurse, hospital_id, Uuid::new_v4());
+
+        assert!(ensure_hospital_access(&nurse, ResourceKind::Patient, hospital_id).is_ok());
+    }
+
+    #[test]
+    fn test_cross_hospital_denied() {
+        let other_hospital = HospitalId::new();
+        let nurse = Ctx::new(UserId::new(), UserRole::Nurse, HospitalId::new(), Uuid::new_v4());
+
+        assert_eq!(
+            ensure_hospital_access(&nurse, ResourceKind::Patient, other_hospital),
+            Err(AuthError::HospitalAccessDenied {
+                hospital_id: other_hospital
+            })
+        );
+    }
+
+    #[test]
+    fn test_admin_cross_hospital_rules() {
+        let admin = Ctx::new(UserId::new(), UserRole::Admin, HospitalId::new(), Uuid::new_v4());
+        let other_hospital = HospitalId::new();
+
+        assert!(ensure_hospital_access(&admin, ResourceKind::Staff, other_hospital).is_ok());
+        assert!(ensure_hospital_access(&admin, ResourceKind::Patient, other_hospital).is_err());
+        assert!(ensure_hospital_access(&admin, ResourceKind::Bed, other_hospital).is_err());
+    }
+
+    struct FixedGrant(Option<BreakGlassGrant>);
+
+    #[async_trait]
+    impl BreakGlassStore for FixedGrant {
+        async fn activate(&self, _grant: &BreakGlassGrant) -> Result<(), AppError> {
+            Ok(())
+        }
+
+        async fn active_grant(
+            &self,
+            _user_id: UserId,
+            _hospital_id: HospitalId,
+        ) -> Result<Option<BreakGlassGrant>, AppError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_break_glass_read_only_patient_access() {
+        let nurse = Ctx::new(UserId::new(), UserRole::Nurse, HospitalId::new(), Uuid::new_v4());
+        let other_hospital = HospitalId::new();
+        let grant = BreakGlassPolicy::default()
+            .grant(&nurse, other_hospital, "Disaster response, patient records needed", None)
+            .unwrap();
+        let resolver = Arc::new(FixedResolver(Some(other_hospital)));
+
+        let scope = HospitalScope::new(resolver.clone(), ResourceKind::Patient)
+            .with_break_glass(Arc::new(FixedGrant(Some(grant.clone()))));
+        let patient_id = Uuid::new_v4();
+        assert_eq!(
+            scope.break_glass_grant(&nurse, other_hospital, patient_id, &Method::GET).await,
+            Some(grant)
+        );
+        assert!(scope.break_glass_grant(&nurse, other_hospital, patient_id, &Method::PUT).await.is_none());
+
+        let without_grant = HospitalScope::new(resolver, ResourceKind::Patient)
+            .with_break_glass(Arc::new(FixedGrant(None)));
+        assert!(without_grant.break_glass_grant(&nurse, other_hospital, patient_id, &Method::GET).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_owning_hospital_resolution() {
+        let owner = HospitalId::new();
+        let scope = HospitalScope::new(Arc::new(FixedResolver(Some(owner))), ResourceKind::Patient)
+            .with_param("patient_id");
+        assert_eq!(scope.param, "patient_id");
+        assert_eq!(scope.owning_hospital(Uuid::new_v4()).await, Ok(Some(owner)));
+
+        // Hospital routes never hit the resolver
+        let hospital_id = Uuid::new_v4();
+        let scope = HospitalScope::new(Arc::new(FixedResolver(None)), ResourceKind::Hospital);
+        assert_eq!(scope.owning_hospital(hospital_id).await, Ok(Some(hospital_id.into())));
+    }
+}
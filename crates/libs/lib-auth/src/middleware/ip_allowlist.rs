@@ -0,0 +1,142 @@
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use ipnet::IpNet;
+use tracing::warn;
+
+use lib_types::enums::UserRole;
+use lib_types::errors::AuthError;
+
+use crate::ctx::Ctx;
+
+use super::rejection::AuthRejection;
+
+/// Networks each role may connect from; an empty list leaves the role
+/// unrestricted.
+///
+/// Used to keep e.g. administration reachable only from hospital networks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IpAllowlists {
+    pub er_director: Vec<IpNet>,
+    pub paramedic: Vec<IpNet>,
+    pub nurse: Vec<IpNet>,
+    pub specialist: Vec<IpNet>,
+    pub admin: Vec<IpNet>,
+}
+
+impl IpAllowlists {
+    /// Get the allowed networks of a role
+    pub fn for_role(&self, role: UserRole) -> &[IpNet] {
+        match role {
+            UserRole::ErDirector => &self.er_director,
+            UserRole::Paramedic => &self.paramedic,
+            UserRole::Nurse => &self.nurse,
+            UserRole::Specialist => &self.specialist,
+            UserRole::Admin => &self.admin,
+        }
+    }
+
+    /// Check if `role` may be used from `ip` (unknown addresses only pass
+    /// for unrestricted roles)
+    pub fn allows(&self, role: UserRole, ip: Option<IpAddr>) -> bool {
+        let networks = self.for_role(role);
+        networks.is_empty()
+            || ip
+                .map(|ip| ip.to_canonical())
+                .is_some_and(|ip| networks.iter().any(|network| network.contains(&ip)))
+    }
+
+    /// Check the caller's own role and, during a delegation, the delegated one
+    pub fn check(&self, ctx: &Ctx, ip: Option<IpAddr>) -> Result<(), AuthError> {
+        if self.allows(ctx.own_role(), ip) && self.allows(ctx.role(), ip) {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientPermissions)
+        }
+    }
+}
+
+/// Reject requests from outside the networks allowed for the caller's role.
+///
+/// Install with `route_layer(from_fn_with_state(allowlists, mw_require_ip_allowlist))`
+/// inside `mw_require_auth`. The peer address of the connection is used, so
+/// the server must be run with `into_make_service_with_connect_info`.
+pub async fn mw_require_ip_allowlist(
+    State(allowlists): State<Arc<IpAllowlists>>,
+    ctx: Ctx,
+    req: Request,
+    next: Next,
+) -> Result<Response, AuthRejection> {
+    let ip = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    if let Err(error) = allowlists.check(&ctx, ip) {
+        warn!(
+            target: "audit",
+            event = "ip_allowlist_denied",
+            user_id = %ctx.user_id(),
+            role = ctx.role().display_name(),
+            ip = ?ip,
+            path = %req.uri().path(),
+            "Request from outside the networks allowed for the role"
+        );
+        return Err(error.into());
+    }
+
+    Ok(next.run(req).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib_types::ids::{HospitalId, UserId};
+    use uuid::Uuid;
+
+    fn allowlists() -> IpAllowlists {
+        IpAllowlists {
+            admin: vec![
+                "10.20.0.0/16".parse().unwrap(),
+                "fd00:20::/32".parse().unwrap(),
+            ],
+            ..IpAllowlists::default()
+        }
+    }
+
+    #[test]
+    fn test_restricted_role() {
+        let allowlists = allowlists();
+        let ip = |s: &str| Some(s.parse::<IpAddr>().unwrap());
+
+        assert!(allowlists.allows(UserRole::Admin, ip("10.20.4.7")));
+        assert!(allowlists.allows(UserRole::Admin, ip("::ffff:10.20.4.7")));
+        assert!(allowlists.allows(UserRole::Admin, ip("fd00:20::1")));
+        assert!(!allowlists.allows(UserRole::Admin, ip("203.0.113.9")));
+        assert!(!allowlists.allows(UserRole::Admin, None));
+
+        // Roles without an allowlist work from anywhere, e.g. ambulances on LTE
+        assert!(allowlists.allows(UserRole::Paramedic, ip("203.0.113.9")));
+        assert!(allowlists.allows(UserRole::Paramedic, None));
+    }
+
+    #[test]
+    fn test_check_rejects_out_of_zone_caller() {
+        let admin = Ctx::new(
+            UserId::new(),
+            UserRole::Admin,
+            HospitalId::new(),
+            Uuid::new_v4(),
+        );
+        let outside = Some("198.51.100.3".parse().unwrap());
+
+        assert_eq!(
+            allowlists().check(&admin, outside),
+            Err(AuthError::InsufficientPermissions)
+        );
+        assert!(IpAllowlists::default().check(&admin, outside).is_ok());
+    }
+}
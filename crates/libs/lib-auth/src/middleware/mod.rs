@@ -1 +1,20 @@
 // pub mod middleware;
+
+pub mod hospital_scope;
+pub mod ip_allowlist;
+pub mod mw_auth;
+pub mod mw_request_ctx;
+pub mod mw_service_auth;
+pub mod recent_auth;
+pub mod rejection;
+
+pub use hospital_scope::{
+    ensure_hospital_access, mw_require_hospital_scope, HospitalResolver, HospitalScope,
+    ResourceKind,
+};
+pub use ip_allowlist::{mw_require_ip_allowlist, IpAllowlists};
+pub use mw_auth::{mw_require_auth, AuthState, WEBSOCKET_AUTH_PROTOCOL};
+pub use mw_request_ctx::mw_request_ctx;
+pub use mw_service_auth::mw_require_service_auth;
+pub use recent_auth::ensure_recent_auth;
+pub use rejection::AuthRejection;
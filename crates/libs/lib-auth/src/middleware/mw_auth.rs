@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::header::{AUTHORIZATION, SEC_WEBSOCKET_PROTOCOL};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use lib_types::errors::AuthError;
+
+use crate::ctx::{Ctx, RequestCtx};
+use crate::jwt::JwtService;
+use crate::rbac::{audit_delegated_action, DelegationStore};
+use crate::session::SessionStore;
+
+use super::rejection::AuthRejection;
+
+/// WebSocket subprotocol announcing that the next one is an access token.
+/// Browsers cannot set headers on a WebSocket handshake, so live dashboards
+/// connect with `Sec-WebSocket-Protocol: bearer, <token>`.
+pub const WEBSOCKET_AUTH_PROTOCOL: &str = "bearer";
+
+/// State required by `mw_require_auth`
+#[derive(Clone)]
+pub struct AuthState {
+    pub jwt: Arc<JwtService>,
+    pub sessions: Arc<dyn SessionStore>,
+    pub delegations: Arc<dyn DelegationStore>,
+}
+
+/// Require a valid bearer token backed by an active session and store the
+/// resulting `Ctx` in the request extensions.
+///
+/// A delegation carried by the token only applies while it is still on record,
+/// so revoking it takes effect immediately; every request made under it is audited.
+///
+/// The caller is also added to the request's `RequestCtx` for the store layer,
+/// and the `Ctx` is attached to the response so outer layers know who made
+/// the request.
+pub async fn mw_require_auth(
+    State(auth): State<AuthState>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AuthRejection> {
+    let token = bearer_token(&req)
+        .or_else(|| websocket_token(&req))
+        .ok_or(AuthError::MissingToken)?;
+    let claims = auth.jwt.verify(token)?;
+
+    // Terminating a session (or letting it idle out) revokes its unexpired tokens
+    auth
+        .sessions
+        .touch(claims.session_id())
+        .await?
+        .filter(|session| session.user_id == claims.user_id())
+        .ok_or(AuthError::SessionTerminated)?;
+
+    let mut ctx = Ctx::from_claims(&claims);
+    if let Some(delegation) = ctx.delegation().cloned() {
+        let active = auth
+            .delegations
+            .active_for_delegate(ctx.user_id())
+            .await?
+            .is_some_and(|current| current.id == delegation.id);
+
+        if active {
+            audit_delegated_action(&ctx, &delegation, req.method().as_str(), req.uri().path());
+        } else {
+            ctx = ctx.without_delegation();
+        }
+    }
+    let request_ctx = req
+        .extensions()
+        .get::<RequestCtx>()
+        .cloned()
+        .unwrap_or_else(|| RequestCtx::from_headers(req.headers()));
+    req.extensions_mut().insert(request_ctx.with_user(ctx.clone()));
+    req.extensions_mut().insert(ctx.clone());
+
+    let mut response = next.run(req).await;
+    response.extensions_mut().insert(ctx);
+    Ok(response)
+}
+
+/// Extract the token from an `Authorization: Bearer <token>` header
+pub(super) fn bearer_token(req: &Request) -> Option<&str> {
+    req.headers()
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Extract the token a WebSocket handshake offers as its second subprotocol,
+/// after `WEBSOCKET_AUTH_PROTOCOL`
+fn websocket_token(req: &Request) -> Option<&str> {
+    let mut protocols = req
+        .headers()
+        .get(SEC_WEBSOCKET_PROTOCOL)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .map(str::trim);
+    if protocols.next()? != WEBSOCKET_AUTH_PROTOCOL {
+        return None;
+    }
+    protocols.next().filter(|token| !token.is_empty())
+}
@@ -0,0 +1,51 @@
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::{error, info_span, Instrument};
+
+use lib_types::errors::AppError;
+
+use super::rejection::render_error_response;
+use crate::ctx::{RequestCtx, CORRELATION_ID_HEADER, REQUEST_ID_HEADER};
+
+/// Build the `RequestCtx` from the request headers, store it in the request
+/// extensions and echo the request and correlation ids on the response.
+/// Error responses are rendered in the caller's preferred language with both
+/// ids, and server-side failures are logged with them. Everything logged
+/// while handling the request is in a span carrying both ids.
+///
+/// Must wrap `mw_require_auth`, which adds the authenticated caller to it.
+pub async fn mw_request_ctx(mut req: Request, next: Next) -> Response {
+    let ctx = RequestCtx::from_headers(req.headers());
+    let request_id = HeaderValue::from_str(ctx.request_id()).ok();
+    let correlation_id = HeaderValue::from_str(ctx.correlation_id()).ok();
+    req.extensions_mut().insert(ctx.clone());
+
+    let span = info_span!(
+        "request",
+        request_id = ctx.request_id(),
+        correlation_id = ctx.correlation_id()
+    );
+    let mut response = next.run(req).instrument(span).await;
+    if let Some(error) = response.extensions().get::<AppError>().cloned() {
+        if error.should_log_error() {
+            error!(
+                request_id = ctx.request_id(),
+                correlation_id = ctx.correlation_id(),
+                "Request failed: {}",
+                error
+            );
+        }
+        response = render_error_response(response, &error, &ctx);
+    }
+    if let Some(request_id) = request_id {
+        response.headers_mut().insert(REQUEST_ID_HEADER, request_id);
+    }
+    if let Some(correlation_id) = correlation_id {
+        response
+            .headers_mut()
+            .insert(CORRELATION_ID_HEADER, correlation_id);
+    }
+    response
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use lib_types::errors::AuthError;
+
+use crate::ctx::{RequestCtx, ServiceCtx};
+use crate::jwt::JwtService;
+
+use super::mw_auth::bearer_token;
+use super::rejection::AuthRejection;
+
+/// Require a valid service account bearer token and store the resulting
+/// `ServiceCtx` in the request extensions.
+///
+/// The account is also added to the request's `RequestCtx`, so accounts
+/// limited to a hospital only reach that hospital's rows.
+///
+/// Service tokens are short-lived and carry no server-side session, so
+/// deactivating an account takes effect once its current token expires.
+pub async fn mw_require_service_auth(
+    State(jwt): State<Arc<JwtService>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, AuthRejection> {
+    let token = bearer_token(&req).ok_or(AuthError::MissingToken)?;
+    let claims = jwt.verify_service_token(token)?;
+
+    let service = ServiceCtx::from_claims(&claims);
+
+    let request_ctx = req
+        .extensions()
+        .get::<RequestCtx>()
+        .cloned()
+        .unwrap_or_else(|| RequestCtx::from_headers(req.headers()));
+    req.extensions_mut()
+        .insert(request_ctx.with_service(service.clone()));
+    req.extensions_mut().insert(service);
+
+    Ok(next.run(req).await)
+}
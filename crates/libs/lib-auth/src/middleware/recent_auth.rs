@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use lib_types::errors::AuthError;
+
+use crate::ctx::Ctx;
+
+/// Check that the caller entered their credentials within `max_age`
+pub fn ensure_recent_auth(ctx: &Ctx, max_age: Duration) -> Result<(), AuthError> {
+    let elapsed = (Utc::now() - ctx.auth_time()).num_seconds();
+    if elapsed <= max_age.as_secs() as i64 {
+        Ok(())
+    } else {
+        Err(AuthError::ReauthenticationRequired {
+            max_age_seconds: max_age.as_secs(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib_types::enums::UserRole;
+    use lib_types::ids::{HospitalId, UserId};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_stale_auth_challenged() {
+        let max_age = Duration::from_secs(300);
+        let specialist = Ctx::new(
+            UserId::new(),
+            UserRole::Specialist,
+            HospitalId::new(),
+            Uuid::new_v4(),
+        );
+        assert!(ensure_recent_auth(&specialist, max_age).is_ok());
+
+        let stale = specialist.with_auth_time(Utc::now() - chrono::Duration::minutes(10));
+        let error = ensure_recent_auth(&stale, max_age).unwrap_err();
+        assert_eq!(
+            error,
+            AuthError::ReauthenticationRequired {
+                max_age_seconds: 300
+            }
+        );
+        assert_eq!(error.status_code(), 428);
+    }
+}
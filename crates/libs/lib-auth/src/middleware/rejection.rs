@@ -0,0 +1,79 @@
+use axum::http::header::{CONTENT_LANGUAGE, CONTENT_LENGTH};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+
+use lib_types::errors::{ApiErrorResponse, AppError, AuthError};
+
+use crate::ctx::RequestCtx;
+
+/// Response wrapper for failures raised by the auth middleware and extractors
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthRejection(pub AppError);
+
+impl From<AuthError> for AuthRejection {
+    fn from(error: AuthError) -> Self {
+        Self(AppError::Auth(error))
+    }
+}
+
+impl From<AppError> for AuthRejection {
+    fn from(error: AppError) -> Self {
+        Self(error)
+    }
+}
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.0.status_code()).unwrap_or(StatusCode::UNAUTHORIZED);
+        let body = ApiErrorResponse::from_app_error(&self.0);
+
+        let mut response = (status, Json(body)).into_response();
+        // Exposed to outer layers (e.g. the auth audit log, localization)
+        response.extensions_mut().insert(self.0.clone());
+        if let AppError::Auth(auth_error) = self.0 {
+            response.extensions_mut().insert(auth_error);
+        }
+        response
+    }
+}
+
+/// Re-render an error response's body for the request: the message in the
+/// caller's language and the request and correlation ids, keeping the
+/// status, headers and extensions
+pub(crate) fn render_error_response(
+    response: Response,
+    error: &AppError,
+    ctx: &RequestCtx,
+) -> Response {
+    let locale = ctx.message_locale();
+    let (mut parts, _) = response.into_parts();
+    parts
+        .headers
+        .insert(CONTENT_LANGUAGE, HeaderValue::from_static(locale.code()));
+    parts.headers.remove(CONTENT_LENGTH);
+
+    let body = ApiErrorResponse::from_app_error_localized(error, locale)
+        .with_correlation_id(ctx.correlation_id())
+        .with_request_id(ctx.request_id());
+    Response::from_parts(parts, Json(body).into_response().into_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_keeps_status_and_marks_language() {
+        let error = AppError::Auth(AuthError::TokenExpired);
+        let response = AuthRejection(error.clone()).into_response();
+        assert_eq!(response.extensions().get::<AppError>(), Some(&error));
+
+        let ctx = RequestCtx::new("ambulance-7f3a", "ar-AE");
+        let rendered = render_error_response(response, &error, &ctx);
+        assert_eq!(rendered.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(rendered.headers()[CONTENT_LANGUAGE], "ar");
+        assert!(rendered.extensions().get::<AuthError>().is_some());
+    }
+}
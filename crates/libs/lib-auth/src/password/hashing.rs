@@ -0,0 +1,195 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use password_hash::rand_core::OsRng;
+use password_hash::{PasswordHash, PasswordHasher as _, PasswordVerifier, SaltString};
+
+use lib_types::errors::AppError;
+
+/// Argon2id cost parameters for new password hashes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        // OWASP recommended minimum for Argon2id
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// Hashes passwords with Argon2id using the configured parameters.
+///
+/// Hashes created with older parameters, or legacy bcrypt hashes, still verify
+/// but are reported by `needs_rehash` so they can be upgraded at the next login.
+#[derive(Debug, Clone)]
+pub struct PasswordHasher {
+    params: Argon2Params,
+    argon2: Argon2<'static>,
+    /// Checked when no account matches, so unknown usernames cost the same
+    dummy_hash: String,
+}
+
+impl PasswordHasher {
+    /// Create a hasher, rejecting parameters Argon2 does not accept
+    pub fn new(params: Argon2Params) -> Result<Self, AppError> {
+        let argon2_params = Params::new(
+            params.memory_kib,
+            params.iterations,
+            params.parallelism,
+            None,
+        )
+        .map_err(|e| AppError::Configuration {
+            message: format!("Invalid Argon2 parameters: {}", e),
+        })?;
+
+        let mut hasher = Self {
+            params,
+            argon2: Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params),
+            dummy_hash: String::new(),
+        };
+        hasher.dummy_hash = hasher.hash("no-account-has-this-password")?;
+        Ok(hasher)
+    }
+
+    pub fn params(&self) -> Argon2Params {
+        self.params
+    }
+
+    /// Hash a password for storage
+    pub fn hash(&self, password: &str) -> Result<String, AppError> {
+        let salt = SaltString::generate(&mut OsRng);
+        self.argon2
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|_| AppError::Internal)
+    }
+
+    /// Check a password against an account's stored hash. With no account
+    /// the password is still checked against a dummy hash and rejected, so
+    /// the response time does not reveal whether the account exists.
+    pub fn verify_or_dummy(&self, password: &str, password_hash: Option<&str>) -> bool {
+        match password_hash {
+            Some(password_hash) => verify_password(password, password_hash),
+            None => {
+                verify_password(password, &self.dummy_hash);
+                false
+            }
+        }
+    }
+
+    /// Check if a stored hash should be replaced with one using the current parameters
+    pub fn needs_rehash(&self, password_hash: &str) -> bool {
+        let Ok(parsed) = PasswordHash::new(password_hash) else {
+            return true;
+        };
+
+        if parsed.algorithm != Algorithm::Argon2id.ident()
+            || parsed.version != Some(Version::V0x13.into())
+        {
+            return true;
+        }
+
+        match Params::try_from(&parsed) {
+            Ok(params) => {
+                params.m_cost() != self.params.memory_kib
+                    || params.t_cost() != self.params.iterations
+                    || params.p_cost() != self.params.parallelism
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+/// Check a password against a stored Argon2 or legacy bcrypt hash
+/// (malformed hashes never match)
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    if is_bcrypt_hash(password_hash) {
+        return bcrypt::verify(password, password_hash).unwrap_or(false);
+    }
+
+    // Argon2 reads the algorithm and parameters from the hash itself
+    PasswordHash::new(password_hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+fn is_bcrypt_hash(password_hash: &str) -> bool {
+    ["$2a$", "$2b$", "$2x$", "$2y$"]
+        .iter()
+        .any(|prefix| password_hash.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_hasher(iterations: u32) -> PasswordHasher {
+        PasswordHasher::new(Argon2Params {
+            memory_kib: 1024,
+            iterations,
+            parallelism: 1,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hasher = fast_hasher(1);
+        let hash = hasher.hash("Emergency#2024").unwrap();
+
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_password("Emergency#2024", &hash));
+        assert!(!verify_password("emergency#2024", &hash));
+        assert!(!verify_password("Emergency#2024", "not-a-hash"));
+    }
+
+    #[test]
+    fn test_verify_without_account_always_fails() {
+        let hasher = fast_hasher(1);
+        let hash = hasher.hash("Emergency#2024").unwrap();
+
+        assert!(hasher.verify_or_dummy("Emergency#2024", Some(&hash)));
+        assert!(!hasher.verify_or_dummy("Emergency#2024", None));
+        assert!(!hasher.verify_or_dummy("no-account-has-this-password", None));
+    }
+
+    #[test]
+    fn test_legacy_bcrypt_verifies_and_needs_rehash() {
+        let hasher = fast_hasher(1);
+        let legacy = bcrypt::hash("Emergency#2024", 4).unwrap();
+
+        assert!(verify_password("Emergency#2024", &legacy));
+        assert!(hasher.needs_rehash(&legacy));
+    }
+
+    #[test]
+    fn test_parameter_upgrade_needs_rehash() {
+        let old_hash = fast_hasher(1).hash("Emergency#2024").unwrap();
+        let upgraded = fast_hasher(2);
+
+        assert!(!fast_hasher(1).needs_rehash(&old_hash));
+        assert!(upgraded.needs_rehash(&old_hash));
+        // Old hashes keep verifying until they are replaced
+        assert!(verify_password("Emergency#2024", &old_hash));
+        assert!(!upgraded.needs_rehash(&upgraded.hash("Emergency#2024").unwrap()));
+    }
+
+    #[test]
+    fn test_invalid_params_rejected() {
+        let params = Argon2Params {
+            memory_kib: 1,
+            ..Argon2Params::default()
+        };
+        assert!(PasswordHasher::new(params).is_err());
+    }
+}
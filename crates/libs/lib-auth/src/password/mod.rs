@@ -1 +1,9 @@
 // pub mod password;
+
+pub mod hashing;
+pub mod policy;
+pub mod secret;
+
+pub use hashing::{verify_password, Argon2Params, PasswordHasher};
+pub use policy::PasswordPolicy;
+pub use secret::generate_client_secret;
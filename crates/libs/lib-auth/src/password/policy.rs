@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+
+use lib_types::errors::AuthError;
+
+use super::hashing::verify_password;
+
+/// Passwords rejected regardless of configuration
+const COMMON_PASSWORDS: &[&str] = &[
+    "password",
+    "password1",
+    "password123",
+    "passw0rd",
+    "123456",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "qwerty",
+    "qwerty123",
+    "letmein",
+    "welcome",
+    "welcome1",
+    "welcome123",
+    "admin",
+    "admin123",
+    "administrator",
+    "iloveyou",
+    "changeme",
+    "monkey",
+    "dragon",
+    "football",
+    "dubai",
+    "dubai123",
+    "dubai2024",
+    "hospital",
+    "hospital123",
+    "emergency",
+    "emergency1",
+    "healthcare",
+    "nurse123",
+    "doctor123",
+];
+
+/// Rules every new password must satisfy.
+///
+/// This is the single source of truth for password strength: registration and
+/// password changes both go through `validate`, and the `WeakPassword` reason
+/// lists exactly the rules that were not met.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub require_uppercase: bool,
+    pub require_lowercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub history_size: usize, // Number of previous passwords that may not be reused
+    pub deny_list: HashSet<String>, // Lowercased; see `with_denied_passwords`
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 12,
+            max_length: 128,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_symbol: true,
+            history_size: 5,
+            deny_list: COMMON_PASSWORDS.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Add passwords to the deny list (matched case-insensitively)
+    pub fn with_denied_passwords<I, S>(mut self, passwords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.deny_list.extend(
+            passwords
+                .into_iter()
+                .map(|p| p.as_ref().trim().to_lowercase())
+                .filter(|p| !p.is_empty()),
+        );
+        self
+    }
+
+    /// Check if a password is on the deny list
+    pub fn is_denied(&self, password: &str) -> bool {
+        self.deny_list.contains(&password.to_lowercase())
+    }
+
+    /// List every rule the password breaks (empty if it is acceptable)
+    pub fn violations(&self, password: &str, username: &str) -> Vec<String> {
+        let mut violations = Vec::new();
+        let length = password.chars().count();
+
+        if length < self.min_length {
+            violations.push(format!("must be at least {} characters", self.min_length));
+        }
+
+        if length > self.max_length {
+            violations.push(format!("must be at most {} characters", self.max_length));
+        }
+
+        if self.require_uppercase && !password.chars().any(|c| c.is_uppercase()) {
+            violations.push("must contain an uppercase letter".to_string());
+        }
+
+        if self.require_lowercase && !password.chars().any(|c| c.is_lowercase()) {
+            violations.push("must contain a lowercase letter".to_string());
+        }
+
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push("must contain a digit".to_string());
+        }
+
+        if self.require_symbol && password.chars().all(|c| c.is_alphanumeric()) {
+            violations.push("must contain a symbol".to_string());
+        }
+
+        if self.is_denied(password) {
+            violations.push("is too common".to_string());
+        }
+
+        let username = username.trim().to_lowercase();
+        if !username.is_empty() && password.to_lowercase().contains(&username) {
+            violations.push("must not contain the username".to_string());
+        }
+
+        violations
+    }
+
+    /// Check a new password against the policy
+    pub fn validate(&self, password: &str, username: &str) -> Result<(), AuthError> {
+        let violations = self.violations(password, username);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(AuthError::WeakPassword {
+                reason: format!("password {}", violations.join(", ")),
+            })
+        }
+    }
+
+    /// Reject reuse of recent passwords.
+    ///
+    /// `previous_hashes` must be ordered newest first (current password first);
+    /// only the last `history_size` of them are checked.
+    pub fn check_reuse<'a, I>(&self, password: &str, previous_hashes: I) -> Result<(), AuthError>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let reused = previous_hashes
+            .into_iter()
+            .take(self.history_size)
+            .any(|hash| verify_password(password, hash));
+
+        if reused {
+            Err(AuthError::WeakPassword {
+                reason: format!(
+                    "password must not match any of the last {} passwords",
+                    self.history_size
+                ),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strong_password_accepted() {
+        let policy = PasswordPolicy::default();
+        assert!(policy.validate("Falcon#Desert2024", "ahmed.director").is_ok());
+    }
+
+    #[test]
+    fn test_violations_listed_in_reason() {
+        let policy = PasswordPolicy::default();
+
+        match policy.validate("short", "nurse.sarah") {
+            Err(AuthError::WeakPassword { reason }) => {
+                assert!(reason.contains("at least 12 characters"));
+                assert!(reason.contains("uppercase"));
+                assert!(reason.contains("digit"));
+                assert!(reason.contains("symbol"));
+                assert!(!reason.contains("lowercase"));
+            }
+            other => panic!("Expected WeakPassword, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_deny_list_and_username() {
+        let policy = PasswordPolicy {
+            min_length: 6,
+            require_uppercase: false,
+            require_digit: false,
+            require_symbol: false,
+            ..PasswordPolicy::default()
+        }
+        .with_denied_passwords(["RashidHospital"]);
+
+        assert!(policy.is_denied("Password123"));
+        assert!(policy.is_denied("rashidhospital"));
+        assert!(policy.validate("rashidhospital", "ahmed").is_err());
+        assert!(policy.validate("ahmed.director-pw", "Ahmed.Director").is_err());
+        assert!(policy.validate("sandstorm-pw", "ahmed.director").is_ok());
+    }
+
+    #[test]
+    fn test_reuse_of_recent_passwords() {
+        let policy = PasswordPolicy {
+            history_size: 2,
+            ..PasswordPolicy::default()
+        };
+        let current = bcrypt::hash("Current#Pass2024", 4).unwrap();
+        let previous = bcrypt::hash("Previous#Pass2023", 4).unwrap();
+        let oldest = bcrypt::hash("Oldest#Pass2022", 4).unwrap();
+        let history = [current.as_str(), previous.as_str(), oldest.as_str()];
+
+        assert!(policy.check_reuse("Current#Pass2024", history).is_err());
+        assert!(policy.check_reuse("Previous#Pass2023", history).is_err());
+        // Outside the configured history window
+        assert!(policy.check_reuse("Oldest#Pass2022", history).is_ok());
+    }
+}
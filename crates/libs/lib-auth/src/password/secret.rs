@@ -0,0 +1,30 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use password_hash::rand_core::{OsRng, RngCore};
+
+/// Number of random bytes in a generated client secret
+const CLIENT_SECRET_BYTES: usize = 32;
+
+/// Generate a random client secret for a service account.
+///
+/// Secrets are stored like passwords (hashed with `PasswordHasher`) and are
+/// only shown once, when the account is created.
+pub fn generate_client_secret() -> String {
+    let mut bytes = [0u8; CLIENT_SECRET_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_secrets_are_random() {
+        let first = generate_client_secret();
+        let second = generate_client_secret();
+
+        assert_eq!(first.len(), 43);
+        assert_ne!(first, second);
+    }
+}
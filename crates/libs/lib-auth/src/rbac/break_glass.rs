@@ -0,0 +1,205 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use lib_types::errors::{AppError, AuthError};
+use lib_types::ids::{HospitalId, UserId};
+
+use crate::ctx::Ctx;
+
+/// Time-boxed emergency access to the patients of another hospital
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakGlassGrant {
+    pub user_id: UserId,
+    pub hospital_id: HospitalId, // Hospital whose patients become readable
+    pub reason: String,
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl BreakGlassGrant {
+    /// Check if the grant is still in effect at the given time
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        now < self.expires_at
+    }
+
+    /// Seconds until the grant expires (never less than one)
+    pub fn ttl_seconds(&self, now: DateTime<Utc>) -> u64 {
+        (self.expires_at - now).num_seconds().max(1) as u64
+    }
+}
+
+/// Rules for activating break-glass access
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakGlassPolicy {
+    pub max_duration: Duration,
+    pub min_reason_length: usize,
+}
+
+impl Default for BreakGlassPolicy {
+    fn default() -> Self {
+        Self {
+            max_duration: Duration::from_secs(60 * 60),
+            min_reason_length: 20,
+        }
+    }
+}
+
+impl BreakGlassPolicy {
+    /// Validate a break-glass request and build the grant.
+    ///
+    /// Only clinical staff may break the glass, a justification is mandatory,
+    /// and the requested duration is capped at `max_duration`.
+    pub fn grant(
+        &self,
+        ctx: &Ctx,
+        hospital_id: HospitalId,
+        reason: &str,
+        duration: Option<Duration>,
+    ) -> Result<BreakGlassGrant, AppError> {
+        if !ctx.role().can_access_patients() {
+            return Err(AuthError::InsufficientPermissions.into());
+        }
+
+        if ctx.same_hospital(hospital_id) {
+            return Err(AppError::validation_error(
+                "hospital_id",
+                "break-glass is only needed for other hospitals",
+            ));
+        }
+
+        let reason = reason.trim();
+        if reason.chars().count() < self.min_reason_length {
+            return Err(AppError::validation_error(
+                "reason",
+                format!("must be at least {} characters", self.min_reason_length),
+            ));
+        }
+
+        let duration = duration.unwrap_or(self.max_duration).min(self.max_duration);
+        if duration.is_zero() {
+            return Err(AppError::validation_error(
+                "duration",
+                "must be greater than 0",
+            ));
+        }
+
+        let now = Utc::now();
+        Ok(BreakGlassGrant {
+            user_id: ctx.user_id(),
+            hospital_id,
+            reason: reason.to_string(),
+            granted_at: now,
+            expires_at: now + chrono::Duration::seconds(duration.as_secs() as i64),
+        })
+    }
+}
+
+/// Persistence for active break-glass grants
+#[async_trait]
+pub trait BreakGlassStore: Send + Sync {
+    /// Store a grant until it expires, replacing any earlier grant for the same hospital
+    async fn activate(&self, grant: &BreakGlassGrant) -> Result<(), AppError>;
+
+    /// Get the caller's unexpired grant for a hospital
+    async fn active_grant(
+        &self,
+        user_id: UserId,
+        hospital_id: HospitalId,
+    ) -> Result<Option<BreakGlassGrant>, AppError>;
+}
+
+/// Emit the audit event for a newly activated grant
+pub fn audit_break_glass_activated(grant: &BreakGlassGrant) {
+    warn!(
+        target: "audit",
+        event = "break_glass_activated",
+        user_id = %grant.user_id,
+        hospital_id = %grant.hospital_id,
+        expires_at = %grant.expires_at,
+        reason = %grant.reason,
+        "Break-glass access activated"
+    );
+}
+
+/// Emit the audit event for a resource read under a grant
+pub fn audit_break_glass_access(grant: &BreakGlassGrant, resource: &str, resource_id: Uuid) {
+    warn!(
+        target: "audit",
+        event = "break_glass_access",
+        user_id = %grant.user_id,
+        hospital_id = %grant.hospital_id,
+        resource,
+        resource_id = %resource_id,
+        "Resource accessed under break-glass"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib_types::enums::UserRole;
+
+    const REASON: &str = "Mass casualty incident, patient transferred without records";
+
+    fn ctx(role: UserRole) -> Ctx {
+        Ctx::new(UserId::new(), role, HospitalId::new(), Uuid::new_v4())
+    }
+
+    #[test]
+    fn test_grant_is_time_boxed() {
+        let policy = BreakGlassPolicy::default();
+        let nurse = ctx(UserRole::Nurse);
+        let other_hospital = HospitalId::new();
+
+        let grant = policy
+            .grant(
+                &nurse,
+                other_hospital,
+                REASON,
+                Some(Duration::from_secs(4 * 3600)),
+            )
+            .unwrap();
+
+        assert_eq!(grant.user_id, nurse.user_id());
+        assert_eq!(
+            grant.expires_at - grant.granted_at,
+            chrono::Duration::hours(1)
+        );
+        assert!(grant.is_active_at(grant.granted_at + chrono::Duration::minutes(59)));
+        assert!(!grant.is_active_at(grant.expires_at));
+    }
+
+    #[test]
+    fn test_reason_required() {
+        let policy = BreakGlassPolicy::default();
+        let result = policy.grant(
+            &ctx(UserRole::Paramedic),
+            HospitalId::new(),
+            "  need it  ",
+            None,
+        );
+
+        assert!(matches!(result, Err(AppError::Validation { field, .. }) if field == "reason"));
+    }
+
+    #[test]
+    fn test_non_clinical_and_own_hospital_rejected() {
+        let policy = BreakGlassPolicy::default();
+
+        let admin = ctx(UserRole::Admin);
+        assert_eq!(
+            policy.grant(&admin, HospitalId::new(), REASON, None),
+            Err(AppError::Auth(AuthError::InsufficientPermissions))
+        );
+
+        let nurse = ctx(UserRole::Nurse);
+        assert!(policy
+            .grant(&nurse, nurse.hospital_id(), REASON, None)
+            .is_err());
+    }
+}
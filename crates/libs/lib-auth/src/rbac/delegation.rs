@@ -0,0 +1,349 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use uuid::Uuid;
+
+use lib_types::enums::UserRole;
+use lib_types::errors::{AppError, AuthError};
+use lib_types::ids::{HospitalId, UserId};
+
+use crate::ctx::Ctx;
+use crate::jwt::DelegationClaim;
+
+/// Time-boxed handover of a user's role to a colleague (e.g. an ER Director
+/// going off shift lending approval powers to a specialist)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Delegation {
+    pub id: Uuid,
+    pub delegator_id: UserId,
+    pub delegate_id: UserId,
+    pub hospital_id: HospitalId,
+    pub role: UserRole, // Role the delegate may act with
+    pub reason: String,
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Delegation {
+    /// Check if the delegation is still in effect at the given time
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        now < self.expires_at
+    }
+
+    /// Seconds until the delegation expires (never less than one)
+    pub fn ttl_seconds(&self, now: DateTime<Utc>) -> u64 {
+        (self.expires_at - now).num_seconds().max(1) as u64
+    }
+
+    /// Claim embedded in the delegate's access tokens
+    pub fn claim(&self) -> DelegationClaim {
+        DelegationClaim {
+            id: self.id,
+            delegator_id: self.delegator_id,
+            role: self.role,
+            exp: self.expires_at.timestamp(),
+        }
+    }
+}
+
+/// Rules for delegating a role
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelegationPolicy {
+    pub default_duration: Duration,
+    pub max_duration: Duration,
+    pub min_reason_length: usize,
+}
+
+impl Default for DelegationPolicy {
+    fn default() -> Self {
+        Self {
+            default_duration: Duration::from_secs(8 * 60 * 60),
+            max_duration: Duration::from_secs(12 * 60 * 60),
+            min_reason_length: 10,
+        }
+    }
+}
+
+impl DelegationPolicy {
+    /// Validate a delegation request and build the grant.
+    ///
+    /// Only ER Directors acting with their own role may delegate, and only to
+    /// clinical staff of their hospital. The duration defaults to
+    /// `default_duration` and is capped at `max_duration`.
+    pub fn grant(
+        &self,
+        ctx: &Ctx,
+        delegate_id: UserId,
+        delegate_role: UserRole,
+        delegate_hospital_id: HospitalId,
+        reason: &str,
+        duration: Option<Duration>,
+    ) -> Result<Delegation, AppError> {
+        // Delegated powers cannot be passed on
+        if ctx.own_role() != UserRole::ErDirector || ctx.delegation().is_some() {
+            return Err(AuthError::InsufficientPermissions.into());
+        }
+
+        if delegate_id == ctx.user_id() {
+            return Err(AppError::validation_error(
+                "delegate_id",
+                "cannot delegate to yourself",
+            ));
+        }
+
+        if !ctx.same_hospital(delegate_hospital_id) {
+            return Err(AuthError::HospitalAccessDenied {
+                hospital_id: delegate_hospital_id,
+            }
+            .into());
+        }
+
+        if !delegate_role.can_access_patients() || delegate_role == ctx.own_role() {
+            return Err(AppError::validation_error(
+                "delegate_id",
+                format!("cannot delegate to a {}", delegate_role.display_name()),
+            ));
+        }
+
+        let reason = reason.trim();
+        if reason.chars().count() < self.min_reason_length {
+            return Err(AppError::validation_error(
+                "reason",
+                format!("must be at least {} characters", self.min_reason_length),
+            ));
+        }
+
+        let duration = duration
+            .unwrap_or(self.default_duration)
+            .min(self.max_duration);
+        if duration.is_zero() {
+            return Err(AppError::validation_error(
+                "duration",
+                "must be greater than 0",
+            ));
+        }
+
+        let now = Utc::now();
+        Ok(Delegation {
+            id: Uuid::new_v4(),
+            delegator_id: ctx.user_id(),
+            delegate_id,
+            hospital_id: ctx.hospital_id(),
+            role: ctx.own_role(),
+            reason: reason.to_string(),
+            granted_at: now,
+            expires_at: now + chrono::Duration::seconds(duration.as_secs() as i64),
+        })
+    }
+}
+
+/// Persistence for active delegations (at most one per delegate)
+#[async_trait]
+pub trait DelegationStore: Send + Sync {
+    /// Store a delegation until it expires, replacing any earlier one for the same delegate
+    async fn grant(&self, delegation: &Delegation) -> Result<(), AppError>;
+
+    /// Get the unexpired delegation held by a user
+    async fn active_for_delegate(
+        &self,
+        delegate_id: UserId,
+    ) -> Result<Option<Delegation>, AppError>;
+
+    /// Remove the delegation held by a user, returning it if there was one
+    async fn revoke(&self, delegate_id: UserId) -> Result<Option<Delegation>, AppError>;
+}
+
+/// Emit the audit event for a newly granted delegation
+pub fn audit_delegation_granted(delegation: &Delegation) {
+    warn!(
+        target: "audit",
+        event = "delegation_granted",
+        delegation_id = %delegation.id,
+        delegator_id = %delegation.delegator_id,
+        delegate_id = %delegation.delegate_id,
+        role = delegation.role.display_name(),
+        expires_at = %delegation.expires_at,
+        reason = %delegation.reason,
+        "Role delegated"
+    );
+}
+
+/// Emit the audit event for a delegation ended before it expired
+pub fn audit_delegation_revoked(delegation: &Delegation, revoked_by: UserId) {
+    warn!(
+        target: "audit",
+        event = "delegation_revoked",
+        delegation_id = %delegation.id,
+        delegator_id = %delegation.delegator_id,
+        delegate_id = %delegation.delegate_id,
+        revoked_by = %revoked_by,
+        "Role delegation revoked"
+    );
+}
+
+/// Emit the audit event for a request made under a delegation
+pub fn audit_delegated_action(ctx: &Ctx, delegation: &DelegationClaim, method: &str, path: &str) {
+    warn!(
+        target: "audit",
+        event = "delegated_action",
+        delegation_id = %delegation.id,
+        delegator_id = %delegation.delegator_id,
+        user_id = %ctx.user_id(),
+        role = delegation.role.display_name(),
+        method,
+        path,
+        "Request made under delegation"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REASON: &str = "Off shift until 07:00";
+
+    fn director() -> Ctx {
+        Ctx::new(
+            UserId::new(),
+            UserRole::ErDirector,
+            HospitalId::new(),
+            Uuid::new_v4(),
+        )
+    }
+
+    #[test]
+    fn test_grant_defaults_to_shift_length() {
+        let policy = DelegationPolicy::default();
+        let director = director();
+        let delegate_id = UserId::new();
+
+        let delegation = policy
+            .grant(
+                &director,
+                delegate_id,
+                UserRole::Specialist,
+                director.hospital_id(),
+                REASON,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(delegation.delegator_id, director.user_id());
+        assert_eq!(delegation.role, UserRole::ErDirector);
+        assert_eq!(
+            delegation.expires_at - delegation.granted_at,
+            chrono::Duration::hours(8)
+        );
+        assert_eq!(delegation.claim().exp, delegation.expires_at.timestamp());
+        assert!(!delegation.is_active_at(delegation.expires_at));
+
+        let capped = policy
+            .grant(
+                &director,
+                delegate_id,
+                UserRole::Specialist,
+                director.hospital_id(),
+                REASON,
+                Some(Duration::from_secs(48 * 3600)),
+            )
+            .unwrap();
+        assert_eq!(
+            capped.expires_at - capped.granted_at,
+            chrono::Duration::hours(12)
+        );
+    }
+
+    #[test]
+    fn test_only_directors_delegate_within_hospital() {
+        let policy = DelegationPolicy::default();
+        let director = director();
+        let hospital_id = director.hospital_id();
+
+        let nurse = Ctx::new(UserId::new(), UserRole::Nurse, hospital_id, Uuid::new_v4());
+        assert_eq!(
+            policy.grant(
+                &nurse,
+                UserId::new(),
+                UserRole::Specialist,
+                hospital_id,
+                REASON,
+                None
+            ),
+            Err(AppError::Auth(AuthError::InsufficientPermissions))
+        );
+
+        let other_hospital = HospitalId::new();
+        assert!(matches!(
+            policy.grant(
+                &director,
+                UserId::new(),
+                UserRole::Specialist,
+                other_hospital,
+                REASON,
+                None
+            ),
+            Err(AppError::Auth(AuthError::HospitalAccessDenied { .. }))
+        ));
+
+        assert!(policy
+            .grant(
+                &director,
+                UserId::new(),
+                UserRole::Admin,
+                hospital_id,
+                REASON,
+                None
+            )
+            .is_err());
+        assert!(policy
+            .grant(
+                &director,
+                director.user_id(),
+                UserRole::Specialist,
+                hospital_id,
+                REASON,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_delegated_role_cannot_be_redelegated() {
+        let policy = DelegationPolicy::default();
+        let director = director();
+        let delegation = policy
+            .grant(
+                &director,
+                UserId::new(),
+                UserRole::Specialist,
+                director.hospital_id(),
+                REASON,
+                None,
+            )
+            .unwrap();
+
+        let delegate = Ctx::new(
+            delegation.delegate_id,
+            UserRole::Specialist,
+            delegation.hospital_id,
+            Uuid::new_v4(),
+        )
+        .with_delegation(delegation.claim());
+        assert_eq!(delegate.role(), UserRole::ErDirector);
+
+        assert_eq!(
+            policy.grant(
+                &delegate,
+                UserId::new(),
+                UserRole::Nurse,
+                delegation.hospital_id,
+                REASON,
+                None
+            ),
+            Err(AppError::Auth(AuthError::InsufficientPermissions))
+        );
+    }
+}
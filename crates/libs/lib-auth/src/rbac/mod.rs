@@ -1 +1,19 @@
 // pub mod rbac;
+
+pub mod break_glass;
+pub mod delegation;
+pub mod permissions;
+pub mod redis_break_glass;
+pub mod redis_delegation;
+
+pub use break_glass::{
+    audit_break_glass_access, audit_break_glass_activated, BreakGlassGrant, BreakGlassPolicy,
+    BreakGlassStore,
+};
+pub use delegation::{
+    audit_delegated_action, audit_delegation_granted, audit_delegation_revoked, Delegation,
+    DelegationPolicy, DelegationStore,
+};
+pub use permissions::Permissions;
+pub use redis_break_glass::RedisBreakGlassStore;
+pub use redis_delegation::RedisDelegationStore;
@@ -0,0 +1,159 @@
+use std::ops::{BitOr, BitOrAssign};
+
+use serde::{Deserialize, Serialize};
+
+use lib_types::enums::{PatientStatus, UserRole};
+
+/// Bitmap of the actions a token holder may perform, serialized as a plain integer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Permissions(u32);
+
+impl Permissions {
+    pub const NONE: Self = Self(0);
+    pub const VIEW_PATIENTS: Self = Self(1 << 0);
+    pub const EDIT_PATIENTS: Self = Self(1 << 1);
+    pub const MANAGE_BEDS: Self = Self(1 << 2);
+    pub const DISPATCH: Self = Self(1 << 3);
+    pub const MANAGE_STAFF: Self = Self(1 << 4);
+    pub const VIEW_AUDIT: Self = Self(1 << 5);
+    pub const DELEGATE: Self = Self(1 << 6);
+    pub const CLINICAL_SIGN_OFF: Self = Self(1 << 7); // Discharge, transfer, pronounce death
+
+    /// Create from raw bits, dropping any that are not defined
+    pub fn from_bits_truncate(bits: u32) -> Self {
+        Self(bits & Self::all().0)
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Every defined permission
+    pub fn all() -> Self {
+        Self::VIEW_PATIENTS
+            | Self::EDIT_PATIENTS
+            | Self::MANAGE_BEDS
+            | Self::DISPATCH
+            | Self::MANAGE_STAFF
+            | Self::VIEW_AUDIT
+            | Self::DELEGATE
+            | Self::CLINICAL_SIGN_OFF
+    }
+
+    /// Default permissions granted by a role
+    pub fn for_role(role: UserRole) -> Self {
+        match role {
+            UserRole::ErDirector => Self::all(),
+            UserRole::Paramedic => Self::VIEW_PATIENTS | Self::EDIT_PATIENTS | Self::DISPATCH,
+            UserRole::Nurse => Self::VIEW_PATIENTS | Self::EDIT_PATIENTS | Self::MANAGE_BEDS,
+            UserRole::Specialist => {
+                Self::VIEW_PATIENTS | Self::EDIT_PATIENTS | Self::CLINICAL_SIGN_OFF
+            }
+            UserRole::Admin => Self::MANAGE_BEDS | Self::MANAGE_STAFF | Self::VIEW_AUDIT,
+        }
+    }
+
+    /// Permissions needed to move a patient from `from` to `to`, or `None`
+    /// if the workflow does not allow the transition. Transport updates are
+    /// for dispatch, care updates for clinical staff, and ending the visit
+    /// needs a clinician's sign-off.
+    pub fn for_patient_transition(from: PatientStatus, to: PatientStatus) -> Option<Self> {
+        if !from.can_transition_to(to) {
+            return None;
+        }
+        Some(match to {
+            PatientStatus::Dispatched | PatientStatus::EnRoute | PatientStatus::Arrived => {
+                Self::DISPATCH
+            }
+            PatientStatus::InTriage
+            | PatientStatus::InTreatment
+            | PatientStatus::AwaitingResults
+            | PatientStatus::Admitted
+            | PatientStatus::LeftWithoutBeingSeen => Self::EDIT_PATIENTS,
+            PatientStatus::Discharged | PatientStatus::Transferred | PatientStatus::Deceased => {
+                Self::EDIT_PATIENTS | Self::CLINICAL_SIGN_OFF
+            }
+        })
+    }
+
+    /// Check if every permission in `other` is present
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for Permissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Permissions {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_permissions() {
+        let nurse = Permissions::for_role(UserRole::Nurse);
+        assert!(nurse.contains(Permissions::VIEW_PATIENTS | Permissions::MANAGE_BEDS));
+        assert!(!nurse.contains(Permissions::DISPATCH));
+
+        let admin = Permissions::for_role(UserRole::Admin);
+        assert!(!admin.contains(Permissions::VIEW_PATIENTS));
+        assert!(Permissions::for_role(UserRole::ErDirector).contains(admin));
+    }
+
+    #[test]
+    fn test_patient_transition_permissions() {
+        assert_eq!(
+            Permissions::for_patient_transition(PatientStatus::EnRoute, PatientStatus::Arrived),
+            Some(Permissions::DISPATCH)
+        );
+        assert_eq!(
+            Permissions::for_patient_transition(PatientStatus::Admitted, PatientStatus::Discharged),
+            Some(Permissions::EDIT_PATIENTS | Permissions::CLINICAL_SIGN_OFF)
+        );
+        assert_eq!(
+            Permissions::for_patient_transition(PatientStatus::Discharged, PatientStatus::Admitted),
+            None
+        );
+
+        let nurse = Permissions::for_role(UserRole::Nurse);
+        let triage =
+            Permissions::for_patient_transition(PatientStatus::Arrived, PatientStatus::InTriage);
+        assert!(nurse.contains(triage.unwrap()));
+        let death = Permissions::for_patient_transition(
+            PatientStatus::InTreatment,
+            PatientStatus::Deceased,
+        );
+        assert!(!nurse.contains(death.unwrap()));
+        assert!(Permissions::for_role(UserRole::Specialist).contains(death.unwrap()));
+    }
+
+    #[test]
+    fn test_serializes_as_bits() {
+        let permissions = Permissions::VIEW_PATIENTS | Permissions::DISPATCH;
+        assert_eq!(serde_json::to_string(&permissions).unwrap(), "9");
+        assert_eq!(
+            serde_json::from_str::<Permissions>("9").unwrap(),
+            permissions
+        );
+        assert_eq!(
+            Permissions::from_bits_truncate(u32::MAX),
+            Permissions::all()
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use redis::aio::{ConnectionLike, ConnectionManager};
+use redis::{AsyncCommands, RedisError};
+
+use lib_types::errors::AppError;
+use lib_types::ids::{HospitalId, UserId};
+
+use super::break_glass::{BreakGlassGrant, BreakGlassStore};
+
+const GRANT_KEY_PREFIX: &str = "break_glass:";
+
+/// Break-glass grants stored in Redis under `break_glass:{user_id}:{hospital_id}`,
+/// with a TTL so they expire without any cleanup job
+#[derive(Clone)]
+pub struct RedisBreakGlassStore<C = ConnectionManager> {
+    conn: C,
+}
+
+impl<C> RedisBreakGlassStore<C> {
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    fn grant_key(user_id: UserId, hospital_id: HospitalId) -> String {
+        format!("{}{}:{}", GRANT_KEY_PREFIX, user_id, hospital_id)
+    }
+}
+
+fn redis_error(error: RedisError) -> AppError {
+    AppError::external_service_error("Redis", error.to_string())
+}
+
+#[async_trait]
+impl<C> BreakGlassStore for RedisBreakGlassStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    async fn activate(&self, grant: &BreakGlassGrant) -> Result<(), AppError> {
+        let json = serde_json::to_string(grant).map_err(|_| AppError::Internal)?;
+
+        self.conn
+            .clone()
+            .set_ex(
+                Self::grant_key(grant.user_id, grant.hospital_id),
+                json,
+                grant.ttl_seconds(Utc::now()),
+            )
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn active_grant(
+        &self,
+        user_id: UserId,
+        hospital_id: HospitalId,
+    ) -> Result<Option<BreakGlassGrant>, AppError> {
+        let json: Option<String> = self
+            .conn
+            .clone()
+            .get(Self::grant_key(user_id, hospital_id))
+            .await
+            .map_err(redis_error)?;
+
+        let Some(json) = json else {
+            return Ok(None);
+        };
+
+        let grant: BreakGlassGrant = serde_json::from_str(&json).map_err(|e| {
+            AppError::external_service_error("Redis", format!("corrupt break-glass grant: {}", e))
+        })?;
+
+        Ok(Some(grant).filter(|grant| grant.is_active_at(Utc::now())))
+    }
+}
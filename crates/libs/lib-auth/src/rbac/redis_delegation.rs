@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use redis::aio::{ConnectionLike, ConnectionManager};
+use redis::{AsyncCommands, RedisError};
+
+use lib_types::errors::AppError;
+use lib_types::ids::UserId;
+
+use super::delegation::{Delegation, DelegationStore};
+
+const DELEGATION_KEY_PREFIX: &str = "delegation:";
+
+/// Delegations stored in Redis under `delegation:{delegate_id}`, with a TTL so
+/// they expire without any cleanup job
+#[derive(Clone)]
+pub struct RedisDelegationStore<C = ConnectionManager> {
+    conn: C,
+}
+
+impl<C> RedisDelegationStore<C> {
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    fn delegation_key(delegate_id: UserId) -> String {
+        format!("{}{}", DELEGATION_KEY_PREFIX, delegate_id)
+    }
+}
+
+fn redis_error(error: RedisError) -> AppError {
+    AppError::external_service_error("Redis", error.to_string())
+}
+
+fn parse_delegation(json: Option<String>) -> Result<Option<Delegation>, AppError> {
+    let Some(json) = json else {
+        return Ok(None);
+    };
+
+    let delegation: Delegation = serde_json::from_str(&json).map_err(|e| {
+        AppError::external_service_error("Redis", format!("corrupt delegation: {}", e))
+    })?;
+
+    Ok(Some(delegation).filter(|delegation| delegation.is_active_at(Utc::now())))
+}
+
+#[async_trait]
+impl<C> DelegationStore for RedisDelegationStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    async fn grant(&self, delegation: &Delegation) -> Result<(), AppError> {
+        let json = serde_json::to_string(delegation).map_err(|_| AppError::Internal)?;
+
+        self.conn
+            .clone()
+            .set_ex(
+                Self::delegation_key(delegation.delegate_id),
+                json,
+                delegation.ttl_seconds(Utc::now()),
+            )
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn active_for_delegate(
+        &self,
+        delegate_id: UserId,
+    ) -> Result<Option<Delegation>, AppError> {
+        let json: Option<String> = self
+            .conn
+            .clone()
+            .get(Self::delegation_key(delegate_id))
+            .await
+            .map_err(redis_error)?;
+
+        parse_delegation(json)
+    }
+
+    async fn revoke(&self, delegate_id: UserId) -> Result<Option<Delegation>, AppError> {
+        let json: Option<String> = self
+            .conn
+            .clone()
+            .get_del(Self::delegation_key(delegate_id))
+            .await
+            .map_err(redis_error)?;
+
+        parse_delegation(json)
+    }
+}
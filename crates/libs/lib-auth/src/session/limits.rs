@@ -0,0 +1,55 @@
+use lib_types::enums::UserRole;
+
+/// Maximum number of concurrent sessions per role.
+///
+/// Logging in beyond the limit evicts the least recently active sessions, so
+/// shared devices do not accumulate stale sessions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionLimits {
+    pub er_director: usize,
+    pub paramedic: usize,
+    pub nurse: usize,
+    pub specialist: usize,
+    pub admin: usize,
+}
+
+impl Default for SessionLimits {
+    fn default() -> Self {
+        Self {
+            er_director: 5,
+            paramedic: 2, // Shared ambulance tablets
+            nurse: 3,
+            specialist: 3,
+            admin: 3,
+        }
+    }
+}
+
+impl SessionLimits {
+    /// Get the session limit for a role
+    pub fn for_role(&self, role: UserRole) -> usize {
+        match role {
+            UserRole::ErDirector => self.er_director,
+            UserRole::Paramedic => self.paramedic,
+            UserRole::Nurse => self.nurse,
+            UserRole::Specialist => self.specialist,
+            UserRole::Admin => self.admin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_per_role() {
+        let limits = SessionLimits {
+            paramedic: 1,
+            ..SessionLimits::default()
+        };
+
+        assert_eq!(limits.for_role(UserRole::Paramedic), 1);
+        assert_eq!(limits.for_role(UserRole::ErDirector), 5);
+    }
+}
@@ -0,0 +1,9 @@
+// pub mod session;
+
+pub mod limits;
+pub mod redis_store;
+pub mod store;
+
+pub use limits::SessionLimits;
+pub use redis_store::RedisSessionStore;
+pub use store::{Session, SessionStore};
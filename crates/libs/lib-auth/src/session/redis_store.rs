@@ -0,0 +1,145 @@
+use std::cmp::Reverse;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use redis::aio::{ConnectionLike, ConnectionManager};
+use redis::{AsyncCommands, RedisError};
+use uuid::Uuid;
+
+use lib_types::errors::AppError;
+use lib_types::ids::UserId;
+
+use super::store::{Session, SessionStore};
+
+const SESSION_KEY_PREFIX: &str = "session:";
+const USER_SESSIONS_KEY_PREFIX: &str = "user_sessions:";
+
+/// Session store backed by Redis.
+///
+/// Each session is stored as JSON under `session:{id}` with a TTL equal to the
+/// remaining idle time, so Redis expires abandoned sessions on its own. A set
+/// under `user_sessions:{user_id}` indexes the sessions of each user; ids of
+/// expired sessions are pruned from it lazily when the user's sessions are listed.
+///
+/// `C` is the Redis connection: a `ConnectionManager` for a single server, or
+/// any other connection such as a pool that follows Sentinel failovers.
+#[derive(Clone)]
+pub struct RedisSessionStore<C = ConnectionManager> {
+    conn: C,
+    timeout: Duration,
+}
+
+impl<C> RedisSessionStore<C> {
+    /// Create a store whose sessions expire after `timeout` of inactivity
+    pub fn new(conn: C, timeout: Duration) -> Self {
+        Self { conn, timeout }
+    }
+
+    fn session_key(id: Uuid) -> String {
+        format!("{}{}", SESSION_KEY_PREFIX, id)
+    }
+
+    fn user_sessions_key(user_id: UserId) -> String {
+        format!("{}{}", USER_SESSIONS_KEY_PREFIX, user_id)
+    }
+}
+
+fn redis_error(error: RedisError) -> AppError {
+    AppError::external_service_error("Redis", error.to_string())
+}
+
+fn decode(json: &str) -> Result<Session, AppError> {
+    serde_json::from_str(json)
+        .map_err(|e| AppError::external_service_error("Redis", format!("corrupt session: {}", e)))
+}
+
+#[async_trait]
+impl<C> SessionStore for RedisSessionStore<C>
+where
+    C: ConnectionLike + Clone + Send + Sync + 'static,
+{
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    async fn save(&self, session: &Session) -> Result<(), AppError> {
+        let json = serde_json::to_string(session).map_err(|_| AppError::Internal)?;
+        let ttl = session.ttl_seconds(Utc::now());
+        let user_key = Self::user_sessions_key(session.user_id);
+
+        // The index must outlive every session it references
+        redis::pipe()
+            .atomic()
+            .set_ex(Self::session_key(session.id), json, ttl)
+            .ignore()
+            .sadd(&user_key, session.id.to_string())
+            .ignore()
+            .expire(&user_key, self.timeout.as_secs() as i64)
+            .ignore()
+            .query_async::<()>(&mut self.conn.clone())
+            .await
+            .map_err(redis_error)
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<Session>, AppError> {
+        let json: Option<String> = self
+            .conn
+            .clone()
+            .get(Self::session_key(id))
+            .await
+            .map_err(redis_error)?;
+
+        json.as_deref().map(decode).transpose()
+    }
+
+    async fn list_for_user(&self, user_id: UserId) -> Result<Vec<Session>, AppError> {
+        let mut conn = self.conn.clone();
+        let user_key = Self::user_sessions_key(user_id);
+
+        let ids: Vec<String> = conn.smembers(&user_key).await.map_err(redis_error)?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = ids
+            .iter()
+            .map(|id| format!("{}{}", SESSION_KEY_PREFIX, id))
+            .collect();
+        let values: Vec<Option<String>> = conn.mget(&keys).await.map_err(redis_error)?;
+
+        let mut sessions = Vec::with_capacity(values.len());
+        let mut stale = Vec::new();
+        for (id, value) in ids.into_iter().zip(values) {
+            match value {
+                Some(json) => sessions.push(decode(&json)?),
+                None => stale.push(id),
+            }
+        }
+
+        if !stale.is_empty() {
+            let _: () = conn.srem(&user_key, stale).await.map_err(redis_error)?;
+        }
+
+        sessions.sort_by_key(|session| Reverse(session.last_seen_at));
+        Ok(sessions)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+        let Some(session) = self.get(id).await? else {
+            return Ok(false);
+        };
+
+        redis::pipe()
+            .atomic()
+            .del(Self::session_key(id))
+            .ignore()
+            .srem(Self::user_sessions_key(session.user_id), id.to_string())
+            .ignore()
+            .query_async::<()>(&mut self.conn.clone())
+            .await
+            .map_err(redis_error)?;
+
+        Ok(true)
+    }
+}
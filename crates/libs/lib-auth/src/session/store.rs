@@ -0,0 +1,251 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_types::enums::UserRole;
+use lib_types::errors::AppError;
+use lib_types::ids::{HospitalId, UserId};
+
+/// Server-side login session referenced by the `sid` claim of access tokens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub role: UserRole,
+    pub hospital_id: HospitalId,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    #[serde(default)]
+    pub device_id: Option<Uuid>, // Registered device the session was started from
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Session {
+    /// Start a new session that expires after `timeout` of inactivity
+    pub fn new(
+        user_id: UserId,
+        role: UserRole,
+        hospital_id: HospitalId,
+        timeout: Duration,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            role,
+            hospital_id,
+            ip_address: None,
+            user_agent: None,
+            device_id: None,
+            created_at: now,
+            last_seen_at: now,
+            expires_at: now + idle_timeout(timeout),
+        }
+    }
+
+    /// Record the client the session was started from
+    pub fn with_client(mut self, ip_address: Option<String>, user_agent: Option<String>) -> Self {
+        self.ip_address = ip_address;
+        self.user_agent = user_agent;
+        self
+    }
+
+    /// Bind the session to a registered device
+    pub fn with_device(mut self, device_id: Uuid) -> Self {
+        self.device_id = Some(device_id);
+        self
+    }
+
+    /// Check if the session has expired at the given time
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+
+    /// Record activity and push the expiry out by another `timeout`
+    pub fn touch(&mut self, now: DateTime<Utc>, timeout: Duration) {
+        self.last_seen_at = now;
+        self.expires_at = now + idle_timeout(timeout);
+    }
+
+    /// Seconds until the session expires (never less than one)
+    pub fn ttl_seconds(&self, now: DateTime<Utc>) -> u64 {
+        (self.expires_at - now).num_seconds().max(1) as u64
+    }
+}
+
+fn idle_timeout(timeout: Duration) -> chrono::Duration {
+    chrono::Duration::seconds(timeout.as_secs() as i64)
+}
+
+/// Persistence for server-side sessions
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Idle timeout applied to new and refreshed sessions
+    fn timeout(&self) -> Duration;
+
+    /// Insert or replace a session
+    async fn save(&self, session: &Session) -> Result<(), AppError>;
+
+    /// Load a session, or `None` if it expired or was terminated
+    async fn get(&self, id: Uuid) -> Result<Option<Session>, AppError>;
+
+    /// List the active sessions of a user, most recently used first
+    async fn list_for_user(&self, user_id: UserId) -> Result<Vec<Session>, AppError>;
+
+    /// Terminate a session, returning whether it existed
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError>;
+
+    /// Load an active session and extend its idle timeout
+    async fn touch(&self, id: Uuid) -> Result<Option<Session>, AppError> {
+        let Some(mut session) = self.get(id).await? else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        if session.is_expired_at(now) {
+            return Ok(None);
+        }
+
+        session.touch(now, self.timeout());
+        self.save(&session).await?;
+        Ok(Some(session))
+    }
+
+    /// Terminate all but the `keep` most recently used sessions of a user,
+    /// returning the ids of the evicted sessions
+    async fn evict_excess(&self, user_id: UserId, keep: usize) -> Result<Vec<Uuid>, AppError> {
+        let mut evicted = Vec::new();
+        for session in self.list_for_user(user_id).await?.into_iter().skip(keep) {
+            if self.delete(session.id).await? {
+                evicted.push(session.id);
+            }
+        }
+        Ok(evicted)
+    }
+
+    /// Terminate every session of a user (logging them out on all devices),
+    /// returning the ids of the terminated sessions
+    async fn delete_all_for_user(&self, user_id: UserId) -> Result<Vec<Uuid>, AppError> {
+        self.evict_excess(user_id, 0).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Reverse;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemorySessionStore(Mutex<HashMap<Uuid, Session>>);
+
+    #[async_trait]
+    impl SessionStore for MemorySessionStore {
+        fn timeout(&self) -> Duration {
+            Duration::from_secs(60)
+        }
+
+        async fn save(&self, session: &Session) -> Result<(), AppError> {
+            self.0.lock().unwrap().insert(session.id, session.clone());
+            Ok(())
+        }
+
+        async fn get(&self, id: Uuid) -> Result<Option<Session>, AppError> {
+            Ok(self.0.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn list_for_user(&self, user_id: UserId) -> Result<Vec<Session>, AppError> {
+            let mut sessions: Vec<Session> = self
+                .0
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|session| session.user_id == user_id)
+                .cloned()
+                .collect();
+            sessions.sort_by_key(|session| Reverse(session.last_seen_at));
+            Ok(sessions)
+        }
+
+        async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+            Ok(self.0.lock().unwrap().remove(&id).is_some())
+        }
+    }
+
+    #[test]
+    fn test_session_expiry_and_touch() {
+        let timeout = Duration::from_secs(30 * 60);
+        let mut session = Session::new(UserId::new(), UserRole::Nurse, HospitalId::new(), timeout);
+        let start = session.created_at;
+
+        assert!(!session.is_expired_at(start + chrono::Duration::minutes(29)));
+        assert!(session.is_expired_at(start + chrono::Duration::minutes(30)));
+
+        let later = start + chrono::Duration::minutes(20);
+        session.touch(later, timeout);
+        assert_eq!(session.last_seen_at, later);
+        assert!(!session.is_expired_at(start + chrono::Duration::minutes(45)));
+        assert_eq!(session.ttl_seconds(later), 30 * 60);
+        assert_eq!(session.ttl_seconds(later + chrono::Duration::hours(1)), 1);
+    }
+
+    #[test]
+    fn test_session_client_info() {
+        let session = Session::new(
+            UserId::new(),
+            UserRole::Paramedic,
+            HospitalId::new(),
+            Duration::from_secs(60),
+        )
+        .with_client(Some("10.0.0.12".to_string()), Some("ER Tablet".to_string()));
+
+        assert_eq!(session.ip_address.as_deref(), Some("10.0.0.12"));
+        assert_eq!(session.user_agent.as_deref(), Some("ER Tablet"));
+    }
+
+    #[tokio::test]
+    async fn test_evict_least_recently_used() {
+        let store = MemorySessionStore::default();
+        let user_id = UserId::new();
+        let start = Utc::now();
+
+        let mut ids = Vec::new();
+        for minutes in 0..3 {
+            let mut session =
+                Session::new(user_id, UserRole::Paramedic, HospitalId::new(), store.timeout());
+            session.touch(start + chrono::Duration::minutes(minutes), store.timeout());
+            store.save(&session).await.unwrap();
+            ids.push(session.id);
+        }
+
+        let evicted = store.evict_excess(user_id, 2).await.unwrap();
+        assert_eq!(evicted, vec![ids[0]]);
+        assert_eq!(store.list_for_user(user_id).await.unwrap().len(), 2);
+        assert!(store.evict_excess(user_id, 2).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delete_all_for_user() {
+        let store = MemorySessionStore::default();
+        let user_id = UserId::new();
+        let other =
+            Session::new(UserId::new(), UserRole::Nurse, HospitalId::new(), store.timeout());
+        store.save(&other).await.unwrap();
+        for _ in 0..2 {
+            let session =
+                Session::new(user_id, UserRole::Nurse, HospitalId::new(), store.timeout());
+            store.save(&session).await.unwrap();
+        }
+
+        assert_eq!(store.delete_all_for_user(user_id).await.unwrap().len(), 2);
+        assert!(store.list_for_user(user_id).await.unwrap().is_empty());
+        // Other users stay logged in
+        assert!(store.get(other.id).await.unwrap().is_some());
+    }
+}
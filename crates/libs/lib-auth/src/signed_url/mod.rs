@@ -0,0 +1,5 @@
+// pub mod signed_url;
+
+pub mod signer;
+
+pub use signer::{SignedUrl, UrlSigner, EXPIRES_PARAM, SIGNATURE_PARAM};
@@ -0,0 +1,189 @@
+use std::fmt;
+use std::time::Duration;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use lib_types::errors::AuthError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Query parameter carrying the unix time after which a link stops working
+pub const EXPIRES_PARAM: &str = "expires";
+/// Query parameter carrying the HMAC of the path and expiry
+pub const SIGNATURE_PARAM: &str = "signature";
+
+/// Link that grants access to a single path until it expires
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedUrl {
+    pub url: String, // Path and query, relative to the API origin
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Creates and checks HMAC-SHA256 signed links, used to share a document with
+/// someone who has no account (e.g. an ambulance crew) for a short time.
+///
+/// Only the path and expiry are signed; any other query parameters are ignored
+/// by `verify` and must not influence what is served.
+#[derive(Clone)]
+pub struct UrlSigner {
+    key: Vec<u8>,
+    ttl: Duration,
+}
+
+impl UrlSigner {
+    pub fn new(key: impl AsRef<[u8]>, ttl: Duration) -> Self {
+        Self {
+            key: key.as_ref().to_vec(),
+            ttl,
+        }
+    }
+
+    /// How long issued links stay valid
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Sign `path` so it can be opened without logging in until `now + ttl`
+    pub fn sign(&self, path: &str, now: DateTime<Utc>) -> SignedUrl {
+        let expires = now.timestamp() + self.ttl.as_secs() as i64;
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(path, expires).finalize().into_bytes());
+
+        SignedUrl {
+            url: format!("{path}?{EXPIRES_PARAM}={expires}&{SIGNATURE_PARAM}={signature}"),
+            expires_at: Utc.timestamp_opt(expires, 0).single().unwrap_or(now),
+        }
+    }
+
+    /// Check the signature of a request for `path` and return when the link expires
+    pub fn verify(
+        &self,
+        path: &str,
+        query: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, AuthError> {
+        let param = |name: &str| {
+            query?
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value)
+        };
+
+        let expires: i64 = param(EXPIRES_PARAM)
+            .and_then(|value| value.parse().ok())
+            .ok_or(AuthError::MissingToken)?;
+        let signature = param(SIGNATURE_PARAM)
+            .and_then(|value| URL_SAFE_NO_PAD.decode(value).ok())
+            .ok_or(AuthError::MissingToken)?;
+
+        // Constant-time comparison; the signature is checked before the
+        // expiry so tampered links never look merely expired
+        self.mac(path, expires)
+            .verify_slice(&signature)
+            .map_err(|_| AuthError::InvalidToken)?;
+
+        if now.timestamp() >= expires {
+            return Err(AuthError::TokenExpired);
+        }
+        Utc.timestamp_opt(expires, 0)
+            .single()
+            .ok_or(AuthError::InvalidToken)
+    }
+
+    fn mac(&self, path: &str, expires: i64) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(path.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires.to_string().as_bytes());
+        mac
+    }
+}
+
+impl fmt::Debug for UrlSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UrlSigner")
+            .field("key", &"[REDACTED]")
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH: &str = "/api/shared/documents/discharge-summary-4821.pdf";
+
+    fn signer() -> UrlSigner {
+        UrlSigner::new(
+            "test-url-signing-key-at-least-32-chars",
+            Duration::from_secs(15 * 60),
+        )
+    }
+
+    fn query(url: &str) -> Option<&str> {
+        url.split_once('?').map(|(_, query)| query)
+    }
+
+    #[test]
+    fn test_signed_url_round_trip() {
+        let now = Utc::now();
+        let signed = signer().sign(PATH, now);
+
+        assert!(signed.url.starts_with(PATH));
+        assert_eq!(signed.expires_at.timestamp(), now.timestamp() + 15 * 60);
+        assert_eq!(
+            signer().verify(PATH, query(&signed.url), now),
+            Ok(signed.expires_at)
+        );
+    }
+
+    #[test]
+    fn test_link_expires() {
+        let now = Utc::now();
+        let signed = signer().sign(PATH, now);
+        let later = now + chrono::Duration::minutes(16);
+
+        assert_eq!(
+            signer().verify(PATH, query(&signed.url), later),
+            Err(AuthError::TokenExpired)
+        );
+    }
+
+    #[test]
+    fn test_tampering_rejected() {
+        let now = Utc::now();
+        let signed = signer().sign(PATH, now);
+        let query = query(&signed.url).unwrap();
+
+        let other_path = "/api/shared/documents/discharge-summary-4822.pdf";
+        assert_eq!(
+            signer().verify(other_path, Some(query), now),
+            Err(AuthError::InvalidToken)
+        );
+
+        let extended = query.replace(
+            &signed.expires_at.timestamp().to_string(),
+            &(signed.expires_at.timestamp() + 3600).to_string(),
+        );
+        assert_eq!(
+            signer().verify(PATH, Some(&extended), now),
+            Err(AuthError::InvalidToken)
+        );
+
+        let other_key = UrlSigner::new("another-url-signing-key-of-32-chars!", signer().ttl());
+        assert_eq!(
+            other_key.verify(PATH, Some(query), now),
+            Err(AuthError::InvalidToken)
+        );
+        assert_eq!(
+            signer().verify(PATH, None, now),
+            Err(AuthError::MissingToken)
+        );
+    }
+}
@@ -0,0 +1,112 @@
+-- Dubai Healthcare Emergency Response System
+-- Initial schema for users, hospitals, staff, patients and vitals
+
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+-- Enum types (must match the sqlx type names in lib-types::enums)
+CREATE TYPE user_role AS ENUM ('er_director', 'paramedic', 'nurse', 'specialist', 'admin');
+CREATE TYPE triage_level AS ENUM ('critical', 'high', 'medium', 'low');
+CREATE TYPE patient_status AS ENUM ('dispatched', 'en_route', 'arrived', 'admitted', 'discharged');
+CREATE TYPE availability_status AS ENUM ('available', 'busy', 'off_duty', 'on_call');
+CREATE TYPE bed_type AS ENUM ('general', 'icu', 'emergency', 'isolation', 'pediatric');
+
+CREATE TABLE hospitals (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name            TEXT NOT NULL,
+    license_number  TEXT NOT NULL UNIQUE,
+    location        TEXT NOT NULL,
+    address         TEXT NOT NULL,
+    phone_number    TEXT NOT NULL,
+    email           TEXT NOT NULL,
+    total_beds      INTEGER NOT NULL CHECK (total_beds >= 0),
+    available_beds  INTEGER NOT NULL CHECK (available_beds >= 0 AND available_beds <= total_beds),
+    specialties     JSONB NOT NULL DEFAULT '[]'::jsonb,
+    hospital_type   TEXT NOT NULL,
+    status          TEXT NOT NULL DEFAULT 'Active',
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE users (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    username        TEXT NOT NULL UNIQUE,
+    email           TEXT NOT NULL UNIQUE,
+    password_hash   TEXT NOT NULL,
+    role            user_role NOT NULL,
+    hospital_id     UUID NOT NULL REFERENCES hospitals(id),
+    first_name      TEXT NOT NULL,
+    last_name       TEXT NOT NULL,
+    phone_number    TEXT,
+    is_active       BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_users_hospital_id ON users(hospital_id);
+
+CREATE TABLE medical_staff (
+    id                   UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id              UUID NOT NULL UNIQUE REFERENCES users(id),
+    hospital_id          UUID NOT NULL REFERENCES hospitals(id),
+    staff_id             TEXT NOT NULL,
+    specialty            TEXT NOT NULL,
+    availability_status  availability_status NOT NULL DEFAULT 'available',
+    license_number       TEXT NOT NULL,
+    certifications       JSONB NOT NULL DEFAULT '[]'::jsonb,
+    shift_schedule       JSONB NOT NULL DEFAULT '{}'::jsonb,
+    department           TEXT NOT NULL,
+    seniority_level      TEXT NOT NULL,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (hospital_id, staff_id)
+);
+
+CREATE INDEX idx_medical_staff_hospital_id ON medical_staff(hospital_id);
+
+CREATE TABLE patients (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    patient_number      TEXT NOT NULL UNIQUE,
+    national_id         TEXT,
+    first_name          TEXT NOT NULL,
+    last_name           TEXT NOT NULL,
+    age                 INTEGER NOT NULL CHECK (age >= 0),
+    gender              TEXT NOT NULL,
+    chief_complaint     TEXT NOT NULL,
+    triage_level        triage_level NOT NULL,
+    status              patient_status NOT NULL DEFAULT 'dispatched',
+    hospital_id         UUID NOT NULL REFERENCES hospitals(id),
+    assigned_staff_id   UUID REFERENCES medical_staff(id),
+    ambulance_id        UUID,
+    bed_id              UUID,
+    emergency_contacts  JSONB NOT NULL DEFAULT '{}'::jsonb,
+    medical_history     JSONB NOT NULL DEFAULT '{}'::jsonb,
+    allergies           JSONB NOT NULL DEFAULT '[]'::jsonb,
+    insurance_info      JSONB NOT NULL DEFAULT '{}'::jsonb,
+    incident_location   TEXT,
+    incident_time       TIMESTAMPTZ,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_patients_hospital_id ON patients(hospital_id);
+CREATE INDEX idx_patients_hospital_status ON patients(hospital_id, status);
+
+CREATE TABLE patient_vitals (
+    id                       UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    patient_id               UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    recorded_by              UUID NOT NULL REFERENCES users(id),
+    systolic_bp              INTEGER,
+    diastolic_bp             INTEGER,
+    heart_rate               INTEGER,
+    oxygen_saturation        INTEGER,
+    temperature              REAL,
+    respiratory_rate         INTEGER,
+    weight                   REAL,
+    device_id                TEXT,
+    additional_measurements  JSONB NOT NULL DEFAULT '{}'::jsonb,
+    notes                    TEXT,
+    recorded_at              TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at               TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_patient_vitals_patient_recorded ON patient_vitals(patient_id, recorded_at DESC);
@@ -0,0 +1,14 @@
+-- Non-human principals authenticating with the client-credentials grant
+
+CREATE TABLE service_accounts (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    client_id           VARCHAR(64) NOT NULL UNIQUE,
+    client_secret_hash  TEXT NOT NULL,
+    name                VARCHAR(255) NOT NULL,
+    hospital_id         UUID REFERENCES hospitals(id),
+    scopes              TEXT[] NOT NULL DEFAULT '{}',
+    is_active           BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at        TIMESTAMPTZ
+);
@@ -0,0 +1,16 @@
+-- Devices users have logged in from, optionally trusted to skip MFA
+
+CREATE TABLE user_devices (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    fingerprint     CHAR(64) NOT NULL,
+    name            VARCHAR(100),
+    user_agent      TEXT,
+    last_ip         VARCHAR(45),
+    trusted_until   TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_seen_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, fingerprint)
+);
+
+CREATE INDEX idx_user_devices_user_last_seen ON user_devices(user_id, last_seen_at DESC);
@@ -0,0 +1,34 @@
+-- Append-only log of authentication events for security review
+
+-- Enum types (must match the sqlx type names in lib-types::enums)
+CREATE TYPE auth_event AS ENUM (
+    'login', 'login_failed', 'account_locked', 'logout', 'token_refresh',
+    'service_token', 'access_denied', 'invalid_token'
+);
+CREATE TYPE auth_outcome AS ENUM ('success', 'failure');
+
+CREATE TABLE auth_audit (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    occurred_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    event           auth_event NOT NULL,
+    outcome         auth_outcome NOT NULL,
+    user_id         UUID, -- No foreign key: entries must outlive the accounts they mention
+    username        TEXT,
+    ip_address      VARCHAR(45),
+    user_agent      TEXT,
+    error_code      TEXT,
+    resource        TEXT
+);
+
+CREATE INDEX idx_auth_audit_occurred_at ON auth_audit(occurred_at DESC);
+CREATE INDEX idx_auth_audit_user_occurred ON auth_audit(user_id, occurred_at DESC);
+
+CREATE FUNCTION reject_auth_audit_change() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'auth_audit is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER auth_audit_append_only
+    BEFORE UPDATE OR DELETE ON auth_audit
+    FOR EACH ROW EXECUTE FUNCTION reject_auth_audit_change();
@@ -0,0 +1,11 @@
+-- Audit columns filled in from the request context by the store layer
+
+-- Staff member who created or last changed the row (NULL for system changes)
+ALTER TABLE users ADD COLUMN created_by UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE users ADD COLUMN updated_by UUID REFERENCES users(id) ON DELETE SET NULL;
+ALTER TABLE service_accounts ADD COLUMN created_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+-- Correlates audit entries with application logs of the same request
+ALTER TABLE auth_audit ADD COLUMN correlation_id TEXT;
+
+CREATE INDEX idx_auth_audit_correlation_id ON auth_audit(correlation_id);
@@ -0,0 +1,23 @@
+-- Ambulance fleet and dispatch status
+
+CREATE TYPE ambulance_status AS ENUM ('available', 'dispatched', 'en_route', 'at_scene', 'transporting');
+
+CREATE TABLE ambulances (
+    id                   UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    call_sign            TEXT NOT NULL UNIQUE,
+    hospital_id          UUID NOT NULL REFERENCES hospitals(id),
+    base_station         TEXT NOT NULL,
+    crew                 UUID[] NOT NULL DEFAULT '{}',
+    status               ambulance_status NOT NULL DEFAULT 'available',
+    latitude             DOUBLE PRECISION CHECK (latitude BETWEEN -90 AND 90),
+    longitude            DOUBLE PRECISION CHECK (longitude BETWEEN -180 AND 180),
+    location_updated_at  TIMESTAMPTZ,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_ambulances_hospital_status ON ambulances(hospital_id, status);
+
+-- Patients were already linked to an ambulance id without a table behind it
+ALTER TABLE patients
+    ADD CONSTRAINT fk_patients_ambulance FOREIGN KEY (ambulance_id) REFERENCES ambulances(id) ON DELETE SET NULL;
@@ -0,0 +1,24 @@
+-- Hospital beds and their occupancy
+
+CREATE TYPE bed_status AS ENUM ('available', 'occupied', 'cleaning', 'out_of_service');
+
+CREATE TABLE beds (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    hospital_id         UUID NOT NULL REFERENCES hospitals(id),
+    ward                TEXT NOT NULL,
+    room                TEXT NOT NULL,
+    bed_number          TEXT NOT NULL,
+    bed_type            bed_type NOT NULL,
+    status              bed_status NOT NULL DEFAULT 'available',
+    current_patient_id  UUID UNIQUE REFERENCES patients(id) ON DELETE SET NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (hospital_id, ward, room, bed_number),
+    CHECK ((status = 'occupied') = (current_patient_id IS NOT NULL))
+);
+
+CREATE INDEX idx_beds_hospital_type_status ON beds(hospital_id, bed_type, status);
+
+-- Patients were already linked to a bed id without a table behind it
+ALTER TABLE patients
+    ADD CONSTRAINT fk_patients_bed FOREIGN KEY (bed_id) REFERENCES beds(id) ON DELETE SET NULL;
@@ -0,0 +1,34 @@
+-- Emergency incidents (e.g. mass-casualty events) linking patients, ambulances and hospitals
+
+CREATE TYPE incident_type AS ENUM (
+    'traffic_collision', 'fire', 'structural_collapse', 'hazardous_materials',
+    'explosion', 'mass_gathering', 'natural_disaster', 'other'
+);
+CREATE TYPE incident_severity AS ENUM ('minor', 'major', 'mass_casualty', 'disaster');
+CREATE TYPE incident_status AS ENUM ('reported', 'active', 'contained', 'closed');
+
+CREATE TABLE emergency_incidents (
+    id                        UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    incident_type             incident_type NOT NULL,
+    severity                  incident_severity NOT NULL,
+    status                    incident_status NOT NULL DEFAULT 'reported',
+    location                  TEXT NOT NULL,
+    latitude                  DOUBLE PRECISION CHECK (latitude BETWEEN -90 AND 90),
+    longitude                 DOUBLE PRECISION CHECK (longitude BETWEEN -180 AND 180),
+    description               TEXT,
+    commander_id              UUID REFERENCES users(id),
+    coordinating_hospital_id  UUID NOT NULL REFERENCES hospitals(id),
+    hospital_ids              UUID[] NOT NULL DEFAULT '{}',
+    ambulance_ids             UUID[] NOT NULL DEFAULT '{}',
+    patient_ids               UUID[] NOT NULL DEFAULT '{}',
+    reported_by               UUID NOT NULL REFERENCES users(id),
+    closed_at                 TIMESTAMPTZ,
+    created_at                TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at                TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK ((status = 'closed') = (closed_at IS NOT NULL))
+);
+
+CREATE INDEX idx_emergency_incidents_status ON emergency_incidents(status, created_at DESC);
+-- Tenancy and "which incident is this patient part of" lookups
+CREATE INDEX idx_emergency_incidents_hospitals ON emergency_incidents USING GIN (hospital_ids);
+CREATE INDEX idx_emergency_incidents_patients ON emergency_incidents USING GIN (patient_ids);
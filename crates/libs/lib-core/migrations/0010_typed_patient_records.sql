@@ -0,0 +1,33 @@
+-- Patient emergency contacts, medical history and insurance move to typed JSON
+-- shapes. Older shapes are still accepted when reading (see the
+-- `from_legacy_json` helpers in lib-types); this brings stored rows in line.
+
+-- Emergency contacts become a list: '{}' meant none, an object was a single contact
+UPDATE patients
+SET emergency_contacts = CASE
+        WHEN emergency_contacts = '{}'::jsonb THEN '[]'::jsonb
+        ELSE jsonb_build_array(emergency_contacts)
+    END
+WHERE jsonb_typeof(emergency_contacts) = 'object';
+
+ALTER TABLE patients
+    ALTER COLUMN emergency_contacts SET DEFAULT '[]'::jsonb,
+    ADD CONSTRAINT chk_patients_emergency_contacts_list CHECK (jsonb_typeof(emergency_contacts) = 'array');
+
+-- Free-text medical history is kept as notes
+UPDATE patients
+SET medical_history = CASE
+        WHEN btrim(medical_history #>> '{}') = '' THEN '{}'::jsonb
+        ELSE jsonb_build_object('notes', medical_history #>> '{}')
+    END
+WHERE jsonb_typeof(medical_history) = 'string';
+
+ALTER TABLE patients
+    ADD CONSTRAINT chk_patients_medical_history_object CHECK (jsonb_typeof(medical_history) = 'object');
+
+-- Unknown insurance is NULL rather than an empty object
+ALTER TABLE patients
+    ALTER COLUMN insurance_info DROP NOT NULL,
+    ALTER COLUMN insurance_info DROP DEFAULT;
+
+UPDATE patients SET insurance_info = NULL WHERE insurance_info IN ('{}'::jsonb, 'null'::jsonb);
@@ -0,0 +1,21 @@
+-- Medications prescribed to patients, checked against their allergies before insert
+
+CREATE TYPE medication_route AS ENUM (
+    'oral', 'sublingual', 'intravenous', 'intramuscular', 'subcutaneous',
+    'inhaled', 'topical', 'rectal'
+);
+
+CREATE TABLE medications (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    patient_id      UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id     UUID NOT NULL REFERENCES hospitals(id),
+    name            TEXT NOT NULL,
+    dosage          TEXT NOT NULL,
+    route           medication_route NOT NULL,
+    frequency       TEXT NOT NULL,
+    notes           TEXT,
+    prescribed_by   UUID NOT NULL REFERENCES users(id),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_medications_patient ON medications(patient_id, created_at DESC);
@@ -0,0 +1,9 @@
+-- Branching patient workflow: triage, treatment and results in the department,
+-- and every way a visit can end
+
+ALTER TYPE patient_status ADD VALUE IF NOT EXISTS 'in_triage' AFTER 'arrived';
+ALTER TYPE patient_status ADD VALUE IF NOT EXISTS 'in_treatment' AFTER 'in_triage';
+ALTER TYPE patient_status ADD VALUE IF NOT EXISTS 'awaiting_results' AFTER 'in_treatment';
+ALTER TYPE patient_status ADD VALUE IF NOT EXISTS 'transferred' AFTER 'discharged';
+ALTER TYPE patient_status ADD VALUE IF NOT EXISTS 'deceased' AFTER 'transferred';
+ALTER TYPE patient_status ADD VALUE IF NOT EXISTS 'left_without_being_seen' AFTER 'deceased';
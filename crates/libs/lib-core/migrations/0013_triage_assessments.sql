@@ -0,0 +1,17 @@
+-- Five-level ESI triage scale and the history of every (re)triage.
+-- patients.triage_level keeps the latest level.
+
+ALTER TYPE triage_level ADD VALUE IF NOT EXISTS 'non_urgent' AFTER 'low';
+
+CREATE TABLE triage_assessments (
+    id              UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    patient_id      UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id     UUID NOT NULL REFERENCES hospitals(id),
+    triage_level    triage_level NOT NULL,
+    previous_level  triage_level,
+    rationale       TEXT NOT NULL,
+    assessed_by     UUID NOT NULL REFERENCES users(id),
+    assessed_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_triage_assessments_patient ON triage_assessments(patient_id, assessed_at);
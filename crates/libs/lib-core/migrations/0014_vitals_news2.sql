@@ -0,0 +1,7 @@
+-- Inputs of the NEWS2 early warning score missing from recorded vitals
+
+CREATE TYPE consciousness_level AS ENUM ('alert', 'new_confusion', 'voice', 'pain', 'unresponsive');
+
+ALTER TABLE patient_vitals
+    ADD COLUMN consciousness_level     consciousness_level,
+    ADD COLUMN on_supplemental_oxygen  BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,8 @@
+-- Neurological, pain and glucose measurements on recorded vitals
+
+ALTER TABLE patient_vitals
+    ADD COLUMN gcs_eye        INTEGER CHECK (gcs_eye BETWEEN 1 AND 4),
+    ADD COLUMN gcs_verbal     INTEGER CHECK (gcs_verbal BETWEEN 1 AND 5),
+    ADD COLUMN gcs_motor      INTEGER CHECK (gcs_motor BETWEEN 1 AND 6),
+    ADD COLUMN pain_score     INTEGER CHECK (pain_score BETWEEN 0 AND 10),
+    ADD COLUMN blood_glucose  REAL;
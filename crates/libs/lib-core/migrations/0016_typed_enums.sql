@@ -0,0 +1,29 @@
+-- Free-text gender, hospital type/status and seniority columns become enums.
+-- Known spellings are normalised; any other value makes the cast fail so it
+-- can be fixed by hand rather than guessed.
+
+CREATE TYPE gender AS ENUM ('male', 'female', 'other');
+CREATE TYPE hospital_type AS ENUM ('public', 'private', 'specialized');
+CREATE TYPE hospital_status AS ENUM ('active', 'maintenance', 'emergency_only');
+CREATE TYPE seniority_level AS ENUM ('junior', 'senior', 'consultant', 'director');
+
+ALTER TABLE patients
+    ALTER COLUMN gender TYPE gender USING (
+        CASE lower(trim(gender))
+            WHEN 'm' THEN 'male'
+            WHEN 'f' THEN 'female'
+            ELSE lower(trim(gender))
+        END
+    )::gender;
+
+ALTER TABLE hospitals
+    ALTER COLUMN hospital_type TYPE hospital_type
+        USING (lower(trim(hospital_type)))::hospital_type,
+    ALTER COLUMN status DROP DEFAULT,
+    ALTER COLUMN status TYPE hospital_status
+        USING (replace(lower(trim(status)), ' ', '_'))::hospital_status,
+    ALTER COLUMN status SET DEFAULT 'active';
+
+ALTER TABLE medical_staff
+    ALTER COLUMN seniority_level TYPE seniority_level
+        USING (lower(trim(seniority_level)))::seniority_level;
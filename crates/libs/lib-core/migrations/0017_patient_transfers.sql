@@ -0,0 +1,28 @@
+-- Inter-hospital patient transfers, requested by the origin and accepted,
+-- rejected and completed by the destination
+
+CREATE TYPE transfer_status AS ENUM ('requested', 'accepted', 'rejected', 'completed');
+
+CREATE TABLE patient_transfers (
+    id                      UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    patient_id              UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    origin_hospital_id      UUID NOT NULL REFERENCES hospitals(id),
+    destination_hospital_id UUID NOT NULL REFERENCES hospitals(id),
+    reason                  TEXT NOT NULL,
+    status                  transfer_status NOT NULL DEFAULT 'requested',
+    requested_by            UUID NOT NULL REFERENCES users(id),
+    responded_by            UUID REFERENCES users(id),
+    rejection_reason        TEXT,
+    ambulance_id            UUID REFERENCES ambulances(id) ON DELETE SET NULL,
+    requested_at            TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    responded_at            TIMESTAMPTZ,
+    completed_at            TIMESTAMPTZ,
+    updated_at              TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (origin_hospital_id <> destination_hospital_id)
+);
+
+-- A patient can only be on the way to one hospital at a time
+CREATE UNIQUE INDEX idx_patient_transfers_open ON patient_transfers(patient_id)
+    WHERE status IN ('requested', 'accepted');
+CREATE INDEX idx_patient_transfers_origin ON patient_transfers(origin_hospital_id, status);
+CREATE INDEX idx_patient_transfers_destination ON patient_transfers(destination_hospital_id, status);
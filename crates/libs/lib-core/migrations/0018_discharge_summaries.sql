@@ -0,0 +1,21 @@
+-- Discharge summaries, written when a patient is discharged
+
+CREATE TYPE discharge_disposition AS ENUM (
+    'home', 'home_with_care', 'nursing_facility', 'rehabilitation', 'against_medical_advice'
+);
+
+CREATE TABLE discharge_summaries (
+    id                  UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    patient_id          UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id         UUID NOT NULL REFERENCES hospitals(id),
+    diagnosis           TEXT NOT NULL,
+    disposition         discharge_disposition NOT NULL,
+    instructions        TEXT NOT NULL,
+    follow_up           TEXT,
+    guardian_consent_by TEXT,
+    document            TEXT NOT NULL,
+    discharged_by       UUID NOT NULL REFERENCES users(id),
+    discharged_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_discharge_summaries_patient ON discharge_summaries(patient_id, discharged_at DESC);
@@ -0,0 +1,15 @@
+-- Hospital and incident locations are stored as "latitude,longitude" text
+-- and read back as points. Whitespace is normalised; any other value makes
+-- the constraint fail so it can be fixed by hand rather than guessed.
+
+UPDATE hospitals SET location = regexp_replace(location, '\s', '', 'g');
+UPDATE patients SET incident_location = regexp_replace(incident_location, '\s', '', 'g')
+    WHERE incident_location IS NOT NULL;
+
+ALTER TABLE hospitals
+    ADD CONSTRAINT hospitals_location_point
+        CHECK (location ~ '^-?[0-9]+(\.[0-9]+)?,-?[0-9]+(\.[0-9]+)?$');
+
+ALTER TABLE patients
+    ADD CONSTRAINT patients_incident_location_point
+        CHECK (incident_location ~ '^-?[0-9]+(\.[0-9]+)?,-?[0-9]+(\.[0-9]+)?$');
@@ -0,0 +1,17 @@
+-- Blood type, do-not-resuscitate orders and isolation precautions on patients
+
+CREATE TYPE blood_type AS ENUM (
+    'a_positive', 'a_negative', 'b_positive', 'b_negative',
+    'ab_positive', 'ab_negative', 'o_positive', 'o_negative'
+);
+
+CREATE TYPE isolation_precaution AS ENUM (
+    'contact', 'enteric_contact', 'droplet', 'airborne', 'protective'
+);
+
+ALTER TABLE patients
+    ADD COLUMN blood_type            blood_type,
+    ADD COLUMN dnr                   BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN dnr_recorded_by       UUID REFERENCES users(id),
+    ADD COLUMN dnr_recorded_at       TIMESTAMPTZ,
+    ADD COLUMN isolation_precautions isolation_precaution[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,25 @@
+-- Patients can be known by several identifiers: Emirates ID, passport, GCC ID,
+-- hospital MRN or a temporary alias for an unidentified patient. national_id
+-- stays as a mirror of the Emirates ID for existing queries and reports.
+
+-- Store Emirates IDs in one form so they can be matched as identifiers
+UPDATE patients
+SET national_id = regexp_replace(
+        regexp_replace(national_id, '\D', '', 'g'),
+        '^(\d{3})(\d{4})(\d{7})(\d)$', '\1-\2-\3-\4')
+WHERE regexp_replace(national_id, '\D', '', 'g') ~ '^\d{15}$';
+
+UPDATE patients SET national_id = NULL WHERE trim(national_id) = '';
+
+ALTER TABLE patients ADD COLUMN identifiers JSONB NOT NULL DEFAULT '[]';
+
+UPDATE patients
+SET identifiers = jsonb_build_array(
+        jsonb_build_object('scheme', 'emirates_id', 'value', trim(national_id)))
+WHERE national_id IS NOT NULL;
+
+-- Serves `identifiers @> '[{"scheme": ..., "value": ...}]'` lookups.
+-- Uniqueness cannot be expressed as a constraint over array elements and is
+-- checked by the store; duplicates already in the table are left to be
+-- merged by hand.
+CREATE INDEX idx_patients_identifiers ON patients USING GIN (identifiers jsonb_path_ops);
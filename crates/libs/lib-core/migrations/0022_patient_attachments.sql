@@ -0,0 +1,20 @@
+-- Files attached to patient records (scene photos, ECGs, consent forms,
+-- reports). The content lives in object storage under storage_key.
+
+CREATE TYPE attachment_kind AS ENUM ('photo', 'ecg', 'consent_form', 'report');
+
+CREATE TABLE patient_attachments (
+    id           UUID PRIMARY KEY,
+    patient_id   UUID NOT NULL REFERENCES patients(id),
+    hospital_id  UUID NOT NULL REFERENCES hospitals(id),
+    kind         attachment_kind NOT NULL,
+    file_name    VARCHAR(255) NOT NULL,
+    content_type VARCHAR(100) NOT NULL,
+    size_bytes   BIGINT NOT NULL CHECK (size_bytes > 0),
+    checksum     CHAR(64) NOT NULL,
+    storage_key  TEXT NOT NULL UNIQUE,
+    uploaded_by  UUID NOT NULL REFERENCES users(id),
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_patient_attachments_patient ON patient_attachments(patient_id, created_at);
@@ -0,0 +1,43 @@
+-- Lab orders and the analyte results reported against them. Critical
+-- results stay unacknowledged until a clinician has been told.
+
+CREATE TYPE lab_order_status AS ENUM ('ordered', 'resulted', 'cancelled');
+
+CREATE TYPE abnormal_flag AS ENUM ('normal', 'low', 'high', 'critical_low', 'critical_high');
+
+CREATE TABLE lab_orders (
+    id          UUID PRIMARY KEY,
+    patient_id  UUID NOT NULL REFERENCES patients(id),
+    hospital_id UUID NOT NULL REFERENCES hospitals(id),
+    panel       VARCHAR(100) NOT NULL,
+    status      lab_order_status NOT NULL DEFAULT 'ordered',
+    notes       TEXT,
+    ordered_by  UUID NOT NULL REFERENCES users(id),
+    ordered_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resulted_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_lab_orders_patient ON lab_orders(patient_id, ordered_at);
+
+CREATE TABLE lab_results (
+    id              UUID PRIMARY KEY,
+    order_id        UUID NOT NULL REFERENCES lab_orders(id),
+    patient_id      UUID NOT NULL REFERENCES patients(id),
+    hospital_id     UUID NOT NULL REFERENCES hospitals(id),
+    analyte         VARCHAR(50) NOT NULL,
+    value           DOUBLE PRECISION NOT NULL,
+    units           VARCHAR(20) NOT NULL,
+    reference_low   DOUBLE PRECISION,
+    reference_high  DOUBLE PRECISION,
+    flag            abnormal_flag NOT NULL,
+    resulted_at     TIMESTAMPTZ NOT NULL,
+    acknowledged_by UUID REFERENCES users(id),
+    acknowledged_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_lab_results_patient ON lab_results(patient_id, resulted_at);
+CREATE INDEX idx_lab_results_order ON lab_results(order_id);
+
+-- Critical results still waiting for a clinician
+CREATE INDEX idx_lab_results_unacknowledged ON lab_results(hospital_id, resulted_at)
+    WHERE flag IN ('critical_low', 'critical_high') AND acknowledged_at IS NULL;
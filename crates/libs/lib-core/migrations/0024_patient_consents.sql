@@ -0,0 +1,34 @@
+-- Documented consent for patient care. Withdrawn consents are kept with
+-- the withdrawal recorded alongside.
+
+CREATE TYPE consent_type AS ENUM (
+    'treatment', 'procedure', 'blood_transfusion', 'transfer', 'discharge', 'data_sharing'
+);
+
+CREATE TYPE consent_method AS ENUM (
+    'written', 'electronic', 'verbal', 'telephone', 'emergency_implied'
+);
+
+CREATE TYPE contact_relationship AS ENUM (
+    'spouse', 'parent', 'child', 'sibling', 'guardian', 'relative', 'friend', 'caregiver', 'other'
+);
+
+CREATE TABLE patient_consents (
+    id                UUID PRIMARY KEY,
+    patient_id        UUID NOT NULL REFERENCES patients(id),
+    hospital_id       UUID NOT NULL REFERENCES hospitals(id),
+    consent_type      consent_type NOT NULL,
+    granted_by        VARCHAR(100) NOT NULL,
+    relationship      contact_relationship, -- NULL when the patient consented for themselves
+    scope             TEXT,
+    method            consent_method NOT NULL,
+    witness           VARCHAR(100),
+    expires_at        TIMESTAMPTZ,
+    recorded_by       UUID NOT NULL REFERENCES users(id),
+    recorded_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    withdrawn_by      UUID REFERENCES users(id),
+    withdrawn_at      TIMESTAMPTZ,
+    withdrawal_reason TEXT
+);
+
+CREATE INDEX idx_patient_consents_patient ON patient_consents(patient_id, recorded_at);
@@ -0,0 +1,25 @@
+-- Ambulance-to-ED handovers in the ATMIST format. A handover is pending
+-- until receiving staff acknowledge it.
+
+CREATE TABLE handover_reports (
+    id               UUID PRIMARY KEY,
+    patient_id       UUID NOT NULL REFERENCES patients(id),
+    hospital_id      UUID NOT NULL REFERENCES hospitals(id),
+    ambulance_id     UUID NOT NULL REFERENCES ambulances(id),
+    age_years        INTEGER NOT NULL,
+    time_of_incident TIMESTAMPTZ,
+    mechanism        TEXT NOT NULL,
+    injuries         TEXT NOT NULL,
+    signs            TEXT NOT NULL,
+    treatment        TEXT NOT NULL,
+    created_by       UUID NOT NULL REFERENCES users(id),
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    acknowledged_by  UUID REFERENCES users(id),
+    acknowledged_at  TIMESTAMPTZ
+);
+
+CREATE INDEX idx_handover_reports_patient ON handover_reports(patient_id, created_at);
+
+-- The pending-handover queue of each emergency department
+CREATE INDEX idx_handover_reports_pending ON handover_reports(hospital_id, created_at)
+    WHERE acknowledged_at IS NULL;
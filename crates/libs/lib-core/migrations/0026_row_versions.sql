@@ -0,0 +1,7 @@
+-- Row versions for optimistic concurrency control. Every write to a row
+-- bumps its version; an edit made against an older version is rejected
+-- instead of overwriting someone else's change.
+
+ALTER TABLE patients ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE hospitals ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+ALTER TABLE beds ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
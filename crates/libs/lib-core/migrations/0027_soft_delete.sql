@@ -0,0 +1,10 @@
+-- Soft delete for staff and hospital records. Deleted rows stay in place,
+-- hidden from normal queries, so a mistaken removal can be restored.
+
+ALTER TABLE hospitals
+    ADD COLUMN deleted_at TIMESTAMPTZ,
+    ADD COLUMN deleted_by UUID REFERENCES users(id);
+
+ALTER TABLE medical_staff
+    ADD COLUMN deleted_at TIMESTAMPTZ,
+    ADD COLUMN deleted_by UUID REFERENCES users(id);
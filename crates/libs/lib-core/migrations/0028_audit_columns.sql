@@ -0,0 +1,18 @@
+-- Who created and last changed each core record. Filled in by the store
+-- layer from the authenticated user; NULL for rows written by the system.
+
+ALTER TABLE patients
+    ADD COLUMN created_by UUID REFERENCES users(id),
+    ADD COLUMN updated_by UUID REFERENCES users(id);
+
+ALTER TABLE hospitals
+    ADD COLUMN created_by UUID REFERENCES users(id),
+    ADD COLUMN updated_by UUID REFERENCES users(id);
+
+ALTER TABLE beds
+    ADD COLUMN created_by UUID REFERENCES users(id),
+    ADD COLUMN updated_by UUID REFERENCES users(id);
+
+ALTER TABLE medical_staff
+    ADD COLUMN created_by UUID REFERENCES users(id),
+    ADD COLUMN updated_by UUID REFERENCES users(id);
@@ -0,0 +1,43 @@
+-- Full-text and fuzzy patient search.
+--
+-- Names reach the system spelled many ways: "Mohammed", "Muhammad" and
+-- "محمد" are the same person. patient_name_key reduces a name to a
+-- consonant skeleton that most of those spellings share, and trigram
+-- similarity on the skeleton absorbs the rest.
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- The skeleton of a name, Latin or Arabic script: the article "al"/"ال"
+-- dropped, Latin digraphs and Arabic letters mapped to one Latin consonant
+-- each, vowels (long Arabic vowels, hamza and diacritics included) removed
+-- and doubled letters collapsed. "Mohammed Al Mansoori", "Muhammad
+-- Almansuri" and "محمد المنصوري" all come out close to "mhmd mnsr".
+CREATE FUNCTION patient_name_key(name TEXT) RETURNS TEXT
+LANGUAGE SQL IMMUTABLE PARALLEL SAFE STRICT AS $$
+    SELECT btrim(regexp_replace(regexp_replace(
+        translate(
+            regexp_replace(
+                regexp_replace(
+                    regexp_replace(lower(name), '(^|[\s-])(al|el)[\s-]+', '\1', 'g'),
+                    '(^|\s)ال', '\1', 'g'),
+                '([kstdgz])h', '\1', 'g'),
+            'qc' || 'بتثجحخدذرزسشصضطظغفقكلمنه'
+                || 'aeiouyw' || 'عاأإآىةءؤئوي'
+                || U&'\064B\064C\064D\064E\064F\0650\0651\0652\0640',
+            'kk' || 'bttjhkddrzsssdtzgfkklmnh'),
+        '(.)\1+', '\1', 'g'), '[^a-z0-9]+', ' ', 'g'))
+$$;
+
+ALTER TABLE patients
+    ADD COLUMN name_key TEXT GENERATED ALWAYS AS (
+        patient_name_key(first_name || ' ' || last_name)
+    ) STORED,
+    -- Names and numbers are matched as written; complaints are stemmed
+    ADD COLUMN search_document TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple', first_name || ' ' || last_name), 'A')
+            || setweight(to_tsvector('simple', patient_number), 'A')
+            || setweight(to_tsvector('english', chief_complaint), 'B')
+    ) STORED;
+
+CREATE INDEX idx_patients_search_document ON patients USING GIN (search_document);
+CREATE INDEX idx_patients_name_key ON patients USING GIN (name_key gin_trgm_ops);
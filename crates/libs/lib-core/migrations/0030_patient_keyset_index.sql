@@ -0,0 +1,7 @@
+-- Patient lists page newest first on (created_at, id). Each index serves
+-- the keyset condition and the order directly, so a page deep into the
+-- list is read as cheaply as the first: within a hospital for its staff,
+-- and across hospitals for unscoped callers.
+
+CREATE INDEX idx_patients_hospital_created ON patients(hospital_id, created_at, id);
+CREATE INDEX idx_patients_created ON patients(created_at, id);
@@ -0,0 +1,22 @@
+-- Transactional outbox. Events are inserted in the same transaction as the
+-- change they describe, so they commit or roll back with it, and the
+-- server's dispatcher delivers them afterwards. A message is claimed by
+-- moving available_at past a lease; a dispatcher that dies mid-delivery
+-- leaves it to be claimed again once the lease runs out, so delivery is at
+-- least once.
+
+CREATE TABLE outbox (
+    id UUID PRIMARY KEY,
+    topic VARCHAR(100) NOT NULL,
+    aggregate_id UUID NOT NULL,
+    hospital_id UUID REFERENCES hospitals(id),
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    available_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    dispatched_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_outbox_pending ON outbox(available_at) WHERE dispatched_at IS NULL;
+CREATE INDEX idx_outbox_aggregate ON outbox(aggregate_id, created_at);
@@ -0,0 +1,59 @@
+-- Row-level security keeping each hospital's clinical data to itself. The
+-- store scopes every connection it hands out through the
+-- app.tenant_hospital_id setting: staff get their hospital, while admins
+-- and system work get an empty setting and see every row. A query that
+-- forgets its hospital filter then finds nothing of other hospitals instead
+-- of leaking it.
+--
+-- FORCE applies the policies to the table owner, which is usually the role
+-- the server connects as. Superusers and roles with BYPASSRLS skip them, so
+-- the server must not connect as one for the policies to hold.
+
+CREATE FUNCTION app_tenant_hospital_id() RETURNS UUID
+LANGUAGE SQL STABLE PARALLEL SAFE
+AS $$
+    SELECT NULLIF(current_setting('app.tenant_hospital_id', TRUE), '')::UUID
+$$;
+
+-- Tables with a hospital_id of their own
+DO $$
+DECLARE
+    tenant_table TEXT;
+BEGIN
+    FOREACH tenant_table IN ARRAY ARRAY[
+        'patients', 'beds', 'medical_staff', 'medications', 'triage_assessments',
+        'discharge_summaries', 'patient_attachments', 'lab_orders', 'lab_results',
+        'patient_consents', 'handover_reports'
+    ] LOOP
+        EXECUTE format('ALTER TABLE %I ENABLE ROW LEVEL SECURITY', tenant_table);
+        EXECUTE format('ALTER TABLE %I FORCE ROW LEVEL SECURITY', tenant_table);
+        EXECUTE format(
+            'CREATE POLICY tenant_isolation ON %I USING '
+            '(app_tenant_hospital_id() IS NULL OR hospital_id = app_tenant_hospital_id())',
+            tenant_table
+        );
+    END LOOP;
+END $$;
+
+-- A transfer belongs to both ends
+ALTER TABLE patient_transfers ENABLE ROW LEVEL SECURITY;
+ALTER TABLE patient_transfers FORCE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON patient_transfers USING (
+    app_tenant_hospital_id() IS NULL
+    OR app_tenant_hospital_id() IN (origin_hospital_id, destination_hospital_id)
+);
+
+-- An incident belongs to every receiving hospital
+ALTER TABLE emergency_incidents ENABLE ROW LEVEL SECURITY;
+ALTER TABLE emergency_incidents FORCE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON emergency_incidents USING (
+    app_tenant_hospital_id() IS NULL OR app_tenant_hospital_id() = ANY(hospital_ids)
+);
+
+-- Vitals follow their patient, whose own policy filters the subquery
+ALTER TABLE patient_vitals ENABLE ROW LEVEL SECURITY;
+ALTER TABLE patient_vitals FORCE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON patient_vitals USING (
+    app_tenant_hospital_id() IS NULL
+    OR EXISTS (SELECT 1 FROM patients WHERE patients.id = patient_vitals.patient_id)
+);
@@ -0,0 +1,18 @@
+-- Per-hospital daily counters behind generated patient numbers such as
+-- DHA001-20250611-0042. A counter row is created by the first registration
+-- of the day and bumped in place by the rest; the row lock taken by the
+-- upsert keeps concurrent registrations from drawing the same number, and
+-- rolls the counter back with a registration that fails.
+
+CREATE TABLE patient_number_sequences (
+    hospital_id UUID NOT NULL REFERENCES hospitals(id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    last_value INTEGER NOT NULL CHECK (last_value > 0),
+    PRIMARY KEY (hospital_id, day)
+);
+
+ALTER TABLE patient_number_sequences ENABLE ROW LEVEL SECURITY;
+ALTER TABLE patient_number_sequences FORCE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON patient_number_sequences USING (
+    app_tenant_hospital_id() IS NULL OR hospital_id = app_tenant_hospital_id()
+);
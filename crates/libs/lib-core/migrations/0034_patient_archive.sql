@@ -0,0 +1,29 @@
+-- Archive of patients whose visit ended long enough ago to leave the live
+-- tables. The retention job moves each patient here as one JSON document
+-- holding the patient row and the rows of every table that refers to it,
+-- then deletes them from the live tables. Attachment contents stay in
+-- object storage under the storage keys kept in the document.
+
+CREATE TABLE archived_patients (
+    id             UUID PRIMARY KEY,
+    hospital_id    UUID NOT NULL REFERENCES hospitals(id),
+    patient_number TEXT NOT NULL,
+    status         patient_status NOT NULL,
+    left_care_at   TIMESTAMPTZ NOT NULL, -- Last change to the patient before archiving
+    archived_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    record         JSONB NOT NULL
+);
+
+CREATE INDEX idx_archived_patients_hospital ON archived_patients(hospital_id, left_care_at);
+CREATE INDEX idx_archived_patients_number ON archived_patients(patient_number);
+
+ALTER TABLE archived_patients ENABLE ROW LEVEL SECURITY;
+ALTER TABLE archived_patients FORCE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON archived_patients USING (
+    app_tenant_hospital_id() IS NULL OR hospital_id = app_tenant_hospital_id()
+);
+
+-- Finding patients due for archiving and vitals due for purging
+CREATE INDEX idx_patients_left_care ON patients(updated_at)
+    WHERE status IN ('discharged', 'transferred', 'deceased', 'left_without_being_seen');
+CREATE INDEX idx_patient_vitals_recorded_at ON patient_vitals(recorded_at);
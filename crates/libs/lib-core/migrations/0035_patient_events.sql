@@ -0,0 +1,30 @@
+-- Log of changes to patients: status transitions, triage changes and
+-- staff, ambulance and bed assignments, each with who made it and why.
+-- Rows are written in the transaction that makes the change.
+
+CREATE TYPE patient_event_kind AS ENUM (
+    'status_changed', 'triage_changed', 'staff_assigned', 'ambulance_assigned', 'bed_assigned'
+);
+
+CREATE TABLE patient_events (
+    id          UUID PRIMARY KEY,
+    patient_id  UUID NOT NULL REFERENCES patients(id) ON DELETE CASCADE,
+    hospital_id UUID NOT NULL REFERENCES hospitals(id), -- Where the change was made
+    kind        patient_event_kind NOT NULL,
+    from_value  TEXT,
+    to_value    TEXT,
+    reason      TEXT,
+    actor_id    UUID REFERENCES users(id), -- NULL for changes made by the system
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_patient_events_patient ON patient_events(patient_id, occurred_at);
+
+-- Events follow their patient, so a receiving hospital sees the history
+-- from before the transfer
+ALTER TABLE patient_events ENABLE ROW LEVEL SECURITY;
+ALTER TABLE patient_events FORCE ROW LEVEL SECURITY;
+CREATE POLICY tenant_isolation ON patient_events USING (
+    app_tenant_hospital_id() IS NULL
+    OR EXISTS (SELECT 1 FROM patients WHERE patients.id = patient_events.patient_id)
+);
@@ -0,0 +1,16 @@
+-- Ambulance missions and GPS tracking. A dispatched ambulance records where
+-- it was sent and, for a major incident, which one; the mission is cleared
+-- when the ambulance is available again. The last position is bucketed by
+-- geohash cell for nearest-unit searches, filled in on the next GPS update.
+
+ALTER TABLE ambulances
+    ADD COLUMN incident_id UUID REFERENCES emergency_incidents(id) ON DELETE SET NULL,
+    ADD COLUMN destination TEXT,
+    ADD COLUMN destination_latitude DOUBLE PRECISION
+        CHECK (destination_latitude BETWEEN -90 AND 90),
+    ADD COLUMN destination_longitude DOUBLE PRECISION
+        CHECK (destination_longitude BETWEEN -180 AND 180),
+    ADD COLUMN position_geohash TEXT;
+
+CREATE INDEX idx_ambulances_position ON ambulances(position_geohash)
+    WHERE position_geohash IS NOT NULL;
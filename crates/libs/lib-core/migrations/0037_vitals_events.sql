@@ -0,0 +1,5 @@
+-- Recorded vital signs are logged with the patient's other events, so
+-- clients following a patient's event stream see new readings. The event
+-- refers to the reading by id in to_value.
+
+ALTER TYPE patient_event_kind ADD VALUE 'vitals_recorded';
@@ -0,0 +1,6 @@
+-- The request that enqueued each outbox message, so its delivery can be
+-- traced back to it. Empty for messages enqueued before this migration.
+
+ALTER TABLE outbox
+    ADD COLUMN request_id VARCHAR(128),
+    ADD COLUMN correlation_id VARCHAR(128);
@@ -1,8 +1,26 @@
 use anyhow::{Context, Result};
+use lib_auth::jwt::{JwtAlgorithm, JwtKeyRing};
+use lib_auth::lockout::LockoutPolicy;
+use lib_auth::middleware::IpAllowlists;
+use lib_auth::password::{Argon2Params, PasswordHasher, PasswordPolicy};
+use lib_auth::rbac::{BreakGlassPolicy, DelegationPolicy};
+use lib_auth::session::SessionLimits;
+use lib_auth::signed_url::UrlSigner;
+use lib_utils::location::{EtaEstimator, ZoneRegistry};
+use lib_utils::reference_ranges::ReferenceRanges;
+use redis::aio::ConnectionManagerConfig;
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use super::database::DatabaseConfig;
+use super::layered::{read_layered, EnvOverrides};
+use super::rate_limit::RateLimitConfig;
+use super::retention::RetentionConfig;
+use super::secrets::{ResolvedSecrets, SecretsConfig};
+use crate::storage::S3ObjectStorage;
+use crate::store::RedisPool;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -10,8 +28,15 @@ pub struct AppConfig {
     pub database: DatabaseConfig,
     pub jwt: JwtConfig,
     pub redis: RedisConfig,
+    pub security: SecurityConfig,
     pub logging: LoggingConfig,
     pub healthcare: HealthcareConfig,
+    pub storage: StorageConfig,
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    pub rate_limit: RateLimitConfig,
     pub environment: Environment,
 }
 
@@ -23,6 +48,32 @@ pub struct ServerConfig {
     pub request_timeout_seconds: u64,
     pub max_request_size_mb: usize,
     pub enable_metrics: bool,
+    #[serde(default)]
+    pub maintenance_mode: bool, // Reject changes with 503 while reads keep working
+    #[serde(default)]
+    pub tls: Option<TlsConfig>, // Serve HTTPS (HTTP/2 and HTTP/1.1) when set
+    // Networks (CIDR) each role may connect from; empty means unrestricted.
+    // Defaulted because configuration layers drop empty lists.
+    #[serde(default)]
+    pub ip_allowlist_er_director: Vec<String>,
+    #[serde(default)]
+    pub ip_allowlist_paramedic: Vec<String>,
+    #[serde(default)]
+    pub ip_allowlist_nurse: Vec<String>,
+    #[serde(default)]
+    pub ip_allowlist_specialist: Vec<String>,
+    #[serde(default)]
+    pub ip_allowlist_admin: Vec<String>,
+}
+
+/// Certificate and key for HTTPS. The files are re-read when they change,
+/// so certificates can be rotated without a restart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    pub cert_path: String, // PEM certificate chain, leaf first
+    pub key_path: String,  // PEM private key (PKCS#8, PKCS#1 or SEC1)
+    #[serde(default)]
+    pub client_ca_path: Option<String>, // Require client certificates issued by this CA (mTLS)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,16 +81,72 @@ pub struct JwtConfig {
     pub secret: String,
     pub expiration_seconds: i64,
     pub refresh_expiration_seconds: i64,
+    pub service_expiration_seconds: i64, // Lifetime of service account tokens
     pub issuer: String,
     pub audience: String,
+    pub algorithm: JwtAlgorithm,
+    pub key_dir: Option<String>, // Directory with <kid>.private.pem / <kid>.public.pem files
+    pub active_kid: Option<String>, // Key id used to sign new tokens
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RedisConfig {
-    pub url: String,
+    pub url: String, // With Sentinel, only the credentials, database and scheme are used
+    #[serde(default)]
+    pub topology: RedisTopology,
+    #[serde(default)]
+    pub nodes: Vec<String>, // Sentinel addresses, or cluster seed nodes
+    #[serde(default)]
+    pub sentinel_master: Option<String>, // Master name monitored by the sentinels
     pub max_connections: u32,
+    #[serde(default)]
+    pub pool_size: usize, // Multiplexed connections shared by all requests
     pub connection_timeout_seconds: u64,
     pub command_timeout_seconds: u64,
+    #[serde(default)]
+    pub hospital_cache_ttl_seconds: u64, // Hospital lists served from Redis; 0 disables
+    #[serde(default)]
+    pub idempotency_ttl_seconds: u64, // Responses replayed for Idempotency-Key; 0 disables
+}
+
+/// How Redis is deployed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedisTopology {
+    #[default]
+    Standalone,
+    Sentinel, // A master and replicas, with sentinels promoting a replica on failure
+    Cluster,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityConfig {
+    pub max_failed_logins: u32,
+    pub failed_login_window_minutes: u32,
+    pub lockout_minutes: u32,
+    pub login_backoff_base_seconds: u64,
+    pub login_backoff_max_seconds: u64,
+    pub password_min_length: usize,
+    pub password_require_uppercase: bool,
+    pub password_require_lowercase: bool,
+    pub password_require_digit: bool,
+    pub password_require_symbol: bool,
+    pub password_history_size: usize,
+    pub password_deny_list_file: Option<String>, // Extra denied passwords, one per line
+    pub argon2_memory_kib: u32,
+    pub argon2_iterations: u32,
+    pub argon2_parallelism: u32,
+    pub break_glass_max_minutes: u32,
+    pub delegation_max_hours: u32,
+    pub url_signing_key: Option<String>, // Enables signed document links when set
+    pub signed_url_ttl_minutes: u32,
+    pub step_up_max_age_minutes: u64,
+    pub trusted_device_days: u32,
+    pub max_sessions_er_director: usize,
+    pub max_sessions_paramedic: usize,
+    pub max_sessions_nurse: usize,
+    pub max_sessions_specialist: usize,
+    pub max_sessions_admin: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,8 +161,11 @@ pub struct LoggingConfig {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LogFormat {
+    #[serde(alias = "json")]
     Json,
+    #[serde(alias = "pretty")]
     Pretty,
+    #[serde(alias = "compact")]
     Compact,
 }
 
@@ -70,6 +180,30 @@ pub struct HealthcareConfig {
     pub max_patient_age: u16,
     pub default_session_timeout_minutes: u32,
     pub enable_triage_ai: bool,
+    pub shared_documents_dir: Option<String>, // Documents that can be shared through signed links
+    #[serde(default)]
+    pub notification_webhooks: Vec<String>, // Endpoints that receive patient status changes
+    #[serde(default)]
+    pub eta: EtaEstimator, // Travel times shown with hospital distances
+    #[serde(default)]
+    pub catchment: CatchmentConfig,
+    #[serde(default)]
+    pub reference_ranges_path: Option<String>, // JSON overrides of the vital sign reference ranges
+}
+
+/// DHA catchment zones that incidents are classified into
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatchmentConfig {
+    pub zones_path: Option<String>, // GeoJSON FeatureCollection, one feature per zone
+    pub max_distance_km: f64, // How far outside every zone the nearest one still applies
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageConfig {
+    pub attachments_bucket: Option<String>, // Enables patient attachments when set
+    pub s3_region: String,
+    pub s3_endpoint: Option<String>, // S3-compatible service, e.g. MinIO in development
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -80,6 +214,18 @@ pub enum Environment {
     Production,
 }
 
+impl Environment {
+    /// Get the lowercase name used for per-environment configuration files
+    pub fn name(&self) -> &'static str {
+        match self {
+            Environment::Development => "development",
+            Environment::Testing => "testing",
+            Environment::Staging => "staging",
+            Environment::Production => "production",
+        }
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -87,8 +233,13 @@ impl Default for AppConfig {
             database: DatabaseConfig::default(),
             jwt: JwtConfig::default(),
             redis: RedisConfig::default(),
+            security: SecurityConfig::default(),
             logging: LoggingConfig::default(),
             healthcare: HealthcareConfig::default(),
+            storage: StorageConfig::default(),
+            secrets: SecretsConfig::default(),
+            retention: RetentionConfig::default(),
+            rate_limit: RateLimitConfig::default(),
             environment: Environment::Development,
         }
     }
@@ -103,6 +254,13 @@ impl Default for ServerConfig {
             request_timeout_seconds: 30,
             max_request_size_mb: 10,
             enable_metrics: true,
+            maintenance_mode: false,
+            tls: None,
+            ip_allowlist_er_director: Vec::new(),
+            ip_allowlist_paramedic: Vec::new(),
+            ip_allowlist_nurse: Vec::new(),
+            ip_allowlist_specialist: Vec::new(),
+            ip_allowlist_admin: Vec::new(),
         }
     }
 }
@@ -113,8 +271,12 @@ impl Default for JwtConfig {
             secret: "your-super-secret-key-change-this-in-production".to_string(),
             expiration_seconds: 3600,        // 1 hour
             refresh_expiration_seconds: 86400, // 24 hours
+            service_expiration_seconds: 900, // 15 minutes
             issuer: "dubai-healthcare-emergency".to_string(),
             audience: "healthcare-staff".to_string(),
+            algorithm: JwtAlgorithm::Hs256,
+            key_dir: None,
+            active_kid: None,
         }
     }
 }
@@ -123,9 +285,48 @@ impl Default for RedisConfig {
     fn default() -> Self {
         Self {
             url: "redis://localhost:6379".to_string(),
+            topology: RedisTopology::Standalone,
+            nodes: Vec::new(),
+            sentinel_master: None,
             max_connections: 50,
+            pool_size: 4,
             connection_timeout_seconds: 5,
             command_timeout_seconds: 5,
+            hospital_cache_ttl_seconds: 10,
+            idempotency_ttl_seconds: 86400, // 24 hours
+        }
+    }
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            max_failed_logins: 5,
+            failed_login_window_minutes: 15,
+            lockout_minutes: 15,
+            login_backoff_base_seconds: 1,
+            login_backoff_max_seconds: 30,
+            password_min_length: 12,
+            password_require_uppercase: true,
+            password_require_lowercase: true,
+            password_require_digit: true,
+            password_require_symbol: true,
+            password_history_size: 5,
+            password_deny_list_file: None,
+            argon2_memory_kib: 19456,
+            argon2_iterations: 2,
+            argon2_parallelism: 1,
+            break_glass_max_minutes: 60,
+            delegation_max_hours: 12,
+            url_signing_key: None,
+            signed_url_ttl_minutes: 15,
+            step_up_max_age_minutes: 5,
+            trusted_device_days: 30,
+            max_sessions_er_director: 5,
+            max_sessions_paramedic: 2,
+            max_sessions_nurse: 3,
+            max_sessions_specialist: 3,
+            max_sessions_admin: 3,
         }
     }
 }
@@ -155,6 +356,30 @@ impl Default for HealthcareConfig {
             max_patient_age: 150,
             default_session_timeout_minutes: 480, // 8 hours
             enable_triage_ai: false, // Disabled by default
+            shared_documents_dir: None,
+            notification_webhooks: Vec::new(),
+            eta: EtaEstimator::default(),
+            catchment: CatchmentConfig::default(),
+            reference_ranges_path: None,
+        }
+    }
+}
+
+impl Default for CatchmentConfig {
+    fn default() -> Self {
+        Self {
+            zones_path: None,
+            max_distance_km: 5.0,
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            attachments_bucket: None,
+            s3_region: "me-central-1".to_string(), // UAE
+            s3_endpoint: None,
         }
     }
 }
@@ -171,8 +396,13 @@ impl AppConfig {
             database: DatabaseConfig::from_env()?,
             jwt: JwtConfig::from_env()?,
             redis: RedisConfig::from_env()?,
+            security: SecurityConfig::from_env()?,
             logging: LoggingConfig::from_env(&environment)?,
             healthcare: HealthcareConfig::from_env()?,
+            storage: StorageConfig::from_env(),
+            secrets: SecretsConfig::from_env()?,
+            retention: RetentionConfig::from_env()?,
+            rate_limit: RateLimitConfig::from_env()?,
             environment,
         };
 
@@ -180,8 +410,50 @@ impl AppConfig {
         Ok(config)
     }
 
+    /// Load configuration from `config/default.toml`, then
+    /// `config/{environment}.toml`, then environment variables, each layer
+    /// overriding the previous one. `CONFIG_DIR` changes the directory.
+    /// Secrets named in the `secrets` section are then read from the
+    /// secrets manager.
+    pub async fn load() -> Result<Self> {
+        dotenvy::dotenv().ok(); // Load .env file if it exists
+
+        let environment = Self::detect_environment();
+        let mut config = read_layered(&Self::config_dir(), environment, EnvOverrides::from_env())?;
+
+        if let Some(provider) = config.secrets.provider()? {
+            ResolvedSecrets::fetch(&config.secrets, provider.as_ref())
+                .await?
+                .apply(&mut config);
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Directory holding the configuration files, `CONFIG_DIR` or `config`
+    pub fn config_dir() -> PathBuf {
+        PathBuf::from(env::var("CONFIG_DIR").unwrap_or_else(|_| "config".to_string()))
+    }
+
+    /// Built-in values underneath the configuration files. Like
+    /// `from_env`, the database URL and the JWT secret have no default.
+    pub(crate) fn defaults_for(environment: Environment) -> Self {
+        let mut config = Self::default();
+        config.database.url = String::new();
+        config.jwt.secret = String::new();
+        config.logging.level = match environment {
+            Environment::Production => "warn",
+            Environment::Staging => "info",
+            _ => "debug",
+        }
+        .to_string();
+        config.environment = environment;
+        config
+    }
+
     /// Detect environment from ENV variable
-    fn detect_environment() -> Environment {
+    pub fn detect_environment() -> Environment {
         match env::var("ENVIRONMENT")
             .or_else(|_| env::var("APP_ENV"))
             .unwrap_or_else(|_| "development".to_string())
@@ -201,8 +473,13 @@ impl AppConfig {
         self.database.validate()?;
         self.jwt.validate()?;
         self.redis.validate()?;
+        self.security.validate()?;
         self.logging.validate()?;
         self.healthcare.validate()?;
+        self.storage.validate()?;
+        self.secrets.validate()?;
+        self.retention.validate()?;
+        self.rate_limit.validate()?;
         Ok(())
     }
 
@@ -223,6 +500,19 @@ impl AppConfig {
         if let Some(ref mut api_key) = config.healthcare.dha_api_key {
             *api_key = "[REDACTED]".to_string();
         }
+        if let Some(ref mut key) = config.security.url_signing_key {
+            *key = "[REDACTED]".to_string();
+        }
+        for secret in [
+            &mut config.database.password,
+            &mut config.secrets.vault_token,
+            &mut config.secrets.azure_client_secret,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *secret = "[REDACTED]".to_string();
+        }
         serde_json::to_string_pretty(&config).context("Failed to serialize config")
     }
 }
@@ -252,6 +542,16 @@ impl ServerConfig {
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
                 .unwrap_or(true),
+            maintenance_mode: env::var("MAINTENANCE_MODE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            tls: TlsConfig::from_env(),
+            ip_allowlist_er_director: env_list("IP_ALLOWLIST_ER_DIRECTOR"),
+            ip_allowlist_paramedic: env_list("IP_ALLOWLIST_PARAMEDIC"),
+            ip_allowlist_nurse: env_list("IP_ALLOWLIST_NURSE"),
+            ip_allowlist_specialist: env_list("IP_ALLOWLIST_SPECIALIST"),
+            ip_allowlist_admin: env_list("IP_ALLOWLIST_ADMIN"),
         })
     }
 
@@ -265,18 +565,133 @@ impl ServerConfig {
         if self.request_timeout_seconds == 0 {
             anyhow::bail!("Request timeout must be greater than 0");
         }
+        self.ip_allowlists()?;
+        if let Some(tls) = &self.tls {
+            tls.validate()?;
+        }
         Ok(())
     }
+
+    /// Networks each role may connect from
+    pub fn ip_allowlists(&self) -> Result<IpAllowlists> {
+        let parse = |name: &str, networks: &[String]| {
+            networks
+                .iter()
+                .map(|network| {
+                    network
+                        .parse()
+                        .with_context(|| format!("Invalid CIDR in {}: {}", name, network))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(IpAllowlists {
+            er_director: parse("IP_ALLOWLIST_ER_DIRECTOR", &self.ip_allowlist_er_director)?,
+            paramedic: parse("IP_ALLOWLIST_PARAMEDIC", &self.ip_allowlist_paramedic)?,
+            nurse: parse("IP_ALLOWLIST_NURSE", &self.ip_allowlist_nurse)?,
+            specialist: parse("IP_ALLOWLIST_SPECIALIST", &self.ip_allowlist_specialist)?,
+            admin: parse("IP_ALLOWLIST_ADMIN", &self.ip_allowlist_admin)?,
+        })
+    }
 }
 
-impl JwtConfig {
+impl TlsConfig {
+    /// HTTPS is enabled by setting both TLS_CERT_PATH and TLS_KEY_PATH
+    fn from_env() -> Option<Self> {
+        Some(Self {
+            cert_path: env::var("TLS_CERT_PATH").ok()?,
+            key_path: env::var("TLS_KEY_PATH").ok()?,
+            client_ca_path: env::var("TLS_CLIENT_CA_PATH").ok(),
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.cert_path.is_empty() || self.key_path.is_empty() {
+            anyhow::bail!("TLS_CERT_PATH and TLS_KEY_PATH must both be set for HTTPS");
+        }
+        Ok(())
+    }
+}
+
+/// Read a comma-separated environment variable, skipping empty entries
+impl CatchmentConfig {
     fn from_env() -> Result<Self> {
-        let secret = env::var("JWT_SECRET")
-            .context("JWT_SECRET environment variable is required")?;
+        let defaults = Self::default();
+        Ok(Self {
+            zones_path: env::var("CATCHMENT_ZONES_PATH").ok(),
+            max_distance_km: match env::var("CATCHMENT_MAX_DISTANCE_KM") {
+                Ok(value) => value.parse().context("Invalid CATCHMENT_MAX_DISTANCE_KM")?,
+                Err(_) => defaults.max_distance_km,
+            },
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.max_distance_km.is_nan() || self.max_distance_km < 0.0 {
+            anyhow::bail!("CATCHMENT_MAX_DISTANCE_KM cannot be negative");
+        }
+        Ok(())
+    }
 
-        if secret.len() < 32 {
-            anyhow::bail!("JWT_SECRET must be at least 32 characters long");
+    /// The catchment zones, or `None` when no zones file is configured
+    pub fn zones(&self) -> Result<Option<ZoneRegistry>> {
+        self.zones_path
+            .as_deref()
+            .map(|path| ZoneRegistry::load(path).context("Failed to load catchment zones"))
+            .transpose()
+    }
+}
+
+fn eta_from_env() -> Result<EtaEstimator> {
+    let defaults = EtaEstimator::default();
+    let parse = |name: &str, default: f64| -> Result<f64> {
+        match env::var(name) {
+            Ok(value) => value.parse().with_context(|| format!("Invalid {}", name)),
+            Err(_) => Ok(default),
         }
+    };
+
+    Ok(EtaEstimator {
+        average_speed_kmh: parse("ETA_AVERAGE_SPEED_KMH", defaults.average_speed_kmh)?,
+        road_factor: parse("ETA_ROAD_FACTOR", defaults.road_factor)?,
+        peak_factor: parse("ETA_PEAK_FACTOR", defaults.peak_factor)?,
+        night_factor: parse("ETA_NIGHT_FACTOR", defaults.night_factor)?,
+        utc_offset_hours: match env::var("ETA_UTC_OFFSET_HOURS") {
+            Ok(value) => value.parse().context("Invalid ETA_UTC_OFFSET_HOURS")?,
+            Err(_) => defaults.utc_offset_hours,
+        },
+    })
+}
+
+fn env_list(name: &str) -> Vec<String> {
+    env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+impl JwtConfig {
+    fn from_env() -> Result<Self> {
+        let algorithm: JwtAlgorithm = env::var("JWT_ALGORITHM")
+            .unwrap_or_else(|_| "HS256".to_string())
+            .parse()
+            .context("Invalid JWT_ALGORITHM")?;
+
+        // The shared secret is only needed when tokens are signed with HMAC
+        let secret = if algorithm.is_asymmetric() {
+            env::var("JWT_SECRET").unwrap_or_default()
+        } else {
+            let secret = env::var("JWT_SECRET")
+                .context("JWT_SECRET environment variable is required")?;
+
+            if secret.len() < 32 {
+                anyhow::bail!("JWT_SECRET must be at least 32 characters long");
+            }
+            secret
+        };
 
         Ok(Self {
             secret,
@@ -288,32 +703,82 @@ impl JwtConfig {
                 .unwrap_or_else(|_| "86400".to_string())
                 .parse()
                 .context("Invalid JWT_REFRESH_EXPIRATION")?,
+            service_expiration_seconds: env::var("JWT_SERVICE_EXPIRATION")
+                .unwrap_or_else(|_| "900".to_string())
+                .parse()
+                .context("Invalid JWT_SERVICE_EXPIRATION")?,
             issuer: env::var("JWT_ISSUER")
                 .unwrap_or_else(|_| "dubai-healthcare-emergency".to_string()),
             audience: env::var("JWT_AUDIENCE")
                 .unwrap_or_else(|_| "healthcare-staff".to_string()),
+            algorithm,
+            key_dir: env::var("JWT_KEY_DIR").ok(),
+            active_kid: env::var("JWT_ACTIVE_KID").ok(),
         })
     }
 
     fn validate(&self) -> Result<()> {
-        if self.secret.len() < 32 {
+        if self.algorithm.is_asymmetric() {
+            if self.key_dir.is_none() {
+                anyhow::bail!("JWT_KEY_DIR must be set when using {}", self.algorithm);
+            }
+            if self.active_kid.as_deref().unwrap_or_default().is_empty() {
+                anyhow::bail!("JWT_ACTIVE_KID must be set when using {}", self.algorithm);
+            }
+        } else if self.secret.len() < 32 {
             anyhow::bail!("JWT secret must be at least 32 characters");
         }
         if self.expiration_seconds <= 0 {
             anyhow::bail!("JWT expiration must be positive");
         }
+        if self.service_expiration_seconds <= 0 {
+            anyhow::bail!("JWT service token expiration must be positive");
+        }
         Ok(())
     }
+
+    /// Build the signing/verification key ring described by this configuration
+    pub fn key_ring(&self) -> Result<JwtKeyRing> {
+        if !self.algorithm.is_asymmetric() {
+            return Ok(JwtKeyRing::hmac(self.secret.as_bytes()));
+        }
+
+        let key_dir = self.key_dir.as_deref().context("JWT_KEY_DIR is not set")?;
+        let active_kid = self.active_kid.as_deref().context("JWT_ACTIVE_KID is not set")?;
+
+        JwtKeyRing::load_dir(self.algorithm, key_dir, active_kid)
+            .with_context(|| format!("Failed to load JWT keys from {}", key_dir))
+    }
 }
 
 impl RedisConfig {
     fn from_env() -> Result<Self> {
         Ok(Self {
             url: env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string()),
+            topology: match env::var("REDIS_TOPOLOGY").as_deref() {
+                Ok("sentinel") => RedisTopology::Sentinel,
+                Ok("cluster") => RedisTopology::Cluster,
+                Ok("standalone") | Err(_) => RedisTopology::Standalone,
+                Ok(other) => anyhow::bail!("Invalid REDIS_TOPOLOGY: {}", other),
+            },
+            nodes: env::var("REDIS_NODES")
+                .map(|nodes| {
+                    nodes
+                        .split(',')
+                        .map(|node| node.trim().to_string())
+                        .filter(|node| !node.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            sentinel_master: env::var("REDIS_SENTINEL_MASTER").ok(),
             max_connections: env::var("REDIS_MAX_CONNECTIONS")
                 .unwrap_or_else(|_| "50".to_string())
                 .parse()
                 .context("Invalid REDIS_MAX_CONNECTIONS")?,
+            pool_size: env::var("REDIS_POOL_SIZE")
+                .unwrap_or_else(|_| "4".to_string())
+                .parse()
+                .context("Invalid REDIS_POOL_SIZE")?,
             connection_timeout_seconds: env::var("REDIS_CONNECTION_TIMEOUT")
                 .unwrap_or_else(|_| "5".to_string())
                 .parse()
@@ -322,15 +787,322 @@ impl RedisConfig {
                 .unwrap_or_else(|_| "5".to_string())
                 .parse()
                 .context("Invalid REDIS_COMMAND_TIMEOUT")?,
+            hospital_cache_ttl_seconds: env::var("HOSPITAL_CACHE_TTL")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("Invalid HOSPITAL_CACHE_TTL")?,
+            idempotency_ttl_seconds: env::var("IDEMPOTENCY_TTL")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()
+                .context("Invalid IDEMPOTENCY_TTL")?,
         })
     }
 
     fn validate(&self) -> Result<()> {
-        if !self.url.starts_with("redis://") && !self.url.starts_with("rediss://") {
+        let is_redis_url = |url: &str| url.starts_with("redis://") || url.starts_with("rediss://");
+        if !is_redis_url(&self.url) {
             anyhow::bail!("Redis URL must start with redis:// or rediss://");
         }
+
+        match self.topology {
+            RedisTopology::Standalone => {}
+            RedisTopology::Sentinel | RedisTopology::Cluster => {
+                if self.nodes.is_empty() {
+                    anyhow::bail!("Redis {:?} topology requires REDIS_NODES", self.topology);
+                }
+                if let Some(node) = self.nodes.iter().find(|node| !is_redis_url(node)) {
+                    anyhow::bail!("Redis node {} must start with redis:// or rediss://", node);
+                }
+            }
+        }
+        if self.topology == RedisTopology::Sentinel && self.sentinel_master.is_none() {
+            anyhow::bail!("Redis Sentinel topology requires REDIS_SENTINEL_MASTER");
+        }
+
+        if self.pool_size == 0 {
+            anyhow::bail!("Redis pool_size must be greater than 0");
+        }
         Ok(())
     }
+
+    /// Open the Redis connection pool for the configured topology
+    pub async fn connect(&self) -> Result<RedisPool> {
+        self.validate()
+            .context("Redis configuration validation failed")?;
+
+        let manager_config = ConnectionManagerConfig::new()
+            .set_connection_timeout(Duration::from_secs(self.connection_timeout_seconds))
+            .set_response_timeout(Duration::from_secs(self.command_timeout_seconds));
+
+        let pool = match self.topology {
+            RedisTopology::Standalone => {
+                RedisPool::standalone(&self.url, manager_config, self.pool_size).await
+            }
+            RedisTopology::Sentinel => {
+                let master = self.sentinel_master.as_deref().unwrap_or_default();
+                RedisPool::sentinel(&self.nodes, master, &self.url, manager_config, self.pool_size)
+                    .await
+            }
+            RedisTopology::Cluster => {
+                RedisPool::cluster(
+                    &self.nodes,
+                    Duration::from_secs(self.connection_timeout_seconds),
+                    Duration::from_secs(self.command_timeout_seconds),
+                    self.pool_size,
+                )
+                .await
+            }
+        };
+        pool.with_context(|| format!("Failed to connect to Redis ({:?})", self.topology))
+    }
+
+    /// How long hospital lists stay cached
+    pub fn hospital_cache_ttl(&self) -> Duration {
+        Duration::from_secs(self.hospital_cache_ttl_seconds)
+    }
+
+    /// How long responses are kept for requests retried with the same
+    /// `Idempotency-Key`
+    pub fn idempotency_ttl(&self) -> Duration {
+        Duration::from_secs(self.idempotency_ttl_seconds)
+    }
+}
+
+impl SecurityConfig {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            max_failed_logins: env::var("MAX_FAILED_LOGINS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("Invalid MAX_FAILED_LOGINS")?,
+            failed_login_window_minutes: env::var("FAILED_LOGIN_WINDOW_MINUTES")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .context("Invalid FAILED_LOGIN_WINDOW_MINUTES")?,
+            lockout_minutes: env::var("LOCKOUT_MINUTES")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .context("Invalid LOCKOUT_MINUTES")?,
+            login_backoff_base_seconds: env::var("LOGIN_BACKOFF_BASE_SECONDS")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .context("Invalid LOGIN_BACKOFF_BASE_SECONDS")?,
+            login_backoff_max_seconds: env::var("LOGIN_BACKOFF_MAX_SECONDS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid LOGIN_BACKOFF_MAX_SECONDS")?,
+            password_min_length: env::var("PASSWORD_MIN_LENGTH")
+                .unwrap_or_else(|_| "12".to_string())
+                .parse()
+                .context("Invalid PASSWORD_MIN_LENGTH")?,
+            password_require_uppercase: env::var("PASSWORD_REQUIRE_UPPERCASE")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .context("Invalid PASSWORD_REQUIRE_UPPERCASE")?,
+            password_require_lowercase: env::var("PASSWORD_REQUIRE_LOWERCASE")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .context("Invalid PASSWORD_REQUIRE_LOWERCASE")?,
+            password_require_digit: env::var("PASSWORD_REQUIRE_DIGIT")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .context("Invalid PASSWORD_REQUIRE_DIGIT")?,
+            password_require_symbol: env::var("PASSWORD_REQUIRE_SYMBOL")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .context("Invalid PASSWORD_REQUIRE_SYMBOL")?,
+            password_history_size: env::var("PASSWORD_HISTORY_SIZE")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("Invalid PASSWORD_HISTORY_SIZE")?,
+            password_deny_list_file: env::var("PASSWORD_DENY_LIST_FILE").ok(),
+            argon2_memory_kib: env::var("ARGON2_MEMORY_KIB")
+                .unwrap_or_else(|_| "19456".to_string())
+                .parse()
+                .context("Invalid ARGON2_MEMORY_KIB")?,
+            argon2_iterations: env::var("ARGON2_ITERATIONS")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .context("Invalid ARGON2_ITERATIONS")?,
+            argon2_parallelism: env::var("ARGON2_PARALLELISM")
+                .unwrap_or_else(|_| "1".to_string())
+                .parse()
+                .context("Invalid ARGON2_PARALLELISM")?,
+            break_glass_max_minutes: env::var("BREAK_GLASS_MAX_MINUTES")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .context("Invalid BREAK_GLASS_MAX_MINUTES")?,
+            delegation_max_hours: env::var("DELEGATION_MAX_HOURS")
+                .unwrap_or_else(|_| "12".to_string())
+                .parse()
+                .context("Invalid DELEGATION_MAX_HOURS")?,
+            url_signing_key: env::var("URL_SIGNING_KEY").ok(),
+            signed_url_ttl_minutes: env::var("SIGNED_URL_TTL_MINUTES")
+                .unwrap_or_else(|_| "15".to_string())
+                .parse()
+                .context("Invalid SIGNED_URL_TTL_MINUTES")?,
+            step_up_max_age_minutes: env::var("STEP_UP_MAX_AGE_MINUTES")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("Invalid STEP_UP_MAX_AGE_MINUTES")?,
+            trusted_device_days: env::var("TRUSTED_DEVICE_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("Invalid TRUSTED_DEVICE_DAYS")?,
+            max_sessions_er_director: env::var("MAX_SESSIONS_ER_DIRECTOR")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .context("Invalid MAX_SESSIONS_ER_DIRECTOR")?,
+            max_sessions_paramedic: env::var("MAX_SESSIONS_PARAMEDIC")
+                .unwrap_or_else(|_| "2".to_string())
+                .parse()
+                .context("Invalid MAX_SESSIONS_PARAMEDIC")?,
+            max_sessions_nurse: env::var("MAX_SESSIONS_NURSE")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("Invalid MAX_SESSIONS_NURSE")?,
+            max_sessions_specialist: env::var("MAX_SESSIONS_SPECIALIST")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("Invalid MAX_SESSIONS_SPECIALIST")?,
+            max_sessions_admin: env::var("MAX_SESSIONS_ADMIN")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("Invalid MAX_SESSIONS_ADMIN")?,
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.max_failed_logins == 0 {
+            anyhow::bail!("MAX_FAILED_LOGINS must be greater than 0");
+        }
+        if self.failed_login_window_minutes == 0 || self.lockout_minutes == 0 {
+            anyhow::bail!("Failed login window and lockout duration must be greater than 0");
+        }
+        if self.login_backoff_base_seconds > self.login_backoff_max_seconds {
+            anyhow::bail!("LOGIN_BACKOFF_BASE_SECONDS cannot exceed LOGIN_BACKOFF_MAX_SECONDS");
+        }
+        if self.password_min_length < 8 {
+            anyhow::bail!("PASSWORD_MIN_LENGTH must be at least 8");
+        }
+        if self.step_up_max_age_minutes == 0 {
+            anyhow::bail!("STEP_UP_MAX_AGE_MINUTES must be greater than 0");
+        }
+        if self.trusted_device_days == 0 {
+            anyhow::bail!("TRUSTED_DEVICE_DAYS must be greater than 0");
+        }
+        let limits = self.session_limits();
+        if [limits.er_director, limits.paramedic, limits.nurse, limits.specialist, limits.admin]
+            .contains(&0)
+        {
+            anyhow::bail!("MAX_SESSIONS_* limits must be greater than 0");
+        }
+        if self.break_glass_max_minutes == 0 || self.break_glass_max_minutes > 24 * 60 {
+            anyhow::bail!("BREAK_GLASS_MAX_MINUTES must be between 1 and 1440");
+        }
+        if self.delegation_max_hours == 0 || self.delegation_max_hours > 24 {
+            anyhow::bail!("DELEGATION_MAX_HOURS must be between 1 and 24");
+        }
+        if self.url_signing_key.as_ref().is_some_and(|key| key.len() < 32) {
+            anyhow::bail!("URL_SIGNING_KEY must be at least 32 characters long");
+        }
+        if self.signed_url_ttl_minutes == 0 || self.signed_url_ttl_minutes > 60 {
+            anyhow::bail!("SIGNED_URL_TTL_MINUTES must be between 1 and 60");
+        }
+        self.password_hasher()?;
+        Ok(())
+    }
+
+    /// How recently a user must have entered their credentials for sensitive operations
+    pub fn step_up_max_age(&self) -> Duration {
+        Duration::from_secs(self.step_up_max_age_minutes * 60)
+    }
+
+    /// Concurrent session limits per role
+    pub fn session_limits(&self) -> SessionLimits {
+        SessionLimits {
+            er_director: self.max_sessions_er_director,
+            paramedic: self.max_sessions_paramedic,
+            nurse: self.max_sessions_nurse,
+            specialist: self.max_sessions_specialist,
+            admin: self.max_sessions_admin,
+        }
+    }
+
+    /// How long a device stays trusted after the user marks it
+    pub fn trusted_device_duration(&self) -> Duration {
+        Duration::from_secs(u64::from(self.trusted_device_days) * 24 * 60 * 60)
+    }
+
+    /// Limits for emergency cross-hospital access
+    pub fn break_glass_policy(&self) -> BreakGlassPolicy {
+        BreakGlassPolicy {
+            max_duration: Duration::from_secs(u64::from(self.break_glass_max_minutes) * 60),
+            ..BreakGlassPolicy::default()
+        }
+    }
+
+    /// Limits for handing a role over to a colleague (shorter maximums also
+    /// shorten the default duration)
+    pub fn delegation_policy(&self) -> DelegationPolicy {
+        let defaults = DelegationPolicy::default();
+        let max_duration = Duration::from_secs(u64::from(self.delegation_max_hours) * 3600);
+        DelegationPolicy {
+            default_duration: defaults.default_duration.min(max_duration),
+            max_duration,
+            ..defaults
+        }
+    }
+
+    /// Signer for time-limited document links, if a signing key is configured
+    pub fn url_signer(&self) -> Option<UrlSigner> {
+        let ttl = Duration::from_secs(u64::from(self.signed_url_ttl_minutes) * 60);
+        self.url_signing_key
+            .as_ref()
+            .map(|key| UrlSigner::new(key, ttl))
+    }
+
+    /// Argon2id hasher for new and upgraded password hashes
+    pub fn password_hasher(&self) -> Result<PasswordHasher> {
+        PasswordHasher::new(Argon2Params {
+            memory_kib: self.argon2_memory_kib,
+            iterations: self.argon2_iterations,
+            parallelism: self.argon2_parallelism,
+        })
+        .map_err(|e| anyhow::anyhow!(e.to_string()))
+    }
+
+    /// Password strength rules, including any deny-list file
+    pub fn password_policy(&self) -> Result<PasswordPolicy> {
+        let mut policy = PasswordPolicy {
+            min_length: self.password_min_length,
+            require_uppercase: self.password_require_uppercase,
+            require_lowercase: self.password_require_lowercase,
+            require_digit: self.password_require_digit,
+            require_symbol: self.password_require_symbol,
+            history_size: self.password_history_size,
+            ..PasswordPolicy::default()
+        };
+
+        if let Some(path) = &self.password_deny_list_file {
+            let contents = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read password deny list {}", path))?;
+            policy = policy.with_denied_passwords(contents.lines());
+        }
+
+        Ok(policy)
+    }
+
+    /// Failed-login lockout thresholds
+    pub fn lockout_policy(&self) -> LockoutPolicy {
+        LockoutPolicy {
+            max_failed_attempts: self.max_failed_logins,
+            attempt_window: Duration::from_secs(u64::from(self.failed_login_window_minutes) * 60),
+            lockout_duration: Duration::from_secs(u64::from(self.lockout_minutes) * 60),
+            backoff_base: Duration::from_secs(self.login_backoff_base_seconds),
+            backoff_max: Duration::from_secs(self.login_backoff_max_seconds),
+        }
+    }
 }
 
 impl LoggingConfig {
@@ -407,6 +1179,11 @@ impl HealthcareConfig {
                 .unwrap_or_else(|_| "false".to_string())
                 .parse()
                 .unwrap_or(false),
+            shared_documents_dir: env::var("SHARED_DOCUMENTS_DIR").ok(),
+            notification_webhooks: env_list("NOTIFICATION_WEBHOOKS"),
+            eta: eta_from_env()?,
+            catchment: CatchmentConfig::from_env()?,
+            reference_ranges_path: env::var("REFERENCE_RANGES_PATH").ok(),
         })
     }
 
@@ -420,8 +1197,72 @@ impl HealthcareConfig {
         if self.dha_integration_enabled && self.dha_api_url.is_none() {
             anyhow::bail!("DHA_API_URL is required when DHA integration is enabled");
         }
+        if self.default_session_timeout_minutes == 0 {
+            anyhow::bail!("SESSION_TIMEOUT_MINUTES must be greater than 0");
+        }
+        for url in &self.notification_webhooks {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                anyhow::bail!("NOTIFICATION_WEBHOOKS entries must be HTTP(S) URLs: {}", url);
+            }
+        }
+        self.catchment.validate()?;
+        if !self.eta.is_valid() {
+            anyhow::bail!(
+                "ETA_AVERAGE_SPEED_KMH and the ETA factors must be greater than 0, \
+                 and ETA_UTC_OFFSET_HOURS between -12 and 14"
+            );
+        }
+        Ok(())
+    }
+
+    /// Idle timeout after which an inactive session expires
+    pub fn session_timeout(&self) -> Duration {
+        Duration::from_secs(u64::from(self.default_session_timeout_minutes) * 60)
+    }
+
+    /// The vital sign reference ranges: the built-in ones with any overrides
+    /// from `reference_ranges_path`
+    pub fn reference_ranges(&self) -> Result<ReferenceRanges> {
+        match self.reference_ranges_path.as_deref() {
+            Some(path) => ReferenceRanges::load(path).context("Failed to load reference ranges"),
+            None => Ok(ReferenceRanges::builtin()),
+        }
+    }
+}
+
+impl StorageConfig {
+    fn from_env() -> Self {
+        Self {
+            attachments_bucket: env::var("ATTACHMENTS_BUCKET")
+                .ok()
+                .filter(|bucket| !bucket.is_empty()),
+            s3_region: env::var("S3_REGION").unwrap_or_else(|_| "me-central-1".to_string()),
+            s3_endpoint: env::var("S3_ENDPOINT").ok(),
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.s3_region.is_empty() {
+            anyhow::bail!("S3_REGION cannot be empty");
+        }
+        if let Some(ref endpoint) = self.s3_endpoint {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                anyhow::bail!("S3_ENDPOINT must start with http:// or https://");
+            }
+        }
         Ok(())
     }
+
+    /// Storage for patient attachments, or `None` when no bucket is configured
+    pub fn attachment_storage(&self) -> Result<Option<S3ObjectStorage>> {
+        self.attachments_bucket
+            .as_deref()
+            .map(|bucket| {
+                S3ObjectStorage::new(bucket, &self.s3_region, self.s3_endpoint.as_deref())
+                    .context("Failed to configure attachment storage")
+            })
+            .transpose()
+    }
 }
 
 #[cfg(test)]
@@ -464,6 +1305,27 @@ mod tests {
         config.host = "localhost".to_string();
         config.port = 0;
         assert!(config.validate().is_err());
+
+        config.port = 8443;
+        config.tls = Some(TlsConfig {
+            cert_path: "/etc/er/tls/server.crt".to_string(),
+            key_path: "".to_string(),
+            client_ca_path: None,
+        });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_ip_allowlists_from_config() {
+        let mut config = ServerConfig::default();
+        assert_eq!(config.ip_allowlists().unwrap(), IpAllowlists::default());
+
+        config.ip_allowlist_admin = vec!["10.20.0.0/16".to_string(), "fd00:20::/32".to_string()];
+        assert!(config.validate().is_ok());
+        assert_eq!(config.ip_allowlists().unwrap().admin.len(), 2);
+
+        config.ip_allowlist_admin = vec!["10.20.0.0/33".to_string()];
+        assert!(config.validate().is_err());
     }
 
     #[test]
@@ -479,6 +1341,36 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_asymmetric_jwt_config_validation() {
+        let mut config = JwtConfig {
+            secret: String::new(), // Not needed for asymmetric keys
+            algorithm: JwtAlgorithm::Rs256,
+            ..Default::default()
+        };
+
+        // Key directory and active kid are required
+        assert!(config.validate().is_err());
+
+        config.key_dir = Some("/etc/healthcare/jwt".to_string());
+        assert!(config.validate().is_err());
+
+        config.active_kid = Some("2024-01".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_hmac_key_ring() {
+        let config = JwtConfig {
+            secret: "this-is-a-long-enough-secret-key-for-jwt".to_string(),
+            ..Default::default()
+        };
+
+        let ring = config.key_ring().unwrap();
+        assert_eq!(ring.algorithm(), JwtAlgorithm::Hs256);
+        assert!(ring.jwks().keys.is_empty());
+    }
+
     #[test]
     fn test_redis_config_validation() {
         let mut config = RedisConfig::default();
@@ -486,6 +1378,92 @@ mod tests {
         
         config.url = "invalid-url".to_string();
         assert!(config.validate().is_err());
+
+        config.url = "redis://:password@localhost".to_string();
+        config.topology = RedisTopology::Sentinel;
+        assert!(config.validate().is_err()); // No sentinels
+        config.nodes = vec!["redis://sentinel-1:26379".to_string()];
+        assert!(config.validate().is_err()); // No master name
+        config.sentinel_master = Some("mymaster".to_string());
+        assert!(config.validate().is_ok());
+
+        config.topology = RedisTopology::Cluster;
+        config.nodes.push("sentinel-2:26379".to_string());
+        assert!(config.validate().is_err());
+        config.nodes.pop();
+        config.pool_size = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_security_config_validation() {
+        let mut config = SecurityConfig::default();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.lockout_policy(), LockoutPolicy::default());
+
+        config.login_backoff_base_seconds = 60;
+        assert!(config.validate().is_err());
+
+        config.login_backoff_base_seconds = 1;
+        config.max_failed_logins = 0;
+        assert!(config.validate().is_err());
+
+        config.max_failed_logins = 5;
+        config.password_min_length = 6;
+        assert!(config.validate().is_err());
+
+        config.password_min_length = 12;
+        config.argon2_iterations = 0;
+        assert!(config.validate().is_err());
+
+        config.argon2_iterations = 2;
+        config.break_glass_max_minutes = 0;
+        assert!(config.validate().is_err());
+
+        config.break_glass_max_minutes = 60;
+        config.delegation_max_hours = 25;
+        assert!(config.validate().is_err());
+
+        config.delegation_max_hours = 4;
+        assert_eq!(
+            config.delegation_policy().default_duration,
+            Duration::from_secs(4 * 3600)
+        );
+        config.step_up_max_age_minutes = 0;
+        assert!(config.validate().is_err());
+
+        config.step_up_max_age_minutes = 5;
+        assert_eq!(config.session_limits(), SessionLimits::default());
+        config.max_sessions_paramedic = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_url_signer_from_config() {
+        let mut config = SecurityConfig::default();
+        assert!(config.url_signer().is_none());
+
+        config.url_signing_key = Some("too-short".to_string());
+        assert!(config.validate().is_err());
+
+        config.url_signing_key = Some("url-signing-key-with-at-least-32-chars".to_string());
+        assert!(config.validate().is_ok());
+        assert_eq!(
+            config.url_signer().unwrap().ttl(),
+            Duration::from_secs(15 * 60)
+        );
+
+        config.signed_url_ttl_minutes = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_password_policy_from_config() {
+        let mut config = SecurityConfig::default();
+        assert_eq!(config.password_policy().unwrap(), PasswordPolicy::default());
+
+        config.password_deny_list_file = Some("/nonexistent/deny-list.txt".to_string());
+        assert!(config.password_policy().is_err());
     }
 
     #[test]
@@ -520,10 +1498,12 @@ mod tests {
         let mut config = AppConfig::default();
         config.jwt.secret = "super-secret-key-that-should-be-redacted".to_string();
         config.healthcare.dha_api_key = Some("secret-api-key".to_string());
+        config.security.url_signing_key = Some("url-signing-key-to-redact".to_string());
         
         let json = config.to_json_redacted().unwrap();
         assert!(!json.contains("super-secret-key"));
         assert!(!json.contains("secret-api-key"));
+        assert!(!json.contains("url-signing-key"));
         assert!(json.contains("[REDACTED]"));
     }
 }
\ No newline at end of file
@@ -5,6 +5,8 @@ use sqlx::{ConnectOptions, Connection, PgConnection};
 use std::time::Duration;
 use tracing::{info, warn};
 
+use crate::store::{CircuitBreaker, Db, DbPolicy, RetryPolicy};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
     pub url: String,
@@ -15,6 +17,21 @@ pub struct DatabaseConfig {
     pub max_lifetime_seconds: u64,
     pub connect_timeout_seconds: u64,
     pub enable_logging: bool,
+    pub run_migrations: bool, // Apply pending schema migrations at startup
+    #[serde(default)]
+    pub replica_urls: Vec<String>, // Read replicas for dashboards and reports
+    #[serde(default)]
+    pub password: Option<String>, // Replaces the password in the URL, e.g. from a secrets manager
+    #[serde(default)]
+    pub slow_query_threshold_ms: u64, // Queries taking longer are logged; 0 disables
+    #[serde(default)]
+    pub max_retries: u32, // Retries of a connection that could not be taken, e.g. pool timeouts
+    #[serde(default)]
+    pub retry_base_delay_ms: u64, // Doubled per retry, with random jitter
+    #[serde(default)]
+    pub breaker_failure_threshold: u32, // Failures in a row that open the circuit; 0 disables
+    #[serde(default)]
+    pub breaker_open_seconds: u64, // How long an open circuit fails calls at once
 }
 
 impl Default for DatabaseConfig {
@@ -28,6 +45,14 @@ impl Default for DatabaseConfig {
             max_lifetime_seconds: 1800, // 30 minutes
             connect_timeout_seconds: 10,
             enable_logging: false, // Set to true for development
+            run_migrations: true,
+            replica_urls: Vec::new(),
+            password: None,
+            slow_query_threshold_ms: 500,
+            max_retries: 2,
+            retry_base_delay_ms: 50,
+            breaker_failure_threshold: 5,
+            breaker_open_seconds: 30,
         }
     }
 }
@@ -73,6 +98,36 @@ impl DatabaseConfig {
             .parse()
             .unwrap_or(false);
 
+        let run_migrations = std::env::var("DB_RUN_MIGRATIONS")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .context("Invalid DB_RUN_MIGRATIONS value")?;
+
+        let slow_query_threshold_ms = std::env::var("DB_SLOW_QUERY_THRESHOLD_MS")
+            .unwrap_or_else(|_| "500".to_string())
+            .parse()
+            .context("Invalid DB_SLOW_QUERY_THRESHOLD_MS value")?;
+
+        let max_retries = std::env::var("DB_MAX_RETRIES")
+            .unwrap_or_else(|_| "2".to_string())
+            .parse()
+            .context("Invalid DB_MAX_RETRIES value")?;
+
+        let retry_base_delay_ms = std::env::var("DB_RETRY_BASE_DELAY_MS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .context("Invalid DB_RETRY_BASE_DELAY_MS value")?;
+
+        let breaker_failure_threshold = std::env::var("DB_BREAKER_THRESHOLD")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()
+            .context("Invalid DB_BREAKER_THRESHOLD value")?;
+
+        let breaker_open_seconds = std::env::var("DB_BREAKER_OPEN_SECONDS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .context("Invalid DB_BREAKER_OPEN_SECONDS value")?;
+
         Ok(Self {
             url,
             max_connections,
@@ -82,6 +137,20 @@ impl DatabaseConfig {
             max_lifetime_seconds,
             connect_timeout_seconds,
             enable_logging,
+            run_migrations,
+            replica_urls: std::env::var("DATABASE_REPLICA_URLS")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(str::to_string)
+                .collect(),
+            password: None,
+            slow_query_threshold_ms,
+            max_retries,
+            retry_base_delay_ms,
+            breaker_failure_threshold,
+            breaker_open_seconds,
         })
     }
 
@@ -95,6 +164,12 @@ impl DatabaseConfig {
             anyhow::bail!("Database URL must be a PostgreSQL connection string");
         }
 
+        if let Some(url) = self.replica_urls.iter().find(|url| {
+            !url.starts_with("postgresql://") && !url.starts_with("postgres://")
+        }) {
+            anyhow::bail!("Replica URL {} must be a PostgreSQL connection string", url);
+        }
+
         if self.max_connections == 0 {
             anyhow::bail!("max_connections must be greater than 0");
         }
@@ -117,24 +192,8 @@ impl DatabaseConfig {
 
         info!("Creating database connection pool with {} max connections", self.max_connections);
 
-        let mut options = PgPoolOptions::new()
-            .max_connections(self.max_connections)
-            .min_connections(self.min_connections)
-            .acquire_timeout(Duration::from_secs(self.acquire_timeout_seconds))
-            .idle_timeout(Duration::from_secs(self.idle_timeout_seconds))
-            .max_lifetime(Duration::from_secs(self.max_lifetime_seconds));
-
-        // Configure connection options
-        let mut connect_options: PgConnectOptions = self.url.parse()
-            .context("Failed to parse database URL")?;
-
-        if self.enable_logging {
-            connect_options = connect_options.log_statements(tracing::log::LevelFilter::Info);
-        }
-
-        connect_options = connect_options
-            .statement_cache_capacity(100)
-            .application_name("dubai-healthcare-emergency");
+        let options = self.pool_options();
+        let connect_options = self.connect_options()?;
 
         // Create pool with retry logic
         let pool = self.create_pool_with_retry(options, connect_options).await?;
@@ -176,12 +235,112 @@ impl DatabaseConfig {
         }
     }
 
+    /// Connect to the primary and the read replicas. Replica pools connect
+    /// lazily, so a replica that is down does not stop the server; it takes
+    /// reads once a health check passes.
+    pub async fn connect(&self) -> Result<Db> {
+        let primary = self.create_pool().await?;
+
+        let replicas = self
+            .replica_urls
+            .iter()
+            .map(|url| {
+                Ok(self
+                    .pool_options()
+                    .connect_lazy_with(self.connect_options_for(url)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let db = Db::new(primary)
+            .with_replicas(replicas)
+            .with_policy(self.policy());
+        db.metrics().set_slow_threshold(self.slow_query_threshold());
+        if !self.replica_urls.is_empty() {
+            info!("Routing reports to {} read replica(s)", self.replica_urls.len());
+            db.check_replicas().await;
+        }
+        Ok(db)
+    }
+
+    /// Point the pools of `db` at this configuration, e.g. after the
+    /// password was rotated. Open connections are kept until they retire.
+    pub fn update_connect_options(&self, db: &Db) -> Result<()> {
+        db.metrics().set_slow_threshold(self.slow_query_threshold());
+        db.primary().set_connect_options(self.connect_options()?);
+        for (replica, url) in db.replicas().zip(&self.replica_urls) {
+            replica.set_connect_options(self.connect_options_for(url)?);
+        }
+        Ok(())
+    }
+
+    pub fn slow_query_threshold(&self) -> Duration {
+        Duration::from_millis(self.slow_query_threshold_ms)
+    }
+
+    /// Retries and circuit breaking for taking connections
+    pub fn policy(&self) -> DbPolicy {
+        DbPolicy::new(
+            RetryPolicy {
+                max_retries: self.max_retries,
+                base_delay: Duration::from_millis(self.retry_base_delay_ms),
+                max_delay: Duration::from_secs(2),
+                ..Default::default()
+            },
+            CircuitBreaker::new(
+                self.breaker_failure_threshold,
+                Duration::from_secs(self.breaker_open_seconds),
+            ),
+        )
+    }
+
+    /// Pool sizing and timeouts, shared by the primary and replica pools
+    fn pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(Duration::from_secs(self.acquire_timeout_seconds))
+            .idle_timeout(Duration::from_secs(self.idle_timeout_seconds))
+            .max_lifetime(Duration::from_secs(self.max_lifetime_seconds))
+    }
+
+    /// Connection options for new pool connections. A pool picks up a
+    /// rotated password through `PgPool::set_connect_options`.
+    pub fn connect_options(&self) -> Result<PgConnectOptions> {
+        self.connect_options_for(&self.url)
+    }
+
+    /// Connection options for the primary or a replica URL
+    pub fn connect_options_for(&self, url: &str) -> Result<PgConnectOptions> {
+        let mut connect_options: PgConnectOptions = url.parse()
+            .context("Failed to parse database URL")?;
+
+        if let Some(password) = &self.password {
+            connect_options = connect_options.password(password);
+        }
+
+        if self.enable_logging {
+            connect_options = connect_options.log_statements(tracing::log::LevelFilter::Info);
+        }
+
+        // sqlx logs the statement text, in which bound parameters only
+        // appear as $1, $2, ...; their values are never logged
+        let slow_level = match self.slow_query_threshold_ms {
+            0 => tracing::log::LevelFilter::Off,
+            _ => tracing::log::LevelFilter::Warn,
+        };
+        connect_options =
+            connect_options.log_slow_statements(slow_level, self.slow_query_threshold());
+
+        Ok(connect_options
+            .statement_cache_capacity(100)
+            .application_name("dubai-healthcare-emergency"))
+    }
+
     /// Test database connection
     pub async fn test_connection(&self) -> Result<()> {
         info!("Testing database connection...");
 
-        let connect_options: PgConnectOptions = self.url.parse()
-            .context("Failed to parse database URL")?;
+        let connect_options = self.connect_options()?;
 
         let mut connection = PgConnection::connect_with(&connect_options)
             .await
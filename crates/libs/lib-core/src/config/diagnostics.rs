@@ -0,0 +1,428 @@
+use std::fmt;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use sqlx::{Connection, PgConnection};
+use tracing::warn;
+
+use super::app_config::{AppConfig, Environment, RedisTopology};
+use crate::store::migration_status;
+
+/// How long any single startup check may take
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of a startup check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    Skipped,
+}
+
+/// One row of the startup report
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub target: String, // What was checked, with credentials redacted
+    pub status: CheckStatus,
+    pub detail: String,
+    pub critical: bool, // Production refuses to start when a critical check fails
+}
+
+/// Report of the checks run by [`AppConfig::diagnose`]
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    pub environment: Environment,
+    pub checks: Vec<CheckResult>,
+}
+
+impl CheckResult {
+    fn new(name: impl Into<String>, target: impl Into<String>, critical: bool) -> Self {
+        Self {
+            name: name.into(),
+            target: target.into(),
+            status: CheckStatus::Skipped,
+            detail: String::new(),
+            critical,
+        }
+    }
+
+    fn pass(mut self, detail: impl Into<String>) -> Self {
+        self.status = CheckStatus::Pass;
+        self.detail = detail.into();
+        self
+    }
+
+    fn fail(mut self, detail: impl Into<String>) -> Self {
+        self.status = CheckStatus::Fail;
+        self.detail = detail.into();
+        self
+    }
+
+    fn skip(mut self, detail: impl Into<String>) -> Self {
+        self.status = CheckStatus::Skipped;
+        self.detail = detail.into();
+        self
+    }
+}
+
+impl AppConfig {
+    /// Check the services the server depends on: the database and its
+    /// migrations, read replicas, Redis, the JWT keys and, when enabled, the
+    /// DHA API. Each check is bounded by a timeout, so an unreachable
+    /// service fails its check rather than hanging startup.
+    pub async fn diagnose(&self) -> Diagnostics {
+        let mut checks = self.check_database().await;
+        for (index, url) in self.database.replica_urls.iter().enumerate() {
+            checks.push(self.check_replica(index + 1, url).await);
+        }
+        checks.push(self.check_redis().await);
+        checks.push(self.check_jwt_keys());
+        checks.push(self.check_dha().await);
+
+        Diagnostics {
+            environment: self.environment.clone(),
+            checks,
+        }
+    }
+
+    /// Connectivity to the primary, then whether the schema matches the
+    /// migrations embedded in this build
+    async fn check_database(&self) -> Vec<CheckResult> {
+        let target = redact_url(&self.database.url);
+        let database = CheckResult::new("database", target.clone(), true);
+        let migrations = CheckResult::new("migrations", target, true);
+
+        let started = Instant::now();
+        let connected = match self.database.connect_options() {
+            Ok(options) => {
+                with_timeout(async {
+                    PgConnection::connect_with(&options)
+                        .await
+                        .map_err(|e| e.to_string())
+                })
+                .await
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        let mut connection = match connected {
+            Ok(connection) => connection,
+            Err(e) => return vec![database.fail(e), migrations.skip("database unreachable")],
+        };
+        let database = database.pass(format!("connected in {} ms", started.elapsed().as_millis()));
+
+        let status = with_timeout(async {
+            migration_status(&mut connection)
+                .await
+                .map_err(|e| e.to_string())
+        })
+        .await;
+        let migrations = match status {
+            Ok(status) => {
+                if let Some(failed) = status.applied.iter().find(|m| m.success != Some(true)) {
+                    migrations.fail(format!("migration {} did not complete", failed.version))
+                } else if let Some(modified) = status.applied.iter().find(|m| m.modified) {
+                    migrations.fail(format!(
+                        "migration {} changed after it was applied",
+                        modified.version
+                    ))
+                } else if let Some(first) = status.pending.first() {
+                    migrations.fail(format!(
+                        "{} pending, from version {}",
+                        status.pending.len(),
+                        first.version
+                    ))
+                } else {
+                    match status.current_version {
+                        Some(version) => migrations.pass(format!("version {}", version)),
+                        None => migrations.fail("no migrations applied"),
+                    }
+                }
+            }
+            Err(e) => migrations.fail(e),
+        };
+        let _ = connection.close().await;
+
+        vec![database, migrations]
+    }
+
+    /// A replica that is down only costs the primary some report queries
+    async fn check_replica(&self, number: usize, url: &str) -> CheckResult {
+        let check = CheckResult::new(format!("read replica {}", number), redact_url(url), false);
+        let connected = match self.database.connect_options_for(url) {
+            Ok(options) => {
+                with_timeout(async {
+                    let mut connection = PgConnection::connect_with(&options).await?;
+                    sqlx::query("SELECT 1").execute(&mut connection).await?;
+                    connection.close().await
+                })
+                .await
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        match connected {
+            Ok(()) => check.pass("connected"),
+            Err(e) => check.fail(e),
+        }
+    }
+
+    async fn check_redis(&self) -> CheckResult {
+        let target = match self.redis.topology {
+            RedisTopology::Standalone => redact_url(&self.redis.url),
+            topology => format!(
+                "{:?}: {}",
+                topology,
+                self.redis
+                    .nodes
+                    .iter()
+                    .map(|node| redact_url(node))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let check = CheckResult::new("redis", target, true);
+
+        let started = Instant::now();
+        let pinged = with_timeout(async {
+            let mut pool = self.redis.connect().await.map_err(|e| format!("{:#}", e))?;
+            redis::cmd("PING")
+                .query_async::<String>(&mut pool)
+                .await
+                .map_err(|e| e.to_string())
+        })
+        .await;
+        match pinged {
+            Ok(_) => check.pass(format!("PING in {} ms", started.elapsed().as_millis())),
+            Err(e) => check.fail(e),
+        }
+    }
+
+    fn check_jwt_keys(&self) -> CheckResult {
+        let target = match &self.jwt.key_dir {
+            Some(key_dir) => key_dir.clone(),
+            None => "JWT_SECRET".to_string(),
+        };
+        let check = CheckResult::new("jwt keys", target, true);
+        match self.jwt.key_ring() {
+            Ok(ring) => check.pass(format!(
+                "{}, signing with {}, {} verification key(s)",
+                ring.algorithm(),
+                ring.signing_kid(),
+                ring.verification_kids().len()
+            )),
+            Err(e) => check.fail(format!("{:#}", e)),
+        }
+    }
+
+    /// Any HTTP response counts as reachable: the check is about the
+    /// network path, not the credentials. Not critical, so an outage at the
+    /// DHA does not keep emergency care offline.
+    async fn check_dha(&self) -> CheckResult {
+        let healthcare = &self.healthcare;
+        let target = healthcare
+            .dha_api_url
+            .as_deref()
+            .map(redact_url)
+            .unwrap_or_default();
+        let check = CheckResult::new("dha api", target, false);
+        if !healthcare.dha_integration_enabled {
+            return check.skip("integration disabled");
+        }
+        let Some(url) = healthcare.dha_api_url.as_deref() else {
+            return check.fail("DHA_API_URL is not set");
+        };
+
+        let response = with_timeout(async {
+            reqwest::Client::new()
+                .get(url)
+                .send()
+                .await
+                .map_err(|e| e.to_string())
+        })
+        .await;
+        match response {
+            Ok(response) => check.pass(format!("HTTP {}", response.status().as_u16())),
+            Err(e) => check.fail(e),
+        }
+    }
+}
+
+impl Diagnostics {
+    /// Critical checks that did not pass
+    pub fn critical_failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.checks
+            .iter()
+            .filter(|check| check.critical && check.status != CheckStatus::Pass)
+    }
+
+    /// Refuse to start production with a failed critical check. Other
+    /// environments start anyway, with a warning per failed check, so a
+    /// developer can run without every service.
+    pub fn ensure_ready(&self) -> Result<()> {
+        let failed: Vec<&str> = self
+            .critical_failures()
+            .map(|check| check.name.as_str())
+            .collect();
+        if failed.is_empty() {
+            return Ok(());
+        }
+        if self.environment == Environment::Production {
+            anyhow::bail!("Startup checks failed: {}", failed.join(", "));
+        }
+        for name in failed {
+            warn!(
+                "Startup check '{}' failed; continuing outside production",
+                name
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Renders the report as a plain-text table
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = ["CHECK", "STATUS", "TARGET", "DETAIL"];
+        let rows: Vec<[String; 4]> = self
+            .checks
+            .iter()
+            .map(|check| {
+                let status = match (check.status, check.critical) {
+                    (CheckStatus::Pass, _) => "ok",
+                    (CheckStatus::Fail, true) => "FAIL",
+                    (CheckStatus::Fail, false) => "warn",
+                    (CheckStatus::Skipped, _) => "skipped",
+                };
+                [
+                    check.name.clone(),
+                    status.to_string(),
+                    check.target.clone(),
+                    check.detail.clone(),
+                ]
+            })
+            .collect();
+
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let header = header.map(String::from);
+        for row in std::iter::once(&header).chain(&rows) {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+async fn with_timeout<T, E: fmt::Display>(
+    check: impl Future<Output = std::result::Result<T, E>>,
+) -> std::result::Result<T, String> {
+    match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err(format!("timed out after {} s", CHECK_TIMEOUT.as_secs())),
+    }
+}
+
+/// Hide the password in a connection URL, e.g. `redis://:***@host:6379`
+pub fn redact_url(url: &str) -> String {
+    let Some(scheme_end) = url.find("://").map(|i| i + 3) else {
+        return url.to_string();
+    };
+    let authority_end = url[scheme_end..]
+        .find(['/', '?', '#'])
+        .map_or(url.len(), |i| scheme_end + i);
+    let Some(at) = url[scheme_end..authority_end]
+        .rfind('@')
+        .map(|i| scheme_end + i)
+    else {
+        return url.to_string();
+    };
+
+    let userinfo = &url[scheme_end..at];
+    match userinfo.find(':') {
+        Some(colon) => format!(
+            "{}{}:***{}",
+            &url[..scheme_end],
+            &userinfo[..colon],
+            &url[at..]
+        ),
+        None => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_url() {
+        assert_eq!(
+            redact_url("postgresql://app:s3cret@db:5432/emergency"),
+            "postgresql://app:***@db:5432/emergency"
+        );
+        assert_eq!(
+            redact_url("redis://:p@ss@cache:6379/0"),
+            "redis://:***@cache:6379/0"
+        );
+        assert_eq!(redact_url("redis://cache:6379"), "redis://cache:6379");
+        assert_eq!(redact_url("redis://user@cache"), "redis://user@cache");
+    }
+
+    fn report(environment: Environment, redis: CheckStatus) -> Diagnostics {
+        let mut checks = vec![
+            CheckResult::new("database", "postgresql://app:***@db/emergency", true)
+                .pass("connected in 3 ms"),
+            CheckResult::new("dha api", "https://api.dha.gov.ae", false)
+                .fail("timed out after 5 s"),
+        ];
+        let mut redis_check = CheckResult::new("redis", "redis://cache:6379", true);
+        redis_check.status = redis;
+        checks.push(redis_check);
+        Diagnostics {
+            environment,
+            checks,
+        }
+    }
+
+    #[test]
+    fn test_ensure_ready() {
+        // A non-critical failure never blocks startup
+        assert!(report(Environment::Production, CheckStatus::Pass)
+            .ensure_ready()
+            .is_ok());
+
+        let failed = report(Environment::Production, CheckStatus::Fail);
+        let error = failed.ensure_ready().unwrap_err().to_string();
+        assert!(error.contains("redis") && !error.contains("dha"));
+
+        assert!(report(Environment::Development, CheckStatus::Fail)
+            .ensure_ready()
+            .is_ok());
+        // A critical check that could not run has not passed
+        assert!(report(Environment::Production, CheckStatus::Skipped)
+            .ensure_ready()
+            .is_err());
+    }
+
+    #[test]
+    fn test_table() {
+        let table = report(Environment::Staging, CheckStatus::Fail).to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("CHECK     STATUS"));
+        assert!(lines[1].contains("ok") && lines[1].contains("app:***@db"));
+        assert!(lines[2].contains("warn"));
+        assert!(lines[3].contains("FAIL"));
+        assert_eq!(lines[1].find("ok"), lines[3].find("FAIL"));
+    }
+}
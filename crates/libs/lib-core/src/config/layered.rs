@@ -0,0 +1,431 @@
+//! Layered configuration: built-in defaults, then `config/default.toml`,
+//! then `config/{environment}.toml`, then environment variables. Each
+//! layer only needs the keys it changes.
+
+use std::env;
+use std::path::Path;
+
+use ::config::{Config, ConfigError, File, Map, Source, Value, ValueKind};
+use anyhow::{Context, Result};
+
+use super::app_config::{AppConfig, Environment};
+
+/// Environment variables and the keys they override. These are the
+/// variables `AppConfig::from_env` reads, so deployments configured through
+/// the environment keep working once configuration files are added.
+const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("SERVER_HOST", "server.host"),
+    ("SERVER_PORT", "server.port"),
+    ("CORS_ORIGINS", "server.cors_origins"),
+    ("REQUEST_TIMEOUT", "server.request_timeout_seconds"),
+    ("MAX_REQUEST_SIZE_MB", "server.max_request_size_mb"),
+    ("ENABLE_METRICS", "server.enable_metrics"),
+    ("MAINTENANCE_MODE", "server.maintenance_mode"),
+    ("TLS_CERT_PATH", "server.tls.cert_path"),
+    ("TLS_KEY_PATH", "server.tls.key_path"),
+    ("TLS_CLIENT_CA_PATH", "server.tls.client_ca_path"),
+    (
+        "IP_ALLOWLIST_ER_DIRECTOR",
+        "server.ip_allowlist_er_director",
+    ),
+    ("IP_ALLOWLIST_PARAMEDIC", "server.ip_allowlist_paramedic"),
+    ("IP_ALLOWLIST_NURSE", "server.ip_allowlist_nurse"),
+    ("IP_ALLOWLIST_SPECIALIST", "server.ip_allowlist_specialist"),
+    ("IP_ALLOWLIST_ADMIN", "server.ip_allowlist_admin"),
+    ("DATABASE_URL", "database.url"),
+    ("DATABASE_REPLICA_URLS", "database.replica_urls"),
+    ("DB_MAX_CONNECTIONS", "database.max_connections"),
+    ("DB_MIN_CONNECTIONS", "database.min_connections"),
+    ("DB_ACQUIRE_TIMEOUT", "database.acquire_timeout_seconds"),
+    ("DB_IDLE_TIMEOUT", "database.idle_timeout_seconds"),
+    ("DB_MAX_LIFETIME", "database.max_lifetime_seconds"),
+    ("DB_CONNECT_TIMEOUT", "database.connect_timeout_seconds"),
+    ("DB_ENABLE_LOGGING", "database.enable_logging"),
+    ("DB_RUN_MIGRATIONS", "database.run_migrations"),
+    ("DB_SLOW_QUERY_THRESHOLD_MS", "database.slow_query_threshold_ms"),
+    ("DB_MAX_RETRIES", "database.max_retries"),
+    ("DB_RETRY_BASE_DELAY_MS", "database.retry_base_delay_ms"),
+    ("DB_BREAKER_THRESHOLD", "database.breaker_failure_threshold"),
+    ("DB_BREAKER_OPEN_SECONDS", "database.breaker_open_seconds"),
+    ("JWT_SECRET", "jwt.secret"),
+    ("JWT_EXPIRATION", "jwt.expiration_seconds"),
+    ("JWT_REFRESH_EXPIRATION", "jwt.refresh_expiration_seconds"),
+    ("JWT_SERVICE_EXPIRATION", "jwt.service_expiration_seconds"),
+    ("JWT_ISSUER", "jwt.issuer"),
+    ("JWT_AUDIENCE", "jwt.audience"),
+    ("JWT_ALGORITHM", "jwt.algorithm"),
+    ("JWT_KEY_DIR", "jwt.key_dir"),
+    ("JWT_ACTIVE_KID", "jwt.active_kid"),
+    ("REDIS_URL", "redis.url"),
+    ("REDIS_TOPOLOGY", "redis.topology"),
+    ("REDIS_NODES", "redis.nodes"),
+    ("REDIS_SENTINEL_MASTER", "redis.sentinel_master"),
+    ("REDIS_MAX_CONNECTIONS", "redis.max_connections"),
+    ("REDIS_POOL_SIZE", "redis.pool_size"),
+    (
+        "REDIS_CONNECTION_TIMEOUT",
+        "redis.connection_timeout_seconds",
+    ),
+    ("REDIS_COMMAND_TIMEOUT", "redis.command_timeout_seconds"),
+    ("HOSPITAL_CACHE_TTL", "redis.hospital_cache_ttl_seconds"),
+    ("IDEMPOTENCY_TTL", "redis.idempotency_ttl_seconds"),
+    ("MAX_FAILED_LOGINS", "security.max_failed_logins"),
+    (
+        "FAILED_LOGIN_WINDOW_MINUTES",
+        "security.failed_login_window_minutes",
+    ),
+    ("LOCKOUT_MINUTES", "security.lockout_minutes"),
+    (
+        "LOGIN_BACKOFF_BASE_SECONDS",
+        "security.login_backoff_base_seconds",
+    ),
+    (
+        "LOGIN_BACKOFF_MAX_SECONDS",
+        "security.login_backoff_max_seconds",
+    ),
+    ("PASSWORD_MIN_LENGTH", "security.password_min_length"),
+    (
+        "PASSWORD_REQUIRE_UPPERCASE",
+        "security.password_require_uppercase",
+    ),
+    (
+        "PASSWORD_REQUIRE_LOWERCASE",
+        "security.password_require_lowercase",
+    ),
+    ("PASSWORD_REQUIRE_DIGIT", "security.password_require_digit"),
+    (
+        "PASSWORD_REQUIRE_SYMBOL",
+        "security.password_require_symbol",
+    ),
+    ("PASSWORD_HISTORY_SIZE", "security.password_history_size"),
+    (
+        "PASSWORD_DENY_LIST_FILE",
+        "security.password_deny_list_file",
+    ),
+    ("ARGON2_MEMORY_KIB", "security.argon2_memory_kib"),
+    ("ARGON2_ITERATIONS", "security.argon2_iterations"),
+    ("ARGON2_PARALLELISM", "security.argon2_parallelism"),
+    (
+        "BREAK_GLASS_MAX_MINUTES",
+        "security.break_glass_max_minutes",
+    ),
+    ("DELEGATION_MAX_HOURS", "security.delegation_max_hours"),
+    ("URL_SIGNING_KEY", "security.url_signing_key"),
+    ("SIGNED_URL_TTL_MINUTES", "security.signed_url_ttl_minutes"),
+    (
+        "STEP_UP_MAX_AGE_MINUTES",
+        "security.step_up_max_age_minutes",
+    ),
+    ("TRUSTED_DEVICE_DAYS", "security.trusted_device_days"),
+    (
+        "MAX_SESSIONS_ER_DIRECTOR",
+        "security.max_sessions_er_director",
+    ),
+    ("MAX_SESSIONS_PARAMEDIC", "security.max_sessions_paramedic"),
+    ("MAX_SESSIONS_NURSE", "security.max_sessions_nurse"),
+    (
+        "MAX_SESSIONS_SPECIALIST",
+        "security.max_sessions_specialist",
+    ),
+    ("MAX_SESSIONS_ADMIN", "security.max_sessions_admin"),
+    ("RUST_LOG", "logging.level"),
+    ("LOG_FORMAT", "logging.format"),
+    ("LOG_CONSOLE", "logging.enable_console"),
+    ("LOG_FILE", "logging.enable_file"),
+    ("LOG_FILE_PATH", "logging.file_path"),
+    ("LOG_REQUESTS", "logging.enable_request_logging"),
+    ("HOSPITAL_NAME", "healthcare.hospital_name"),
+    ("HOSPITAL_ID", "healthcare.hospital_id"),
+    (
+        "DHA_INTEGRATION_ENABLED",
+        "healthcare.dha_integration_enabled",
+    ),
+    ("DHA_API_URL", "healthcare.dha_api_url"),
+    ("DHA_API_KEY", "healthcare.dha_api_key"),
+    (
+        "EMERGENCY_CONTACT_REQUIRED",
+        "healthcare.emergency_contact_required",
+    ),
+    ("MAX_PATIENT_AGE", "healthcare.max_patient_age"),
+    (
+        "SESSION_TIMEOUT_MINUTES",
+        "healthcare.default_session_timeout_minutes",
+    ),
+    ("ENABLE_TRIAGE_AI", "healthcare.enable_triage_ai"),
+    ("SHARED_DOCUMENTS_DIR", "healthcare.shared_documents_dir"),
+    ("NOTIFICATION_WEBHOOKS", "healthcare.notification_webhooks"),
+    ("ETA_AVERAGE_SPEED_KMH", "healthcare.eta.average_speed_kmh"),
+    ("ETA_ROAD_FACTOR", "healthcare.eta.road_factor"),
+    ("ETA_PEAK_FACTOR", "healthcare.eta.peak_factor"),
+    ("ETA_NIGHT_FACTOR", "healthcare.eta.night_factor"),
+    ("ETA_UTC_OFFSET_HOURS", "healthcare.eta.utc_offset_hours"),
+    ("CATCHMENT_ZONES_PATH", "healthcare.catchment.zones_path"),
+    (
+        "CATCHMENT_MAX_DISTANCE_KM",
+        "healthcare.catchment.max_distance_km",
+    ),
+    ("REFERENCE_RANGES_PATH", "healthcare.reference_ranges_path"),
+    ("ATTACHMENTS_BUCKET", "storage.attachments_bucket"),
+    ("S3_REGION", "storage.s3_region"),
+    ("S3_ENDPOINT", "storage.s3_endpoint"),
+    ("SECRETS_PROVIDER", "secrets.provider"),
+    ("SECRET_JWT_SECRET", "secrets.jwt_secret"),
+    ("SECRET_DATABASE_PASSWORD", "secrets.database_password"),
+    ("SECRET_DHA_API_KEY", "secrets.dha_api_key"),
+    ("SECRETS_CACHE_TTL", "secrets.cache_ttl_seconds"),
+    (
+        "SECRETS_ROTATION_INTERVAL",
+        "secrets.rotation_interval_seconds",
+    ),
+    ("VAULT_ADDR", "secrets.vault_addr"),
+    ("VAULT_TOKEN", "secrets.vault_token"),
+    ("VAULT_MOUNT", "secrets.vault_mount"),
+    ("AWS_REGION", "secrets.aws_region"),
+    ("AZURE_KEY_VAULT_URL", "secrets.azure_vault_url"),
+    ("AZURE_TENANT_ID", "secrets.azure_tenant_id"),
+    ("AZURE_CLIENT_ID", "secrets.azure_client_id"),
+    ("AZURE_CLIENT_SECRET", "secrets.azure_client_secret"),
+    ("RETENTION_ENABLED", "retention.enabled"),
+    ("RETENTION_DRY_RUN", "retention.dry_run"),
+    ("RETENTION_INTERVAL_MINUTES", "retention.interval_minutes"),
+    ("RETENTION_BATCH_SIZE", "retention.batch_size"),
+    ("RETENTION_PATIENT_DAYS", "retention.patients.days"),
+    ("RETENTION_VITALS_DAYS", "retention.vitals.days"),
+    ("RATE_LIMIT_ENABLED", "rate_limit.enabled"),
+    (
+        "RATE_LIMIT_ANONYMOUS_PER_MINUTE",
+        "rate_limit.anonymous_per_minute",
+    ),
+    (
+        "RATE_LIMIT_SERVICE_PER_MINUTE",
+        "rate_limit.service_per_minute",
+    ),
+    (
+        "RATE_LIMIT_ER_DIRECTOR_PER_MINUTE",
+        "rate_limit.er_director_per_minute",
+    ),
+    (
+        "RATE_LIMIT_PARAMEDIC_PER_MINUTE",
+        "rate_limit.paramedic_per_minute",
+    ),
+    ("RATE_LIMIT_NURSE_PER_MINUTE", "rate_limit.nurse_per_minute"),
+    (
+        "RATE_LIMIT_SPECIALIST_PER_MINUTE",
+        "rate_limit.specialist_per_minute",
+    ),
+    ("RATE_LIMIT_ADMIN_PER_MINUTE", "rate_limit.admin_per_minute"),
+];
+
+/// Keys whose environment variable holds a comma-separated list
+const LIST_KEYS: &[&str] = &[
+    "server.cors_origins",
+    "server.ip_allowlist_er_director",
+    "server.ip_allowlist_paramedic",
+    "server.ip_allowlist_nurse",
+    "server.ip_allowlist_specialist",
+    "server.ip_allowlist_admin",
+    "database.replica_urls",
+    "redis.nodes",
+    "healthcare.notification_webhooks",
+];
+
+/// The environment variables that override configuration keys, captured
+/// once so a load sees a consistent snapshot
+#[derive(Debug, Clone, Default)]
+pub struct EnvOverrides {
+    vars: Vec<(&'static str, &'static str, String)>, // (variable, key, value)
+}
+
+impl EnvOverrides {
+    /// Capture the overrides set in the process environment
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// Capture overrides from a lookup function, e.g. a map in tests
+    pub fn from_vars(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let vars = ENV_OVERRIDES
+            .iter()
+            .filter_map(|&(name, key)| lookup(name).map(|value| (name, key, value)))
+            .collect();
+        Self { vars }
+    }
+}
+
+impl Source for EnvOverrides {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        Ok(self
+            .vars
+            .iter()
+            .map(|(name, key, value)| {
+                let origin = format!("environment variable {}", name);
+                let kind = if LIST_KEYS.contains(key) {
+                    ValueKind::Array(
+                        value
+                            .split(',')
+                            .map(str::trim)
+                            .filter(|item| !item.is_empty())
+                            .map(|item| Value::new(Some(&origin), item))
+                            .collect(),
+                    )
+                } else {
+                    ValueKind::String(value.clone())
+                };
+                (key.to_string(), Value::new(Some(&origin), kind))
+            })
+            .collect())
+    }
+}
+
+/// Build the configuration from the layers in `dir`. Missing files are
+/// skipped. Type errors name the offending key and the file or variable it
+/// came from, e.g. "invalid type: string \"abc\", expected u16 for key
+/// `server.port` in config/production.toml".
+///
+/// The result is not validated yet, since secrets may still have to be
+/// read from a secrets manager.
+pub fn read_layered(
+    dir: &Path,
+    environment: Environment,
+    overrides: EnvOverrides,
+) -> Result<AppConfig> {
+    let defaults = Config::try_from(&AppConfig::defaults_for(environment.clone()))
+        .context("Failed to serialize default configuration")?;
+
+    let config = Config::builder()
+        .add_source(defaults)
+        .add_source(File::from(dir.join("default.toml")).required(false))
+        .add_source(File::from(dir.join(format!("{}.toml", environment.name()))).required(false))
+        .add_source(overrides)
+        .build()
+        .context("Failed to read configuration files")?;
+
+    let mut app_config: AppConfig = config
+        .try_deserialize()
+        .map_err(|e| anyhow::anyhow!("Invalid configuration: {}", e))?;
+    app_config.environment = environment;
+    Ok(app_config)
+}
+
+/// [`read_layered`] followed by validation, for configurations that keep
+/// their secrets in the layers
+pub fn load_layered(
+    dir: &Path,
+    environment: Environment,
+    overrides: EnvOverrides,
+) -> Result<AppConfig> {
+    let app_config = read_layered(dir, environment, overrides)?;
+    app_config.validate()?;
+    Ok(app_config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fs;
+
+    fn config_dir(files: &[(&str, &str)]) -> std::path::PathBuf {
+        let dir = env::temp_dir().join(format!("layered-config-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, contents) in files {
+            fs::write(dir.join(name), contents).unwrap();
+        }
+        dir
+    }
+
+    fn overrides(vars: &[(&str, &str)]) -> EnvOverrides {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        EnvOverrides::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_precedence() {
+        let dir = config_dir(&[
+            (
+                "default.toml",
+                "[server]\nport = 8080\nrequest_timeout_seconds = 20\n\n\
+                 [healthcare]\nhospital_name = \"Rashid Hospital\"\n",
+            ),
+            ("staging.toml", "[server]\nport = 8443\n"),
+        ]);
+
+        let config = load_layered(
+            &dir,
+            Environment::Staging,
+            overrides(&[
+                ("DATABASE_URL", "postgres://localhost/er"),
+                ("JWT_SECRET", "a-test-secret-that-is-at-least-32-chars"),
+                ("REQUEST_TIMEOUT", "45"),
+                ("CORS_ORIGINS", "https://a.example, https://b.example"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.server.port, 8443); // staging.toml over default.toml
+        assert_eq!(config.server.request_timeout_seconds, 45); // environment over files
+        assert_eq!(config.healthcare.hospital_name, "Rashid Hospital");
+        assert_eq!(
+            config.server.cors_origins,
+            vec!["https://a.example", "https://b.example"]
+        );
+        assert_eq!(config.redis.max_connections, 50); // built-in default
+        assert_eq!(config.logging.level, "info");
+        assert_eq!(config.environment, Environment::Staging);
+    }
+
+    #[test]
+    fn test_rate_limit_routes() {
+        let dir = config_dir(&[(
+            "default.toml",
+            "[rate_limit]\nnurse_per_minute = 200\n\n\
+             [[rate_limit.routes]]\nmethod = \"POST\"\npath = \"/api/auth/login\"\n\
+             per_minute = 10\n",
+        )]);
+
+        let config = load_layered(
+            &dir,
+            Environment::Development,
+            overrides(&[
+                ("DATABASE_URL", "postgres://localhost/er"),
+                ("JWT_SECRET", "a-test-secret-that-is-at-least-32-chars"),
+                ("RATE_LIMIT_ADMIN_PER_MINUTE", "30"),
+            ]),
+        )
+        .unwrap();
+
+        let limits = &config.rate_limit;
+        assert_eq!(limits.nurse_per_minute, 200);
+        assert_eq!(limits.admin_per_minute, 30);
+        assert_eq!(limits.paramedic_per_minute, 600); // built in
+        let login = limits.route_budget("POST", "/api/auth/login").unwrap();
+        assert_eq!(login.per_minute, 10);
+    }
+
+    #[test]
+    fn test_errors_name_the_key() {
+        let dir = config_dir(&[("production.toml", "[server]\nport = \"eighty\"\n")]);
+        let error = load_layered(&dir, Environment::Production, EnvOverrides::default())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("server.port"), "{}", error);
+        assert!(error.contains("production.toml"), "{}", error);
+
+        let error = load_layered(
+            &dir,
+            Environment::Development,
+            overrides(&[("DB_MAX_CONNECTIONS", "many")]),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("database.max_connections"), "{}", error);
+        assert!(error.contains("DB_MAX_CONNECTIONS"), "{}", error);
+    }
+}
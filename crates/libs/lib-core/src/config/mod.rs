@@ -2,9 +2,21 @@
 
 pub mod database;
 pub mod app_config;
+pub mod diagnostics;
+pub mod layered;
+pub mod rate_limit;
+pub mod retention;
+pub mod secrets;
+pub mod watcher;
 
 pub use database::{DatabaseConfig, DatabaseHealth, HealthStatus};
+pub use diagnostics::{CheckResult, CheckStatus, Diagnostics};
+pub use layered::{load_layered, read_layered, EnvOverrides};
+pub use rate_limit::{RateLimitConfig, RouteRateLimit};
+pub use retention::{RetentionConfig, RetentionPolicy};
+pub use secrets::{ResolvedSecrets, SecretBackend, SecretProvider, SecretsConfig};
+pub use watcher::{ConfigWatcher, SharedConfig};
 pub use app_config::{
-    AppConfig, ServerConfig, JwtConfig, RedisConfig, LoggingConfig, 
-    HealthcareConfig, Environment, LogFormat
+    AppConfig, ServerConfig, JwtConfig, RedisConfig, RedisTopology, SecurityConfig, LoggingConfig, 
+    HealthcareConfig, CatchmentConfig, StorageConfig, TlsConfig, Environment, LogFormat
 };
\ No newline at end of file
@@ -0,0 +1,188 @@
+use std::env;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use lib_types::enums::UserRole;
+
+/// Request budgets enforced across every instance. Signed-in staff are
+/// limited per user by role, API clients per client id and everyone else
+/// per IP address. Routes listed in `routes` also get a budget of their
+/// own per caller, on top of the general one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    pub anonymous_per_minute: u32, // Per IP address
+    pub service_per_minute: u32,   // Per API client
+    pub er_director_per_minute: u32,
+    pub paramedic_per_minute: u32,
+    pub nurse_per_minute: u32,
+    pub specialist_per_minute: u32,
+    pub admin_per_minute: u32,
+    pub routes: Vec<RouteRateLimit>, // Configuration files only
+}
+
+/// Budget of one route, e.g. `POST /api/auth/login`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteRateLimit {
+    #[serde(default)]
+    pub method: Option<String>, // Every method when unset
+    pub path: String, // As routed, e.g. `/api/patients/:id`
+    pub per_minute: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            anonymous_per_minute: 60,
+            service_per_minute: 600,
+            er_director_per_minute: 300,
+            paramedic_per_minute: 600, // GPS and vitals from devices in the field
+            nurse_per_minute: 300,
+            specialist_per_minute: 300,
+            admin_per_minute: 120,
+            routes: Vec::new(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub(crate) fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+        let parse = |name: &str, default: u32| -> Result<u32> {
+            match env::var(name) {
+                Ok(value) => value.parse().with_context(|| format!("Invalid {}", name)),
+                Err(_) => Ok(default),
+            }
+        };
+
+        Ok(Self {
+            enabled: match env::var("RATE_LIMIT_ENABLED") {
+                Ok(value) => value.parse().context("Invalid RATE_LIMIT_ENABLED")?,
+                Err(_) => defaults.enabled,
+            },
+            anonymous_per_minute: parse(
+                "RATE_LIMIT_ANONYMOUS_PER_MINUTE",
+                defaults.anonymous_per_minute,
+            )?,
+            service_per_minute: parse(
+                "RATE_LIMIT_SERVICE_PER_MINUTE",
+                defaults.service_per_minute,
+            )?,
+            er_director_per_minute: parse(
+                "RATE_LIMIT_ER_DIRECTOR_PER_MINUTE",
+                defaults.er_director_per_minute,
+            )?,
+            paramedic_per_minute: parse(
+                "RATE_LIMIT_PARAMEDIC_PER_MINUTE",
+                defaults.paramedic_per_minute,
+            )?,
+            nurse_per_minute: parse("RATE_LIMIT_NURSE_PER_MINUTE", defaults.nurse_per_minute)?,
+            specialist_per_minute: parse(
+                "RATE_LIMIT_SPECIALIST_PER_MINUTE",
+                defaults.specialist_per_minute,
+            )?,
+            admin_per_minute: parse("RATE_LIMIT_ADMIN_PER_MINUTE", defaults.admin_per_minute)?,
+            routes: defaults.routes,
+        })
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        let budgets = [
+            self.anonymous_per_minute,
+            self.service_per_minute,
+            self.er_director_per_minute,
+            self.paramedic_per_minute,
+            self.nurse_per_minute,
+            self.specialist_per_minute,
+            self.admin_per_minute,
+        ];
+        if budgets.contains(&0) {
+            anyhow::bail!("Rate limits must be greater than 0");
+        }
+        for route in &self.routes {
+            if !route.path.starts_with('/') {
+                anyhow::bail!("Rate limited route {:?} must start with '/'", route.path);
+            }
+            if route.per_minute == 0 {
+                anyhow::bail!("Rate limit of route {} must be greater than 0", route.path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Requests per minute for each signed-in user with `role`
+    pub fn role_budget(&self, role: UserRole) -> u32 {
+        match role {
+            UserRole::ErDirector => self.er_director_per_minute,
+            UserRole::Paramedic => self.paramedic_per_minute,
+            UserRole::Nurse => self.nurse_per_minute,
+            UserRole::Specialist => self.specialist_per_minute,
+            UserRole::Admin => self.admin_per_minute,
+        }
+    }
+
+    /// The budget of the route matched as `path`, if it has one. A rule
+    /// naming the method wins over one for every method.
+    pub fn route_budget(&self, method: &str, path: &str) -> Option<&RouteRateLimit> {
+        let mut matching = self.routes.iter().filter(|route| route.path == path);
+        let exact = matching.clone().find(|route| {
+            route
+                .method
+                .as_deref()
+                .is_some_and(|m| m.eq_ignore_ascii_case(method))
+        });
+        exact.or_else(|| matching.find(|route| route.method.is_none()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(method: Option<&str>, path: &str, per_minute: u32) -> RouteRateLimit {
+        RouteRateLimit {
+            method: method.map(String::from),
+            path: path.to_string(),
+            per_minute,
+        }
+    }
+
+    #[test]
+    fn test_route_budget() {
+        let config = RateLimitConfig {
+            routes: vec![
+                route(None, "/api/patients/:id", 100),
+                route(Some("post"), "/api/auth/login", 10),
+                route(Some("PUT"), "/api/patients/:id", 20),
+            ],
+            ..Default::default()
+        };
+
+        let budget = |method, path| config.route_budget(method, path).map(|r| r.per_minute);
+        assert_eq!(budget("POST", "/api/auth/login"), Some(10));
+        assert_eq!(budget("GET", "/api/auth/login"), None);
+        assert_eq!(budget("PUT", "/api/patients/:id"), Some(20));
+        assert_eq!(budget("GET", "/api/patients/:id"), Some(100));
+        assert_eq!(budget("GET", "/api/patients"), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(RateLimitConfig::default().validate().is_ok());
+
+        let config = RateLimitConfig {
+            nurse_per_minute: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = RateLimitConfig {
+            routes: vec![route(None, "api/patients", 10)],
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
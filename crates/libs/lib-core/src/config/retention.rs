@@ -0,0 +1,126 @@
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How long clinical data stays in the live tables. The retention job
+/// archives patients whose visit ended more than `patients.days` ago and
+/// purges vitals recorded more than `vitals.days` ago.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    pub enabled: bool,
+    pub dry_run: bool, // Log what would be archived or purged, change nothing
+    pub interval_minutes: u64,
+    pub batch_size: u32, // Rows per statement, so a run never holds locks for long
+    pub patients: RetentionPolicy, // Moved to archived_patients
+    pub vitals: RetentionPolicy, // Deleted
+}
+
+/// Retention of one table
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub days: u32, // 0 keeps rows forever
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dry_run: true,
+            interval_minutes: 60,
+            batch_size: 500,
+            patients: RetentionPolicy { days: 365 },
+            vitals: RetentionPolicy { days: 1825 }, // 5 years
+        }
+    }
+}
+
+/// Parse an optional numeric variable, rejecting values out of range for `T`
+fn parse<T>(name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    env::var(name)
+        .ok()
+        .map(|value| value.parse().with_context(|| format!("Invalid {}", name)))
+        .transpose()
+}
+
+impl RetentionConfig {
+    pub(crate) fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+        let flag = |name: &str, default: bool| -> Result<bool> {
+            match env::var(name) {
+                Ok(value) => value.parse().with_context(|| format!("Invalid {}", name)),
+                Err(_) => Ok(default),
+            }
+        };
+
+        Ok(Self {
+            enabled: flag("RETENTION_ENABLED", defaults.enabled)?,
+            dry_run: flag("RETENTION_DRY_RUN", defaults.dry_run)?,
+            interval_minutes: parse("RETENTION_INTERVAL_MINUTES")?
+                .unwrap_or(defaults.interval_minutes),
+            batch_size: parse("RETENTION_BATCH_SIZE")?.unwrap_or(defaults.batch_size),
+            patients: RetentionPolicy {
+                days: parse("RETENTION_PATIENT_DAYS")?.unwrap_or(defaults.patients.days),
+            },
+            vitals: RetentionPolicy {
+                days: parse("RETENTION_VITALS_DAYS")?.unwrap_or(defaults.vitals.days),
+            },
+        })
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.interval_minutes == 0 {
+            anyhow::bail!("RETENTION_INTERVAL_MINUTES must be greater than 0");
+        }
+        if self.batch_size == 0 {
+            anyhow::bail!("RETENTION_BATCH_SIZE must be greater than 0");
+        }
+        Ok(())
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_minutes * 60)
+    }
+}
+
+impl RetentionPolicy {
+    /// Rows older than this are due, or `None` when rows are kept forever
+    pub fn cutoff(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        (self.days > 0).then(|| now - chrono::Duration::days(i64::from(self.days)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cutoff() {
+        let now = Utc::now();
+        assert_eq!(
+            RetentionPolicy { days: 30 }.cutoff(now),
+            Some(now - chrono::Duration::days(30))
+        );
+        assert_eq!(RetentionPolicy { days: 0 }.cutoff(now), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_values_out_of_range() {
+        env::set_var("RETENTION_TEST_DAYS", "4294967296");
+        assert!(parse::<u32>("RETENTION_TEST_DAYS").is_err());
+
+        env::set_var("RETENTION_TEST_DAYS", "1825");
+        assert_eq!(parse::<u32>("RETENTION_TEST_DAYS").unwrap(), Some(1825));
+        env::remove_var("RETENTION_TEST_DAYS");
+        assert_eq!(parse::<u32>("RETENTION_TEST_DAYS").unwrap(), None);
+    }
+}
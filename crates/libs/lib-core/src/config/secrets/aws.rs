@@ -0,0 +1,181 @@
+use std::env;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use super::{select_field, split_reference, SecretProvider};
+
+const SERVICE: &str = "secretsmanager";
+const TARGET: &str = "secretsmanager.GetSecretValue";
+const CONTENT_TYPE: &str = "application/x-amz-json-1.1";
+
+/// AWS Secrets Manager, called directly with Signature Version 4.
+///
+/// `emergency/db#password` reads key `password` of a JSON secret string;
+/// without `#` the whole secret string is returned.
+pub struct AwsSecretsManager {
+    client: reqwest::Client,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl AwsSecretsManager {
+    /// Use the credentials in `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`
+    /// and, for temporary credentials, `AWS_SESSION_TOKEN`
+    pub fn from_env(client: reqwest::Client, region: String) -> Result<Self> {
+        Ok(Self {
+            client,
+            region,
+            access_key_id: env::var("AWS_ACCESS_KEY_ID")
+                .context("AWS_ACCESS_KEY_ID is required for AWS Secrets Manager")?,
+            secret_access_key: env::var("AWS_SECRET_ACCESS_KEY")
+                .context("AWS_SECRET_ACCESS_KEY is required for AWS Secrets Manager")?,
+            session_token: env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+
+    fn host(&self) -> String {
+        format!("{}.{}.amazonaws.com", SERVICE, self.region)
+    }
+
+    /// The `Authorization` header for a request with these headers and body
+    fn authorization(&self, headers: &[(&str, String)], body: &str, at: DateTime<Utc>) -> String {
+        let amz_date = at.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = at.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/{}/aws4_request", date, self.region, SERVICE);
+
+        // Headers are already lowercase and sorted by name
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "POST\n/\n\n{}\n{}\n{}",
+            canonical_headers,
+            signed_headers,
+            hex(&Sha256::digest(body.as_bytes()))
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = signing_key(&self.secret_access_key, &date, &self.region, SERVICE);
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key_id,
+            scope,
+            signed_headers,
+            hex(&hmac_sha256(&key, string_to_sign.as_bytes()))
+        )
+    }
+}
+
+#[async_trait]
+impl SecretProvider for AwsSecretsManager {
+    fn name(&self) -> &'static str {
+        "AWS Secrets Manager"
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<String> {
+        let (secret_id, field) = split_reference(name);
+        let body = json!({ "SecretId": secret_id }).to_string();
+        let now = Utc::now();
+
+        let mut headers = vec![
+            ("content-type", CONTENT_TYPE.to_string()),
+            ("host", self.host()),
+            ("x-amz-date", now.format("%Y%m%dT%H%M%SZ").to_string()),
+        ];
+        if let Some(token) = &self.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+        headers.push(("x-amz-target", TARGET.to_string()));
+        let authorization = self.authorization(&headers, &body, now);
+
+        let mut request = self
+            .client
+            .post(format!("https://{}/", self.host()))
+            .header("authorization", authorization)
+            .body(body);
+        for (name, value) in headers.into_iter().filter(|(name, _)| *name != "host") {
+            request = request.header(name, value);
+        }
+
+        let response: Value = request
+            .send()
+            .await
+            .context("AWS Secrets Manager request failed")?
+            .error_for_status()
+            .context("AWS Secrets Manager rejected the request")?
+            .json()
+            .await
+            .context("Invalid AWS Secrets Manager response")?;
+        let secret = response["SecretString"]
+            .as_str()
+            .context("Secret has no SecretString; binary secrets are not supported")?;
+
+        match field {
+            Some(field) => {
+                let fields: Value =
+                    serde_json::from_str(secret).context("Secret string is not JSON")?;
+                select_field(&fields, field)
+            }
+            None => Ok(secret.to_string()),
+        }
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// SigV4 key derived for a day, region and service
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let key = hmac_sha256(
+        format!("AWS4{}", secret_access_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_key() {
+        // Example from the AWS Signature Version 4 documentation
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+}
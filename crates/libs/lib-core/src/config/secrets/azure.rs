@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use super::{select_field, split_reference, SecretProvider};
+
+const API_VERSION: &str = "7.4";
+const SCOPE: &str = "https://vault.azure.net/.default";
+
+/// Azure Key Vault, authenticated as an app registration with a client
+/// secret.
+///
+/// `emergency-db#password` reads key `password` of a JSON secret value;
+/// without `#` the whole value is returned.
+pub struct AzureKeyVault {
+    client: reqwest::Client,
+    vault_url: String,
+    tenant_id: String,
+    client_id: String,
+    client_secret: String,
+    token: Mutex<Option<(String, DateTime<Utc>)>>, // (access token, expires at)
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: i64,
+}
+
+#[derive(Deserialize)]
+struct SecretBundle {
+    value: String,
+}
+
+impl AzureKeyVault {
+    pub fn new(
+        client: reqwest::Client,
+        vault_url: String,
+        tenant_id: String,
+        client_id: String,
+        client_secret: String,
+    ) -> Self {
+        Self {
+            client,
+            vault_url: vault_url.trim_end_matches('/').to_string(),
+            tenant_id,
+            client_id,
+            client_secret,
+            token: Mutex::new(None),
+        }
+    }
+
+    /// An access token for Key Vault, renewed a minute before it expires
+    async fn access_token(&self) -> Result<String> {
+        let mut token = self.token.lock().await;
+        if let Some((access_token, expires_at)) = token.as_ref() {
+            if Utc::now() + Duration::minutes(1) < *expires_at {
+                return Ok(access_token.clone());
+            }
+        }
+
+        let response: TokenResponse = self
+            .client
+            .post(format!(
+                "https://login.microsoftonline.com/{}/oauth2/v2.0/token",
+                self.tenant_id
+            ))
+            .form(&[
+                ("grant_type", "client_credentials"),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("scope", SCOPE),
+            ])
+            .send()
+            .await
+            .context("Azure AD token request failed")?
+            .error_for_status()
+            .context("Azure AD rejected the client credentials")?
+            .json()
+            .await
+            .context("Invalid Azure AD token response")?;
+
+        let expires_at = Utc::now() + Duration::seconds(response.expires_in);
+        *token = Some((response.access_token.clone(), expires_at));
+        Ok(response.access_token)
+    }
+}
+
+#[async_trait]
+impl SecretProvider for AzureKeyVault {
+    fn name(&self) -> &'static str {
+        "Azure Key Vault"
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<String> {
+        let (secret_name, field) = split_reference(name);
+        let token = self.access_token().await?;
+
+        let secret: SecretBundle = self
+            .client
+            .get(format!("{}/secrets/{}", self.vault_url, secret_name))
+            .query(&[("api-version", API_VERSION)])
+            .bearer_auth(token)
+            .send()
+            .await
+            .context("Azure Key Vault request failed")?
+            .error_for_status()
+            .context("Azure Key Vault rejected the request")?
+            .json()
+            .await
+            .context("Invalid Azure Key Vault response")?;
+
+        match field {
+            Some(field) => {
+                let fields: serde_json::Value =
+                    serde_json::from_str(&secret.value).context("Secret value is not JSON")?;
+                select_field(&fields, field)
+            }
+            None => Ok(secret.value),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use super::SecretProvider;
+
+/// Keeps secrets for a while so every reload does not call the provider
+pub struct CachedSecretProvider {
+    inner: Arc<dyn SecretProvider>,
+    ttl: Duration,
+    entries: RwLock<HashMap<String, (String, Instant)>>, // name -> (value, fetched at)
+}
+
+impl CachedSecretProvider {
+    pub fn new(inner: Arc<dyn SecretProvider>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Forget every cached value, e.g. before checking for rotated secrets
+    pub fn clear(&self) {
+        self.entries.write().unwrap().clear();
+    }
+}
+
+#[async_trait]
+impl SecretProvider for CachedSecretProvider {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<String> {
+        if let Some((value, fetched_at)) = self.entries.read().unwrap().get(name) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(value.clone());
+            }
+        }
+
+        let value = self.inner.get_secret(name).await?;
+        self.entries
+            .write()
+            .unwrap()
+            .insert(name.to_string(), (value.clone(), Instant::now()));
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct CountingProvider(AtomicU32);
+
+    #[async_trait]
+    impl SecretProvider for CountingProvider {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn get_secret(&self, _name: &str) -> Result<String> {
+            Ok(format!("v{}", self.0.fetch_add(1, Ordering::SeqCst) + 1))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_and_clear() {
+        let provider = CachedSecretProvider::new(
+            Arc::new(CountingProvider(AtomicU32::new(0))),
+            Duration::from_secs(60),
+        );
+        assert_eq!(provider.get_secret("jwt").await.unwrap(), "v1");
+        assert_eq!(provider.get_secret("jwt").await.unwrap(), "v1");
+
+        provider.clear();
+        assert_eq!(provider.get_secret("jwt").await.unwrap(), "v2");
+    }
+}
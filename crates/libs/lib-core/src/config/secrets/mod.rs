@@ -0,0 +1,375 @@
+//! Secrets read from a secrets manager instead of the configuration.
+//!
+//! The configuration names the secrets (`secrets.jwt_secret`,
+//! `secrets.database_password`, `secrets.dha_api_key`) and the provider
+//! that holds them. Names may select a field of a structured secret with
+//! `#`, e.g. `emergency/jwt#secret`.
+
+pub mod aws;
+pub mod azure;
+pub mod cache;
+pub mod vault;
+
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::app_config::AppConfig;
+
+pub use aws::AwsSecretsManager;
+pub use azure::AzureKeyVault;
+pub use cache::CachedSecretProvider;
+pub use vault::VaultProvider;
+
+/// Timeout for requests to a secrets manager
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A store that secrets can be read from by name
+#[async_trait]
+pub trait SecretProvider: Send + Sync {
+    /// Provider name for logs and errors
+    fn name(&self) -> &'static str;
+
+    /// Read the current value of a secret
+    async fn get_secret(&self, name: &str) -> Result<String>;
+}
+
+/// Secrets managers the platform can read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretBackend {
+    Vault, // HashiCorp Vault, KV version 2
+    Aws,   // AWS Secrets Manager
+    Azure, // Azure Key Vault
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecretsConfig {
+    pub provider: Option<SecretBackend>, // None keeps secrets in the configuration
+    pub jwt_secret: Option<String>,      // Secret names; unset ones come from the configuration
+    pub database_password: Option<String>,
+    pub dha_api_key: Option<String>,
+    pub cache_ttl_seconds: u64,
+    pub rotation_interval_seconds: u64, // 0 disables scheduled rotation
+    pub vault_addr: Option<String>,
+    pub vault_token: Option<String>,
+    pub vault_mount: String,
+    pub aws_region: Option<String>, // Credentials come from the standard AWS_* variables
+    pub azure_vault_url: Option<String>,
+    pub azure_tenant_id: Option<String>,
+    pub azure_client_id: Option<String>,
+    pub azure_client_secret: Option<String>,
+}
+
+/// Values read for the secrets named in [`SecretsConfig`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolvedSecrets {
+    pub jwt_secret: Option<String>,
+    pub database_password: Option<String>,
+    pub dha_api_key: Option<String>,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        Self {
+            provider: None,
+            jwt_secret: None,
+            database_password: None,
+            dha_api_key: None,
+            cache_ttl_seconds: 300,          // 5 minutes
+            rotation_interval_seconds: 3600, // 1 hour
+            vault_addr: None,
+            vault_token: None,
+            vault_mount: "secret".to_string(),
+            aws_region: None,
+            azure_vault_url: None,
+            azure_tenant_id: None,
+            azure_client_id: None,
+            azure_client_secret: None,
+        }
+    }
+}
+
+impl SecretsConfig {
+    pub(crate) fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+        let provider = match env::var("SECRETS_PROVIDER") {
+            Ok(value) if !value.is_empty() => Some(
+                serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+                    .context("Invalid SECRETS_PROVIDER, expected vault, aws or azure")?,
+            ),
+            _ => None,
+        };
+
+        Ok(Self {
+            provider,
+            jwt_secret: env::var("SECRET_JWT_SECRET").ok(),
+            database_password: env::var("SECRET_DATABASE_PASSWORD").ok(),
+            dha_api_key: env::var("SECRET_DHA_API_KEY").ok(),
+            cache_ttl_seconds: match env::var("SECRETS_CACHE_TTL") {
+                Ok(value) => value.parse().context("Invalid SECRETS_CACHE_TTL")?,
+                Err(_) => defaults.cache_ttl_seconds,
+            },
+            rotation_interval_seconds: match env::var("SECRETS_ROTATION_INTERVAL") {
+                Ok(value) => value.parse().context("Invalid SECRETS_ROTATION_INTERVAL")?,
+                Err(_) => defaults.rotation_interval_seconds,
+            },
+            vault_addr: env::var("VAULT_ADDR").ok(),
+            vault_token: env::var("VAULT_TOKEN").ok(),
+            vault_mount: env::var("VAULT_MOUNT").unwrap_or(defaults.vault_mount),
+            aws_region: env::var("AWS_REGION").ok(),
+            azure_vault_url: env::var("AZURE_KEY_VAULT_URL").ok(),
+            azure_tenant_id: env::var("AZURE_TENANT_ID").ok(),
+            azure_client_id: env::var("AZURE_CLIENT_ID").ok(),
+            azure_client_secret: env::var("AZURE_CLIENT_SECRET").ok(),
+        })
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        let Some(provider) = self.provider else {
+            if self.has_references() {
+                anyhow::bail!("SECRETS_PROVIDER must be set when secrets are named");
+            }
+            return Ok(());
+        };
+
+        let missing = |value: &Option<String>| value.as_deref().unwrap_or_default().is_empty();
+        match provider {
+            SecretBackend::Vault if missing(&self.vault_addr) || missing(&self.vault_token) => {
+                anyhow::bail!("VAULT_ADDR and VAULT_TOKEN must be set for Vault")
+            }
+            SecretBackend::Aws if missing(&self.aws_region) => {
+                anyhow::bail!("AWS_REGION must be set for AWS Secrets Manager")
+            }
+            SecretBackend::Azure
+                if missing(&self.azure_vault_url)
+                    || missing(&self.azure_tenant_id)
+                    || missing(&self.azure_client_id)
+                    || missing(&self.azure_client_secret) =>
+            {
+                anyhow::bail!(
+                    "AZURE_KEY_VAULT_URL, AZURE_TENANT_ID, AZURE_CLIENT_ID and \
+                     AZURE_CLIENT_SECRET must be set for Azure Key Vault"
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check if any secret is read from the provider
+    pub fn has_references(&self) -> bool {
+        self.jwt_secret.is_some() || self.database_password.is_some() || self.dha_api_key.is_some()
+    }
+
+    /// Build the configured provider behind a cache, or `None` when no
+    /// secret is read from a secrets manager
+    pub fn provider(&self) -> Result<Option<Arc<CachedSecretProvider>>> {
+        self.validate()?;
+        let Some(backend) = self.provider.filter(|_| self.has_references()) else {
+            return Ok(None);
+        };
+
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to build secrets manager HTTP client")?;
+        let required = |value: &Option<String>| value.clone().unwrap_or_default();
+
+        let provider: Arc<dyn SecretProvider> = match backend {
+            SecretBackend::Vault => Arc::new(VaultProvider::new(
+                client,
+                required(&self.vault_addr),
+                required(&self.vault_token),
+                self.vault_mount.clone(),
+            )),
+            SecretBackend::Aws => Arc::new(AwsSecretsManager::from_env(
+                client,
+                required(&self.aws_region),
+            )?),
+            SecretBackend::Azure => Arc::new(AzureKeyVault::new(
+                client,
+                required(&self.azure_vault_url),
+                required(&self.azure_tenant_id),
+                required(&self.azure_client_id),
+                required(&self.azure_client_secret),
+            )),
+        };
+        Ok(Some(Arc::new(CachedSecretProvider::new(
+            provider,
+            Duration::from_secs(self.cache_ttl_seconds),
+        ))))
+    }
+
+    /// How often secrets are re-read to pick up rotations
+    pub fn rotation_interval(&self) -> Option<Duration> {
+        (self.rotation_interval_seconds > 0)
+            .then(|| Duration::from_secs(self.rotation_interval_seconds))
+    }
+}
+
+impl ResolvedSecrets {
+    /// Read every secret named in `config`
+    pub async fn fetch(config: &SecretsConfig, provider: &dyn SecretProvider) -> Result<Self> {
+        let read = |name: &Option<String>| {
+            let name = name.clone();
+            async move {
+                match name {
+                    Some(name) => provider
+                        .get_secret(&name)
+                        .await
+                        .with_context(|| {
+                            format!("Failed to read secret {} from {}", name, provider.name())
+                        })
+                        .map(Some),
+                    None => Ok(None),
+                }
+            }
+        };
+
+        Ok(Self {
+            jwt_secret: read(&config.jwt_secret).await?,
+            database_password: read(&config.database_password).await?,
+            dha_api_key: read(&config.dha_api_key).await?,
+        })
+    }
+
+    /// The values of the provider-backed secrets currently in `config`
+    pub fn taken_from(config: &AppConfig) -> Self {
+        let secrets = &config.secrets;
+        Self {
+            jwt_secret: secrets
+                .jwt_secret
+                .as_ref()
+                .map(|_| config.jwt.secret.clone()),
+            database_password: secrets
+                .database_password
+                .as_ref()
+                .and(config.database.password.clone()),
+            dha_api_key: secrets
+                .dha_api_key
+                .as_ref()
+                .and(config.healthcare.dha_api_key.clone()),
+        }
+    }
+
+    /// Put the values into the configuration
+    pub fn apply(&self, config: &mut AppConfig) {
+        if let Some(secret) = &self.jwt_secret {
+            config.jwt.secret = secret.clone();
+        }
+        if let Some(password) = &self.database_password {
+            config.database.password = Some(password.clone());
+        }
+        if let Some(key) = &self.dha_api_key {
+            config.healthcare.dha_api_key = Some(key.clone());
+        }
+    }
+}
+
+/// Split a secret name into the secret and the field selected with `#`
+pub(crate) fn split_reference(name: &str) -> (&str, Option<&str>) {
+    match name.split_once('#') {
+        Some((secret, field)) => (secret, Some(field)),
+        None => (name, None),
+    }
+}
+
+/// Pick the field named in a reference out of a JSON secret
+pub(crate) fn select_field(value: &serde_json::Value, field: &str) -> Result<String> {
+    match value.get(field) {
+        Some(serde_json::Value::String(value)) => Ok(value.clone()),
+        Some(other) => Ok(other.to_string()),
+        None => anyhow::bail!("Secret has no field {}", field),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct StaticProvider(HashMap<&'static str, &'static str>);
+
+    #[async_trait]
+    impl SecretProvider for StaticProvider {
+        fn name(&self) -> &'static str {
+            "static"
+        }
+
+        async fn get_secret(&self, name: &str) -> Result<String> {
+            self.0
+                .get(name)
+                .map(|value| value.to_string())
+                .ok_or_else(|| anyhow::anyhow!("not found"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_and_apply() {
+        let provider = StaticProvider(HashMap::from([
+            (
+                "emergency/jwt",
+                "a-secret-from-the-vault-that-is-long-enough",
+            ),
+            ("emergency/db#password", "db-password"),
+        ]));
+        let secrets = SecretsConfig {
+            provider: Some(SecretBackend::Vault),
+            jwt_secret: Some("emergency/jwt".to_string()),
+            database_password: Some("emergency/db#password".to_string()),
+            ..Default::default()
+        };
+
+        let resolved = ResolvedSecrets::fetch(&secrets, &provider).await.unwrap();
+        let mut config = AppConfig {
+            secrets,
+            ..Default::default()
+        };
+        resolved.apply(&mut config);
+        assert_eq!(
+            config.jwt.secret,
+            "a-secret-from-the-vault-that-is-long-enough"
+        );
+        assert_eq!(config.database.password.as_deref(), Some("db-password"));
+        assert_eq!(config.healthcare.dha_api_key, None);
+        assert_eq!(ResolvedSecrets::taken_from(&config), resolved);
+
+        let missing = SecretsConfig {
+            dha_api_key: Some("emergency/dha".to_string()),
+            ..config.secrets.clone()
+        };
+        let error = ResolvedSecrets::fetch(&missing, &provider)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("emergency/dha"));
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(SecretsConfig::default().validate().is_ok());
+
+        let unnamed_provider = SecretsConfig {
+            jwt_secret: Some("emergency/jwt".to_string()),
+            ..Default::default()
+        };
+        assert!(unnamed_provider.validate().is_err());
+
+        let vault = SecretsConfig {
+            provider: Some(SecretBackend::Vault),
+            vault_addr: Some("https://vault.internal:8200".to_string()),
+            ..Default::default()
+        };
+        assert!(vault.validate().is_err()); // No token
+
+        assert_eq!(
+            split_reference("emergency/db#password"),
+            ("emergency/db", Some("password"))
+        );
+        assert_eq!(split_reference("jwt-secret"), ("jwt-secret", None));
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{select_field, split_reference, SecretProvider};
+
+/// Field read when a Vault reference does not name one
+const DEFAULT_FIELD: &str = "value";
+
+/// HashiCorp Vault KV version 2 secrets engine.
+///
+/// `emergency/jwt#secret` reads field `secret` of `{mount}/data/emergency/jwt`.
+pub struct VaultProvider {
+    client: reqwest::Client,
+    addr: String,
+    token: String,
+    mount: String,
+}
+
+impl VaultProvider {
+    pub fn new(client: reqwest::Client, addr: String, token: String, mount: String) -> Self {
+        Self {
+            client,
+            addr: addr.trim_end_matches('/').to_string(),
+            token,
+            mount: mount.trim_matches('/').to_string(),
+        }
+    }
+
+    fn secret_url(&self, path: &str) -> String {
+        format!(
+            "{}/v1/{}/data/{}",
+            self.addr,
+            self.mount,
+            path.trim_matches('/')
+        )
+    }
+}
+
+#[async_trait]
+impl SecretProvider for VaultProvider {
+    fn name(&self) -> &'static str {
+        "Vault"
+    }
+
+    async fn get_secret(&self, name: &str) -> Result<String> {
+        let (path, field) = split_reference(name);
+        let body: Value = self
+            .client
+            .get(self.secret_url(path))
+            .header("X-Vault-Token", &self.token)
+            .send()
+            .await
+            .context("Vault request failed")?
+            .error_for_status()
+            .context("Vault rejected the request")?
+            .json()
+            .await
+            .context("Invalid Vault response")?;
+
+        // KV v2 nests the secret's fields under data.data
+        select_field(&body["data"]["data"], field.unwrap_or(DEFAULT_FIELD))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_url() {
+        let vault = VaultProvider::new(
+            reqwest::Client::new(),
+            "https://vault.internal:8200/".to_string(),
+            "token".to_string(),
+            "/secret/".to_string(),
+        );
+        assert_eq!(
+            vault.secret_url("emergency/jwt"),
+            "https://vault.internal:8200/v1/secret/data/emergency/jwt"
+        );
+    }
+}
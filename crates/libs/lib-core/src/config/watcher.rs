@@ -0,0 +1,301 @@
+//! Hot reload of the settings that are safe to change while running. The
+//! rest of the configuration is wired into components at startup, so
+//! changes to it are reported and wait for a restart.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use arc_swap::ArcSwap;
+use serde_json::Value;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use super::app_config::AppConfig;
+use super::layered::{read_layered, EnvOverrides};
+use super::secrets::{CachedSecretProvider, ResolvedSecrets, SecretProvider};
+
+/// How often the configuration files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The running configuration, replaced as a whole on reload
+pub type SharedConfig = Arc<ArcSwap<AppConfig>>;
+
+/// Re-reads the configuration on SIGHUP or when a configuration file
+/// changes, publishes the reloadable settings through a [`SharedConfig`]
+/// and notifies subscribers.
+///
+/// Reloadable settings: the log level, CORS origins, login rate limits,
+/// feature flags and maintenance mode. An invalid configuration is
+/// rejected and the running one kept.
+pub struct ConfigWatcher {
+    dir: PathBuf,
+    current: SharedConfig,
+    changes: watch::Sender<Arc<AppConfig>>,
+}
+
+impl ConfigWatcher {
+    pub fn new(config: AppConfig, dir: impl Into<PathBuf>) -> Self {
+        let config = Arc::new(config);
+        let (changes, _) = watch::channel(config.clone());
+        Self {
+            dir: dir.into(),
+            current: Arc::new(ArcSwap::new(config)),
+            changes,
+        }
+    }
+
+    /// Handle to the running configuration
+    pub fn config(&self) -> SharedConfig {
+        self.current.clone()
+    }
+
+    /// Receive the configuration after every reload that changed it
+    pub fn subscribe(&self) -> watch::Receiver<Arc<AppConfig>> {
+        self.changes.subscribe()
+    }
+
+    /// Re-read the configuration files and environment, returning whether
+    /// any reloadable setting changed
+    pub fn reload(&self) -> Result<bool> {
+        let current = self.current.load_full();
+        let mut loaded = read_layered(
+            &self.dir,
+            current.environment.clone(),
+            EnvOverrides::from_env(),
+        )?;
+        // Secrets are refreshed by rotation, not by re-reading the files
+        ResolvedSecrets::taken_from(&current).apply(&mut loaded);
+        loaded.validate()?;
+        Ok(self.apply(&loaded))
+    }
+
+    /// Publish rotated secrets, returning whether any value changed
+    pub fn apply_secrets(&self, secrets: &ResolvedSecrets) -> bool {
+        let current = self.current.load_full();
+        if ResolvedSecrets::taken_from(&current) == *secrets {
+            return false;
+        }
+
+        let mut next = (*current).clone();
+        secrets.apply(&mut next);
+        let next = Arc::new(next);
+        self.current.store(next.clone());
+        self.changes.send_replace(next);
+        true
+    }
+
+    /// Publish the reloadable settings of a freshly loaded configuration,
+    /// returning whether any of them changed
+    pub fn apply(&self, loaded: &AppConfig) -> bool {
+        let current = self.current.load_full();
+        let next = with_reloadable(&current, loaded);
+
+        let pending = changed_sections(&next, loaded);
+        if !pending.is_empty() {
+            warn!(
+                "Configuration changes to {} take effect after a restart",
+                pending.join(", ")
+            );
+        }
+        if changed_sections(&current, &next).is_empty() {
+            return false;
+        }
+
+        let next = Arc::new(next);
+        self.current.store(next.clone());
+        self.changes.send_replace(next);
+        true
+    }
+
+    /// Reload on SIGHUP and whenever `default.toml` or the environment's
+    /// file is created, modified or removed
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut hangup = Hangup::new();
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            let mut stamp = self.file_stamp();
+
+            loop {
+                tokio::select! {
+                    _ = hangup.recv() => info!("SIGHUP received, reloading configuration"),
+                    _ = interval.tick() => {
+                        let latest = self.file_stamp();
+                        if latest == stamp {
+                            continue;
+                        }
+                        stamp = latest;
+                        info!("Configuration files changed, reloading");
+                    }
+                }
+
+                match self.reload() {
+                    Ok(true) => info!("Configuration reloaded"),
+                    Ok(false) => info!("Configuration reloaded, no reloadable setting changed"),
+                    Err(e) => warn!("Keeping the running configuration: {:#}", e),
+                }
+            }
+        })
+    }
+
+    /// Re-read the named secrets from `provider` on the configured schedule
+    /// and publish them when they have been rotated. Does nothing when
+    /// rotation is disabled.
+    pub fn spawn_secret_rotation(
+        self: Arc<Self>,
+        provider: Arc<CachedSecretProvider>,
+    ) -> Option<JoinHandle<()>> {
+        let period = self.current.load().secrets.rotation_interval()?;
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.tick().await; // The first tick completes immediately
+
+            loop {
+                interval.tick().await;
+                provider.clear();
+
+                let secrets = self.current.load().secrets.clone();
+                match ResolvedSecrets::fetch(&secrets, provider.as_ref()).await {
+                    Ok(resolved) => {
+                        if self.apply_secrets(&resolved) {
+                            info!("Rotated secrets loaded from {}", provider.name());
+                        }
+                    }
+                    Err(e) => warn!("Keeping the current secrets: {:#}", e),
+                }
+            }
+        }))
+    }
+
+    fn file_stamp(&self) -> [Option<SystemTime>; 2] {
+        let environment = self.current.load().environment.name();
+        let modified = |name: String| {
+            std::fs::metadata(self.dir.join(name))
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        };
+        [
+            modified("default.toml".to_string()),
+            modified(format!("{}.toml", environment)),
+        ]
+    }
+}
+
+/// `current` with the reloadable settings taken from `loaded`
+fn with_reloadable(current: &AppConfig, loaded: &AppConfig) -> AppConfig {
+    let mut next = current.clone();
+
+    next.logging.level = loaded.logging.level.clone();
+    next.server.cors_origins = loaded.server.cors_origins.clone();
+    next.server.maintenance_mode = loaded.server.maintenance_mode;
+
+    next.security.max_failed_logins = loaded.security.max_failed_logins;
+    next.security.failed_login_window_minutes = loaded.security.failed_login_window_minutes;
+    next.security.lockout_minutes = loaded.security.lockout_minutes;
+    next.security.login_backoff_base_seconds = loaded.security.login_backoff_base_seconds;
+    next.security.login_backoff_max_seconds = loaded.security.login_backoff_max_seconds;
+
+    next.rate_limit = loaded.rate_limit.clone();
+
+    next.healthcare.enable_triage_ai = loaded.healthcare.enable_triage_ai;
+    next.healthcare.dha_integration_enabled = loaded.healthcare.dha_integration_enabled;
+    next.healthcare.dha_api_url = loaded.healthcare.dha_api_url.clone();
+    next.healthcare.dha_api_key = loaded.healthcare.dha_api_key.clone();
+
+    next
+}
+
+/// Top-level sections that differ between two configurations
+fn changed_sections(a: &AppConfig, b: &AppConfig) -> Vec<String> {
+    let sections = |config: &AppConfig| match serde_json::to_value(config) {
+        Ok(Value::Object(sections)) => sections,
+        _ => Default::default(),
+    };
+    let (a, b) = (sections(a), sections(b));
+    let mut changed: Vec<String> = a
+        .iter()
+        .filter(|(name, value)| b.get(name.as_str()) != Some(value))
+        .map(|(name, _)| name.clone())
+        .collect();
+    changed.sort();
+    changed
+}
+
+/// SIGHUP listener; never fires where the signal is unavailable
+struct Hangup {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+#[cfg(unix)]
+impl Hangup {
+    fn new() -> Self {
+        use tokio::signal::unix::{signal, SignalKind};
+        let signal = signal(SignalKind::hangup())
+            .map_err(|e| warn!("Cannot listen for SIGHUP: {}", e))
+            .ok();
+        Self { signal }
+    }
+
+    async fn recv(&mut self) {
+        match self.signal.as_mut() {
+            Some(signal) => {
+                signal.recv().await;
+            }
+            None => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(not(unix))]
+impl Hangup {
+    fn new() -> Self {
+        Self {}
+    }
+
+    async fn recv(&mut self) {
+        std::future::pending().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reloadable_settings_are_published() {
+        let watcher = ConfigWatcher::new(AppConfig::default(), "config");
+        let shared = watcher.config();
+        let mut changes = watcher.subscribe();
+
+        let mut loaded = AppConfig::default();
+        loaded.logging.level = "warn".to_string();
+        loaded.server.maintenance_mode = true;
+        loaded.security.max_failed_logins = 3;
+        loaded.server.port = 8443; // Needs a restart
+
+        assert!(watcher.apply(&loaded));
+        assert!(changes.has_changed().unwrap());
+        let config = changes.borrow_and_update().clone();
+        assert_eq!(config.logging.level, "warn");
+        assert!(shared.load().server.maintenance_mode);
+        assert_eq!(shared.load().security.max_failed_logins, 3);
+        assert_eq!(shared.load().server.port, 3000);
+
+        // Nothing reloadable changed this time
+        assert!(!watcher.apply(&loaded));
+        assert!(!changes.has_changed().unwrap());
+    }
+
+    #[test]
+    fn test_changed_sections() {
+        let current = AppConfig::default();
+        let mut loaded = current.clone();
+        loaded.server.port = 8443;
+        loaded.redis.max_connections = 5;
+        assert_eq!(changed_sections(&current, &loaded), vec!["redis", "server"]);
+        assert!(changed_sections(&current, &current).is_empty());
+    }
+}
@@ -2,6 +2,8 @@
 
 pub mod config;
 pub mod model;
+pub mod seed;
+pub mod storage;
 pub mod store;
 
 // Re-exports for convenience
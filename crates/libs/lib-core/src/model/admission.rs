@@ -0,0 +1,63 @@
+//! Admits a patient to a bed and frees it again. The patient, the bed and
+//! the hospital's count of available beds change together or not at all.
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::{Bed, Patient};
+use lib_types::enums::PatientStatus;
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{BedId, PatientId};
+
+use crate::store::{BedRepository, Db, HospitalRepository, PatientRepository, PgPatientRepository};
+
+/// Admit a patient to a bed in their hospital. A patient who already had a
+/// bed keeps their place in the hospital's count; their old bed goes to
+/// cleaning.
+pub async fn admit_patient(
+    db: &Db,
+    ctx: &RequestCtx,
+    patient_id: PatientId,
+    bed_id: BedId,
+) -> Result<(Patient, Bed), AppError> {
+    db.transaction(ctx, |txn| async move {
+        let patients = PgPatientRepository::in_txn(&txn);
+        let patient = patients
+            .find_by_id(ctx, patient_id)
+            .await?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        let bed = BedRepository::in_txn(&txn)
+            .assign_patient(ctx, bed_id, patient_id)
+            .await?;
+        if patient.bed_id.is_none() {
+            HospitalRepository::in_txn(&txn)
+                .reserve_bed(ctx, patient.hospital_id)
+                .await?;
+        }
+        let patient = if patient.status == PatientStatus::Admitted {
+            patients
+                .find_by_id(ctx, patient_id)
+                .await?
+                .ok_or(PatientError::NotFound { patient_id })?
+        } else {
+            patients
+                .transition_status(ctx, patient_id, patient.status, PatientStatus::Admitted)
+                .await?
+        };
+
+        Ok((patient, bed))
+    })
+    .await
+}
+
+/// Free an occupied bed, e.g. when its patient moves to a ward elsewhere.
+/// The bed goes to cleaning and counts as available for the hospital again.
+pub async fn release_bed(db: &Db, ctx: &RequestCtx, bed_id: BedId) -> Result<Bed, AppError> {
+    db.transaction(ctx, |txn| async move {
+        let bed = BedRepository::in_txn(&txn).release(ctx, bed_id).await?;
+        HospitalRepository::in_txn(&txn)
+            .release_bed(ctx, bed.hospital_id)
+            .await?;
+        Ok(bed)
+    })
+    .await
+}
@@ -0,0 +1,210 @@
+//! Flags lab results that are past a critical limit and must be phoned
+//! through to the treating clinician
+
+use lib_types::dtos::LabResultInput;
+use lib_types::enums::AbnormalFlag;
+
+/// Critical limits for common emergency analytes, by the names and units
+/// laboratory systems report them under. A value below `low` or above
+/// `high` is critical whatever the reporting lab's own flag says.
+struct CriticalLimit {
+    analytes: &'static [&'static str],
+    units: &'static [&'static str],
+    low: Option<f64>,
+    high: Option<f64>,
+}
+
+const CRITICAL_LIMITS: &[CriticalLimit] = &[
+    CriticalLimit {
+        analytes: &["k", "k+", "potassium"],
+        units: &["mmol/l", "meq/l"],
+        low: Some(2.5),
+        high: Some(6.0),
+    },
+    CriticalLimit {
+        analytes: &["na", "na+", "sodium"],
+        units: &["mmol/l", "meq/l"],
+        low: Some(120.0),
+        high: Some(160.0),
+    },
+    CriticalLimit {
+        analytes: &["glucose", "glu", "blood glucose"],
+        units: &["mmol/l"],
+        low: Some(2.5),
+        high: Some(25.0),
+    },
+    CriticalLimit {
+        analytes: &["glucose", "glu", "blood glucose"],
+        units: &["mg/dl"],
+        low: Some(45.0),
+        high: Some(450.0),
+    },
+    CriticalLimit {
+        analytes: &["ca", "calcium"],
+        units: &["mmol/l"],
+        low: Some(1.5),
+        high: Some(3.25),
+    },
+    CriticalLimit {
+        analytes: &["hb", "hgb", "haemoglobin", "hemoglobin"],
+        units: &["g/l"],
+        low: Some(70.0),
+        high: Some(200.0),
+    },
+    CriticalLimit {
+        analytes: &["hb", "hgb", "haemoglobin", "hemoglobin"],
+        units: &["g/dl"],
+        low: Some(7.0),
+        high: Some(20.0),
+    },
+    CriticalLimit {
+        analytes: &["plt", "platelets"],
+        units: &["10^9/l", "x10^9/l"],
+        low: Some(20.0),
+        high: Some(1000.0),
+    },
+    CriticalLimit {
+        analytes: &["wbc", "white cell count"],
+        units: &["10^9/l", "x10^9/l"],
+        low: Some(2.0),
+        high: Some(30.0),
+    },
+    CriticalLimit {
+        analytes: &["ph"],
+        units: &["", "ph"],
+        low: Some(7.2),
+        high: Some(7.6),
+    },
+    CriticalLimit {
+        analytes: &["lactate", "lac"],
+        units: &["mmol/l"],
+        low: None,
+        high: Some(4.0),
+    },
+    CriticalLimit {
+        analytes: &["inr"],
+        units: &["", "ratio"],
+        low: None,
+        high: Some(5.0),
+    },
+];
+
+/// Work out the flag for a result from, in order of severity: the critical
+/// limits above, the flag the lab reported, and the reference range sent
+/// with the result. The most severe of the three wins, so a lab that sends
+/// K+ 7.1 flagged only "H" still raises a critical alert.
+pub fn classify_result(result: &LabResultInput) -> AbnormalFlag {
+    let candidates = [
+        critical_flag(&result.analyte, &result.units, result.value),
+        result.flag,
+        Some(reference_flag(result)),
+    ];
+    candidates
+        .into_iter()
+        .flatten()
+        .max_by_key(severity)
+        .unwrap_or(AbnormalFlag::Normal)
+}
+
+/// Check a value against the critical limits for its analyte and units.
+/// Returns `None` for analytes or units the table does not cover.
+pub fn critical_flag(analyte: &str, units: &str, value: f64) -> Option<AbnormalFlag> {
+    let analyte = analyte.trim().to_lowercase();
+    let units = units.trim().to_lowercase().replace(' ', "");
+    let limit = CRITICAL_LIMITS.iter().find(|limit| {
+        limit.analytes.contains(&analyte.as_str()) && limit.units.contains(&units.as_str())
+    })?;
+
+    if limit.low.is_some_and(|low| value < low) {
+        Some(AbnormalFlag::CriticalLow)
+    } else if limit.high.is_some_and(|high| value > high) {
+        Some(AbnormalFlag::CriticalHigh)
+    } else {
+        None
+    }
+}
+
+fn reference_flag(result: &LabResultInput) -> AbnormalFlag {
+    if result.reference_low.is_some_and(|low| result.value < low) {
+        AbnormalFlag::Low
+    } else if result
+        .reference_high
+        .is_some_and(|high| result.value > high)
+    {
+        AbnormalFlag::High
+    } else {
+        AbnormalFlag::Normal
+    }
+}
+
+fn severity(flag: &AbnormalFlag) -> u8 {
+    match flag {
+        AbnormalFlag::Normal => 0,
+        AbnormalFlag::Low | AbnormalFlag::High => 1,
+        AbnormalFlag::CriticalLow | AbnormalFlag::CriticalHigh => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(analyte: &str, value: f64, units: &str) -> LabResultInput {
+        LabResultInput {
+            analyte: analyte.to_string(),
+            value,
+            units: units.to_string(),
+            reference_low: None,
+            reference_high: None,
+            flag: None,
+        }
+    }
+
+    #[test]
+    fn test_critical_potassium() {
+        assert_eq!(
+            classify_result(&result("K+", 7.1, "mmol/L")),
+            AbnormalFlag::CriticalHigh
+        );
+        assert_eq!(
+            classify_result(&result("Potassium", 2.1, "mEq/L")),
+            AbnormalFlag::CriticalLow
+        );
+        assert_eq!(
+            classify_result(&result("K", 4.2, "mmol/L")),
+            AbnormalFlag::Normal
+        );
+    }
+
+    #[test]
+    fn test_lab_flag_is_raised_not_lowered() {
+        let mut potassium = result("K", 7.1, "mmol/L");
+        potassium.flag = Some(AbnormalFlag::High);
+        assert_eq!(classify_result(&potassium), AbnormalFlag::CriticalHigh);
+
+        // A lab's own critical flag is kept even for analytes we do not know
+        let mut ammonia = result("Ammonia", 180.0, "umol/L");
+        ammonia.flag = Some(AbnormalFlag::CriticalHigh);
+        assert_eq!(classify_result(&ammonia), AbnormalFlag::CriticalHigh);
+    }
+
+    #[test]
+    fn test_reference_range_and_units() {
+        let mut sodium = result("Na", 131.0, "mmol/L");
+        sodium.reference_low = Some(135.0);
+        sodium.reference_high = Some(145.0);
+        assert_eq!(classify_result(&sodium), AbnormalFlag::Low);
+
+        // Limits only apply in the units they are written for
+        assert_eq!(
+            critical_flag("Hb", "g/dL", 6.5),
+            Some(AbnormalFlag::CriticalLow)
+        );
+        assert_eq!(critical_flag("Hb", "g/L", 95.0), None);
+        assert_eq!(critical_flag("Hb", "mmol/L", 4.0), None);
+        assert_eq!(
+            critical_flag("Glucose", "mg/dL", 38.0),
+            Some(AbnormalFlag::CriticalLow)
+        );
+    }
+}
@@ -0,0 +1,67 @@
+//! Sends ambulances out and puts patients on board. The ambulance, its
+//! incident and the patient it carries change together or not at all.
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::DispatchAmbulanceRequest;
+use lib_types::entities::{Ambulance, Patient};
+use lib_types::errors::{AmbulanceError, AppError, PatientError};
+use lib_types::ids::{AmbulanceId, PatientId};
+
+use crate::store::{AmbulanceRepository, Db, PatientRepository, PgPatientRepository, Txn};
+
+/// Dispatch an ambulance to a call, putting the patient on board when the
+/// call is for one already registered
+pub async fn dispatch_ambulance(
+    db: &Db,
+    ctx: &RequestCtx,
+    request: &DispatchAmbulanceRequest,
+) -> Result<(Ambulance, Option<Patient>), AppError> {
+    let request = request.clone();
+    db.transaction(ctx, |txn| async move {
+        let ambulance = AmbulanceRepository::in_txn(&txn)
+            .dispatch(ctx, &request)
+            .await?;
+        let patient = match request.patient_id {
+            Some(patient_id) => Some(board(&txn, ctx, &ambulance, patient_id).await?),
+            None => None,
+        };
+        Ok((ambulance, patient))
+    })
+    .await
+}
+
+/// Put a patient on board an ambulance that is out on a mission
+pub async fn assign_ambulance(
+    db: &Db,
+    ctx: &RequestCtx,
+    ambulance_id: AmbulanceId,
+    patient_id: PatientId,
+) -> Result<(Ambulance, Patient), AppError> {
+    db.transaction(ctx, |txn| async move {
+        let ambulance = AmbulanceRepository::in_txn(&txn)
+            .find_by_id(ctx, ambulance_id)
+            .await?
+            .ok_or(AmbulanceError::NotFound { ambulance_id })?;
+        let patient = board(&txn, ctx, &ambulance, patient_id).await?;
+        Ok((ambulance, patient))
+    })
+    .await
+}
+
+async fn board(
+    txn: &Txn,
+    ctx: &RequestCtx,
+    ambulance: &Ambulance,
+    patient_id: PatientId,
+) -> Result<Patient, AppError> {
+    let patients = PgPatientRepository::in_txn(txn);
+    let patient = patients
+        .find_by_id(ctx, patient_id)
+        .await?
+        .ok_or(PatientError::NotFound { patient_id })?;
+    ambulance.check_can_carry(&patient)?;
+
+    patients
+        .assign_ambulance(ctx, patient_id, Some(ambulance.id))
+        .await
+}
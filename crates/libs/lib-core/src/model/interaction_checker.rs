@@ -0,0 +1,260 @@
+//! Checks medication orders against a patient's recorded allergies
+
+use lib_types::entities::Patient;
+use lib_types::errors::PatientError;
+
+/// Drug classes and the drugs that cross-react within them. An allergy to
+/// the class or to any member rules out every member.
+const ALLERGEN_CLASSES: &[(&str, &[&str])] = &[
+    (
+        "penicillin",
+        &[
+            "amoxicillin",
+            "ampicillin",
+            "benzylpenicillin",
+            "phenoxymethylpenicillin",
+            "flucloxacillin",
+            "cloxacillin",
+            "dicloxacillin",
+            "oxacillin",
+            "nafcillin",
+            "piperacillin",
+            "co-amoxiclav",
+            "augmentin",
+            "tazocin",
+        ],
+    ),
+    (
+        "cephalosporin",
+        &[
+            "cefalexin",
+            "cephalexin",
+            "cefazolin",
+            "cefuroxime",
+            "ceftriaxone",
+            "cefotaxime",
+            "ceftazidime",
+            "cefixime",
+            "cefepime",
+        ],
+    ),
+    (
+        "sulfonamide",
+        &[
+            "sulfa",
+            "sulfamethoxazole",
+            "co-trimoxazole",
+            "bactrim",
+            "septrin",
+            "sulfadiazine",
+            "sulfasalazine",
+        ],
+    ),
+    (
+        "nsaid",
+        &[
+            "aspirin",
+            "ibuprofen",
+            "naproxen",
+            "diclofenac",
+            "ketorolac",
+            "indomethacin",
+            "mefenamic",
+            "brufen",
+            "voltaren",
+        ],
+    ),
+    (
+        "macrolide",
+        &["erythromycin", "azithromycin", "clarithromycin"],
+    ),
+    (
+        "fluoroquinolone",
+        &[
+            "quinolone",
+            "ciprofloxacin",
+            "levofloxacin",
+            "moxifloxacin",
+            "ofloxacin",
+        ],
+    ),
+    (
+        "tetracycline",
+        &["doxycycline", "minocycline", "oxytetracycline"],
+    ),
+];
+
+/// An allergy that rules out a medication
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllergyMatch {
+    pub allergy: String,
+    pub medication: String,
+    pub allergen_class: Option<&'static str>, // None when the drug itself is named
+}
+
+/// Reject a medication order if it conflicts with one of the patient's allergies
+pub fn check_allergies(patient: &Patient, medication: &str) -> Result<(), PatientError> {
+    match find_allergy_conflict(&patient.get_allergies(), medication) {
+        Some(conflict) => Err(PatientError::AllergyConflict {
+            medication: conflict.medication,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Find the first allergy that rules out `medication`, either by name (e.g.
+/// "Metformin" and "Metformin 500 mg") or through a shared drug class (e.g.
+/// "Penicillin" and "Amoxicillin")
+pub fn find_allergy_conflict(allergies: &[String], medication: &str) -> Option<AllergyMatch> {
+    let medication_terms = terms(medication);
+    let medication_classes: Vec<&'static str> = medication_terms
+        .iter()
+        .filter_map(|term| allergen_class(term))
+        .collect();
+
+    allergies.iter().find_map(|allergy| {
+        let allergy_terms = terms(allergy);
+        if allergy_terms.is_empty() {
+            return None;
+        }
+
+        let allergen_class = if contains_sequence(&medication_terms, &allergy_terms) {
+            None
+        } else {
+            Some(
+                allergy_terms
+                    .iter()
+                    .filter_map(|term| allergen_class(term))
+                    .find(|class| medication_classes.contains(class))?,
+            )
+        };
+
+        Some(AllergyMatch {
+            allergy: allergy.clone(),
+            medication: medication.trim().to_string(),
+            allergen_class,
+        })
+    })
+}
+
+/// Split a drug or allergy name into lowercase words, dropping remarks in
+/// parentheses such as "(rash)" and splitting combinations such as
+/// "trimethoprim-sulfamethoxazole" unless the whole name is a known drug
+fn terms(text: &str) -> Vec<String> {
+    let mut without_remarks = String::with_capacity(text.len());
+    let mut depth = 0usize;
+    for c in text.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 => without_remarks.push(c),
+            _ => {}
+        }
+    }
+
+    without_remarks
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            if word.contains('-') && allergen_class(word).is_none() {
+                word.split('-')
+                    .filter(|part| !part.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            } else {
+                vec![word.to_string()]
+            }
+        })
+        .collect()
+}
+
+/// The drug class a term names or belongs to, accepting plurals such as "penicillins"
+fn allergen_class(term: &str) -> Option<&'static str> {
+    let lookup = |term: &str| {
+        ALLERGEN_CLASSES
+            .iter()
+            .find(|(class, members)| *class == term || members.contains(&term))
+            .map(|(class, _)| *class)
+    };
+    lookup(term).or_else(|| term.strip_suffix('s').and_then(lookup))
+}
+
+fn contains_sequence(haystack: &[String], needle: &[String]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib_types::enums::{Gender, TriageLevel};
+    use lib_types::ids::HospitalId;
+
+    fn allergies(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_class_synonyms() {
+        let conflict = find_allergy_conflict(&allergies(&["Penicillin"]), "Amoxicillin").unwrap();
+        assert_eq!(conflict.allergen_class, Some("penicillin"));
+        assert_eq!(conflict.allergy, "Penicillin");
+
+        // Members of the same class cross-react
+        assert!(
+            find_allergy_conflict(&allergies(&["Amoxicillin"]), "Flucloxacillin 500 mg").is_some()
+        );
+        assert!(find_allergy_conflict(&allergies(&["NSAIDs"]), "Ibuprofen").is_some());
+        assert!(find_allergy_conflict(
+            &allergies(&["Sulfa drugs"]),
+            "Trimethoprim-sulfamethoxazole"
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn test_direct_match() {
+        let conflict =
+            find_allergy_conflict(&allergies(&["Metformin (rash)"]), " metformin 500 mg ").unwrap();
+        assert_eq!(conflict.allergen_class, None);
+        assert_eq!(conflict.medication, "metformin 500 mg");
+    }
+
+    #[test]
+    fn test_no_conflict() {
+        assert!(
+            find_allergy_conflict(&allergies(&["Penicillin", "Latex"]), "Paracetamol").is_none()
+        );
+        assert!(find_allergy_conflict(&allergies(&["Valproic acid"]), "Mefenamic acid").is_none());
+        assert!(find_allergy_conflict(&allergies(&["Sulfa"]), "Ibuprofen").is_none());
+        assert!(find_allergy_conflict(&[], "Amoxicillin").is_none());
+    }
+
+    #[test]
+    fn test_check_patient() {
+        let mut patient = Patient::new(
+            "P-0001".to_string(),
+            None,
+            "Ahmed".to_string(),
+            "Hassan".to_string(),
+            34,
+            Gender::Male,
+            "Fever".to_string(),
+            TriageLevel::High,
+            HospitalId::new(),
+            None,
+            None,
+        );
+        assert!(check_allergies(&patient, "Co-amoxiclav").is_ok());
+
+        patient.add_allergy("Penicillin".to_string());
+        assert_eq!(
+            check_allergies(&patient, "Co-amoxiclav"),
+            Err(PatientError::AllergyConflict {
+                medication: "Co-amoxiclav".to_string()
+            })
+        );
+    }
+}
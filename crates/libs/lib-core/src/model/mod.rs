@@ -1 +1,11 @@
 // pub mod model;
+
+pub mod admission;
+pub mod critical_values;
+pub mod dispatch;
+pub mod interaction_checker;
+
+pub use admission::{admit_patient, release_bed};
+pub use critical_values::{classify_result, critical_flag};
+pub use dispatch::{assign_ambulance, dispatch_ambulance};
+pub use interaction_checker::{check_allergies, find_allergy_conflict, AllergyMatch};
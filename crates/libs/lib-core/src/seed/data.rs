@@ -0,0 +1,306 @@
+//! The demo records. Locations, licenses and phone numbers are modelled on
+//! Dubai Health Authority hospitals; every person is fictional.
+
+use lib_types::enums::{Gender, HospitalType, SeniorityLevel, TriageLevel, UserRole};
+
+pub(super) struct SeedHospital {
+    pub key: u16,
+    pub name: &'static str,
+    pub license_number: &'static str,
+    pub location: (f64, f64),
+    pub address: &'static str,
+    pub phone_number: &'static str,
+    pub email: &'static str,
+    pub total_beds: i32,
+    pub specialties: &'static [&'static str],
+    pub hospital_type: HospitalType,
+}
+
+pub(super) struct SeedUser {
+    pub key: u16,
+    pub hospital: u16,
+    pub username: &'static str,
+    pub first_name: &'static str,
+    pub last_name: &'static str,
+    pub role: UserRole,
+    pub phone_number: &'static str,
+    pub staff: Option<SeedStaff>,
+}
+
+pub(super) struct SeedStaff {
+    pub staff_id: &'static str,
+    pub specialty: &'static str,
+    pub license_number: &'static str,
+    pub department: &'static str,
+    pub seniority_level: SeniorityLevel,
+    pub certifications: &'static [&'static str],
+}
+
+pub(super) struct SeedPatient {
+    pub key: u16,
+    pub hospital: u16,
+    pub birth_year: Option<u16>, // None for a patient not yet identified
+    pub first_name: &'static str,
+    pub last_name: &'static str,
+    pub age: i32,
+    pub gender: Gender,
+    pub chief_complaint: &'static str,
+    pub triage_level: TriageLevel,
+    pub incident_location: Option<(f64, f64)>,
+}
+
+pub(super) const HOSPITALS: &[SeedHospital] = &[
+    SeedHospital {
+        key: 1,
+        name: "Rashid Hospital",
+        license_number: "DHA-H1001",
+        location: (25.2366, 55.3172),
+        address: "Oud Metha Road, Umm Hurair 2, Dubai",
+        phone_number: "+97142192000",
+        email: "er@rashid-hospital.example",
+        total_beds: 120,
+        specialties: &[
+            "Emergency Medicine",
+            "Trauma Surgery",
+            "Neurosurgery",
+            "Orthopedics",
+        ],
+        hospital_type: HospitalType::Public,
+    },
+    SeedHospital {
+        key: 2,
+        name: "Dubai Hospital",
+        license_number: "DHA-H1002",
+        location: (25.2846, 55.3204),
+        address: "Al Khaleej Road, Al Baraha, Deira, Dubai",
+        phone_number: "+97142195000",
+        email: "er@dubai-hospital.example",
+        total_beds: 90,
+        specialties: &[
+            "Emergency Medicine",
+            "Cardiology",
+            "Nephrology",
+            "General Surgery",
+        ],
+        hospital_type: HospitalType::Public,
+    },
+    SeedHospital {
+        key: 3,
+        name: "Latifa Women and Children Hospital",
+        license_number: "DHA-H1003",
+        location: (25.2240, 55.3230),
+        address: "Oud Metha Road, Al Jaddaf, Dubai",
+        phone_number: "+97142193000",
+        email: "er@latifa-hospital.example",
+        total_beds: 60,
+        specialties: &["Pediatrics", "Obstetrics", "Neonatology"],
+        hospital_type: HospitalType::Specialized,
+    },
+];
+
+pub(super) const USERS: &[SeedUser] = &[
+    SeedUser {
+        key: 1,
+        hospital: 1,
+        username: "fatima.almansoori",
+        first_name: "Fatima",
+        last_name: "Al Mansoori",
+        role: UserRole::ErDirector,
+        phone_number: "+971501234501",
+        staff: Some(SeedStaff {
+            staff_id: "RH-ER-001",
+            specialty: "Emergency Medicine",
+            license_number: "DHA-P-10234501",
+            department: "Emergency",
+            seniority_level: SeniorityLevel::Director,
+            certifications: &["ACLS", "ATLS"],
+        }),
+    },
+    SeedUser {
+        key: 2,
+        hospital: 1,
+        username: "omar.alhashimi",
+        first_name: "Omar",
+        last_name: "Al Hashimi",
+        role: UserRole::Specialist,
+        phone_number: "+971501234502",
+        staff: Some(SeedStaff {
+            staff_id: "RH-TS-014",
+            specialty: "Trauma Surgery",
+            license_number: "DHA-P-10234502",
+            department: "Surgery",
+            seniority_level: SeniorityLevel::Consultant,
+            certifications: &["ATLS"],
+        }),
+    },
+    SeedUser {
+        key: 3,
+        hospital: 1,
+        username: "mariam.alsuwaidi",
+        first_name: "Mariam",
+        last_name: "Al Suwaidi",
+        role: UserRole::Nurse,
+        phone_number: "+971501234503",
+        staff: Some(SeedStaff {
+            staff_id: "RH-RN-102",
+            specialty: "Emergency Nursing",
+            license_number: "DHA-N-20234503",
+            department: "Emergency",
+            seniority_level: SeniorityLevel::Senior,
+            certifications: &["BLS", "PALS"],
+        }),
+    },
+    SeedUser {
+        key: 4,
+        hospital: 1,
+        username: "khalid.alfalasi",
+        first_name: "Khalid",
+        last_name: "Al Falasi",
+        role: UserRole::Paramedic,
+        phone_number: "+971501234504",
+        staff: None,
+    },
+    SeedUser {
+        key: 5,
+        hospital: 2,
+        username: "youssef.alzaabi",
+        first_name: "Youssef",
+        last_name: "Al Zaabi",
+        role: UserRole::Specialist,
+        phone_number: "+971501234505",
+        staff: Some(SeedStaff {
+            staff_id: "DH-CA-007",
+            specialty: "Cardiology",
+            license_number: "DHA-P-10234505",
+            department: "Cardiology",
+            seniority_level: SeniorityLevel::Consultant,
+            certifications: &["ACLS"],
+        }),
+    },
+    SeedUser {
+        key: 6,
+        hospital: 3,
+        username: "noura.alketbi",
+        first_name: "Noura",
+        last_name: "Al Ketbi",
+        role: UserRole::Nurse,
+        phone_number: "+971501234506",
+        staff: Some(SeedStaff {
+            staff_id: "LH-RN-031",
+            specialty: "Pediatric Nursing",
+            license_number: "DHA-N-20234506",
+            department: "Pediatric Emergency",
+            seniority_level: SeniorityLevel::Junior,
+            certifications: &["BLS", "PALS"],
+        }),
+    },
+    SeedUser {
+        key: 7,
+        hospital: 1,
+        username: "aisha.almarri",
+        first_name: "Aisha",
+        last_name: "Al Marri",
+        role: UserRole::Admin,
+        phone_number: "+971501234507",
+        staff: None,
+    },
+];
+
+pub(super) const PATIENTS: &[SeedPatient] = &[
+    SeedPatient {
+        key: 1,
+        hospital: 1,
+        birth_year: Some(1968),
+        first_name: "Ahmed",
+        last_name: "Al Nuaimi",
+        age: 58,
+        gender: Gender::Male,
+        chief_complaint: "Chest pain radiating to left arm",
+        triage_level: TriageLevel::High,
+        incident_location: Some((25.1972, 55.2744)), // Downtown Dubai
+    },
+    SeedPatient {
+        key: 2,
+        hospital: 1,
+        birth_year: None,
+        first_name: "Unknown",
+        last_name: "Unknown",
+        age: 30,
+        gender: Gender::Male,
+        chief_complaint: "Motorcycle collision, unresponsive",
+        triage_level: TriageLevel::Critical,
+        incident_location: Some((25.0805, 55.1403)), // Sheikh Zayed Road, Dubai Marina
+    },
+    SeedPatient {
+        key: 3,
+        hospital: 1,
+        birth_year: Some(1995),
+        first_name: "Salma",
+        last_name: "Al Shamsi",
+        age: 31,
+        gender: Gender::Female,
+        chief_complaint: "Fall from ladder, suspected wrist fracture",
+        triage_level: TriageLevel::Medium,
+        incident_location: Some((25.2285, 55.2867)), // Al Wasl
+    },
+    SeedPatient {
+        key: 4,
+        hospital: 2,
+        birth_year: Some(1952),
+        first_name: "Hamdan",
+        last_name: "Al Muhairi",
+        age: 74,
+        gender: Gender::Male,
+        chief_complaint: "Shortness of breath, history of heart failure",
+        triage_level: TriageLevel::High,
+        incident_location: Some((25.2697, 55.3095)), // Deira
+    },
+    SeedPatient {
+        key: 5,
+        hospital: 2,
+        birth_year: Some(1988),
+        first_name: "Layla",
+        last_name: "Haddad",
+        age: 38,
+        gender: Gender::Female,
+        chief_complaint: "Severe abdominal pain",
+        triage_level: TriageLevel::Medium,
+        incident_location: None,
+    },
+    SeedPatient {
+        key: 6,
+        hospital: 3,
+        birth_year: Some(2021),
+        first_name: "Zayed",
+        last_name: "Al Dhaheri",
+        age: 5,
+        gender: Gender::Male,
+        chief_complaint: "High fever and febrile seizure",
+        triage_level: TriageLevel::High,
+        incident_location: Some((25.2048, 55.2708)), // Al Satwa
+    },
+    SeedPatient {
+        key: 7,
+        hospital: 3,
+        birth_year: Some(1997),
+        first_name: "Hessa",
+        last_name: "Al Qasimi",
+        age: 29,
+        gender: Gender::Female,
+        chief_complaint: "Pregnant at 36 weeks, reduced fetal movement",
+        triage_level: TriageLevel::Medium,
+        incident_location: None,
+    },
+    SeedPatient {
+        key: 8,
+        hospital: 2,
+        birth_year: Some(2001),
+        first_name: "Rashed",
+        last_name: "Bin Saeed",
+        age: 25,
+        gender: Gender::Male,
+        chief_complaint: "Sprained ankle playing football",
+        triage_level: TriageLevel::NonUrgent,
+        incident_location: Some((25.2532, 55.3657)), // Al Garhoud
+    },
+];
@@ -0,0 +1,284 @@
+//! Demo data for development and integration tests: a few Dubai hospitals,
+//! their staff and user accounts, and patients in the emergency department.
+//!
+//! Every seeded record has a fixed id, so seeding again only creates what
+//! is missing and never duplicates or overwrites existing rows.
+
+mod data;
+
+use std::fmt;
+
+use anyhow::{bail, Context, Result};
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_auth::password::{Argon2Params, PasswordHasher};
+use lib_types::entities::{Hospital, MedicalStaff, Patient, User};
+use lib_types::ids::{HospitalId, PatientId, UserId};
+use lib_utils::location::GeoPoint;
+use lib_utils::validation::EmiratesId;
+
+use crate::config::Environment;
+use crate::store::{
+    Db, HospitalRepository, PatientRepository, PgPatientRepository, StaffRepository, UserRepository,
+};
+use data::{SeedHospital, SeedPatient, SeedUser, HOSPITALS, PATIENTS, USERS};
+
+/// High bits shared by all seeded ids, so they are easy to spot
+const SEED_NAMESPACE: u128 = 0x5eed_0000_0000_4000_8000_0000_0000_0000;
+
+const HOSPITAL: u16 = 1;
+const USER: u16 = 2;
+const STAFF: u16 = 3;
+const PATIENT: u16 = 4;
+
+/// Counts of the records a seeding run created and found already present
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SeedReport {
+    pub created: usize,
+    pub existing: usize,
+}
+
+impl SeedReport {
+    fn record(&mut self, created: bool) {
+        if created {
+            self.created += 1;
+        } else {
+            self.existing += 1;
+        }
+    }
+}
+
+impl fmt::Display for SeedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} records created, {} already present",
+            self.created, self.existing
+        )
+    }
+}
+
+/// Seed the demo data. Refused outside development and testing, where the
+/// records and their shared `password` would be real accounts.
+pub async fn seed(environment: &Environment, db: &Db, password: &str) -> Result<SeedReport> {
+    if !matches!(environment, Environment::Development | Environment::Testing) {
+        bail!(
+            "Refusing to seed demo data in the {} environment",
+            environment.name()
+        );
+    }
+    if password.is_empty() {
+        bail!("A password for the demo accounts is required");
+    }
+
+    let ctx = RequestCtx::system();
+    let mut report = SeedReport::default();
+
+    for hospital in HOSPITALS {
+        report.record(seed_hospital(&ctx, db, hospital).await?);
+    }
+
+    let password_hash = PasswordHasher::new(Argon2Params::default())?.hash(password)?;
+    for user in USERS {
+        report.record(seed_user(&ctx, db, user, &password_hash).await?);
+        if user.staff.is_some() {
+            report.record(seed_staff(&ctx, db, user).await?);
+        }
+    }
+
+    for patient in PATIENTS {
+        report.record(seed_patient(&ctx, db, patient).await?);
+    }
+
+    Ok(report)
+}
+
+async fn seed_hospital(ctx: &RequestCtx, db: &Db, seed: &SeedHospital) -> Result<bool> {
+    // Soft-deleted hospitals count as present: seeding does not undo a delete
+    let hospitals = HospitalRepository::new(db.clone()).with_deleted();
+    let id = hospital_id(seed.key);
+    if hospitals.find_by_id(ctx, id).await?.is_some() {
+        return Ok(false);
+    }
+
+    let (lat, lon) = seed.location;
+    let mut hospital = Hospital::new(
+        seed.name.to_string(),
+        seed.license_number.to_string(),
+        GeoPoint::new(lat, lon)?,
+        seed.address.to_string(),
+        seed.phone_number.to_string(),
+        seed.email.to_string(),
+        seed.total_beds,
+        strings(seed.specialties),
+        seed.hospital_type,
+    );
+    hospital.id = id;
+    hospitals
+        .create(ctx, &hospital)
+        .await
+        .with_context(|| format!("Failed to seed hospital {}", seed.name))?;
+    Ok(true)
+}
+
+async fn seed_user(
+    ctx: &RequestCtx,
+    db: &Db,
+    seed: &SeedUser,
+    password_hash: &str,
+) -> Result<bool> {
+    let users = UserRepository::new(db.clone());
+    let id = user_id(seed.key);
+    if users.find_by_id(ctx, id).await?.is_some() {
+        return Ok(false);
+    }
+
+    let hospital = HOSPITALS
+        .iter()
+        .find(|hospital| hospital.key == seed.hospital)
+        .context("Seeded user belongs to an unknown hospital")?;
+    let mut user = User::new(
+        seed.username.to_string(),
+        format!(
+            "{}@{}",
+            seed.username,
+            hospital
+                .email
+                .split_once('@')
+                .map_or("", |(_, domain)| domain)
+        ),
+        password_hash.to_string(),
+        seed.role,
+        hospital_id(seed.hospital),
+        seed.first_name.to_string(),
+        seed.last_name.to_string(),
+        Some(seed.phone_number.to_string()),
+    );
+    user.id = id;
+    users
+        .create(ctx, &user)
+        .await
+        .with_context(|| format!("Failed to seed user {}", seed.username))?;
+    Ok(true)
+}
+
+async fn seed_staff(ctx: &RequestCtx, db: &Db, seed: &SeedUser) -> Result<bool> {
+    let Some(ref staff) = seed.staff else {
+        return Ok(false);
+    };
+    let repository = StaffRepository::new(db.clone()).with_deleted();
+    let id = seed_id(STAFF, seed.key);
+    if repository.find_by_id(ctx, id).await?.is_some() {
+        return Ok(false);
+    }
+
+    let mut record = MedicalStaff::new(
+        user_id(seed.key),
+        hospital_id(seed.hospital),
+        staff.staff_id.to_string(),
+        staff.specialty.to_string(),
+        staff.license_number.to_string(),
+        staff.department.to_string(),
+        staff.seniority_level,
+        strings(staff.certifications),
+    );
+    record.id = id;
+    repository
+        .create(ctx, &record)
+        .await
+        .with_context(|| format!("Failed to seed staff record {}", staff.staff_id))?;
+    Ok(true)
+}
+
+async fn seed_patient(ctx: &RequestCtx, db: &Db, seed: &SeedPatient) -> Result<bool> {
+    let patients = PgPatientRepository::new(db.clone());
+    let id = PatientId::from(seed_id(PATIENT, seed.key));
+    if patients.find_by_id(ctx, id).await?.is_some() {
+        return Ok(false);
+    }
+
+    let incident_location = match seed.incident_location {
+        Some((lat, lon)) => Some(GeoPoint::new(lat, lon)?),
+        None => None,
+    };
+    let mut patient = Patient::new(
+        format!("DEMO-{:04}", seed.key),
+        national_id(seed).map(|id| id.to_string()),
+        seed.first_name.to_string(),
+        seed.last_name.to_string(),
+        seed.age,
+        seed.gender,
+        seed.chief_complaint.to_string(),
+        seed.triage_level,
+        hospital_id(seed.hospital),
+        incident_location,
+        None,
+    );
+    patient.id = id;
+    patients
+        .create(ctx, &patient)
+        .await
+        .with_context(|| format!("Failed to seed patient DEMO-{:04}", seed.key))?;
+    Ok(true)
+}
+
+fn seed_id(kind: u16, key: u16) -> Uuid {
+    Uuid::from_u128(SEED_NAMESPACE | u128::from(kind) << 16 | u128::from(key))
+}
+
+fn hospital_id(key: u16) -> HospitalId {
+    seed_id(HOSPITAL, key).into()
+}
+
+fn user_id(key: u16) -> UserId {
+    seed_id(USER, key).into()
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+/// A made-up Emirates ID with a valid check digit for the patient's birth
+/// year, unless they are not yet identified
+fn national_id(seed: &SeedPatient) -> Option<EmiratesId> {
+    seed.birth_year
+        .map(|year| EmiratesId::new(year, 4_000_000 + u32::from(seed.key) * 1_237))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emirates_ids_are_valid() {
+        for id in PATIENTS.iter().filter_map(national_id) {
+            assert_eq!(id.to_string().parse::<EmiratesId>(), Ok(id));
+        }
+    }
+
+    #[test]
+    fn test_seed_data_is_consistent() {
+        let mut ids: Vec<Uuid> = Vec::new();
+        ids.extend(HOSPITALS.iter().map(|h| seed_id(HOSPITAL, h.key)));
+        ids.extend(USERS.iter().map(|u| seed_id(USER, u.key)));
+        ids.extend(PATIENTS.iter().map(|p| seed_id(PATIENT, p.key)));
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count);
+
+        let hospital_exists = |key| HOSPITALS.iter().any(|h| h.key == key);
+        assert!(USERS.iter().all(|u| hospital_exists(u.hospital)));
+        assert!(PATIENTS.iter().all(|p| hospital_exists(p.hospital)));
+    }
+
+    #[tokio::test]
+    async fn test_refuses_production() {
+        let db = Db::new(sqlx::PgPool::connect_lazy("postgres://localhost/unused").unwrap());
+        let error = seed(&Environment::Production, &db, "secret")
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("production"));
+    }
+}
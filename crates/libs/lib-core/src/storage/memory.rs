@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+
+use lib_types::errors::AppError;
+
+use super::ObjectStorage;
+
+/// Keeps objects in memory, for development and tests. Everything is lost
+/// when the process exits.
+#[derive(Default)]
+pub struct MemoryObjectStorage {
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryObjectStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for MemoryObjectStorage {
+    async fn put(&self, key: &str, _content_type: &str, content: Vec<u8>) -> Result<(), AppError> {
+        self.objects
+            .lock()
+            .map_err(|_| AppError::Internal)?
+            .insert(key.to_string(), content);
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        Ok(self
+            .objects
+            .lock()
+            .map_err(|_| AppError::Internal)?
+            .get(key)
+            .cloned())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        self.objects
+            .lock()
+            .map_err(|_| AppError::Internal)?
+            .remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let storage = MemoryObjectStorage::new();
+        storage
+            .put("patients/1/attachments/2", "image/png", b"png".to_vec())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage.get("patients/1/attachments/2").await.unwrap(),
+            Some(b"png".to_vec())
+        );
+
+        storage.delete("patients/1/attachments/2").await.unwrap();
+        storage.delete("patients/1/attachments/2").await.unwrap();
+        assert_eq!(storage.get("patients/1/attachments/2").await.unwrap(), None);
+    }
+}
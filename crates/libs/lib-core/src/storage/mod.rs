@@ -0,0 +1,46 @@
+//! Object storage for files too large for the database, such as patient
+//! attachments. Keys are opaque paths like `patients/<id>/attachments/<id>`.
+
+pub mod memory;
+pub mod s3;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+
+use lib_types::errors::AppError;
+
+pub use memory::MemoryObjectStorage;
+pub use s3::S3ObjectStorage;
+
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    /// Store `content` under `key`, replacing any existing object
+    async fn put(&self, key: &str, content_type: &str, content: Vec<u8>) -> Result<(), AppError>;
+
+    /// Read the object stored under `key`, if there is one
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError>;
+
+    /// Delete the object stored under `key`; deleting a missing object is not an error
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// Hex SHA-256 of `content`, kept with stored objects to detect corruption
+pub fn checksum(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(
+            checksum(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use object_store::aws::{AmazonS3, AmazonS3Builder};
+use object_store::path::Path;
+use object_store::{Attribute, Attributes, ObjectStore, PutOptions};
+
+use lib_types::errors::AppError;
+
+use super::ObjectStorage;
+
+/// Stores objects in an S3 bucket, or an S3-compatible service such as
+/// MinIO when an endpoint is given. Credentials are read from the usual
+/// `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` environment variables or
+/// the instance profile.
+pub struct S3ObjectStorage {
+    store: AmazonS3,
+}
+
+impl S3ObjectStorage {
+    pub fn new(bucket: &str, region: &str, endpoint: Option<&str>) -> Result<Self, AppError> {
+        let mut builder = AmazonS3Builder::from_env()
+            .with_bucket_name(bucket)
+            .with_region(region);
+        if let Some(endpoint) = endpoint {
+            builder = builder
+                .with_endpoint(endpoint)
+                .with_allow_http(endpoint.starts_with("http://"));
+        }
+
+        let store = builder.build().map_err(|e| AppError::Configuration {
+            message: format!("Invalid S3 configuration: {}", e),
+        })?;
+        Ok(Self { store })
+    }
+}
+
+fn s3_error(error: object_store::Error) -> AppError {
+    AppError::external_service_error("S3", error.to_string())
+}
+
+#[async_trait]
+impl ObjectStorage for S3ObjectStorage {
+    async fn put(&self, key: &str, content_type: &str, content: Vec<u8>) -> Result<(), AppError> {
+        let options = PutOptions {
+            attributes: Attributes::from_iter([(Attribute::ContentType, content_type.to_string())]),
+            ..Default::default()
+        };
+        self.store
+            .put_opts(&Path::from(key), content.into(), options)
+            .await
+            .map_err(s3_error)?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        let object = match self.store.get(&Path::from(key)).await {
+            Ok(object) => object,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(s3_error(e)),
+        };
+        let content = object.bytes().await.map_err(s3_error)?;
+        Ok(Some(content.to_vec()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match self.store.delete(&Path::from(key)).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(s3_error(e)),
+        }
+    }
+}
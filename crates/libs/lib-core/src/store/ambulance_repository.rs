@@ -0,0 +1,216 @@
+use sqlx::{Connection, PgConnection};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{DispatchAmbulanceRequest, LiveEvent};
+use lib_types::entities::ambulance::{Ambulance, POSITION_GEOHASH_PRECISION};
+use lib_types::enums::AmbulanceStatus;
+use lib_types::errors::{AmbulanceError, AppError};
+use lib_types::ids::{AmbulanceId, HospitalId};
+use lib_utils::location::GeoPoint;
+
+use super::incident_repository::{lock_incident, save_incident};
+use super::live_events::notify;
+use super::query_metrics::Observe;
+use super::{db_error, Db, DbExecutor, ReadPreference, Txn};
+
+const AMBULANCE_COLUMNS: &str = "id, call_sign, hospital_id, base_station, crew, status, \
+     latitude, longitude, location_updated_at, incident_id, destination, destination_latitude, \
+     destination_longitude, created_at, updated_at";
+
+/// Data access for the ambulance fleet: dispatch, status and GPS tracking.
+/// Every change is pushed to live dashboards.
+#[derive(Clone)]
+pub struct AmbulanceRepository {
+    exec: DbExecutor,
+}
+
+impl AmbulanceRepository {
+    pub fn new(db: Db) -> Self {
+        Self { exec: db.into() }
+    }
+
+    /// Run this repository's statements in `txn`
+    pub fn in_txn(txn: &Txn) -> Self {
+        Self { exec: txn.into() }
+    }
+
+    /// Find an ambulance by id within the caller's hospital
+    pub async fn find_by_id(
+        &self,
+        ctx: &RequestCtx,
+        id: AmbulanceId,
+    ) -> Result<Option<Ambulance>, AppError> {
+        let query = format!(
+            "SELECT {} FROM ambulances WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2)",
+            AMBULANCE_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Ambulance>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "ambulances.find_by_id")
+            .await
+    }
+
+    /// List a hospital's fleet by call sign. Feeds the dispatch board, so
+    /// it may read from a replica.
+    pub async fn list_for_hospital(
+        &self,
+        ctx: &RequestCtx,
+        hospital_id: HospitalId,
+    ) -> Result<Vec<Ambulance>, AppError> {
+        let query = format!(
+            "SELECT {} FROM ambulances \
+             WHERE hospital_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             ORDER BY call_sign",
+            AMBULANCE_COLUMNS
+        );
+
+        let mut conn = self
+            .exec
+            .acquire(ctx, ReadPreference::PreferReplica)
+            .await?;
+        sqlx::query_as::<_, Ambulance>(&query)
+            .bind(hospital_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "ambulances.list_for_hospital")
+            .await
+    }
+
+    /// Send an available, crewed ambulance to a call. A call that belongs
+    /// to a major incident links the ambulance to it; the incident must be
+    /// open and linked to the caller's hospital.
+    pub async fn dispatch(
+        &self,
+        ctx: &RequestCtx,
+        request: &DispatchAmbulanceRequest,
+    ) -> Result<Ambulance, AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let mut ambulance = lock_ambulance(&mut tx, ctx, request.ambulance_id).await?;
+        ambulance.dispatch_to(
+            request.incident_location.trim().to_string(),
+            request.position(),
+            request.incident_id,
+        )?;
+        if let Some(incident_id) = request.incident_id {
+            let mut incident = lock_incident(&mut tx, ctx, incident_id).await?;
+            incident.link(&[], &[ambulance.id], &[])?;
+            save_incident(&mut tx, ctx, &incident).await?;
+        }
+        save_ambulance(&mut tx, ctx, &ambulance).await?;
+        notify(&mut tx, ctx, &LiveEvent::ambulance_updated(&ambulance)).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(ambulance)
+    }
+
+    /// Move an ambulance along the dispatch workflow; becoming available
+    /// again ends its mission
+    pub async fn update_status(
+        &self,
+        ctx: &RequestCtx,
+        id: AmbulanceId,
+        status: AmbulanceStatus,
+    ) -> Result<Ambulance, AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let mut ambulance = lock_ambulance(&mut tx, ctx, id).await?;
+        ambulance.update_status(status)?;
+        save_ambulance(&mut tx, ctx, &ambulance).await?;
+        notify(&mut tx, ctx, &LiveEvent::ambulance_updated(&ambulance)).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(ambulance)
+    }
+
+    /// Record the latest GPS position and its geohash bucket. Positions
+    /// arrive every few seconds, so this is a single statement without
+    /// locking the row first.
+    pub async fn update_location(
+        &self,
+        ctx: &RequestCtx,
+        id: AmbulanceId,
+        position: GeoPoint,
+    ) -> Result<Ambulance, AppError> {
+        if !position.is_valid() {
+            return Err(AmbulanceError::InvalidLocation {
+                latitude: position.lat,
+                longitude: position.lon,
+            }
+            .into());
+        }
+
+        let query = format!(
+            "UPDATE ambulances SET latitude = $2, longitude = $3, position_geohash = $4, \
+             location_updated_at = NOW(), updated_at = NOW() \
+             WHERE id = $1 AND ($5::uuid IS NULL OR hospital_id = $5) RETURNING {}",
+            AMBULANCE_COLUMNS
+        );
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let ambulance = sqlx::query_as::<_, Ambulance>(&query)
+            .bind(id)
+            .bind(position.lat)
+            .bind(position.lon)
+            .bind(position.geohash(POSITION_GEOHASH_PRECISION))
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "ambulances.update_location")
+            .await?
+            .ok_or(AmbulanceError::NotFound { ambulance_id: id })?;
+        notify(&mut conn, ctx, &LiveEvent::ambulance_updated(&ambulance)).await?;
+
+        Ok(ambulance)
+    }
+}
+
+/// Load an ambulance within the caller's hospital and lock it for the
+/// transaction
+async fn lock_ambulance(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    id: AmbulanceId,
+) -> Result<Ambulance, AppError> {
+    let query = format!(
+        "SELECT {} FROM ambulances WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+         FOR UPDATE",
+        AMBULANCE_COLUMNS
+    );
+
+    sqlx::query_as::<_, Ambulance>(&query)
+        .bind(id)
+        .bind(ctx.tenant_hospital_id())
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?
+        .ok_or_else(|| AmbulanceError::NotFound { ambulance_id: id }.into())
+}
+
+/// Write an ambulance's status and mission
+async fn save_ambulance(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    ambulance: &Ambulance,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE ambulances SET status = $2, incident_id = $3, destination = $4, \
+         destination_latitude = $5, destination_longitude = $6, updated_at = $7 WHERE id = $1",
+    )
+    .bind(ambulance.id)
+    .bind(ambulance.status)
+    .bind(ambulance.incident_id)
+    .bind(&ambulance.destination)
+    .bind(ambulance.destination_latitude)
+    .bind(ambulance.destination_longitude)
+    .bind(ambulance.updated_at)
+    .execute(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+
+    Ok(())
+}
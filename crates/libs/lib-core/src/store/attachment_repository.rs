@@ -0,0 +1,93 @@
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::Attachment;
+use lib_types::errors::AppError;
+use lib_types::ids::PatientId;
+
+use super::query_metrics::Observe;
+use super::{db_error, Db, ReadPreference};
+
+const ATTACHMENT_COLUMNS: &str = "id, patient_id, hospital_id, kind, file_name, content_type, \
+     size_bytes, checksum, storage_key, uploaded_by, created_at";
+
+/// Data access for the metadata of patient attachments. The content itself
+/// is kept in object storage.
+#[derive(Clone)]
+pub struct AttachmentRepository {
+    db: Db,
+}
+
+impl AttachmentRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, ctx: &RequestCtx, attachment: &Attachment) -> Result<(), AppError> {
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query(
+            "INSERT INTO patient_attachments (id, patient_id, hospital_id, kind, file_name, \
+             content_type, size_bytes, checksum, storage_key, uploaded_by, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+        )
+        .bind(attachment.id)
+        .bind(attachment.patient_id)
+        .bind(attachment.hospital_id)
+        .bind(attachment.kind)
+        .bind(&attachment.file_name)
+        .bind(&attachment.content_type)
+        .bind(attachment.size_bytes)
+        .bind(&attachment.checksum)
+        .bind(&attachment.storage_key)
+        .bind(attachment.uploaded_by)
+        .bind(attachment.created_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+        Ok(())
+    }
+
+    /// List a patient's attachments, oldest first
+    pub async fn list_for_patient(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<Attachment>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patient_attachments \
+             WHERE patient_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             ORDER BY created_at",
+            ATTACHMENT_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Attachment>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "attachments.list_for_patient")
+            .await
+    }
+
+    pub async fn find(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        id: Uuid,
+    ) -> Result<Option<Attachment>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patient_attachments \
+             WHERE id = $1 AND patient_id = $2 AND ($3::uuid IS NULL OR hospital_id = $3)",
+            ATTACHMENT_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Attachment>(&query)
+            .bind(id)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+}
@@ -0,0 +1,87 @@
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::AuthAuditQuery;
+use lib_types::entities::AuthAuditEntry;
+use lib_types::errors::AppError;
+
+use super::query_metrics::Observe;
+use super::{db_error, Db, ReadPreference};
+
+const AUTH_AUDIT_COLUMNS: &str = "id, occurred_at, event, outcome, user_id, username, \
+     ip_address, user_agent, error_code, resource, correlation_id";
+
+/// Data access for the append-only authentication audit log
+#[derive(Clone)]
+pub struct AuthAuditRepository {
+    db: Db,
+}
+
+impl AuthAuditRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Append an entry to the audit log, tagged with the request's correlation
+    /// id unless the entry already has one
+    pub async fn record(&self, ctx: &RequestCtx, entry: &AuthAuditEntry) -> Result<(), AppError> {
+        let correlation_id = entry
+            .correlation_id
+            .as_deref()
+            .unwrap_or(ctx.correlation_id());
+
+        sqlx::query(
+            "INSERT INTO auth_audit (id, occurred_at, event, outcome, user_id, username, \
+             ip_address, user_agent, error_code, resource, correlation_id) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+        )
+        .bind(entry.id)
+        .bind(entry.occurred_at)
+        .bind(entry.event)
+        .bind(entry.outcome)
+        .bind(entry.user_id)
+        .bind(&entry.username)
+        .bind(&entry.ip_address)
+        .bind(&entry.user_agent)
+        .bind(&entry.error_code)
+        .bind(&entry.resource)
+        .bind(correlation_id)
+        .execute(self.db.primary())
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        Ok(())
+    }
+
+    /// Search the audit log, newest entries first. Reads from a replica
+    /// when one is healthy.
+    pub async fn search(
+        &self,
+        ctx: &RequestCtx,
+        query: &AuthAuditQuery,
+    ) -> Result<Vec<AuthAuditEntry>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM auth_audit \
+             WHERE ($1::uuid IS NULL OR user_id = $1) \
+             AND ($2::auth_event IS NULL OR event = $2) \
+             AND ($3::auth_outcome IS NULL OR outcome = $3) \
+             AND ($4::text IS NULL OR ip_address = $4) \
+             AND ($5::text IS NULL OR correlation_id = $5) \
+             AND ($6::timestamptz IS NULL OR occurred_at >= $6) \
+             AND ($7::timestamptz IS NULL OR occurred_at <= $7) \
+             ORDER BY occurred_at DESC LIMIT $8",
+            AUTH_AUDIT_COLUMNS
+        );
+
+        sqlx::query_as::<_, AuthAuditEntry>(&sql)
+            .bind(query.user_id)
+            .bind(query.event)
+            .bind(query.outcome)
+            .bind(&query.ip_address)
+            .bind(&query.correlation_id)
+            .bind(query.from)
+            .bind(query.to)
+            .bind(i64::from(query.limit()))
+            .fetch_all(self.db.reader(ReadPreference::PreferReplica))
+            .observe(self.db.metrics(), ctx, "auth_audit.search")
+            .await
+    }
+}
@@ -0,0 +1,298 @@
+use sqlx::{Connection, PgConnection};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::{Bed, Patient, PatientEvent};
+use lib_types::enums::{BedStatus, BedType};
+use lib_types::errors::{AppError, HospitalError, PatientError};
+use lib_types::ids::{BedId, HospitalId, PatientId};
+
+use super::patient_event_repository::record_event;
+use super::query_metrics::Observe;
+use super::{check_version, db_error, Db, DbExecutor, ReadPreference, Txn, PATIENT_COLUMNS};
+
+const BED_COLUMNS: &str = "id, hospital_id, ward, room, bed_number, bed_type, status, \
+     current_patient_id, created_at, updated_at, version, created_by, updated_by";
+
+/// Data access for hospital beds and bed assignments
+#[derive(Clone)]
+pub struct BedRepository {
+    exec: DbExecutor,
+}
+
+impl BedRepository {
+    pub fn new(db: Db) -> Self {
+        Self { exec: db.into() }
+    }
+
+    /// Run this repository's statements in `txn`
+    pub fn in_txn(txn: &Txn) -> Self {
+        Self { exec: txn.into() }
+    }
+
+    /// Find a bed by id within the caller's hospital
+    pub async fn find_by_id(&self, ctx: &RequestCtx, id: BedId) -> Result<Option<Bed>, AppError> {
+        let query = format!(
+            "SELECT {} FROM beds WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2)",
+            BED_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Bed>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "beds.find_by_id")
+            .await
+    }
+
+    /// List a hospital's beds, optionally only those with the given status.
+    /// Feeds the bed board, so it may read from a replica.
+    pub async fn list_for_hospital(
+        &self,
+        ctx: &RequestCtx,
+        hospital_id: HospitalId,
+        status: Option<BedStatus>,
+    ) -> Result<Vec<Bed>, AppError> {
+        let query = format!(
+            "SELECT {} FROM beds WHERE hospital_id = $1 AND ($2::bed_status IS NULL OR status = $2) \
+             ORDER BY ward, room, bed_number",
+            BED_COLUMNS
+        );
+
+        let mut conn = self
+            .exec
+            .acquire(ctx, ReadPreference::PreferReplica)
+            .await?;
+        sqlx::query_as::<_, Bed>(&query)
+            .bind(hospital_id)
+            .bind(status)
+            .fetch_all(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "beds.list_for_hospital")
+            .await
+    }
+
+    /// List the free beds of a type in a hospital
+    pub async fn find_available(
+        &self,
+        ctx: &RequestCtx,
+        hospital_id: HospitalId,
+        bed_type: BedType,
+    ) -> Result<Vec<Bed>, AppError> {
+        let query = format!(
+            "SELECT {} FROM beds WHERE hospital_id = $1 AND bed_type = $2 AND status = $3 \
+             ORDER BY ward, room, bed_number",
+            BED_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Bed>(&query)
+            .bind(hospital_id)
+            .bind(bed_type)
+            .bind(BedStatus::Available)
+            .fetch_all(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "beds.find_available")
+            .await
+    }
+
+    /// Insert a new bed
+    pub async fn create(&self, ctx: &RequestCtx, bed: &Bed) -> Result<(), AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query(
+            "INSERT INTO beds (id, hospital_id, ward, room, bed_number, bed_type, status, \
+             current_patient_id, created_at, updated_at, created_by, updated_by) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11)",
+        )
+        .bind(bed.id)
+        .bind(bed.hospital_id)
+        .bind(&bed.ward)
+        .bind(&bed.room)
+        .bind(&bed.bed_number)
+        .bind(bed.bed_type)
+        .bind(bed.status)
+        .bind(bed.current_patient_id)
+        .bind(bed.created_at)
+        .bind(bed.updated_at)
+        .bind(ctx.user_id())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                AppError::Conflict {
+                    message: format!("Bed {} already exists", bed.label()),
+                }
+            }
+            _ => db_error(ctx, e),
+        })?;
+
+        Ok(())
+    }
+
+    /// Change a bed's status (cleaning, back in service, ...) following the
+    /// bed workflow; patients are placed with `assign_patient` and occupied
+    /// beds freed with `release`. Fails with `AppError::Conflict` when
+    /// `expected_version` is given and the bed has moved on from it.
+    pub async fn update_status(
+        &self,
+        ctx: &RequestCtx,
+        id: BedId,
+        status: BedStatus,
+        expected_version: Option<i64>,
+    ) -> Result<Bed, AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let mut bed = lock_bed(&mut tx, ctx, id).await?;
+        check_version("Bed", expected_version, bed.version)?;
+        if bed.status == BedStatus::Occupied {
+            return Err(HospitalError::InvalidBedStatusTransition {
+                current: bed.status,
+                requested: status,
+            }
+            .into());
+        }
+        bed.update_status(status)?;
+        save_bed(&mut tx, ctx, &mut bed).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(bed)
+    }
+
+    /// Place a patient in a bed after checking the bed is free, in the
+    /// patient's hospital and suitable for them. A bed the patient occupied
+    /// before is released for cleaning.
+    pub async fn assign_patient(
+        &self,
+        ctx: &RequestCtx,
+        bed_id: BedId,
+        patient_id: PatientId,
+    ) -> Result<Bed, AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let mut bed = lock_bed(&mut tx, ctx, bed_id).await?;
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR UPDATE",
+            PATIENT_COLUMNS
+        );
+        let mut patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        let current_bed = patient.bed_id;
+        let previous_bed = current_bed.filter(|id| *id != bed_id);
+        patient.assign_bed(&mut bed)?;
+
+        if let Some(previous_id) = previous_bed {
+            let mut previous = lock_bed(&mut tx, ctx, previous_id).await?;
+            if previous.current_patient_id == Some(patient_id) {
+                previous.release();
+                save_bed(&mut tx, ctx, &mut previous).await?;
+            }
+        }
+        save_bed(&mut tx, ctx, &mut bed).await?;
+
+        sqlx::query(
+            "UPDATE patients SET bed_id = $2, updated_at = $3, updated_by = $4, \
+             version = version + 1 WHERE id = $1",
+        )
+        .bind(patient.id)
+        .bind(patient.bed_id)
+        .bind(patient.updated_at)
+        .bind(ctx.user_id())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+        record_event(
+            &mut tx,
+            ctx,
+            &PatientEvent::bed_assigned(&patient, current_bed, ctx.user_id()),
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(bed)
+    }
+
+    /// Free a bed when its patient leaves; the bed goes to cleaning
+    pub async fn release(&self, ctx: &RequestCtx, bed_id: BedId) -> Result<Bed, AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let mut bed = lock_bed(&mut tx, ctx, bed_id).await?;
+        if bed.status != BedStatus::Occupied {
+            return Err(HospitalError::InvalidBedStatusTransition {
+                current: bed.status,
+                requested: BedStatus::Cleaning,
+            }
+            .into());
+        }
+
+        if let Some(patient_id) = bed.current_patient_id {
+            let query = format!(
+                "UPDATE patients SET bed_id = NULL, updated_at = NOW(), updated_by = $3, \
+                 version = version + 1 WHERE id = $1 AND bed_id = $2 RETURNING {}",
+                PATIENT_COLUMNS
+            );
+            let released = sqlx::query_as::<_, Patient>(&query)
+                .bind(patient_id)
+                .bind(bed.id)
+                .bind(ctx.user_id())
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| db_error(ctx, e))?;
+            if let Some(patient) = released {
+                let event = PatientEvent::bed_assigned(&patient, Some(bed.id), ctx.user_id());
+                record_event(&mut tx, ctx, &event).await?;
+            }
+        }
+        bed.release();
+        save_bed(&mut tx, ctx, &mut bed).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(bed)
+    }
+}
+
+/// Load a bed within the caller's hospital and lock it for the transaction
+async fn lock_bed(conn: &mut PgConnection, ctx: &RequestCtx, id: BedId) -> Result<Bed, AppError> {
+    let query = format!(
+        "SELECT {} FROM beds WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) FOR UPDATE",
+        BED_COLUMNS
+    );
+
+    sqlx::query_as::<_, Bed>(&query)
+        .bind(id)
+        .bind(ctx.tenant_hospital_id())
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?
+        .ok_or_else(|| HospitalError::BedNotFound { bed_id: id }.into())
+}
+
+/// Write a bed's status and occupant, bumping its version
+async fn save_bed(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    bed: &mut Bed,
+) -> Result<(), AppError> {
+    bed.updated_by = ctx.user_id();
+    bed.version = sqlx::query_scalar(
+        "UPDATE beds SET status = $2, current_patient_id = $3, updated_at = $4, \
+         updated_by = $5, version = version + 1 WHERE id = $1 RETURNING version",
+    )
+    .bind(bed.id)
+    .bind(bed.status)
+    .bind(bed.current_patient_id)
+    .bind(bed.updated_at)
+    .bind(bed.updated_by)
+    .fetch_one(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+
+    Ok(())
+}
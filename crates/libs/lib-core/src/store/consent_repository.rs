@@ -0,0 +1,168 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::RecordConsentRequest;
+use lib_types::entities::{Consent, Patient};
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{PatientId, UserId};
+
+use super::{db_error, Db, ReadPreference, PATIENT_COLUMNS};
+
+const CONSENT_COLUMNS: &str = "id, patient_id, hospital_id, consent_type, granted_by, \
+     relationship, scope, method, witness, expires_at, recorded_by, recorded_at, withdrawn_by, \
+     withdrawn_at, withdrawal_reason";
+
+/// Data access for consents recorded for patients
+#[derive(Clone)]
+pub struct ConsentRepository {
+    db: Db,
+}
+
+impl ConsentRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// List a patient's consents, including withdrawn and expired ones,
+    /// newest first
+    pub async fn list_for_patient(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<Consent>, AppError> {
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        list_consents(&mut conn, ctx, patient_id).await
+    }
+
+    /// Record a consent. A minor cannot consent for themselves; someone
+    /// entitled to consent on their behalf must be named.
+    pub async fn record(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        request: &RecordConsentRequest,
+        recorded_by: UserId,
+    ) -> Result<Consent, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR SHARE",
+            PATIENT_COLUMNS
+        );
+        let patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        let consent = request.to_consent(patient.id, patient.hospital_id, recorded_by);
+        if patient.is_minor() && !consent.is_valid_for_minor() {
+            return Err(PatientError::MinorConsentRequired.into());
+        }
+
+        sqlx::query(
+            "INSERT INTO patient_consents (id, patient_id, hospital_id, consent_type, \
+             granted_by, relationship, scope, method, witness, expires_at, recorded_by, \
+             recorded_at, withdrawn_by, withdrawn_at, withdrawal_reason) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)",
+        )
+        .bind(consent.id)
+        .bind(consent.patient_id)
+        .bind(consent.hospital_id)
+        .bind(consent.consent_type)
+        .bind(&consent.granted_by)
+        .bind(consent.relationship)
+        .bind(&consent.scope)
+        .bind(consent.method)
+        .bind(&consent.witness)
+        .bind(consent.expires_at)
+        .bind(consent.recorded_by)
+        .bind(consent.recorded_at)
+        .bind(consent.withdrawn_by)
+        .bind(consent.withdrawn_at)
+        .bind(&consent.withdrawal_reason)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(consent)
+    }
+
+    /// Withdraw a consent. The record is kept so the history shows what was
+    /// consented to and when it stopped applying.
+    pub async fn withdraw(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        id: Uuid,
+        withdrawn_by: UserId,
+        reason: String,
+    ) -> Result<Consent, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM patient_consents \
+             WHERE id = $1 AND patient_id = $2 AND ($3::uuid IS NULL OR hospital_id = $3) \
+             FOR UPDATE",
+            CONSENT_COLUMNS
+        );
+        let mut consent = sqlx::query_as::<_, Consent>(&query)
+            .bind(id)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or_else(|| AppError::NotFound {
+                resource: "Consent".to_string(),
+            })?;
+
+        if !consent.withdraw(withdrawn_by, reason) {
+            return Err(AppError::Conflict {
+                message: format!("Consent {} is already withdrawn", consent.id),
+            });
+        }
+
+        sqlx::query(
+            "UPDATE patient_consents SET withdrawn_by = $2, withdrawn_at = $3, \
+             withdrawal_reason = $4 WHERE id = $1",
+        )
+        .bind(consent.id)
+        .bind(consent.withdrawn_by)
+        .bind(consent.withdrawn_at)
+        .bind(&consent.withdrawal_reason)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(consent)
+    }
+}
+
+/// Load a patient's consents on a connection the caller holds, so a check
+/// such as `Patient::require_consent` can run inside its transaction
+pub(crate) async fn list_consents(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    patient_id: PatientId,
+) -> Result<Vec<Consent>, AppError> {
+    let query = format!(
+        "SELECT {} FROM patient_consents \
+         WHERE patient_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+         ORDER BY recorded_at DESC",
+        CONSENT_COLUMNS
+    );
+
+    sqlx::query_as::<_, Consent>(&query)
+        .bind(patient_id)
+        .bind(ctx.tenant_hospital_id())
+        .fetch_all(conn)
+        .await
+        .map_err(|e| db_error(ctx, e))
+}
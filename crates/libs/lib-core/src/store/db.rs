@@ -0,0 +1,198 @@
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use super::{DbPolicy, QueryMetrics};
+
+/// How often replicas are checked, and how long a check may take
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Where a read may be served from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadPreference {
+    /// Always the primary; reads see the caller's own writes
+    #[default]
+    Primary,
+    /// A healthy replica when there is one, otherwise the primary. For
+    /// dashboards and reports that tolerate a little replication lag.
+    PreferReplica,
+}
+
+/// Database handle shared by the store layer: the primary, which takes
+/// every write, and optional read replicas. Dereferences to the primary.
+#[derive(Clone)]
+pub struct Db {
+    primary: PgPool,
+    replicas: Arc<Vec<Replica>>,
+    next_replica: Arc<AtomicUsize>,
+    metrics: QueryMetrics,
+    policy: DbPolicy,
+}
+
+struct Replica {
+    pool: PgPool,
+    healthy: AtomicBool,
+}
+
+impl Db {
+    pub fn new(primary: PgPool) -> Self {
+        Self {
+            primary,
+            replicas: Arc::new(Vec::new()),
+            next_replica: Arc::new(AtomicUsize::new(0)),
+            metrics: QueryMetrics::new(),
+            policy: DbPolicy::default(),
+        }
+    }
+
+    /// Add read replicas. They take reads once a health check has passed.
+    pub fn with_replicas(mut self, replicas: Vec<PgPool>) -> Self {
+        self.replicas = Arc::new(
+            replicas
+                .into_iter()
+                .map(|pool| Replica {
+                    pool,
+                    healthy: AtomicBool::new(false),
+                })
+                .collect(),
+        );
+        self
+    }
+
+    /// Retry and circuit breaking for taking connections from the pools
+    pub fn with_policy(mut self, policy: DbPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn policy(&self) -> &DbPolicy {
+        &self.policy
+    }
+
+    /// The primary, for writes and reads that must be current
+    pub fn primary(&self) -> &PgPool {
+        &self.primary
+    }
+
+    /// Timings of the store's named queries
+    pub fn metrics(&self) -> &QueryMetrics {
+        &self.metrics
+    }
+
+    /// The replica pools, in configuration order
+    pub fn replicas(&self) -> impl Iterator<Item = &PgPool> {
+        self.replicas.iter().map(|replica| &replica.pool)
+    }
+
+    /// A pool for a read: healthy replicas take turns, and the primary
+    /// serves the read when no replica is healthy
+    pub fn reader(&self, preference: ReadPreference) -> &PgPool {
+        if preference == ReadPreference::Primary || self.replicas.is_empty() {
+            return &self.primary;
+        }
+
+        let start = self.next_replica.fetch_add(1, Ordering::Relaxed);
+        (0..self.replicas.len())
+            .map(|offset| &self.replicas[(start + offset) % self.replicas.len()])
+            .find(|replica| replica.healthy.load(Ordering::Relaxed))
+            .map_or(&self.primary, |replica| &replica.pool)
+    }
+
+    /// Check every replica now
+    pub async fn check_replicas(&self) {
+        for (index, replica) in self.replicas.iter().enumerate() {
+            let healthy = matches!(
+                tokio::time::timeout(
+                    HEALTH_CHECK_TIMEOUT,
+                    sqlx::query("SELECT 1").execute(&replica.pool),
+                )
+                .await,
+                Ok(Ok(_))
+            );
+            let was_healthy = replica.healthy.swap(healthy, Ordering::Relaxed);
+            match (was_healthy, healthy) {
+                (false, true) => info!("Read replica {} is healthy", index + 1),
+                (true, false) => warn!(
+                    "Read replica {} failed its health check; reads fall back to the primary",
+                    index + 1
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    /// Keep checking the replicas so unhealthy ones stop taking reads and
+    /// recovered ones take them again. Does nothing without replicas.
+    pub fn spawn_health_checks(&self) -> Option<JoinHandle<()>> {
+        if self.replicas.is_empty() {
+            return None;
+        }
+
+        let db = self.clone();
+        Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                db.check_replicas().await;
+            }
+        }))
+    }
+}
+
+impl Deref for Db {
+    type Target = PgPool;
+
+    fn deref(&self) -> &PgPool {
+        &self.primary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    fn lazy_pool(name: &str) -> PgPool {
+        PgPoolOptions::new()
+            .connect_lazy(&format!("postgres://localhost/{}", name))
+            .unwrap()
+    }
+
+    fn database_name(pool: &PgPool) -> String {
+        pool.connect_options().get_database().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_reader_routing() {
+        let db = Db::new(lazy_pool("primary"))
+            .with_replicas(vec![lazy_pool("replica1"), lazy_pool("replica2")]);
+
+        // Replicas start unhealthy until checked
+        assert_eq!(
+            database_name(db.reader(ReadPreference::PreferReplica)),
+            "primary"
+        );
+
+        db.replicas[0].healthy.store(true, Ordering::Relaxed);
+        db.replicas[1].healthy.store(true, Ordering::Relaxed);
+        let first = database_name(db.reader(ReadPreference::PreferReplica));
+        let second = database_name(db.reader(ReadPreference::PreferReplica));
+        assert_ne!(first, second);
+        assert!(first.starts_with("replica") && second.starts_with("replica"));
+        assert_eq!(database_name(db.reader(ReadPreference::Primary)), "primary");
+
+        db.replicas[0].healthy.store(false, Ordering::Relaxed);
+        for _ in 0..3 {
+            assert_eq!(
+                database_name(db.reader(ReadPreference::PreferReplica)),
+                "replica2"
+            );
+        }
+    }
+}
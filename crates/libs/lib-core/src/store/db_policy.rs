@@ -0,0 +1,272 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::errors::AppError;
+use lib_utils::retry::RetryPolicy;
+
+/// Stops sending work to a database that keeps failing. After
+/// `failure_threshold` transient failures in a row the circuit opens and
+/// calls fail at once with `ServiceUnavailable`; once `open_for` has passed
+/// one call is let through, and its outcome closes or reopens the circuit.
+/// A trial that never reports back (e.g. its request was cancelled) is
+/// replaced by a new one after another `open_for`.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32, // Zero never opens the circuit
+    open_for: Duration,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BreakerState {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { since: Instant }, // A trial call is in flight
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            failure_threshold,
+            open_for,
+            state: Arc::new(Mutex::new(BreakerState::Closed { failures: 0 })),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        !matches!(*self.lock(), BreakerState::Closed { .. })
+    }
+
+    /// Whether a call may go ahead; the first call after the circuit has
+    /// been open long enough becomes the trial, as does the first call after
+    /// a trial has gone unanswered for as long
+    fn allow(&self) -> bool {
+        let mut state = self.lock();
+        let now = Instant::now();
+        match *state {
+            BreakerState::Closed { .. } => true,
+            BreakerState::Open { until } if now >= until => {
+                *state = BreakerState::HalfOpen { since: now };
+                true
+            }
+            BreakerState::HalfOpen { since } if now >= since + self.open_for => {
+                *state = BreakerState::HalfOpen { since: now };
+                true
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => false,
+        }
+    }
+
+    fn record_success(&self) {
+        let mut state = self.lock();
+        if matches!(*state, BreakerState::HalfOpen { .. }) {
+            info!("Database circuit closed; the database answers again");
+        }
+        *state = BreakerState::Closed { failures: 0 };
+    }
+
+    fn record_failure(&self) {
+        if self.failure_threshold == 0 {
+            return;
+        }
+
+        let mut state = self.lock();
+        let failures = match *state {
+            BreakerState::Closed { failures } => failures + 1,
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => self.failure_threshold,
+        };
+        *state = if failures >= self.failure_threshold {
+            if !matches!(*state, BreakerState::Open { .. }) {
+                warn!(
+                    "Database circuit opened after {} failures; failing fast for {:?}",
+                    failures, self.open_for
+                );
+            }
+            BreakerState::Open {
+                until: Instant::now() + self.open_for,
+            }
+        } else {
+            BreakerState::Closed { failures }
+        };
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(5, Duration::from_secs(30))
+    }
+}
+
+/// Retries and circuit breaking for database work. Only transient errors
+/// (`AppError::is_retryable`: pool timeouts, lost connections, serialization
+/// failures and deadlocks) are retried or counted against the database;
+/// wrapped work must be safe to repeat after one of those.
+#[derive(Clone, Default)]
+pub struct DbPolicy {
+    pub retry: RetryPolicy,
+    pub breaker: CircuitBreaker,
+}
+
+impl DbPolicy {
+    pub fn new(retry: RetryPolicy, breaker: CircuitBreaker) -> Self {
+        Self { retry, breaker }
+    }
+
+    /// Run `operation`, retrying it after transient errors
+    pub async fn run<T, F, Fut>(&self, ctx: &RequestCtx, mut operation: F) -> Result<T, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let started = Instant::now();
+        let mut retry = 0;
+        loop {
+            if !self.breaker.allow() {
+                return Err(AppError::ServiceUnavailable);
+            }
+
+            match self.retry.attempt(operation()).await {
+                Err(error) if error.is_retryable() => {
+                    self.breaker.record_failure();
+                    let Some(delay) = self.retry.next_delay(retry, started) else {
+                        return Err(error);
+                    };
+                    warn!(
+                        correlation_id = ctx.correlation_id(),
+                        "Retrying database operation in {:?} ({} of {}): {}",
+                        delay,
+                        retry + 1,
+                        self.retry.max_retries,
+                        error
+                    );
+                    tokio::time::sleep(delay).await;
+                    retry += 1;
+                }
+                result => {
+                    self.breaker.record_success();
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn policy(max_retries: u32, failure_threshold: u32, open_for: Duration) -> DbPolicy {
+        DbPolicy::new(
+            RetryPolicy {
+                max_retries,
+                base_delay: Duration::ZERO,
+                max_delay: Duration::ZERO,
+                ..Default::default()
+            },
+            CircuitBreaker::new(failure_threshold, open_for),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors_only() {
+        let ctx = RequestCtx::system();
+        let policy = policy(2, 0, Duration::ZERO);
+
+        let calls = AtomicU32::new(0);
+        let result = policy
+            .run(&ctx, || async {
+                match calls.fetch_add(1, Ordering::Relaxed) {
+                    0 | 1 => Err(AppError::ServiceUnavailable),
+                    _ => Ok(42),
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = policy
+            .run(&ctx, || async {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Err(AppError::not_found("Patient"))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = policy
+            .run(&ctx, || async {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Err(AppError::ServiceUnavailable)
+            })
+            .await;
+        assert!(matches!(result, Err(AppError::ServiceUnavailable)));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_breaker_fails_fast_until_a_trial_succeeds() {
+        let ctx = RequestCtx::system();
+        let policy = policy(0, 2, Duration::from_secs(3600));
+        let calls = AtomicU32::new(0);
+        let failing = || async {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Err::<(), _>(AppError::ServiceUnavailable)
+        };
+
+        let _ = policy.run(&ctx, failing).await;
+        assert!(!policy.breaker.is_open());
+        let _ = policy.run(&ctx, failing).await;
+        assert!(policy.breaker.is_open());
+
+        // Open: the database is not touched
+        let _ = policy.run(&ctx, failing).await;
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // Once the circuit may be tried again, a success closes it
+        let policy = DbPolicy {
+            breaker: CircuitBreaker::new(2, Duration::ZERO),
+            ..policy
+        };
+        let _ = policy.run(&ctx, failing).await;
+        let _ = policy.run(&ctx, failing).await;
+        assert!(policy.breaker.is_open());
+        assert_eq!(policy.run(&ctx, || async { Ok(1) }).await.unwrap(), 1);
+        assert!(!policy.breaker.is_open());
+    }
+
+    #[tokio::test]
+    async fn test_dropped_trial_is_replaced() {
+        let ctx = RequestCtx::system();
+        let open_for = Duration::from_millis(50);
+        let policy = policy(0, 1, open_for);
+
+        let _ = policy
+            .run(&ctx, || async { Err::<(), _>(AppError::ServiceUnavailable) })
+            .await;
+        assert!(policy.breaker.is_open());
+        tokio::time::sleep(open_for).await;
+
+        // The trial's request is cancelled before the database answers
+        let trial = policy.run(&ctx, std::future::pending::<Result<(), AppError>>);
+        assert!(tokio::time::timeout(Duration::from_millis(1), trial).await.is_err());
+        assert!(matches!(
+            policy.run(&ctx, || async { Ok(1) }).await,
+            Err(AppError::ServiceUnavailable)
+        ));
+
+        tokio::time::sleep(open_for).await;
+        assert_eq!(policy.run(&ctx, || async { Ok(1) }).await.unwrap(), 1);
+        assert!(!policy.breaker.is_open());
+    }
+}
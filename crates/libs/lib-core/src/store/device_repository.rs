@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::UserDevice;
+use lib_types::errors::AppError;
+use lib_types::ids::UserId;
+
+use super::{db_error, Db};
+
+const DEVICE_COLUMNS: &str = "id, user_id, fingerprint, name, user_agent, last_ip, \
+     trusted_until, created_at, last_seen_at";
+
+/// Data access for the devices users log in from
+#[derive(Clone)]
+pub struct DeviceRepository {
+    db: Db,
+}
+
+impl DeviceRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Register a login from a device, creating the device on first sight
+    pub async fn record_login(
+        &self,
+        ctx: &RequestCtx,
+        user_id: UserId,
+        fingerprint: &str,
+        user_agent: Option<&str>,
+        ip_address: Option<&str>,
+    ) -> Result<UserDevice, AppError> {
+        let query = format!(
+            "INSERT INTO user_devices (user_id, fingerprint, user_agent, last_ip) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (user_id, fingerprint) DO UPDATE SET \
+             user_agent = EXCLUDED.user_agent, last_ip = EXCLUDED.last_ip, last_seen_at = NOW() \
+             RETURNING {}",
+            DEVICE_COLUMNS
+        );
+
+        sqlx::query_as::<_, UserDevice>(&query)
+            .bind(user_id)
+            .bind(fingerprint)
+            .bind(user_agent)
+            .bind(ip_address)
+            .fetch_one(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// List a user's devices, most recently used first
+    pub async fn list_for_user(
+        &self,
+        ctx: &RequestCtx,
+        user_id: UserId,
+    ) -> Result<Vec<UserDevice>, AppError> {
+        let query = format!(
+            "SELECT {} FROM user_devices WHERE user_id = $1 ORDER BY last_seen_at DESC",
+            DEVICE_COLUMNS
+        );
+
+        sqlx::query_as::<_, UserDevice>(&query)
+            .bind(user_id)
+            .fetch_all(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Mark one of a user's devices as trusted until the given time.
+    ///
+    /// Returns `None` if the user has no such device.
+    pub async fn trust(
+        &self,
+        ctx: &RequestCtx,
+        user_id: UserId,
+        id: Uuid,
+        name: Option<&str>,
+        trusted_until: DateTime<Utc>,
+    ) -> Result<Option<UserDevice>, AppError> {
+        let query = format!(
+            "UPDATE user_devices SET trusted_until = $3, name = COALESCE($4, name) \
+             WHERE user_id = $1 AND id = $2 RETURNING {}",
+            DEVICE_COLUMNS
+        );
+
+        sqlx::query_as::<_, UserDevice>(&query)
+            .bind(user_id)
+            .bind(id)
+            .bind(trusted_until)
+            .bind(name)
+            .fetch_optional(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Forget one of a user's devices, returning whether it existed
+    pub async fn delete(
+        &self,
+        ctx: &RequestCtx,
+        user_id: UserId,
+        id: Uuid,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM user_devices WHERE user_id = $1 AND id = $2")
+            .bind(user_id)
+            .bind(id)
+            .execute(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
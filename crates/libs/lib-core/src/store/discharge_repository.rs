@@ -0,0 +1,159 @@
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{DischargePatientRequest, LiveEvent};
+use lib_types::entities::{DischargeSummary, Patient, PatientEvent};
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{PatientId, UserId};
+
+use super::consent_repository::list_consents;
+use super::live_events::notify;
+use super::outbox_repository::enqueue_status_change;
+use super::patient_event_repository::record_event;
+use super::query_metrics::Observe;
+use super::{db_error, Db, ReadPreference, PATIENT_COLUMNS};
+
+const SUMMARY_COLUMNS: &str = "id, patient_id, hospital_id, diagnosis, disposition, \
+     instructions, follow_up, guardian_consent_by, document, discharged_by, discharged_at";
+
+/// Data access for patient discharges and their summaries
+#[derive(Clone)]
+pub struct DischargeRepository {
+    db: Db,
+}
+
+impl DischargeRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// List a patient's discharge summaries, newest first
+    pub async fn list_for_patient(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<DischargeSummary>, AppError> {
+        let query = format!(
+            "SELECT {} FROM discharge_summaries \
+             WHERE patient_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             ORDER BY discharged_at DESC",
+            SUMMARY_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, DischargeSummary>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "discharges.list_for_patient")
+            .await
+    }
+
+    /// Discharge a patient of the caller's hospital and store the summary.
+    /// The bed the patient held goes to cleaning.
+    pub async fn discharge(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        request: &DischargePatientRequest,
+        discharged_by: UserId,
+    ) -> Result<(DischargeSummary, Patient), AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR UPDATE",
+            PATIENT_COLUMNS
+        );
+        let mut patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        let consents = list_consents(&mut tx, ctx, patient.id).await?;
+        let bed_id = patient.bed_id;
+        let previous_status = patient.status;
+        let summary = patient.discharge(
+            request.diagnosis.trim().to_string(),
+            request.disposition,
+            request.instructions.trim().to_string(),
+            request.follow_up(),
+            &consents,
+            discharged_by,
+        )?;
+
+        if let Some(bed_id) = bed_id {
+            sqlx::query(
+                "UPDATE beds SET status = 'cleaning', current_patient_id = NULL, \
+                 updated_at = NOW(), updated_by = $3, version = version + 1 \
+                 WHERE id = $1 AND current_patient_id = $2",
+            )
+            .bind(bed_id)
+            .bind(patient.id)
+            .bind(ctx.user_id())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        }
+
+        patient.updated_by = ctx.user_id();
+        patient.version = sqlx::query_scalar(
+            "UPDATE patients SET status = $2, bed_id = $3, updated_at = $4, updated_by = $5, \
+             version = version + 1 WHERE id = $1 RETURNING version",
+        )
+        .bind(patient.id)
+        .bind(patient.status)
+        .bind(patient.bed_id)
+        .bind(patient.updated_at)
+        .bind(patient.updated_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        sqlx::query(
+            "INSERT INTO discharge_summaries (id, patient_id, hospital_id, diagnosis, \
+             disposition, instructions, follow_up, guardian_consent_by, document, \
+             discharged_by, discharged_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+        )
+        .bind(summary.id)
+        .bind(summary.patient_id)
+        .bind(summary.hospital_id)
+        .bind(&summary.diagnosis)
+        .bind(summary.disposition)
+        .bind(&summary.instructions)
+        .bind(&summary.follow_up)
+        .bind(&summary.guardian_consent_by)
+        .bind(&summary.document)
+        .bind(summary.discharged_by)
+        .bind(summary.discharged_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+        enqueue_status_change(&mut tx, ctx, &patient, previous_status).await?;
+        let disposition = summary.disposition.display_name();
+        record_event(
+            &mut tx,
+            ctx,
+            &PatientEvent::status_changed(&patient, previous_status, ctx.user_id())
+                .with_reason(disposition),
+        )
+        .await?;
+        record_event(
+            &mut tx,
+            ctx,
+            &PatientEvent::bed_assigned(&patient, bed_id, ctx.user_id()),
+        )
+        .await?;
+        notify(
+            &mut tx,
+            ctx,
+            &LiveEvent::patient_status_changed(&patient, previous_status),
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok((summary, patient))
+    }
+}
@@ -0,0 +1,192 @@
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::CreateHandoverRequest;
+use lib_types::entities::{HandoverReport, Patient};
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{PatientId, UserId};
+
+use super::query_metrics::Observe;
+use super::{db_error, Db, ReadPreference, PATIENT_COLUMNS};
+
+const HANDOVER_COLUMNS: &str = "id, patient_id, hospital_id, ambulance_id, age_years, \
+     time_of_incident, mechanism, injuries, signs, treatment, created_by, created_at, \
+     acknowledged_by, acknowledged_at";
+
+/// Data access for ambulance-to-ED handover reports
+#[derive(Clone)]
+pub struct HandoverRepository {
+    db: Db,
+}
+
+impl HandoverRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// List a patient's handovers, oldest first
+    pub async fn list_for_patient(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<HandoverReport>, AppError> {
+        let query = format!(
+            "SELECT {} FROM handover_reports \
+             WHERE patient_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             ORDER BY created_at",
+            HANDOVER_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, HandoverReport>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "handovers.list_for_patient")
+            .await
+    }
+
+    /// Handovers not yet taken by receiving staff, longest waiting first
+    pub async fn list_pending(&self, ctx: &RequestCtx) -> Result<Vec<HandoverReport>, AppError> {
+        let query = format!(
+            "SELECT {} FROM handover_reports \
+             WHERE acknowledged_at IS NULL AND ($1::uuid IS NULL OR hospital_id = $1) \
+             ORDER BY created_at",
+            HANDOVER_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, HandoverReport>(&query)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "handovers.list_pending")
+            .await
+    }
+
+    /// Record the crew's handover for a patient. Without an explicit
+    /// ambulance the patient's assigned ambulance is used.
+    pub async fn create(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        request: &CreateHandoverRequest,
+        created_by: UserId,
+    ) -> Result<HandoverReport, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR SHARE",
+            PATIENT_COLUMNS
+        );
+        let patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        let ambulance_id = request
+            .ambulance_id
+            .or(patient.ambulance_id)
+            .ok_or_else(|| {
+                AppError::validation_error(
+                    "ambulance_id",
+                    "Patient has no assigned ambulance; give the ambulance explicitly",
+                )
+            })?;
+        let ambulance_exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM ambulances WHERE id = $1)")
+                .bind(ambulance_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| db_error(ctx, e))?;
+        if !ambulance_exists {
+            return Err(AppError::not_found("Ambulance"));
+        }
+
+        let mut report = HandoverReport::new(
+            &patient,
+            ambulance_id,
+            request.mechanism.trim().to_string(),
+            request.injuries.trim().to_string(),
+            request.signs.trim().to_string(),
+            request.treatment.trim().to_string(),
+            created_by,
+        );
+        if request.time_of_incident.is_some() {
+            report.time_of_incident = request.time_of_incident;
+        }
+
+        sqlx::query(
+            "INSERT INTO handover_reports (id, patient_id, hospital_id, ambulance_id, \
+             age_years, time_of_incident, mechanism, injuries, signs, treatment, created_by, \
+             created_at, acknowledged_by, acknowledged_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)",
+        )
+        .bind(report.id)
+        .bind(report.patient_id)
+        .bind(report.hospital_id)
+        .bind(report.ambulance_id)
+        .bind(report.age_years)
+        .bind(report.time_of_incident)
+        .bind(&report.mechanism)
+        .bind(&report.injuries)
+        .bind(&report.signs)
+        .bind(&report.treatment)
+        .bind(report.created_by)
+        .bind(report.created_at)
+        .bind(report.acknowledged_by)
+        .bind(report.acknowledged_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(report)
+    }
+
+    /// Record that receiving staff took the handover
+    pub async fn acknowledge(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        acknowledged_by: UserId,
+    ) -> Result<HandoverReport, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM handover_reports \
+             WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) FOR UPDATE",
+            HANDOVER_COLUMNS
+        );
+        let mut report = sqlx::query_as::<_, HandoverReport>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or_else(|| AppError::not_found("Handover"))?;
+
+        if !report.acknowledge(acknowledged_by) {
+            return Err(AppError::Conflict {
+                message: format!("Handover {} is already acknowledged", report.id),
+            });
+        }
+
+        sqlx::query(
+            "UPDATE handover_reports SET acknowledged_by = $2, acknowledged_at = $3 \
+             WHERE id = $1",
+        )
+        .bind(report.id)
+        .bind(report.acknowledged_by)
+        .bind(report.acknowledged_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(report)
+    }
+}
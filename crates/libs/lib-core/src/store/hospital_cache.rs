@@ -0,0 +1,149 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use redis::AsyncCommands;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use lib_types::dtos::CacheStats;
+
+use super::RedisPool;
+
+/// Redis hash holding every cached hospital response, one field per query
+const CACHE_KEY: &str = "cache:hospitals";
+
+/// Read-through cache in Redis for hospital lists, which ambulances poll
+/// for capacity all the time. Entries live for a short TTL and are dropped
+/// whenever a hospital or its bed count changes through a repository built
+/// `with_cache`, on every server instance sharing the Redis.
+///
+/// A read racing a change can still put the previous state back for up to
+/// the TTL. When Redis fails, lookups fall through to the database.
+#[derive(Clone)]
+pub struct HospitalCache {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    redis: Option<RedisPool>, // None for a cache built `disabled`
+    ttl: Duration, // Zero disables caching
+    hits: AtomicU64,
+    misses: AtomicU64,
+    errors: AtomicU64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    cached_at: DateTime<Utc>,
+    value: T,
+}
+
+impl HospitalCache {
+    pub fn new(redis: RedisPool, ttl: Duration) -> Self {
+        Self::build(Some(redis), ttl)
+    }
+
+    /// A cache that never caches or invalidates, for running without Redis
+    /// (e.g. in handler tests)
+    pub fn disabled() -> Self {
+        Self::build(None, Duration::ZERO)
+    }
+
+    fn build(redis: Option<RedisPool>, ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                redis,
+                ttl,
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+                errors: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.inner.hits.load(Ordering::Relaxed),
+            misses: self.inner.misses.load(Ordering::Relaxed),
+            errors: self.inner.errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Redis, while caching is enabled
+    fn enabled_redis(&self) -> Option<RedisPool> {
+        self.inner
+            .redis
+            .clone()
+            .filter(|_| !self.inner.ttl.is_zero())
+    }
+
+    /// The cached response for `field`, if there is one younger than the TTL
+    pub(crate) async fn get<T: DeserializeOwned>(&self, field: &str) -> Option<T> {
+        let mut conn = self.enabled_redis()?;
+
+        let cached: Option<String> = match conn.hget(CACHE_KEY, field).await {
+            Ok(cached) => cached,
+            Err(e) => {
+                self.inner.errors.fetch_add(1, Ordering::Relaxed);
+                warn!("Hospital cache lookup failed: {}", e);
+                return None;
+            }
+        };
+        let fresh = cached
+            .and_then(|json| serde_json::from_str::<Entry<T>>(&json).ok())
+            .filter(|entry| self.is_fresh(entry.cached_at));
+        let counter = match fresh {
+            Some(_) => &self.inner.hits,
+            None => &self.inner.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        fresh.map(|entry| entry.value)
+    }
+
+    pub(crate) async fn put<T: Serialize>(&self, field: &str, value: &T) {
+        let Some(mut conn) = self.enabled_redis() else {
+            return;
+        };
+
+        let entry = Entry {
+            cached_at: Utc::now(),
+            value,
+        };
+        let Ok(json) = serde_json::to_string(&entry) else {
+            return;
+        };
+        // The hash goes once nothing was cached for a TTL; entries that are
+        // older are skipped by `get`
+        let result = redis::pipe()
+            .hset(CACHE_KEY, field, json)
+            .ignore()
+            .expire(CACHE_KEY, self.inner.ttl.as_secs().max(1) as i64)
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await;
+        if let Err(e) = result {
+            warn!("Hospital cache update failed: {}", e);
+        }
+    }
+
+    /// Drop every cached response
+    pub async fn invalidate(&self) {
+        let Some(mut conn) = self.inner.redis.clone() else {
+            return;
+        };
+        if let Err(e) = conn.del::<_, ()>(CACHE_KEY).await {
+            // Stale entries now last until they expire
+            warn!("Hospital cache invalidation failed: {}", e);
+        }
+    }
+
+    fn is_fresh(&self, cached_at: DateTime<Utc>) -> bool {
+        Utc::now()
+            .signed_duration_since(cached_at)
+            .to_std()
+            .map_or(true, |age| age < self.inner.ttl)
+    }
+}
@@ -0,0 +1,409 @@
+use sqlx::{Connection, PgConnection, Postgres, QueryBuilder};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{
+    CursorPage, HospitalFilter, HospitalSortField, HospitalSummary, LiveEvent, PageRequest,
+    UpdateHospitalRequest,
+};
+use lib_types::entities::Hospital;
+use lib_types::errors::{AppError, HospitalError};
+use lib_types::ids::HospitalId;
+
+use super::live_events::notify;
+use super::query_metrics::Observe;
+use super::{
+    check_version, db_error, push_page, Db, DbExecutor, HospitalCache, ReadPreference, Txn,
+};
+
+const HOSPITAL_COLUMNS: &str = "id, name, license_number, location, address, phone_number, \
+     email, total_beds, available_beds, specialties, hospital_type, status, created_at, \
+     updated_at, version, deleted_at, deleted_by, created_by, updated_by";
+
+/// Data access for hospitals. Soft-deleted hospitals are left out of reads
+/// unless the repository is built `with_deleted`.
+#[derive(Clone)]
+pub struct HospitalRepository {
+    exec: DbExecutor,
+    include_deleted: bool,
+    cache: Option<HospitalCache>,
+}
+
+impl HospitalRepository {
+    pub fn new(db: Db) -> Self {
+        Self {
+            exec: db.into(),
+            include_deleted: false,
+            cache: None,
+        }
+    }
+
+    /// Run this repository's statements in `txn`
+    pub fn in_txn(txn: &Txn) -> Self {
+        Self {
+            exec: txn.into(),
+            include_deleted: false,
+            cache: None,
+        }
+    }
+
+    /// Include soft-deleted hospitals in reads
+    pub fn with_deleted(mut self) -> Self {
+        self.include_deleted = true;
+        self
+    }
+
+    /// Serve lists from `cache`, and invalidate it when a hospital changes.
+    /// Inside a transaction the invalidation comes before the commit, so a
+    /// concurrent read may cache the old state until the entry expires.
+    pub fn with_cache(mut self, cache: HospitalCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub async fn find_by_id(
+        &self,
+        ctx: &RequestCtx,
+        id: HospitalId,
+    ) -> Result<Option<Hospital>, AppError> {
+        let query = format!(
+            "SELECT {} FROM hospitals WHERE id = $1 AND ($2::uuid IS NULL OR id = $2) \
+             AND ($3 OR deleted_at IS NULL)",
+            HOSPITAL_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Hospital>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .bind(self.include_deleted)
+            .fetch_optional(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "hospitals.find_by_id")
+            .await
+    }
+
+    /// List the hospitals matching `filter` one page at a time, from the
+    /// cache or else from a replica when one is healthy. Capacity is shared
+    /// across the network so crews can pick where to go, so every caller
+    /// sees every hospital; changes stay limited to the caller's own.
+    pub async fn list(
+        &self,
+        ctx: &RequestCtx,
+        page: &PageRequest<HospitalSortField>,
+        filter: &HospitalFilter,
+    ) -> Result<CursorPage<HospitalSummary>, AppError> {
+        let cache = self.cache.as_ref().filter(|_| !self.include_deleted);
+        let field = list_cache_field(page, filter);
+        if let Some(cache) = cache {
+            if let Some(hospitals) = cache.get(&field).await {
+                return Ok(hospitals);
+            }
+        }
+
+        let mut select = self.list_query(page, filter)?;
+        let mut conn = self
+            .exec
+            .acquire(ctx, ReadPreference::PreferReplica)
+            .await?;
+        let hospitals = select
+            .build_query_as::<Hospital>()
+            .fetch_all(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "hospitals.list")
+            .await?;
+
+        let mut hospitals = CursorPage::from_rows(hospitals, page, HospitalSummary::from_hospital);
+        if filter.specialty().is_some() {
+            for hospital in &mut hospitals.items {
+                hospital.has_specialty = Some(true);
+            }
+        }
+        if let Some(cache) = cache {
+            cache.put(&field, &hospitals).await;
+        }
+        Ok(hospitals)
+    }
+
+    /// Build the query for one page of `list`; it is the same for every caller
+    fn list_query(
+        &self,
+        page: &PageRequest<HospitalSortField>,
+        filter: &HospitalFilter,
+    ) -> Result<QueryBuilder<'static, Postgres>, AppError> {
+        let mut select = QueryBuilder::new(format!(
+            "SELECT {} FROM hospitals WHERE TRUE",
+            HOSPITAL_COLUMNS
+        ));
+        if !self.include_deleted {
+            select.push(" AND deleted_at IS NULL");
+        }
+        if let Some(status) = filter.status {
+            select.push(" AND status = ").push_bind(status);
+        }
+        if let Some(specialty) = filter.specialty() {
+            select
+                .push(
+                    " AND EXISTS (SELECT 1 FROM jsonb_array_elements_text(specialties) AS s \
+                     WHERE lower(s) = lower(",
+                )
+                .push_bind(specialty.to_string())
+                .push("))");
+        }
+        if let Some(beds) = filter.min_available_beds {
+            select.push(" AND available_beds >= ").push_bind(beds);
+        }
+        push_page(&mut select, page)?;
+        Ok(select)
+    }
+
+    /// Register a hospital; license numbers are unique
+    pub async fn create(&self, ctx: &RequestCtx, hospital: &Hospital) -> Result<(), AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query(
+            "INSERT INTO hospitals (id, name, license_number, location, address, phone_number, \
+             email, total_beds, available_beds, specialties, hospital_type, status, \
+             created_at, updated_at, created_by, updated_by) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $15)",
+        )
+        .bind(hospital.id)
+        .bind(&hospital.name)
+        .bind(&hospital.license_number)
+        .bind(hospital.location)
+        .bind(&hospital.address)
+        .bind(&hospital.phone_number)
+        .bind(&hospital.email)
+        .bind(hospital.total_beds)
+        .bind(hospital.available_beds)
+        .bind(&hospital.specialties)
+        .bind(hospital.hospital_type)
+        .bind(hospital.status)
+        .bind(hospital.created_at)
+        .bind(hospital.updated_at)
+        .bind(ctx.user_id())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| license_conflict(ctx, e, &hospital.license_number))?;
+
+        self.invalidate_cache().await;
+        Ok(())
+    }
+
+    /// Apply a partial update to a hospital, returning the hospital and the
+    /// names of the fields that changed. Fails with `AppError::Conflict`
+    /// when the update carries a version the hospital has moved on from.
+    pub async fn update(
+        &self,
+        ctx: &RequestCtx,
+        id: HospitalId,
+        update: &UpdateHospitalRequest,
+    ) -> Result<(Hospital, Vec<&'static str>), AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let mut hospital = lock_hospital(&mut tx, ctx, id).await?;
+        check_version("Hospital", update.version, hospital.version)?;
+        let changed = update.apply_to(&mut hospital)?;
+        if changed.is_empty() {
+            return Ok((hospital, changed));
+        }
+
+        hospital.updated_by = ctx.user_id();
+        hospital.version = sqlx::query_scalar(
+            "UPDATE hospitals SET name = $2, license_number = $3, location = $4, address = $5, \
+             phone_number = $6, email = $7, total_beds = $8, available_beds = $9, \
+             specialties = $10, hospital_type = $11, status = $12, updated_at = $13, \
+             updated_by = $14, version = version + 1 WHERE id = $1 RETURNING version",
+        )
+        .bind(hospital.id)
+        .bind(&hospital.name)
+        .bind(&hospital.license_number)
+        .bind(hospital.location)
+        .bind(&hospital.address)
+        .bind(&hospital.phone_number)
+        .bind(&hospital.email)
+        .bind(hospital.total_beds)
+        .bind(hospital.available_beds)
+        .bind(&hospital.specialties)
+        .bind(hospital.hospital_type)
+        .bind(hospital.status)
+        .bind(hospital.updated_at)
+        .bind(hospital.updated_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| license_conflict(ctx, e, &hospital.license_number))?;
+        if changed.contains(&"available_beds") || changed.contains(&"total_beds") {
+            notify(
+                &mut tx,
+                ctx,
+                &LiveEvent::bed_availability_changed(&hospital),
+            )
+            .await?;
+        }
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        self.invalidate_cache().await;
+        Ok((hospital, changed))
+    }
+
+    /// Take one bed out of `available_beds` for an admission. Fails with
+    /// `HospitalError::AtCapacity` when no bed is left.
+    pub async fn reserve_bed(
+        &self,
+        ctx: &RequestCtx,
+        id: HospitalId,
+    ) -> Result<Hospital, AppError> {
+        self.adjust_available_beds(ctx, id, -1).await
+    }
+
+    /// Return one bed to `available_beds`, e.g. after a discharge
+    pub async fn release_bed(
+        &self,
+        ctx: &RequestCtx,
+        id: HospitalId,
+    ) -> Result<Hospital, AppError> {
+        self.adjust_available_beds(ctx, id, 1).await
+    }
+
+    /// Add `delta` (negative to take beds) to `available_beds` in a single
+    /// statement, so concurrent admissions and discharges cannot lose each
+    /// other's updates the way read-modify-write through
+    /// `Hospital::update_available_beds` can. The count stays between zero
+    /// and `total_beds`.
+    pub async fn adjust_available_beds(
+        &self,
+        ctx: &RequestCtx,
+        id: HospitalId,
+        delta: i32,
+    ) -> Result<Hospital, AppError> {
+        let query = format!(
+            "UPDATE hospitals SET available_beds = available_beds + $2, updated_at = NOW(), \
+             updated_by = $4, version = version + 1 \
+             WHERE id = $1 AND ($3::uuid IS NULL OR id = $3) AND deleted_at IS NULL \
+             AND available_beds + $2 BETWEEN 0 AND total_beds \
+             RETURNING {}",
+            HOSPITAL_COLUMNS
+        );
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let updated = sqlx::query_as::<_, Hospital>(&query)
+            .bind(id)
+            .bind(delta)
+            .bind(ctx.tenant_hospital_id())
+            .bind(ctx.user_id())
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        if let Some(hospital) = updated {
+            notify(
+                &mut conn,
+                ctx,
+                &LiveEvent::bed_availability_changed(&hospital),
+            )
+            .await?;
+            drop(conn);
+            self.invalidate_cache().await;
+            return Ok(hospital);
+        }
+        drop(conn);
+
+        // The hospital is either not visible to the caller or out of range
+        let hospital = self
+            .find_by_id(ctx, id)
+            .await?
+            .ok_or(HospitalError::NotFound { hospital_id: id })?;
+        if delta < 0 {
+            Err(HospitalError::AtCapacity.into())
+        } else {
+            Err(HospitalError::InvalidCapacityUpdate {
+                requested: hospital.available_beds + delta,
+            }
+            .into())
+        }
+    }
+
+    /// Hide a hospital from normal queries, keeping the row so it can be
+    /// restored
+    pub async fn soft_delete(
+        &self,
+        ctx: &RequestCtx,
+        id: HospitalId,
+    ) -> Result<Hospital, AppError> {
+        self.set_deleted(ctx, id, true).await
+    }
+
+    /// Bring back a soft-deleted hospital
+    pub async fn restore(&self, ctx: &RequestCtx, id: HospitalId) -> Result<Hospital, AppError> {
+        self.set_deleted(ctx, id, false).await
+    }
+
+    async fn set_deleted(
+        &self,
+        ctx: &RequestCtx,
+        id: HospitalId,
+        deleted: bool,
+    ) -> Result<Hospital, AppError> {
+        let query = format!(
+            "UPDATE hospitals SET deleted_at = CASE WHEN $2 THEN NOW() END, \
+             deleted_by = CASE WHEN $2 THEN $3 END, updated_at = NOW(), updated_by = $3, \
+             version = version + 1 \
+             WHERE id = $1 AND ($4::uuid IS NULL OR id = $4) AND (deleted_at IS NULL) = $2 \
+             RETURNING {}",
+            HOSPITAL_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let hospital = sqlx::query_as::<_, Hospital>(&query)
+            .bind(id)
+            .bind(deleted)
+            .bind(ctx.user_id())
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(HospitalError::NotFound { hospital_id: id })?;
+        drop(conn);
+
+        self.invalidate_cache().await;
+        Ok(hospital)
+    }
+
+    async fn invalidate_cache(&self) {
+        if let Some(ref cache) = self.cache {
+            cache.invalidate().await;
+        }
+    }
+}
+
+/// Cache field of a filtered list page: callers scoped to a hospital see
+/// only it
+fn list_cache_field(page: &PageRequest<HospitalSortField>, filter: &HospitalFilter) -> String {
+    let page = serde_json::to_string(page).unwrap_or_default();
+    let filter = serde_json::to_string(filter).unwrap_or_default();
+    format!("list:{}:{}", page, filter)
+}
+
+async fn lock_hospital(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    id: HospitalId,
+) -> Result<Hospital, AppError> {
+    let query = format!(
+        "SELECT {} FROM hospitals WHERE id = $1 AND ($2::uuid IS NULL OR id = $2) \
+         AND deleted_at IS NULL FOR UPDATE",
+        HOSPITAL_COLUMNS
+    );
+
+    sqlx::query_as::<_, Hospital>(&query)
+        .bind(id)
+        .bind(ctx.tenant_hospital_id())
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?
+        .ok_or_else(|| HospitalError::NotFound { hospital_id: id }.into())
+}
+
+fn license_conflict(ctx: &RequestCtx, error: sqlx::Error, license_number: &str) -> AppError {
+    match &error {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => AppError::Conflict {
+            message: format!("A hospital with license {} already exists", license_number),
+        },
+        _ => db_error(ctx, error),
+    }
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use tracing::error;
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_auth::middleware::{HospitalResolver, ResourceKind};
+use lib_types::errors::AuthError;
+use lib_types::ids::HospitalId;
+
+use super::{db_error, Db, ReadPreference};
+
+/// Resolves resource ownership for hospital-scoped authorization from Postgres
+#[derive(Clone)]
+pub struct PgHospitalResolver {
+    db: Db,
+}
+
+impl PgHospitalResolver {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    fn ownership_query(kind: ResourceKind) -> Option<&'static str> {
+        match kind {
+            ResourceKind::Hospital => None,
+            ResourceKind::Patient => Some("SELECT hospital_id FROM patients WHERE id = $1"),
+            ResourceKind::Bed => Some("SELECT hospital_id FROM beds WHERE id = $1"),
+            ResourceKind::Staff => Some("SELECT hospital_id FROM medical_staff WHERE id = $1"),
+            ResourceKind::Ambulance => Some("SELECT hospital_id FROM ambulances WHERE id = $1"),
+        }
+    }
+}
+
+#[async_trait]
+impl HospitalResolver for PgHospitalResolver {
+    async fn resolve_hospital(
+        &self,
+        kind: ResourceKind,
+        id: Uuid,
+    ) -> Result<Option<HospitalId>, AuthError> {
+        let Some(query) = Self::ownership_query(kind) else {
+            return Ok(Some(id.into()));
+        };
+
+        // Ownership is looked up across hospitals, so a resource of another
+        // hospital is refused rather than reported missing
+        let ctx = RequestCtx::system();
+        let lookup = async {
+            let mut conn = self.db.acquire_for(&ctx, ReadPreference::Primary).await?;
+            sqlx::query_scalar::<_, HospitalId>(query)
+                .bind(id)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| db_error(&ctx, e))
+        };
+        lookup.await.map_err(|e| {
+            // Fail closed: an ownership lookup failure must never grant access
+            error!(
+                "Hospital ownership lookup failed for {:?} {}: {}",
+                kind, id, e
+            );
+            AuthError::InsufficientPermissions
+        })
+    }
+}
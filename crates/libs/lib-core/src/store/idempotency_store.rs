@@ -0,0 +1,279 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use redis::{AsyncCommands, ErrorKind, RedisError, Script};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::RedisPool;
+
+const KEY_PREFIX: &str = "idempotency:";
+/// How long a request holds its key while it runs. A server that dies
+/// mid-request leaves the key to be claimed again after this.
+const IN_PROGRESS_TTL: Duration = Duration::from_secs(60);
+
+/// Store the response only while the key still holds the caller's claim
+static COMPLETE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+if redis.call('GET', KEYS[1]) ~= ARGV[1] then
+    return 0
+end
+redis.call('SET', KEYS[1], ARGV[2], 'EX', ARGV[3])
+return 1
+"#,
+    )
+});
+
+/// Delete the key only while it still holds the caller's claim
+static RELEASE: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+if redis.call('GET', KEYS[1]) ~= ARGV[1] then
+    return 0
+end
+return redis.call('DEL', KEYS[1])
+"#,
+    )
+});
+
+/// A response kept for replaying to retries of its request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub location: Option<String>,
+    pub body: String,
+}
+
+/// Proof of holding a key, so a request whose claim expired cannot
+/// overwrite or free the key for the request that claimed it next
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimToken(String); // The in-progress record as stored
+
+/// What a request carrying an idempotency key should do
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyClaim {
+    /// First use of the key: run the request, then `complete` or `release`
+    Acquired(ClaimToken),
+    /// The same request is still running elsewhere
+    InProgress,
+    /// The request already ran; answer with its response
+    Completed(StoredResponse),
+    /// The key was used for a different request
+    Mismatch,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+enum Record {
+    InProgress {
+        fingerprint: String,
+        #[serde(default)]
+        claim_id: String, // Tells apart claims of the same request
+    },
+    Completed {
+        fingerprint: String,
+        response: StoredResponse,
+    },
+}
+
+/// Responses of unsafe requests keyed by the client's `Idempotency-Key`,
+/// shared by every instance through Redis. A retry with the same key and
+/// request gets the stored response instead of running the request again;
+/// responses live for the TTL.
+#[derive(Clone)]
+pub struct IdempotencyStore {
+    redis: Option<RedisPool>, // None for a store built `disabled`
+    ttl: Duration,            // Zero disables the store
+}
+
+impl IdempotencyStore {
+    pub fn new(redis: RedisPool, ttl: Duration) -> Self {
+        Self {
+            redis: Some(redis),
+            ttl,
+        }
+    }
+
+    /// A store that keeps nothing, for running without Redis (e.g. in
+    /// handler tests)
+    pub fn disabled() -> Self {
+        Self {
+            redis: None,
+            ttl: Duration::ZERO,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.redis.is_some() && !self.ttl.is_zero()
+    }
+
+    fn redis(&self) -> Result<RedisPool, RedisError> {
+        self.redis
+            .clone()
+            .ok_or_else(|| (ErrorKind::ClientError, "Idempotency store is disabled").into())
+    }
+
+    /// Identify a request by what it asks for, so a key reused for another
+    /// request can be told apart from a retry
+    pub fn fingerprint(method: &str, path: &str, body: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(method.as_bytes());
+        hasher.update(b" ");
+        hasher.update(path.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(body);
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Claim `key` for the request identified by `fingerprint`, or find out
+    /// how an earlier request with the key went
+    pub async fn claim(
+        &self,
+        key: &str,
+        fingerprint: &str,
+    ) -> Result<IdempotencyClaim, RedisError> {
+        let in_progress = serde_json::to_string(&Record::InProgress {
+            fingerprint: fingerprint.to_string(),
+            claim_id: Uuid::new_v4().to_string(),
+        })
+        .unwrap_or_default();
+
+        let mut conn = self.redis()?;
+        // The key can expire between the two commands; claim it again then
+        for _ in 0..2 {
+            let claimed: Option<String> = redis::cmd("SET")
+                .arg(redis_key(key))
+                .arg(&in_progress)
+                .arg("NX")
+                .arg("EX")
+                .arg(IN_PROGRESS_TTL.as_secs())
+                .query_async(&mut conn)
+                .await?;
+            if claimed.is_some() {
+                return Ok(IdempotencyClaim::Acquired(ClaimToken(in_progress)));
+            }
+
+            let record: Option<String> = conn.get(redis_key(key)).await?;
+            if let Some(record) = record {
+                return Ok(decide(&record, fingerprint));
+            }
+        }
+        Ok(IdempotencyClaim::InProgress)
+    }
+
+    /// Keep the response of the request holding `key` for the TTL. Returns
+    /// false, keeping nothing, if the claim expired and the key moved on.
+    pub async fn complete(
+        &self,
+        key: &str,
+        claim: &ClaimToken,
+        fingerprint: &str,
+        response: StoredResponse,
+    ) -> Result<bool, RedisError> {
+        let record = Record::Completed {
+            fingerprint: fingerprint.to_string(),
+            response,
+        };
+        let Ok(json) = serde_json::to_string(&record) else {
+            return Ok(false);
+        };
+        let stored: i64 = COMPLETE
+            .key(redis_key(key))
+            .arg(&claim.0)
+            .arg(json)
+            .arg(self.ttl.as_secs().max(1))
+            .invoke_async(&mut self.redis()?)
+            .await?;
+        Ok(stored == 1)
+    }
+
+    /// Give up `key` so the request can be retried, e.g. after it failed.
+    /// Returns false if the claim had already expired.
+    pub async fn release(&self, key: &str, claim: &ClaimToken) -> Result<bool, RedisError> {
+        let released: i64 = RELEASE
+            .key(redis_key(key))
+            .arg(&claim.0)
+            .invoke_async(&mut self.redis()?)
+            .await?;
+        Ok(released == 1)
+    }
+}
+
+fn redis_key(key: &str) -> String {
+    format!("{}{}", KEY_PREFIX, key)
+}
+
+/// How a request with `fingerprint` should proceed given the stored record
+/// of its key; an unreadable record counts as still running
+fn decide(record: &str, fingerprint: &str) -> IdempotencyClaim {
+    match serde_json::from_str::<Record>(record) {
+        Ok(Record::InProgress {
+            fingerprint: stored,
+            ..
+        }) if stored == fingerprint => IdempotencyClaim::InProgress,
+        Ok(Record::Completed {
+            fingerprint: stored,
+            response,
+        }) if stored == fingerprint => IdempotencyClaim::Completed(response),
+        Ok(_) => IdempotencyClaim::Mismatch,
+        Err(_) => IdempotencyClaim::InProgress,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide() {
+        let fingerprint = IdempotencyStore::fingerprint("POST", "/api/patients", b"{}");
+        let other = IdempotencyStore::fingerprint("POST", "/api/patients", b"{\"a\":1}");
+        assert_ne!(fingerprint, other);
+
+        let in_progress = serde_json::to_string(&Record::InProgress {
+            fingerprint: fingerprint.clone(),
+            claim_id: Uuid::new_v4().to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            decide(&in_progress, &fingerprint),
+            IdempotencyClaim::InProgress
+        );
+        assert_eq!(decide(&in_progress, &other), IdempotencyClaim::Mismatch);
+
+        let response = StoredResponse {
+            status: 201,
+            content_type: Some("application/json".to_string()),
+            location: None,
+            body: "{\"id\":1}".to_string(),
+        };
+        let completed = serde_json::to_string(&Record::Completed {
+            fingerprint: fingerprint.clone(),
+            response: response.clone(),
+        })
+        .unwrap();
+        assert_eq!(
+            decide(&completed, &fingerprint),
+            IdempotencyClaim::Completed(response)
+        );
+        assert_eq!(decide(&completed, &other), IdempotencyClaim::Mismatch);
+        assert_eq!(
+            decide("garbage", &fingerprint),
+            IdempotencyClaim::InProgress
+        );
+    }
+
+    #[test]
+    fn test_claims_written_before_claim_ids_still_decide() {
+        let fingerprint = IdempotencyStore::fingerprint("POST", "/api/patients", b"{}");
+        let record = format!(r#"{{"state":"in_progress","fingerprint":"{}"}}"#, fingerprint);
+        assert_eq!(decide(&record, &fingerprint), IdempotencyClaim::InProgress);
+    }
+}
@@ -0,0 +1,314 @@
+use sqlx::postgres::PgHasArrayType;
+use sqlx::{Encode, PgConnection, Postgres, Type};
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::EmergencyIncident;
+use lib_types::enums::{IncidentSeverity, IncidentStatus};
+use lib_types::errors::{AppError, IncidentError};
+use lib_types::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+use super::query_metrics::Observe;
+use super::{db_error, Db, ReadPreference};
+
+const INCIDENT_COLUMNS: &str = "id, incident_type, severity, status, location, latitude, \
+     longitude, description, commander_id, coordinating_hospital_id, hospital_ids, \
+     ambulance_ids, patient_ids, reported_by, closed_at, created_at, updated_at";
+
+/// Data access for emergency incidents. An incident is visible to every
+/// hospital linked to it.
+#[derive(Clone)]
+pub struct IncidentRepository {
+    db: Db,
+}
+
+impl IncidentRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Find an incident by id among those linked to the caller's hospital
+    pub async fn find_by_id(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+    ) -> Result<Option<EmergencyIncident>, AppError> {
+        let query = format!(
+            "SELECT {} FROM emergency_incidents \
+             WHERE id = $1 AND ($2::uuid IS NULL OR $2 = ANY(hospital_ids))",
+            INCIDENT_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, EmergencyIncident>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .observe(self.db.metrics(), ctx, "incidents.find_by_id")
+            .await
+    }
+
+    /// List the incidents linked to the caller's hospital, newest first,
+    /// optionally only those with the given status
+    pub async fn list(
+        &self,
+        ctx: &RequestCtx,
+        status: Option<IncidentStatus>,
+    ) -> Result<Vec<EmergencyIncident>, AppError> {
+        let query = format!(
+            "SELECT {} FROM emergency_incidents \
+             WHERE ($1::uuid IS NULL OR $1 = ANY(hospital_ids)) \
+             AND ($2::incident_status IS NULL OR status = $2) \
+             ORDER BY created_at DESC",
+            INCIDENT_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, EmergencyIncident>(&query)
+            .bind(ctx.tenant_hospital_id())
+            .bind(status)
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "incidents.list")
+            .await
+    }
+
+    /// Insert a new incident
+    pub async fn create(
+        &self,
+        ctx: &RequestCtx,
+        incident: &EmergencyIncident,
+    ) -> Result<(), AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        ensure_exist(
+            &mut tx,
+            ctx,
+            "hospitals",
+            "Hospital",
+            &incident.hospital_ids,
+        )
+        .await?;
+        if let Some(commander_id) = incident.commander_id {
+            ensure_commander(&mut tx, ctx, incident, commander_id).await?;
+        }
+
+        sqlx::query(
+            "INSERT INTO emergency_incidents (id, incident_type, severity, status, location, \
+             latitude, longitude, description, commander_id, coordinating_hospital_id, \
+             hospital_ids, ambulance_ids, patient_ids, reported_by, closed_at, created_at, \
+             updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)",
+        )
+        .bind(incident.id)
+        .bind(incident.incident_type)
+        .bind(incident.severity)
+        .bind(incident.status)
+        .bind(&incident.location)
+        .bind(incident.latitude)
+        .bind(incident.longitude)
+        .bind(&incident.description)
+        .bind(incident.commander_id)
+        .bind(incident.coordinating_hospital_id)
+        .bind(&incident.hospital_ids)
+        .bind(&incident.ambulance_ids)
+        .bind(&incident.patient_ids)
+        .bind(incident.reported_by)
+        .bind(incident.closed_at)
+        .bind(incident.created_at)
+        .bind(incident.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(())
+    }
+
+    /// Move an incident through its workflow (active, contained, closed)
+    pub async fn update_status(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        status: IncidentStatus,
+    ) -> Result<EmergencyIncident, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut incident = lock_incident(&mut tx, ctx, id).await?;
+        incident.update_status(status)?;
+        save_incident(&mut tx, ctx, &incident).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(incident)
+    }
+
+    /// Escalate or downgrade the declared severity of an open incident
+    pub async fn update_severity(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        severity: IncidentSeverity,
+    ) -> Result<EmergencyIncident, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut incident = lock_incident(&mut tx, ctx, id).await?;
+        incident.update_severity(severity)?;
+        save_incident(&mut tx, ctx, &incident).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(incident)
+    }
+
+    /// Hand command of an incident to an active user of one of its hospitals
+    pub async fn assign_commander(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        commander_id: UserId,
+    ) -> Result<EmergencyIncident, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut incident = lock_incident(&mut tx, ctx, id).await?;
+        ensure_commander(&mut tx, ctx, &incident, commander_id).await?;
+        incident.assign_commander(commander_id)?;
+        save_incident(&mut tx, ctx, &incident).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(incident)
+    }
+
+    /// Link patients, ambulances and receiving hospitals to an open incident.
+    /// Patients and ambulances must belong to the caller's hospital.
+    pub async fn link(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        patient_ids: &[PatientId],
+        ambulance_ids: &[AmbulanceId],
+        hospital_ids: &[HospitalId],
+    ) -> Result<EmergencyIncident, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut incident = lock_incident(&mut tx, ctx, id).await?;
+        ensure_exist(&mut tx, ctx, "patients", "Patient", patient_ids).await?;
+        ensure_exist(&mut tx, ctx, "ambulances", "Ambulance", ambulance_ids).await?;
+        ensure_exist(&mut tx, ctx, "hospitals", "Hospital", hospital_ids).await?;
+
+        incident.link(patient_ids, ambulance_ids, hospital_ids)?;
+        save_incident(&mut tx, ctx, &incident).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(incident)
+    }
+}
+
+/// Load an incident linked to the caller's hospital and lock it for the transaction
+pub(super) async fn lock_incident(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    id: Uuid,
+) -> Result<EmergencyIncident, AppError> {
+    let query = format!(
+        "SELECT {} FROM emergency_incidents \
+         WHERE id = $1 AND ($2::uuid IS NULL OR $2 = ANY(hospital_ids)) FOR UPDATE",
+        INCIDENT_COLUMNS
+    );
+
+    sqlx::query_as::<_, EmergencyIncident>(&query)
+        .bind(id)
+        .bind(ctx.tenant_hospital_id())
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?
+        .ok_or_else(|| IncidentError::NotFound { incident_id: id }.into())
+}
+
+pub(super) async fn save_incident(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    incident: &EmergencyIncident,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE emergency_incidents SET severity = $2, status = $3, latitude = $4, \
+         longitude = $5, commander_id = $6, hospital_ids = $7, ambulance_ids = $8, \
+         patient_ids = $9, closed_at = $10, updated_at = $11 WHERE id = $1",
+    )
+    .bind(incident.id)
+    .bind(incident.severity)
+    .bind(incident.status)
+    .bind(incident.latitude)
+    .bind(incident.longitude)
+    .bind(incident.commander_id)
+    .bind(&incident.hospital_ids)
+    .bind(&incident.ambulance_ids)
+    .bind(&incident.patient_ids)
+    .bind(incident.closed_at)
+    .bind(incident.updated_at)
+    .execute(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+
+    Ok(())
+}
+
+/// Check that every id exists in `table` (a fixed table name), reporting a
+/// missing `resource` otherwise. Tables with a `hospital_id` are restricted
+/// to the caller's hospital.
+async fn ensure_exist<T>(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    table: &'static str,
+    resource: &'static str,
+    ids: &[T],
+) -> Result<(), AppError>
+where
+    T: Copy + Ord + Send + Sync + for<'q> Encode<'q, Postgres> + Type<Postgres> + PgHasArrayType,
+{
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let tenant_filter = match table {
+        "hospitals" => "",
+        _ => "AND ($2::uuid IS NULL OR hospital_id = $2)",
+    };
+    let query = format!(
+        "SELECT COUNT(*) FROM {} WHERE id = ANY($1) {}",
+        table, tenant_filter
+    );
+    let mut query = sqlx::query_scalar::<_, i64>(&query).bind(ids);
+    if !tenant_filter.is_empty() {
+        query = query.bind(ctx.tenant_hospital_id());
+    }
+    let found = query.fetch_one(conn).await.map_err(|e| db_error(ctx, e))?;
+
+    let mut unique = ids.to_vec();
+    unique.sort_unstable();
+    unique.dedup();
+    if found as usize != unique.len() {
+        return Err(AppError::not_found(resource));
+    }
+    Ok(())
+}
+
+/// Check that the commander is an active user of one of the incident's hospitals
+async fn ensure_commander(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    incident: &EmergencyIncident,
+    user_id: UserId,
+) -> Result<(), AppError> {
+    let eligible: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM users \
+         WHERE id = $1 AND is_active AND hospital_id = ANY($2))",
+    )
+    .bind(user_id)
+    .bind(&incident.hospital_ids)
+    .fetch_one(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+
+    if !eligible {
+        return Err(IncidentError::InvalidCommander { user_id }.into());
+    }
+    Ok(())
+}
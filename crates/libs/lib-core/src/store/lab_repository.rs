@@ -0,0 +1,294 @@
+use chrono::Utc;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{CreateLabOrderRequest, IngestLabResultsRequest};
+use lib_types::entities::{LabOrder, LabResult, Patient};
+use lib_types::enums::LabOrderStatus;
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{PatientId, UserId};
+
+use super::query_metrics::Observe;
+use super::{db_error, Db, ReadPreference, PATIENT_COLUMNS};
+use crate::model::classify_result;
+
+const LAB_ORDER_COLUMNS: &str =
+    "id, patient_id, hospital_id, panel, status, notes, ordered_by, ordered_at, resulted_at";
+
+const LAB_RESULT_COLUMNS: &str = "id, order_id, patient_id, hospital_id, analyte, value, units, \
+     reference_low, reference_high, flag, resulted_at, acknowledged_by, acknowledged_at";
+
+/// Data access for lab orders and the results reported against them
+#[derive(Clone)]
+pub struct LabRepository {
+    db: Db,
+}
+
+impl LabRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// List a patient's lab orders, newest first
+    pub async fn list_orders(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<LabOrder>, AppError> {
+        let query = format!(
+            "SELECT {} FROM lab_orders \
+             WHERE patient_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             ORDER BY ordered_at DESC",
+            LAB_ORDER_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, LabOrder>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "lab.list_orders")
+            .await
+    }
+
+    /// List a patient's lab results, newest first
+    pub async fn list_results(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<LabResult>, AppError> {
+        let query = format!(
+            "SELECT {} FROM lab_results \
+             WHERE patient_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             ORDER BY resulted_at DESC, analyte",
+            LAB_RESULT_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, LabResult>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "lab.list_results")
+            .await
+    }
+
+    pub async fn create_order(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        request: &CreateLabOrderRequest,
+        ordered_by: UserId,
+    ) -> Result<LabOrder, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR SHARE",
+            PATIENT_COLUMNS
+        );
+        let patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        let mut order = LabOrder::new(
+            patient.id,
+            patient.hospital_id,
+            request.panel.trim().to_string(),
+            ordered_by,
+        );
+        order.notes = request.notes.clone();
+
+        sqlx::query(
+            "INSERT INTO lab_orders (id, patient_id, hospital_id, panel, status, notes, \
+             ordered_by, ordered_at, resulted_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(order.id)
+        .bind(order.patient_id)
+        .bind(order.hospital_id)
+        .bind(&order.panel)
+        .bind(order.status)
+        .bind(&order.notes)
+        .bind(order.ordered_by)
+        .bind(order.ordered_at)
+        .bind(order.resulted_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(order)
+    }
+
+    /// Store the results for an order, flagging critical values, and mark
+    /// the order resulted. Results can arrive in several batches; the order
+    /// keeps the time of the first.
+    pub async fn record_results(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        request: &IngestLabResultsRequest,
+    ) -> Result<(LabOrder, Vec<LabResult>), AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut order = self
+            .lock_order(&mut tx, ctx, patient_id, request.order_id)
+            .await?;
+        if !order.status.accepts_results() {
+            return Err(AppError::Conflict {
+                message: format!("Lab order {} is {}", order.id, order.status),
+            });
+        }
+
+        let resulted_at = request.resulted_at.unwrap_or_else(Utc::now);
+        let mut results = Vec::with_capacity(request.results.len());
+        for input in &request.results {
+            let result = input.to_result(&order, classify_result(input), resulted_at);
+
+            sqlx::query(
+                "INSERT INTO lab_results (id, order_id, patient_id, hospital_id, analyte, value, \
+                 units, reference_low, reference_high, flag, resulted_at, acknowledged_by, \
+                 acknowledged_at) \
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+            )
+            .bind(result.id)
+            .bind(result.order_id)
+            .bind(result.patient_id)
+            .bind(result.hospital_id)
+            .bind(&result.analyte)
+            .bind(result.value)
+            .bind(&result.units)
+            .bind(result.reference_low)
+            .bind(result.reference_high)
+            .bind(result.flag)
+            .bind(result.resulted_at)
+            .bind(result.acknowledged_by)
+            .bind(result.acknowledged_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+            results.push(result);
+        }
+
+        order.status = LabOrderStatus::Resulted;
+        order.resulted_at = Some(
+            order
+                .resulted_at
+                .map_or(resulted_at, |t| t.min(resulted_at)),
+        );
+        sqlx::query("UPDATE lab_orders SET status = $2, resulted_at = $3 WHERE id = $1")
+            .bind(order.id)
+            .bind(order.status)
+            .bind(order.resulted_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok((order, results))
+    }
+
+    /// Cancel an order that has no results yet
+    pub async fn cancel_order(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        order_id: Uuid,
+    ) -> Result<LabOrder, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut order = self.lock_order(&mut tx, ctx, patient_id, order_id).await?;
+        if order.status != LabOrderStatus::Ordered {
+            return Err(AppError::Conflict {
+                message: format!("Lab order {} is {}", order.id, order.status),
+            });
+        }
+
+        order.status = LabOrderStatus::Cancelled;
+        sqlx::query("UPDATE lab_orders SET status = $2 WHERE id = $1")
+            .bind(order.id)
+            .bind(order.status)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(order)
+    }
+
+    /// Record that a clinician was told of a result. Acknowledging twice
+    /// keeps the first acknowledgement.
+    pub async fn acknowledge(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        result_id: Uuid,
+        by: UserId,
+    ) -> Result<LabResult, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM lab_results \
+             WHERE id = $1 AND patient_id = $2 AND ($3::uuid IS NULL OR hospital_id = $3) \
+             FOR UPDATE",
+            LAB_RESULT_COLUMNS
+        );
+        let mut result = sqlx::query_as::<_, LabResult>(&query)
+            .bind(result_id)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or_else(|| AppError::NotFound {
+                resource: "Lab result".to_string(),
+            })?;
+
+        if result.acknowledge(by) {
+            sqlx::query(
+                "UPDATE lab_results SET acknowledged_by = $2, acknowledged_at = $3 WHERE id = $1",
+            )
+            .bind(result.id)
+            .bind(result.acknowledged_by)
+            .bind(result.acknowledged_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        }
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(result)
+    }
+
+    async fn lock_order(
+        &self,
+        conn: &mut PgConnection,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        order_id: Uuid,
+    ) -> Result<LabOrder, AppError> {
+        let query = format!(
+            "SELECT {} FROM lab_orders \
+             WHERE id = $1 AND patient_id = $2 AND ($3::uuid IS NULL OR hospital_id = $3) \
+             FOR UPDATE",
+            LAB_ORDER_COLUMNS
+        );
+
+        sqlx::query_as::<_, LabOrder>(&query)
+            .bind(order_id)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(conn)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or_else(|| AppError::NotFound {
+                resource: "Lab order".to_string(),
+            })
+    }
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use sqlx::postgres::PgListener;
+use sqlx::types::Json;
+use sqlx::PgConnection;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::LiveEvent;
+use lib_types::errors::AppError;
+
+use super::{db_error, Db};
+
+/// Events a subscriber may fall behind by before it starts missing them
+const BUS_CAPACITY: usize = 1024;
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// In-process fan-out of live events to dashboards. Events reach the bus
+/// only through Postgres notifications (see `spawn_listener`), so changes
+/// made by every server instance arrive the same way.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<LiveEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BUS_CAPACITY);
+        Self { sender }
+    }
+
+    /// Receive events published from now on. A receiver that falls more
+    /// than the bus capacity behind skips the oldest events.
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveEvent> {
+        self.sender.subscribe()
+    }
+
+    fn publish(&self, event: LiveEvent) {
+        // Nobody listening is not an error
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Announce `event` to every server instance. Inside a transaction the
+/// notification is only sent when it commits.
+pub(crate) async fn notify(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    event: &LiveEvent,
+) -> Result<(), AppError> {
+    sqlx::query("SELECT pg_notify($1, $2::text)")
+        .bind(event.channel())
+        .bind(Json(event))
+        .execute(conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+    Ok(())
+}
+
+/// Listen for live event notifications and forward them to `bus`. The
+/// listener reconnects after losing its connection; notifications sent
+/// while it was away are missed, as dashboards reload their state anyway
+/// when they reconnect.
+pub fn spawn_listener(db: &Db, bus: EventBus) -> JoinHandle<()> {
+    let pool = db.primary().clone();
+    tokio::spawn(async move {
+        loop {
+            let mut listener = match PgListener::connect_with(&pool).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    warn!("Live event listener could not connect: {}", e);
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                    continue;
+                }
+            };
+            if let Err(e) = listener.listen_all(LiveEvent::CHANNELS).await {
+                warn!("Live event listener could not subscribe: {}", e);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+            info!("Listening for live events on {:?}", LiveEvent::CHANNELS);
+
+            loop {
+                match listener.recv().await {
+                    Ok(notification) => match serde_json::from_str(notification.payload()) {
+                        Ok(event) => bus.publish(event),
+                        Err(e) => warn!(
+                            "Ignoring malformed notification on {}: {}",
+                            notification.channel(),
+                            e
+                        ),
+                    },
+                    Err(e) => {
+                        warn!("Live event listener lost its connection: {}", e);
+                        break;
+                    }
+                }
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    })
+}
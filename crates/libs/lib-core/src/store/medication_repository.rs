@@ -0,0 +1,107 @@
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::PrescribeMedicationRequest;
+use lib_types::entities::{Medication, Patient};
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{PatientId, UserId};
+
+use super::query_metrics::Observe;
+use super::{db_error, Db, ReadPreference, PATIENT_COLUMNS};
+use crate::model::check_allergies;
+
+const MEDICATION_COLUMNS: &str = "id, patient_id, hospital_id, name, dosage, route, frequency, \
+     notes, prescribed_by, created_at";
+
+/// Data access for medications prescribed to patients
+#[derive(Clone)]
+pub struct MedicationRepository {
+    db: Db,
+}
+
+impl MedicationRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// List the medications prescribed to a patient, newest first
+    pub async fn list_for_patient(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<Medication>, AppError> {
+        let query = format!(
+            "SELECT {} FROM medications \
+             WHERE patient_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             ORDER BY created_at DESC",
+            MEDICATION_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Medication>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "medications.list_for_patient")
+            .await
+    }
+
+    /// Prescribe a medication after checking it against the patient's
+    /// allergies. The patient row is locked so an allergy recorded
+    /// concurrently cannot slip past the check.
+    pub async fn prescribe(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        request: &PrescribeMedicationRequest,
+        prescribed_by: UserId,
+    ) -> Result<Medication, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR SHARE",
+            PATIENT_COLUMNS
+        );
+        let patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        check_allergies(&patient, &request.name)?;
+
+        let mut medication = Medication::new(
+            patient.id,
+            patient.hospital_id,
+            request.name.trim().to_string(),
+            request.dosage.trim().to_string(),
+            request.route,
+            request.frequency.trim().to_string(),
+            prescribed_by,
+        );
+        medication.notes = request.notes.clone();
+
+        sqlx::query(
+            "INSERT INTO medications (id, patient_id, hospital_id, name, dosage, route, \
+             frequency, notes, prescribed_by, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        )
+        .bind(medication.id)
+        .bind(medication.patient_id)
+        .bind(medication.hospital_id)
+        .bind(&medication.name)
+        .bind(&medication.dosage)
+        .bind(medication.route)
+        .bind(&medication.frequency)
+        .bind(&medication.notes)
+        .bind(medication.prescribed_by)
+        .bind(medication.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(medication)
+    }
+}
@@ -0,0 +1,674 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{
+    Cursor, CursorPage, LookupPatientRequest, PatientListQuery, PatientSearchHit,
+    PatientSearchRequest, PatientSortField, PatientSummary, RetriageRequest, SortDirection,
+    UpdatePatientRequest,
+};
+use lib_types::entities::{Patient, TriageAssessment};
+use lib_types::enums::{PatientStatus, TriageLevel};
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+use super::patient_number::format_patient_number;
+use super::{check_version, PatientRepository};
+
+/// Keeps patients in memory, for tests that should not need Postgres.
+/// Follows the rules of [`PgPatientRepository`](super::PgPatientRepository)
+/// for tenancy, versions, identifiers and status changes, but publishes no
+/// events, and a free-text query matches names, numbers and complaints by
+/// substring rather than by rank. Generated patient numbers use the first
+/// eight hex digits of the hospital id as the hospital's code.
+#[derive(Default)]
+pub struct MemoryPatientRepository {
+    patients: RwLock<HashMap<PatientId, Patient>>,
+    triage: RwLock<HashMap<PatientId, Vec<TriageAssessment>>>,
+}
+
+impl MemoryPatientRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> Result<RwLockReadGuard<'_, HashMap<PatientId, Patient>>, AppError> {
+        self.patients.read().map_err(|_| AppError::Internal)
+    }
+
+    fn write(&self) -> Result<RwLockWriteGuard<'_, HashMap<PatientId, Patient>>, AppError> {
+        self.patients.write().map_err(|_| AppError::Internal)
+    }
+
+    fn record_assessment(&self, assessment: TriageAssessment) -> Result<(), AppError> {
+        self.triage
+            .write()
+            .map_err(|_| AppError::Internal)?
+            .entry(assessment.patient_id)
+            .or_default()
+            .push(assessment);
+        Ok(())
+    }
+
+    /// Change a patient visible to the caller, returning the result of `change`
+    fn modify<T>(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        change: impl FnOnce(&mut Patient) -> Result<T, AppError>,
+    ) -> Result<T, AppError> {
+        let mut patients = self.write()?;
+        let patient = patients
+            .get_mut(&id)
+            .filter(|patient| is_visible(ctx, patient))
+            .ok_or(PatientError::NotFound { patient_id: id })?;
+        change(patient)
+    }
+}
+
+#[async_trait]
+impl PatientRepository for MemoryPatientRepository {
+    async fn create(&self, ctx: &RequestCtx, patient: &Patient) -> Result<Patient, AppError> {
+        if ctx
+            .tenant_hospital_id()
+            .is_some_and(|hospital_id| hospital_id != patient.hospital_id)
+        {
+            return Err(PatientError::HospitalMismatch {
+                hospital_id: patient.hospital_id,
+            }
+            .into());
+        }
+
+        let mut patients = self.write()?;
+        let mut created = patient.clone();
+        if created.patient_number.trim().is_empty() {
+            created.patient_number = next_patient_number(&patients, created.hospital_id);
+        }
+        if patients.contains_key(&created.id)
+            || patients
+                .values()
+                .any(|other| other.patient_number == created.patient_number)
+        {
+            return Err(AppError::Conflict {
+                message: "Record already exists".to_string(),
+            });
+        }
+        ensure_identifiers_unique(&patients, &created)?;
+
+        created.created_by = ctx.user_id();
+        created.updated_by = ctx.user_id();
+        patients.insert(created.id, created.clone());
+        if let Some(user_id) = ctx.user_id() {
+            let rationale = created.chief_complaint.clone();
+            self.record_assessment(TriageAssessment::initial(&created, rationale, user_id))?;
+        }
+        Ok(created)
+    }
+
+    async fn find_by_id(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+    ) -> Result<Option<Patient>, AppError> {
+        Ok(self
+            .read()?
+            .get(&id)
+            .filter(|patient| is_visible(ctx, patient))
+            .cloned())
+    }
+
+    async fn find_by_number(
+        &self,
+        ctx: &RequestCtx,
+        patient_number: &str,
+    ) -> Result<Option<Patient>, AppError> {
+        Ok(self
+            .read()?
+            .values()
+            .find(|patient| {
+                patient.patient_number == patient_number.trim() && is_visible(ctx, patient)
+            })
+            .cloned())
+    }
+
+    async fn find_by_identifier(
+        &self,
+        ctx: &RequestCtx,
+        request: &LookupPatientRequest,
+    ) -> Result<Option<Patient>, AppError> {
+        let identifier = request.identifier();
+        let patients = self.read()?;
+        let mut matches = patients.values().filter(|patient| {
+            is_visible(ctx, patient)
+                && request
+                    .hospital_id
+                    .is_none_or(|hospital_id| patient.hospital_id == hospital_id)
+                && patient.identifiers.contains(&identifier)
+        });
+
+        let found = matches.next().cloned();
+        if matches.next().is_some() {
+            return Err(PatientError::InvalidData {
+                field: "hospital_id".to_string(),
+                reason: format!("{} matches patients at several hospitals", request.scheme),
+            }
+            .into());
+        }
+        Ok(found)
+    }
+
+    async fn search(
+        &self,
+        ctx: &RequestCtx,
+        request: &PatientSearchRequest,
+    ) -> Result<CursorPage<PatientSummary>, AppError> {
+        let page = &request.page;
+        let cursor = page
+            .decode_cursor()
+            .map_err(|message| AppError::validation_error("cursor", message))?;
+        let query = request.query_text().map(str::to_lowercase);
+
+        let mut hits: Vec<PatientSearchHit> = self
+            .read()?
+            .values()
+            .filter(|patient| is_visible(ctx, patient) && matches_filters(patient, request))
+            .filter_map(|patient| {
+                let distance = match &query {
+                    Some(query) => search_distance(patient, query)?,
+                    None => 0.0,
+                };
+                Some(PatientSearchHit {
+                    patient: patient.clone(),
+                    search_distance: distance,
+                })
+            })
+            .collect();
+
+        let order = |a: &PatientSearchHit, b: &PatientSearchHit| {
+            let ordering = compare_keys(
+                &sort_key(page.sort, a),
+                a.patient.id.as_uuid(),
+                &sort_key(page.sort, b),
+                b.patient.id.as_uuid(),
+            );
+            match page.direction {
+                SortDirection::Asc => ordering,
+                SortDirection::Desc => ordering.reverse(),
+            }
+        };
+        hits.sort_by(order);
+
+        if let Some(cursor) = cursor {
+            let after = cursor_key(&cursor)
+                .ok_or_else(|| AppError::validation_error("cursor", "Invalid cursor"))?;
+            hits.retain(|hit| {
+                let ordering = compare_keys(
+                    &sort_key(page.sort, hit),
+                    hit.patient.id.as_uuid(),
+                    &after,
+                    cursor.id,
+                );
+                match page.direction {
+                    SortDirection::Asc => ordering == Ordering::Greater,
+                    SortDirection::Desc => ordering == Ordering::Less,
+                }
+            });
+        }
+        hits.truncate(page.fetch_limit() as usize);
+
+        Ok(CursorPage::from_rows(hits, page, |hit| {
+            PatientSummary::from_patient(&hit.patient)
+        }))
+    }
+
+    async fn list(
+        &self,
+        ctx: &RequestCtx,
+        query: &PatientListQuery,
+    ) -> Result<CursorPage<PatientSummary>, AppError> {
+        let request = PatientSearchRequest {
+            page: query.page(),
+            ..Default::default()
+        };
+        self.search(ctx, &request).await
+    }
+
+    async fn update(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        update: &UpdatePatientRequest,
+    ) -> Result<(Patient, Vec<&'static str>), AppError> {
+        let mut patients = self.write()?;
+        let mut patient = patients
+            .get(&id)
+            .filter(|patient| is_visible(ctx, patient))
+            .cloned()
+            .ok_or(PatientError::NotFound { patient_id: id })?;
+
+        check_version("Patient", update.version, patient.version)?;
+        let changed = update.apply_to(&mut patient);
+        if changed.is_empty() {
+            return Ok((patient, changed));
+        }
+        if changed.contains(&"identifiers") {
+            ensure_identifiers_unique(&patients, &patient)?;
+        }
+
+        touch(ctx, &mut patient);
+        patients.insert(id, patient.clone());
+        Ok((patient, changed))
+    }
+
+    async fn record_dnr(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        dnr: bool,
+        recorded_by: UserId,
+    ) -> Result<(Patient, bool), AppError> {
+        self.modify(ctx, id, |patient| {
+            if !patient.record_dnr(dnr, recorded_by) {
+                return Ok((patient.clone(), false));
+            }
+            touch(ctx, patient);
+            Ok((patient.clone(), true))
+        })
+    }
+
+    async fn retriage(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        request: &RetriageRequest,
+        assessed_by: UserId,
+    ) -> Result<(Patient, TriageAssessment), AppError> {
+        let (patient, assessment) = self.modify(ctx, id, |patient| {
+            let assessment = patient.retriage(
+                request.triage_level,
+                request.rationale.trim().to_string(),
+                assessed_by,
+            )?;
+            touch(ctx, patient);
+            Ok((patient.clone(), assessment))
+        })?;
+        self.record_assessment(assessment.clone())?;
+        Ok((patient, assessment))
+    }
+
+    async fn triage_history(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+    ) -> Result<Vec<TriageAssessment>, AppError> {
+        if self.find_by_id(ctx, id).await?.is_none() {
+            return Ok(Vec::new());
+        }
+        let triage = self.triage.read().map_err(|_| AppError::Internal)?;
+        Ok(triage.get(&id).cloned().unwrap_or_default())
+    }
+
+    async fn transition_status(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        expected: PatientStatus,
+        next: PatientStatus,
+    ) -> Result<Patient, AppError> {
+        if !expected.can_transition_to(next) {
+            return Err(PatientError::InvalidStatusTransition {
+                current: expected,
+                requested: next,
+            }
+            .into());
+        }
+
+        self.modify(ctx, id, |patient| {
+            if patient.status != expected {
+                return Err(AppError::Conflict {
+                    message: format!(
+                        "Patient status is now {}, not {}; reload and try again",
+                        patient.status, expected
+                    ),
+                });
+            }
+            patient.status = next;
+            patient.updated_at = Utc::now();
+            touch(ctx, patient);
+            Ok(patient.clone())
+        })
+    }
+
+    async fn assign_staff(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        staff_id: Option<Uuid>,
+    ) -> Result<Patient, AppError> {
+        self.modify(ctx, id, |patient| {
+            patient.assigned_staff_id = staff_id;
+            patient.updated_at = Utc::now();
+            touch(ctx, patient);
+            Ok(patient.clone())
+        })
+    }
+
+    async fn assign_ambulance(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        ambulance_id: Option<AmbulanceId>,
+    ) -> Result<Patient, AppError> {
+        self.modify(ctx, id, |patient| {
+            patient.ambulance_id = ambulance_id;
+            patient.updated_at = Utc::now();
+            touch(ctx, patient);
+            Ok(patient.clone())
+        })
+    }
+}
+
+fn is_visible(ctx: &RequestCtx, patient: &Patient) -> bool {
+    ctx.tenant_hospital_id()
+        .is_none_or(|hospital_id| patient.hospital_id == hospital_id)
+}
+
+/// Record a stored change, as the UPDATE statements do. `updated_at` is
+/// set by whoever made the change.
+fn touch(ctx: &RequestCtx, patient: &mut Patient) {
+    patient.updated_by = ctx.user_id();
+    patient.version += 1;
+}
+
+/// The day's next number at the hospital, as `patient_number` draws it
+fn next_patient_number(patients: &HashMap<PatientId, Patient>, hospital_id: HospitalId) -> String {
+    let day = Utc::now().date_naive();
+    let code = hospital_id.to_string()[..8].to_uppercase();
+    let prefix = format!("{}-{}-", code, day.format("%Y%m%d"));
+    let drawn = patients
+        .values()
+        .filter(|patient| patient.patient_number.starts_with(&prefix))
+        .count();
+    format_patient_number(&code, day, drawn as i32 + 1)
+}
+
+/// MRNs and aliases only clash within the same hospital
+fn ensure_identifiers_unique(
+    patients: &HashMap<PatientId, Patient>,
+    patient: &Patient,
+) -> Result<(), AppError> {
+    for identifier in patient.identifiers.iter() {
+        let taken = patients.values().any(|other| {
+            other.id != patient.id
+                && (!identifier.scheme.is_hospital_scoped()
+                    || other.hospital_id == patient.hospital_id)
+                && other.identifiers.contains(identifier)
+        });
+        if taken {
+            return Err(PatientError::DuplicateIdentifier {
+                scheme: identifier.scheme,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn matches_filters(patient: &Patient, request: &PatientSearchRequest) -> bool {
+    let name_prefix = request
+        .name
+        .as_deref()
+        .map(|name| name.trim().to_lowercase());
+    let full_name = format!("{} {}", patient.first_name, patient.last_name).to_lowercase();
+
+    request
+        .hospital_id
+        .is_none_or(|hospital_id| patient.hospital_id == hospital_id)
+        && name_prefix.is_none_or(|prefix| {
+            patient.first_name.to_lowercase().starts_with(&prefix)
+                || patient.last_name.to_lowercase().starts_with(&prefix)
+                || full_name.starts_with(&prefix)
+        })
+        && request
+            .patient_number
+            .as_deref()
+            .is_none_or(|number| patient.patient_number == number.trim())
+        && request.national_id_digits().is_none_or(|digits| {
+            patient
+                .national_id
+                .as_deref()
+                .is_some_and(|id| id.replace('-', "") == digits)
+        })
+        && (request.triage_levels.is_empty()
+            || request.triage_levels.contains(&patient.triage_level))
+        && (request.statuses.is_empty() || request.statuses.contains(&patient.status))
+        && request
+            .created_from
+            .is_none_or(|from| patient.created_at >= from)
+        && request.created_to.is_none_or(|to| patient.created_at <= to)
+        && request
+            .assigned_staff_id
+            .is_none_or(|staff_id| patient.assigned_staff_id == Some(staff_id))
+}
+
+/// How far a patient is from a lowercase query: -1 when a name, the number
+/// or the complaint contains it, `None` when nothing does
+fn search_distance(patient: &Patient, query: &str) -> Option<f32> {
+    [
+        &patient.first_name,
+        &patient.last_name,
+        &patient.patient_number,
+        &patient.chief_complaint,
+    ]
+    .iter()
+    .any(|text| text.to_lowercase().contains(query))
+    .then_some(-1.0)
+}
+
+/// A sort column value, compared the way Postgres compares the column
+#[derive(Debug, PartialEq, PartialOrd)]
+enum SortKey {
+    Triage(TriageLevel),
+    Time(DateTime<Utc>),
+    Text(String),
+    Distance(f32),
+}
+
+fn sort_key(sort: PatientSortField, hit: &PatientSearchHit) -> SortKey {
+    let patient = &hit.patient;
+    match sort {
+        PatientSortField::TriageLevel => SortKey::Triage(patient.triage_level),
+        PatientSortField::CreatedAt => SortKey::Time(patient.created_at),
+        PatientSortField::LastName => SortKey::Text(patient.last_name.clone()),
+        PatientSortField::PatientNumber => SortKey::Text(patient.patient_number.clone()),
+        PatientSortField::Relevance => SortKey::Distance(hit.search_distance),
+    }
+}
+
+/// The key a cursor points after, parsed from its text form
+fn cursor_key(cursor: &Cursor<PatientSortField>) -> Option<SortKey> {
+    let value = &cursor.value;
+    Some(match cursor.sort {
+        PatientSortField::TriageLevel => {
+            SortKey::Triage(serde_json::from_value(serde_json::Value::String(value.clone())).ok()?)
+        }
+        PatientSortField::CreatedAt => SortKey::Time(
+            DateTime::parse_from_rfc3339(value)
+                .ok()?
+                .with_timezone(&Utc),
+        ),
+        PatientSortField::LastName | PatientSortField::PatientNumber => {
+            SortKey::Text(value.clone())
+        }
+        PatientSortField::Relevance => SortKey::Distance(value.parse().ok()?),
+    })
+}
+
+/// Order by key, then by id as the tie-breaker
+fn compare_keys(a: &SortKey, a_id: Uuid, b: &SortKey, b_id: Uuid) -> Ordering {
+    a.partial_cmp(b)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a_id.cmp(&b_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lib_auth::ctx::Ctx;
+    use lib_types::dtos::PageRequest;
+    use lib_types::enums::{Gender, UserRole};
+
+    fn staff_ctx(hospital_id: HospitalId) -> RequestCtx {
+        RequestCtx::system().with_user(Ctx::new(
+            UserId::new(),
+            UserRole::Nurse,
+            hospital_id,
+            Uuid::new_v4(),
+        ))
+    }
+
+    fn patient(number: &str, last_name: &str, hospital_id: HospitalId) -> Patient {
+        Patient::new(
+            number.to_string(),
+            None,
+            "Test".to_string(),
+            last_name.to_string(),
+            40,
+            Gender::Female,
+            "Chest pain".to_string(),
+            TriageLevel::High,
+            hospital_id,
+            None,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_confines_callers_to_their_hospital() {
+        let (own, other) = (HospitalId::new(), HospitalId::new());
+        let repository = MemoryPatientRepository::new();
+        let ctx = staff_ctx(own);
+
+        let mine = repository
+            .create(&ctx, &patient("P-1", "Own", own))
+            .await
+            .unwrap();
+        let theirs = repository
+            .create(&RequestCtx::system(), &patient("P-2", "Other", other))
+            .await
+            .unwrap();
+        assert!(repository
+            .create(&ctx, &patient("P-3", "Other", other))
+            .await
+            .is_err());
+
+        assert!(repository
+            .find_by_id(&ctx, mine.id)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(repository
+            .find_by_id(&ctx, theirs.id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(repository
+            .assign_staff(&ctx, theirs.id, Some(Uuid::new_v4()))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generates_missing_patient_numbers() {
+        let hospital_id = HospitalId::new();
+        let repository = MemoryPatientRepository::new();
+        let ctx = staff_ctx(hospital_id);
+
+        let first = repository
+            .create(&ctx, &patient("", "Smith", hospital_id))
+            .await
+            .unwrap();
+        let second = repository
+            .create(&ctx, &patient(" ", "Jones", hospital_id))
+            .await
+            .unwrap();
+        assert!(first.patient_number.ends_with("-0001"));
+        assert!(second.patient_number.ends_with("-0002"));
+        assert_eq!(first.patient_number[..18], second.patient_number[..18]);
+    }
+
+    #[tokio::test]
+    async fn test_status_changes_check_the_expected_status() {
+        let hospital_id = HospitalId::new();
+        let repository = MemoryPatientRepository::new();
+        let ctx = staff_ctx(hospital_id);
+        let created = repository
+            .create(&ctx, &patient("P-1", "Smith", hospital_id))
+            .await
+            .unwrap();
+
+        let moved = repository
+            .transition_status(&ctx, created.id, created.status, PatientStatus::EnRoute)
+            .await
+            .unwrap();
+        assert_eq!(moved.status, PatientStatus::EnRoute);
+        assert_eq!(moved.version, created.version + 1);
+
+        let stale = repository
+            .transition_status(&ctx, created.id, created.status, PatientStatus::EnRoute)
+            .await;
+        assert!(matches!(stale, Err(AppError::Conflict { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_search_pages_in_sort_order() {
+        let hospital_id = HospitalId::new();
+        let repository = MemoryPatientRepository::new();
+        let ctx = staff_ctx(hospital_id);
+        for (number, name) in [("P-1", "Carter"), ("P-2", "Adams"), ("P-3", "Baker")] {
+            repository
+                .create(&ctx, &patient(number, name, hospital_id))
+                .await
+                .unwrap();
+        }
+
+        let mut request = PatientSearchRequest {
+            page: PageRequest {
+                limit: 2,
+                sort: PatientSortField::LastName,
+                direction: SortDirection::Asc,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let first = repository.search(&ctx, &request).await.unwrap();
+        let names: Vec<_> = first
+            .items
+            .iter()
+            .map(|p| p.patient_number.clone())
+            .collect();
+        assert_eq!(names, ["P-2", "P-3"]);
+        assert!(first.has_more);
+
+        request.page.cursor = first.next_cursor;
+        let second = repository.search(&ctx, &request).await.unwrap();
+        let names: Vec<_> = second
+            .items
+            .iter()
+            .map(|p| p.patient_number.clone())
+            .collect();
+        assert_eq!(names, ["P-1"]);
+        assert!(!second.has_more);
+
+        request.page.cursor = None;
+        request.query = Some("bak".to_string());
+        let found = repository.search(&ctx, &request).await.unwrap();
+        assert_eq!(found.items.len(), 1);
+        assert_eq!(found.items[0].patient_number, "P-3");
+    }
+}
@@ -0,0 +1,150 @@
+//! Schema migrations embedded in the binary, so a deployment carries the
+//! schema it expects instead of relying on scripts run out of band.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::PgConnection;
+use tracing::info;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{MigrationInfo, MigrationStatusResponse};
+use lib_types::errors::AppError;
+
+use super::{db_error, Db};
+
+/// The migrations in `lib-core/migrations`
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// A row of sqlx's `_sqlx_migrations` table
+type Recorded = (i64, String, DateTime<Utc>, bool, Vec<u8>);
+
+/// Apply any pending migrations to the primary, returning how many ran
+pub async fn run_migrations(db: &Db) -> Result<usize, MigrateError> {
+    let mut conn = db.primary().acquire().await?;
+    let pending = migration_status(&mut conn).await?.pending.len();
+    drop(conn);
+
+    if pending > 0 {
+        info!("Applying {} database migration(s)", pending);
+        MIGRATOR.run(db.primary()).await?;
+    }
+    Ok(pending)
+}
+
+/// Compare the embedded migrations with those recorded in the database. A
+/// database never migrated has every migration pending.
+pub async fn migration_status(
+    conn: &mut PgConnection,
+) -> Result<MigrationStatusResponse, sqlx::Error> {
+    let migrated: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+    let recorded = if migrated {
+        sqlx::query_as::<_, Recorded>(
+            "SELECT version, description, installed_on, success, checksum \
+             FROM _sqlx_migrations ORDER BY version",
+        )
+        .fetch_all(&mut *conn)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    let embedded: Vec<_> = MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| {
+            (
+                migration.version,
+                migration.description.as_ref(),
+                migration.checksum.as_ref(),
+            )
+        })
+        .collect();
+    Ok(compare(&embedded, recorded))
+}
+
+/// [`migration_status`] of the primary, for the admin API
+pub async fn schema_status(ctx: &RequestCtx, db: &Db) -> Result<MigrationStatusResponse, AppError> {
+    let mut conn = db.primary().acquire().await.map_err(|e| db_error(ctx, e))?;
+    migration_status(&mut conn)
+        .await
+        .map_err(|e| db_error(ctx, e))
+}
+
+fn compare(embedded: &[(i64, &str, &[u8])], recorded: Vec<Recorded>) -> MigrationStatusResponse {
+    let applied_versions: HashSet<i64> = recorded.iter().map(|row| row.0).collect();
+    let current_version = recorded.iter().filter(|row| row.3).map(|row| row.0).max();
+
+    let applied = recorded
+        .into_iter()
+        .map(|(version, description, installed_on, success, checksum)| {
+            let modified = embedded
+                .iter()
+                .any(|(v, _, expected)| *v == version && *expected != checksum.as_slice());
+            MigrationInfo {
+                version,
+                description,
+                installed_on: Some(installed_on),
+                success: Some(success),
+                modified,
+            }
+        })
+        .collect();
+    let pending = embedded
+        .iter()
+        .filter(|(version, _, _)| !applied_versions.contains(version))
+        .map(|(version, description, _)| MigrationInfo {
+            version: *version,
+            description: description.to_string(),
+            installed_on: None,
+            success: None,
+            modified: false,
+        })
+        .collect();
+
+    MigrationStatusResponse {
+        current_version,
+        applied,
+        pending,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_migrations_are_ordered() {
+        let versions: Vec<i64> = MIGRATOR.iter().map(|migration| migration.version).collect();
+        assert!(!versions.is_empty());
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_compare() {
+        let embedded = [
+            (1, "initial schema", &[1u8][..]),
+            (2, "beds", &[2u8][..]),
+            (3, "incidents", &[3u8][..]),
+        ];
+        let recorded = vec![
+            (1, "initial schema".to_string(), Utc::now(), true, vec![1]),
+            (2, "beds".to_string(), Utc::now(), true, vec![9]),
+        ];
+
+        let status = compare(&embedded, recorded);
+        assert_eq!(status.current_version, Some(2));
+        assert!(!status.applied[0].modified);
+        assert!(status.applied[1].modified);
+        assert_eq!(status.pending.len(), 1);
+        assert_eq!(status.pending[0].version, 3);
+        assert!(!status.is_up_to_date());
+
+        let status = compare(&embedded, Vec::new());
+        assert_eq!(status.current_version, None);
+        assert_eq!(status.pending.len(), 3);
+    }
+}
@@ -1 +1,156 @@
 // pub mod store;
+
+pub mod ambulance_repository;
+pub mod attachment_repository;
+pub mod auth_audit_repository;
+pub mod bed_repository;
+pub mod consent_repository;
+pub mod db;
+pub mod db_policy;
+pub mod device_repository;
+pub mod discharge_repository;
+pub mod handover_repository;
+pub mod hospital_cache;
+pub mod hospital_repository;
+pub mod hospital_resolver;
+pub mod idempotency_store;
+pub mod incident_repository;
+pub mod lab_repository;
+pub mod live_events;
+pub mod medication_repository;
+pub mod memory_patient_repository;
+pub mod migrate;
+pub mod outbox_repository;
+pub mod patient_event_repository;
+pub mod patient_number;
+pub mod patient_repository;
+pub mod query_metrics;
+pub mod redis_pool;
+pub mod retention_repository;
+pub mod service_account_repository;
+pub mod staff_repository;
+pub mod tenancy;
+pub mod transfer_repository;
+pub mod triage_repository;
+pub mod txn;
+pub mod user_repository;
+pub mod vitals_repository;
+
+pub use ambulance_repository::AmbulanceRepository;
+pub use attachment_repository::AttachmentRepository;
+pub use auth_audit_repository::AuthAuditRepository;
+pub use bed_repository::BedRepository;
+pub use consent_repository::ConsentRepository;
+pub use db::{Db, ReadPreference};
+pub use db_policy::{CircuitBreaker, DbPolicy};
+pub use lib_utils::retry::RetryPolicy;
+pub use device_repository::DeviceRepository;
+pub use discharge_repository::DischargeRepository;
+pub use handover_repository::HandoverRepository;
+pub use hospital_cache::HospitalCache;
+pub use hospital_repository::HospitalRepository;
+pub use hospital_resolver::PgHospitalResolver;
+pub use idempotency_store::{ClaimToken, IdempotencyClaim, IdempotencyStore, StoredResponse};
+pub use incident_repository::IncidentRepository;
+pub use lab_repository::LabRepository;
+pub use live_events::{spawn_listener, EventBus};
+pub use medication_repository::MedicationRepository;
+pub use memory_patient_repository::MemoryPatientRepository;
+pub use migrate::{migration_status, run_migrations, schema_status, MIGRATOR};
+pub use outbox_repository::{OutboxMessage, OutboxRepository};
+pub use patient_event_repository::PatientEventRepository;
+pub use patient_repository::{PatientRepository, PgPatientRepository};
+pub use query_metrics::QueryMetrics;
+pub use redis_pool::RedisPool;
+pub use retention_repository::RetentionRepository;
+pub use service_account_repository::ServiceAccountRepository;
+pub use staff_repository::StaffRepository;
+pub use transfer_repository::TransferRepository;
+pub use triage_repository::TriageRepository;
+pub use txn::{DbExecutor, Txn};
+pub use user_repository::UserRepository;
+pub use vitals_repository::VitalsRepository;
+
+use sqlx::{Postgres, QueryBuilder};
+use tracing::{error, warn};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{PageRequest, SortField};
+use lib_types::errors::AppError;
+
+/// Columns selected into a `Patient`
+pub(crate) const PATIENT_COLUMNS: &str = "id, patient_number, national_id, first_name, \
+     last_name, age, gender, chief_complaint, triage_level, status, hospital_id, \
+     assigned_staff_id, ambulance_id, bed_id, emergency_contacts, medical_history, allergies, \
+     insurance_info, incident_location, incident_time, blood_type, dnr, dnr_recorded_by, \
+     dnr_recorded_at, isolation_precautions, identifiers, created_at, updated_at, version, \
+     created_by, updated_by";
+
+/// Reject a write made against an older version of a record. `expected` is
+/// the version the client last read; `None` skips the check.
+pub(crate) fn check_version(
+    record: &str,
+    expected: Option<i64>,
+    current: i64,
+) -> Result<(), AppError> {
+    match expected {
+        Some(expected) if expected != current => Err(AppError::Conflict {
+            message: format!(
+                "{} was changed by someone else (version {}, expected {}); \
+                 reload and try again",
+                record, current, expected
+            ),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Map a database error, logging it with the request's correlation id.
+/// Expected failures such as constraint violations are logged as warnings.
+pub(crate) fn db_error(ctx: &RequestCtx, error: sqlx::Error) -> AppError {
+    let message = error.to_string();
+    let app_error = AppError::from(error);
+    if app_error.should_log_error() {
+        error!(correlation_id = ctx.correlation_id(), "Database error: {}", message);
+    } else {
+        warn!(correlation_id = ctx.correlation_id(), "Database error: {}", message);
+    }
+    app_error
+}
+
+/// Append keyset paging to a query: the rows after the cursor, ordered by
+/// the sort column with the id breaking ties, and one row more than the
+/// limit. The column comes from the sort whitelist; cursor values are bound.
+pub(crate) fn push_page<S: SortField>(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    page: &PageRequest<S>,
+) -> Result<(), AppError> {
+    let column = page.sort.column();
+    let direction = page.direction;
+
+    let cursor = page
+        .decode_cursor()
+        .map_err(|message| AppError::validation_error("cursor", message))?;
+    if let Some(cursor) = cursor {
+        builder
+            .push(format!(
+                " AND ({}, id) {} (CAST(",
+                column,
+                direction.comparison()
+            ))
+            .push_bind(cursor.value)
+            .push(format!(" AS {}), ", page.sort.sql_type()))
+            .push_bind(cursor.id)
+            .push(")");
+    }
+
+    builder
+        .push(format!(
+            " ORDER BY {} {}, id {} LIMIT ",
+            column,
+            direction.as_sql(),
+            direction.as_sql()
+        ))
+        .push_bind(page.fetch_limit());
+    Ok(())
+}
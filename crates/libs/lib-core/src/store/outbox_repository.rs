@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::{FromRow, PgConnection};
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::{Deterioration, Patient, PatientVitals};
+use lib_types::enums::PatientStatus;
+use lib_types::errors::AppError;
+use lib_types::ids::HospitalId;
+
+use super::{db_error, Db};
+
+/// A patient moved from one status to another
+pub const PATIENT_STATUS_CHANGED: &str = "patient.status_changed";
+/// Vital signs recorded for a patient show them deteriorating
+pub const PATIENT_DETERIORATED: &str = "patient.deteriorated";
+
+const OUTBOX_COLUMNS: &str =
+    "id, topic, aggregate_id, hospital_id, payload, attempts, last_error, \
+     request_id, correlation_id, created_at";
+
+/// Delay before the first retry of a failed delivery; doubled per attempt
+const RETRY_BASE: Duration = Duration::from_secs(1);
+const RETRY_MAX: Duration = Duration::from_secs(300);
+
+/// An event waiting to be delivered
+#[derive(Debug, Clone, FromRow)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub topic: String,
+    pub aggregate_id: Uuid,
+    pub hospital_id: Option<HospitalId>,
+    pub payload: serde_json::Value,
+    pub attempts: i32, // Including the delivery in progress
+    pub last_error: Option<String>,
+    pub request_id: Option<String>, // Of the request that enqueued the message
+    pub correlation_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Record that `patient` left `previous`. Must run on the connection of the
+/// transaction that stored the new status, so the event exists exactly when
+/// the change does.
+pub(crate) async fn enqueue_status_change(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    patient: &Patient,
+    previous: PatientStatus,
+) -> Result<(), AppError> {
+    let payload = json!({
+        "patient_id": patient.id,
+        "patient_number": patient.patient_number,
+        "hospital_id": patient.hospital_id,
+        "previous_status": previous,
+        "status": patient.status,
+        "version": patient.version,
+        "changed_by": ctx.user_id(),
+        "changed_at": patient.updated_at,
+    });
+    enqueue(conn, ctx, PATIENT_STATUS_CHANGED, patient, payload).await
+}
+
+/// Record that `vitals` show `patient` deteriorating, on the connection of
+/// the transaction that stored them
+pub(crate) async fn enqueue_deterioration(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    patient: &Patient,
+    vitals: &PatientVitals,
+    findings: &[Deterioration],
+) -> Result<(), AppError> {
+    let payload = json!({
+        "patient_id": patient.id,
+        "patient_number": patient.patient_number,
+        "hospital_id": patient.hospital_id,
+        "vitals_id": vitals.id,
+        "findings": findings,
+        "summary": findings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        "recorded_by": vitals.recorded_by,
+        "recorded_at": vitals.recorded_at,
+    });
+    enqueue(conn, ctx, PATIENT_DETERIORATED, patient, payload).await
+}
+
+async fn enqueue(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    topic: &str,
+    patient: &Patient,
+    payload: serde_json::Value,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO outbox (id, topic, aggregate_id, hospital_id, payload, request_id, \
+         correlation_id) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(Uuid::new_v4())
+    .bind(topic)
+    .bind(patient.id)
+    .bind(patient.hospital_id)
+    .bind(payload)
+    .bind(ctx.request_id())
+    .bind(ctx.correlation_id())
+    .execute(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+    Ok(())
+}
+
+/// Delivery bookkeeping for the outbox
+#[derive(Clone)]
+pub struct OutboxRepository {
+    db: Db,
+}
+
+impl OutboxRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Claim up to `limit` messages that are due, oldest first. A claimed
+    /// message is hidden from other dispatchers for `lease`; unless it is
+    /// marked dispatched or failed by then, it is delivered again.
+    pub async fn claim(
+        &self,
+        ctx: &RequestCtx,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxMessage>, AppError> {
+        let query = format!(
+            "UPDATE outbox SET attempts = attempts + 1, \
+             available_at = NOW() + make_interval(secs => $2) \
+             WHERE id IN (SELECT id FROM outbox \
+                 WHERE dispatched_at IS NULL AND available_at <= NOW() \
+                 ORDER BY available_at, created_at LIMIT $1 FOR UPDATE SKIP LOCKED) \
+             RETURNING {}",
+            OUTBOX_COLUMNS
+        );
+
+        let mut messages = sqlx::query_as::<_, OutboxMessage>(&query)
+            .bind(limit)
+            .bind(lease.as_secs_f64())
+            .fetch_all(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        messages.sort_by_key(|message| message.created_at);
+        Ok(messages)
+    }
+
+    pub async fn mark_dispatched(&self, ctx: &RequestCtx, id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE outbox SET dispatched_at = NOW(), last_error = NULL WHERE id = $1")
+            .bind(id)
+            .execute(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        Ok(())
+    }
+
+    /// Schedule another delivery of a message after a failed one, backing
+    /// off exponentially with the number of attempts
+    pub async fn mark_failed(
+        &self,
+        ctx: &RequestCtx,
+        message: &OutboxMessage,
+        error: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE outbox SET last_error = $2, \
+             available_at = NOW() + make_interval(secs => $3) WHERE id = $1",
+        )
+        .bind(message.id)
+        .bind(error)
+        .bind(retry_delay(message.attempts).as_secs_f64())
+        .execute(self.db.primary())
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+        Ok(())
+    }
+}
+
+/// How long to wait after the `attempts`th failed delivery: one second,
+/// doubling each time, at most five minutes
+pub fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE.saturating_mul(1 << exponent).min(RETRY_MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(2), Duration::from_secs(2));
+        assert_eq!(retry_delay(5), Duration::from_secs(16));
+        assert_eq!(retry_delay(9), Duration::from_secs(256));
+        assert_eq!(retry_delay(10), RETRY_MAX);
+        assert_eq!(retry_delay(i32::MAX), RETRY_MAX);
+        assert_eq!(retry_delay(0), RETRY_BASE);
+    }
+}
@@ -0,0 +1,158 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::PatientEvent;
+use lib_types::errors::AppError;
+use lib_types::ids::PatientId;
+
+use super::query_metrics::Observe;
+use super::{db_error, Db, ReadPreference};
+
+const EVENT_COLUMNS: &str =
+    "id, patient_id, hospital_id, kind, from_value, to_value, reason, actor_id, occurred_at";
+
+/// Add `event` to the patient's log, unless nothing changed. Must run on
+/// the connection of the transaction that made the change.
+pub(crate) async fn record_event(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    event: &PatientEvent,
+) -> Result<(), AppError> {
+    if !event.is_change() {
+        return Ok(());
+    }
+
+    sqlx::query(&format!(
+        "INSERT INTO patient_events ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        EVENT_COLUMNS
+    ))
+    .bind(event.id)
+    .bind(event.patient_id)
+    .bind(event.hospital_id)
+    .bind(event.kind)
+    .bind(&event.from_value)
+    .bind(&event.to_value)
+    .bind(&event.reason)
+    .bind(event.actor_id)
+    .bind(event.occurred_at)
+    .execute(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+    Ok(())
+}
+
+/// Log the vital signs stored with `vitals_ids`, each for its patient at
+/// the patient's current hospital. Must run in the transaction that stored
+/// them.
+pub(crate) async fn record_vitals_events(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    vitals_ids: &[Uuid],
+) -> Result<(), AppError> {
+    if vitals_ids.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(
+        "INSERT INTO patient_events (id, patient_id, hospital_id, kind, to_value, actor_id, \
+         occurred_at) \
+         SELECT gen_random_uuid(), v.patient_id, p.hospital_id, 'vitals_recorded', v.id::text, \
+             v.recorded_by, v.created_at \
+         FROM patient_vitals v JOIN patients p ON p.id = v.patient_id \
+         WHERE v.id = ANY($1)",
+    )
+    .bind(vitals_ids)
+    .execute(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+    Ok(())
+}
+
+/// Read access to the patient event log
+#[derive(Clone)]
+pub struct PatientEventRepository {
+    db: Db,
+}
+
+impl PatientEventRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// A patient's events, oldest first. Events made at a previous hospital
+    /// are included for the hospital the patient is at now.
+    pub async fn list_for_patient(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<PatientEvent>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patient_events WHERE patient_id = $1 \
+             AND EXISTS (SELECT 1 FROM patients \
+                 WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2)) \
+             ORDER BY occurred_at, id",
+            EVENT_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, PatientEvent>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "patient_events.list_for_patient")
+            .await
+    }
+
+    /// Up to `limit` of a patient's events logged after the one with id
+    /// `after`, oldest first. An id that is not in the patient's log
+    /// replays it from the start.
+    pub async fn list_after(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<PatientEvent>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patient_events WHERE patient_id = $1 \
+             AND EXISTS (SELECT 1 FROM patients \
+                 WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2)) \
+             AND COALESCE((occurred_at, id) > (SELECT occurred_at, id FROM patient_events \
+                 WHERE id = $3 AND patient_id = $1), TRUE) \
+             ORDER BY occurred_at, id LIMIT $4",
+            EVENT_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, PatientEvent>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .bind(after)
+            .bind(limit)
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "patient_events.list_after")
+            .await
+    }
+
+    /// The id of a patient's most recent event, where a stream of new
+    /// events starts
+    pub async fn latest_id(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Option<Uuid>, AppError> {
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_scalar(
+            "SELECT id FROM patient_events WHERE patient_id = $1 \
+             AND EXISTS (SELECT 1 FROM patients \
+                 WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2)) \
+             ORDER BY occurred_at DESC, id DESC LIMIT 1",
+        )
+        .bind(patient_id)
+        .bind(ctx.tenant_hospital_id())
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| db_error(ctx, e))
+    }
+}
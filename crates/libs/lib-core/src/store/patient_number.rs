@@ -0,0 +1,65 @@
+use chrono::{NaiveDate, Utc};
+use sqlx::PgConnection;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::errors::{AppError, HospitalError};
+use lib_types::ids::HospitalId;
+
+use super::db_error;
+
+/// Draw the next patient number for `hospital_id`, e.g.
+/// `DHA001-20250611-0042`: the hospital's code, the UTC day of registration
+/// and the day's sequence. The code is the hospital's license number without
+/// separators. Run inside the transaction that inserts the patient, so the
+/// number is given back if the insert fails.
+pub(crate) async fn next_patient_number(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    hospital_id: HospitalId,
+) -> Result<String, AppError> {
+    let day = Utc::now().date_naive();
+    let (code, sequence) = sqlx::query_as::<_, (String, i32)>(
+        "WITH hospital AS ( \
+             SELECT id, upper(regexp_replace(license_number, '[^A-Za-z0-9]', '', 'g')) AS code \
+             FROM hospitals WHERE id = $1 \
+         ), next AS ( \
+             INSERT INTO patient_number_sequences (hospital_id, day, last_value) \
+             SELECT id, $2, 1 FROM hospital \
+             ON CONFLICT (hospital_id, day) \
+             DO UPDATE SET last_value = patient_number_sequences.last_value + 1 \
+             RETURNING last_value \
+         ) \
+         SELECT hospital.code, next.last_value FROM hospital, next",
+    )
+    .bind(hospital_id)
+    .bind(day)
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?
+    .ok_or(HospitalError::NotFound { hospital_id })?;
+
+    Ok(format_patient_number(&code, day, sequence))
+}
+
+/// Four sequence digits cover a busy day; later numbers grow a digit
+pub(crate) fn format_patient_number(code: &str, day: NaiveDate, sequence: i32) -> String {
+    format!("{}-{}-{:04}", code, day.format("%Y%m%d"), sequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_patient_number() {
+        let day = NaiveDate::from_ymd_opt(2025, 6, 11).unwrap();
+        assert_eq!(
+            format_patient_number("DXB01", day, 42),
+            "DXB01-20250611-0042"
+        );
+        assert_eq!(
+            format_patient_number("DXB01", day, 12345),
+            "DXB01-20250611-12345"
+        );
+    }
+}
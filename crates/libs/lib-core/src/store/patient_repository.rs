@@ -0,0 +1,775 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::types::Json;
+use sqlx::{Connection, PgConnection, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{
+    CursorPage, LiveEvent, LookupPatientRequest, PatientListQuery, PatientSearchHit,
+    PatientSearchRequest, PatientSummary, RetriageRequest, UpdatePatientRequest,
+};
+use lib_types::entities::{Patient, PatientEvent, TriageAssessment};
+use lib_types::enums::PatientStatus;
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{AmbulanceId, PatientId, UserId};
+
+use super::live_events::notify;
+use super::outbox_repository::enqueue_status_change;
+use super::patient_event_repository::record_event;
+use super::patient_number::next_patient_number;
+use super::query_metrics::Observe;
+use super::triage_repository::insert_assessment;
+use super::{
+    check_version, db_error, push_page, Db, DbExecutor, ReadPreference, TriageRepository, Txn,
+    PATIENT_COLUMNS,
+};
+
+/// Data access for patient records.
+///
+/// Every method takes the request context: reads and writes are confined to
+/// the caller's hospital, and database errors are logged with the request's
+/// correlation id.
+#[async_trait]
+pub trait PatientRepository: Send + Sync {
+    /// Insert a new patient. The patient must belong to the caller's
+    /// hospital and hold no identifier another patient already has. A blank
+    /// patient number is replaced by the hospital's next generated one.
+    /// When a user registers the patient, their triage level starts the
+    /// triage history.
+    async fn create(&self, ctx: &RequestCtx, patient: &Patient) -> Result<Patient, AppError>;
+
+    async fn find_by_id(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+    ) -> Result<Option<Patient>, AppError>;
+
+    async fn find_by_number(
+        &self,
+        ctx: &RequestCtx,
+        patient_number: &str,
+    ) -> Result<Option<Patient>, AppError>;
+
+    /// Find the patient holding an identifier
+    async fn find_by_identifier(
+        &self,
+        ctx: &RequestCtx,
+        request: &LookupPatientRequest,
+    ) -> Result<Option<Patient>, AppError>;
+
+    /// Search patients one page at a time, by free text and filters
+    async fn search(
+        &self,
+        ctx: &RequestCtx,
+        request: &PatientSearchRequest,
+    ) -> Result<CursorPage<PatientSummary>, AppError>;
+
+    /// List patients newest first, one page at a time
+    async fn list(
+        &self,
+        ctx: &RequestCtx,
+        query: &PatientListQuery,
+    ) -> Result<CursorPage<PatientSummary>, AppError>;
+
+    /// Apply a partial update, returning the patient and the changed fields.
+    /// Fails with `AppError::Conflict` when the update carries a version
+    /// the patient has moved on from.
+    async fn update(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        update: &UpdatePatientRequest,
+    ) -> Result<(Patient, Vec<&'static str>), AppError>;
+
+    /// Record or withdraw a do-not-resuscitate order
+    async fn record_dnr(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        dnr: bool,
+        recorded_by: UserId,
+    ) -> Result<(Patient, bool), AppError>;
+
+    /// Re-triage the patient, returning the patient with their new level
+    /// and the assessment added to their history
+    async fn retriage(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        request: &RetriageRequest,
+        assessed_by: UserId,
+    ) -> Result<(Patient, TriageAssessment), AppError>;
+
+    /// List every (re)triage of the patient, oldest first
+    async fn triage_history(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+    ) -> Result<Vec<TriageAssessment>, AppError>;
+
+    /// Move the patient from `expected` to `next`. Fails with a conflict
+    /// when the status is no longer `expected`, so of two users acting on
+    /// the same patient the second sees the first's change instead of
+    /// silently undoing it.
+    async fn transition_status(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        expected: PatientStatus,
+        next: PatientStatus,
+    ) -> Result<Patient, AppError>;
+
+    /// Assign the patient to a staff member, or unassign with `None`
+    async fn assign_staff(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        staff_id: Option<Uuid>,
+    ) -> Result<Patient, AppError>;
+
+    /// Assign the patient to an ambulance, or unassign with `None`
+    async fn assign_ambulance(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        ambulance_id: Option<AmbulanceId>,
+    ) -> Result<Patient, AppError>;
+}
+
+/// Postgres implementation of [`PatientRepository`]
+#[derive(Clone)]
+pub struct PgPatientRepository {
+    exec: DbExecutor,
+}
+
+impl PgPatientRepository {
+    pub fn new(db: Db) -> Self {
+        Self { exec: db.into() }
+    }
+
+    /// Run this repository's statements in `txn`
+    pub fn in_txn(txn: &Txn) -> Self {
+        Self { exec: txn.into() }
+    }
+
+    /// Set one assignment column, returning the updated patient. `event`
+    /// builds the log entry from the updated patient and the previous one.
+    async fn set_assignment<T>(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        column: &'static str,
+        value: Option<T>,
+        event: impl FnOnce(&Patient, &Patient) -> PatientEvent + Send,
+    ) -> Result<Patient, AppError>
+    where
+        T: for<'q> sqlx::Encode<'q, Postgres> + sqlx::Type<Postgres> + Send,
+    {
+        let select = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR UPDATE",
+            PATIENT_COLUMNS
+        );
+        let update = format!(
+            "UPDATE patients SET {} = $2, updated_at = $3, updated_by = $4, \
+             version = version + 1 WHERE id = $1 RETURNING {}",
+            column, PATIENT_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+        let previous = sqlx::query_as::<_, Patient>(&select)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id: id })?;
+        let patient = sqlx::query_as::<_, Patient>(&update)
+            .bind(id)
+            .bind(value)
+            .bind(Utc::now())
+            .bind(ctx.user_id())
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        record_event(&mut tx, ctx, &event(&patient, &previous)).await?;
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(patient)
+    }
+}
+
+#[async_trait]
+impl PatientRepository for PgPatientRepository {
+    async fn create(&self, ctx: &RequestCtx, patient: &Patient) -> Result<Patient, AppError> {
+        if ctx
+            .tenant_hospital_id()
+            .is_some_and(|hospital_id| hospital_id != patient.hospital_id)
+        {
+            return Err(PatientError::HospitalMismatch {
+                hospital_id: patient.hospital_id,
+            }
+            .into());
+        }
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+        ensure_identifiers_unique(&mut tx, ctx, patient).await?;
+        let patient_number = match patient.patient_number.trim() {
+            "" => next_patient_number(&mut tx, ctx, patient.hospital_id).await?,
+            number => number.to_string(),
+        };
+
+        let query = format!(
+            "INSERT INTO patients ({}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, \
+             $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, \
+             $28, $29, $30, $31) RETURNING {}",
+            PATIENT_COLUMNS, PATIENT_COLUMNS
+        );
+        let created = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient.id)
+            .bind(&patient_number)
+            .bind(&patient.national_id)
+            .bind(&patient.first_name)
+            .bind(&patient.last_name)
+            .bind(patient.age)
+            .bind(patient.gender)
+            .bind(&patient.chief_complaint)
+            .bind(patient.triage_level)
+            .bind(patient.status)
+            .bind(patient.hospital_id)
+            .bind(patient.assigned_staff_id)
+            .bind(patient.ambulance_id)
+            .bind(patient.bed_id)
+            .bind(&patient.emergency_contacts)
+            .bind(&patient.medical_history)
+            .bind(&patient.allergies)
+            .bind(&patient.insurance_info)
+            .bind(patient.incident_location)
+            .bind(patient.incident_time)
+            .bind(patient.blood_type)
+            .bind(patient.dnr)
+            .bind(patient.dnr_recorded_by)
+            .bind(patient.dnr_recorded_at)
+            .bind(&patient.isolation_precautions)
+            .bind(&patient.identifiers)
+            .bind(patient.created_at)
+            .bind(patient.updated_at)
+            .bind(patient.version)
+            .bind(ctx.user_id())
+            .bind(ctx.user_id())
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        if let Some(user_id) = ctx.user_id() {
+            let assessment =
+                TriageAssessment::initial(&created, created.chief_complaint.clone(), user_id);
+            insert_assessment(&mut tx, ctx, &assessment).await?;
+        }
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(created)
+    }
+
+    async fn find_by_id(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+    ) -> Result<Option<Patient>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2)",
+            PATIENT_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Patient>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "patients.find_by_id")
+            .await
+    }
+
+    async fn find_by_number(
+        &self,
+        ctx: &RequestCtx,
+        patient_number: &str,
+    ) -> Result<Option<Patient>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patients WHERE patient_number = $1 \
+             AND ($2::uuid IS NULL OR hospital_id = $2)",
+            PATIENT_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_number.trim())
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "patients.find_by_number")
+            .await
+    }
+
+    /// MRNs and aliases are only unique within a hospital, so a lookup
+    /// across hospitals that matches several patients asks for the hospital
+    /// instead of picking one.
+    async fn find_by_identifier(
+        &self,
+        ctx: &RequestCtx,
+        request: &LookupPatientRequest,
+    ) -> Result<Option<Patient>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patients WHERE identifiers @> $1 \
+             AND ($2::uuid IS NULL OR hospital_id = $2) \
+             AND ($3::uuid IS NULL OR hospital_id = $3) \
+             LIMIT 2",
+            PATIENT_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut patients = sqlx::query_as::<_, Patient>(&query)
+            .bind(Json([request.identifier()]))
+            .bind(ctx.tenant_hospital_id())
+            .bind(request.hospital_id)
+            .fetch_all(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "patients.find_by_identifier")
+            .await?;
+
+        if patients.len() > 1 {
+            return Err(PatientError::InvalidData {
+                field: "hospital_id".to_string(),
+                reason: format!("{} matches patients at several hospitals", request.scheme),
+            }
+            .into());
+        }
+        Ok(patients.pop())
+    }
+
+    /// Most acute first by default. The caller's tenant is always applied
+    /// on top of the requested filters.
+    async fn search(
+        &self,
+        ctx: &RequestCtx,
+        request: &PatientSearchRequest,
+    ) -> Result<CursorPage<PatientSummary>, AppError> {
+        // The rank is computed in a subquery so pages can be keyed on it
+        let mut select = QueryBuilder::new(format!("SELECT * FROM (SELECT {}, ", PATIENT_COLUMNS));
+        push_search_distance(&mut select, request.query_text());
+        select.push(" AS search_distance FROM patients WHERE TRUE");
+        push_search_filters(&mut select, ctx, request);
+        select.push(") AS hits WHERE TRUE");
+        push_page(&mut select, &request.page)?;
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let hits = select
+            .build_query_as::<PatientSearchHit>()
+            .fetch_all(&mut *conn)
+            .observe(self.exec.metrics(), ctx, "patients.search")
+            .await?;
+
+        Ok(CursorPage::from_rows(hits, &request.page, |hit| {
+            PatientSummary::from_patient(&hit.patient)
+        }))
+    }
+
+    /// An unfiltered search keyed on `(created_at, id)`
+    async fn list(
+        &self,
+        ctx: &RequestCtx,
+        query: &PatientListQuery,
+    ) -> Result<CursorPage<PatientSummary>, AppError> {
+        let request = PatientSearchRequest {
+            page: query.page(),
+            ..Default::default()
+        };
+        self.search(ctx, &request).await
+    }
+
+    /// Nothing is written when the update matches the stored record
+    async fn update(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        update: &UpdatePatientRequest,
+    ) -> Result<(Patient, Vec<&'static str>), AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR UPDATE",
+            PATIENT_COLUMNS
+        );
+        let mut patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id: id })?;
+
+        check_version("Patient", update.version, patient.version)?;
+        let changed = update.apply_to(&mut patient);
+        if changed.is_empty() {
+            return Ok((patient, changed));
+        }
+        if changed.contains(&"identifiers") {
+            ensure_identifiers_unique(&mut tx, ctx, &patient).await?;
+        }
+
+        patient.updated_by = ctx.user_id();
+        patient.version = sqlx::query_scalar(
+            "UPDATE patients SET first_name = $2, last_name = $3, age = $4, gender = $5, \
+             national_id = $6, chief_complaint = $7, incident_location = $8, \
+             incident_time = $9, emergency_contacts = $10, allergies = $11, \
+             medical_history = $12, insurance_info = $13, blood_type = $14, \
+             isolation_precautions = $15, identifiers = $16, updated_at = $17, \
+             updated_by = $18, version = version + 1 WHERE id = $1 RETURNING version",
+        )
+        .bind(patient.id)
+        .bind(&patient.first_name)
+        .bind(&patient.last_name)
+        .bind(patient.age)
+        .bind(patient.gender)
+        .bind(&patient.national_id)
+        .bind(&patient.chief_complaint)
+        .bind(patient.incident_location)
+        .bind(patient.incident_time)
+        .bind(&patient.emergency_contacts)
+        .bind(&patient.allergies)
+        .bind(&patient.medical_history)
+        .bind(&patient.insurance_info)
+        .bind(patient.blood_type)
+        .bind(&patient.isolation_precautions)
+        .bind(&patient.identifiers)
+        .bind(patient.updated_at)
+        .bind(patient.updated_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok((patient, changed))
+    }
+
+    /// Returns the patient and whether the order changed
+    async fn record_dnr(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        dnr: bool,
+        recorded_by: UserId,
+    ) -> Result<(Patient, bool), AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR UPDATE",
+            PATIENT_COLUMNS
+        );
+        let mut patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id: id })?;
+
+        if !patient.record_dnr(dnr, recorded_by) {
+            return Ok((patient, false));
+        }
+
+        patient.updated_by = ctx.user_id();
+        patient.version = sqlx::query_scalar(
+            "UPDATE patients SET dnr = $2, dnr_recorded_by = $3, dnr_recorded_at = $4, \
+             updated_at = $5, updated_by = $6, version = version + 1 \
+             WHERE id = $1 RETURNING version",
+        )
+        .bind(patient.id)
+        .bind(patient.dnr)
+        .bind(patient.dnr_recorded_by)
+        .bind(patient.dnr_recorded_at)
+        .bind(patient.updated_at)
+        .bind(patient.updated_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok((patient, true))
+    }
+
+    async fn retriage(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        request: &RetriageRequest,
+        assessed_by: UserId,
+    ) -> Result<(Patient, TriageAssessment), AppError> {
+        TriageRepository::with_executor(self.exec.clone())
+            .retriage(ctx, id, request, assessed_by)
+            .await
+    }
+
+    async fn triage_history(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+    ) -> Result<Vec<TriageAssessment>, AppError> {
+        TriageRepository::with_executor(self.exec.clone())
+            .history(ctx, id)
+            .await
+    }
+
+    async fn transition_status(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        expected: PatientStatus,
+        next: PatientStatus,
+    ) -> Result<Patient, AppError> {
+        if !expected.can_transition_to(next) {
+            return Err(PatientError::InvalidStatusTransition {
+                current: expected,
+                requested: next,
+            }
+            .into());
+        }
+
+        let query = format!(
+            "UPDATE patients SET status = $3, updated_at = $4, updated_by = $6, \
+             version = version + 1 \
+             WHERE id = $1 AND status = $2 AND ($5::uuid IS NULL OR hospital_id = $5) \
+             RETURNING {}",
+            PATIENT_COLUMNS
+        );
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+        let updated = sqlx::query_as::<_, Patient>(&query)
+            .bind(id)
+            .bind(expected)
+            .bind(next)
+            .bind(Utc::now())
+            .bind(ctx.tenant_hospital_id())
+            .bind(ctx.user_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        if let Some(patient) = updated {
+            enqueue_status_change(&mut tx, ctx, &patient, expected).await?;
+            record_event(
+                &mut tx,
+                ctx,
+                &PatientEvent::status_changed(&patient, expected, ctx.user_id()),
+            )
+            .await?;
+            notify(
+                &mut tx,
+                ctx,
+                &LiveEvent::patient_status_changed(&patient, expected),
+            )
+            .await?;
+            tx.commit().await.map_err(|e| db_error(ctx, e))?;
+            return Ok(patient);
+        }
+        drop(tx);
+        drop(conn);
+
+        // Either the patient is not visible to the caller, or someone else
+        // moved it on first
+        match self.find_by_id(ctx, id).await? {
+            None => Err(PatientError::NotFound { patient_id: id }.into()),
+            Some(current) => Err(AppError::Conflict {
+                message: format!(
+                    "Patient status is now {}, not {}; reload and try again",
+                    current.status, expected
+                ),
+            }),
+        }
+    }
+
+    async fn assign_staff(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        staff_id: Option<Uuid>,
+    ) -> Result<Patient, AppError> {
+        self.set_assignment(
+            ctx,
+            id,
+            "assigned_staff_id",
+            staff_id,
+            |patient, previous| {
+                PatientEvent::staff_assigned(patient, previous.assigned_staff_id, ctx.user_id())
+            },
+        )
+        .await
+    }
+
+    async fn assign_ambulance(
+        &self,
+        ctx: &RequestCtx,
+        id: PatientId,
+        ambulance_id: Option<AmbulanceId>,
+    ) -> Result<Patient, AppError> {
+        self.set_assignment(
+            ctx,
+            id,
+            "ambulance_id",
+            ambulance_id,
+            |patient, previous| {
+                PatientEvent::ambulance_assigned(patient, previous.ambulance_id, ctx.user_id())
+            },
+        )
+        .await
+    }
+}
+
+/// Fail if another patient already holds one of the patient's identifiers.
+/// MRNs and aliases only clash within the same hospital. Each identifier is
+/// locked until the transaction ends, so two writers cannot both claim it.
+async fn ensure_identifiers_unique(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    patient: &Patient,
+) -> Result<(), AppError> {
+    let mut identifiers: Vec<_> = patient.identifiers.iter().collect();
+    // Lock in a fixed order so concurrent updates cannot deadlock
+    identifiers.sort_by(|a, b| (a.scheme, &a.value).cmp(&(b.scheme, &b.value)));
+
+    for identifier in identifiers {
+        let scope = identifier
+            .scheme
+            .is_hospital_scoped()
+            .then_some(patient.hospital_id);
+
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(format!(
+                "patient_identifier:{}:{}",
+                identifier.scheme, identifier.value
+            ))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+
+        let taken = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM patients WHERE identifiers @> $1 AND id <> $2 \
+             AND ($3::uuid IS NULL OR hospital_id = $3))",
+        )
+        .bind(Json([identifier]))
+        .bind(patient.id)
+        .bind(scope)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        if taken {
+            return Err(PatientError::DuplicateIdentifier {
+                scheme: identifier.scheme,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Append how far a patient is from the search query: the negated sum of
+/// the full-text rank and the similarity of the name skeletons (see
+/// `patient_name_key` in the migrations), so the best match sorts first
+fn push_search_distance(builder: &mut QueryBuilder<'_, Postgres>, query: Option<&str>) {
+    let Some(query) = query else {
+        builder.push("0::real");
+        return;
+    };
+    builder
+        .push("-(ts_rank(search_document, websearch_to_tsquery('simple', ")
+        .push_bind(query.to_string())
+        .push(")) + ts_rank(search_document, websearch_to_tsquery('english', ")
+        .push_bind(query.to_string())
+        .push(")) + word_similarity(patient_name_key(")
+        .push_bind(query.to_string())
+        .push("), name_key))::real");
+}
+
+/// Append the search filters as `AND` clauses. Values are always bound,
+/// never formatted into the SQL.
+fn push_search_filters(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    ctx: &RequestCtx,
+    request: &PatientSearchRequest,
+) {
+    if let Some(hospital_id) = ctx.tenant_hospital_id() {
+        builder.push(" AND hospital_id = ").push_bind(hospital_id);
+    }
+    if let Some(hospital_id) = request.hospital_id {
+        builder.push(" AND hospital_id = ").push_bind(hospital_id);
+    }
+
+    if let (Some(query), Some(number_pattern)) =
+        (request.query_text(), request.query_prefix_pattern())
+    {
+        // Names and numbers match as written, complaints by word stem, and
+        // names also by skeleton, which tolerates spelling and script
+        builder
+            .push(" AND (search_document @@ websearch_to_tsquery('simple', ")
+            .push_bind(query.to_string())
+            .push(") OR search_document @@ websearch_to_tsquery('english', ")
+            .push_bind(query.to_string())
+            .push(") OR patient_name_key(")
+            .push_bind(query.to_string())
+            .push(") <% name_key OR patient_number ILIKE ")
+            .push_bind(number_pattern)
+            .push(")");
+    }
+
+    if let Some(pattern) = request.name_pattern() {
+        builder
+            .push(" AND (first_name ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR last_name ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR first_name || ' ' || last_name ILIKE ")
+            .push_bind(pattern)
+            .push(")");
+    }
+    if let Some(ref patient_number) = request.patient_number {
+        builder
+            .push(" AND patient_number = ")
+            .push_bind(patient_number.trim().to_string());
+    }
+    if let Some(national_id) = request.national_id_digits() {
+        builder
+            .push(" AND replace(national_id, '-', '') = ")
+            .push_bind(national_id);
+    }
+
+    if !request.triage_levels.is_empty() {
+        builder.push(" AND triage_level IN (");
+        let mut levels = builder.separated(", ");
+        for level in &request.triage_levels {
+            levels.push_bind(*level);
+        }
+        levels.push_unseparated(")");
+    }
+    if !request.statuses.is_empty() {
+        builder.push(" AND status IN (");
+        let mut statuses = builder.separated(", ");
+        for status in &request.statuses {
+            statuses.push_bind(*status);
+        }
+        statuses.push_unseparated(")");
+    }
+
+    if let Some(created_from) = request.created_from {
+        builder.push(" AND created_at >= ").push_bind(created_from);
+    }
+    if let Some(created_to) = request.created_to {
+        builder.push(" AND created_at <= ").push_bind(created_to);
+    }
+    if let Some(staff_id) = request.assigned_staff_id {
+        builder
+            .push(" AND assigned_staff_id = ")
+            .push_bind(staff_id);
+    }
+}
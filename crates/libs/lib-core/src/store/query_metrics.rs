@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use sqlx::postgres::PgQueryResult;
+use tracing::warn;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::errors::AppError;
+
+use super::db_error;
+
+/// Upper bounds of the duration buckets, in seconds
+const DURATION_BUCKETS: [f64; 12] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+/// Upper bounds of the row count buckets
+const ROW_BUCKETS: [f64; 8] = [0.0, 1.0, 10.0, 50.0, 100.0, 500.0, 1000.0, 5000.0];
+
+const DEFAULT_SLOW_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// Timings of named store queries, exposed as Prometheus histograms
+/// (`db_query_duration_seconds`, `db_query_rows`) and a failure counter
+/// (`db_query_errors_total`), each labelled with the query name. Queries
+/// slower than the threshold are logged by name.
+#[derive(Clone)]
+pub struct QueryMetrics {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    slow_threshold_us: AtomicU64, // Zero disables slow query logging
+    queries: Mutex<BTreeMap<&'static str, QueryStats>>,
+}
+
+#[derive(Default)]
+struct QueryStats {
+    duration: Histogram<{ DURATION_BUCKETS.len() }>,
+    rows: Histogram<{ ROW_BUCKETS.len() }>,
+    errors: u64,
+}
+
+struct Histogram<const N: usize> {
+    buckets: [u64; N], // Observations per bucket, not cumulative
+    sum: f64,
+    count: u64,
+}
+
+impl<const N: usize> Default for Histogram<N> {
+    fn default() -> Self {
+        Self {
+            buckets: [0; N],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl<const N: usize> Histogram<N> {
+    fn observe(&mut self, bounds: &[f64; N], value: f64) {
+        if let Some(bucket) = bounds.iter().position(|bound| value <= *bound) {
+            self.buckets[bucket] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, metric: &str, query: &str, bounds: &[f64; N]) {
+        let mut cumulative = 0;
+        for (bound, count) in bounds.iter().zip(self.buckets) {
+            cumulative += count;
+            let _ = writeln!(
+                out,
+                "{}_bucket{{query=\"{}\",le=\"{}\"}} {}",
+                metric, query, bound, cumulative
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{}_bucket{{query=\"{}\",le=\"+Inf\"}} {}",
+            metric, query, self.count
+        );
+        let _ = writeln!(out, "{}_sum{{query=\"{}\"}} {}", metric, query, self.sum);
+        let _ = writeln!(
+            out,
+            "{}_count{{query=\"{}\"}} {}",
+            metric, query, self.count
+        );
+    }
+}
+
+/// Rows a query returned or affected
+pub(crate) trait RowCount {
+    fn row_count(&self) -> u64;
+}
+
+impl<T> RowCount for Vec<T> {
+    fn row_count(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+impl<T> RowCount for Option<T> {
+    fn row_count(&self) -> u64 {
+        self.is_some() as u64
+    }
+}
+
+impl RowCount for PgQueryResult {
+    fn row_count(&self) -> u64 {
+        self.rows_affected()
+    }
+}
+
+/// Time a query future: `.fetch_all(&mut *conn).observe(metrics, ctx, "name").await`
+/// in place of awaiting it and mapping the error with `db_error`
+pub(crate) trait Observe<T>: Future<Output = Result<T, sqlx::Error>> + Sized {
+    async fn observe(
+        self,
+        metrics: &QueryMetrics,
+        ctx: &RequestCtx,
+        name: &'static str,
+    ) -> Result<T, AppError>
+    where
+        T: RowCount,
+    {
+        metrics.observe(ctx, name, self).await
+    }
+}
+
+impl<T, F: Future<Output = Result<T, sqlx::Error>>> Observe<T> for F {}
+
+impl QueryMetrics {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                slow_threshold_us: AtomicU64::new(DEFAULT_SLOW_THRESHOLD.as_micros() as u64),
+                queries: Mutex::new(BTreeMap::new()),
+            }),
+        }
+    }
+
+    /// Log queries that take longer than `threshold`; zero logs none
+    pub fn set_slow_threshold(&self, threshold: Duration) {
+        self.inner
+            .slow_threshold_us
+            .store(threshold.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Run `query` and record it under `name`, converting its error like
+    /// `db_error` does
+    pub(crate) async fn observe<T, F>(
+        &self,
+        ctx: &RequestCtx,
+        name: &'static str,
+        query: F,
+    ) -> Result<T, AppError>
+    where
+        T: RowCount,
+        F: Future<Output = Result<T, sqlx::Error>>,
+    {
+        let started = Instant::now();
+        let result = query.await;
+        let elapsed = started.elapsed();
+        let rows = result.as_ref().ok().map(RowCount::row_count);
+        self.record(name, elapsed, rows);
+
+        let threshold = self.inner.slow_threshold_us.load(Ordering::Relaxed);
+        if threshold > 0 && elapsed >= Duration::from_micros(threshold) {
+            warn!(
+                correlation_id = ctx.correlation_id(),
+                query = name,
+                elapsed_ms = elapsed.as_millis() as u64,
+                rows = rows,
+                "Slow query"
+            );
+        }
+        result.map_err(|e| db_error(ctx, e))
+    }
+
+    /// Record a run of `name` that returned `rows`, or failed with `None`
+    fn record(&self, name: &'static str, elapsed: Duration, rows: Option<u64>) {
+        let mut queries = self.inner.queries.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = queries.entry(name).or_default();
+        stats
+            .duration
+            .observe(&DURATION_BUCKETS, elapsed.as_secs_f64());
+        match rows {
+            Some(rows) => stats.rows.observe(&ROW_BUCKETS, rows as f64),
+            None => stats.errors += 1,
+        }
+    }
+
+    /// The metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let queries = self.inner.queries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        out.push_str("# HELP db_query_duration_seconds Time taken by a database query\n");
+        out.push_str("# TYPE db_query_duration_seconds histogram\n");
+        for (name, stats) in queries.iter() {
+            stats.duration.render(
+                &mut out,
+                "db_query_duration_seconds",
+                name,
+                &DURATION_BUCKETS,
+            );
+        }
+
+        out.push_str("# HELP db_query_rows Rows returned or affected by a database query\n");
+        out.push_str("# TYPE db_query_rows histogram\n");
+        for (name, stats) in queries.iter() {
+            stats
+                .rows
+                .render(&mut out, "db_query_rows", name, &ROW_BUCKETS);
+        }
+
+        out.push_str("# HELP db_query_errors_total Database queries that failed\n");
+        out.push_str("# TYPE db_query_errors_total counter\n");
+        for (name, stats) in queries.iter() {
+            let _ = writeln!(
+                out,
+                "db_query_errors_total{{query=\"{}\"}} {}",
+                name, stats.errors
+            );
+        }
+        out
+    }
+}
+
+impl Default for QueryMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_histograms() {
+        let metrics = QueryMetrics::new();
+        metrics.record("patients.list", Duration::from_millis(3), Some(20));
+        metrics.record("patients.list", Duration::from_millis(30), Some(0));
+        metrics.record("patients.list", Duration::from_secs(10), None);
+
+        let text = metrics.render();
+        let lines: Vec<&str> = text.lines().collect();
+        for expected in [
+            "db_query_duration_seconds_bucket{query=\"patients.list\",le=\"0.0025\"} 0",
+            "db_query_duration_seconds_bucket{query=\"patients.list\",le=\"0.005\"} 1",
+            "db_query_duration_seconds_bucket{query=\"patients.list\",le=\"0.05\"} 2",
+            "db_query_duration_seconds_bucket{query=\"patients.list\",le=\"5\"} 2",
+            "db_query_duration_seconds_bucket{query=\"patients.list\",le=\"+Inf\"} 3",
+            "db_query_duration_seconds_count{query=\"patients.list\"} 3",
+            "db_query_rows_bucket{query=\"patients.list\",le=\"0\"} 1",
+            "db_query_rows_bucket{query=\"patients.list\",le=\"10\"} 1",
+            "db_query_rows_bucket{query=\"patients.list\",le=\"50\"} 2",
+            "db_query_rows_sum{query=\"patients.list\"} 20",
+            "db_query_rows_count{query=\"patients.list\"} 2",
+            "db_query_errors_total{query=\"patients.list\"} 1",
+        ] {
+            assert!(lines.contains(&expected), "missing {}\n{}", expected, text);
+        }
+    }
+}
@@ -0,0 +1,404 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use futures::StreamExt;
+use redis::aio::{ConnectionLike, ConnectionManager, ConnectionManagerConfig};
+use redis::cluster::ClusterClient;
+use redis::cluster_async::ClusterConnection;
+use redis::sentinel::{Sentinel, SentinelNodeConnectionInfo};
+use redis::{
+    Client, Cmd, ConnectionAddr, ErrorKind, IntoConnectionInfo, Msg, Pipeline, RedisError,
+    RedisFuture, RedisResult, TlsMode, Value,
+};
+use tokio::sync::{mpsc, Mutex, Notify};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// How often connections are checked, and how long a check may take
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Wait before resubscribing after a subscription connection is lost
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// Redis handle shared by the session, lockout and RBAC stores.
+///
+/// A small pool of multiplexed connections to a standalone server, to the
+/// master a group of sentinels points at, or to a cluster. Connections that
+/// fail a command or a health check leave the rotation until they have been
+/// reopened; with Sentinel, reopening asks the sentinels for the current
+/// master, so the pool follows a failover.
+#[derive(Clone)]
+pub struct RedisPool {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    target: Target,
+    members: Vec<Member>,
+    next_member: AtomicUsize,
+    failed: Notify, // Wakes the health checks early when a command hits a dead connection
+}
+
+enum Target {
+    Standalone {
+        client: Client,
+        config: ConnectionManagerConfig,
+    },
+    Sentinel {
+        sentinel: Mutex<Sentinel>,
+        master: String,
+        node: SentinelNodeConnectionInfo,
+        config: ConnectionManagerConfig,
+    },
+    Cluster {
+        client: ClusterClient,
+        nodes: Vec<Client>, // For subscriptions, which any node can serve
+    },
+}
+
+struct Member {
+    conn: ArcSwap<Connection>,
+    healthy: AtomicBool,
+}
+
+#[derive(Clone)]
+enum Connection {
+    Node(Box<ConnectionManager>),
+    Cluster(ClusterConnection),
+}
+
+impl RedisPool {
+    /// Pool of `size` connections to a single server
+    pub async fn standalone(
+        url: &str,
+        config: ConnectionManagerConfig,
+        size: usize,
+    ) -> RedisResult<Self> {
+        let client = Client::open(url)?;
+        Self::open(Target::Standalone { client, config }, size).await
+    }
+
+    /// Pool of `size` connections to the master the `sentinels` know as
+    /// `master`. Credentials, database and TLS for the master come from
+    /// `master_url`; its host is ignored.
+    pub async fn sentinel(
+        sentinels: &[String],
+        master: &str,
+        master_url: &str,
+        config: ConnectionManagerConfig,
+        size: usize,
+    ) -> RedisResult<Self> {
+        let info = master_url.into_connection_info()?;
+        let tls_mode = match info.addr {
+            ConnectionAddr::TcpTls { insecure: true, .. } => Some(TlsMode::Insecure),
+            ConnectionAddr::TcpTls { .. } => Some(TlsMode::Secure),
+            _ => None,
+        };
+        let target = Target::Sentinel {
+            sentinel: Mutex::new(Sentinel::build(sentinels.to_vec())?),
+            master: master.to_string(),
+            node: SentinelNodeConnectionInfo {
+                tls_mode,
+                redis_connection_info: Some(info.redis),
+            },
+            config,
+        };
+        Self::open(target, size).await
+    }
+
+    /// Pool of `size` connections to the cluster reachable through `nodes`.
+    /// Each connection follows slot moves and replica promotions itself.
+    pub async fn cluster(
+        nodes: &[String],
+        connection_timeout: Duration,
+        response_timeout: Duration,
+        size: usize,
+    ) -> RedisResult<Self> {
+        let client = ClusterClient::builder(nodes.to_vec())
+            .connection_timeout(connection_timeout)
+            .response_timeout(response_timeout)
+            .build()?;
+        let nodes = nodes
+            .iter()
+            .map(|node| Client::open(node.as_str()))
+            .collect::<RedisResult<_>>()?;
+        Self::open(Target::Cluster { client, nodes }, size).await
+    }
+
+    async fn open(target: Target, size: usize) -> RedisResult<Self> {
+        let mut members = Vec::with_capacity(size);
+        for _ in 0..size.max(1) {
+            members.push(Member {
+                conn: ArcSwap::from_pointee(target.connect().await?),
+                healthy: AtomicBool::new(true),
+            });
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                target,
+                members,
+                next_member: AtomicUsize::new(0),
+                failed: Notify::new(),
+            }),
+        })
+    }
+
+    /// Healthy connections take turns. When none is healthy the next one is
+    /// used anyway, so callers see the real error rather than a pool error.
+    fn member(&self) -> &Member {
+        let members = &self.inner.members;
+        let start = self.inner.next_member.fetch_add(1, Ordering::Relaxed);
+        (0..members.len())
+            .map(|offset| &members[(start + offset) % members.len()])
+            .find(|member| member.healthy.load(Ordering::Relaxed))
+            .unwrap_or(&members[start % members.len()])
+    }
+
+    /// Take a connection out of the rotation after a command shows it is
+    /// gone, or pointing at a master that has since been demoted
+    fn observe<T>(&self, member: &Member, result: &RedisResult<T>) {
+        if let Err(error) = result {
+            if is_connection_failure(error) && member.healthy.swap(false, Ordering::Relaxed) {
+                warn!("Redis connection failed ({}); reconnecting", error);
+                self.inner.failed.notify_one();
+            }
+        }
+    }
+
+    /// Check every connection now, reopening the ones that fail
+    pub async fn check_health(&self) {
+        for (index, member) in self.inner.members.iter().enumerate() {
+            let was_healthy = member.healthy.load(Ordering::Relaxed);
+            if was_healthy && ping(&member.conn.load()).await {
+                continue;
+            }
+
+            let healthy = match self.inner.target.connect().await {
+                Ok(conn) if ping(&conn).await => {
+                    member.conn.store(Arc::new(conn));
+                    true
+                }
+                Ok(_) => false,
+                Err(error) => {
+                    if was_healthy {
+                        warn!(
+                            "Redis connection {} could not reconnect: {}",
+                            index + 1,
+                            error
+                        );
+                    }
+                    false
+                }
+            };
+            member.healthy.store(healthy, Ordering::Relaxed);
+            match (was_healthy, healthy) {
+                (false, true) => info!("Redis connection {} restored", index + 1),
+                (true, false) => warn!("Redis connection {} failed its health check", index + 1),
+                _ => {}
+            }
+        }
+    }
+
+    /// Keep checking the connections, and reconnect straight away when a
+    /// command reports a failure
+    pub fn spawn_health_checks(&self) -> JoinHandle<()> {
+        let pool = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = pool.inner.failed.notified() => {}
+                }
+                pool.check_health().await;
+            }
+        })
+    }
+
+    /// Subscribe to `channels`. The subscription is re-established on the
+    /// current master (or another cluster node) whenever its connection is
+    /// lost; messages published in between are missed. Ends when the
+    /// receiver is dropped.
+    pub fn subscribe(&self, channels: Vec<String>) -> mpsc::Receiver<Msg> {
+        let (tx, rx) = mpsc::channel(256);
+        let pool = self.clone();
+        tokio::spawn(async move {
+            let mut attempt = 0usize;
+            while !tx.is_closed() {
+                match pool.inner.target.pubsub_client(attempt).await {
+                    Ok(client) => match client.get_async_pubsub().await {
+                        Ok(mut pubsub) => {
+                            if let Err(error) = pubsub.subscribe(&channels).await {
+                                warn!("Redis subscribe to {:?} failed: {}", channels, error);
+                            } else {
+                                let mut messages = pubsub.into_on_message();
+                                while let Some(msg) = messages.next().await {
+                                    if tx.send(msg).await.is_err() {
+                                        return;
+                                    }
+                                }
+                                warn!("Redis subscription to {:?} lost; resubscribing", channels);
+                            }
+                        }
+                        Err(error) => warn!("Redis subscription connection failed: {}", error),
+                    },
+                    Err(error) => warn!("Redis subscription connection failed: {}", error),
+                }
+                attempt += 1;
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+            }
+        });
+        rx
+    }
+}
+
+impl Target {
+    async fn connect(&self) -> RedisResult<Connection> {
+        match self {
+            Target::Standalone { client, config } => client
+                .get_connection_manager_with_config(config.clone())
+                .await
+                .map(|conn| Connection::Node(Box::new(conn))),
+            Target::Sentinel { config, .. } => self
+                .master()
+                .await?
+                .get_connection_manager_with_config(config.clone())
+                .await
+                .map(|conn| Connection::Node(Box::new(conn))),
+            Target::Cluster { client, .. } => {
+                client.get_async_connection().await.map(Connection::Cluster)
+            }
+        }
+    }
+
+    /// The master's address as the sentinels currently report it
+    async fn master(&self) -> RedisResult<Client> {
+        match self {
+            Target::Sentinel {
+                sentinel,
+                master,
+                node,
+                ..
+            } => {
+                sentinel
+                    .lock()
+                    .await
+                    .async_master_for(master, Some(node))
+                    .await
+            }
+            Target::Standalone { client, .. } => Ok(client.clone()),
+            Target::Cluster { nodes, .. } => Ok(nodes[0].clone()),
+        }
+    }
+
+    /// Server to subscribe on. Cluster nodes are tried in turn, since a
+    /// message published on any node reaches subscribers on every node.
+    async fn pubsub_client(&self, attempt: usize) -> RedisResult<Client> {
+        match self {
+            Target::Cluster { nodes, .. } => Ok(nodes[attempt % nodes.len()].clone()),
+            _ => self.master().await,
+        }
+    }
+}
+
+async fn ping(conn: &Connection) -> bool {
+    let mut conn = conn.clone();
+    matches!(
+        tokio::time::timeout(
+            HEALTH_CHECK_TIMEOUT,
+            redis::cmd("PING").query_async::<String>(&mut conn),
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+/// Errors that mean the connection should be replaced, rather than that the
+/// command itself was wrong. `READONLY` comes from a master that Sentinel
+/// has demoted to a replica.
+fn is_connection_failure(error: &RedisError) -> bool {
+    error.is_io_error()
+        || error.is_connection_dropped()
+        || error.is_timeout()
+        || error.kind() == ErrorKind::ReadOnly
+}
+
+impl ConnectionLike for Connection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        match self {
+            Connection::Node(conn) => conn.req_packed_command(cmd),
+            Connection::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        match self {
+            Connection::Node(conn) => conn.req_packed_commands(cmd, offset, count),
+            Connection::Cluster(conn) => conn.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            Connection::Node(conn) => conn.get_db(),
+            Connection::Cluster(conn) => conn.get_db(),
+        }
+    }
+}
+
+impl ConnectionLike for RedisPool {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a Cmd) -> RedisFuture<'a, Value> {
+        Box::pin(async move {
+            let member = self.member();
+            let mut conn = Connection::clone(&member.conn.load());
+            let result = conn.req_packed_command(cmd).await;
+            self.observe(member, &result);
+            result
+        })
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<Value>> {
+        Box::pin(async move {
+            let member = self.member();
+            let mut conn = Connection::clone(&member.conn.load());
+            let result = conn.req_packed_commands(cmd, offset, count).await;
+            self.observe(member, &result);
+            result
+        })
+    }
+
+    fn get_db(&self) -> i64 {
+        self.inner.members[0].conn.load().get_db()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_failures() {
+        let io = RedisError::from(std::io::Error::from(std::io::ErrorKind::ConnectionReset));
+        assert!(is_connection_failure(&io));
+
+        let demoted = RedisError::from((ErrorKind::ReadOnly, "READONLY"));
+        assert!(is_connection_failure(&demoted));
+
+        let wrong_type = RedisError::from((ErrorKind::TypeError, "WRONGTYPE"));
+        assert!(!is_connection_failure(&wrong_type));
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::errors::AppError;
+
+use super::{db_error, Db, ReadPreference};
+
+/// Patients whose visit ended, matching the partial index of migration 0034
+const LEFT_CARE: &str =
+    "status IN ('discharged', 'transferred', 'deceased', 'left_without_being_seen')";
+
+/// Tables referring to patients, archived under the given keys
+const RELATED_TABLES: [(&str, &str); 11] = [
+    ("vitals", "patient_vitals"),
+    ("triage_assessments", "triage_assessments"),
+    ("medications", "medications"),
+    ("lab_orders", "lab_orders"),
+    ("lab_results", "lab_results"),
+    ("attachments", "patient_attachments"),
+    ("consents", "patient_consents"),
+    ("handovers", "handover_reports"),
+    ("transfers", "patient_transfers"),
+    ("discharge_summaries", "discharge_summaries"),
+    ("events", "patient_events"),
+];
+
+/// Tables whose rows are not deleted with their patient, in deletion order
+const UNCASCADED_TABLES: [&str; 5] = [
+    "lab_results",
+    "lab_orders",
+    "patient_attachments",
+    "handover_reports",
+    "patient_consents",
+];
+
+/// Archiving and purging for the retention job. Works across hospitals, so
+/// it is meant for the system context.
+#[derive(Clone)]
+pub struct RetentionRepository {
+    db: Db,
+}
+
+impl RetentionRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Count the patients whose visit ended before `before`
+    pub async fn count_patients_due(
+        &self,
+        ctx: &RequestCtx,
+        before: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let query = format!(
+            "SELECT COUNT(*) FROM patients WHERE {} AND updated_at < $1",
+            LEFT_CARE
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_scalar(&query)
+            .bind(before)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Move up to `limit` patients whose visit ended before `before` into
+    /// `archived_patients`, with the rows of every related table, and
+    /// delete them from the live tables. Returns how many were archived.
+    /// Patients locked by a concurrent run are left to it.
+    pub async fn archive_patients(
+        &self,
+        ctx: &RequestCtx,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let ids: Vec<Uuid> = sqlx::query_scalar(&format!(
+            "SELECT id FROM patients WHERE {} AND updated_at < $1 \
+             ORDER BY updated_at LIMIT $2 FOR UPDATE SKIP LOCKED",
+            LEFT_CARE
+        ))
+        .bind(before)
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let related: Vec<String> = RELATED_TABLES
+            .iter()
+            .map(|(key, table)| {
+                format!(
+                    "'{}', (SELECT COALESCE(jsonb_agg(to_jsonb(t)), '[]'::jsonb) \
+                     FROM {} t WHERE t.patient_id = p.id)",
+                    key, table
+                )
+            })
+            .collect();
+        sqlx::query(&format!(
+            "INSERT INTO archived_patients \
+             (id, hospital_id, patient_number, status, left_care_at, record) \
+             SELECT p.id, p.hospital_id, p.patient_number, p.status, p.updated_at, \
+             jsonb_build_object('patient', to_jsonb(p), {}) \
+             FROM patients p WHERE p.id = ANY($1)",
+            related.join(", ")
+        ))
+        .bind(&ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        // The remaining related tables cascade from patients
+        for table in UNCASCADED_TABLES {
+            sqlx::query(&format!("DELETE FROM {} WHERE patient_id = ANY($1)", table))
+                .bind(&ids)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| db_error(ctx, e))?;
+        }
+        let archived = sqlx::query("DELETE FROM patients WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .rows_affected();
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(archived)
+    }
+
+    /// Count the vitals recorded before `before`
+    pub async fn count_vitals_due(
+        &self,
+        ctx: &RequestCtx,
+        before: DateTime<Utc>,
+    ) -> Result<i64, AppError> {
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_scalar("SELECT COUNT(*) FROM patient_vitals WHERE recorded_at < $1")
+            .bind(before)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Delete up to `limit` vitals recorded before `before`, returning how
+    /// many were deleted
+    pub async fn purge_vitals(
+        &self,
+        ctx: &RequestCtx,
+        before: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<u64, AppError> {
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        let purged = sqlx::query(
+            "DELETE FROM patient_vitals WHERE id IN (SELECT id FROM patient_vitals \
+             WHERE recorded_at < $1 ORDER BY recorded_at LIMIT $2 FOR UPDATE SKIP LOCKED)",
+        )
+        .bind(before)
+        .bind(limit)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?
+        .rows_affected();
+        Ok(purged)
+    }
+}
@@ -0,0 +1,82 @@
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::ServiceAccount;
+use lib_types::errors::AppError;
+
+use super::{db_error, Db};
+
+const SERVICE_ACCOUNT_COLUMNS: &str = "id, client_id, client_secret_hash, name, hospital_id, \
+     scopes, is_active, created_at, updated_at, last_used_at";
+
+/// Data access for service accounts
+#[derive(Clone)]
+pub struct ServiceAccountRepository {
+    db: Db,
+}
+
+impl ServiceAccountRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Find a service account by client id
+    pub async fn find_by_client_id(
+        &self,
+        ctx: &RequestCtx,
+        client_id: &str,
+    ) -> Result<Option<ServiceAccount>, AppError> {
+        let query = format!(
+            "SELECT {} FROM service_accounts WHERE client_id = $1",
+            SERVICE_ACCOUNT_COLUMNS
+        );
+
+        sqlx::query_as::<_, ServiceAccount>(&query)
+            .bind(client_id)
+            .fetch_optional(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Insert a new service account, recording the caller as its creator
+    pub async fn create(&self, ctx: &RequestCtx, account: &ServiceAccount) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO service_accounts (id, client_id, client_secret_hash, name, hospital_id, \
+             scopes, is_active, created_at, updated_at, created_by) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+        )
+        .bind(account.id)
+        .bind(&account.client_id)
+        .bind(&account.client_secret_hash)
+        .bind(&account.name)
+        .bind(account.hospital_id)
+        .bind(&account.scopes)
+        .bind(account.is_active)
+        .bind(account.created_at)
+        .bind(account.updated_at)
+        .bind(ctx.user_id())
+        .execute(self.db.primary())
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                AppError::Conflict {
+                    message: "client_id already in use".to_string(),
+                }
+            }
+            _ => db_error(ctx, e),
+        })?;
+
+        Ok(())
+    }
+
+    /// Record that the account has just been issued a token
+    pub async fn touch_last_used(&self, ctx: &RequestCtx, id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE service_accounts SET last_used_at = NOW() WHERE id = $1")
+            .bind(id)
+            .execute(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,285 @@
+use sqlx::QueryBuilder;
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{
+    CursorPage, PageRequest, StaffRosterEntry, StaffRosterQuery, StaffSortField,
+};
+use lib_types::entities::MedicalStaff;
+use lib_types::enums::AvailabilityStatus;
+use lib_types::errors::AppError;
+use lib_types::ids::{HospitalId, UserId};
+
+use super::query_metrics::Observe;
+use super::{db_error, push_page, Db, ReadPreference};
+
+const STAFF_COLUMNS: &str = "id, user_id, hospital_id, staff_id, specialty, availability_status, \
+     license_number, certifications, shift_schedule, department, seniority_level, created_at, \
+     updated_at, deleted_at, deleted_by, created_by, updated_by";
+
+/// Data access for the clinical staff of hospitals. Soft-deleted staff are
+/// left out of reads unless the repository is built `with_deleted`.
+#[derive(Clone)]
+pub struct StaffRepository {
+    db: Db,
+    include_deleted: bool,
+}
+
+impl StaffRepository {
+    pub fn new(db: Db) -> Self {
+        Self {
+            db,
+            include_deleted: false,
+        }
+    }
+
+    /// Include soft-deleted staff in reads
+    pub fn with_deleted(mut self) -> Self {
+        self.include_deleted = true;
+        self
+    }
+
+    pub async fn find_by_id(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+    ) -> Result<Option<MedicalStaff>, AppError> {
+        let query = format!(
+            "SELECT {} FROM medical_staff WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             AND ($3 OR deleted_at IS NULL)",
+            STAFF_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, MedicalStaff>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .bind(self.include_deleted)
+            .fetch_optional(&mut *conn)
+            .observe(self.db.metrics(), ctx, "staff.find_by_id")
+            .await
+    }
+
+    /// Find the staff record of a user; each user has at most one
+    pub async fn find_by_user(
+        &self,
+        ctx: &RequestCtx,
+        user_id: UserId,
+    ) -> Result<Option<MedicalStaff>, AppError> {
+        let query = format!(
+            "SELECT {} FROM medical_staff WHERE user_id = $1 \
+             AND ($2::uuid IS NULL OR hospital_id = $2) AND ($3 OR deleted_at IS NULL)",
+            STAFF_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, MedicalStaff>(&query)
+            .bind(user_id)
+            .bind(ctx.tenant_hospital_id())
+            .bind(self.include_deleted)
+            .fetch_optional(&mut *conn)
+            .observe(self.db.metrics(), ctx, "staff.find_by_user")
+            .await
+    }
+
+    /// Onboard a user as staff. The user must belong to the same hospital
+    /// and can only have one staff record.
+    pub async fn create(&self, ctx: &RequestCtx, staff: &MedicalStaff) -> Result<(), AppError> {
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        let user_hospital_id =
+            sqlx::query_scalar::<_, HospitalId>("SELECT hospital_id FROM users WHERE id = $1")
+                .bind(staff.user_id)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| db_error(ctx, e))?
+                .ok_or_else(|| AppError::not_found("User"))?;
+        if user_hospital_id != staff.hospital_id {
+            return Err(AppError::validation_error(
+                "user_id",
+                "User belongs to another hospital",
+            ));
+        }
+
+        sqlx::query(
+            "INSERT INTO medical_staff (id, user_id, hospital_id, staff_id, specialty, \
+             availability_status, license_number, certifications, shift_schedule, department, \
+             seniority_level, created_at, updated_at, created_by, updated_by) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $14)",
+        )
+        .bind(staff.id)
+        .bind(staff.user_id)
+        .bind(staff.hospital_id)
+        .bind(&staff.staff_id)
+        .bind(&staff.specialty)
+        .bind(staff.availability_status)
+        .bind(&staff.license_number)
+        .bind(&staff.certifications)
+        .bind(&staff.shift_schedule)
+        .bind(&staff.department)
+        .bind(staff.seniority_level)
+        .bind(staff.created_at)
+        .bind(staff.updated_at)
+        .bind(ctx.user_id())
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                AppError::Conflict {
+                    message: format!(
+                        "Staff ID {} or the user is already registered",
+                        staff.staff_id
+                    ),
+                }
+            }
+            _ => db_error(ctx, e),
+        })?;
+
+        Ok(())
+    }
+
+    /// Change the availability of a staff member
+    pub async fn update_availability(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        status: AvailabilityStatus,
+    ) -> Result<MedicalStaff, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM medical_staff WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             AND deleted_at IS NULL FOR UPDATE",
+            STAFF_COLUMNS
+        );
+        let mut staff = sqlx::query_as::<_, MedicalStaff>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or_else(|| AppError::not_found("Staff member"))?;
+
+        staff.update_availability(status);
+        staff.updated_by = ctx.user_id();
+        sqlx::query(
+            "UPDATE medical_staff SET availability_status = $2, updated_at = $3, \
+             updated_by = $4 WHERE id = $1",
+        )
+        .bind(staff.id)
+        .bind(staff.availability_status)
+        .bind(staff.updated_at)
+        .bind(staff.updated_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(staff)
+    }
+
+    /// List the staff of a hospital, optionally of one department or only
+    /// those who can take an assignment
+    pub async fn roster(
+        &self,
+        ctx: &RequestCtx,
+        hospital_id: HospitalId,
+        query: &StaffRosterQuery,
+    ) -> Result<Vec<MedicalStaff>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM medical_staff \
+             WHERE hospital_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             AND ($3::text IS NULL OR lower(department) = lower($3)) \
+             AND (NOT $4 OR availability_status IN ('available', 'on_call')) \
+             AND ($5 OR deleted_at IS NULL) \
+             ORDER BY staff_id",
+            STAFF_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, MedicalStaff>(&sql)
+            .bind(hospital_id)
+            .bind(ctx.tenant_hospital_id())
+            .bind(query.department.as_deref().map(str::trim))
+            .bind(query.available_only)
+            .bind(self.include_deleted)
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "staff.roster")
+            .await
+    }
+
+    /// List the staff of a hospital one page at a time, from a replica when
+    /// one is healthy
+    pub async fn list(
+        &self,
+        ctx: &RequestCtx,
+        hospital_id: HospitalId,
+        page: &PageRequest<StaffSortField>,
+    ) -> Result<CursorPage<StaffRosterEntry>, AppError> {
+        let mut select = QueryBuilder::new(format!(
+            "SELECT {} FROM medical_staff WHERE hospital_id = ",
+            STAFF_COLUMNS
+        ));
+        select.push_bind(hospital_id);
+        if let Some(tenant_id) = ctx.tenant_hospital_id() {
+            select.push(" AND hospital_id = ").push_bind(tenant_id);
+        }
+        if !self.include_deleted {
+            select.push(" AND deleted_at IS NULL");
+        }
+        push_page(&mut select, page)?;
+
+        let mut conn = self
+            .db
+            .acquire_for(ctx, ReadPreference::PreferReplica)
+            .await?;
+        let staff = select
+            .build_query_as::<MedicalStaff>()
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "staff.list")
+            .await?;
+
+        Ok(CursorPage::from_rows(
+            staff,
+            page,
+            StaffRosterEntry::from_staff,
+        ))
+    }
+
+    /// Remove a staff member from rosters and lists, keeping the record so
+    /// it can be restored
+    pub async fn soft_delete(&self, ctx: &RequestCtx, id: Uuid) -> Result<MedicalStaff, AppError> {
+        self.set_deleted(ctx, id, true).await
+    }
+
+    /// Bring back a soft-deleted staff member
+    pub async fn restore(&self, ctx: &RequestCtx, id: Uuid) -> Result<MedicalStaff, AppError> {
+        self.set_deleted(ctx, id, false).await
+    }
+
+    async fn set_deleted(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        deleted: bool,
+    ) -> Result<MedicalStaff, AppError> {
+        let query = format!(
+            "UPDATE medical_staff SET deleted_at = CASE WHEN $2 THEN NOW() END, \
+             deleted_by = CASE WHEN $2 THEN $3 END, updated_at = NOW(), updated_by = $3 \
+             WHERE id = $1 AND ($4::uuid IS NULL OR hospital_id = $4) \
+             AND (deleted_at IS NULL) = $2 \
+             RETURNING {}",
+            STAFF_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, MedicalStaff>(&query)
+            .bind(id)
+            .bind(deleted)
+            .bind(ctx.user_id())
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or_else(|| AppError::not_found("Staff member"))
+    }
+}
@@ -0,0 +1,87 @@
+use sqlx::pool::PoolConnection;
+use sqlx::{PgConnection, Postgres, Transaction};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::errors::AppError;
+
+use super::{db_error, Db, ReadPreference};
+
+/// Setting read by the row-level security policies (migration 0032). Empty
+/// or unset lets a connection see every hospital's rows.
+const TENANT_SETTING: &str = "app.tenant_hospital_id";
+
+/// Scope `conn` to the caller's hospital, or to every hospital for admins
+/// and system work. A `local` scope ends with the current transaction;
+/// otherwise it lasts until the connection is scoped again.
+pub(crate) async fn scope(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    local: bool,
+) -> Result<(), AppError> {
+    let tenant = ctx
+        .tenant_hospital_id()
+        .map(|id| id.to_string())
+        .unwrap_or_default();
+    set_tenant(conn, ctx, &tenant, local).await
+}
+
+/// Let the rest of the current transaction reach every hospital's rows.
+/// Only for work that crosses hospitals by design, after the caller's
+/// access has been checked under its own scope.
+pub(crate) async fn lift(conn: &mut PgConnection, ctx: &RequestCtx) -> Result<(), AppError> {
+    set_tenant(conn, ctx, "", true).await
+}
+
+async fn set_tenant(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    tenant: &str,
+    local: bool,
+) -> Result<(), AppError> {
+    sqlx::query("SELECT set_config($1, $2, $3)")
+        .bind(TENANT_SETTING)
+        .bind(tenant)
+        .bind(local)
+        .execute(conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+    Ok(())
+}
+
+impl Db {
+    /// A pooled connection scoped to the caller's hospital. Pooled
+    /// connections keep the scope of their last user, so every statement on
+    /// a tenant table must run on a connection from here or `begin_for`.
+    /// Taking the connection follows the retry and circuit breaker policy.
+    pub async fn acquire_for(
+        &self,
+        ctx: &RequestCtx,
+        preference: ReadPreference,
+    ) -> Result<PoolConnection<Postgres>, AppError> {
+        let pool = self.reader(preference);
+        let mut conn = self
+            .policy()
+            .run(ctx, || async {
+                pool.acquire().await.map_err(|e| db_error(ctx, e))
+            })
+            .await?;
+        scope(&mut conn, ctx, false).await?;
+        Ok(conn)
+    }
+
+    /// A transaction on the primary scoped to the caller's hospital, begun
+    /// under the retry and circuit breaker policy
+    pub async fn begin_for(
+        &self,
+        ctx: &RequestCtx,
+    ) -> Result<Transaction<'static, Postgres>, AppError> {
+        let mut tx = self
+            .policy()
+            .run(ctx, || async {
+                self.primary().begin().await.map_err(|e| db_error(ctx, e))
+            })
+            .await?;
+        scope(&mut tx, ctx, true).await?;
+        Ok(tx)
+    }
+}
@@ -0,0 +1,343 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::LiveEvent;
+use lib_types::entities::{Patient, PatientEvent, PatientTransfer};
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+use super::live_events::notify;
+use super::outbox_repository::enqueue_status_change;
+use super::patient_event_repository::record_event;
+use super::query_metrics::Observe;
+use super::tenancy;
+use super::{db_error, Db, ReadPreference, PATIENT_COLUMNS};
+
+const TRANSFER_COLUMNS: &str = "id, patient_id, origin_hospital_id, destination_hospital_id, \
+     reason, status, requested_by, responded_by, rejection_reason, ambulance_id, requested_at, \
+     responded_at, completed_at, updated_at";
+
+/// Data access for inter-hospital patient transfers. A transfer is visible
+/// to both the origin and the destination hospital.
+#[derive(Clone)]
+pub struct TransferRepository {
+    db: Db,
+}
+
+impl TransferRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Find a transfer by id among those to or from the caller's hospital
+    pub async fn find_by_id(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+    ) -> Result<Option<PatientTransfer>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patient_transfers WHERE id = $1 \
+             AND ($2::uuid IS NULL OR $2 IN (origin_hospital_id, destination_hospital_id))",
+            TRANSFER_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, PatientTransfer>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *conn)
+            .observe(self.db.metrics(), ctx, "transfers.find_by_id")
+            .await
+    }
+
+    /// List a patient's transfers, newest first
+    pub async fn list_for_patient(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<PatientTransfer>, AppError> {
+        let query = format!(
+            "SELECT {} FROM patient_transfers WHERE patient_id = $1 \
+             AND ($2::uuid IS NULL OR $2 IN (origin_hospital_id, destination_hospital_id)) \
+             ORDER BY requested_at DESC",
+            TRANSFER_COLUMNS
+        );
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, PatientTransfer>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "transfers.list_for_patient")
+            .await
+    }
+
+    /// Request the transfer of a patient of the caller's hospital. A patient
+    /// can only have one open transfer at a time.
+    pub async fn create(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        destination_hospital_id: HospitalId,
+        reason: String,
+        requested_by: UserId,
+    ) -> Result<PatientTransfer, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR UPDATE",
+            PATIENT_COLUMNS
+        );
+        let patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        let destination_exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM hospitals WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(destination_hospital_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+        if !destination_exists {
+            return Err(AppError::not_found("Hospital"));
+        }
+
+        let transfer =
+            PatientTransfer::request(&patient, destination_hospital_id, reason, requested_by)?;
+
+        sqlx::query(
+            "INSERT INTO patient_transfers (id, patient_id, origin_hospital_id, \
+             destination_hospital_id, reason, status, requested_by, requested_at, updated_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+        )
+        .bind(transfer.id)
+        .bind(transfer.patient_id)
+        .bind(transfer.origin_hospital_id)
+        .bind(transfer.destination_hospital_id)
+        .bind(&transfer.reason)
+        .bind(transfer.status)
+        .bind(transfer.requested_by)
+        .bind(transfer.requested_at)
+        .bind(transfer.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                PatientError::TransferFailed {
+                    reason: "patient already has an open transfer".to_string(),
+                }
+                .into()
+            }
+            _ => db_error(ctx, e),
+        })?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(transfer)
+    }
+
+    /// Accept a transfer, optionally assigning an available ambulance of the
+    /// origin or destination hospital to carry the patient
+    pub async fn accept(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        accepted_by: UserId,
+        ambulance_id: Option<AmbulanceId>,
+    ) -> Result<PatientTransfer, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut transfer = lock_transfer(&mut tx, ctx, id).await?;
+        if let Some(ambulance_id) = ambulance_id {
+            ensure_ambulance_available(&mut tx, ctx, &transfer, ambulance_id).await?;
+        }
+        transfer.accept(accepted_by, ambulance_id)?;
+        save_transfer(&mut tx, ctx, &transfer).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(transfer)
+    }
+
+    /// Decline a transfer
+    pub async fn reject(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+        rejected_by: UserId,
+        reason: String,
+    ) -> Result<PatientTransfer, AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut transfer = lock_transfer(&mut tx, ctx, id).await?;
+        transfer.reject(rejected_by, reason)?;
+        save_transfer(&mut tx, ctx, &transfer).await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(transfer)
+    }
+
+    /// Record the patient's arrival: the patient moves to the destination
+    /// hospital and the bed they held at the origin goes to cleaning
+    pub async fn complete(
+        &self,
+        ctx: &RequestCtx,
+        id: Uuid,
+    ) -> Result<(PatientTransfer, Patient), AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let mut transfer = lock_transfer(&mut tx, ctx, id).await?;
+        // The patient and their bed still belong to the origin, so the
+        // caller's tenancy was already checked through the transfer
+        tenancy::lift(&mut tx, ctx).await?;
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 FOR UPDATE",
+            PATIENT_COLUMNS
+        );
+        let mut patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(transfer.patient_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound {
+                patient_id: transfer.patient_id,
+            })?;
+
+        let origin_bed = patient.bed_id;
+        let previous_status = patient.status;
+        let previous_staff = patient.assigned_staff_id;
+        let previous_ambulance = patient.ambulance_id;
+        transfer.complete(&mut patient)?;
+
+        if let Some(bed_id) = origin_bed {
+            sqlx::query(
+                "UPDATE beds SET status = 'cleaning', current_patient_id = NULL, \
+                 updated_at = NOW(), updated_by = $3, version = version + 1 \
+                 WHERE id = $1 AND current_patient_id = $2",
+            )
+            .bind(bed_id)
+            .bind(patient.id)
+            .bind(ctx.user_id())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        }
+
+        patient.updated_by = ctx.user_id();
+        patient.version = sqlx::query_scalar(
+            "UPDATE patients SET hospital_id = $2, status = $3, bed_id = $4, \
+             assigned_staff_id = $5, ambulance_id = $6, updated_at = $7, updated_by = $8, \
+             version = version + 1 WHERE id = $1 RETURNING version",
+        )
+        .bind(patient.id)
+        .bind(patient.hospital_id)
+        .bind(patient.status)
+        .bind(patient.bed_id)
+        .bind(patient.assigned_staff_id)
+        .bind(patient.ambulance_id)
+        .bind(patient.updated_at)
+        .bind(patient.updated_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+        save_transfer(&mut tx, ctx, &transfer).await?;
+        enqueue_status_change(&mut tx, ctx, &patient, previous_status).await?;
+        let actor = ctx.user_id();
+        for event in [
+            PatientEvent::status_changed(&patient, previous_status, actor)
+                .with_reason(transfer.reason.clone()),
+            PatientEvent::bed_assigned(&patient, origin_bed, actor),
+            PatientEvent::staff_assigned(&patient, previous_staff, actor),
+            PatientEvent::ambulance_assigned(&patient, previous_ambulance, actor),
+        ] {
+            record_event(&mut tx, ctx, &event).await?;
+        }
+        notify(
+            &mut tx,
+            ctx,
+            &LiveEvent::patient_status_changed(&patient, previous_status),
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok((transfer, patient))
+    }
+}
+
+/// Load a transfer to or from the caller's hospital and lock it for the transaction
+async fn lock_transfer(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    id: Uuid,
+) -> Result<PatientTransfer, AppError> {
+    let query = format!(
+        "SELECT {} FROM patient_transfers WHERE id = $1 \
+         AND ($2::uuid IS NULL OR $2 IN (origin_hospital_id, destination_hospital_id)) \
+         FOR UPDATE",
+        TRANSFER_COLUMNS
+    );
+
+    sqlx::query_as::<_, PatientTransfer>(&query)
+        .bind(id)
+        .bind(ctx.tenant_hospital_id())
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| db_error(ctx, e))?
+        .ok_or_else(|| AppError::not_found("Transfer"))
+}
+
+async fn save_transfer(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    transfer: &PatientTransfer,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE patient_transfers SET status = $2, responded_by = $3, rejection_reason = $4, \
+         ambulance_id = $5, responded_at = $6, completed_at = $7, updated_at = $8 WHERE id = $1",
+    )
+    .bind(transfer.id)
+    .bind(transfer.status)
+    .bind(transfer.responded_by)
+    .bind(&transfer.rejection_reason)
+    .bind(transfer.ambulance_id)
+    .bind(transfer.responded_at)
+    .bind(transfer.completed_at)
+    .bind(transfer.updated_at)
+    .execute(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+
+    Ok(())
+}
+
+/// Check that the ambulance belongs to one of the two hospitals and is free
+async fn ensure_ambulance_available(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    transfer: &PatientTransfer,
+    ambulance_id: AmbulanceId,
+) -> Result<(), AppError> {
+    let available: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM ambulances WHERE id = $1 AND status = 'available' \
+         AND hospital_id IN ($2, $3))",
+    )
+    .bind(ambulance_id)
+    .bind(transfer.origin_hospital_id)
+    .bind(transfer.destination_hospital_id)
+    .fetch_one(conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+
+    if !available {
+        return Err(PatientError::TransferFailed {
+            reason: "ambulance is not available".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use sqlx::{Connection, PgConnection};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{LiveEvent, RetriageRequest};
+use lib_types::entities::{Patient, PatientEvent, TriageAssessment};
+use lib_types::errors::{AppError, PatientError};
+use lib_types::ids::{PatientId, UserId};
+
+use super::live_events::notify;
+use super::patient_event_repository::record_event;
+use super::{db_error, Db, DbExecutor, ReadPreference, PATIENT_COLUMNS};
+
+const ASSESSMENT_COLUMNS: &str = "id, patient_id, hospital_id, triage_level, previous_level, \
+     rationale, assessed_by, assessed_at";
+
+/// Data access for the triage history of patients
+#[derive(Clone)]
+pub struct TriageRepository {
+    exec: DbExecutor,
+}
+
+impl TriageRepository {
+    pub fn new(db: Db) -> Self {
+        Self { exec: db.into() }
+    }
+
+    /// Share the executor of another repository, and so its transaction
+    pub(super) fn with_executor(exec: DbExecutor) -> Self {
+        Self { exec }
+    }
+
+    /// List every (re)triage of a patient, oldest first
+    pub async fn history(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+    ) -> Result<Vec<TriageAssessment>, AppError> {
+        let query = format!(
+            "SELECT {} FROM triage_assessments \
+             WHERE patient_id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             ORDER BY assessed_at",
+            ASSESSMENT_COLUMNS
+        );
+
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        sqlx::query_as::<_, TriageAssessment>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_all(&mut *conn)
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Re-triage a patient, storing the assessment and updating the
+    /// patient's latest triage level together
+    pub async fn retriage(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        request: &RetriageRequest,
+        assessed_by: UserId,
+    ) -> Result<(Patient, TriageAssessment), AppError> {
+        let mut conn = self.exec.acquire(ctx, ReadPreference::Primary).await?;
+        let mut tx = conn.begin().await.map_err(|e| db_error(ctx, e))?;
+
+        let query = format!(
+            "SELECT {} FROM patients WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2) \
+             FOR UPDATE",
+            PATIENT_COLUMNS
+        );
+        let mut patient = sqlx::query_as::<_, Patient>(&query)
+            .bind(patient_id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?
+            .ok_or(PatientError::NotFound { patient_id })?;
+
+        let assessment = patient.retriage(
+            request.triage_level,
+            request.rationale.trim().to_string(),
+            assessed_by,
+        )?;
+        insert_assessment(&mut tx, ctx, &assessment).await?;
+
+        patient.updated_by = ctx.user_id();
+        patient.version = sqlx::query_scalar(
+            "UPDATE patients SET triage_level = $2, updated_at = $3, updated_by = $4, \
+             version = version + 1 WHERE id = $1 RETURNING version",
+        )
+        .bind(patient.id)
+        .bind(patient.triage_level)
+        .bind(patient.updated_at)
+        .bind(patient.updated_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+        if let Some(previous) = assessment.previous_level {
+            let event = PatientEvent::triage_changed(&patient, previous, ctx.user_id())
+                .with_reason(assessment.rationale.clone());
+            record_event(&mut tx, ctx, &event).await?;
+        }
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok((patient, assessment))
+    }
+}
+
+/// Store an assessment and announce it to live dashboards
+pub(super) async fn insert_assessment(
+    conn: &mut PgConnection,
+    ctx: &RequestCtx,
+    assessment: &TriageAssessment,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO triage_assessments (id, patient_id, hospital_id, triage_level, \
+         previous_level, rationale, assessed_by, assessed_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(assessment.id)
+    .bind(assessment.patient_id)
+    .bind(assessment.hospital_id)
+    .bind(assessment.triage_level)
+    .bind(assessment.previous_level)
+    .bind(&assessment.rationale)
+    .bind(assessment.assessed_by)
+    .bind(assessment.assessed_at)
+    .execute(&mut *conn)
+    .await
+    .map_err(|e| db_error(ctx, e))?;
+    notify(conn, ctx, &LiveEvent::patient_triaged(assessment)).await?;
+
+    Ok(())
+}
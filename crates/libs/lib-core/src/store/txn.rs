@@ -0,0 +1,158 @@
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use sqlx::pool::PoolConnection;
+use sqlx::{PgConnection, Postgres, Transaction};
+use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::errors::AppError;
+
+use super::{db_error, Db, QueryMetrics, ReadPreference};
+
+/// A database transaction shared by several repositories, so that work
+/// spanning tables (an admission touches the patient, the bed and the
+/// hospital) commits or rolls back as one.
+///
+/// Repositories built with `in_txn` run their statements inside it; a
+/// repository method that needs its own transaction gets a savepoint. A
+/// `Txn` dropped without `commit` rolls back.
+#[derive(Clone)]
+pub struct Txn {
+    tx: Arc<Mutex<Option<Transaction<'static, Postgres>>>>,
+    metrics: QueryMetrics,
+}
+
+impl Txn {
+    /// Begin a transaction scoped to the caller's hospital
+    pub async fn begin(db: &Db, ctx: &RequestCtx) -> Result<Self, AppError> {
+        Ok(Self {
+            tx: Arc::new(Mutex::new(Some(db.begin_for(ctx).await?))),
+            metrics: db.metrics().clone(),
+        })
+    }
+
+    pub async fn commit(self) -> Result<(), sqlx::Error> {
+        match self.tx.lock().await.take() {
+            Some(tx) => tx.commit().await,
+            None => Err(finished()),
+        }
+    }
+
+    pub async fn rollback(self) -> Result<(), sqlx::Error> {
+        match self.tx.lock().await.take() {
+            Some(tx) => tx.rollback().await,
+            None => Err(finished()),
+        }
+    }
+
+    /// The transaction's connection, held until the guard is dropped
+    async fn conn(&self) -> Result<MappedMutexGuard<'_, PgConnection>, sqlx::Error> {
+        MutexGuard::try_map(self.tx.lock().await, |tx| tx.as_deref_mut()).map_err(|_| finished())
+    }
+}
+
+fn finished() -> sqlx::Error {
+    sqlx::Error::Protocol("transaction has already been committed or rolled back".to_string())
+}
+
+impl Db {
+    /// Run `work` in a transaction: committed when it returns `Ok`, rolled
+    /// back when it returns an error
+    pub async fn transaction<F, Fut, T>(&self, ctx: &RequestCtx, work: F) -> Result<T, AppError>
+    where
+        F: FnOnce(Txn) -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let txn = Txn::begin(self, ctx).await?;
+        match work(txn.clone()).await {
+            Ok(value) => {
+                txn.commit().await.map_err(|e| db_error(ctx, e))?;
+                Ok(value)
+            }
+            Err(error) => {
+                // A failed rollback leaves nothing to undo: the connection
+                // is discarded and the server aborts the transaction
+                let _ = txn.rollback().await;
+                Err(error)
+            }
+        }
+    }
+}
+
+/// Where a repository runs its statements: on the pools, or inside a
+/// shared transaction
+#[derive(Clone)]
+pub enum DbExecutor {
+    Pool(Db),
+    Txn(Txn),
+}
+
+impl DbExecutor {
+    /// A connection for one repository operation, scoped to the caller's
+    /// hospital. Inside a transaction every read goes to the transaction,
+    /// so it sees its own writes.
+    pub(crate) async fn acquire(
+        &self,
+        ctx: &RequestCtx,
+        preference: ReadPreference,
+    ) -> Result<DbConn<'_>, AppError> {
+        match self {
+            DbExecutor::Pool(db) => db
+                .acquire_for(ctx, preference)
+                .await
+                .map(|c| DbConn::Pool(Box::new(c))),
+            DbExecutor::Txn(txn) => txn
+                .conn()
+                .await
+                .map(DbConn::Txn)
+                .map_err(|e| db_error(ctx, e)),
+        }
+    }
+
+    pub(crate) fn metrics(&self) -> &QueryMetrics {
+        match self {
+            DbExecutor::Pool(db) => db.metrics(),
+            DbExecutor::Txn(txn) => &txn.metrics,
+        }
+    }
+}
+
+impl From<Db> for DbExecutor {
+    fn from(db: Db) -> Self {
+        DbExecutor::Pool(db)
+    }
+}
+
+impl From<&Txn> for DbExecutor {
+    fn from(txn: &Txn) -> Self {
+        DbExecutor::Txn(txn.clone())
+    }
+}
+
+/// A connection from [`DbExecutor::acquire`]
+pub(crate) enum DbConn<'a> {
+    Pool(Box<PoolConnection<Postgres>>),
+    Txn(MappedMutexGuard<'a, PgConnection>),
+}
+
+impl Deref for DbConn<'_> {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            DbConn::Pool(conn) => conn,
+            DbConn::Txn(conn) => conn,
+        }
+    }
+}
+
+impl DerefMut for DbConn<'_> {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            DbConn::Pool(conn) => conn,
+            DbConn::Txn(conn) => conn,
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use lib_auth::ctx::RequestCtx;
+use lib_types::entities::User;
+use lib_types::errors::AppError;
+use lib_types::ids::UserId;
+
+use super::{db_error, Db};
+
+const USER_COLUMNS: &str = "id, username, email, password_hash, role, hospital_id, \
+     first_name, last_name, phone_number, is_active, created_at, updated_at";
+
+/// Data access for staff user accounts
+#[derive(Clone)]
+pub struct UserRepository {
+    db: Db,
+}
+
+impl UserRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Find a user by username (case-insensitive) within the caller's hospital
+    pub async fn find_by_username(
+        &self,
+        ctx: &RequestCtx,
+        username: &str,
+    ) -> Result<Option<User>, AppError> {
+        let query = format!(
+            "SELECT {} FROM users WHERE lower(username) = lower($1) \
+             AND ($2::uuid IS NULL OR hospital_id = $2)",
+            USER_COLUMNS
+        );
+
+        sqlx::query_as::<_, User>(&query)
+            .bind(username)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Find a user by id within the caller's hospital
+    pub async fn find_by_id(&self, ctx: &RequestCtx, id: UserId) -> Result<Option<User>, AppError> {
+        let query = format!(
+            "SELECT {} FROM users WHERE id = $1 AND ($2::uuid IS NULL OR hospital_id = $2)",
+            USER_COLUMNS
+        );
+
+        sqlx::query_as::<_, User>(&query)
+            .bind(id)
+            .bind(ctx.tenant_hospital_id())
+            .fetch_optional(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Insert a new user, recording the caller as its creator
+    pub async fn create(&self, ctx: &RequestCtx, user: &User) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO users (id, username, email, password_hash, role, hospital_id, \
+             first_name, last_name, phone_number, is_active, created_at, updated_at, \
+             created_by, updated_by) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $13)",
+        )
+        .bind(user.id)
+        .bind(&user.username)
+        .bind(&user.email)
+        .bind(&user.password_hash)
+        .bind(user.role)
+        .bind(user.hospital_id)
+        .bind(&user.first_name)
+        .bind(&user.last_name)
+        .bind(&user.phone_number)
+        .bind(user.is_active)
+        .bind(user.created_at)
+        .bind(user.updated_at)
+        .bind(ctx.user_id())
+        .execute(self.db.primary())
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                AppError::Conflict {
+                    message: "Username or email already in use".to_string(),
+                }
+            }
+            _ => db_error(ctx, e),
+        })?;
+
+        Ok(())
+    }
+
+    /// Get up to `limit` previous password hashes, newest first
+    pub async fn password_history(
+        &self,
+        ctx: &RequestCtx,
+        user_id: UserId,
+        limit: usize,
+    ) -> Result<Vec<String>, AppError> {
+        sqlx::query_scalar::<_, String>(
+            "SELECT password_hash FROM password_history \
+             WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2",
+        )
+        .bind(user_id)
+        .bind(limit as i64)
+        .fetch_all(self.db.primary())
+        .await
+        .map_err(|e| db_error(ctx, e))
+    }
+
+    /// Replace a user's password, moving the old hash into the history and
+    /// keeping at most `history_size` entries
+    pub async fn update_password(
+        &self,
+        ctx: &RequestCtx,
+        user_id: UserId,
+        password_hash: &str,
+        history_size: usize,
+    ) -> Result<(), AppError> {
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        sqlx::query(
+            "INSERT INTO password_history (user_id, password_hash) \
+             SELECT id, password_hash FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        sqlx::query(
+            "UPDATE users SET password_hash = $2, updated_at = NOW(), updated_by = $3 WHERE id = $1",
+        )
+        .bind(user_id)
+        .bind(password_hash)
+        .bind(ctx.user_id())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        sqlx::query(
+            "DELETE FROM password_history WHERE user_id = $1 AND id NOT IN \
+             (SELECT id FROM password_history WHERE user_id = $1 ORDER BY created_at DESC LIMIT $2)",
+        )
+        .bind(user_id)
+        .bind(history_size as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?;
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))
+    }
+
+    /// Replace a password hash with an upgraded hash of the same password.
+    ///
+    /// Does nothing if the password was changed concurrently.
+    pub async fn rehash_password(
+        &self,
+        ctx: &RequestCtx,
+        user_id: UserId,
+        old_hash: &str,
+        new_hash: &str,
+    ) -> Result<(), AppError> {
+        sqlx::query("UPDATE users SET password_hash = $3 WHERE id = $1 AND password_hash = $2")
+            .bind(user_id)
+            .bind(old_hash)
+            .bind(new_hash)
+            .execute(self.db.primary())
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+
+        Ok(())
+    }
+}
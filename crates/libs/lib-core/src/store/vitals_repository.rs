@@ -0,0 +1,227 @@
+use std::collections::{HashMap, HashSet};
+
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_types::dtos::{
+    CursorPage, LiveEvent, VitalsBatchResult, VitalsDto, VitalsHistoryQuery, MAX_VITALS_BATCH,
+};
+use lib_types::entities::{Deterioration, Patient, PatientVitals};
+use lib_types::errors::{AppError, Validate, ValidationErrors};
+use lib_types::ids::PatientId;
+
+use super::live_events::notify;
+use super::outbox_repository::enqueue_deterioration;
+use super::patient_event_repository::record_vitals_events;
+use super::query_metrics::Observe;
+use super::{db_error, push_page, Db, ReadPreference};
+
+const VITALS_COLUMNS: &str = "id, patient_id, recorded_by, systolic_bp, diastolic_bp, heart_rate, \
+     oxygen_saturation, temperature, respiratory_rate, consciousness_level, \
+     on_supplemental_oxygen, gcs_eye, gcs_verbal, gcs_motor, pain_score, blood_glucose, weight, \
+     device_id, additional_measurements, notes, recorded_at, created_at";
+
+/// Readings per INSERT statement, keeping the bound parameters (22 per
+/// reading) well under the Postgres limit of 65535
+const INSERT_CHUNK: usize = 1000;
+
+/// Data access for recorded vital signs
+#[derive(Clone)]
+pub struct VitalsRepository {
+    db: Db,
+}
+
+impl VitalsRepository {
+    pub fn new(db: Db) -> Self {
+        Self { db }
+    }
+
+    /// Store one set of vital signs for `patient`, who the caller has
+    /// already looked up, and log it with the patient's events. Signs of
+    /// deterioration are announced to live dashboards and queued for other
+    /// systems in the same transaction, so the alert exists exactly when the
+    /// reading does.
+    pub async fn record(
+        &self,
+        ctx: &RequestCtx,
+        patient: &Patient,
+        vitals: &PatientVitals,
+        findings: &[Deterioration],
+    ) -> Result<PatientVitals, AppError> {
+        vitals.validate()?;
+
+        let mut tx = self.db.begin_for(ctx).await?;
+        let mut insert =
+            QueryBuilder::new(format!("INSERT INTO patient_vitals ({}) ", VITALS_COLUMNS));
+        push_rows(&mut insert, std::slice::from_ref(vitals));
+        insert.push(format!(" RETURNING {}", VITALS_COLUMNS));
+        let stored = insert
+            .build_query_as::<PatientVitals>()
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| db_error(ctx, e))?;
+        record_vitals_events(&mut tx, ctx, &[stored.id]).await?;
+
+        if !findings.is_empty() {
+            enqueue_deterioration(&mut tx, ctx, patient, &stored, findings).await?;
+            notify(
+                &mut tx,
+                ctx,
+                &LiveEvent::patient_deteriorated(patient, &stored, findings),
+            )
+            .await?;
+        }
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+        Ok(stored)
+    }
+
+    /// A page of a patient's vital signs, newest first, optionally limited
+    /// to a time window. Patients outside the caller's hospital have none.
+    pub async fn list_for_patient(
+        &self,
+        ctx: &RequestCtx,
+        patient_id: PatientId,
+        query: &VitalsHistoryQuery,
+    ) -> Result<CursorPage<VitalsDto>, AppError> {
+        let mut select = QueryBuilder::new(format!(
+            "SELECT {} FROM patient_vitals WHERE patient_id = ",
+            VITALS_COLUMNS
+        ));
+        select.push_bind(patient_id);
+        if let Some(hospital_id) = ctx.tenant_hospital_id() {
+            select
+                .push(" AND patient_id IN (SELECT id FROM patients WHERE hospital_id = ")
+                .push_bind(hospital_id)
+                .push(")");
+        }
+        if let Some(from) = query.from {
+            select.push(" AND recorded_at >= ").push_bind(from);
+        }
+        if let Some(to) = query.to {
+            select.push(" AND recorded_at < ").push_bind(to);
+        }
+        let page = query.page();
+        push_page(&mut select, &page)?;
+
+        let mut conn = self.db.acquire_for(ctx, ReadPreference::Primary).await?;
+        let rows = select
+            .build_query_as::<PatientVitals>()
+            .fetch_all(&mut *conn)
+            .observe(self.db.metrics(), ctx, "vitals.list_for_patient")
+            .await?;
+
+        Ok(CursorPage::from_rows(rows, &page, VitalsDto::from_vitals))
+    }
+
+    /// Store a burst of readings with one multi-row INSERT per thousand
+    /// readings. Readings that fail validation, belong to a patient outside
+    /// the caller's hospital or were already stored (a device resending a
+    /// backfill) are rejected and reported; the rest are stored together,
+    /// or not at all if the database fails.
+    pub async fn insert_batch(
+        &self,
+        ctx: &RequestCtx,
+        vitals: Vec<PatientVitals>,
+    ) -> Result<VitalsBatchResult, AppError> {
+        if vitals.len() > MAX_VITALS_BATCH {
+            return Err(AppError::validation_error(
+                "vitals",
+                format!("At most {} readings can be sent at once", MAX_VITALS_BATCH),
+            ));
+        }
+
+        let mut result = VitalsBatchResult::default();
+        let mut tx = self.db.begin_for(ctx).await?;
+
+        let patient_ids: Vec<Uuid> = vitals.iter().map(|v| v.patient_id.as_uuid()).collect();
+        let known_patients: HashSet<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM patients WHERE id = ANY($1) AND ($2::uuid IS NULL OR hospital_id = $2)",
+        )
+        .bind(&patient_ids)
+        .bind(ctx.tenant_hospital_id())
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| db_error(ctx, e))?
+        .into_iter()
+        .collect();
+
+        let mut accepted = Vec::with_capacity(vitals.len());
+        let mut positions = HashMap::with_capacity(vitals.len());
+        for (index, reading) in vitals.into_iter().enumerate() {
+            let mut errors = reading.validate().err().unwrap_or_default();
+            if !known_patients.contains(&reading.patient_id.as_uuid()) {
+                errors.add("patient_id", "not_found", "Patient not found");
+            }
+            if positions.contains_key(&reading.id) {
+                errors.add("id", "duplicate", "Reading appears twice in the batch");
+            }
+
+            if errors.is_empty() {
+                positions.insert(reading.id, index);
+                accepted.push(reading);
+            } else {
+                result.reject(index, reading.id, errors);
+            }
+        }
+
+        let mut stored = HashSet::with_capacity(accepted.len());
+        for chunk in accepted.chunks(INSERT_CHUNK) {
+            let mut insert =
+                QueryBuilder::new(format!("INSERT INTO patient_vitals ({}) ", VITALS_COLUMNS));
+            push_rows(&mut insert, chunk);
+            insert.push(" ON CONFLICT (id) DO NOTHING RETURNING id");
+
+            let ids: Vec<Uuid> = insert
+                .build_query_scalar()
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(|e| db_error(ctx, e))?;
+            record_vitals_events(&mut tx, ctx, &ids).await?;
+            stored.extend(ids);
+        }
+
+        tx.commit().await.map_err(|e| db_error(ctx, e))?;
+
+        for reading in &accepted {
+            if stored.contains(&reading.id) {
+                result.inserted.push(reading.id);
+            } else {
+                let mut errors = ValidationErrors::new();
+                errors.add("id", "duplicate", "Reading already recorded");
+                result.reject(positions[&reading.id], reading.id, errors);
+            }
+        }
+        result.sort_rejected();
+        Ok(result)
+    }
+}
+
+/// Append `VALUES` for `readings`, in the order of `VITALS_COLUMNS`
+fn push_rows<'a>(insert: &mut QueryBuilder<'a, Postgres>, readings: &'a [PatientVitals]) {
+    insert.push_values(readings, |mut row, reading| {
+        row.push_bind(reading.id)
+            .push_bind(reading.patient_id)
+            .push_bind(reading.recorded_by)
+            .push_bind(reading.systolic_bp)
+            .push_bind(reading.diastolic_bp)
+            .push_bind(reading.heart_rate)
+            .push_bind(reading.oxygen_saturation)
+            .push_bind(reading.temperature)
+            .push_bind(reading.respiratory_rate)
+            .push_bind(reading.consciousness_level)
+            .push_bind(reading.on_supplemental_oxygen)
+            .push_bind(reading.gcs_eye)
+            .push_bind(reading.gcs_verbal)
+            .push_bind(reading.gcs_motor)
+            .push_bind(reading.pain_score)
+            .push_bind(reading.blood_glucose)
+            .push_bind(reading.weight)
+            .push_bind(&reading.device_id)
+            .push_bind(&reading.additional_measurements)
+            .push_bind(&reading.notes)
+            .push_bind(reading.recorded_at)
+            .push_bind(reading.created_at);
+    });
+}
@@ -26,4 +26,68 @@ async fn test_database_connection() {
     println!("Health status: {:?}", health.status);
     println!("Response time: {}ms", health.response_time_ms);
     println!("Active connections: {}", health.active_connections);
-}
\ No newline at end of file
+}
+#[tokio::test]
+#[ignore] // Requires a running database
+async fn test_hospital_list_spans_hospitals_but_updates_do_not() {
+    use lib_auth::ctx::{Ctx, RequestCtx};
+    use lib_core::store::{run_migrations, Db, HospitalRepository};
+    use lib_types::dtos::{HospitalFilter, PageRequest, UpdateHospitalRequest};
+    use lib_types::entities::Hospital;
+    use lib_types::enums::{HospitalType, UserRole};
+    use lib_types::errors::{AppError, HospitalError};
+    use lib_types::ids::UserId;
+    use lib_utils::location::GeoPoint;
+    use uuid::Uuid;
+
+    if env::var("DATABASE_URL").is_err() {
+        println!("Skipping database test - DATABASE_URL not set");
+        return;
+    }
+
+    let config = DatabaseConfig::from_env().expect("Failed to load database config");
+    let db = Db::new(config.create_pool().await.expect("Failed to create connection pool"));
+    run_migrations(&db).await.expect("Failed to run migrations");
+
+    let hospitals = HospitalRepository::new(db);
+    let hospital = |name: &str| {
+        Hospital::new(
+            name.to_string(),
+            format!("DHA-{}", &Uuid::new_v4().simple().to_string()[..8]),
+            GeoPoint::new(25.2, 55.3).unwrap(),
+            "Dubai".to_string(),
+            "+97140000000".to_string(),
+            "er@example.ae".to_string(),
+            40,
+            vec![],
+            HospitalType::Public,
+        )
+    };
+    let own = hospital("Rashid Hospital");
+    let other = hospital("Dubai Hospital");
+    for hospital in [&own, &other] {
+        hospitals.create(&RequestCtx::system(), hospital).await.unwrap();
+    }
+
+    let paramedic = Ctx::new(UserId::new(), UserRole::Paramedic, own.id, Uuid::new_v4());
+    let ctx = RequestCtx::system().with_user(paramedic);
+
+    let page = PageRequest {
+        limit: 100,
+        ..Default::default()
+    };
+    let listed = hospitals.list(&ctx, &page, &HospitalFilter::default()).await.unwrap();
+    let ids: Vec<_> = listed.items.iter().map(|hospital| hospital.id).collect();
+    assert!(ids.contains(&own.id));
+    assert!(ids.contains(&other.id));
+
+    let rename = UpdateHospitalRequest {
+        name: Some("Renamed".to_string()),
+        ..Default::default()
+    };
+    // Changes stay limited to the caller's own hospital
+    assert!(matches!(
+        hospitals.update(&ctx, other.id, &rename).await,
+        Err(AppError::Hospital(HospitalError::NotFound { .. }))
+    ));
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::Ambulance;
+use crate::enums::AmbulanceStatus;
+use crate::ids::{AmbulanceId, HospitalId, UserId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmbulanceResponse {
+    pub id: AmbulanceId,
+    pub call_sign: String,
+    pub hospital_id: HospitalId,
+    pub base_station: String,
+    pub crew: Vec<UserId>,
+    pub status: AmbulanceStatus,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub location_updated_at: Option<DateTime<Utc>>,
+    pub incident_id: Option<Uuid>,
+    pub destination: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmbulanceListResponse {
+    pub ambulances: Vec<AmbulanceResponse>,
+    pub available_count: usize,
+}
+
+impl AmbulanceResponse {
+    /// Create from Ambulance entity
+    pub fn from_ambulance(ambulance: &Ambulance) -> Self {
+        Self {
+            id: ambulance.id,
+            call_sign: ambulance.call_sign.clone(),
+            hospital_id: ambulance.hospital_id,
+            base_station: ambulance.base_station.clone(),
+            crew: ambulance.crew.clone(),
+            status: ambulance.status,
+            latitude: ambulance.latitude,
+            longitude: ambulance.longitude,
+            location_updated_at: ambulance.location_updated_at,
+            incident_id: ambulance.incident_id,
+            destination: ambulance.destination.clone(),
+            updated_at: ambulance.updated_at,
+        }
+    }
+}
+
+impl AmbulanceListResponse {
+    /// Create from ambulance entities, counting units ready for dispatch
+    pub fn from_ambulances(ambulances: &[Ambulance]) -> Self {
+        Self {
+            ambulances: ambulances
+                .iter()
+                .map(AmbulanceResponse::from_ambulance)
+                .collect(),
+            available_count: ambulances.iter().filter(|a| a.is_available()).count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_response() {
+        let hospital_id = HospitalId::new();
+        let mut ready = Ambulance::new(
+            "DXB-AMB-07".to_string(),
+            hospital_id,
+            "Al Barsha".to_string(),
+        );
+        ready.assign_crew(vec![UserId::new()]);
+        let unstaffed = Ambulance::new(
+            "DXB-AMB-08".to_string(),
+            hospital_id,
+            "Al Barsha".to_string(),
+        );
+
+        let response = AmbulanceListResponse::from_ambulances(&[ready.clone(), unstaffed]);
+        assert_eq!(response.ambulances.len(), 2);
+        assert_eq!(response.available_count, 1);
+        assert_eq!(
+            response.ambulances[0],
+            AmbulanceResponse::from_ambulance(&ready)
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{HospitalId, UserId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateAmbulanceRequest {
+    pub call_sign: String,
+    pub hospital_id: HospitalId,
+    pub base_station: String,
+    pub crew: Option<Vec<UserId>>, // User ids of the crew on shift
+}
+
+impl CreateAmbulanceRequest {
+    /// Call signs are read out over the radio, so keep them short and plain
+    fn is_valid_call_sign(call_sign: &str) -> bool {
+        (2..=20).contains(&call_sign.len())
+            && call_sign
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-')
+    }
+}
+
+impl Validate for CreateAmbulanceRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if !Self::is_valid_call_sign(self.call_sign.trim()) {
+            errors.add(
+                "call_sign",
+                "invalid_format",
+                "Call sign must be 2-20 uppercase letters, digits or dashes",
+            );
+        }
+
+        if self.base_station.trim().is_empty() {
+            errors.add("base_station", "required", "Base station is required");
+        }
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_valid_request() -> CreateAmbulanceRequest {
+        CreateAmbulanceRequest {
+            call_sign: "DXB-AMB-07".to_string(),
+            hospital_id: HospitalId::new(),
+            base_station: "Al Barsha Station".to_string(),
+            crew: None,
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        assert!(create_valid_request().validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_request() {
+        let mut request = create_valid_request();
+        request.call_sign = "amb 7".to_string();
+        request.base_station = " ".to_string();
+
+        let errors = request.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.has_field("call_sign"));
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::location::GeoPoint;
+use lib_utils::validation::rules;
+
+use crate::enums::{AmbulanceStatus, TriageLevel};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{AmbulanceId, PatientId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DispatchAmbulanceRequest {
+    pub ambulance_id: AmbulanceId,
+    pub patient_id: Option<PatientId>, // Unknown until the crew registers the patient
+    pub incident_location: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub triage_level: Option<TriageLevel>, // Initial assessment from the call taker
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub incident_id: Option<Uuid>, // Major incident the call belongs to
+}
+
+impl DispatchAmbulanceRequest {
+    /// The incident's GPS position, when given
+    pub fn position(&self) -> Option<GeoPoint> {
+        self.latitude
+            .zip(self.longitude)
+            .map(|(lat, lon)| GeoPoint { lat, lon })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateAmbulanceStatusRequest {
+    pub status: AmbulanceStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateAmbulanceLocationRequest {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Put the patient the crew is treating on the ambulance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssignAmbulancePatientRequest {
+    pub patient_id: PatientId,
+}
+
+impl Validate for DispatchAmbulanceRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        rules::required(
+            &mut errors,
+            "incident_location",
+            &self.incident_location,
+            "Incident location is required",
+        );
+
+        match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => {
+                if !is_valid_position(latitude, longitude) {
+                    errors.add_with_value(
+                        "latitude",
+                        "out_of_range",
+                        "Incident GPS position is out of range",
+                        (latitude, longitude),
+                    );
+                }
+            }
+            (None, None) => {}
+            _ => errors.add(
+                "longitude",
+                "required",
+                "Latitude and longitude must be given together",
+            ),
+        }
+
+        errors.into_result()
+    }
+}
+
+impl UpdateAmbulanceLocationRequest {
+    /// Check that the position is a valid GPS coordinate
+    pub fn is_valid(&self) -> bool {
+        is_valid_position(self.latitude, self.longitude)
+    }
+
+    pub fn position(&self) -> GeoPoint {
+        GeoPoint {
+            lat: self.latitude,
+            lon: self.longitude,
+        }
+    }
+}
+
+impl Validate for UpdateAmbulanceLocationRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if !self.is_valid() {
+            errors.add_with_value(
+                "latitude",
+                "out_of_range",
+                "GPS position is out of range",
+                (self.latitude, self.longitude),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+fn is_valid_position(latitude: f64, longitude: f64) -> bool {
+    (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_valid_request() -> DispatchAmbulanceRequest {
+        DispatchAmbulanceRequest {
+            ambulance_id: AmbulanceId::new(),
+            patient_id: None,
+            incident_location: "Sheikh Zayed Road, Interchange 4".to_string(),
+            latitude: Some(25.1124),
+            longitude: Some(55.1390),
+            triage_level: Some(TriageLevel::Critical),
+            notes: None,
+            incident_id: None,
+        }
+    }
+
+    #[test]
+    fn test_valid_dispatch() {
+        let request = create_valid_request();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.position().map(|point| point.lat), Some(25.1124));
+    }
+
+    #[test]
+    fn test_invalid_dispatch() {
+        let mut request = create_valid_request();
+        request.incident_location = "".to_string();
+        request.longitude = None;
+
+        let errors = request.validate().unwrap_err();
+        assert!(errors.has_field("incident_location"));
+        assert!(errors.iter().any(|e| e.message.contains("together")));
+    }
+
+    #[test]
+    fn test_location_update() {
+        let update = UpdateAmbulanceLocationRequest {
+            latitude: 25.2048,
+            longitude: 55.2708,
+        };
+        assert!(update.is_valid());
+
+        let invalid = UpdateAmbulanceLocationRequest {
+            latitude: -95.0,
+            longitude: 55.2708,
+        };
+        assert!(!invalid.is_valid());
+        assert!(invalid.validate().unwrap_err().has_field("latitude"));
+    }
+}
@@ -0,0 +1,12 @@
+//! Ambulance and dispatch DTOs
+
+pub mod ambulance_response;
+pub mod create_ambulance;
+pub mod dispatch;
+
+pub use ambulance_response::{AmbulanceListResponse, AmbulanceResponse};
+pub use create_ambulance::CreateAmbulanceRequest;
+pub use dispatch::{
+    AssignAmbulancePatientRequest, DispatchAmbulanceRequest, UpdateAmbulanceLocationRequest,
+    UpdateAmbulanceStatusRequest,
+};
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::AuthAuditEntry;
+use crate::enums::{AuthEvent, AuthOutcome};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::UserId;
+
+/// Filters for searching the authentication audit log
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuthAuditQuery {
+    pub user_id: Option<UserId>,
+    pub event: Option<AuthEvent>,
+    pub outcome: Option<AuthOutcome>,
+    pub ip_address: Option<String>,
+    pub correlation_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+}
+
+impl AuthAuditQuery {
+    pub const DEFAULT_LIMIT: u32 = 100;
+    pub const MAX_LIMIT: u32 = 500;
+
+    /// Get the number of entries to return, capped at `MAX_LIMIT`
+    pub fn limit(&self) -> u32 {
+        self.limit
+            .unwrap_or(Self::DEFAULT_LIMIT)
+            .clamp(1, Self::MAX_LIMIT)
+    }
+}
+
+impl Validate for AuthAuditQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                errors.add("from", "invalid_range", "from must not be after to");
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthAuditListResponse {
+    pub entries: Vec<AuthAuditEntry>,
+    pub total: usize,
+}
+
+impl AuthAuditListResponse {
+    /// Create new audit list response
+    pub fn new(entries: Vec<AuthAuditEntry>) -> Self {
+        let total = entries.len();
+        Self { entries, total }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_and_range() {
+        assert_eq!(AuthAuditQuery::default().limit(), 100);
+
+        let query = AuthAuditQuery {
+            limit: Some(10_000),
+            from: Some(Utc::now()),
+            to: Some(Utc::now() - chrono::Duration::hours(1)),
+            ..AuthAuditQuery::default()
+        };
+        assert_eq!(query.limit(), 500);
+        assert!(query.validate().is_err());
+    }
+}
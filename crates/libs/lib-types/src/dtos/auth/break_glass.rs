@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::ids::HospitalId;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakGlassRequest {
+    pub hospital_id: HospitalId, // Hospital whose patients need to be accessed
+    pub reason: String,
+    pub duration_minutes: Option<u32>, // Capped by the configured maximum
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BreakGlassResponse {
+    pub hospital_id: HospitalId,
+    pub reason: String,
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl BreakGlassResponse {
+    /// Get remaining access time in minutes at the given time
+    pub fn remaining_minutes(&self, now: DateTime<Utc>) -> i64 {
+        (self.expires_at - now).num_minutes().max(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_minutes() {
+        let now = Utc::now();
+        let response = BreakGlassResponse {
+            hospital_id: HospitalId::new(),
+            reason: "Mass casualty incident on Sheikh Zayed Road".to_string(),
+            granted_at: now,
+            expires_at: now + chrono::Duration::minutes(30),
+        };
+
+        assert_eq!(response.remaining_minutes(now), 30);
+        assert_eq!(
+            response.remaining_minutes(now + chrono::Duration::hours(1)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_request_deserialization() {
+        let json = r#"{"hospital_id":"6f1c1b7e-8a8e-4a43-9d43-2f1f5c7f0b11","reason":"Disaster","duration_minutes":null}"#;
+        let request: BreakGlassRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.duration_minutes, None);
+    }
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use crate::errors::{Validate, ValidationErrors};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Password strength is checked by the password policy, not here
+impl Validate for ChangePasswordRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        rules::required(
+            &mut errors,
+            "current_password",
+            &self.current_password,
+            "Current password is required",
+        );
+
+        if rules::required(
+            &mut errors,
+            "new_password",
+            &self.new_password,
+            "New password is required",
+        ) && self.new_password == self.current_password
+        {
+            errors.add(
+                "new_password",
+                "unchanged",
+                "New password must be different from the current password",
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_password_validation() {
+        let request = ChangePasswordRequest {
+            current_password: "Old#Password2023".to_string(),
+            new_password: "New#Password2024".to_string(),
+        };
+        assert!(request.validate().is_ok());
+
+        let same = ChangePasswordRequest {
+            current_password: "Old#Password2023".to_string(),
+            new_password: "Old#Password2023".to_string(),
+        };
+        assert!(same.validate().unwrap_err().has_field("new_password"));
+
+        let empty = ChangePasswordRequest {
+            current_password: "".to_string(),
+            new_password: "New#Password2024".to_string(),
+        };
+        assert!(empty.validate().unwrap_err().has_field("current_password"));
+    }
+}
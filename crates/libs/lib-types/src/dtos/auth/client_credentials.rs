@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use crate::enums::ServiceScope;
+use crate::errors::{AuthError, Validate, ValidationErrors};
+
+/// OAuth 2.0 client-credentials token request used by service accounts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientCredentialsRequest {
+    pub grant_type: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub scope: Option<String>, // Space-delimited; defaults to every granted scope
+}
+
+impl Validate for ClientCredentialsRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        rules::one_of(
+            &mut errors,
+            "grant_type",
+            &self.grant_type,
+            &["client_credentials"],
+            "Unsupported grant_type, expected client_credentials",
+        );
+        rules::required(
+            &mut errors,
+            "client_id",
+            &self.client_id,
+            "client_id is required",
+        );
+        rules::required(
+            &mut errors,
+            "client_secret",
+            &self.client_secret,
+            "client_secret is required",
+        );
+
+        errors.into_result()
+    }
+}
+
+impl ClientCredentialsRequest {
+    /// Parse the requested scopes, if any
+    pub fn requested_scopes(&self) -> Result<Option<Vec<ServiceScope>>, AuthError> {
+        match self.scope.as_deref().map(str::trim) {
+            None | Some("") => Ok(None),
+            Some(scope) => ServiceScope::parse_list(scope)
+                .map(Some)
+                .map_err(|scope| AuthError::InvalidScope { scope }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceTokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64, // Seconds until expiration
+    pub scope: String,
+}
+
+impl ServiceTokenResponse {
+    /// Create new service token response
+    pub fn new(access_token: String, expires_in: i64, scopes: &[ServiceScope]) -> Self {
+        Self {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in,
+            scope: scopes
+                .iter()
+                .map(ServiceScope::as_str)
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(grant_type: &str, scope: Option<&str>) -> ClientCredentialsRequest {
+        ClientCredentialsRequest {
+            grant_type: grant_type.to_string(),
+            client_id: "reporting-jobs".to_string(),
+            client_secret: "secret".to_string(),
+            scope: scope.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(request("client_credentials", None).validate().is_ok());
+        assert!(request("password", None)
+            .validate()
+            .unwrap_err()
+            .has_field("grant_type"));
+    }
+
+    #[test]
+    fn test_requested_scopes() {
+        assert_eq!(
+            request("client_credentials", Some(" ")).requested_scopes(),
+            Ok(None)
+        );
+        assert_eq!(
+            request("client_credentials", Some("reports:read")).requested_scopes(),
+            Ok(Some(vec![ServiceScope::ReportsRead]))
+        );
+        assert!(request("client_credentials", Some("admin"))
+            .requested_scopes()
+            .is_err());
+    }
+}
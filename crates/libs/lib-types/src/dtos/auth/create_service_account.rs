@@ -0,0 +1,99 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::validation::{rules, Regex};
+
+use crate::entities::ServiceAccount;
+use crate::enums::ServiceScope;
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::HospitalId;
+
+/// Lowercase letters, digits, '-' and '_'
+static CLIENT_ID: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z0-9_-]*$").unwrap());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateServiceAccountRequest {
+    pub client_id: String,
+    pub name: String,
+    pub hospital_id: Option<HospitalId>,
+    pub scopes: Vec<ServiceScope>,
+}
+
+impl Validate for CreateServiceAccountRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let client_id = self.client_id.trim();
+        rules::length(
+            &mut errors,
+            "client_id",
+            client_id,
+            3,
+            64,
+            "client_id must be between 3 and 64 characters",
+        );
+        rules::pattern(
+            &mut errors,
+            "client_id",
+            client_id,
+            &CLIENT_ID,
+            "client_id may only contain lowercase letters, digits, '-' and '_'",
+        );
+        rules::required(&mut errors, "name", &self.name, "name is required");
+
+        if self.scopes.is_empty() {
+            errors.add("scopes", "required", "at least one scope is required");
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Returned once at creation; the plain client secret is never retrievable again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceAccountCreatedResponse {
+    pub id: Uuid,
+    pub client_id: String,
+    pub client_secret: String,
+    pub name: String,
+    pub hospital_id: Option<HospitalId>,
+    pub scopes: Vec<ServiceScope>,
+}
+
+impl ServiceAccountCreatedResponse {
+    pub fn new(account: &ServiceAccount, client_secret: String) -> Self {
+        Self {
+            id: account.id,
+            client_id: account.client_id.clone(),
+            client_secret,
+            name: account.name.clone(),
+            hospital_id: account.hospital_id,
+            scopes: account.allowed_scopes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let request = CreateServiceAccountRequest {
+            client_id: "dispatch-optimizer".to_string(),
+            name: "Dispatch optimizer".to_string(),
+            hospital_id: None,
+            scopes: vec![ServiceScope::BedsRead],
+        };
+        assert!(request.validate().is_ok());
+
+        let invalid = CreateServiceAccountRequest {
+            client_id: "Dispatch Optimizer".to_string(),
+            scopes: vec![],
+            ..request
+        };
+        assert_eq!(invalid.validate().unwrap_err().len(), 2);
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::enums::UserRole;
+use crate::ids::UserId;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DelegateRoleRequest {
+    pub delegate_id: UserId, // Colleague who takes over the caller's role
+    pub reason: String,
+    pub duration_hours: Option<u32>, // Defaults to a shift; capped by the configured maximum
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DelegationResponse {
+    pub id: Uuid,
+    pub delegator_id: UserId,
+    pub delegate_id: UserId,
+    pub role: UserRole,
+    pub reason: String,
+    pub granted_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl DelegationResponse {
+    /// Get remaining delegation time in minutes at the given time
+    pub fn remaining_minutes(&self, now: DateTime<Utc>) -> i64 {
+        (self.expires_at - now).num_minutes().max(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_deserialization() {
+        let json = r#"{"delegate_id":"6f1c1b7e-8a8e-4a43-9d43-2f1f5c7f0b11","reason":"Off shift until 07:00"}"#;
+        let request: DelegateRoleRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.duration_hours, None);
+    }
+
+    #[test]
+    fn test_remaining_minutes() {
+        let now = Utc::now();
+        let response = DelegationResponse {
+            id: Uuid::new_v4(),
+            delegator_id: UserId::new(),
+            delegate_id: UserId::new(),
+            role: UserRole::ErDirector,
+            reason: "Off shift until 07:00".to_string(),
+            granted_at: now,
+            expires_at: now + chrono::Duration::hours(8),
+        };
+
+        assert_eq!(response.remaining_minutes(now), 8 * 60);
+        assert_eq!(
+            response.remaining_minutes(now + chrono::Duration::hours(9)),
+            0
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::UserDevice;
+
+/// Registered device as shown to its owner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceResponse {
+    pub id: Uuid,
+    pub name: Option<String>,
+    pub user_agent: Option<String>,
+    pub last_ip: Option<String>,
+    pub trusted: bool,
+    pub trusted_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub is_current: bool, // Device of the session used for this request
+}
+
+impl DeviceResponse {
+    /// Create from domain UserDevice entity
+    pub fn from_device(device: &UserDevice, is_current: bool) -> Self {
+        Self {
+            id: device.id,
+            name: device.name.clone(),
+            user_agent: device.user_agent.clone(),
+            last_ip: device.last_ip.clone(),
+            trusted: device.is_trusted_at(Utc::now()),
+            trusted_until: device.trusted_until,
+            created_at: device.created_at,
+            last_seen_at: device.last_seen_at,
+            is_current,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceListResponse {
+    pub devices: Vec<DeviceResponse>,
+    pub total: usize,
+}
+
+impl DeviceListResponse {
+    /// Create new device list response
+    pub fn new(devices: Vec<DeviceResponse>) -> Self {
+        let total = devices.len();
+        Self { devices, total }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrustDeviceRequest {
+    pub name: Option<String>,
+}
+
+impl TrustDeviceRequest {
+    /// Get the trimmed device label, if one was given
+    pub fn sanitized_name(&self) -> Option<String> {
+        self.name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| name.chars().take(100).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitized_name() {
+        let request = TrustDeviceRequest {
+            name: Some("  Ambulance 12 tablet ".to_string()),
+        };
+        assert_eq!(
+            request.sanitized_name().as_deref(),
+            Some("Ambulance 12 tablet")
+        );
+
+        let blank = TrustDeviceRequest {
+            name: Some("   ".to_string()),
+        };
+        assert_eq!(blank.sanitized_name(), None);
+    }
+}
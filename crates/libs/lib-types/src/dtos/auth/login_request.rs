@@ -1,36 +1,55 @@
 use serde::{Deserialize, Serialize};
 
+use lib_utils::validation::rules;
+
+use crate::errors::{Validate, ValidationErrors};
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LoginRequest {
     pub username: String,
     pub password: String,
 }
 
-impl LoginRequest {
-    /// Create new login request
-    pub fn new(username: String, password: String) -> Self {
-        Self { username, password }
-    }
-
-    /// Validate login request
-    pub fn validate(&self) -> Result<(), String> {
-        if self.username.trim().is_empty() {
-            return Err("Username is required".to_string());
+impl Validate for LoginRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if rules::required(
+            &mut errors,
+            "username",
+            &self.username,
+            "Username is required",
+        ) && self.username.len() < 3
+        {
+            errors.add(
+                "username",
+                "too_short",
+                "Username must be at least 3 characters",
+            );
         }
 
-        if self.username.len() < 3 {
-            return Err("Username must be at least 3 characters".to_string());
+        if rules::required(
+            &mut errors,
+            "password",
+            &self.password,
+            "Password is required",
+        ) && self.password.len() < 6
+        {
+            errors.add(
+                "password",
+                "too_short",
+                "Password must be at least 6 characters",
+            );
         }
 
-        if self.password.is_empty() {
-            return Err("Password is required".to_string());
-        }
-
-        if self.password.len() < 6 {
-            return Err("Password must be at least 6 characters".to_string());
-        }
+        errors.into_result()
+    }
+}
 
-        Ok(())
+impl LoginRequest {
+    /// Create new login request
+    pub fn new(username: String, password: String) -> Self {
+        Self { username, password }
     }
 
     /// Sanitize username (trim whitespace, lowercase)
@@ -59,22 +78,31 @@ mod tests {
     #[test]
     fn test_invalid_username() {
         let request = LoginRequest::new("ab".to_string(), "password123".to_string());
-        assert!(request.validate().is_err());
-        assert!(request.validate().unwrap_err().contains("at least 3 characters"));
+        assert!(request
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("at least 3 characters"));
     }
 
     #[test]
     fn test_invalid_password() {
         let request = LoginRequest::new("ahmed.director".to_string(), "123".to_string());
-        assert!(request.validate().is_err());
-        assert!(request.validate().unwrap_err().contains("at least 6 characters"));
+        assert!(request
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("at least 6 characters"));
     }
 
     #[test]
     fn test_empty_fields() {
         let request = LoginRequest::new("".to_string(), "".to_string());
-        let error = request.validate().unwrap_err();
-        assert!(error.contains("Username is required"));
+        let errors = request.validate().unwrap_err();
+        assert_eq!(
+            errors.messages(),
+            ["Username is required", "Password is required"]
+        );
     }
 
     #[test]
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::enums::UserRole;
+use crate::ids::{HospitalId, UserId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LoginResponse {
@@ -10,15 +11,17 @@ pub struct LoginResponse {
     pub token_type: String,
     pub expires_in: i64, // Seconds until expiration
     pub user_profile: UserProfileDto,
+    pub device_id: Option<Uuid>,
+    pub device_trusted: bool, // Trusted devices are exempt from MFA challenges
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UserProfileDto {
-    pub id: Uuid,
+    pub id: UserId,
     pub username: String,
     pub email: String,
     pub role: UserRole,
-    pub hospital_id: Uuid,
+    pub hospital_id: HospitalId,
     pub first_name: String,
     pub last_name: String,
     pub phone_number: Option<String>,
@@ -38,9 +41,18 @@ impl LoginResponse {
             token_type: "Bearer".to_string(),
             expires_in,
             user_profile,
+            device_id: None,
+            device_trusted: false,
         }
     }
 
+    /// Report the device the login was recorded against
+    pub fn with_device(mut self, device_id: Uuid, trusted: bool) -> Self {
+        self.device_id = Some(device_id);
+        self.device_trusted = trusted;
+        self
+    }
+
     /// Check if token is about to expire (within 5 minutes)
     pub fn is_near_expiry(&self) -> bool {
         self.expires_in < 300 // 5 minutes
@@ -86,7 +98,7 @@ mod tests {
             "ahmed@dubaihospital.ae".to_string(),
             "hashed_password".to_string(),
             UserRole::ErDirector,
-            Uuid::new_v4(),
+            HospitalId::new(),
             "Ahmed".to_string(),
             "Al-Mansoori".to_string(),
             Some("+971501234567".to_string()),
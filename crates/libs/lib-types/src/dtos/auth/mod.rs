@@ -1,6 +1,27 @@
-
+pub mod auth_audit_query;
+pub mod break_glass;
+pub mod change_password;
+pub mod client_credentials;
+pub mod create_service_account;
+pub mod delegation;
+pub mod device_response;
 pub mod login_request;
 pub mod login_response;
+pub mod reauthenticate;
+pub mod register_user;
+pub mod session_response;
+pub mod shared_link;
 
+pub use auth_audit_query::{AuthAuditListResponse, AuthAuditQuery};
+pub use break_glass::{BreakGlassRequest, BreakGlassResponse};
+pub use change_password::ChangePasswordRequest;
+pub use client_credentials::{ClientCredentialsRequest, ServiceTokenResponse};
+pub use create_service_account::{CreateServiceAccountRequest, ServiceAccountCreatedResponse};
+pub use delegation::{DelegateRoleRequest, DelegationResponse};
+pub use device_response::{DeviceListResponse, DeviceResponse, TrustDeviceRequest};
 pub use login_request::LoginRequest;
-pub use login_response::{LoginResponse, UserProfileDto};
\ No newline at end of file
+pub use login_response::{LoginResponse, UserProfileDto};
+pub use reauthenticate::ReauthenticateRequest;
+pub use register_user::RegisterUserRequest;
+pub use session_response::{LogoutAllResponse, SessionListResponse, SessionResponse};
+pub use shared_link::{CreateSharedLinkRequest, SharedLinkResponse};
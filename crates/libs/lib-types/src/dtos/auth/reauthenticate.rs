@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use crate::errors::{Validate, ValidationErrors};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReauthenticateRequest {
+    pub password: String,
+}
+
+impl Validate for ReauthenticateRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        rules::required(
+            &mut errors,
+            "password",
+            &self.password,
+            "Password is required",
+        );
+        errors.into_result()
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::{rules, Regex};
+
+use crate::enums::UserRole;
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::HospitalId;
+
+/// Lowercase letters, digits, '.', '_' and '-'
+static USERNAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z0-9._-]*$").unwrap());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegisterUserRequest {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+    pub role: UserRole,
+    pub hospital_id: HospitalId,
+    pub first_name: String,
+    pub last_name: String,
+    pub phone_number: Option<String>,
+}
+
+/// Password strength is checked by the password policy, not here
+impl Validate for RegisterUserRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let username = self.sanitized_username();
+        if username.len() < 3 {
+            errors.add(
+                "username",
+                "too_short",
+                "Username must be at least 3 characters",
+            );
+        }
+
+        rules::pattern(
+            &mut errors,
+            "username",
+            &username,
+            &USERNAME,
+            "Username may only contain letters, digits, '.', '_' and '-'",
+        );
+
+        if !Self::is_valid_email(self.email.trim()) {
+            errors.add("email", "invalid_format", "Invalid email address");
+        }
+
+        rules::required(
+            &mut errors,
+            "password",
+            &self.password,
+            "Password is required",
+        );
+        rules::required(
+            &mut errors,
+            "first_name",
+            &self.first_name,
+            "First name is required",
+        );
+        rules::required(
+            &mut errors,
+            "last_name",
+            &self.last_name,
+            "Last name is required",
+        );
+
+        errors.into_result()
+    }
+}
+
+impl RegisterUserRequest {
+    /// Basic email validation (simplified)
+    fn is_valid_email(email: &str) -> bool {
+        match email.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty() && domain.contains('.') && !domain.starts_with('.')
+            }
+            None => false,
+        }
+    }
+
+    /// Sanitize username (trim whitespace, lowercase)
+    pub fn sanitized_username(&self) -> String {
+        self.username.trim().to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_valid_request() -> RegisterUserRequest {
+        RegisterUserRequest {
+            username: "Sarah.Nurse".to_string(),
+            email: "sarah@rashidhospital.ae".to_string(),
+            password: "Falcon#Desert2024".to_string(),
+            role: UserRole::Nurse,
+            hospital_id: HospitalId::new(),
+            first_name: "Sarah".to_string(),
+            last_name: "Khan".to_string(),
+            phone_number: Some("+971501234567".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        let request = create_valid_request();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.sanitized_username(), "sarah.nurse");
+    }
+
+    #[test]
+    fn test_invalid_fields() {
+        let mut request = create_valid_request();
+        request.username = "a b".to_string();
+        request.email = "not-an-email".to_string();
+        request.first_name = " ".to_string();
+
+        let errors = request.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].field, "username");
+        assert_eq!(errors[0].code, "invalid_format");
+    }
+}
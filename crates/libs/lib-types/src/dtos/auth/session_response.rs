@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::ids::UserId;
+
+/// Active login session as shown to its owner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionResponse {
+    pub id: Uuid,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub device_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub is_current: bool, // Session of the token used for this request
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionListResponse {
+    pub sessions: Vec<SessionResponse>,
+    pub total: usize,
+}
+
+impl SessionListResponse {
+    /// Create new session list response
+    pub fn new(sessions: Vec<SessionResponse>) -> Self {
+        let total = sessions.len();
+        Self { sessions, total }
+    }
+
+    /// Get the session of the current request, if listed
+    pub fn current(&self) -> Option<&SessionResponse> {
+        self.sessions.iter().find(|session| session.is_current)
+    }
+}
+
+/// Result of logging a user out on every device
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogoutAllResponse {
+    pub user_id: UserId,
+    pub terminated_sessions: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_session(is_current: bool) -> SessionResponse {
+        let now = Utc::now();
+        SessionResponse {
+            id: Uuid::new_v4(),
+            ip_address: Some("10.20.0.5".to_string()),
+            user_agent: Some("Mozilla/5.0".to_string()),
+            device_id: None,
+            created_at: now,
+            last_seen_at: now,
+            expires_at: now + chrono::Duration::hours(8),
+            is_current,
+        }
+    }
+
+    #[test]
+    fn test_session_list() {
+        let current = create_test_session(true);
+        let list = SessionListResponse::new(vec![create_test_session(false), current.clone()]);
+
+        assert_eq!(list.total, 2);
+        assert_eq!(list.current(), Some(&current));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let list = SessionListResponse::new(vec![create_test_session(true)]);
+        let json = serde_json::to_string(&list).unwrap();
+        let deserialized: SessionListResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(list, deserialized);
+    }
+}
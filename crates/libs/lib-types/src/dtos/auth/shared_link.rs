@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateSharedLinkRequest {
+    pub path: String, // e.g. /api/shared/documents/<patient id>/discharge-summary.pdf
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedLinkResponse {
+    pub url: String, // Opens without login until expires_at
+    pub expires_at: DateTime<Utc>,
+}
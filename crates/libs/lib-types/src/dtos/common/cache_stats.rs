@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Lookups served by a cache since the server started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub errors: u64, // Lookups that fell back to the database because the cache failed
+}
+
+impl CacheStats {
+    /// Share of lookups answered from the cache, if there were any
+    pub fn hit_ratio(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses + self.errors;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hit_ratio() {
+        assert_eq!(CacheStats::default().hit_ratio(), None);
+
+        let stats = CacheStats {
+            hits: 6,
+            misses: 1,
+            errors: 1,
+        };
+        assert_eq!(stats.hit_ratio(), Some(0.75));
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::LiveEvent;
+use crate::errors::ValidationErrors;
+use crate::ids::HospitalId;
+
+/// Hospitals a single dashboard connection may follow at once
+pub const MAX_DASHBOARD_HOSPITALS: usize = 32;
+
+/// Sent by a dashboard to choose what it receives. Omitting `channels`
+/// subscribes to all of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DashboardCommand {
+    Subscribe {
+        hospital_id: HospitalId,
+        #[serde(default)]
+        channels: Vec<String>, // Names from `LiveEvent::CHANNELS`
+    },
+    Unsubscribe {
+        hospital_id: HospitalId,
+    },
+}
+
+/// Sent to a dashboard alongside the live events themselves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DashboardNotice {
+    Subscribed {
+        hospital_id: HospitalId,
+        channels: Vec<String>,
+    },
+    Unsubscribed {
+        hospital_id: HospitalId,
+    },
+    /// The dashboard fell behind and `missed` events were dropped; it
+    /// should reload its state
+    Resync {
+        missed: u64,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// What a dashboard connection receives: events on the chosen channels of
+/// each hospital it follows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DashboardSubscription {
+    hospitals: HashMap<HospitalId, Vec<&'static str>>,
+}
+
+impl DashboardSubscription {
+    /// Follow `hospital_id` on `channels`, or on every channel when empty,
+    /// replacing any earlier choice for that hospital. Returns the channels
+    /// now followed.
+    pub fn subscribe(
+        &mut self,
+        hospital_id: HospitalId,
+        channels: &[String],
+    ) -> Result<Vec<&'static str>, ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if !self.hospitals.contains_key(&hospital_id)
+            && self.hospitals.len() >= MAX_DASHBOARD_HOSPITALS
+        {
+            errors.add(
+                "hospital_id",
+                "too_many",
+                format!(
+                    "A dashboard can follow at most {} hospitals",
+                    MAX_DASHBOARD_HOSPITALS
+                ),
+            );
+        }
+
+        let mut selected = Vec::new();
+        for name in channels {
+            match LiveEvent::CHANNELS
+                .iter()
+                .find(|channel| **channel == name.trim())
+            {
+                Some(channel) if !selected.contains(channel) => selected.push(*channel),
+                Some(_) => {}
+                None => errors.add_with_value(
+                    "channels",
+                    "unknown",
+                    "Unknown live event channel",
+                    name.as_str(),
+                ),
+            }
+        }
+        errors.into_result()?;
+
+        if selected.is_empty() {
+            selected = LiveEvent::CHANNELS.to_vec();
+        }
+        self.hospitals.insert(hospital_id, selected.clone());
+        Ok(selected)
+    }
+
+    /// Stop following `hospital_id`; false if it was not followed
+    pub fn unsubscribe(&mut self, hospital_id: HospitalId) -> bool {
+        self.hospitals.remove(&hospital_id).is_some()
+    }
+
+    /// Check if `event` should be sent to this dashboard
+    pub fn matches(&self, event: &LiveEvent) -> bool {
+        self.hospitals
+            .get(&event.hospital_id())
+            .is_some_and(|channels| channels.contains(&event.channel()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hospitals.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bed_event(hospital_id: HospitalId) -> LiveEvent {
+        LiveEvent::BedAvailabilityChanged {
+            hospital_id,
+            available_beds: 3,
+            total_beds: 40,
+        }
+    }
+
+    #[test]
+    fn test_subscription_filters_by_hospital_and_channel() {
+        let hospital_id = HospitalId::new();
+        let mut subscription = DashboardSubscription::default();
+        assert!(!subscription.matches(&bed_event(hospital_id)));
+
+        let channels = subscription.subscribe(hospital_id, &[]).unwrap();
+        assert_eq!(channels.len(), LiveEvent::CHANNELS.len());
+        assert!(subscription.matches(&bed_event(hospital_id)));
+        assert!(!subscription.matches(&bed_event(HospitalId::new())));
+
+        let channels = subscription
+            .subscribe(hospital_id, &["ambulance_updates".to_string()])
+            .unwrap();
+        assert_eq!(channels, vec!["ambulance_updates"]);
+        assert!(!subscription.matches(&bed_event(hospital_id)));
+
+        assert!(subscription.unsubscribe(hospital_id));
+        assert!(!subscription.unsubscribe(hospital_id));
+        assert!(subscription.is_empty());
+    }
+
+    #[test]
+    fn test_subscription_limits() {
+        let mut subscription = DashboardSubscription::default();
+        let errors = subscription
+            .subscribe(HospitalId::new(), &["gossip".to_string()])
+            .unwrap_err();
+        assert!(errors.has_field("channels"));
+        assert!(subscription.is_empty());
+
+        for _ in 0..MAX_DASHBOARD_HOSPITALS {
+            subscription.subscribe(HospitalId::new(), &[]).unwrap();
+        }
+        assert!(subscription.subscribe(HospitalId::new(), &[]).is_err());
+    }
+
+    #[test]
+    fn test_command_json() {
+        let hospital_id = HospitalId::new();
+        let json = serde_json::json!({ "action": "subscribe", "hospital_id": hospital_id });
+        let command: DashboardCommand = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            command,
+            DashboardCommand::Subscribe {
+                hospital_id,
+                channels: vec![]
+            }
+        );
+
+        let notice = serde_json::to_value(DashboardNotice::Resync { missed: 12 }).unwrap();
+        assert_eq!(notice["type"], "resync");
+        assert_eq!(notice["missed"], 12);
+    }
+}
@@ -0,0 +1,176 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::{
+    Ambulance, Deterioration, Hospital, Patient, PatientVitals, TriageAssessment,
+};
+use crate::enums::{AmbulanceStatus, PatientStatus, TriageLevel};
+use crate::ids::{AmbulanceId, HospitalId, PatientId};
+
+/// A change pushed to live dashboards as it happens, whichever server
+/// instance made it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveEvent {
+    PatientStatusChanged {
+        patient_id: PatientId,
+        hospital_id: HospitalId,
+        previous_status: PatientStatus,
+        status: PatientStatus,
+        version: i64,
+    },
+    BedAvailabilityChanged {
+        hospital_id: HospitalId,
+        available_beds: i32,
+        total_beds: i32,
+    },
+    PatientDeteriorated {
+        patient_id: PatientId,
+        hospital_id: HospitalId,
+        vitals_id: Uuid,
+        findings: Vec<Deterioration>,
+        recorded_at: DateTime<Utc>,
+    },
+    AmbulanceUpdated {
+        ambulance_id: AmbulanceId,
+        hospital_id: HospitalId,
+        call_sign: String,
+        status: AmbulanceStatus,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        incident_id: Option<Uuid>,
+    },
+    PatientTriaged {
+        patient_id: PatientId,
+        hospital_id: HospitalId,
+        triage_level: TriageLevel,
+        previous_level: Option<TriageLevel>,
+        assessed_at: DateTime<Utc>,
+    },
+}
+
+impl LiveEvent {
+    /// Postgres notification channels carrying live events
+    pub const CHANNELS: [&'static str; 5] = [
+        "patient_status",
+        "bed_availability",
+        "patient_deterioration",
+        "ambulance_updates",
+        "patient_triage",
+    ];
+
+    /// `patient` has just left `previous_status`
+    pub fn patient_status_changed(patient: &Patient, previous_status: PatientStatus) -> Self {
+        LiveEvent::PatientStatusChanged {
+            patient_id: patient.id,
+            hospital_id: patient.hospital_id,
+            previous_status,
+            status: patient.status,
+            version: patient.version,
+        }
+    }
+
+    /// `hospital`'s bed counts have just changed
+    pub fn bed_availability_changed(hospital: &Hospital) -> Self {
+        LiveEvent::BedAvailabilityChanged {
+            hospital_id: hospital.id,
+            available_beds: hospital.available_beds,
+            total_beds: hospital.total_beds,
+        }
+    }
+
+    /// `vitals` recorded for `patient` show them deteriorating
+    pub fn patient_deteriorated(
+        patient: &Patient,
+        vitals: &PatientVitals,
+        findings: &[Deterioration],
+    ) -> Self {
+        LiveEvent::PatientDeteriorated {
+            patient_id: patient.id,
+            hospital_id: patient.hospital_id,
+            vitals_id: vitals.id,
+            findings: findings.to_vec(),
+            recorded_at: vitals.recorded_at,
+        }
+    }
+
+    /// `ambulance` has just changed status or reported its position
+    pub fn ambulance_updated(ambulance: &Ambulance) -> Self {
+        LiveEvent::AmbulanceUpdated {
+            ambulance_id: ambulance.id,
+            hospital_id: ambulance.hospital_id,
+            call_sign: ambulance.call_sign.clone(),
+            status: ambulance.status,
+            latitude: ambulance.latitude,
+            longitude: ambulance.longitude,
+            incident_id: ambulance.incident_id,
+        }
+    }
+
+    /// `assessment` has just been recorded, on registration or re-triage
+    pub fn patient_triaged(assessment: &TriageAssessment) -> Self {
+        LiveEvent::PatientTriaged {
+            patient_id: assessment.patient_id,
+            hospital_id: assessment.hospital_id,
+            triage_level: assessment.triage_level,
+            previous_level: assessment.previous_level,
+            assessed_at: assessment.assessed_at,
+        }
+    }
+
+    /// The notification channel this event is sent on
+    pub fn channel(&self) -> &'static str {
+        match self {
+            LiveEvent::PatientStatusChanged { .. } => Self::CHANNELS[0],
+            LiveEvent::BedAvailabilityChanged { .. } => Self::CHANNELS[1],
+            LiveEvent::PatientDeteriorated { .. } => Self::CHANNELS[2],
+            LiveEvent::AmbulanceUpdated { .. } => Self::CHANNELS[3],
+            LiveEvent::PatientTriaged { .. } => Self::CHANNELS[4],
+        }
+    }
+
+    /// The patient the event concerns, if it is about one
+    pub fn patient_id(&self) -> Option<PatientId> {
+        match self {
+            LiveEvent::PatientStatusChanged { patient_id, .. }
+            | LiveEvent::PatientDeteriorated { patient_id, .. }
+            | LiveEvent::PatientTriaged { patient_id, .. } => Some(*patient_id),
+            LiveEvent::BedAvailabilityChanged { .. } | LiveEvent::AmbulanceUpdated { .. } => None,
+        }
+    }
+
+    /// The hospital the event concerns, for scoping subscribers
+    pub fn hospital_id(&self) -> HospitalId {
+        match self {
+            LiveEvent::PatientStatusChanged { hospital_id, .. }
+            | LiveEvent::BedAvailabilityChanged { hospital_id, .. }
+            | LiveEvent::PatientDeteriorated { hospital_id, .. }
+            | LiveEvent::AmbulanceUpdated { hospital_id, .. }
+            | LiveEvent::PatientTriaged { hospital_id, .. } => *hospital_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_event_json() {
+        let hospital_id = HospitalId::new();
+        let event = LiveEvent::BedAvailabilityChanged {
+            hospital_id,
+            available_beds: 3,
+            total_beds: 40,
+        };
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "bed_availability_changed");
+        assert_eq!(json["available_beds"], 3);
+        assert_eq!(serde_json::from_value::<LiveEvent>(json).unwrap(), event);
+        assert_eq!(event.channel(), "bed_availability");
+        assert_eq!(event.hospital_id(), hospital_id);
+        assert_eq!(event.patient_id(), None);
+    }
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One schema migration known to the server or recorded in the database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationInfo {
+    pub version: i64,
+    pub description: String,
+    pub installed_on: Option<DateTime<Utc>>, // None while pending
+    pub success: Option<bool>,
+    pub modified: bool, // Applied from a script that has since changed
+}
+
+/// Applied and pending schema migrations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MigrationStatusResponse {
+    pub current_version: Option<i64>,
+    pub applied: Vec<MigrationInfo>,
+    pub pending: Vec<MigrationInfo>,
+}
+
+impl MigrationStatusResponse {
+    /// Check if every migration is applied, successfully and unchanged
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending.is_empty()
+            && self
+                .applied
+                .iter()
+                .all(|migration| migration.success == Some(true) && !migration.modified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migration(version: i64, success: Option<bool>) -> MigrationInfo {
+        MigrationInfo {
+            version,
+            description: format!("migration {}", version),
+            installed_on: success.map(|_| Utc::now()),
+            success,
+            modified: false,
+        }
+    }
+
+    #[test]
+    fn test_is_up_to_date() {
+        let mut status = MigrationStatusResponse {
+            current_version: Some(2),
+            applied: vec![migration(1, Some(true)), migration(2, Some(true))],
+            pending: Vec::new(),
+        };
+        assert!(status.is_up_to_date());
+
+        status.applied[1].modified = true;
+        assert!(!status.is_up_to_date());
+
+        status.applied[1] = migration(2, Some(false));
+        assert!(!status.is_up_to_date());
+
+        status.applied.pop();
+        status.pending.push(migration(2, None));
+        assert!(!status.is_up_to_date());
+    }
+}
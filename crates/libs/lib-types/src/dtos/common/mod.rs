@@ -0,0 +1,17 @@
+//! Shared DTOs
+
+pub mod cache_stats;
+pub mod dashboard;
+pub mod live_event;
+pub mod migration_status;
+pub mod pagination;
+
+pub use cache_stats::CacheStats;
+pub use dashboard::{
+    DashboardCommand, DashboardNotice, DashboardSubscription, MAX_DASHBOARD_HOSPITALS,
+};
+pub use live_event::LiveEvent;
+pub use migration_status::{MigrationInfo, MigrationStatusResponse};
+pub use pagination::{
+    Cursor, CursorPage, PageRequest, SortDirection, SortField, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+};
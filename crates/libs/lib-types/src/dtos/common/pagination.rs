@@ -0,0 +1,294 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::{Validate, ValidationErrors};
+
+pub const DEFAULT_PAGE_SIZE: u32 = 25;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Order of a sorted list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+
+    /// Comparison selecting the rows after a cursor
+    pub fn comparison(&self) -> &'static str {
+        match self {
+            SortDirection::Asc => ">",
+            SortDirection::Desc => "<",
+        }
+    }
+}
+
+/// A whitelisted sort column of a list. Each list has its own enum so that
+/// only known columns ever reach the SQL.
+pub trait SortField: Copy + Default + PartialEq + Serialize + DeserializeOwned {
+    /// The row being listed
+    type Item;
+
+    /// Column to order by; must be `NOT NULL`
+    fn column(&self) -> &'static str;
+
+    /// Postgres type of the column, used to cast a cursor value back
+    fn sql_type(&self) -> &'static str;
+
+    /// The item's value in this column, as stored in a cursor
+    fn value(&self, item: &Self::Item) -> String;
+
+    /// Unique id breaking ties between equal values
+    fn id(item: &Self::Item) -> Uuid;
+}
+
+/// Request for one page of a keyset-paginated list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageRequest<S> {
+    #[serde(default)]
+    pub cursor: Option<String>, // `next_cursor` of the previous page
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+    #[serde(default)]
+    pub sort: S,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+fn default_limit() -> u32 {
+    DEFAULT_PAGE_SIZE
+}
+
+impl<S: Default> Default for PageRequest<S> {
+    fn default() -> Self {
+        Self {
+            cursor: None,
+            limit: default_limit(),
+            sort: S::default(),
+            direction: SortDirection::default(),
+        }
+    }
+}
+
+impl<S: SortField> PageRequest<S> {
+    /// Decode the cursor, which must come from a page with the same sort
+    pub fn decode_cursor(&self) -> Result<Option<Cursor<S>>, String> {
+        let Some(ref encoded) = self.cursor else {
+            return Ok(None);
+        };
+        let cursor = Cursor::<S>::decode(encoded).ok_or_else(|| "Invalid cursor".to_string())?;
+        if cursor.sort != self.sort || cursor.direction != self.direction {
+            return Err("Cursor belongs to a list with a different sort".to_string());
+        }
+        Ok(Some(cursor))
+    }
+
+    /// Number of rows to fetch: one more than the limit, to tell whether
+    /// another page follows
+    pub fn fetch_limit(&self) -> i64 {
+        i64::from(self.limit) + 1
+    }
+}
+
+impl<S: SortField> Validate for PageRequest<S> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if !(1..=MAX_PAGE_SIZE).contains(&self.limit) {
+            errors.add_with_value(
+                "limit",
+                "out_of_range",
+                format!("Limit must be between 1 and {}", MAX_PAGE_SIZE),
+                self.limit,
+            );
+        }
+        if let Err(error) = self.decode_cursor() {
+            errors.add("cursor", "invalid", error);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Position after the last item of a page. Clients treat the encoded form
+/// as opaque.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor<S> {
+    pub sort: S,
+    pub direction: SortDirection,
+    pub value: String,
+    pub id: Uuid,
+}
+
+impl<S: SortField> Cursor<S> {
+    /// Cursor pointing just after `item`
+    pub fn after(item: &S::Item, sort: S, direction: SortDirection) -> Self {
+        Self {
+            sort,
+            direction,
+            value: sort.value(item),
+            id: S::id(item),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(encoded.trim()).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// One page of a list and the cursor of the next
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CursorPage<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T> CursorPage<T> {
+    /// Build a page from rows fetched with `PageRequest::fetch_limit`
+    pub fn from_rows<S: SortField>(
+        mut rows: Vec<S::Item>,
+        page: &PageRequest<S>,
+        map: impl Fn(&S::Item) -> T,
+    ) -> Self {
+        let has_more = rows.len() > page.limit as usize;
+        rows.truncate(page.limit as usize);
+
+        let next_cursor = rows
+            .last()
+            .filter(|_| has_more)
+            .map(|last| Cursor::after(last, page.sort, page.direction).encode());
+
+        Self {
+            items: rows.iter().map(map).collect(),
+            next_cursor,
+            has_more,
+        }
+    }
+}
+
+/// The serialized name of an enum value, e.g. for a cursor value
+pub(crate) fn enum_value<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum NumberSort {
+        #[default]
+        Value,
+        Square,
+    }
+
+    impl SortField for NumberSort {
+        type Item = (Uuid, i32);
+
+        fn column(&self) -> &'static str {
+            match self {
+                NumberSort::Value => "value",
+                NumberSort::Square => "square",
+            }
+        }
+
+        fn sql_type(&self) -> &'static str {
+            "integer"
+        }
+
+        fn value(&self, item: &Self::Item) -> String {
+            match self {
+                NumberSort::Value => item.1.to_string(),
+                NumberSort::Square => (item.1 * item.1).to_string(),
+            }
+        }
+
+        fn id(item: &Self::Item) -> Uuid {
+            item.0
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let page: PageRequest<NumberSort> = serde_json::from_str("{}").unwrap();
+        assert_eq!(page, PageRequest::default());
+        assert_eq!(page.limit, DEFAULT_PAGE_SIZE);
+        assert_eq!(page.fetch_limit(), i64::from(DEFAULT_PAGE_SIZE) + 1);
+        assert!(page.validate().is_ok());
+
+        let page: PageRequest<NumberSort> =
+            serde_json::from_str(r#"{"sort": "square", "direction": "desc"}"#).unwrap();
+        assert_eq!(page.sort.column(), "square");
+        assert_eq!(page.direction.as_sql(), "DESC");
+        assert!(serde_json::from_str::<PageRequest<NumberSort>>(r#"{"sort": "id; --"}"#).is_err());
+    }
+
+    #[test]
+    fn test_page_from_rows() {
+        let page = PageRequest::<NumberSort> {
+            limit: 2,
+            ..Default::default()
+        };
+
+        let rows: Vec<_> = (0..3).map(|n| (Uuid::new_v4(), n)).collect();
+        let last = rows[1];
+        let first = CursorPage::from_rows(rows, &page, |row| row.1);
+        assert_eq!(first.items, vec![0, 1]);
+        assert!(first.has_more);
+
+        let next = PageRequest {
+            cursor: first.next_cursor,
+            ..page
+        };
+        let cursor = next.decode_cursor().unwrap().unwrap();
+        assert_eq!(cursor.value, "1");
+        assert_eq!(cursor.id, last.0);
+
+        let last_page = CursorPage::from_rows(vec![(Uuid::new_v4(), 2)], &next, |row| row.1);
+        assert!(!last_page.has_more);
+        assert_eq!(last_page.next_cursor, None);
+    }
+
+    #[test]
+    fn test_rejects_bad_cursors() {
+        let mut page = PageRequest::<NumberSort> {
+            limit: MAX_PAGE_SIZE + 1,
+            cursor: Some("not a cursor".to_string()),
+            ..Default::default()
+        };
+        let errors = page.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "limit");
+        assert_eq!(errors[1].field, "cursor");
+
+        let row = (Uuid::new_v4(), 4);
+        page.limit = 10;
+        page.cursor = Some(Cursor::after(&row, NumberSort::Square, SortDirection::Asc).encode());
+        assert!(page.decode_cursor().unwrap_err().contains("different sort"));
+
+        page.sort = NumberSort::Square;
+        assert_eq!(page.decode_cursor().unwrap().unwrap().value, "16");
+    }
+}
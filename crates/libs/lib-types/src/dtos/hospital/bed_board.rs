@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::Bed;
+use crate::enums::{BedStatus, BedType};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{BedId, HospitalId, PatientId};
+
+/// Longest reason accepted for taking a bed out of service
+const MAX_REASON_LENGTH: usize = 500;
+
+/// A bed as shown on the bed board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BedResponse {
+    pub id: BedId,
+    pub hospital_id: HospitalId,
+    pub label: String, // e.g. "ER / 3 / B"
+    pub ward: String,
+    pub room: String,
+    pub bed_number: String,
+    pub bed_type: BedType,
+    pub status: BedStatus,
+    pub current_patient_id: Option<PatientId>,
+    pub updated_at: DateTime<Utc>,
+    pub version: i64, // Send back with a status change to detect concurrent edits
+}
+
+impl BedResponse {
+    pub fn from_bed(bed: &Bed) -> Self {
+        Self {
+            id: bed.id,
+            hospital_id: bed.hospital_id,
+            label: bed.label(),
+            ward: bed.ward.clone(),
+            room: bed.room.clone(),
+            bed_number: bed.bed_number.clone(),
+            bed_type: bed.bed_type,
+            status: bed.status,
+            current_patient_id: bed.current_patient_id,
+            updated_at: bed.updated_at,
+            version: bed.version,
+        }
+    }
+}
+
+/// Narrows a hospital's bed board to beds of one status
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BedListQuery {
+    #[serde(default)]
+    pub status: Option<BedStatus>,
+}
+
+/// Place a patient in a bed, admitting them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssignBedRequest {
+    pub patient_id: PatientId,
+}
+
+/// Take a bed out of service, e.g. for repair or a deep clean after an
+/// infectious patient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BedOutOfServiceRequest {
+    pub reason: String,
+    #[serde(default)]
+    pub version: Option<i64>,
+}
+
+impl Validate for BedOutOfServiceRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let reason = self.reason.trim();
+        if reason.is_empty() {
+            errors.add("reason", "required", "Reason is required");
+        } else if reason.chars().count() > MAX_REASON_LENGTH {
+            errors.add(
+                "reason",
+                "too_long",
+                format!("Reason cannot exceed {} characters", MAX_REASON_LENGTH),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_service_validation() {
+        let request = BedOutOfServiceRequest {
+            reason: "Broken bed rail".to_string(),
+            version: Some(3),
+        };
+        assert!(request.validate().is_ok());
+
+        let blank = BedOutOfServiceRequest {
+            reason: "  ".to_string(),
+            version: None,
+        };
+        assert!(blank.validate().unwrap_err().has_field("reason"));
+    }
+
+    #[test]
+    fn test_bed_response() {
+        let bed = Bed::new(
+            HospitalId::new(),
+            "ER".to_string(),
+            "3".to_string(),
+            "B".to_string(),
+            BedType::Emergency,
+        );
+        let response = BedResponse::from_bed(&bed);
+        assert_eq!(response.label, "ER / 3 / B");
+        assert_eq!(response.status, BedStatus::Available);
+    }
+}
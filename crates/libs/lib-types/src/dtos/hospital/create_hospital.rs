@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::location::GeoPoint;
+use lib_utils::validation::rules;
+
+use crate::entities::emergency_contact::is_valid_phone_number;
+use crate::entities::Hospital;
+use crate::enums::HospitalType;
+use crate::errors::{Validate, ValidationErrors};
+
+const MAX_NAME_LENGTH: usize = 200;
+const MAX_BEDS: i32 = 10_000;
+
+/// Register a hospital in the network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateHospitalRequest {
+    pub name: String,
+    pub license_number: String, // e.g. DHA-001
+    pub location: GeoPoint,     // {"lat", "lon"} or "latitude,longitude"
+    pub address: String,
+    pub phone_number: String,
+    pub email: String,
+    pub total_beds: i32,
+    pub available_beds: Option<i32>, // Defaults to all beds
+    #[serde(default)]
+    pub specialties: Vec<String>,
+    pub hospital_type: HospitalType,
+}
+
+impl CreateHospitalRequest {
+    /// Build the hospital record
+    pub fn to_hospital(&self) -> Hospital {
+        let mut hospital = Hospital::new(
+            self.name.trim().to_string(),
+            normalize_license_number(&self.license_number),
+            self.location,
+            self.address.trim().to_string(),
+            self.phone_number.trim().to_string(),
+            self.email.trim().to_lowercase(),
+            self.total_beds,
+            normalize_specialties(&self.specialties),
+            self.hospital_type,
+        );
+        if let Some(available_beds) = self.available_beds {
+            hospital.available_beds = available_beds;
+        }
+        hospital
+    }
+}
+
+impl Validate for CreateHospitalRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        validate_name(&mut errors, &self.name);
+        validate_license_number(&mut errors, &self.license_number);
+        validate_location(&mut errors, &self.location);
+        rules::required(&mut errors, "address", &self.address, "Address is required");
+        validate_phone_number(&mut errors, &self.phone_number);
+        validate_email(&mut errors, &self.email);
+        validate_beds(&mut errors, self.total_beds, self.available_beds);
+        validate_specialties(&mut errors, &self.specialties);
+
+        errors.into_result()
+    }
+}
+
+/// Licenses are an issuing authority code and a number, e.g. `DHA-001`
+pub(crate) fn is_valid_license_number(license_number: &str) -> bool {
+    match license_number.trim().split_once('-') {
+        Some((authority, number)) => {
+            (2..=5).contains(&authority.len())
+                && authority.chars().all(|c| c.is_ascii_alphabetic())
+                && (3..=12).contains(&number.len())
+                && number.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        None => false,
+    }
+}
+
+pub(crate) fn normalize_license_number(license_number: &str) -> String {
+    license_number.trim().to_uppercase()
+}
+
+/// Trim specialties and drop blanks and case-insensitive duplicates
+pub(crate) fn normalize_specialties(specialties: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(specialties.len());
+    for specialty in specialties.iter().map(|s| s.trim()) {
+        if !specialty.is_empty() && !normalized.iter().any(|s| s.eq_ignore_ascii_case(specialty)) {
+            normalized.push(specialty.to_string());
+        }
+    }
+    normalized
+}
+
+pub(crate) fn validate_name(errors: &mut ValidationErrors, name: &str) {
+    if rules::required(errors, "name", name, "Hospital name is required") {
+        rules::max_length(
+            errors,
+            "name",
+            name,
+            MAX_NAME_LENGTH,
+            format!(
+                "Hospital name must be at most {} characters",
+                MAX_NAME_LENGTH
+            ),
+        );
+    }
+}
+
+pub(crate) fn validate_license_number(errors: &mut ValidationErrors, license_number: &str) {
+    if !is_valid_license_number(license_number) {
+        errors.add(
+            "license_number",
+            "invalid_format",
+            "License number must look like AUTHORITY-NUMBER, e.g. DHA-001",
+        );
+    }
+}
+
+pub(crate) fn validate_location(errors: &mut ValidationErrors, location: &GeoPoint) {
+    if !location.is_valid() {
+        errors.add_with_value(
+            "location",
+            "out_of_range",
+            "Latitude must be between -90 and 90 and longitude between -180 and 180",
+            location,
+        );
+    }
+}
+
+pub(crate) fn validate_phone_number(errors: &mut ValidationErrors, phone_number: &str) {
+    if !is_valid_phone_number(phone_number) {
+        errors.add(
+            "phone_number",
+            "invalid_format",
+            "Phone must be 7-15 digits with an optional leading +",
+        );
+    }
+}
+
+pub(crate) fn validate_email(errors: &mut ValidationErrors, email: &str) {
+    if !is_valid_email(email.trim()) {
+        errors.add("email", "invalid_format", "Invalid email format");
+    }
+}
+
+pub(crate) fn validate_beds(
+    errors: &mut ValidationErrors,
+    total_beds: i32,
+    available_beds: Option<i32>,
+) {
+    rules::range(
+        errors,
+        "total_beds",
+        total_beds,
+        0..=MAX_BEDS,
+        format!("Total beds must be between 0 and {}", MAX_BEDS),
+    );
+    if let Some(available) = available_beds.filter(|a| !(0..=total_beds).contains(a)) {
+        errors.add_with_value(
+            "available_beds",
+            "out_of_range",
+            "Available beds must be between 0 and the total beds",
+            available,
+        );
+    }
+}
+
+pub(crate) fn validate_specialties(errors: &mut ValidationErrors, specialties: &[String]) {
+    if specialties.iter().any(|s| s.trim().is_empty()) {
+        errors.add("specialties", "required", "Specialties cannot be empty");
+    }
+}
+
+fn is_valid_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty() && domain.contains('.') && !domain.starts_with('.')
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> CreateHospitalRequest {
+        CreateHospitalRequest {
+            name: "Rashid Hospital".to_string(),
+            license_number: "dha-002".to_string(),
+            location: GeoPoint::new(25.2350, 55.3150).unwrap(),
+            address: "Oud Metha, Dubai, UAE".to_string(),
+            phone_number: "+97142192000".to_string(),
+            email: "ER@Rashid.ae".to_string(),
+            total_beds: 120,
+            available_beds: Some(40),
+            specialties: vec![
+                "Trauma".to_string(),
+                " trauma ".to_string(),
+                "Neurosurgery".to_string(),
+            ],
+            hospital_type: HospitalType::Public,
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        let request = request();
+        assert!(request.validate().is_ok());
+
+        let hospital = request.to_hospital();
+        assert_eq!(hospital.license_number, "DHA-002");
+        assert_eq!(hospital.email, "er@rashid.ae");
+        assert_eq!(hospital.available_beds, 40);
+        assert_eq!(hospital.get_specialties(), vec!["Trauma", "Neurosurgery"]);
+    }
+
+    #[test]
+    fn test_invalid_request() {
+        let mut request = request();
+        request.license_number = "12345".to_string();
+        request.location = GeoPoint {
+            lat: 125.2,
+            lon: 55.3,
+        };
+        request.total_beds = 10;
+        request.available_beds = Some(40);
+        request.email = "rashid.ae".to_string();
+
+        let errors = request.validate().unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert_eq!(errors[0].field, "license_number");
+        assert!(errors[0].message.contains("License number"));
+        assert_eq!(errors[3].rejected_value, Some(serde_json::json!(40)));
+    }
+
+    #[test]
+    fn test_license_format() {
+        assert!(is_valid_license_number("DHA-001"));
+        assert!(is_valid_license_number("MOHAP-A12345"));
+        assert!(!is_valid_license_number("D-001"));
+        assert!(!is_valid_license_number("DHA-0 1"));
+        assert!(!is_valid_license_number("DHA001"));
+    }
+}
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::location::GeoPoint;
+
+use crate::dtos::SortField;
+use crate::entities::Hospital;
+use crate::enums::HospitalStatus;
+use crate::errors::{Validate, ValidationErrors};
+
+/// Longest specialty name a hospital list can be filtered by
+const MAX_SPECIALTY_LENGTH: usize = 100;
+
+/// Position hospital lists measure distances and travel times from
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HospitalDistanceQuery {
+    #[serde(default)]
+    pub near: Option<GeoPoint>, // "latitude,longitude"
+}
+
+impl Validate for HospitalDistanceQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(near) = self.near.filter(|near| !near.is_valid()) {
+            errors.add_with_value(
+                "near",
+                "out_of_range",
+                "Latitude must be between -90 and 90 and longitude between -180 and 180",
+                near,
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Narrows a hospital list, e.g. to those able to take a cardiac patient now
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HospitalFilter {
+    #[serde(default)]
+    pub status: Option<HospitalStatus>,
+    #[serde(default)]
+    pub specialty: Option<String>, // Matched ignoring case
+    #[serde(default)]
+    pub min_available_beds: Option<i32>,
+}
+
+impl HospitalFilter {
+    /// The specialty to match, trimmed, if one is given
+    pub fn specialty(&self) -> Option<&str> {
+        self.specialty
+            .as_deref()
+            .map(str::trim)
+            .filter(|specialty| !specialty.is_empty())
+    }
+}
+
+impl Validate for HospitalFilter {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self
+            .specialty()
+            .is_some_and(|specialty| specialty.chars().count() > MAX_SPECIALTY_LENGTH)
+        {
+            errors.add(
+                "specialty",
+                "too_long",
+                format!(
+                    "Specialty cannot exceed {} characters",
+                    MAX_SPECIALTY_LENGTH
+                ),
+            );
+        }
+        if let Some(beds) = self.min_available_beds.filter(|&beds| beds < 0) {
+            errors.add_with_value(
+                "min_available_beds",
+                "out_of_range",
+                "Minimum available beds cannot be negative",
+                beds,
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Columns hospital lists can be sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HospitalSortField {
+    #[default]
+    Name,
+    AvailableBeds,
+    CreatedAt,
+}
+
+impl SortField for HospitalSortField {
+    type Item = Hospital;
+
+    fn column(&self) -> &'static str {
+        match self {
+            HospitalSortField::Name => "name",
+            HospitalSortField::AvailableBeds => "available_beds",
+            HospitalSortField::CreatedAt => "created_at",
+        }
+    }
+
+    fn sql_type(&self) -> &'static str {
+        match self {
+            HospitalSortField::Name => "text",
+            HospitalSortField::AvailableBeds => "integer",
+            HospitalSortField::CreatedAt => "timestamptz",
+        }
+    }
+
+    fn value(&self, hospital: &Hospital) -> String {
+        match self {
+            HospitalSortField::Name => hospital.name.clone(),
+            HospitalSortField::AvailableBeds => hospital.available_beds.to_string(),
+            HospitalSortField::CreatedAt => hospital.created_at.to_rfc3339(),
+        }
+    }
+
+    fn id(hospital: &Hospital) -> Uuid {
+        hospital.id.as_uuid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_validation() {
+        let filter = HospitalFilter {
+            status: Some(HospitalStatus::Active),
+            specialty: Some("  Cardiology ".to_string()),
+            min_available_beds: Some(1),
+        };
+        assert!(filter.validate().is_ok());
+        assert_eq!(filter.specialty(), Some("Cardiology"));
+
+        let filter = HospitalFilter {
+            specialty: Some("x".repeat(101)),
+            min_available_beds: Some(-1),
+            ..Default::default()
+        };
+        assert_eq!(filter.validate().unwrap_err().len(), 2);
+        assert_eq!(HospitalFilter::default().specialty(), None);
+    }
+}
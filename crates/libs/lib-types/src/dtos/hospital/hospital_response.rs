@@ -1,25 +1,30 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
+
+use lib_utils::location::{EtaEstimator, GeoPoint};
 
 use crate::entities::Hospital;
+use crate::enums::{HospitalStatus, HospitalType};
+use crate::ids::HospitalId;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HospitalResponse {
-    pub id: Uuid,
+    pub id: HospitalId,
     pub name: String,
+    pub location: GeoPoint,
     pub address: String,
     pub phone_number: String,
     pub email: String,
     pub total_beds: i32,
     pub available_beds: i32,
     pub specialties: Vec<String>,
-    pub hospital_type: String,
-    pub status: String,
+    pub hospital_type: HospitalType,
+    pub status: HospitalStatus,
     pub capacity_status: CapacityStatus,
     pub distance_km: Option<f64>, // Distance from user's location
     pub eta_minutes: Option<i32>, // Estimated time of arrival
     pub created_at: DateTime<Utc>,
+    pub version: i64, // Send back with an update to detect concurrent edits
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -38,45 +43,71 @@ pub struct HospitalListResponse {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HospitalSummary {
-    pub id: Uuid,
+    pub id: HospitalId,
     pub name: String,
+    pub location: GeoPoint,
     pub available_beds: i32,
     pub total_beds: i32,
     pub occupancy_percentage: f64,
-    pub status: String,
+    pub status: HospitalStatus,
+    pub capacity_status: CapacityStatus,
     pub distance_km: Option<f64>,
     pub eta_minutes: Option<i32>,
     pub has_specialty: Option<bool>, // If filtering by specialty
 }
 
-impl HospitalResponse {
-    /// Create from Hospital entity
+impl CapacityStatus {
+    /// Describe how full a hospital is and whether it takes new patients
     pub fn from_hospital(hospital: &Hospital) -> Self {
-        let capacity_status = CapacityStatus {
+        Self {
             occupancy_percentage: hospital.occupancy_percentage(),
             status_text: hospital.capacity_status().to_string(),
             status_color: hospital.capacity_color().to_string(),
-            is_accepting_patients: hospital.has_available_beds() && hospital.status == "Active",
-        };
+            is_accepting_patients: hospital.has_available_beds()
+                && hospital.status.is_accepting_patients(),
+        }
+    }
+}
+
+impl HospitalResponse {
+    /// Create from Hospital entity
+    pub fn from_hospital(hospital: &Hospital) -> Self {
+        let capacity_status = CapacityStatus::from_hospital(hospital);
 
         Self {
             id: hospital.id,
             name: hospital.name.clone(),
+            location: hospital.location,
             address: hospital.address.clone(),
             phone_number: hospital.phone_number.clone(),
             email: hospital.email.clone(),
             total_beds: hospital.total_beds,
             available_beds: hospital.available_beds,
             specialties: hospital.get_specialties(),
-            hospital_type: hospital.hospital_type.clone(),
-            status: hospital.status.clone(),
+            hospital_type: hospital.hospital_type,
+            status: hospital.status,
             capacity_status,
             distance_km: None, // Set by service layer
             eta_minutes: None, // Set by service layer
             created_at: hospital.created_at,
+            version: hospital.version,
         }
     }
 
+    /// Set the distance from the caller's position
+    pub fn with_distance_from(mut self, origin: &GeoPoint) -> Self {
+        self.distance_km = Some(self.location.distance_km(origin));
+        self
+    }
+
+    /// Set the travel time for the distance, when one is set, leaving at `at`
+    pub fn with_eta(mut self, eta: &EtaEstimator, at: DateTime<Utc>) -> Self {
+        self.eta_minutes = self
+            .distance_km
+            .map(|distance| eta.estimate_minutes(distance, at));
+        self
+    }
+
     /// Check if hospital can accept new patients
     pub fn can_accept_patients(&self) -> bool {
         self.capacity_status.is_accepting_patients
@@ -107,16 +138,32 @@ impl HospitalSummary {
         Self {
             id: hospital.id,
             name: hospital.name.clone(),
+            location: hospital.location,
             available_beds: hospital.available_beds,
             total_beds: hospital.total_beds,
             occupancy_percentage: hospital.occupancy_percentage(),
-            status: hospital.status.clone(),
+            status: hospital.status,
+            capacity_status: CapacityStatus::from_hospital(hospital),
             distance_km: None, // Set by service layer
             eta_minutes: None, // Set by service layer
             has_specialty: None, // Set when filtering
         }
     }
 
+    /// Set the distance from the caller's position
+    pub fn with_distance_from(mut self, origin: &GeoPoint) -> Self {
+        self.distance_km = Some(self.location.distance_km(origin));
+        self
+    }
+
+    /// Set the travel time for the distance, when one is set, leaving at `at`
+    pub fn with_eta(mut self, eta: &EtaEstimator, at: DateTime<Utc>) -> Self {
+        self.eta_minutes = self
+            .distance_km
+            .map(|distance| eta.estimate_minutes(distance, at));
+        self
+    }
+
     /// Get capacity indicator
     pub fn capacity_indicator(&self) -> &str {
         if self.available_beds == 0 {
@@ -179,13 +226,13 @@ mod tests {
         Hospital::new(
             "Dubai Hospital".to_string(),
             "DHA-001".to_string(),
-            "25.2697,55.3094".to_string(),
+            GeoPoint::new(25.2697, 55.3094).unwrap(),
             "Oud Metha, Dubai, UAE".to_string(),
             "+97143193000".to_string(),
             "info@dubaihospital.ae".to_string(),
             100,
             vec!["Emergency Medicine".to_string(), "Cardiology".to_string()],
-            "Public".to_string(),
+            HospitalType::Public,
         )
     }
 
@@ -268,6 +315,27 @@ mod tests {
         assert_eq!(sorted.hospitals[2].name, "Hospital B");
     }
 
+    #[test]
+    fn test_sort_by_distance() {
+        let near = create_test_hospital();
+        let mut far = create_test_hospital();
+        far.name = "Al Qassimi Hospital".to_string();
+        far.location = GeoPoint::new(25.3463, 55.4209).unwrap();
+
+        let caller = GeoPoint::new(25.2048, 55.2708).unwrap();
+        let (eta, now) = (EtaEstimator::default(), Utc::now());
+        let sorted = HospitalListResponse::new(vec![
+            HospitalSummary::from_hospital(&far).with_distance_from(&caller).with_eta(&eta, now),
+            HospitalSummary::from_hospital(&near).with_distance_from(&caller).with_eta(&eta, now),
+        ])
+        .sort_by_distance();
+
+        assert_eq!(sorted.hospitals[0].name, "Dubai Hospital");
+        assert!(sorted.hospitals[0].distance_km < sorted.hospitals[1].distance_km);
+        assert!(sorted.hospitals[0].eta_minutes < sorted.hospitals[1].eta_minutes);
+        assert_eq!(HospitalSummary::from_hospital(&near).with_eta(&eta, now).eta_minutes, None);
+    }
+
     #[test]
     fn test_serialization() {
         let hospital = create_test_hospital();
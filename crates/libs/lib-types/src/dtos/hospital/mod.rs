@@ -1,3 +1,13 @@
+pub mod bed_board;
+pub mod create_hospital;
+pub mod hospital_query;
 pub mod hospital_response;
+pub mod update_capacity;
+pub mod update_hospital;
 
-pub use hospital_response::{HospitalResponse, HospitalSummary, HospitalListResponse, CapacityStatus};
\ No newline at end of file
+pub use bed_board::{AssignBedRequest, BedListQuery, BedOutOfServiceRequest, BedResponse};
+pub use create_hospital::CreateHospitalRequest;
+pub use hospital_query::{HospitalDistanceQuery, HospitalFilter, HospitalSortField};
+pub use hospital_response::{HospitalResponse, HospitalSummary, HospitalListResponse, CapacityStatus};
+pub use update_capacity::UpdateCapacityRequest;
+pub use update_hospital::UpdateHospitalRequest;
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use super::UpdateHospitalRequest;
+use crate::enums::HospitalStatus;
+use crate::errors::{Validate, ValidationErrors};
+
+/// Change to the beds a hospital declares and whether it is taking
+/// patients, made from the capacity dashboard. Absent fields are left
+/// unchanged; `version` works as in `UpdateHospitalRequest`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateCapacityRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_beds: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_beds: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<HospitalStatus>, // e.g. emergency only while the ER is overwhelmed
+}
+
+impl UpdateCapacityRequest {
+    /// The hospital update this amounts to
+    pub fn to_update(&self) -> UpdateHospitalRequest {
+        UpdateHospitalRequest {
+            version: self.version,
+            total_beds: self.total_beds,
+            available_beds: self.available_beds,
+            status: self.status,
+            ..Default::default()
+        }
+    }
+}
+
+impl Validate for UpdateCapacityRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.to_update().validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        assert!(UpdateCapacityRequest::default().validate().is_err());
+
+        let request = UpdateCapacityRequest {
+            available_beds: Some(3),
+            status: Some(HospitalStatus::EmergencyOnly),
+            ..Default::default()
+        };
+        assert!(request.validate().is_ok());
+        assert_eq!(request.to_update().available_beds, Some(3));
+        assert_eq!(request.to_update().name, None);
+
+        let request = UpdateCapacityRequest {
+            total_beds: Some(10),
+            available_beds: Some(12),
+            ..Default::default()
+        };
+        assert!(request.validate().is_err());
+    }
+}
@@ -0,0 +1,265 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::location::GeoPoint;
+
+use super::create_hospital::{
+    normalize_license_number, normalize_specialties, validate_beds, validate_email,
+    validate_license_number, validate_location, validate_name, validate_phone_number,
+    validate_specialties,
+};
+use crate::entities::Hospital;
+use crate::enums::{HospitalStatus, HospitalType};
+use crate::errors::{HospitalError, Validate, ValidationErrors};
+
+/// Partial update of a hospital. Absent fields are left unchanged. When
+/// `version` is given, the update is rejected if the hospital has changed
+/// since the client read it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdateHospitalRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoPoint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_beds: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub available_beds: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub specialties: Option<Vec<String>>, // Replaces the whole list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hospital_type: Option<HospitalType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<HospitalStatus>,
+}
+
+impl UpdateHospitalRequest {
+    /// Check if the request changes nothing
+    pub fn is_empty(&self) -> bool {
+        *self
+            == Self {
+                version: self.version,
+                ..Self::default()
+            }
+    }
+
+    /// Apply the update to a hospital, returning the names of the fields
+    /// that changed
+    pub fn apply_to(&self, hospital: &mut Hospital) -> Result<Vec<&'static str>, HospitalError> {
+        let mut changed = Vec::new();
+
+        let total_beds = self.total_beds.unwrap_or(hospital.total_beds);
+        let available_beds = self.available_beds.unwrap_or(hospital.available_beds);
+        if (total_beds, available_beds) != (hospital.total_beds, hospital.available_beds) {
+            if total_beds != hospital.total_beds {
+                changed.push("total_beds");
+            }
+            if available_beds != hospital.available_beds {
+                changed.push("available_beds");
+            }
+            hospital.update_capacity(total_beds, available_beds)?;
+        }
+
+        let trimmed = |value: &String| value.trim().to_string();
+        set(
+            &mut changed,
+            "name",
+            &mut hospital.name,
+            self.name.as_ref().map(trimmed),
+        );
+        set(
+            &mut changed,
+            "license_number",
+            &mut hospital.license_number,
+            self.license_number.as_deref().map(normalize_license_number),
+        );
+        set(
+            &mut changed,
+            "location",
+            &mut hospital.location,
+            self.location,
+        );
+        set(
+            &mut changed,
+            "address",
+            &mut hospital.address,
+            self.address.as_ref().map(trimmed),
+        );
+        set(
+            &mut changed,
+            "phone_number",
+            &mut hospital.phone_number,
+            self.phone_number.as_ref().map(trimmed),
+        );
+        set(
+            &mut changed,
+            "email",
+            &mut hospital.email,
+            self.email.as_ref().map(|email| email.trim().to_lowercase()),
+        );
+        set(
+            &mut changed,
+            "specialties",
+            &mut hospital.specialties,
+            self.specialties
+                .as_deref()
+                .map(|specialties| serde_json::Value::from(normalize_specialties(specialties))),
+        );
+        set(
+            &mut changed,
+            "hospital_type",
+            &mut hospital.hospital_type,
+            self.hospital_type,
+        );
+        set(&mut changed, "status", &mut hospital.status, self.status);
+
+        if !changed.is_empty() {
+            hospital.updated_at = chrono::Utc::now();
+        }
+        Ok(changed)
+    }
+}
+
+impl Validate for UpdateHospitalRequest {
+    /// Bed counts are checked against the stored hospital by `apply_to`
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.is_empty() {
+            errors.add("", "required", "No fields to update");
+        }
+        if let Some(ref name) = self.name {
+            validate_name(&mut errors, name);
+        }
+        if let Some(ref license_number) = self.license_number {
+            validate_license_number(&mut errors, license_number);
+        }
+        if let Some(ref location) = self.location {
+            validate_location(&mut errors, location);
+        }
+        if self
+            .address
+            .as_ref()
+            .is_some_and(|address| address.trim().is_empty())
+        {
+            errors.add("address", "required", "Address cannot be empty");
+        }
+        if let Some(ref phone_number) = self.phone_number {
+            validate_phone_number(&mut errors, phone_number);
+        }
+        if let Some(ref email) = self.email {
+            validate_email(&mut errors, email);
+        }
+        if let Some(total_beds) = self.total_beds {
+            validate_beds(&mut errors, total_beds, self.available_beds);
+        } else if let Some(available) = self.available_beds.filter(|&a| a < 0) {
+            errors.add_with_value(
+                "available_beds",
+                "out_of_range",
+                "Available beds cannot be negative",
+                available,
+            );
+        }
+        if let Some(ref specialties) = self.specialties {
+            validate_specialties(&mut errors, specialties);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Overwrite `target` with a requested value, recording the field if it changed
+fn set<T: PartialEq>(
+    changed: &mut Vec<&'static str>,
+    field: &'static str,
+    target: &mut T,
+    value: Option<T>,
+) {
+    if let Some(value) = value {
+        if *target != value {
+            *target = value;
+            changed.push(field);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_hospital() -> Hospital {
+        Hospital::new(
+            "Dubai Hospital".to_string(),
+            "DHA-001".to_string(),
+            GeoPoint::new(25.2697, 55.3094).unwrap(),
+            "Oud Metha, Dubai, UAE".to_string(),
+            "+97143193000".to_string(),
+            "info@dubaihospital.ae".to_string(),
+            100,
+            vec!["Emergency Medicine".to_string()],
+            HospitalType::Public,
+        )
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(UpdateHospitalRequest::default().validate().is_err());
+
+        let request = UpdateHospitalRequest {
+            email: Some("ops@dubaihospital.ae".to_string()),
+            status: Some(HospitalStatus::EmergencyOnly),
+            ..Default::default()
+        };
+        assert!(request.validate().is_ok());
+
+        let request = UpdateHospitalRequest {
+            phone_number: Some("12".to_string()),
+            location: Some(GeoPoint {
+                lat: 25.2,
+                lon: 255.3,
+            }),
+            total_beds: Some(-1),
+            ..Default::default()
+        };
+        assert_eq!(request.validate().unwrap_err().len(), 3);
+    }
+
+    #[test]
+    fn test_apply_reports_changed_fields() {
+        let mut hospital = create_test_hospital();
+        let request = UpdateHospitalRequest {
+            name: Some("Dubai Hospital".to_string()),
+            total_beds: Some(150),
+            status: Some(HospitalStatus::Maintenance),
+            ..Default::default()
+        };
+
+        let changed = request.apply_to(&mut hospital).unwrap();
+        assert_eq!(changed, vec!["total_beds", "status"]);
+        assert_eq!(hospital.total_beds, 150);
+        assert_eq!(hospital.available_beds, 100);
+    }
+
+    #[test]
+    fn test_rejects_shrinking_below_available_beds() {
+        let mut hospital = create_test_hospital();
+        let request = UpdateHospitalRequest {
+            total_beds: Some(50),
+            ..Default::default()
+        };
+        assert!(request.validate().is_ok());
+        assert_eq!(
+            request.apply_to(&mut hospital),
+            Err(HospitalError::InvalidCapacityUpdate { requested: 100 })
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{IncidentSeverity, IncidentType};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{HospitalId, UserId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateIncidentRequest {
+    pub incident_type: IncidentType,
+    pub severity: IncidentSeverity,
+    pub location: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub description: Option<String>,
+    pub commander_id: Option<UserId>,
+    pub hospital_ids: Option<Vec<HospitalId>>, // Receiving hospitals besides the caller's own
+}
+
+impl Validate for CreateIncidentRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.location.trim().is_empty() {
+            errors.add("location", "required", "Incident location is required");
+        }
+
+        match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => {
+                if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+                    errors.add_with_value(
+                        "latitude",
+                        "out_of_range",
+                        "Incident GPS position is out of range",
+                        (latitude, longitude),
+                    );
+                }
+            }
+            (None, None) => {}
+            _ => errors.add(
+                "longitude",
+                "required",
+                "Latitude and longitude must be given together",
+            ),
+        }
+
+        if self
+            .description
+            .as_ref()
+            .is_some_and(|description| description.len() > 2000)
+        {
+            errors.add(
+                "description",
+                "too_long",
+                "Description must be at most 2000 characters",
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_valid_request() -> CreateIncidentRequest {
+        CreateIncidentRequest {
+            incident_type: IncidentType::TrafficCollision,
+            severity: IncidentSeverity::MassCasualty,
+            location: "Sheikh Zayed Road, Interchange 4".to_string(),
+            latitude: Some(25.1124),
+            longitude: Some(55.1390),
+            description: Some("Bus and two cars, multiple trapped".to_string()),
+            commander_id: None,
+            hospital_ids: None,
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        assert!(create_valid_request().validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_request() {
+        let mut request = create_valid_request();
+        request.location = " ".to_string();
+        request.latitude = None;
+
+        let errors = request.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.has_field("location"));
+        assert!(errors.iter().any(|e| e.message.contains("together")));
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::IncidentStatus;
+
+/// Query parameters for listing incidents
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IncidentListQuery {
+    pub status: Option<IncidentStatus>,
+}
@@ -0,0 +1,188 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::location::{GeoPoint, ZoneRegistry};
+
+use crate::entities::EmergencyIncident;
+use crate::enums::{IncidentSeverity, IncidentStatus, IncidentType};
+use crate::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncidentResponse {
+    pub id: Uuid,
+    pub incident_type: IncidentType,
+    pub severity: IncidentSeverity,
+    pub status: IncidentStatus,
+    pub location: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub description: Option<String>,
+    pub commander_id: Option<UserId>,
+    pub coordinating_hospital_id: HospitalId,
+    pub hospital_ids: Vec<HospitalId>,
+    pub ambulance_ids: Vec<AmbulanceId>,
+    pub patient_ids: Vec<PatientId>,
+    pub casualty_count: usize,
+    pub is_mass_casualty: bool,
+    pub needs_escalation: bool,
+    pub requires_decontamination: bool,
+    pub catchment_zone: Option<CatchmentZone>, // Set when catchment zones are configured
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The DHA catchment zone an incident is in, or the nearest one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatchmentZone {
+    pub id: String,
+    pub name: String,
+    pub distance_km: f64, // 0 inside the zone
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncidentListResponse {
+    pub incidents: Vec<IncidentResponse>,
+    pub open_count: usize,
+    pub mass_casualty_count: usize,
+}
+
+impl IncidentResponse {
+    /// Create from EmergencyIncident entity
+    pub fn from_incident(incident: &EmergencyIncident) -> Self {
+        Self {
+            id: incident.id,
+            incident_type: incident.incident_type,
+            severity: incident.severity,
+            status: incident.status,
+            location: incident.location.clone(),
+            latitude: incident.latitude,
+            longitude: incident.longitude,
+            description: incident.description.clone(),
+            commander_id: incident.commander_id,
+            coordinating_hospital_id: incident.coordinating_hospital_id,
+            hospital_ids: incident.hospital_ids.clone(),
+            ambulance_ids: incident.ambulance_ids.clone(),
+            patient_ids: incident.patient_ids.clone(),
+            casualty_count: incident.casualty_count(),
+            is_mass_casualty: incident.is_mass_casualty(),
+            needs_escalation: incident.needs_escalation(),
+            requires_decontamination: incident.incident_type.requires_decontamination(),
+            catchment_zone: None,
+            closed_at: incident.closed_at,
+            created_at: incident.created_at,
+            updated_at: incident.updated_at,
+        }
+    }
+
+    /// Classify the incident position into a catchment zone, see
+    /// [`ZoneRegistry::classify`]
+    pub fn with_catchment_zone(mut self, zones: &ZoneRegistry, max_distance_km: f64) -> Self {
+        let position = match (self.latitude, self.longitude) {
+            (Some(latitude), Some(longitude)) => GeoPoint::new(latitude, longitude).ok(),
+            _ => None,
+        };
+        self.catchment_zone = position
+            .and_then(|position| zones.classify(&position, max_distance_km))
+            .map(|found| CatchmentZone {
+                id: found.zone.id.clone(),
+                name: found.zone.name.clone(),
+                distance_km: found.distance_km,
+            });
+        self
+    }
+}
+
+impl IncidentListResponse {
+    /// Create from incident entities, counting open and mass-casualty incidents
+    pub fn from_incidents(incidents: &[EmergencyIncident]) -> Self {
+        Self {
+            incidents: incidents
+                .iter()
+                .map(IncidentResponse::from_incident)
+                .collect(),
+            open_count: incidents.iter().filter(|i| i.is_open()).count(),
+            mass_casualty_count: incidents
+                .iter()
+                .filter(|i| i.is_open() && i.is_mass_casualty())
+                .count(),
+        }
+    }
+
+    /// Classify every incident into a catchment zone
+    pub fn with_catchment_zones(mut self, zones: &ZoneRegistry, max_distance_km: f64) -> Self {
+        self.incidents = self
+            .incidents
+            .into_iter()
+            .map(|incident| incident.with_catchment_zone(zones, max_distance_km))
+            .collect();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_response() {
+        let hospital_id = HospitalId::new();
+        let mass_casualty = EmergencyIncident::new(
+            IncidentType::HazardousMaterials,
+            IncidentSeverity::MassCasualty,
+            "Jebel Ali Port, Gate 5".to_string(),
+            hospital_id,
+            UserId::new(),
+        );
+        let mut closed = EmergencyIncident::new(
+            IncidentType::Fire,
+            IncidentSeverity::Minor,
+            "Al Quoz Industrial 3".to_string(),
+            hospital_id,
+            UserId::new(),
+        );
+        closed.update_status(IncidentStatus::Closed).unwrap();
+
+        let response = IncidentListResponse::from_incidents(&[mass_casualty.clone(), closed]);
+        assert_eq!(response.incidents.len(), 2);
+        assert_eq!(response.open_count, 1);
+        assert_eq!(response.mass_casualty_count, 1);
+        assert!(response.incidents[0].requires_decontamination);
+        assert_eq!(
+            response.incidents[0],
+            IncidentResponse::from_incident(&mass_casualty)
+        );
+    }
+
+    #[test]
+    fn test_catchment_zone() {
+        let zones = ZoneRegistry::from_geojson(
+            r#"{"type": "FeatureCollection", "features": [{"type": "Feature",
+                "properties": {"id": "deira", "name": "Deira"},
+                "geometry": {"type": "Polygon", "coordinates":
+                    [[[55.30, 25.25], [55.35, 25.25], [55.35, 25.30], [55.30, 25.30]]]}}]}"#,
+        )
+        .unwrap();
+        let mut incident = EmergencyIncident::new(
+            IncidentType::TrafficCollision,
+            IncidentSeverity::Major,
+            "Al Maktoum Bridge".to_string(),
+            HospitalId::new(),
+            UserId::new(),
+        );
+
+        let response = IncidentResponse::from_incident(&incident).with_catchment_zone(&zones, 5.0);
+        assert_eq!(response.catchment_zone, None);
+
+        incident.set_position(25.27, 55.32).unwrap();
+        let response = IncidentResponse::from_incident(&incident).with_catchment_zone(&zones, 5.0);
+        let zone = response.catchment_zone.unwrap();
+        assert_eq!((zone.id.as_str(), zone.name.as_str()), ("deira", "Deira"));
+        assert_eq!(zone.distance_km, 0.0);
+
+        incident.set_position(25.5, 55.32).unwrap();
+        let response = IncidentResponse::from_incident(&incident).with_catchment_zone(&zones, 5.0);
+        assert_eq!(response.catchment_zone, None);
+    }
+}
@@ -0,0 +1,14 @@
+//! Emergency incident DTOs
+
+pub mod create_incident;
+pub mod incident_query;
+pub mod incident_response;
+pub mod update_incident;
+
+pub use create_incident::CreateIncidentRequest;
+pub use incident_query::IncidentListQuery;
+pub use incident_response::{CatchmentZone, IncidentListResponse, IncidentResponse};
+pub use update_incident::{
+    AssignCommanderRequest, LinkIncidentResourcesRequest, UpdateIncidentSeverityRequest,
+    UpdateIncidentStatusRequest,
+};
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::{IncidentSeverity, IncidentStatus};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+/// Most ids that can be linked in one request
+const MAX_LINKS_PER_REQUEST: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateIncidentStatusRequest {
+    pub status: IncidentStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateIncidentSeverityRequest {
+    pub severity: IncidentSeverity,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssignCommanderRequest {
+    pub commander_id: UserId,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LinkIncidentResourcesRequest {
+    #[serde(default)]
+    pub patient_ids: Vec<PatientId>,
+    #[serde(default)]
+    pub ambulance_ids: Vec<AmbulanceId>,
+    #[serde(default)]
+    pub hospital_ids: Vec<HospitalId>,
+}
+
+impl Validate for LinkIncidentResourcesRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let total = self.patient_ids.len() + self.ambulance_ids.len() + self.hospital_ids.len();
+        if total == 0 {
+            errors.add(
+                "",
+                "required",
+                "At least one patient, ambulance or hospital is required",
+            );
+        } else if total > MAX_LINKS_PER_REQUEST {
+            errors.add_with_value(
+                "",
+                "too_many",
+                format!(
+                    "At most {} resources can be linked at once",
+                    MAX_LINKS_PER_REQUEST
+                ),
+                total,
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_link_request() {
+        let request = LinkIncidentResourcesRequest {
+            patient_ids: vec![PatientId::new()],
+            ..Default::default()
+        };
+        assert!(request.validate().is_ok());
+        assert!(LinkIncidentResourcesRequest::default().validate().is_err());
+
+        let request: LinkIncidentResourcesRequest =
+            serde_json::from_str(&format!(r#"{{"ambulance_ids":["{}"]}}"#, Uuid::new_v4()))
+                .unwrap();
+        assert!(request.patient_ids.is_empty());
+        assert!(request.validate().is_ok());
+    }
+}
@@ -1,9 +1,19 @@
 // pub mod dtos;
 
 pub mod auth;
+pub mod common;
 pub mod patient;
 pub mod hospital;
+pub mod ambulance;
+pub mod incident;
+pub mod staff;
+pub mod transfer;
 
 pub use auth::*;
+pub use common::*;
 pub use patient::*;
-pub use hospital::*;
\ No newline at end of file
+pub use hospital::*;
+pub use ambulance::*;
+pub use incident::*;
+pub use staff::*;
+pub use transfer::*;
\ No newline at end of file
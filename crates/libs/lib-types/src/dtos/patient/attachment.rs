@@ -0,0 +1,242 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::validation::rules;
+
+use crate::entities::Attachment;
+use crate::enums::AttachmentKind;
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{PatientId, UserId};
+
+/// Largest accepted file; stays under the request size limit once base64-encoded
+pub const MAX_ATTACHMENT_BYTES: usize = 7 * 1024 * 1024;
+const MAX_FILE_NAME_LENGTH: usize = 255;
+
+/// Attach a file to a patient record. The content is sent base64-encoded;
+/// `checksum` is an optional hex SHA-256 of the decoded content, checked on
+/// receipt to catch uploads corrupted on a poor connection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadAttachmentRequest {
+    pub kind: AttachmentKind,
+    pub file_name: String,
+    pub content_type: String,
+    pub content: String,
+    #[serde(default)]
+    pub checksum: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttachmentResponse {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub kind: AttachmentKind,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub checksum: String,
+    pub uploaded_by: UserId,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An attachment with its base64-encoded content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttachmentDownloadResponse {
+    #[serde(flatten)]
+    pub attachment: AttachmentResponse,
+    pub content: String,
+}
+
+impl UploadAttachmentRequest {
+    /// Get the content type without parameters, lower-cased
+    pub fn content_type(&self) -> String {
+        self.content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+    }
+
+    /// Get the trimmed file name
+    pub fn file_name(&self) -> String {
+        self.file_name.trim().to_string()
+    }
+
+    /// Decode the content, checking it is present and within the size limit
+    pub fn decode_content(&self) -> Result<Vec<u8>, ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        match STANDARD.decode(self.content.trim()) {
+            Ok(content) if content.is_empty() => {
+                errors.add("content", "required", "File content is required");
+            }
+            Ok(content) if content.len() > MAX_ATTACHMENT_BYTES => {
+                errors.add(
+                    "content",
+                    "too_large",
+                    format!(
+                        "File must be at most {} MB",
+                        MAX_ATTACHMENT_BYTES / (1024 * 1024)
+                    ),
+                );
+            }
+            Ok(content) => return Ok(content),
+            Err(_) => {
+                errors.add(
+                    "content",
+                    "invalid_format",
+                    "Content must be base64-encoded",
+                );
+            }
+        }
+        Err(errors)
+    }
+
+    /// Check the content against the checksum sent by the client, if any
+    pub fn checksum_matches(&self, checksum: &str) -> bool {
+        self.checksum
+            .as_ref()
+            .is_none_or(|expected| expected.eq_ignore_ascii_case(checksum))
+    }
+}
+
+impl Validate for UploadAttachmentRequest {
+    /// Only the file metadata; the content is checked when decoded
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let file_name = self.file_name.trim();
+        if rules::required(&mut errors, "file_name", file_name, "File name is required")
+            && rules::max_length(
+                &mut errors,
+                "file_name",
+                file_name,
+                MAX_FILE_NAME_LENGTH,
+                format!(
+                    "File name must be at most {} characters",
+                    MAX_FILE_NAME_LENGTH
+                ),
+            )
+            && (file_name.contains(['/', '\\']) || file_name.chars().any(char::is_control))
+        {
+            errors.add(
+                "file_name",
+                "invalid_format",
+                "File name cannot contain path separators or control characters",
+            );
+        }
+
+        let allowed = self.kind.allowed_content_types();
+        rules::one_of(
+            &mut errors,
+            "content_type",
+            &self.content_type(),
+            allowed,
+            format!("{} must be one of: {}", self.kind, allowed.join(", ")),
+        );
+
+        if let Some(ref checksum) = self.checksum {
+            if checksum.len() != 64 || !checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+                errors.add(
+                    "checksum",
+                    "invalid_format",
+                    "Checksum must be a hex SHA-256 digest",
+                );
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+impl AttachmentResponse {
+    /// Create from Attachment entity
+    pub fn from_attachment(attachment: &Attachment) -> Self {
+        Self {
+            id: attachment.id,
+            patient_id: attachment.patient_id,
+            kind: attachment.kind,
+            file_name: attachment.file_name.clone(),
+            content_type: attachment.content_type.clone(),
+            size_bytes: attachment.size_bytes,
+            checksum: attachment.checksum.clone(),
+            uploaded_by: attachment.uploaded_by,
+            created_at: attachment.created_at,
+        }
+    }
+}
+
+impl AttachmentDownloadResponse {
+    pub fn new(attachment: &Attachment, content: &[u8]) -> Self {
+        Self {
+            attachment: AttachmentResponse::from_attachment(attachment),
+            content: STANDARD.encode(content),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::NewAttachment;
+
+    fn create_request() -> UploadAttachmentRequest {
+        UploadAttachmentRequest {
+            kind: AttachmentKind::Photo,
+            file_name: "scene.jpg".to_string(),
+            content_type: "image/JPEG".to_string(),
+            content: STANDARD.encode(b"\xff\xd8\xff\xe0 jpeg"),
+            checksum: None,
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        let request = create_request();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.decode_content().unwrap(), b"\xff\xd8\xff\xe0 jpeg");
+
+        let request = UploadAttachmentRequest {
+            file_name: "../../etc/passwd".to_string(),
+            content_type: "application/pdf".to_string(),
+            checksum: Some("not-a-digest".to_string()),
+            ..create_request()
+        };
+        let errors = request.validate().unwrap_err();
+        assert!(errors.has_field("file_name"));
+        assert!(errors.has_field("content_type"));
+        assert!(errors.has_field("checksum"));
+    }
+
+    #[test]
+    fn test_content_decoding() {
+        let mut request = create_request();
+        request.content = "not base64!".to_string();
+        assert!(request.decode_content().unwrap_err().has_field("content"));
+
+        request.content = STANDARD.encode(vec![0u8; MAX_ATTACHMENT_BYTES + 1]);
+        assert!(request.decode_content().unwrap_err()[0]
+            .message
+            .contains("at most 7 MB"));
+    }
+
+    #[test]
+    fn test_download_flattens_metadata() {
+        let attachment = Attachment::new(NewAttachment {
+            patient_id: PatientId::new(),
+            hospital_id: crate::ids::HospitalId::new(),
+            kind: AttachmentKind::Photo,
+            file_name: "scene.jpg".to_string(),
+            content_type: "image/jpeg".to_string(),
+            size_bytes: 4,
+            checksum: "ab".repeat(32),
+            uploaded_by: UserId::new(),
+        });
+        let json =
+            serde_json::to_value(AttachmentDownloadResponse::new(&attachment, b"jpeg")).unwrap();
+        assert_eq!(json["file_name"], "scene.jpg");
+        assert_eq!(json["content"], "anBlZw==");
+    }
+}
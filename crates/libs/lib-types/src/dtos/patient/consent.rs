@@ -0,0 +1,246 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::validation::rules;
+
+use crate::entities::Consent;
+use crate::enums::{ConsentMethod, ConsentType, ContactRelationship};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{HospitalId, PatientId, UserId};
+
+const MAX_NAME_LENGTH: usize = 100;
+const MAX_TEXT_LENGTH: usize = 1000;
+
+/// Record consent given by the patient, or on their behalf when
+/// `relationship` is set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordConsentRequest {
+    pub consent_type: ConsentType,
+    pub granted_by: String,
+    #[serde(default)]
+    pub relationship: Option<ContactRelationship>,
+    #[serde(default)]
+    pub scope: Option<String>,
+    pub method: ConsentMethod,
+    #[serde(default)]
+    pub witness: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WithdrawConsentRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsentResponse {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub consent_type: ConsentType,
+    pub granted_by: String,
+    pub relationship: Option<ContactRelationship>,
+    pub scope: Option<String>,
+    pub method: ConsentMethod,
+    pub witness: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub is_active: bool,
+    pub recorded_by: UserId,
+    pub recorded_at: DateTime<Utc>,
+    pub withdrawn_by: Option<UserId>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    pub withdrawal_reason: Option<String>,
+}
+
+impl RecordConsentRequest {
+    /// Get the scope, if one was given
+    pub fn scope(&self) -> Option<String> {
+        non_empty(&self.scope)
+    }
+
+    /// Get the witness, if one was given
+    pub fn witness(&self) -> Option<String> {
+        non_empty(&self.witness)
+    }
+
+    /// Create the consent entity from the request
+    pub fn to_consent(
+        &self,
+        patient_id: PatientId,
+        hospital_id: HospitalId,
+        recorded_by: UserId,
+    ) -> Consent {
+        let mut consent = Consent::new(
+            patient_id,
+            hospital_id,
+            self.consent_type,
+            self.granted_by.trim().to_string(),
+            self.relationship,
+            self.method,
+            recorded_by,
+        );
+        consent.scope = self.scope();
+        consent.witness = self.witness();
+        consent.expires_at = self.expires_at;
+        consent
+    }
+}
+
+impl Validate for RecordConsentRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let granted_by = self.granted_by.trim();
+        if rules::required(
+            &mut errors,
+            "granted_by",
+            granted_by,
+            "Name of the person consenting is required",
+        ) {
+            rules::max_length(
+                &mut errors,
+                "granted_by",
+                granted_by,
+                MAX_NAME_LENGTH,
+                format!("Name must be at most {} characters", MAX_NAME_LENGTH),
+            );
+        }
+
+        match self.scope() {
+            None if self.consent_type.requires_scope() => errors.add(
+                "scope",
+                "required",
+                format!("{} consent must state what it covers", self.consent_type),
+            ),
+            Some(scope) if scope.chars().count() > MAX_TEXT_LENGTH => errors.add(
+                "scope",
+                "too_long",
+                format!("Scope must be at most {} characters", MAX_TEXT_LENGTH),
+            ),
+            _ => {}
+        }
+
+        match self.witness() {
+            None if self.method.requires_witness() => errors.add(
+                "witness",
+                "required",
+                format!("{} consent must be witnessed", self.method),
+            ),
+            Some(witness) if witness.chars().count() > MAX_NAME_LENGTH => errors.add(
+                "witness",
+                "too_long",
+                format!("Witness must be at most {} characters", MAX_NAME_LENGTH),
+            ),
+            _ => {}
+        }
+
+        if self
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now())
+        {
+            errors.add("expires_at", "invalid", "Expiry must be in the future");
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for WithdrawConsentRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let reason = self.reason.trim();
+        if rules::required(
+            &mut errors,
+            "reason",
+            reason,
+            "Reason for withdrawal is required",
+        ) {
+            rules::max_length(
+                &mut errors,
+                "reason",
+                reason,
+                MAX_TEXT_LENGTH,
+                format!("Reason must be at most {} characters", MAX_TEXT_LENGTH),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+impl ConsentResponse {
+    /// Create from Consent entity
+    pub fn from_consent(consent: &Consent) -> Self {
+        Self {
+            id: consent.id,
+            patient_id: consent.patient_id,
+            consent_type: consent.consent_type,
+            granted_by: consent.granted_by.clone(),
+            relationship: consent.relationship,
+            scope: consent.scope.clone(),
+            method: consent.method,
+            witness: consent.witness.clone(),
+            expires_at: consent.expires_at,
+            is_active: consent.is_active(Utc::now()),
+            recorded_by: consent.recorded_by,
+            recorded_at: consent.recorded_at,
+            withdrawn_by: consent.withdrawn_by,
+            withdrawn_at: consent.withdrawn_at,
+            withdrawal_reason: consent.withdrawal_reason.clone(),
+        }
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> RecordConsentRequest {
+        RecordConsentRequest {
+            consent_type: ConsentType::Procedure,
+            granted_by: "Fatima Al-Rashid".to_string(),
+            relationship: Some(ContactRelationship::Parent),
+            scope: Some("Closed reduction of left wrist".to_string()),
+            method: ConsentMethod::Telephone,
+            witness: Some("Nurse Aisha Khan".to_string()),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(request().validate().is_ok());
+
+        let invalid = RecordConsentRequest {
+            granted_by: " ".to_string(),
+            scope: Some("  ".to_string()),
+            witness: None,
+            expires_at: Some(Utc::now() - chrono::Duration::hours(1)),
+            ..request()
+        };
+        let errors = invalid.validate().unwrap_err();
+        for field in ["granted_by", "scope", "witness", "expires_at"] {
+            assert!(errors.has_field(field), "{}", field);
+        }
+
+        // Written general consent needs neither scope nor witness
+        let written = RecordConsentRequest {
+            consent_type: ConsentType::Treatment,
+            scope: None,
+            method: ConsentMethod::Written,
+            witness: None,
+            ..request()
+        };
+        assert!(written.validate().is_ok());
+    }
+}
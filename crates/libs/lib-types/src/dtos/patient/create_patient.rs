@@ -1,98 +1,100 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
-use crate::enums::TriageLevel;
+use lib_utils::location::GeoPoint;
+use lib_utils::validation::{rules, EmiratesId};
+
+use crate::entities::{
+    EmergencyContact, EmergencyContacts, Identifier, Identifiers, InsuranceInfo, MedicalHistory,
+    Patient,
+};
+use crate::enums::{BloodType, Gender, IdentifierScheme, IsolationPrecaution, TriageLevel};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::HospitalId;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CreatePatientRequest {
     pub first_name: String,
     pub last_name: String,
     pub age: i32,
-    pub gender: String, // "Male", "Female", "Other"
+    pub gender: Gender,
     pub national_id: Option<String>, // Emirates ID
+    #[serde(default)]
+    pub identifiers: Vec<Identifier>, // Passport, MRN, alias, ...
     pub chief_complaint: String,
     pub triage_level: TriageLevel,
-    pub hospital_id: Uuid,
-    pub incident_location: Option<String>,
+    pub hospital_id: HospitalId,
+    pub incident_location: Option<GeoPoint>,
     pub incident_time: Option<DateTime<Utc>>,
-    pub emergency_contacts: Option<EmergencyContact>,
+    #[serde(default)]
+    pub emergency_contacts: Vec<EmergencyContact>, // In order of preference
     pub allergies: Option<Vec<String>>,
-    pub medical_history: Option<String>,
+    pub medical_history: Option<MedicalHistory>,
     pub insurance_info: Option<InsuranceInfo>,
-}
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct EmergencyContact {
-    pub name: String,
-    pub relationship: String,
-    pub phone_number: String,
-    pub email: Option<String>,
-}
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct InsuranceInfo {
-    pub provider: String,
-    pub policy_number: String,
-    pub group_number: Option<String>,
-    pub member_id: String,
+    pub blood_type: Option<BloodType>,
+    #[serde(default)]
+    pub isolation_precautions: Vec<IsolationPrecaution>,
 }
 
 impl CreatePatientRequest {
-    /// Validate the create patient request
-    pub fn validate(&self) -> Result<(), Vec<String>> {
-        let mut errors = Vec::new();
-
-        // Required field validations
-        if self.first_name.trim().is_empty() {
-            errors.push("First name is required".to_string());
-        }
-
-        if self.last_name.trim().is_empty() {
-            errors.push("Last name is required".to_string());
-        }
-
-        if self.age < 0 || self.age > 150 {
-            errors.push("Age must be between 0 and 150".to_string());
-        }
-
-        if !matches!(self.gender.as_str(), "Male" | "Female" | "Other") {
-            errors.push("Gender must be Male, Female, or Other".to_string());
-        }
+    /// Critical patients need someone to call, unless they are not yet
+    /// identified (no national ID) and nobody can be known
+    pub fn requires_emergency_contact(&self) -> bool {
+        self.triage_level == TriageLevel::Critical
+            && self.national_id.as_ref().is_some_and(|id| !id.is_empty())
+    }
 
-        if self.chief_complaint.trim().is_empty() {
-            errors.push("Chief complaint is required".to_string());
+    /// Get the identifiers as stored on the patient, including the national ID
+    pub fn identifier_list(&self) -> Identifiers {
+        let mut identifiers = Identifiers::new(self.identifiers.clone()).normalized();
+        if let Some(national_id) = self.national_id.as_deref().filter(|id| !id.is_empty()) {
+            identifiers.set(IdentifierScheme::EmiratesId, Some(national_id.to_string()));
         }
+        identifiers
+    }
 
-        // Emirates ID validation (if provided)
-        if let Some(ref national_id) = self.national_id {
-            if !national_id.is_empty() && !Self::is_valid_emirates_id(national_id) {
-                errors.push("Invalid Emirates ID format".to_string());
-            }
-        }
+    /// Get the emergency contacts as stored on the patient
+    pub fn emergency_contact_list(&self) -> EmergencyContacts {
+        EmergencyContacts::new(self.emergency_contacts.clone())
+    }
 
-        // Emergency contact validation (if provided)
-        if let Some(ref contact) = self.emergency_contacts {
-            if contact.name.trim().is_empty() {
-                errors.push("Emergency contact name is required".to_string());
-            }
-            if contact.phone_number.trim().is_empty() {
-                errors.push("Emergency contact phone is required".to_string());
+    /// Build the patient to be stored. The patient number is left blank
+    /// for the repository to assign the next one.
+    pub fn to_patient(&self) -> Patient {
+        let national_id = self
+            .national_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string);
+        let mut patient = Patient::new(
+            String::new(),
+            national_id,
+            self.sanitized_first_name(),
+            self.sanitized_last_name(),
+            self.age,
+            self.gender,
+            self.chief_complaint.trim().to_string(),
+            self.triage_level,
+            self.hospital_id,
+            self.incident_location,
+            self.incident_time,
+        );
+        patient.identifiers = self.identifier_list();
+        patient.emergency_contacts = self.emergency_contact_list();
+        for allergy in self.allergies.iter().flatten() {
+            let allergy = allergy.trim();
+            if !allergy.is_empty() {
+                patient.add_allergy(allergy.to_string());
             }
         }
-
-        if errors.is_empty() {
-            Ok(())
-        } else {
-            Err(errors)
+        if let Some(ref medical_history) = self.medical_history {
+            patient.medical_history = medical_history.clone();
         }
-    }
-
-    /// Basic Emirates ID validation (simplified)
-    fn is_valid_emirates_id(id: &str) -> bool {
-        // Emirates ID format: XXX-YYYY-XXXXXXX-X (15 digits with dashes)
-        let clean_id = id.replace("-", "");
-        clean_id.len() == 15 && clean_id.chars().all(|c| c.is_ascii_digit())
+        patient.insurance_info = self.insurance_info.clone();
+        patient.blood_type = self.blood_type;
+        patient.isolation_precautions = self.isolation_precautions.clone();
+        patient
     }
 
     /// Get sanitized first name
@@ -121,36 +123,144 @@ impl CreatePatientRequest {
     }
 }
 
+impl Validate for CreatePatientRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        // Required field validations
+        rules::required(
+            &mut errors,
+            "first_name",
+            &self.first_name,
+            "First name is required",
+        );
+        rules::required(
+            &mut errors,
+            "last_name",
+            &self.last_name,
+            "Last name is required",
+        );
+        rules::range(
+            &mut errors,
+            "age",
+            self.age,
+            0..=150,
+            "Age must be between 0 and 150",
+        );
+        rules::required(
+            &mut errors,
+            "chief_complaint",
+            &self.chief_complaint,
+            "Chief complaint is required",
+        );
+
+        // Emirates ID validation (if provided)
+        if let Some(ref national_id) = self.national_id {
+            if !national_id.is_empty() {
+                if let Err(error) = national_id.parse::<EmiratesId>() {
+                    errors.add("national_id", "invalid_format", error.to_string());
+                }
+            }
+        }
+
+        let identifiers = Identifiers::new(self.identifiers.clone());
+        rules::nested(&mut errors, "identifiers", &identifiers);
+        validate_national_id_matches(&mut errors, self.national_id.as_deref(), &identifiers);
+
+        if let Some(location) = self.incident_location {
+            validate_incident_location(&mut errors, location);
+        }
+
+        // Emergency contact validation
+        let contacts = self.emergency_contact_list();
+        rules::nested(&mut errors, "emergency_contacts", &contacts);
+        if self.requires_emergency_contact() && !contacts.has_reachable_contact() {
+            errors.add(
+                "emergency_contacts",
+                "required",
+                "Critical patients need at least one reachable emergency contact",
+            );
+        }
+
+        rules::nested(&mut errors, "medical_history", &self.medical_history);
+        rules::nested(&mut errors, "insurance_info", &self.insurance_info);
+
+        errors.into_result()
+    }
+}
+
+pub(crate) fn validate_incident_location(errors: &mut ValidationErrors, location: GeoPoint) {
+    if !location.is_valid() {
+        errors.add_with_value(
+            "incident_location",
+            "out_of_range",
+            "Latitude must be between -90 and 90 and longitude between -180 and 180",
+            location,
+        );
+    }
+}
+
+/// The Emirates ID may be given both as `national_id` and in the identifier
+/// list, but the two must agree
+pub(crate) fn validate_national_id_matches(
+    errors: &mut ValidationErrors,
+    national_id: Option<&str>,
+    identifiers: &Identifiers,
+) {
+    let national_id = national_id.map(str::trim).filter(|id| !id.is_empty());
+    let emirates_id = identifiers
+        .normalized()
+        .find(IdentifierScheme::EmiratesId)
+        .map(|identifier| identifier.value.clone());
+    if let (Some(national_id), Some(emirates_id)) = (national_id, emirates_id) {
+        let national_id = Identifier::new(IdentifierScheme::EmiratesId, national_id).normalized();
+        if national_id.value != emirates_id {
+            errors.add(
+                "national_id",
+                "mismatch",
+                "National ID does not match the Emirates ID in identifiers",
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::enums::{ContactRelationship, PatientStatus};
 
     fn create_valid_request() -> CreatePatientRequest {
         CreatePatientRequest {
             first_name: "Ahmed".to_string(),
             last_name: "Al-Rashid".to_string(),
             age: 45,
-            gender: "Male".to_string(),
-            national_id: Some("784-1990-1234567-1".to_string()),
+            gender: Gender::Male,
+            national_id: Some("784-1990-1234567-6".to_string()),
+            identifiers: vec![Identifier::new(IdentifierScheme::Passport, "N1234567")],
             chief_complaint: "Chest Pain".to_string(),
             triage_level: TriageLevel::High,
-            hospital_id: Uuid::new_v4(),
-            incident_location: Some("Sheikh Zayed Road".to_string()),
+            hospital_id: HospitalId::new(),
+            incident_location: Some(GeoPoint::new(25.1124, 55.1390).unwrap()),
             incident_time: Some(Utc::now()),
-            emergency_contacts: Some(EmergencyContact {
+            emergency_contacts: vec![EmergencyContact {
                 name: "Fatima Al-Rashid".to_string(),
-                relationship: "Wife".to_string(),
+                relationship: ContactRelationship::Spouse,
                 phone_number: "+971501234567".to_string(),
                 email: Some("fatima@email.com".to_string()),
-            }),
+                is_primary: true,
+                sms_updates_consent: true,
+            }],
             allergies: Some(vec!["Penicillin".to_string()]),
-            medical_history: Some("Hypertension".to_string()),
+            medical_history: Some(MedicalHistory::from_notes("Hypertension")),
             insurance_info: Some(InsuranceInfo {
                 provider: "Dubai Health Insurance".to_string(),
                 policy_number: "DH123456".to_string(),
                 group_number: None,
                 member_id: "MEM789".to_string(),
+                valid_until: None,
             }),
+            blood_type: Some(BloodType::BPositive),
+            isolation_precautions: vec![],
         }
     }
 
@@ -168,13 +278,22 @@ mod tests {
         let mut request = create_valid_request();
         request.first_name = "".to_string();
         request.age = -5;
-        request.gender = "Invalid".to_string();
         
         let errors = request.validate().unwrap_err();
-        assert!(errors.len() >= 3);
-        assert!(errors.iter().any(|e| e.contains("First name")));
-        assert!(errors.iter().any(|e| e.contains("Age must be")));
-        assert!(errors.iter().any(|e| e.contains("Gender must be")));
+        assert!(errors.len() >= 2);
+        assert!(errors.has_field("first_name"));
+        assert!(errors.iter().any(|e| e.message.contains("Age must be")));
+    }
+
+    #[test]
+    fn test_gender_deserialization() {
+        let mut json = serde_json::to_value(create_valid_request()).unwrap();
+        json["gender"] = serde_json::json!("Female"); // Value sent before gender was an enum
+        let request: CreatePatientRequest = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(request.gender, Gender::Female);
+
+        json["gender"] = serde_json::json!("Invalid");
+        assert!(serde_json::from_value::<CreatePatientRequest>(json).is_err());
     }
 
     #[test]
@@ -182,12 +301,53 @@ mod tests {
         let mut request = create_valid_request();
         
         // Valid Emirates ID
-        request.national_id = Some("784-1990-1234567-1".to_string());
+        request.national_id = Some("784-1990-1234567-6".to_string());
         assert!(request.validate().is_ok());
         
         // Invalid Emirates ID
         request.national_id = Some("invalid-id".to_string());
         assert!(request.validate().is_err());
+
+        // Wrong check digit
+        request.national_id = Some("784-1990-1234567-1".to_string());
+        assert!(request.validate().unwrap_err().has_field("national_id"));
+    }
+
+    #[test]
+    fn test_identifiers() {
+        let mut request = create_valid_request();
+        assert_eq!(
+            request.identifier_list().to_vec(),
+            vec![
+                Identifier::new(IdentifierScheme::Passport, "N1234567"),
+                Identifier::new(IdentifierScheme::EmiratesId, "784-1990-1234567-6"),
+            ]
+        );
+
+        request.identifiers.push(Identifier::new(IdentifierScheme::GccId, "12"));
+        request.identifiers.push(Identifier::new(
+            IdentifierScheme::EmiratesId,
+            "784198576543213",
+        ));
+        let errors = request.validate().unwrap_err();
+        assert!(errors.has_field("identifiers[1].value"));
+        assert!(errors.has_field("national_id"));
+    }
+
+    #[test]
+    fn test_to_patient() {
+        let mut request = create_valid_request();
+        request.first_name = " Ahmed ".to_string();
+        request.allergies = Some(vec!["Penicillin".to_string(), " ".to_string()]);
+        let patient = request.to_patient();
+
+        assert!(patient.patient_number.is_empty());
+        assert_eq!(patient.first_name, "Ahmed");
+        assert_eq!(patient.status, PatientStatus::Dispatched);
+        assert_eq!(patient.identifiers, request.identifier_list());
+        assert_eq!(patient.get_allergies(), vec!["Penicillin".to_string()]);
+        assert_eq!(patient.blood_type, Some(BloodType::BPositive));
+        assert_eq!(patient.emergency_contacts.len(), 1);
     }
 
     #[test]
@@ -213,16 +373,41 @@ mod tests {
     #[test]
     fn test_emergency_contact_validation() {
         let mut request = create_valid_request();
-        request.emergency_contacts = Some(EmergencyContact {
+        request.emergency_contacts.push(EmergencyContact {
             name: "".to_string(), // Invalid empty name
-            relationship: "Wife".to_string(),
+            relationship: ContactRelationship::Sibling,
             phone_number: "".to_string(), // Invalid empty phone
             email: None,
+            is_primary: false,
+            sms_updates_consent: false,
         });
         
         let errors = request.validate().unwrap_err();
-        assert!(errors.iter().any(|e| e.contains("contact name")));
-        assert!(errors.iter().any(|e| e.contains("contact phone")));
+        assert!(errors.iter().any(|e| e.message.contains("contact 2 name")));
+        assert!(errors.has_field("emergency_contacts[1].phone_number"));
+    }
+
+    #[test]
+    fn test_critical_patient_needs_contact() {
+        let mut request = create_valid_request();
+        request.triage_level = TriageLevel::Critical;
+        assert!(request.validate().is_ok());
+
+        request.emergency_contacts.clear();
+        let errors = request.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.message.contains("reachable emergency contact")));
+
+        // Unidentified patients are treated first and traced later
+        request.national_id = None;
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_contacts_default_to_empty() {
+        let mut json = serde_json::to_value(create_valid_request()).unwrap();
+        json.as_object_mut().unwrap().remove("emergency_contacts");
+        let request: CreatePatientRequest = serde_json::from_value(json).unwrap();
+        assert!(request.emergency_contacts.is_empty());
     }
 
     #[test]
@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::validation::rules;
+
+use crate::entities::DischargeSummary;
+use crate::enums::DischargeDisposition;
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{PatientId, UserId};
+
+const MAX_TEXT_LENGTH: usize = 4000;
+
+/// Discharge a patient. Minors need a discharge consent from a parent or
+/// guardian recorded beforehand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DischargePatientRequest {
+    pub diagnosis: String,
+    pub disposition: DischargeDisposition,
+    pub instructions: String,
+    pub follow_up: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DischargeSummaryResponse {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub diagnosis: String,
+    pub disposition: DischargeDisposition,
+    pub disposition_display: String,
+    pub instructions: String,
+    pub follow_up: Option<String>,
+    pub guardian_consent_by: Option<String>,
+    pub document: String,
+    pub discharged_by: UserId,
+    pub discharged_at: DateTime<Utc>,
+}
+
+impl DischargePatientRequest {
+    /// Get the follow-up, if one was given
+    pub fn follow_up(&self) -> Option<String> {
+        self.follow_up
+            .as_deref()
+            .map(str::trim)
+            .filter(|follow_up| !follow_up.is_empty())
+            .map(String::from)
+    }
+}
+
+impl Validate for DischargePatientRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        check_text(&mut errors, "diagnosis", "Diagnosis", &self.diagnosis, true);
+        check_text(
+            &mut errors,
+            "instructions",
+            "Instructions",
+            &self.instructions,
+            true,
+        );
+        if let Some(follow_up) = &self.follow_up {
+            check_text(&mut errors, "follow_up", "Follow-up", follow_up, false);
+        }
+
+        errors.into_result()
+    }
+}
+
+fn check_text(errors: &mut ValidationErrors, field: &str, name: &str, value: &str, required: bool) {
+    if !required || rules::required(errors, field, value, format!("{} is required", name)) {
+        rules::max_length(
+            errors,
+            field,
+            value,
+            MAX_TEXT_LENGTH,
+            format!("{} must be at most {} characters", name, MAX_TEXT_LENGTH),
+        );
+    }
+}
+
+impl DischargeSummaryResponse {
+    /// Create from DischargeSummary entity
+    pub fn from_summary(summary: &DischargeSummary) -> Self {
+        Self {
+            id: summary.id,
+            patient_id: summary.patient_id,
+            diagnosis: summary.diagnosis.clone(),
+            disposition: summary.disposition,
+            disposition_display: summary.disposition.display_name().to_string(),
+            instructions: summary.instructions.clone(),
+            follow_up: summary.follow_up.clone(),
+            guardian_consent_by: summary.guardian_consent_by.clone(),
+            document: summary.document.clone(),
+            discharged_by: summary.discharged_by,
+            discharged_at: summary.discharged_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let request: DischargePatientRequest = serde_json::from_str(
+            r#"{"diagnosis": "Viral gastroenteritis", "disposition": "home",
+                "instructions": "Oral fluids", "follow_up": "  "}"#,
+        )
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.follow_up(), None);
+
+        let mut invalid = request.clone();
+        invalid.diagnosis = " ".to_string();
+        invalid.follow_up = Some("x".repeat(MAX_TEXT_LENGTH + 1));
+        let errors = invalid.validate().unwrap_err();
+        assert_eq!(errors[0].field, "diagnosis");
+        assert_eq!(errors[1].code, "too_long");
+    }
+}
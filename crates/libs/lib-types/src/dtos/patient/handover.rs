@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::validation::rules;
+
+use crate::entities::HandoverReport;
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+const MAX_TEXT_LENGTH: usize = 2000;
+
+/// Hand a patient over from the ambulance crew to the emergency department.
+/// `ambulance_id` defaults to the ambulance the patient is assigned to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateHandoverRequest {
+    #[serde(default)]
+    pub ambulance_id: Option<AmbulanceId>,
+    #[serde(default)]
+    pub time_of_incident: Option<DateTime<Utc>>,
+    pub mechanism: String,
+    pub injuries: String,
+    pub signs: String,
+    pub treatment: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandoverReportResponse {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub ambulance_id: AmbulanceId,
+    pub age_years: i32,
+    pub time_of_incident: Option<DateTime<Utc>>,
+    pub mechanism: String,
+    pub injuries: String,
+    pub signs: String,
+    pub treatment: String,
+    pub atmist: String,
+    pub is_pending: bool,
+    pub waiting_minutes: i64,
+    pub created_by: UserId,
+    pub created_at: DateTime<Utc>,
+    pub acknowledged_by: Option<UserId>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+impl Validate for CreateHandoverRequest {
+    /// Every ATMIST section must be filled in; "Nil" is an acceptable answer
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        for (field, name, value) in [
+            ("mechanism", "Mechanism", &self.mechanism),
+            ("injuries", "Injuries", &self.injuries),
+            ("signs", "Signs", &self.signs),
+            ("treatment", "Treatment", &self.treatment),
+        ] {
+            if rules::required(&mut errors, field, value, format!("{} is required", name)) {
+                rules::max_length(
+                    &mut errors,
+                    field,
+                    value,
+                    MAX_TEXT_LENGTH,
+                    format!("{} must be at most {} characters", name, MAX_TEXT_LENGTH),
+                );
+            }
+        }
+        if self.time_of_incident.is_some_and(|time| time > Utc::now()) {
+            errors.add(
+                "time_of_incident",
+                "invalid",
+                "Time of incident cannot be in the future",
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+impl HandoverReportResponse {
+    /// Create from HandoverReport entity
+    pub fn from_report(report: &HandoverReport) -> Self {
+        Self {
+            id: report.id,
+            patient_id: report.patient_id,
+            hospital_id: report.hospital_id,
+            ambulance_id: report.ambulance_id,
+            age_years: report.age_years,
+            time_of_incident: report.time_of_incident,
+            mechanism: report.mechanism.clone(),
+            injuries: report.injuries.clone(),
+            signs: report.signs.clone(),
+            treatment: report.treatment.clone(),
+            atmist: report.atmist(),
+            is_pending: report.is_pending(),
+            waiting_minutes: report.waiting_time(Utc::now()).num_minutes(),
+            created_by: report.created_by,
+            created_at: report.created_at,
+            acknowledged_by: report.acknowledged_by,
+            acknowledged_at: report.acknowledged_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let request: CreateHandoverRequest = serde_json::from_str(
+            r#"{"mechanism": "Fall from 3 m", "injuries": "Suspected L wrist fracture",
+                "signs": "HR 96, BP 132/80, SpO2 98%", "treatment": "Nil"}"#,
+        )
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.ambulance_id, None);
+
+        let invalid = CreateHandoverRequest {
+            signs: " ".to_string(),
+            time_of_incident: Some(Utc::now() + chrono::Duration::hours(1)),
+            ..request
+        };
+        let errors = invalid.validate().unwrap_err();
+        assert!(errors.has_field("signs"));
+        assert!(errors.has_field("time_of_incident"));
+    }
+}
@@ -0,0 +1,315 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::validation::rules;
+
+use crate::entities::{LabOrder, LabResult};
+use crate::enums::{AbnormalFlag, LabOrderStatus};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{PatientId, UserId};
+
+const MAX_PANEL_LENGTH: usize = 100;
+const MAX_ANALYTE_LENGTH: usize = 50;
+const MAX_UNITS_LENGTH: usize = 20;
+const MAX_RESULTS_PER_BATCH: usize = 100;
+
+/// Order a lab panel for a patient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateLabOrderRequest {
+    pub panel: String,
+    pub notes: Option<String>,
+}
+
+/// One analyte as reported by the laboratory system. `flag` is the flag the
+/// analyzer assigned, if any; the server raises it to critical on its own
+/// when the value is past a critical limit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabResultInput {
+    pub analyte: String,
+    pub value: f64,
+    pub units: String,
+    #[serde(default)]
+    pub reference_low: Option<f64>,
+    #[serde(default)]
+    pub reference_high: Option<f64>,
+    #[serde(default)]
+    pub flag: Option<AbnormalFlag>,
+}
+
+/// Results for a lab order, pushed by the laboratory system
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestLabResultsRequest {
+    pub order_id: Uuid,
+    pub results: Vec<LabResultInput>,
+    #[serde(default)]
+    pub resulted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabOrderResponse {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub panel: String,
+    pub status: LabOrderStatus,
+    pub notes: Option<String>,
+    pub ordered_by: UserId,
+    pub ordered_at: DateTime<Utc>,
+    pub resulted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabResultResponse {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub patient_id: PatientId,
+    pub analyte: String,
+    pub value: f64,
+    pub units: String,
+    pub reference_range: Option<String>,
+    pub flag: AbnormalFlag,
+    pub is_critical: bool,
+    pub display_value: String,
+    pub resulted_at: DateTime<Utc>,
+    pub acknowledged_by: Option<UserId>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of ingesting results; `critical` lists the results that need a
+/// clinician to be told straight away
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestLabResultsResponse {
+    pub order: LabOrderResponse,
+    pub results: Vec<LabResultResponse>,
+    pub critical: Vec<LabResultResponse>,
+}
+
+impl Validate for CreateLabOrderRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let panel = self.panel.trim();
+        if rules::required(&mut errors, "panel", panel, "Panel is required") {
+            rules::max_length(
+                &mut errors,
+                "panel",
+                panel,
+                MAX_PANEL_LENGTH,
+                format!("Panel must be at most {} characters", MAX_PANEL_LENGTH),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+impl LabResultInput {
+    /// Create the result entity for `order`, with the flag worked out by the caller
+    pub fn to_result(
+        &self,
+        order: &LabOrder,
+        flag: AbnormalFlag,
+        resulted_at: DateTime<Utc>,
+    ) -> LabResult {
+        let mut result = LabResult::new(
+            order,
+            self.analyte.trim().to_string(),
+            self.value,
+            self.units.trim().to_string(),
+            flag,
+            resulted_at,
+        );
+        result.reference_low = self.reference_low;
+        result.reference_high = self.reference_high;
+        result
+    }
+}
+
+impl Validate for LabResultInput {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let analyte = self.analyte.trim();
+        if rules::required(&mut errors, "analyte", analyte, "Analyte is required") {
+            rules::max_length(
+                &mut errors,
+                "analyte",
+                analyte,
+                MAX_ANALYTE_LENGTH,
+                format!("Analyte must be at most {} characters", MAX_ANALYTE_LENGTH),
+            );
+        }
+        if !self.value.is_finite() {
+            errors.add("value", "invalid", "Value must be a number");
+        }
+        rules::max_length(
+            &mut errors,
+            "units",
+            self.units.trim(),
+            MAX_UNITS_LENGTH,
+            format!("Units must be at most {} characters", MAX_UNITS_LENGTH),
+        );
+        if let (Some(low), Some(high)) = (self.reference_low, self.reference_high) {
+            if low > high {
+                errors.add(
+                    "reference_low",
+                    "invalid_range",
+                    "Reference low cannot be above reference high",
+                );
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for IngestLabResultsRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.results.is_empty() {
+            errors.add("results", "required", "At least one result is required");
+        } else if self.results.len() > MAX_RESULTS_PER_BATCH {
+            errors.add_with_value(
+                "results",
+                "too_many",
+                format!(
+                    "At most {} results can be sent at once",
+                    MAX_RESULTS_PER_BATCH
+                ),
+                self.results.len(),
+            );
+        }
+        rules::each(&mut errors, "results", &self.results);
+        if let Some(resulted_at) = self.resulted_at {
+            if resulted_at > Utc::now() {
+                errors.add(
+                    "resulted_at",
+                    "invalid",
+                    "Result time cannot be in the future",
+                );
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+impl LabOrderResponse {
+    /// Create from LabOrder entity
+    pub fn from_order(order: &LabOrder) -> Self {
+        Self {
+            id: order.id,
+            patient_id: order.patient_id,
+            panel: order.panel.clone(),
+            status: order.status,
+            notes: order.notes.clone(),
+            ordered_by: order.ordered_by,
+            ordered_at: order.ordered_at,
+            resulted_at: order.resulted_at,
+        }
+    }
+}
+
+impl LabResultResponse {
+    /// Create from LabResult entity
+    pub fn from_result(result: &LabResult) -> Self {
+        Self {
+            id: result.id,
+            order_id: result.order_id,
+            patient_id: result.patient_id,
+            analyte: result.analyte.clone(),
+            value: result.value,
+            units: result.units.clone(),
+            reference_range: result.reference_range(),
+            flag: result.flag,
+            is_critical: result.is_critical(),
+            display_value: result.display_value(),
+            resulted_at: result.resulted_at,
+            acknowledged_by: result.acknowledged_by,
+            acknowledged_at: result.acknowledged_at,
+        }
+    }
+}
+
+impl IngestLabResultsResponse {
+    pub fn new(order: &LabOrder, results: &[LabResult]) -> Self {
+        let results: Vec<LabResultResponse> =
+            results.iter().map(LabResultResponse::from_result).collect();
+        Self {
+            order: LabOrderResponse::from_order(order),
+            critical: results.iter().filter(|r| r.is_critical).cloned().collect(),
+            results,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn potassium(value: f64) -> LabResultInput {
+        LabResultInput {
+            analyte: "K".to_string(),
+            value,
+            units: "mmol/L".to_string(),
+            reference_low: Some(3.5),
+            reference_high: Some(5.1),
+            flag: None,
+        }
+    }
+
+    #[test]
+    fn test_ingest_validation() {
+        let request = IngestLabResultsRequest {
+            order_id: Uuid::new_v4(),
+            results: vec![potassium(7.1)],
+            resulted_at: None,
+        };
+        assert!(request.validate().is_ok());
+
+        let mut invalid = potassium(f64::NAN);
+        invalid.reference_low = Some(6.0);
+        let request = IngestLabResultsRequest {
+            results: vec![potassium(4.0), invalid],
+            ..request
+        };
+        let errors = request.validate().unwrap_err();
+        assert!(errors.has_field("results[1].value"));
+        assert!(errors.has_field("results[1].reference_low"));
+
+        let empty = IngestLabResultsRequest {
+            results: vec![],
+            ..request
+        };
+        assert!(empty.validate().unwrap_err().has_field("results"));
+    }
+
+    #[test]
+    fn test_hl7_flag_input() {
+        let input: LabResultInput = serde_json::from_str(
+            r#"{"analyte": "K", "value": 7.1, "units": "mmol/L", "flag": "HH"}"#,
+        )
+        .unwrap();
+        assert_eq!(input.flag, Some(AbnormalFlag::CriticalHigh));
+        assert_eq!(input.reference_low, None);
+    }
+
+    #[test]
+    fn test_to_result_keeps_order_and_reference_range() {
+        let order = LabOrder::new(
+            PatientId::new(),
+            crate::ids::HospitalId::new(),
+            "U&E".to_string(),
+            UserId::new(),
+        );
+        let mut input = potassium(7.1);
+        input.analyte = " K ".to_string();
+
+        let result = input.to_result(&order, AbnormalFlag::CriticalHigh, order.ordered_at);
+        assert_eq!(result.order_id, order.id);
+        assert_eq!(result.hospital_id, order.hospital_id);
+        assert_eq!(result.analyte, "K");
+        assert_eq!(result.reference_range().unwrap(), "3.5-5.1");
+    }
+}
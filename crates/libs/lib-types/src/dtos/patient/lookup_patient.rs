@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::Identifier;
+use crate::enums::IdentifierScheme;
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::HospitalId;
+
+/// Find a patient by any identifier. Sent as a body so the identifier stays
+/// out of URLs and access logs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LookupPatientRequest {
+    pub scheme: IdentifierScheme,
+    pub value: String,
+    #[serde(default)]
+    pub hospital_id: Option<HospitalId>, // Issuing hospital of an MRN or alias
+}
+
+impl LookupPatientRequest {
+    /// Get the identifier in the form it is stored in
+    pub fn identifier(&self) -> Identifier {
+        Identifier::new(self.scheme, self.value.clone()).normalized()
+    }
+}
+
+impl Validate for LookupPatientRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        Identifier::new(self.scheme, self.value.clone()).validate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_normalizes_identifier() {
+        let request: LookupPatientRequest =
+            serde_json::from_str(r#"{"scheme": "emirates_id", "value": "784199012345676"}"#)
+                .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.identifier().value, "784-1990-1234567-6");
+
+        let request = LookupPatientRequest {
+            scheme: IdentifierScheme::Passport,
+            value: "".to_string(),
+            hospital_id: None,
+        };
+        assert!(request.validate().unwrap_err().has_field("value"));
+    }
+}
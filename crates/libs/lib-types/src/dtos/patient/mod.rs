@@ -1,7 +1,46 @@
 //! Patient DTOs
 
+pub mod attachment;
+pub mod consent;
 pub mod create_patient;
+pub mod discharge_patient;
+pub mod handover;
+pub mod lab;
+pub mod lookup_patient;
 pub mod patient_response;
+pub mod patient_search;
+pub mod prescribe_medication;
+pub mod record_dnr;
+pub mod record_vitals;
+pub mod retriage;
+pub mod timeline;
+pub mod update_patient;
+pub mod update_status;
+pub mod vitals_batch;
+pub mod vitals_history;
 
-pub use create_patient::{CreatePatientRequest, EmergencyContact, InsuranceInfo};
-pub use patient_response::{PatientResponse, PatientSummary, PatientListResponse, VitalsDto};
\ No newline at end of file
+pub use attachment::{
+    AttachmentDownloadResponse, AttachmentResponse, UploadAttachmentRequest, MAX_ATTACHMENT_BYTES,
+};
+pub use consent::{ConsentResponse, RecordConsentRequest, WithdrawConsentRequest};
+pub use create_patient::CreatePatientRequest;
+pub use discharge_patient::{DischargePatientRequest, DischargeSummaryResponse};
+pub use handover::{CreateHandoverRequest, HandoverReportResponse};
+pub use lab::{
+    CreateLabOrderRequest, IngestLabResultsRequest, IngestLabResultsResponse, LabOrderResponse,
+    LabResultInput, LabResultResponse,
+};
+pub use lookup_patient::LookupPatientRequest;
+pub use patient_response::{PatientResponse, PatientSummary, PatientListResponse, VitalsDto};
+pub use patient_search::{
+    PatientExportQuery, PatientListQuery, PatientSearchHit, PatientSearchRequest, PatientSortField,
+};
+pub use prescribe_medication::PrescribeMedicationRequest;
+pub use record_dnr::RecordDnrRequest;
+pub use record_vitals::{RecordVitalsRequest, RecordVitalsResponse};
+pub use retriage::RetriageRequest;
+pub use timeline::{build_timeline, TimelineEntry, TimelineEventKind};
+pub use update_patient::UpdatePatientRequest;
+pub use update_status::UpdatePatientStatusRequest;
+pub use vitals_batch::{RejectedVitals, VitalsBatchResult, MAX_VITALS_BATCH};
+pub use vitals_history::{VitalsHistoryQuery, VitalsSortField};
\ No newline at end of file
@@ -2,33 +2,48 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::enums::{PatientStatus, TriageLevel};
-use crate::entities::{Patient, PatientVitals};
+use lib_utils::format::{CsvRecord, Humanizer};
+use lib_utils::location::GeoPoint;
+
+use crate::enums::{
+    BloodType, ConsciousnessLevel, Gender, IsolationPrecaution, PatientStatus, TriageLevel,
+};
+use crate::dtos::CursorPage;
+use crate::entities::{Identifier, News2Score, Patient, PatientVitals};
+use crate::ids::{BedId, HospitalId, PatientId, UserId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PatientResponse {
-    pub id: Uuid,
+    pub id: PatientId,
     pub patient_number: String,
     pub first_name: String,
     pub last_name: String,
+    pub identifiers: Vec<Identifier>,
     pub age: i32,
-    pub gender: String,
+    pub gender: Gender,
     pub chief_complaint: String,
     pub triage_level: TriageLevel,
     pub status: PatientStatus,
-    pub hospital_id: Uuid,
+    pub hospital_id: HospitalId,
     pub hospital_name: Option<String>,
     pub assigned_staff_id: Option<Uuid>,
     pub assigned_staff_name: Option<String>,
     pub ambulance_id: Option<String>,
-    pub bed_id: Option<Uuid>,
+    pub bed_id: Option<BedId>,
     pub bed_number: Option<String>,
-    pub incident_location: Option<String>,
+    pub incident_location: Option<GeoPoint>,
     pub incident_time: Option<DateTime<Utc>>,
     pub latest_vitals: Option<VitalsDto>,
     pub allergies: Vec<String>,
+    pub blood_type: Option<BloodType>,
+    pub dnr: bool,
+    pub dnr_recorded_by: Option<UserId>,
+    pub dnr_recorded_at: Option<DateTime<Utc>>,
+    pub isolation_precautions: Vec<IsolationPrecaution>,
+    pub badges: Vec<String>, // e.g. "DNR", "Airborne isolation"
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub version: i64, // Send back with an update to detect concurrent edits
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -40,33 +55,55 @@ pub struct VitalsDto {
     pub oxygen_saturation: Option<i32>,
     pub temperature: Option<f32>,
     pub respiratory_rate: Option<i32>,
-    pub recorded_by: Uuid,
+    pub consciousness_level: Option<ConsciousnessLevel>,
+    pub on_supplemental_oxygen: bool,
+    pub gcs_total: Option<i32>,
+    pub pain_score: Option<i32>,
+    pub blood_glucose: Option<f32>,
+    pub news2: Option<News2Score>,
+    pub recorded_by: UserId,
     pub recorded_by_name: Option<String>,
     pub recorded_at: DateTime<Utc>,
 }
 
+/// A page of patients. Numbered pages carry counts; keyset pages carry the
+/// cursor of the next page instead, as counting a growing table is the
+/// cost they avoid.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PatientListResponse {
     pub patients: Vec<PatientSummary>,
-    pub total_count: i64,
-    pub page: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<i32>,
     pub page_size: i32,
-    pub total_pages: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_pages: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PatientSummary {
-    pub id: Uuid,
+    pub id: PatientId,
     pub patient_number: String,
     pub display_name: String,
     pub age: i32,
-    pub gender: String,
+    pub gender: Gender,
     pub chief_complaint: String,
     pub triage_level: TriageLevel,
     pub status: PatientStatus,
     pub assigned_staff_name: Option<String>,
     pub ambulance_id: Option<String>,
     pub eta_minutes: Option<i32>,
+    pub blood_type: Option<BloodType>,
+    pub dnr: bool,
+    pub isolation_precautions: Vec<IsolationPrecaution>,
+    pub badges: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waiting_time: Option<String>, // e.g. "25 minutes", set by service layer
     pub created_at: DateTime<Utc>,
 }
 
@@ -78,8 +115,9 @@ impl PatientResponse {
             patient_number: patient.patient_number.clone(),
             first_name: patient.first_name.clone(),
             last_name: patient.last_name.clone(),
+            identifiers: patient.identifiers.to_vec(),
             age: patient.age,
-            gender: patient.gender.clone(),
+            gender: patient.gender,
             chief_complaint: patient.chief_complaint.clone(),
             triage_level: patient.triage_level,
             status: patient.status,
@@ -90,12 +128,19 @@ impl PatientResponse {
             ambulance_id: patient.ambulance_id.map(|id| id.to_string()),
             bed_id: patient.bed_id,
             bed_number: None, // Set by service layer
-            incident_location: patient.incident_location.clone(),
+            incident_location: patient.incident_location,
             incident_time: patient.incident_time,
             latest_vitals: None, // Set by service layer
             allergies: patient.get_allergies(),
+            blood_type: patient.blood_type,
+            dnr: patient.dnr,
+            dnr_recorded_by: patient.dnr_recorded_by,
+            dnr_recorded_at: patient.dnr_recorded_at,
+            isolation_precautions: patient.isolation_precautions.clone(),
+            badges: patient.badges(),
             created_at: patient.created_at,
             updated_at: patient.updated_at,
+            version: patient.version,
         }
     }
 
@@ -146,6 +191,12 @@ impl VitalsDto {
             oxygen_saturation: vitals.oxygen_saturation,
             temperature: vitals.temperature,
             respiratory_rate: vitals.respiratory_rate,
+            consciousness_level: vitals.consciousness_level,
+            on_supplemental_oxygen: vitals.on_supplemental_oxygen,
+            gcs_total: vitals.gcs_total(),
+            pain_score: vitals.pain_score,
+            blood_glucose: vitals.blood_glucose,
+            news2: vitals.news2_score(),
             recorded_by: vitals.recorded_by,
             recorded_by_name: None, // Set by service layer
             recorded_at: vitals.recorded_at,
@@ -178,16 +229,76 @@ impl PatientSummary {
             patient_number: patient.patient_number.clone(),
             display_name: patient.display_name(),
             age: patient.age,
-            gender: patient.gender.clone(),
+            gender: patient.gender,
             chief_complaint: patient.chief_complaint.clone(),
             triage_level: patient.triage_level,
             status: patient.status,
             assigned_staff_name: None, // Set by service layer
             ambulance_id: patient.ambulance_id.map(|id| id.to_string()),
             eta_minutes: None, // Calculated by service layer
+            blood_type: patient.blood_type,
+            dnr: patient.dnr,
+            isolation_precautions: patient.isolation_precautions.clone(),
+            badges: patient.badges(),
+            waiting_time: None, // Set by service layer
             created_at: patient.created_at,
         }
     }
+
+    /// Describe how long a patient at the hospital has been waiting since
+    /// they were registered, in the reader's language
+    pub fn set_waiting_time(&mut self, humanizer: &Humanizer, now: DateTime<Utc>) {
+        let waiting = self.status.is_at_hospital() && self.status.is_active();
+        self.waiting_time = waiting.then(|| humanizer.duration(now - self.created_at));
+    }
+}
+
+/// One row of a patient export. Lists are joined with "; " so each stays in
+/// one cell.
+impl CsvRecord for PatientSummary {
+    fn header() -> &'static [&'static str] {
+        &[
+            "id",
+            "patient_number",
+            "display_name",
+            "age",
+            "gender",
+            "chief_complaint",
+            "triage_level",
+            "status",
+            "assigned_staff_name",
+            "ambulance_id",
+            "blood_type",
+            "dnr",
+            "isolation_precautions",
+            "badges",
+            "created_at",
+        ]
+    }
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.patient_number.clone(),
+            self.display_name.clone(),
+            self.age.to_string(),
+            self.gender.to_string(),
+            self.chief_complaint.clone(),
+            self.triage_level.to_string(),
+            self.status.to_string(),
+            self.assigned_staff_name.clone().unwrap_or_default(),
+            self.ambulance_id.clone().unwrap_or_default(),
+            self.blood_type.map(|b| b.to_string()).unwrap_or_default(),
+            self.dnr.to_string(),
+            self.isolation_precautions
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
+            self.badges.join("; "),
+            self.created_at.to_rfc3339(),
+        ]
+    }
 }
 
 impl PatientListResponse {
@@ -195,11 +306,26 @@ impl PatientListResponse {
     pub fn new(patients: Vec<PatientSummary>, total_count: i64, page: i32, page_size: i32) -> Self {
         let total_pages = ((total_count as f64) / (page_size as f64)).ceil() as i32;
         Self {
+            has_more: page < total_pages,
             patients,
-            total_count,
-            page,
+            total_count: Some(total_count),
+            page: Some(page),
             page_size,
-            total_pages,
+            total_pages: Some(total_pages),
+            next_cursor: None,
+        }
+    }
+
+    /// Create a keyset-paginated response from one page of a cursor list
+    pub fn from_cursor_page(page: CursorPage<PatientSummary>, page_size: u32) -> Self {
+        Self {
+            patients: page.items,
+            total_count: None,
+            page: None,
+            page_size: page_size as i32,
+            total_pages: None,
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
         }
     }
 }
@@ -208,19 +334,20 @@ impl PatientListResponse {
 mod tests {
     use super::*;
     use crate::entities::Patient;
+    use crate::enums::Locale;
 
     fn create_test_patient() -> Patient {
         Patient::new(
             "PAT-001".to_string(),
-            Some("784-1990-1234567-1".to_string()),
+            Some("784-1990-1234567-6".to_string()),
             "Ahmed".to_string(),
             "Al-Rashid".to_string(),
             45,
-            "Male".to_string(),
+            Gender::Male,
             "Chest Pain".to_string(),
             TriageLevel::Critical,
-            Uuid::new_v4(),
-            Some("Sheikh Zayed Road".to_string()),
+            HospitalId::new(),
+            Some(GeoPoint::new(25.1124, 55.1390).unwrap()),
             Some(Utc::now()),
         )
     }
@@ -257,21 +384,75 @@ mod tests {
         assert_eq!(summary.triage_level, TriageLevel::Critical);
     }
 
+    #[test]
+    fn test_waiting_time() {
+        let mut patient = create_test_patient();
+        patient.status = PatientStatus::InTreatment;
+        let mut summary = PatientSummary::from_patient(&patient);
+        assert_eq!(summary.waiting_time, None);
+
+        let now = patient.created_at + chrono::Duration::minutes(95);
+        summary.set_waiting_time(&Humanizer::new(Locale::En), now);
+        assert_eq!(summary.waiting_time.as_deref(), Some("1 hour 35 minutes"));
+
+        summary.status = PatientStatus::Discharged;
+        summary.set_waiting_time(&Humanizer::new(Locale::Ar), now);
+        assert_eq!(summary.waiting_time, None);
+    }
+
+    #[test]
+    fn test_clinical_alerts_surfaced() {
+        let mut patient = create_test_patient();
+        patient.blood_type = Some(BloodType::APositive);
+        patient.isolation_precautions = vec![IsolationPrecaution::Droplet];
+        patient.record_dnr(true, UserId::new());
+
+        let response = PatientResponse::from_patient(&patient);
+        assert!(response.dnr);
+        assert_eq!(response.dnr_recorded_by, patient.dnr_recorded_by);
+        assert_eq!(response.badges, vec!["DNR", "Droplet isolation", "A+"]);
+
+        let summary = PatientSummary::from_patient(&patient);
+        assert_eq!(summary.blood_type, Some(BloodType::APositive));
+        assert_eq!(summary.badges, response.badges);
+    }
+
     #[test]
     fn test_patient_list_response() {
         let patient = create_test_patient();
         let summaries = vec![PatientSummary::from_patient(&patient)];
         let response = PatientListResponse::new(summaries, 25, 1, 10);
         
-        assert_eq!(response.total_count, 25);
-        assert_eq!(response.page, 1);
+        assert_eq!(response.total_count, Some(25));
+        assert_eq!(response.page, Some(1));
         assert_eq!(response.page_size, 10);
-        assert_eq!(response.total_pages, 3); // ceil(25/10) = 3
+        assert_eq!(response.total_pages, Some(3)); // ceil(25/10) = 3
+        assert!(response.has_more);
+        assert_eq!(response.next_cursor, None);
+    }
+
+    #[test]
+    fn test_patient_list_response_from_cursor_page() {
+        let patient = create_test_patient();
+        let page = CursorPage {
+            items: vec![PatientSummary::from_patient(&patient)],
+            next_cursor: Some("next".to_string()),
+            has_more: true,
+        };
+        let response = PatientListResponse::from_cursor_page(page, 1);
+        assert_eq!(response.next_cursor.as_deref(), Some("next"));
+
+        // Keyset pages have no counts to report
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("total_count").is_none());
+        assert!(json.get("total_pages").is_none());
+        assert_eq!(json["page_size"], 1);
+        assert_eq!(json["has_more"], true);
     }
 
     #[test]
     fn test_vitals_dto() {
-        let vitals = PatientVitals::new(Uuid::new_v4(), Uuid::new_v4());
+        let vitals = PatientVitals::new(PatientId::new(), UserId::new());
         let dto = VitalsDto::from_vitals(&vitals);
         
         assert_eq!(dto.id, vitals.id);
@@ -0,0 +1,368 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use lib_utils::format::ExportFormat;
+use lib_utils::validation::{is_valid_emirates_id, rules};
+
+use crate::dtos::common::pagination::enum_value;
+use crate::dtos::{PageRequest, SortDirection, SortField, DEFAULT_PAGE_SIZE};
+use crate::entities::Patient;
+use crate::enums::{PatientStatus, TriageLevel};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::HospitalId;
+
+const MAX_NAME_LENGTH: usize = 100;
+const MAX_QUERY_LENGTH: usize = 200;
+
+/// Search patients. Every filter is optional and filters combine with AND;
+/// list filters match any of their values.
+///
+/// `query` is free text matched against names, patient numbers and chief
+/// complaints. Names match across spelling variants and scripts, so
+/// "Mohammed", "Muhammad" and "محمد" find the same patients.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PatientSearchRequest {
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>, // Prefix of the first, last or full name
+    #[serde(default)]
+    pub patient_number: Option<String>,
+    #[serde(default)]
+    pub national_id: Option<String>, // Emirates ID, with or without dashes
+    #[serde(default)]
+    pub triage_levels: Vec<TriageLevel>,
+    #[serde(default)]
+    pub statuses: Vec<PatientStatus>,
+    #[serde(default)]
+    pub hospital_id: Option<HospitalId>,
+    #[serde(default)]
+    pub created_from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub created_to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub assigned_staff_id: Option<Uuid>,
+    #[serde(flatten)]
+    pub page: PageRequest<PatientSortField>,
+}
+
+/// Page through patients newest first. Pages are keyed on
+/// `(created_at, id)`, so a deep page costs as much as the first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatientListQuery {
+    #[serde(default)]
+    pub cursor: Option<String>, // `next_cursor` of the previous page
+    #[serde(default = "default_list_limit")]
+    pub limit: u32,
+}
+
+fn default_list_limit() -> u32 {
+    DEFAULT_PAGE_SIZE
+}
+
+impl PatientListQuery {
+    /// The page this query asks for
+    pub fn page(&self) -> PageRequest<PatientSortField> {
+        PageRequest {
+            cursor: self.cursor.clone(),
+            limit: self.limit,
+            sort: PatientSortField::CreatedAt,
+            direction: SortDirection::Desc,
+        }
+    }
+}
+
+/// Export the patients of the caller's hospital, newest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatientExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(default = "default_export_bom")]
+    pub bom: bool, // CSV only; on by default so Excel shows Arabic names
+}
+
+fn default_export_bom() -> bool {
+    true
+}
+
+/// Columns patient lists can be sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatientSortField {
+    #[default]
+    TriageLevel, // Most urgent first when ascending
+    CreatedAt,
+    LastName,
+    PatientNumber,
+    Relevance, // Best match to the search query first when ascending
+}
+
+impl SortField for PatientSortField {
+    type Item = PatientSearchHit;
+
+    fn column(&self) -> &'static str {
+        match self {
+            PatientSortField::TriageLevel => "triage_level",
+            PatientSortField::CreatedAt => "created_at",
+            PatientSortField::LastName => "last_name",
+            PatientSortField::PatientNumber => "patient_number",
+            PatientSortField::Relevance => "search_distance",
+        }
+    }
+
+    fn sql_type(&self) -> &'static str {
+        match self {
+            PatientSortField::TriageLevel => "triage_level",
+            PatientSortField::CreatedAt => "timestamptz",
+            PatientSortField::LastName | PatientSortField::PatientNumber => "text",
+            PatientSortField::Relevance => "real",
+        }
+    }
+
+    fn value(&self, hit: &PatientSearchHit) -> String {
+        let patient = &hit.patient;
+        match self {
+            PatientSortField::TriageLevel => enum_value(&patient.triage_level),
+            PatientSortField::CreatedAt => patient.created_at.to_rfc3339(),
+            PatientSortField::LastName => patient.last_name.clone(),
+            PatientSortField::PatientNumber => patient.patient_number.clone(),
+            PatientSortField::Relevance => hit.search_distance.to_string(),
+        }
+    }
+
+    fn id(hit: &PatientSearchHit) -> Uuid {
+        hit.patient.id.as_uuid()
+    }
+}
+
+/// A patient found by a search, with how well it matched the query
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct PatientSearchHit {
+    #[sqlx(flatten)]
+    pub patient: Patient,
+    pub search_distance: f32, // Negated rank: lower is a better match, 0 without a query
+}
+
+impl PatientSearchRequest {
+    /// Get the `LIKE` pattern matching names starting with the name filter,
+    /// with wildcards in the filter itself escaped
+    pub fn name_pattern(&self) -> Option<String> {
+        self.name.as_deref().map(|name| prefix_pattern(name.trim()))
+    }
+
+    /// Get the trimmed free-text query
+    pub fn query_text(&self) -> Option<&str> {
+        self.query.as_deref().map(str::trim)
+    }
+
+    /// Get the `LIKE` pattern matching patient numbers starting with the
+    /// query
+    pub fn query_prefix_pattern(&self) -> Option<String> {
+        self.query_text().map(prefix_pattern)
+    }
+
+    /// Get the Emirates ID filter as bare digits
+    pub fn national_id_digits(&self) -> Option<String> {
+        self.national_id
+            .as_deref()
+            .map(|id| id.trim().replace('-', ""))
+    }
+}
+
+impl Validate for PatientSearchRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if let Some(ref query) = self.query {
+            if rules::required(&mut errors, "query", query, "Search query cannot be empty") {
+                rules::max_length(
+                    &mut errors,
+                    "query",
+                    query,
+                    MAX_QUERY_LENGTH,
+                    format!(
+                        "Search query must be at most {} characters",
+                        MAX_QUERY_LENGTH
+                    ),
+                );
+            }
+        } else if self.page.sort == PatientSortField::Relevance {
+            errors.add(
+                "sort",
+                "invalid",
+                "Sorting by relevance needs a search query",
+            );
+        }
+
+        if let Some(ref name) = self.name {
+            if rules::required(&mut errors, "name", name, "Name filter cannot be empty") {
+                rules::max_length(
+                    &mut errors,
+                    "name",
+                    name,
+                    MAX_NAME_LENGTH,
+                    format!("Name filter must be at most {} characters", MAX_NAME_LENGTH),
+                );
+            }
+        }
+
+        if let Some(ref number) = self.patient_number {
+            rules::required(
+                &mut errors,
+                "patient_number",
+                number,
+                "Patient number filter cannot be empty",
+            );
+        }
+
+        if self
+            .national_id
+            .as_ref()
+            .is_some_and(|id| !is_valid_emirates_id(id))
+        {
+            errors.add("national_id", "invalid_format", "Invalid Emirates ID format");
+        }
+
+        if let (Some(from), Some(to)) = (self.created_from, self.created_to) {
+            if from > to {
+                errors.add(
+                    "created_from",
+                    "invalid_range",
+                    "Date range start must be before its end",
+                );
+            }
+        }
+
+        if let Err(page_errors) = self.page.validate() {
+            errors.extend(page_errors);
+        }
+
+        errors.into_result()
+    }
+}
+
+/// A `LIKE` pattern matching text starting with `prefix`, wildcards in the
+/// prefix itself escaped
+fn prefix_pattern(prefix: &str) -> String {
+    let mut pattern = String::with_capacity(prefix.len() + 1);
+    for c in prefix.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::{Cursor, MAX_PAGE_SIZE};
+    use chrono::Duration;
+
+    #[test]
+    fn test_defaults() {
+        let request: PatientSearchRequest =
+            serde_json::from_str(r#"{"triage_levels": ["critical", "high"]}"#).unwrap();
+        assert_eq!(request.page.limit, DEFAULT_PAGE_SIZE);
+        assert_eq!(request.page.sort, PatientSortField::TriageLevel);
+        assert_eq!(request.triage_levels.len(), 2);
+        assert!(request.statuses.is_empty());
+        assert!(request.validate().is_ok());
+
+        let request: PatientSearchRequest = serde_json::from_str(
+            r#"{"limit": 10, "sort": "created_at", "direction": "desc"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.page.limit, 10);
+        assert_eq!(request.page.sort.column(), "created_at");
+        assert_eq!(request.page.direction, SortDirection::Desc);
+    }
+
+    #[test]
+    fn test_validation() {
+        let now = Utc::now();
+        let request = PatientSearchRequest {
+            name: Some(" ".to_string()),
+            national_id: Some("784-12".to_string()),
+            created_from: Some(now),
+            created_to: Some(now - Duration::days(1)),
+            page: PageRequest {
+                limit: MAX_PAGE_SIZE + 1,
+                cursor: Some("bogus".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(request.validate().unwrap_err().len(), 5);
+    }
+
+    #[test]
+    fn test_filter_values() {
+        let request = PatientSearchRequest {
+            name: Some(" Al_Ra%sh ".to_string()),
+            national_id: Some("784-1990-1234567-6".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(request.name_pattern().as_deref(), Some("Al\\_Ra\\%sh%"));
+        assert_eq!(
+            request.national_id_digits().as_deref(),
+            Some("784199012345676")
+        );
+    }
+
+    #[test]
+    fn test_list_query() {
+        let query: PatientListQuery = serde_json::from_str("{}").unwrap();
+        let page = query.page();
+        assert_eq!(page.limit, DEFAULT_PAGE_SIZE);
+        assert_eq!(page.sort.column(), "created_at");
+        assert_eq!(page.direction, SortDirection::Desc);
+        assert!(page.validate().is_ok());
+
+        // A cursor from a search sorted another way is refused
+        let search_cursor = Cursor {
+            sort: PatientSortField::TriageLevel,
+            direction: SortDirection::Asc,
+            value: "high".to_string(),
+            id: Uuid::nil(),
+        };
+        let query = PatientListQuery {
+            cursor: Some(search_cursor.encode()),
+            limit: MAX_PAGE_SIZE + 1,
+        };
+        assert_eq!(query.page().validate().unwrap_err().len(), 2);
+    }
+
+    #[test]
+    fn test_query() {
+        let request: PatientSearchRequest =
+            serde_json::from_str(r#"{"query": " محمد chest pain ", "sort": "relevance"}"#).unwrap();
+        assert_eq!(request.query_text(), Some("محمد chest pain"));
+        assert_eq!(
+            request.query_prefix_pattern().as_deref(),
+            Some("محمد chest pain%")
+        );
+        assert_eq!(request.page.sort.column(), "search_distance");
+        assert!(request.validate().is_ok());
+
+        let request = PatientSearchRequest {
+            query: Some("x".repeat(MAX_QUERY_LENGTH + 1)),
+            ..Default::default()
+        };
+        assert!(request.validate().unwrap_err().has_field("query"));
+
+        // Relevance only means something against a query
+        let request = PatientSearchRequest {
+            page: PageRequest {
+                sort: PatientSortField::Relevance,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(request.validate().unwrap_err().has_field("sort"));
+    }
+}
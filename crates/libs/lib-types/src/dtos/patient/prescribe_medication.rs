@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use crate::enums::MedicationRoute;
+use crate::errors::{Validate, ValidationErrors};
+
+const MAX_NAME_LENGTH: usize = 200;
+
+/// Order a medication for a patient; rejected if it conflicts with a
+/// recorded allergy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrescribeMedicationRequest {
+    pub name: String,
+    pub dosage: String,
+    pub route: MedicationRoute,
+    pub frequency: String,
+    pub notes: Option<String>,
+}
+
+impl Validate for PrescribeMedicationRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if rules::required(
+            &mut errors,
+            "name",
+            &self.name,
+            "Medication name is required",
+        ) {
+            rules::max_length(
+                &mut errors,
+                "name",
+                &self.name,
+                MAX_NAME_LENGTH,
+                format!(
+                    "Medication name must be at most {} characters",
+                    MAX_NAME_LENGTH
+                ),
+            );
+        }
+        rules::required(&mut errors, "dosage", &self.dosage, "Dosage is required");
+        rules::required(
+            &mut errors,
+            "frequency",
+            &self.frequency,
+            "Frequency is required",
+        );
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> PrescribeMedicationRequest {
+        PrescribeMedicationRequest {
+            name: "Paracetamol".to_string(),
+            dosage: "1 g".to_string(),
+            route: MedicationRoute::Oral,
+            frequency: "every 6 hours".to_string(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(request().validate().is_ok());
+
+        let mut invalid = request();
+        invalid.name = " ".to_string();
+        invalid.dosage = "".to_string();
+        assert_eq!(invalid.validate().unwrap_err().len(), 2);
+
+        invalid.name = "x".repeat(MAX_NAME_LENGTH + 1);
+        assert_eq!(invalid.validate().unwrap_err()[0].code, "too_long");
+    }
+}
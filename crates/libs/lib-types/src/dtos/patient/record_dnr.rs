@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// Record (`true`) or withdraw (`false`) a do-not-resuscitate order. The
+/// caller is kept as the recording clinician.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordDnrRequest {
+    pub dnr: bool,
+}
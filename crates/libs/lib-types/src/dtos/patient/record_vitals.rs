@@ -0,0 +1,335 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use lib_utils::units::{Celsius, Kilograms, MmolPerL};
+use lib_utils::validation::rules;
+
+use crate::dtos::VitalsDto;
+use crate::entities::{Deterioration, PatientVitals};
+use crate::enums::{ConsciousnessLevel, GlucoseUnit, TemperatureUnit, WeightUnit};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{PatientId, UserId};
+
+const MAX_NOTES_LENGTH: usize = 2000;
+const MAX_CLOCK_SKEW_MINUTES: i64 = 5;
+
+/// Record a set of vital signs. Temperature, glucose and weight may be sent
+/// in either unit, as monitors made for the US market report °F, mg/dL and
+/// lb, and are converted to Celsius, mmol/L and kilograms.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecordVitalsRequest {
+    pub systolic_bp: Option<i32>,
+    pub diastolic_bp: Option<i32>,
+    pub heart_rate: Option<i32>,
+    pub oxygen_saturation: Option<i32>,
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub temperature_unit: TemperatureUnit,
+    pub respiratory_rate: Option<i32>,
+    pub consciousness_level: Option<ConsciousnessLevel>,
+    #[serde(default)]
+    pub on_supplemental_oxygen: bool,
+    pub gcs_eye: Option<i32>,
+    pub gcs_verbal: Option<i32>,
+    pub gcs_motor: Option<i32>,
+    pub pain_score: Option<i32>,
+    pub blood_glucose: Option<f32>,
+    #[serde(default)]
+    pub glucose_unit: GlucoseUnit,
+    pub weight: Option<f32>,
+    #[serde(default)]
+    pub weight_unit: WeightUnit,
+    pub device_id: Option<String>,
+    pub notes: Option<String>,
+    pub recorded_at: Option<DateTime<Utc>>, // Defaults to now
+}
+
+/// A recorded set of vital signs and any signs of deterioration found in
+/// it, which have also been raised as an alert
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordVitalsResponse {
+    pub vitals: VitalsDto,
+    pub deterioration: Vec<Deterioration>,
+}
+
+impl RecordVitalsRequest {
+    /// Get the temperature in Celsius
+    pub fn temperature_celsius(&self) -> Option<Celsius> {
+        self.temperature
+            .map(|temperature| self.temperature_unit.to_celsius(temperature))
+    }
+
+    /// Get the blood glucose in mmol/L
+    pub fn blood_glucose_mmol(&self) -> Option<MmolPerL> {
+        self.blood_glucose
+            .map(|glucose| self.glucose_unit.to_mmol_per_l(glucose))
+    }
+
+    /// Get the weight in kilograms
+    pub fn weight_kilograms(&self) -> Option<Kilograms> {
+        self.weight
+            .map(|weight| self.weight_unit.to_kilograms(weight))
+    }
+
+    /// Build the vitals record, converting to stored units
+    pub fn to_vitals(&self, patient_id: PatientId, recorded_by: UserId) -> PatientVitals {
+        let mut vitals = PatientVitals::new(patient_id, recorded_by);
+        vitals.systolic_bp = self.systolic_bp;
+        vitals.diastolic_bp = self.diastolic_bp;
+        vitals.heart_rate = self.heart_rate;
+        vitals.oxygen_saturation = self.oxygen_saturation;
+        vitals.temperature = self.temperature_celsius().map(|celsius| celsius.value());
+        vitals.respiratory_rate = self.respiratory_rate;
+        vitals.consciousness_level = self.consciousness_level;
+        vitals.on_supplemental_oxygen = self.on_supplemental_oxygen;
+        vitals.gcs_eye = self.gcs_eye;
+        vitals.gcs_verbal = self.gcs_verbal;
+        vitals.gcs_motor = self.gcs_motor;
+        vitals.pain_score = self.pain_score;
+        vitals.blood_glucose = self.blood_glucose_mmol().map(|glucose| glucose.value());
+        vitals.weight = self.weight_kilograms().map(|weight| weight.value());
+        vitals.device_id = self.device_id.clone();
+        vitals.notes = self
+            .notes
+            .as_deref()
+            .map(str::trim)
+            .filter(|notes| !notes.is_empty())
+            .map(String::from);
+        if let Some(recorded_at) = self.recorded_at {
+            vitals.recorded_at = recorded_at;
+        }
+        vitals
+    }
+}
+
+impl Validate for RecordVitalsRequest {
+    /// Readings must be physiologically possible. This rejects typos and
+    /// device faults; abnormal but real values are accepted and flagged by
+    /// the vitals assessment instead.
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let has_measurement = self.systolic_bp.is_some()
+            || self.diastolic_bp.is_some()
+            || self.heart_rate.is_some()
+            || self.oxygen_saturation.is_some()
+            || self.temperature.is_some()
+            || self.respiratory_rate.is_some()
+            || self.consciousness_level.is_some()
+            || self.gcs_eye.is_some()
+            || self.gcs_verbal.is_some()
+            || self.gcs_motor.is_some()
+            || self.pain_score.is_some()
+            || self.blood_glucose.is_some()
+            || self.weight.is_some();
+        if !has_measurement {
+            errors.add("", "required", "At least one measurement is required");
+        }
+
+        let ranges = [
+            (
+                "systolic_bp",
+                "Systolic BP",
+                self.systolic_bp,
+                40,
+                300,
+                "mmHg",
+            ),
+            (
+                "diastolic_bp",
+                "Diastolic BP",
+                self.diastolic_bp,
+                20,
+                200,
+                "mmHg",
+            ),
+            ("heart_rate", "Heart rate", self.heart_rate, 20, 300, "bpm"),
+            (
+                "oxygen_saturation",
+                "Oxygen saturation",
+                self.oxygen_saturation,
+                50,
+                100,
+                "%",
+            ),
+            (
+                "respiratory_rate",
+                "Respiratory rate",
+                self.respiratory_rate,
+                0,
+                80,
+                "breaths/min",
+            ),
+        ];
+        for (field, name, value, min, max, unit) in ranges {
+            if let Some(value) = value {
+                rules::range(
+                    &mut errors,
+                    field,
+                    value,
+                    min..=max,
+                    format!(
+                        "{} must be between {} and {} {}, got {}",
+                        name, min, max, unit, value
+                    ),
+                );
+            }
+        }
+
+        match (self.systolic_bp, self.diastolic_bp) {
+            (Some(systolic), Some(diastolic)) if systolic <= diastolic => errors.add(
+                "systolic_bp",
+                "invalid",
+                "Systolic BP must be higher than diastolic BP",
+            ),
+            (None, Some(_)) => errors.add(
+                "systolic_bp",
+                "required",
+                "Systolic and diastolic BP must be recorded together",
+            ),
+            (Some(_), None) => errors.add(
+                "diastolic_bp",
+                "required",
+                "Systolic and diastolic BP must be recorded together",
+            ),
+            _ => {}
+        }
+
+        if let Some(celsius) = self.temperature_celsius() {
+            if !(Celsius(25.0)..=Celsius(45.0)).contains(&celsius) {
+                errors.add_with_value(
+                    "temperature",
+                    "out_of_range",
+                    format!(
+                        "Temperature must be between 25 and 45 °C (77 and 113 °F), got {}{}",
+                        self.temperature.unwrap_or_default(),
+                        self.temperature_unit
+                    ),
+                    self.temperature,
+                );
+            }
+        }
+        if let Some(kilograms) = self.weight_kilograms() {
+            if !(Kilograms(0.3)..=Kilograms(400.0)).contains(&kilograms) {
+                errors.add_with_value(
+                    "weight",
+                    "out_of_range",
+                    format!(
+                        "Weight must be between 0.3 and 400 kg (0.7 and 880 lb), got {} {}",
+                        self.weight.unwrap_or_default(),
+                        self.weight_unit
+                    ),
+                    self.weight,
+                );
+            }
+        }
+
+        if let Some(ref notes) = self.notes {
+            rules::max_length(
+                &mut errors,
+                "notes",
+                notes,
+                MAX_NOTES_LENGTH,
+                format!("Notes must be at most {} characters", MAX_NOTES_LENGTH),
+            );
+        }
+        if self
+            .recorded_at
+            .is_some_and(|at| at > Utc::now() + Duration::minutes(MAX_CLOCK_SKEW_MINUTES))
+        {
+            errors.add_with_value(
+                "recorded_at",
+                "in_future",
+                "Recorded time cannot be in the future",
+                self.recorded_at,
+            );
+        }
+
+        // GCS, pain and glucose scales are checked by the entity
+        if let Err(scale_errors) = self
+            .to_vitals(Uuid::nil().into(), Uuid::nil().into())
+            .validate()
+        {
+            errors.extend(scale_errors);
+        }
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> RecordVitalsRequest {
+        RecordVitalsRequest {
+            systolic_bp: Some(120),
+            diastolic_bp: Some(80),
+            heart_rate: Some(75),
+            oxygen_saturation: Some(98),
+            temperature: Some(37.0),
+            respiratory_rate: Some(16),
+            consciousness_level: Some(ConsciousnessLevel::Alert),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        assert!(request().validate().is_ok());
+        assert!(RecordVitalsRequest::default().validate().is_err());
+    }
+
+    #[test]
+    fn test_rejects_impossible_values() {
+        let mut request = request();
+        request.heart_rate = Some(700);
+        request.temperature = Some(12.0);
+        request.oxygen_saturation = Some(140);
+        let errors = request.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].field, "heart_rate");
+        assert_eq!(errors[0].rejected_value, Some(serde_json::json!(700)));
+
+        let mut request = self::request();
+        request.systolic_bp = Some(70);
+        request.diastolic_bp = Some(90);
+        assert!(request.validate().unwrap_err()[0]
+            .message
+            .contains("higher than diastolic"));
+
+        let mut request = self::request();
+        request.diastolic_bp = None;
+        assert_eq!(request.validate().unwrap_err()[0].field, "diastolic_bp");
+
+        let mut request = self::request();
+        request.gcs_motor = Some(7);
+        assert_eq!(request.validate().unwrap_err()[0].field, "gcs_motor");
+    }
+
+    #[test]
+    fn test_unit_conversion() {
+        let request: RecordVitalsRequest = serde_json::from_str(
+            r#"{"temperature": 101.3, "temperature_unit": "fahrenheit",
+                "blood_glucose": 54, "glucose_unit": "mg_per_dl"}"#,
+        )
+        .unwrap();
+        assert!(request.validate().is_ok());
+
+        let vitals = request.to_vitals(PatientId::new(), UserId::new());
+        assert!((vitals.temperature.unwrap() - 38.5).abs() < 0.01);
+        assert_eq!(vitals.blood_glucose, Some(3.0));
+
+        let request: RecordVitalsRequest =
+            serde_json::from_str(r#"{"weight": 165, "weight_unit": "pounds"}"#).unwrap();
+        assert!(request.validate().is_ok());
+        let vitals = request.to_vitals(PatientId::new(), UserId::new());
+        assert!((vitals.weight.unwrap() - 74.84).abs() < 0.01);
+
+        // 98.6 is a plausible Fahrenheit reading but not a Celsius one
+        let mut request = self::request();
+        request.temperature = Some(98.6);
+        assert_eq!(request.validate().unwrap_err()[0].field, "temperature");
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use crate::enums::TriageLevel;
+use crate::errors::{Validate, ValidationErrors};
+
+const MAX_RATIONALE_LENGTH: usize = 2000;
+
+/// Re-triage a patient; the rationale is kept in the triage history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetriageRequest {
+    pub triage_level: TriageLevel,
+    pub rationale: String,
+}
+
+impl Validate for RetriageRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if rules::required(
+            &mut errors,
+            "rationale",
+            &self.rationale,
+            "Triage rationale is required",
+        ) {
+            rules::max_length(
+                &mut errors,
+                "rationale",
+                &self.rationale,
+                MAX_RATIONALE_LENGTH,
+                format!(
+                    "Triage rationale must be at most {} characters",
+                    MAX_RATIONALE_LENGTH
+                ),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let mut request = RetriageRequest {
+            triage_level: TriageLevel::High,
+            rationale: "New onset confusion".to_string(),
+        };
+        assert!(request.validate().is_ok());
+
+        request.rationale = "  ".to_string();
+        assert!(request.validate().is_err());
+
+        request.rationale = "x".repeat(MAX_RATIONALE_LENGTH + 1);
+        assert!(request.validate().unwrap_err()[0].message.contains("at most"));
+    }
+
+    #[test]
+    fn test_accepts_esi_level_names() {
+        let request: RetriageRequest =
+            serde_json::from_str(r#"{"triage_level": "non_urgent", "rationale": "Minor cut"}"#)
+                .unwrap();
+        assert_eq!(request.triage_level, TriageLevel::NonUrgent);
+    }
+}
@@ -0,0 +1,256 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::{
+    Attachment, DischargeSummary, HandoverReport, LabOrder, LabResult, Medication, PatientEvent,
+    TriageAssessment,
+};
+use crate::enums::{PatientEventKind, PatientStatus};
+
+/// Kind of event shown on a patient's timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    Handover,
+    Triage,
+    Medication,
+    LabOrder,
+    LabResult,
+    Attachment,
+    Discharge,
+    StatusChange,
+    Assignment,
+}
+
+/// One event in the chronological view of a patient's stay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub at: DateTime<Utc>,
+    pub kind: TimelineEventKind,
+    pub summary: String,
+    pub critical: bool, // Highlighted, e.g. a critical lab value or an upgrade in acuity
+    pub reference_id: Uuid,
+}
+
+impl TimelineEntry {
+    pub fn from_handover(report: &HandoverReport) -> Self {
+        Self {
+            at: report.created_at,
+            kind: TimelineEventKind::Handover,
+            summary: format!("Ambulance handover: {}", report.mechanism),
+            critical: false,
+            reference_id: report.id,
+        }
+    }
+
+    pub fn from_triage(assessment: &TriageAssessment) -> Self {
+        let summary = match assessment.previous_level {
+            Some(previous) => format!(
+                "Re-triaged from {} to {}",
+                previous, assessment.triage_level
+            ),
+            None => format!("Triaged as {}", assessment.triage_level),
+        };
+        Self {
+            at: assessment.assessed_at,
+            kind: TimelineEventKind::Triage,
+            summary,
+            critical: assessment.is_escalation(),
+            reference_id: assessment.id,
+        }
+    }
+
+    pub fn from_medication(medication: &Medication) -> Self {
+        Self {
+            at: medication.created_at,
+            kind: TimelineEventKind::Medication,
+            summary: format!("Prescribed {}", medication.chart_line()),
+            critical: false,
+            reference_id: medication.id,
+        }
+    }
+
+    pub fn from_lab_order(order: &LabOrder) -> Self {
+        Self {
+            at: order.ordered_at,
+            kind: TimelineEventKind::LabOrder,
+            summary: format!("Ordered {}", order.panel),
+            critical: false,
+            reference_id: order.id,
+        }
+    }
+
+    pub fn from_lab_result(result: &LabResult) -> Self {
+        Self {
+            at: result.resulted_at,
+            kind: TimelineEventKind::LabResult,
+            summary: result.display_value(),
+            critical: result.is_critical(),
+            reference_id: result.id,
+        }
+    }
+
+    pub fn from_attachment(attachment: &Attachment) -> Self {
+        Self {
+            at: attachment.created_at,
+            kind: TimelineEventKind::Attachment,
+            summary: format!("Attached {}: {}", attachment.kind, attachment.file_name),
+            critical: false,
+            reference_id: attachment.id,
+        }
+    }
+
+    pub fn from_discharge(summary: &DischargeSummary) -> Self {
+        Self {
+            at: summary.discharged_at,
+            kind: TimelineEventKind::Discharge,
+            summary: format!("Discharged ({})", summary.disposition),
+            critical: false,
+            reference_id: summary.id,
+        }
+    }
+
+    /// The entry for a logged status or assignment change. Triage changes
+    /// and vital signs return `None`, as the assessments and the vitals
+    /// chart already show them.
+    pub fn from_patient_event(event: &PatientEvent) -> Option<Self> {
+        let from = event.from_value.as_deref();
+        let to = event.to_value.as_deref();
+        let (kind, summary) = match event.kind {
+            PatientEventKind::TriageChanged | PatientEventKind::VitalsRecorded => return None,
+            PatientEventKind::StatusChanged => (
+                TimelineEventKind::StatusChange,
+                format!(
+                    "Status changed from {} to {}",
+                    status_name(from),
+                    status_name(to)
+                ),
+            ),
+            PatientEventKind::StaffAssigned => (
+                TimelineEventKind::Assignment,
+                match (from, to) {
+                    (_, None) => "Staff unassigned",
+                    (None, Some(_)) => "Staff assigned",
+                    (Some(_), Some(_)) => "Staff reassigned",
+                }
+                .to_string(),
+            ),
+            PatientEventKind::AmbulanceAssigned => (
+                TimelineEventKind::Assignment,
+                match (from, to) {
+                    (_, None) => "Ambulance unassigned",
+                    (None, Some(_)) => "Ambulance assigned",
+                    (Some(_), Some(_)) => "Ambulance reassigned",
+                }
+                .to_string(),
+            ),
+            PatientEventKind::BedAssigned => (
+                TimelineEventKind::Assignment,
+                match (from, to) {
+                    (_, None) => "Bed released",
+                    (None, Some(_)) => "Assigned a bed",
+                    (Some(_), Some(_)) => "Moved to another bed",
+                }
+                .to_string(),
+            ),
+        };
+        let summary = match &event.reason {
+            Some(reason) => format!("{} ({})", summary, reason),
+            None => summary,
+        };
+        Some(Self {
+            at: event.occurred_at,
+            kind,
+            summary,
+            critical: false,
+            reference_id: event.id,
+        })
+    }
+}
+
+/// Display name of a status logged by its API name
+fn status_name(name: Option<&str>) -> String {
+    let Some(name) = name else {
+        return "none".to_string();
+    };
+    match serde_json::from_value::<PatientStatus>(name.into()) {
+        Ok(status) => status.display_name().to_string(),
+        Err(_) => name.to_string(),
+    }
+}
+
+/// Merge events into a single list, oldest first
+pub fn build_timeline(entries: impl IntoIterator<Item = TimelineEntry>) -> Vec<TimelineEntry> {
+    let mut timeline: Vec<TimelineEntry> = entries.into_iter().collect();
+    timeline.sort_by_key(|entry| entry.at);
+    timeline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::AbnormalFlag;
+    use crate::ids::{HospitalId, PatientId, UserId};
+
+    #[test]
+    fn test_timeline_order_and_critical_results() {
+        let patient_id = PatientId::new();
+        let hospital_id = HospitalId::new();
+        let order = LabOrder::new(patient_id, hospital_id, "U&E".to_string(), UserId::new());
+        let result = LabResult::new(
+            &order,
+            "K".to_string(),
+            7.1,
+            "mmol/L".to_string(),
+            AbnormalFlag::CriticalHigh,
+            order.ordered_at + chrono::Duration::minutes(40),
+        );
+
+        let timeline = build_timeline([
+            TimelineEntry::from_lab_result(&result),
+            TimelineEntry::from_lab_order(&order),
+        ]);
+        assert_eq!(timeline[0].kind, TimelineEventKind::LabOrder);
+        assert_eq!(timeline[1].summary, "K 7.1 mmol/L (HH)");
+        assert!(timeline[1].critical);
+    }
+
+    #[test]
+    fn test_patient_event_entries() {
+        let mut patient = crate::entities::Patient::new(
+            "P-1".to_string(),
+            None,
+            "Test".to_string(),
+            "Patient".to_string(),
+            40,
+            crate::enums::Gender::Male,
+            "Fall".to_string(),
+            crate::enums::TriageLevel::High,
+            HospitalId::new(),
+            None,
+            None,
+        );
+        patient.status = PatientStatus::EnRoute;
+
+        let status = PatientEvent::status_changed(&patient, PatientStatus::Dispatched, None);
+        let entry = TimelineEntry::from_patient_event(&status).unwrap();
+        assert_eq!(entry.kind, TimelineEventKind::StatusChange);
+        assert_eq!(
+            entry.summary,
+            format!(
+                "Status changed from {} to {}",
+                PatientStatus::Dispatched.display_name(),
+                PatientStatus::EnRoute.display_name()
+            )
+        );
+
+        patient.bed_id = Some(crate::ids::BedId::new());
+        let bed = PatientEvent::bed_assigned(&patient, None, None).with_reason("Resus");
+        let entry = TimelineEntry::from_patient_event(&bed).unwrap();
+        assert_eq!(entry.summary, "Assigned a bed (Resus)");
+
+        let triage = PatientEvent::triage_changed(&patient, crate::enums::TriageLevel::Low, None);
+        assert!(TimelineEntry::from_patient_event(&triage).is_none());
+    }
+}
@@ -0,0 +1,482 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use lib_utils::location::GeoPoint;
+use lib_utils::validation::{rules, EmiratesId};
+
+use super::create_patient::{validate_incident_location, validate_national_id_matches};
+use crate::entities::{
+    EmergencyContact, EmergencyContacts, Identifier, Identifiers, InsuranceInfo, MedicalHistory,
+    Patient,
+};
+use crate::enums::{BloodType, Gender, IdentifierScheme, IsolationPrecaution};
+use crate::errors::{Validate, ValidationErrors};
+
+/// Partial update of a patient's record. Absent fields are left unchanged;
+/// nullable fields are cleared with an explicit `null`. Triage, status and
+/// DNR orders have their own endpoints.
+///
+/// `version` is the version of the record the client last read. When given,
+/// the update is rejected if the patient has changed since.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UpdatePatientRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gender: Option<Gender>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub national_id: Option<Option<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifiers: Option<Vec<Identifier>>, // Replaces the whole list, Emirates ID included
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chief_complaint: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub incident_location: Option<Option<GeoPoint>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub incident_time: Option<Option<DateTime<Utc>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emergency_contacts: Option<Vec<EmergencyContact>>, // Replaces the whole list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allergies: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub medical_history: Option<MedicalHistory>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub insurance_info: Option<Option<InsuranceInfo>>,
+    #[serde(
+        default,
+        deserialize_with = "nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blood_type: Option<Option<BloodType>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isolation_precautions: Option<Vec<IsolationPrecaution>>, // Replaces the whole list
+}
+
+/// Tell an explicit `null` (`Some(None)`) apart from a missing field (`None`)
+fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl UpdatePatientRequest {
+    /// Check if the request changes nothing
+    pub fn is_empty(&self) -> bool {
+        *self
+            == Self {
+                version: self.version,
+                ..Self::default()
+            }
+    }
+
+    /// Apply the update to a patient, returning the names of the fields
+    /// whose value actually changed. Only the names are meant for audit
+    /// logs; the values are patient data.
+    pub fn apply_to(&self, patient: &mut Patient) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+
+        let trimmed = |value: &String| value.trim().to_string();
+        let non_empty = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+        };
+
+        set(
+            &mut changed,
+            "first_name",
+            &mut patient.first_name,
+            self.first_name.as_ref().map(trimmed),
+        );
+        set(
+            &mut changed,
+            "last_name",
+            &mut patient.last_name,
+            self.last_name.as_ref().map(trimmed),
+        );
+        set(&mut changed, "age", &mut patient.age, self.age);
+        set(&mut changed, "gender", &mut patient.gender, self.gender);
+        if self.national_id.is_some() || self.identifiers.is_some() {
+            // `national_id` mirrors the Emirates ID in the identifier list
+            let mut identifiers = match self.identifiers {
+                Some(ref identifiers) => Identifiers::new(identifiers.clone()).normalized(),
+                None => patient.identifiers.clone(),
+            };
+            if let Some(ref national_id) = self.national_id {
+                identifiers.set(IdentifierScheme::EmiratesId, non_empty(national_id));
+            }
+            let national_id = identifiers
+                .find(IdentifierScheme::EmiratesId)
+                .map(|identifier| identifier.value.clone());
+            set(
+                &mut changed,
+                "national_id",
+                &mut patient.national_id,
+                Some(national_id),
+            );
+            set(
+                &mut changed,
+                "identifiers",
+                &mut patient.identifiers,
+                Some(identifiers),
+            );
+        }
+        set(
+            &mut changed,
+            "chief_complaint",
+            &mut patient.chief_complaint,
+            self.chief_complaint.as_ref().map(trimmed),
+        );
+        set(
+            &mut changed,
+            "incident_location",
+            &mut patient.incident_location,
+            self.incident_location,
+        );
+        set(
+            &mut changed,
+            "incident_time",
+            &mut patient.incident_time,
+            self.incident_time,
+        );
+        set(
+            &mut changed,
+            "emergency_contacts",
+            &mut patient.emergency_contacts,
+            self.emergency_contacts.clone().map(EmergencyContacts::new),
+        );
+        set(
+            &mut changed,
+            "allergies",
+            &mut patient.allergies,
+            self.allergies.as_ref().map(|allergies| {
+                serde_json::Value::from(allergies.iter().map(trimmed).collect::<Vec<_>>())
+            }),
+        );
+        set(
+            &mut changed,
+            "medical_history",
+            &mut patient.medical_history,
+            self.medical_history.clone(),
+        );
+        set(
+            &mut changed,
+            "insurance_info",
+            &mut patient.insurance_info,
+            self.insurance_info.clone(),
+        );
+        set(
+            &mut changed,
+            "blood_type",
+            &mut patient.blood_type,
+            self.blood_type,
+        );
+        set(
+            &mut changed,
+            "isolation_precautions",
+            &mut patient.isolation_precautions,
+            self.isolation_precautions.as_deref().map(normalize_precautions),
+        );
+
+        if !changed.is_empty() {
+            patient.updated_at = Utc::now();
+        }
+        changed
+    }
+}
+
+impl Validate for UpdatePatientRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.is_empty() {
+            errors.add("", "required", "No fields to update");
+        }
+
+        if let Some(ref first_name) = self.first_name {
+            rules::required(
+                &mut errors,
+                "first_name",
+                first_name,
+                "First name cannot be empty",
+            );
+        }
+        if let Some(ref last_name) = self.last_name {
+            rules::required(
+                &mut errors,
+                "last_name",
+                last_name,
+                "Last name cannot be empty",
+            );
+        }
+        if let Some(age) = self.age {
+            rules::range(
+                &mut errors,
+                "age",
+                age,
+                0..=150,
+                "Age must be between 0 and 150",
+            );
+        }
+        if let Some(ref complaint) = self.chief_complaint {
+            rules::required(
+                &mut errors,
+                "chief_complaint",
+                complaint,
+                "Chief complaint cannot be empty",
+            );
+        }
+
+        if let Some(Some(ref national_id)) = self.national_id {
+            let national_id = national_id.trim();
+            if !national_id.is_empty() {
+                if let Err(error) = national_id.parse::<EmiratesId>() {
+                    errors.add("national_id", "invalid_format", error.to_string());
+                }
+            }
+        }
+
+        if let Some(ref identifiers) = self.identifiers {
+            let identifiers = Identifiers::new(identifiers.clone());
+            rules::nested(&mut errors, "identifiers", &identifiers);
+            if let Some(Some(ref national_id)) = self.national_id {
+                validate_national_id_matches(&mut errors, Some(national_id), &identifiers);
+            }
+        }
+
+        if let Some(Some(location)) = self.incident_location {
+            validate_incident_location(&mut errors, location);
+        }
+
+        if let Some(ref contacts) = self.emergency_contacts {
+            let contacts = EmergencyContacts::new(contacts.clone());
+            rules::nested(&mut errors, "emergency_contacts", &contacts);
+        }
+
+        rules::nested(&mut errors, "medical_history", &self.medical_history);
+        rules::nested(&mut errors, "insurance_info", &self.insurance_info);
+
+        errors.into_result()
+    }
+}
+
+/// Sort and deduplicate precautions so reordering is not a change
+pub(crate) fn normalize_precautions(
+    precautions: &[IsolationPrecaution],
+) -> Vec<IsolationPrecaution> {
+    let mut precautions = precautions.to_vec();
+    precautions.sort_unstable();
+    precautions.dedup();
+    precautions
+}
+
+/// Overwrite `target` with a requested value, recording the field if it changed
+fn set<T: PartialEq>(
+    changed: &mut Vec<&'static str>,
+    field: &'static str,
+    target: &mut T,
+    value: Option<T>,
+) {
+    if let Some(value) = value {
+        if *target != value {
+            *target = value;
+            changed.push(field);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::TriageLevel;
+    use crate::ids::HospitalId;
+
+    fn create_test_patient() -> Patient {
+        let mut patient = Patient::new(
+            "P-0001".to_string(),
+            Some("784-1990-1234567-6".to_string()),
+            "Ahmed".to_string(),
+            "Hassan".to_string(),
+            52,
+            Gender::Male,
+            "Chest pain".to_string(),
+            TriageLevel::Medium,
+            HospitalId::new(),
+            Some(GeoPoint::new(25.1124, 55.1390).unwrap()),
+            None,
+        );
+        patient.add_allergy("Penicillin".to_string());
+        patient
+    }
+
+    #[test]
+    fn test_missing_and_null_fields() {
+        let request: UpdatePatientRequest =
+            serde_json::from_str(r#"{"age": 53, "incident_location": null}"#).unwrap();
+        assert_eq!(request.age, Some(53));
+        assert_eq!(request.incident_location, Some(None));
+        assert_eq!(request.national_id, None);
+        assert!(request.validate().is_ok());
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"age": 53, "incident_location": null})
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(
+            UpdatePatientRequest::default()
+                .validate()
+                .unwrap_err()
+                .messages(),
+            vec!["No fields to update".to_string()]
+        );
+
+        // A version alone changes nothing
+        let request = UpdatePatientRequest {
+            version: Some(3),
+            ..Default::default()
+        };
+        assert!(request.is_empty());
+        assert!(request.validate().is_err());
+
+        let request = UpdatePatientRequest {
+            first_name: Some("  ".to_string()),
+            age: Some(200),
+            national_id: Some(Some("12345".to_string())),
+            ..Default::default()
+        };
+        let errors = request.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.has_field("national_id"));
+
+        // Clearing a nullable field is a valid update
+        let request = UpdatePatientRequest {
+            national_id: Some(None),
+            ..Default::default()
+        };
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_apply_reports_changed_fields() {
+        let mut patient = create_test_patient();
+        let request = UpdatePatientRequest {
+            first_name: Some(" Ahmed ".to_string()), // Unchanged once trimmed
+            chief_complaint: Some("Chest pain radiating to left arm".to_string()),
+            incident_location: Some(None),
+            allergies: Some(vec!["Penicillin".to_string(), "Latex".to_string()]),
+            ..Default::default()
+        };
+
+        let changed = request.apply_to(&mut patient);
+        assert_eq!(
+            changed,
+            vec!["chief_complaint", "incident_location", "allergies"]
+        );
+        assert_eq!(patient.first_name, "Ahmed");
+        assert_eq!(patient.incident_location, None);
+        assert_eq!(patient.get_allergies(), vec!["Penicillin", "Latex"]);
+
+        // Re-applying the same update changes nothing
+        assert!(request.apply_to(&mut patient).is_empty());
+    }
+    #[test]
+    fn test_apply_identifiers() {
+        let mut patient = create_test_patient();
+        let request: UpdatePatientRequest = serde_json::from_str(
+            r#"{"identifiers": [
+                {"scheme": "emirates_id", "value": "784-1990-1234567-6"},
+                {"scheme": "passport", "value": "n1234567"}
+            ]}"#,
+        )
+        .unwrap();
+        assert!(request.validate().is_ok());
+        assert_eq!(request.apply_to(&mut patient), vec!["identifiers"]);
+        assert_eq!(
+            patient.identifiers.find(IdentifierScheme::Passport).unwrap().value,
+            "N1234567"
+        );
+
+        // Clearing the national ID drops the Emirates ID from the list
+        let request = UpdatePatientRequest {
+            national_id: Some(None),
+            ..Default::default()
+        };
+        assert_eq!(
+            request.apply_to(&mut patient),
+            vec!["national_id", "identifiers"]
+        );
+        assert_eq!(patient.identifiers.find(IdentifierScheme::EmiratesId), None);
+
+        let conflicting = UpdatePatientRequest {
+            national_id: Some(Some("784-1985-7654321-3".to_string())),
+            identifiers: Some(patient.identifiers.to_vec()),
+            ..Default::default()
+        };
+        assert!(conflicting.validate().is_ok());
+        let conflicting = UpdatePatientRequest {
+            identifiers: Some(vec![Identifier::new(
+                IdentifierScheme::EmiratesId,
+                "784-1990-1234567-6",
+            )]),
+            ..conflicting
+        };
+        assert!(conflicting.validate().unwrap_err().has_field("national_id"));
+    }
+
+    #[test]
+    fn test_apply_clinical_alerts() {
+        let mut patient = create_test_patient();
+        let request: UpdatePatientRequest = serde_json::from_str(
+            r#"{"blood_type": "O-", "isolation_precautions": ["droplet", "contact", "droplet"]}"#,
+        )
+        .unwrap();
+
+        let changed = request.apply_to(&mut patient);
+        assert_eq!(changed, vec!["blood_type", "isolation_precautions"]);
+        assert_eq!(patient.blood_type, Some(BloodType::ONegative));
+        assert_eq!(
+            patient.isolation_precautions,
+            vec![IsolationPrecaution::Contact, IsolationPrecaution::Droplet]
+        );
+
+        let reordered = UpdatePatientRequest {
+            isolation_precautions: Some(vec![
+                IsolationPrecaution::Droplet,
+                IsolationPrecaution::Contact,
+            ]),
+            ..Default::default()
+        };
+        assert!(reordered.apply_to(&mut patient).is_empty());
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::PatientStatus;
+use crate::errors::{Validate, ValidationErrors};
+
+/// Move a patient along the workflow. With `expected_status` the change is
+/// refused if the patient has moved on since the caller last looked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdatePatientStatusRequest {
+    pub status: PatientStatus,
+    #[serde(default)]
+    pub expected_status: Option<PatientStatus>,
+}
+
+impl Validate for UpdatePatientStatusRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        // These statuses carry more than the status itself, so they have
+        // endpoints of their own
+        let reason = match self.status {
+            PatientStatus::Admitted => Some("Patients are admitted by assigning them a bed"),
+            PatientStatus::Discharged => {
+                Some("Patients are discharged through the discharge endpoint")
+            }
+            PatientStatus::Transferred => {
+                Some("Patients are transferred through the transfer endpoints")
+            }
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            errors.add("status", "not_allowed", reason);
+        }
+
+        errors.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let mut request = UpdatePatientStatusRequest {
+            status: PatientStatus::InTriage,
+            expected_status: Some(PatientStatus::Arrived),
+        };
+        assert!(request.validate().is_ok());
+
+        request.status = PatientStatus::Discharged;
+        assert!(request.validate().unwrap_err().has_field("status"));
+
+        let request: UpdatePatientStatusRequest =
+            serde_json::from_str(r#"{"status": "deceased"}"#).unwrap();
+        assert_eq!(request.expected_status, None);
+        assert!(request.validate().is_ok());
+    }
+}
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::ValidationErrors;
+
+/// Most readings accepted in one batch; a device backfilling more sends
+/// several batches
+pub const MAX_VITALS_BATCH: usize = 5000;
+
+/// Outcome of storing a batch of vitals readings. One bad reading does not
+/// hold back the rest: it is reported with its position in the batch and
+/// every other reading is stored.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VitalsBatchResult {
+    pub inserted: Vec<Uuid>,
+    pub rejected: Vec<RejectedVitals>,
+}
+
+/// A reading left out of a batch, and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RejectedVitals {
+    pub index: usize, // Position in the submitted batch
+    pub id: Uuid,
+    pub errors: ValidationErrors,
+}
+
+impl VitalsBatchResult {
+    /// Record a rejected reading
+    pub fn reject(&mut self, index: usize, id: Uuid, errors: ValidationErrors) {
+        self.rejected.push(RejectedVitals { index, id, errors });
+    }
+
+    /// Check if every reading was stored
+    pub fn is_complete(&self) -> bool {
+        self.rejected.is_empty()
+    }
+
+    /// Order rejections by their position in the batch
+    pub fn sort_rejected(&mut self) {
+        self.rejected.sort_by_key(|rejected| rejected.index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejections_by_position() {
+        let mut result = VitalsBatchResult {
+            inserted: vec![Uuid::new_v4()],
+            ..Default::default()
+        };
+        assert!(result.is_complete());
+
+        let mut not_found = ValidationErrors::new();
+        not_found.add("patient_id", "not_found", "Patient not found");
+        result.reject(4, Uuid::new_v4(), not_found);
+        let mut invalid = ValidationErrors::new();
+        invalid.add(
+            "pain_score",
+            "out_of_range",
+            "Pain score must be between 0 and 10",
+        );
+        result.reject(1, Uuid::new_v4(), invalid);
+        result.sort_rejected();
+
+        assert!(!result.is_complete());
+        let indexes: Vec<usize> = result.rejected.iter().map(|r| r.index).collect();
+        assert_eq!(indexes, vec![1, 4]);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["rejected"][1]["errors"][0]["code"], "not_found");
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dtos::{PageRequest, SortDirection, SortField, DEFAULT_PAGE_SIZE};
+use crate::entities::PatientVitals;
+use crate::errors::{Validate, ValidationErrors};
+
+/// A patient's vital signs recorded in a time window, newest first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VitalsHistoryQuery {
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>, // Inclusive
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>, // Exclusive
+    #[serde(default)]
+    pub cursor: Option<String>, // `next_cursor` of the previous page
+    #[serde(default = "default_history_limit")]
+    pub limit: u32,
+}
+
+fn default_history_limit() -> u32 {
+    DEFAULT_PAGE_SIZE
+}
+
+impl VitalsHistoryQuery {
+    /// The page this query asks for
+    pub fn page(&self) -> PageRequest<VitalsSortField> {
+        PageRequest {
+            cursor: self.cursor.clone(),
+            limit: self.limit,
+            sort: VitalsSortField::RecordedAt,
+            direction: SortDirection::Desc,
+        }
+    }
+}
+
+impl Validate for VitalsHistoryQuery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = self.page().validate().err().unwrap_or_default();
+
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from >= to {
+                errors.add("from", "invalid", "From must be before to");
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Columns vitals histories can be sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VitalsSortField {
+    #[default]
+    RecordedAt,
+}
+
+impl SortField for VitalsSortField {
+    type Item = PatientVitals;
+
+    fn column(&self) -> &'static str {
+        "recorded_at"
+    }
+
+    fn sql_type(&self) -> &'static str {
+        "timestamptz"
+    }
+
+    fn value(&self, vitals: &PatientVitals) -> String {
+        vitals.recorded_at.to_rfc3339()
+    }
+
+    fn id(vitals: &PatientVitals) -> Uuid {
+        vitals.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::dtos::{CursorPage, MAX_PAGE_SIZE};
+    use crate::ids::{PatientId, UserId};
+
+    #[test]
+    fn test_validation() {
+        let mut query: VitalsHistoryQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query.limit, DEFAULT_PAGE_SIZE);
+        assert!(query.validate().is_ok());
+
+        let now = Utc::now();
+        query.from = Some(now);
+        query.to = Some(now - Duration::hours(1));
+        query.limit = MAX_PAGE_SIZE + 1;
+        let errors = query.validate().unwrap_err();
+        assert!(errors.has_field("from"));
+        assert!(errors.has_field("limit"));
+    }
+
+    #[test]
+    fn test_cursor() {
+        let query = VitalsHistoryQuery {
+            from: None,
+            to: None,
+            cursor: None,
+            limit: 1,
+        };
+        let rows = vec![
+            PatientVitals::new(PatientId::new(), UserId::new()),
+            PatientVitals::new(PatientId::new(), UserId::new()),
+        ];
+        let page = CursorPage::from_rows(rows, &query.page(), |vitals| vitals.id);
+        assert!(page.has_more);
+
+        let next = VitalsHistoryQuery {
+            cursor: page.next_cursor,
+            ..query
+        };
+        assert!(next.validate().is_ok());
+    }
+}
@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use crate::entities::MedicalStaff;
+use crate::enums::SeniorityLevel;
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{HospitalId, UserId};
+
+const MAX_STAFF_ID_LENGTH: usize = 50;
+const MAX_CERTIFICATIONS: usize = 30;
+const MAX_CERTIFICATION_LENGTH: usize = 50;
+
+/// Onboard an existing user account as clinical staff of a hospital
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateMedicalStaffRequest {
+    pub user_id: UserId,
+    pub hospital_id: HospitalId,
+    pub staff_id: String, // Hospital-specific staff ID
+    pub specialty: String,
+    pub license_number: String, // e.g. LIC-EM-12345
+    pub department: String,
+    pub seniority_level: SeniorityLevel,
+    #[serde(default)]
+    pub certifications: Vec<String>, // e.g. ACLS, PALS
+}
+
+impl CreateMedicalStaffRequest {
+    /// Build the staff record
+    pub fn to_medical_staff(&self) -> MedicalStaff {
+        MedicalStaff::new(
+            self.user_id,
+            self.hospital_id,
+            self.staff_id.trim().to_string(),
+            self.specialty.trim().to_string(),
+            self.license_number.trim().to_uppercase(),
+            self.department.trim().to_string(),
+            self.seniority_level,
+            self.certifications
+                .iter()
+                .map(|c| c.trim().to_string())
+                .collect(),
+        )
+    }
+}
+
+impl Validate for CreateMedicalStaffRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let staff_id = self.staff_id.trim();
+        if staff_id.is_empty() {
+            errors.add("staff_id", "required", "Staff ID is required");
+        } else if staff_id.len() > MAX_STAFF_ID_LENGTH
+            || !staff_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            errors.add(
+                "staff_id",
+                "invalid_format",
+                format!(
+                    "Staff ID must be at most {} letters, digits, '-' or '_'",
+                    MAX_STAFF_ID_LENGTH
+                ),
+            );
+        }
+
+        rules::required(
+            &mut errors,
+            "specialty",
+            &self.specialty,
+            "Specialty is required",
+        );
+        rules::required(
+            &mut errors,
+            "department",
+            &self.department,
+            "Department is required",
+        );
+
+        if !is_valid_license_number(&self.license_number) {
+            errors.add(
+                "license_number",
+                "invalid_format",
+                "License number must be an authority code followed by dash-separated \
+                 segments including a number, e.g. LIC-EM-12345",
+            );
+        }
+
+        if self.certifications.len() > MAX_CERTIFICATIONS {
+            errors.add_with_value(
+                "certifications",
+                "too_many",
+                format!(
+                    "At most {} certifications can be recorded",
+                    MAX_CERTIFICATIONS
+                ),
+                self.certifications.len(),
+            );
+        }
+        let mut seen: Vec<&str> = Vec::with_capacity(self.certifications.len());
+        for (i, certification) in self.certifications.iter().map(|c| c.trim()).enumerate() {
+            let field = format!("certifications[{}]", i);
+            if certification.is_empty() || certification.len() > MAX_CERTIFICATION_LENGTH {
+                errors.add(
+                    field,
+                    "invalid",
+                    format!(
+                        "Certifications must be 1-{} characters",
+                        MAX_CERTIFICATION_LENGTH
+                    ),
+                );
+            } else if seen.iter().any(|c| c.eq_ignore_ascii_case(certification)) {
+                errors.add_with_value(
+                    field,
+                    "duplicate",
+                    format!("Duplicate certification: {}", certification),
+                    certification,
+                );
+            } else {
+                seen.push(certification);
+            }
+        }
+
+        errors.into_result()
+    }
+}
+
+/// Licenses are a 2-5 letter authority code and 1-3 alphanumeric segments,
+/// at least one of them containing a digit
+fn is_valid_license_number(license_number: &str) -> bool {
+    let mut segments = license_number.trim().split('-');
+    let authority = segments.next().unwrap_or_default();
+    let rest: Vec<&str> = segments.collect();
+
+    (2..=5).contains(&authority.len())
+        && authority.chars().all(|c| c.is_ascii_alphabetic())
+        && (1..=3).contains(&rest.len())
+        && rest.iter().all(|segment| {
+            (1..=10).contains(&segment.len()) && segment.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        && rest
+            .iter()
+            .any(|segment| segment.chars().any(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> CreateMedicalStaffRequest {
+        CreateMedicalStaffRequest {
+            user_id: UserId::new(),
+            hospital_id: HospitalId::new(),
+            staff_id: "STAFF-001".to_string(),
+            specialty: "Emergency Medicine".to_string(),
+            license_number: "lic-em-12345".to_string(),
+            department: "Emergency Department".to_string(),
+            seniority_level: SeniorityLevel::Senior,
+            certifications: vec!["ACLS".to_string(), "PALS".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_valid_request() {
+        let request = request();
+        assert!(request.validate().is_ok());
+
+        let staff = request.to_medical_staff();
+        assert_eq!(staff.license_number, "LIC-EM-12345");
+        assert!(staff.has_certification("PALS"));
+    }
+
+    #[test]
+    fn test_invalid_request() {
+        let mut request = request();
+        request.staff_id = "STAFF 001".to_string();
+        request.license_number = "LIC-EM".to_string();
+        request.certifications = vec!["ACLS".to_string(), "acls".to_string(), " ".to_string()];
+
+        let errors = request.validate().unwrap_err();
+        assert_eq!(errors.len(), 4);
+        assert!(errors
+            .iter()
+            .any(|e| e.message == "Duplicate certification: acls"));
+        assert!(errors.has_field("certifications[2]"));
+    }
+
+    #[test]
+    fn test_license_format() {
+        assert!(is_valid_license_number("DHA-P-0012345"));
+        assert!(is_valid_license_number("MOH-12345"));
+        assert!(!is_valid_license_number("12345"));
+        assert!(!is_valid_license_number("DHA-P-00-12-34"));
+        assert!(!is_valid_license_number("DHA--12345"));
+    }
+}
@@ -0,0 +1,11 @@
+//! Medical staff DTOs
+
+pub mod create_medical_staff;
+pub mod staff_query;
+pub mod staff_roster;
+pub mod update_availability;
+
+pub use create_medical_staff::CreateMedicalStaffRequest;
+pub use staff_query::StaffSortField;
+pub use staff_roster::{StaffRosterEntry, StaffRosterQuery, StaffRosterResponse};
+pub use update_availability::UpdateAvailabilityRequest;
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::dtos::common::pagination::enum_value;
+use crate::dtos::SortField;
+use crate::entities::MedicalStaff;
+
+/// Columns staff lists can be sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffSortField {
+    #[default]
+    StaffId,
+    SeniorityLevel, // Junior first when ascending
+    CreatedAt,
+}
+
+impl SortField for StaffSortField {
+    type Item = MedicalStaff;
+
+    fn column(&self) -> &'static str {
+        match self {
+            StaffSortField::StaffId => "staff_id",
+            StaffSortField::SeniorityLevel => "seniority_level",
+            StaffSortField::CreatedAt => "created_at",
+        }
+    }
+
+    fn sql_type(&self) -> &'static str {
+        match self {
+            StaffSortField::StaffId => "text",
+            StaffSortField::SeniorityLevel => "seniority_level",
+            StaffSortField::CreatedAt => "timestamptz",
+        }
+    }
+
+    fn value(&self, staff: &MedicalStaff) -> String {
+        match self {
+            StaffSortField::StaffId => staff.staff_id.clone(),
+            StaffSortField::SeniorityLevel => enum_value(&staff.seniority_level),
+            StaffSortField::CreatedAt => staff.created_at.to_rfc3339(),
+        }
+    }
+
+    fn id(staff: &MedicalStaff) -> Uuid {
+        staff.id
+    }
+}
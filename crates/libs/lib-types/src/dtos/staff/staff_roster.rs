@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::MedicalStaff;
+use crate::enums::{AvailabilityStatus, SeniorityLevel};
+use crate::ids::{HospitalId, UserId};
+
+/// Query parameters for a hospital's staff roster
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StaffRosterQuery {
+    pub department: Option<String>,
+    #[serde(default)]
+    pub available_only: bool, // Only staff who can take an assignment
+}
+
+/// One staff member on the roster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StaffRosterEntry {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub staff_id: String,
+    pub name: Option<String>,
+    pub specialty: String,
+    pub department: String,
+    pub seniority_level: SeniorityLevel,
+    pub availability_status: AvailabilityStatus,
+    pub certifications: Vec<String>,
+}
+
+/// Staff of a hospital, best candidates for an assignment first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StaffRosterResponse {
+    pub hospital_id: HospitalId,
+    pub staff: Vec<StaffRosterEntry>,
+    pub available_count: usize,
+    pub on_call_count: usize,
+    pub busy_count: usize,
+    pub off_duty_count: usize,
+}
+
+impl StaffRosterEntry {
+    /// Create from MedicalStaff entity
+    pub fn from_staff(staff: &MedicalStaff) -> Self {
+        Self {
+            id: staff.id,
+            user_id: staff.user_id,
+            staff_id: staff.staff_id.clone(),
+            name: None, // Set by service layer
+            specialty: staff.specialty.clone(),
+            department: staff.department.clone(),
+            seniority_level: staff.seniority_level,
+            availability_status: staff.availability_status,
+            certifications: staff.get_certifications(),
+        }
+    }
+}
+
+impl StaffRosterResponse {
+    /// Build the roster, ordered by assignment priority
+    pub fn from_staff(hospital_id: HospitalId, staff: &[MedicalStaff]) -> Self {
+        let count = |status: AvailabilityStatus| {
+            staff
+                .iter()
+                .filter(|member| member.availability_status == status)
+                .count()
+        };
+
+        let mut sorted: Vec<&MedicalStaff> = staff.iter().collect();
+        sorted.sort_by_key(|member| (member.assignment_priority(), member.staff_id.clone()));
+
+        Self {
+            hospital_id,
+            staff: sorted
+                .into_iter()
+                .map(StaffRosterEntry::from_staff)
+                .collect(),
+            available_count: count(AvailabilityStatus::Available),
+            on_call_count: count(AvailabilityStatus::OnCall),
+            busy_count: count(AvailabilityStatus::Busy),
+            off_duty_count: count(AvailabilityStatus::OffDuty),
+        }
+    }
+
+    /// Number of staff who can take a new assignment now
+    pub fn assignable_count(&self) -> usize {
+        self.available_count + self.on_call_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staff(
+        staff_id: &str,
+        seniority: SeniorityLevel,
+        status: AvailabilityStatus,
+    ) -> MedicalStaff {
+        let mut staff = MedicalStaff::new(
+            UserId::new(),
+            HospitalId::new(),
+            staff_id.to_string(),
+            "Emergency Medicine".to_string(),
+            "LIC-EM-12345".to_string(),
+            "Emergency Department".to_string(),
+            seniority,
+            vec!["ACLS".to_string()],
+        );
+        staff.update_availability(status);
+        staff
+    }
+
+    #[test]
+    fn test_roster_order_and_counts() {
+        let members = vec![
+            staff("S-1", SeniorityLevel::Junior, AvailabilityStatus::Busy),
+            staff("S-2", SeniorityLevel::Junior, AvailabilityStatus::Available),
+            staff(
+                "S-3",
+                SeniorityLevel::Consultant,
+                AvailabilityStatus::Available,
+            ),
+            staff("S-4", SeniorityLevel::Senior, AvailabilityStatus::OnCall),
+        ];
+        let roster = StaffRosterResponse::from_staff(HospitalId::new(), &members);
+
+        let order: Vec<&str> = roster.staff.iter().map(|s| s.staff_id.as_str()).collect();
+        assert_eq!(order, vec!["S-3", "S-2", "S-4", "S-1"]);
+        assert_eq!(roster.available_count, 2);
+        assert_eq!(roster.assignable_count(), 3);
+        assert_eq!(roster.off_duty_count, 0);
+    }
+}
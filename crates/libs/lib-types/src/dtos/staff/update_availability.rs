@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+use crate::enums::AvailabilityStatus;
+
+/// Change a staff member's availability, e.g. when going on call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateAvailabilityRequest {
+    pub availability_status: AvailabilityStatus,
+}
@@ -0,0 +1,9 @@
+//! Patient transfer DTOs
+
+pub mod request_transfer;
+pub mod respond_transfer;
+pub mod transfer_response;
+
+pub use request_transfer::RequestTransferRequest;
+pub use respond_transfer::{AcceptTransferRequest, RejectTransferRequest};
+pub use transfer_response::{TransferListResponse, TransferResponse};
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::HospitalId;
+
+pub(crate) const MAX_REASON_LENGTH: usize = 2000;
+
+/// Ask another hospital to take over a patient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestTransferRequest {
+    pub destination_hospital_id: HospitalId,
+    pub reason: String, // Clinical reason, e.g. the specialty needed
+}
+
+impl Validate for RequestTransferRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        validate_reason(&mut errors, &self.reason, "Transfer reason");
+        errors.into_result()
+    }
+}
+
+pub(crate) fn validate_reason(errors: &mut ValidationErrors, reason: &str, name: &str) {
+    if rules::required(errors, "reason", reason, format!("{} is required", name)) {
+        rules::max_length(
+            errors,
+            "reason",
+            reason,
+            MAX_REASON_LENGTH,
+            format!("{} must be at most {} characters", name, MAX_REASON_LENGTH),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation() {
+        let mut request = RequestTransferRequest {
+            destination_hospital_id: HospitalId::new(),
+            reason: "Needs neurosurgery".to_string(),
+        };
+        assert!(request.validate().is_ok());
+
+        request.reason = " ".to_string();
+        assert_eq!(request.validate().unwrap_err()[0].field, "reason");
+    }
+}
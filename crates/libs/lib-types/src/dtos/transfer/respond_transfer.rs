@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use super::request_transfer::validate_reason;
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::AmbulanceId;
+
+/// Accept a transfer at the destination hospital
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AcceptTransferRequest {
+    #[serde(default)]
+    pub ambulance_id: Option<AmbulanceId>, // Transport, if already arranged
+}
+
+/// Decline a transfer at the destination hospital
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RejectTransferRequest {
+    pub reason: String,
+}
+
+impl Validate for RejectTransferRequest {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        validate_reason(&mut errors, &self.reason, "Rejection reason");
+        errors.into_result()
+    }
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::PatientTransfer;
+use crate::enums::TransferStatus;
+use crate::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferResponse {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub origin_hospital_id: HospitalId,
+    pub destination_hospital_id: HospitalId,
+    pub reason: String,
+    pub status: TransferStatus,
+    pub status_display: String,
+    pub requested_by: UserId,
+    pub responded_by: Option<UserId>,
+    pub rejection_reason: Option<String>,
+    pub ambulance_id: Option<AmbulanceId>,
+    pub requested_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferListResponse {
+    pub transfers: Vec<TransferResponse>,
+    pub open_count: usize,
+}
+
+impl TransferResponse {
+    /// Create from PatientTransfer entity
+    pub fn from_transfer(transfer: &PatientTransfer) -> Self {
+        Self {
+            id: transfer.id,
+            patient_id: transfer.patient_id,
+            origin_hospital_id: transfer.origin_hospital_id,
+            destination_hospital_id: transfer.destination_hospital_id,
+            reason: transfer.reason.clone(),
+            status: transfer.status,
+            status_display: transfer.status.display_name().to_string(),
+            requested_by: transfer.requested_by,
+            responded_by: transfer.responded_by,
+            rejection_reason: transfer.rejection_reason.clone(),
+            ambulance_id: transfer.ambulance_id,
+            requested_at: transfer.requested_at,
+            responded_at: transfer.responded_at,
+            completed_at: transfer.completed_at,
+        }
+    }
+}
+
+impl TransferListResponse {
+    /// Create from transfer entities, counting those still in progress
+    pub fn from_transfers(transfers: &[PatientTransfer]) -> Self {
+        Self {
+            transfers: transfers
+                .iter()
+                .map(TransferResponse::from_transfer)
+                .collect(),
+            open_count: transfers.iter().filter(|t| t.is_open()).count(),
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use lib_utils::location::GeoPoint;
+
+use crate::entities::Patient;
+use crate::enums::AmbulanceStatus;
+use crate::errors::{AmbulanceError, AppError, PatientError};
+use crate::ids::{AmbulanceId, HospitalId, UserId};
+
+/// Geohash length ambulance positions are bucketed by, cells of about
+/// 5 x 4 km in the UAE
+pub const POSITION_GEOHASH_PRECISION: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Ambulance {
+    pub id: AmbulanceId,
+    pub call_sign: String,       // Radio call sign, e.g. "DXB-AMB-07"
+    pub hospital_id: HospitalId, // Hospital operating the ambulance
+    pub base_station: String,
+    pub crew: Vec<UserId>, // User ids of the crew on shift
+    pub status: AmbulanceStatus,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub location_updated_at: Option<DateTime<Utc>>,
+    pub incident_id: Option<Uuid>, // Major incident of the current mission, if any
+    pub destination: Option<String>, // Where the current mission was sent
+    pub destination_latitude: Option<f64>,
+    pub destination_longitude: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Ambulance {
+    /// Create a new ambulance, available at its base station
+    pub fn new(call_sign: String, hospital_id: HospitalId, base_station: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: AmbulanceId::new(),
+            call_sign,
+            hospital_id,
+            base_station,
+            crew: Vec::new(),
+            status: AmbulanceStatus::Available,
+            latitude: None,
+            longitude: None,
+            location_updated_at: None,
+            incident_id: None,
+            destination: None,
+            destination_latitude: None,
+            destination_longitude: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Check if the ambulance can take a new incident
+    pub fn is_available(&self) -> bool {
+        self.status.is_available() && self.has_crew()
+    }
+
+    pub fn has_crew(&self) -> bool {
+        !self.crew.is_empty()
+    }
+
+    /// Replace the crew on shift
+    pub fn assign_crew(&mut self, crew: Vec<UserId>) {
+        self.crew = crew;
+        self.updated_at = Utc::now();
+    }
+
+    /// Update ambulance status following the dispatch workflow. Becoming
+    /// available again ends the mission.
+    pub fn update_status(&mut self, new_status: AmbulanceStatus) -> Result<(), AmbulanceError> {
+        if !self.status.next_statuses().contains(&new_status) {
+            return Err(AmbulanceError::InvalidStatusTransition {
+                current: self.status,
+                requested: new_status,
+            });
+        }
+        if new_status.is_available() {
+            self.incident_id = None;
+            self.destination = None;
+            self.destination_latitude = None;
+            self.destination_longitude = None;
+        }
+        self.status = new_status;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Send the ambulance to an incident
+    pub fn dispatch(&mut self) -> Result<(), AmbulanceError> {
+        if !self.status.is_available() {
+            return Err(AmbulanceError::NotAvailable {
+                status: self.status,
+            });
+        }
+        if !self.has_crew() {
+            return Err(AmbulanceError::NoCrewAssigned);
+        }
+        self.update_status(AmbulanceStatus::Dispatched)
+    }
+
+    /// Send the ambulance to `destination`, as part of `incident_id` when
+    /// the call belongs to a major incident
+    pub fn dispatch_to(
+        &mut self,
+        destination: String,
+        position: Option<GeoPoint>,
+        incident_id: Option<Uuid>,
+    ) -> Result<(), AmbulanceError> {
+        self.dispatch()?;
+        self.incident_id = incident_id;
+        self.destination = Some(destination);
+        self.destination_latitude = position.map(|point| point.lat);
+        self.destination_longitude = position.map(|point| point.lon);
+        Ok(())
+    }
+
+    /// Check that the crew can take `patient` on board: the ambulance is on
+    /// a mission for the patient's hospital and the patient is still in care
+    pub fn check_can_carry(&self, patient: &Patient) -> Result<(), AppError> {
+        if !self.status.is_on_mission() {
+            return Err(AmbulanceError::NotOnMission {
+                status: self.status,
+            }
+            .into());
+        }
+        if self.hospital_id != patient.hospital_id {
+            return Err(PatientError::HospitalMismatch {
+                hospital_id: self.hospital_id,
+            }
+            .into());
+        }
+        if patient.status.is_terminal() {
+            return Err(PatientError::InvalidData {
+                field: "patient_id".to_string(),
+                reason: format!("Patient is {}", patient.status),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Record the latest GPS position
+    pub fn update_location(&mut self, latitude: f64, longitude: f64) -> Result<(), AmbulanceError> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(AmbulanceError::InvalidLocation {
+                latitude,
+                longitude,
+            });
+        }
+        let now = Utc::now();
+        self.latitude = Some(latitude);
+        self.longitude = Some(longitude);
+        self.location_updated_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Get the GPS position as (latitude, longitude), if known
+    pub fn location(&self) -> Option<(f64, f64)> {
+        self.latitude.zip(self.longitude)
+    }
+
+    /// Get the geohash cell of the GPS position, if known, for bucketing
+    /// ambulances by area
+    pub fn position_geohash(&self) -> Option<String> {
+        self.location()
+            .map(|(lat, lon)| GeoPoint { lat, lon }.geohash(POSITION_GEOHASH_PRECISION))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Gender, PatientStatus, TriageLevel};
+
+    fn create_test_ambulance() -> Ambulance {
+        let mut ambulance = Ambulance::new(
+            "DXB-AMB-07".to_string(),
+            HospitalId::new(),
+            "Al Barsha Station".to_string(),
+        );
+        ambulance.assign_crew(vec![UserId::new(), UserId::new()]);
+        ambulance
+    }
+
+    #[test]
+    fn test_ambulance_creation() {
+        let ambulance = Ambulance::new(
+            "DXB-AMB-07".to_string(),
+            HospitalId::new(),
+            "Al Barsha Station".to_string(),
+        );
+        assert_eq!(ambulance.status, AmbulanceStatus::Available);
+        assert!(!ambulance.has_crew());
+        assert!(!ambulance.is_available()); // No crew yet
+        assert_eq!(ambulance.location(), None);
+    }
+
+    #[test]
+    fn test_dispatch_workflow() {
+        let mut ambulance = create_test_ambulance();
+        assert!(ambulance.dispatch().is_ok());
+        assert_eq!(
+            ambulance.dispatch(),
+            Err(AmbulanceError::NotAvailable {
+                status: AmbulanceStatus::Dispatched
+            })
+        );
+
+        assert!(ambulance.update_status(AmbulanceStatus::EnRoute).is_ok());
+        assert!(ambulance
+            .update_status(AmbulanceStatus::Transporting)
+            .is_err()); // Must reach the scene first
+        assert!(ambulance.update_status(AmbulanceStatus::AtScene).is_ok());
+        assert!(ambulance
+            .update_status(AmbulanceStatus::Transporting)
+            .is_ok());
+        assert!(ambulance.update_status(AmbulanceStatus::Available).is_ok());
+        assert!(ambulance.is_available());
+    }
+
+    #[test]
+    fn test_mission_ends_when_available() {
+        let mut ambulance = create_test_ambulance();
+        let incident_id = Uuid::new_v4();
+        let scene = GeoPoint::new(25.1124, 55.1390).unwrap();
+        ambulance
+            .dispatch_to("Interchange 4".to_string(), Some(scene), Some(incident_id))
+            .unwrap();
+        assert_eq!(ambulance.status, AmbulanceStatus::Dispatched);
+        assert_eq!(ambulance.incident_id, Some(incident_id));
+        assert_eq!(ambulance.destination_latitude, Some(25.1124));
+
+        ambulance.update_status(AmbulanceStatus::EnRoute).unwrap();
+        assert_eq!(ambulance.destination.as_deref(), Some("Interchange 4"));
+        ambulance.update_status(AmbulanceStatus::Available).unwrap();
+        assert_eq!(ambulance.incident_id, None);
+        assert_eq!(ambulance.destination, None);
+    }
+
+    #[test]
+    fn test_carry_checks() {
+        let mut ambulance = create_test_ambulance();
+        let mut patient = Patient::new(
+            "PAT-001".to_string(),
+            None,
+            "Ahmed".to_string(),
+            "Al-Rashid".to_string(),
+            54,
+            Gender::Male,
+            "Chest Pain".to_string(),
+            TriageLevel::High,
+            ambulance.hospital_id,
+            None,
+            None,
+        );
+        assert!(ambulance.check_can_carry(&patient).is_err()); // Not dispatched
+
+        ambulance.dispatch().unwrap();
+        assert!(ambulance.check_can_carry(&patient).is_ok());
+
+        patient.status = PatientStatus::Deceased;
+        assert!(ambulance.check_can_carry(&patient).is_err());
+
+        patient.status = PatientStatus::Dispatched;
+        patient.hospital_id = HospitalId::new();
+        assert!(ambulance.check_can_carry(&patient).is_err());
+    }
+
+    #[test]
+    fn test_dispatch_requires_crew() {
+        let mut ambulance = create_test_ambulance();
+        ambulance.assign_crew(vec![]);
+        assert_eq!(ambulance.dispatch(), Err(AmbulanceError::NoCrewAssigned));
+    }
+
+    #[test]
+    fn test_location_updates() {
+        let mut ambulance = create_test_ambulance();
+        assert!(ambulance.update_location(25.2048, 55.2708).is_ok());
+        assert_eq!(ambulance.location(), Some((25.2048, 55.2708)));
+        assert_eq!(ambulance.position_geohash().as_deref(), Some("thrr3"));
+        assert!(ambulance.location_updated_at.is_some());
+
+        assert!(ambulance.update_location(125.0, 55.2708).is_err());
+        assert_eq!(ambulance.location(), Some((25.2048, 55.2708)));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let ambulance = create_test_ambulance();
+        let json = serde_json::to_string(&ambulance).unwrap();
+        let deserialized: Ambulance = serde_json::from_str(&json).unwrap();
+        assert_eq!(ambulance, deserialized);
+    }
+}
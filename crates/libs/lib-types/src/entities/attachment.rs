@@ -0,0 +1,90 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::enums::AttachmentKind;
+use crate::ids::{HospitalId, PatientId, UserId};
+
+/// A file attached to a patient record, such as a scene photo or a 12-lead
+/// ECG. The content lives in object storage under `storage_key`; only the
+/// metadata is kept in the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub kind: AttachmentKind,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub checksum: String, // Hex SHA-256 of the content
+    pub storage_key: String,
+    pub uploaded_by: UserId,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What is known about an upload before its record is created
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewAttachment {
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub kind: AttachmentKind,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub checksum: String, // Hex SHA-256 of the content
+    pub uploaded_by: UserId,
+}
+
+impl Attachment {
+    /// Create the record for a new upload. The storage key is derived from
+    /// ids only, so nothing identifying ends up in bucket listings.
+    pub fn new(upload: NewAttachment) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            id,
+            patient_id: upload.patient_id,
+            hospital_id: upload.hospital_id,
+            kind: upload.kind,
+            file_name: upload.file_name,
+            content_type: upload.content_type,
+            size_bytes: upload.size_bytes,
+            checksum: upload.checksum,
+            storage_key: format!("patients/{}/attachments/{}", upload.patient_id, id),
+            uploaded_by: upload.uploaded_by,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Check if the attachment can be shown inline as an image
+    pub fn is_image(&self) -> bool {
+        self.content_type.starts_with("image/")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_key_has_no_file_name() {
+        let patient_id = PatientId::new();
+        let attachment = Attachment::new(NewAttachment {
+            patient_id,
+            hospital_id: HospitalId::new(),
+            kind: AttachmentKind::Ecg,
+            file_name: "ahmed-hassan-ecg.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            size_bytes: 48_213,
+            checksum: "ab".repeat(32),
+            uploaded_by: UserId::new(),
+        });
+
+        assert_eq!(
+            attachment.storage_key,
+            format!("patients/{}/attachments/{}", patient_id, attachment.id)
+        );
+        assert!(!attachment.is_image());
+    }
+}
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::enums::{AuthEvent, AuthOutcome};
+use crate::errors::AuthError;
+use crate::ids::UserId;
+
+/// Entry of the append-only authentication audit log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct AuthAuditEntry {
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub event: AuthEvent,
+    pub outcome: AuthOutcome,
+    pub user_id: Option<UserId>,
+    pub username: Option<String>, // Attempted username or service client id
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub error_code: Option<String>,
+    pub resource: Option<String>, // Method and path of the request
+    pub correlation_id: Option<String>, // Links the entry to the request's logs
+}
+
+impl AuthAuditEntry {
+    /// Create a new entry (for creation, before database insert)
+    pub fn new(event: AuthEvent, outcome: AuthOutcome) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            occurred_at: Utc::now(),
+            event,
+            outcome,
+            user_id: None,
+            username: None,
+            ip_address: None,
+            user_agent: None,
+            error_code: None,
+            resource: None,
+            correlation_id: None,
+        }
+    }
+
+    /// Create a failure entry for a rejected request
+    pub fn failure(error: &AuthError) -> Self {
+        Self {
+            error_code: Some(error.error_code().to_string()),
+            ..Self::new(AuthEvent::for_error(error), AuthOutcome::Failure)
+        }
+    }
+
+    pub fn with_user(mut self, user_id: UserId) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn with_client(mut self, ip_address: Option<String>, user_agent: Option<String>) -> Self {
+        self.ip_address = ip_address;
+        self.user_agent = user_agent;
+        self
+    }
+
+    pub fn with_resource(mut self, resource: impl Into<String>) -> Self {
+        self.resource = Some(resource.into());
+        self
+    }
+
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_entry() {
+        let user_id = UserId::new();
+        let entry = AuthAuditEntry::failure(&AuthError::InsufficientPermissions)
+            .with_user(user_id)
+            .with_client(Some("10.20.0.5".to_string()), None)
+            .with_resource("POST /api/admin/users");
+
+        assert_eq!(entry.event, AuthEvent::AccessDenied);
+        assert_eq!(entry.outcome, AuthOutcome::Failure);
+        assert_eq!(
+            entry.error_code.as_deref(),
+            Some("AUTH_INSUFFICIENT_PERMISSIONS")
+        );
+        assert_eq!(entry.user_id, Some(user_id));
+    }
+}
@@ -0,0 +1,237 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::entities::Patient;
+use crate::enums::{BedStatus, BedType};
+use crate::errors::{AppError, HospitalError, PatientError};
+use crate::ids::{BedId, HospitalId, PatientId, UserId};
+
+/// Age below which patients go to pediatric beds
+const PEDIATRIC_MAX_AGE: i32 = 18;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Bed {
+    pub id: BedId,
+    pub hospital_id: HospitalId,
+    pub ward: String,
+    pub room: String,
+    pub bed_number: String,
+    pub bed_type: BedType,
+    pub status: BedStatus,
+    pub current_patient_id: Option<PatientId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub created_by: Option<UserId>, // None when written by the system
+    pub updated_by: Option<UserId>,
+    pub version: i64, // Bumped on every write to the row
+}
+
+impl Bed {
+    /// Create a new, available bed
+    pub fn new(
+        hospital_id: HospitalId,
+        ward: String,
+        room: String,
+        bed_number: String,
+        bed_type: BedType,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: BedId::new(),
+            hospital_id,
+            ward,
+            room,
+            bed_number,
+            bed_type,
+            status: BedStatus::Available,
+            current_patient_id: None,
+            created_at: now,
+            updated_at: now,
+            created_by: None,
+            updated_by: None,
+            version: 1,
+        }
+    }
+
+    /// Get the label shown on ward boards, e.g. "ER / 3 / B"
+    pub fn label(&self) -> String {
+        format!("{} / {} / {}", self.ward, self.room, self.bed_number)
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.status.is_assignable()
+    }
+
+    /// Check if the bed suits the patient's age and triage level
+    pub fn suits(&self, patient: &Patient) -> bool {
+        match self.bed_type {
+            BedType::Pediatric => patient.age < PEDIATRIC_MAX_AGE,
+            bed_type => bed_type.is_suitable_for_triage(patient.triage_level),
+        }
+    }
+
+    /// Check that the patient can be placed in this bed
+    pub fn check_assignable(&self, patient: &Patient) -> Result<(), AppError> {
+        if self.hospital_id != patient.hospital_id {
+            return Err(PatientError::HospitalMismatch {
+                hospital_id: self.hospital_id,
+            }
+            .into());
+        }
+        match (self.status, self.current_patient_id) {
+            (BedStatus::Occupied, Some(current)) if current == patient.id => {}
+            (BedStatus::Occupied, Some(current)) => {
+                return Err(HospitalError::BedOccupied {
+                    patient_id: current,
+                }
+                .into())
+            }
+            (status, _) if !status.is_assignable() => {
+                return Err(PatientError::BedNotAvailable { bed_id: self.id }.into())
+            }
+            _ => {}
+        }
+        if !self.suits(patient) {
+            return Err(HospitalError::IncompatibleBedType.into());
+        }
+        Ok(())
+    }
+
+    /// Place a patient in the bed
+    pub fn occupy(&mut self, patient_id: PatientId) {
+        self.status = BedStatus::Occupied;
+        self.current_patient_id = Some(patient_id);
+        self.updated_at = Utc::now();
+    }
+
+    /// Free the bed after the patient leaves; it needs cleaning before reuse
+    pub fn release(&mut self) {
+        self.status = BedStatus::Cleaning;
+        self.current_patient_id = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Update bed status following the bed workflow
+    pub fn update_status(&mut self, new_status: BedStatus) -> Result<(), HospitalError> {
+        if new_status == BedStatus::Occupied || !self.status.next_statuses().contains(&new_status) {
+            return Err(HospitalError::InvalidBedStatusTransition {
+                current: self.status,
+                requested: new_status,
+            });
+        }
+        if self.status == BedStatus::Occupied {
+            self.current_patient_id = None;
+        }
+        self.status = new_status;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Gender, TriageLevel};
+
+    fn create_test_bed(hospital_id: HospitalId, bed_type: BedType) -> Bed {
+        Bed::new(
+            hospital_id,
+            "ER".to_string(),
+            "3".to_string(),
+            "B".to_string(),
+            bed_type,
+        )
+    }
+
+    fn create_test_patient(
+        hospital_id: HospitalId,
+        age: i32,
+        triage_level: TriageLevel,
+    ) -> Patient {
+        Patient::new(
+            "PAT-001".to_string(),
+            None,
+            "Ahmed".to_string(),
+            "Al-Rashid".to_string(),
+            age,
+            Gender::Male,
+            "Chest Pain".to_string(),
+            triage_level,
+            hospital_id,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_bed_creation() {
+        let bed = create_test_bed(HospitalId::new(), BedType::Emergency);
+        assert!(bed.is_available());
+        assert_eq!(bed.label(), "ER / 3 / B");
+    }
+
+    #[test]
+    fn test_assignment_checks() {
+        let hospital_id = HospitalId::new();
+        let patient = create_test_patient(hospital_id, 45, TriageLevel::Critical);
+
+        let bed = create_test_bed(hospital_id, BedType::Icu);
+        assert!(bed.check_assignable(&patient).is_ok());
+
+        let other_hospital = create_test_bed(HospitalId::new(), BedType::Icu);
+        assert!(matches!(
+            other_hospital.check_assignable(&patient),
+            Err(AppError::Patient(PatientError::HospitalMismatch { .. }))
+        ));
+
+        let general = create_test_bed(hospital_id, BedType::General);
+        assert_eq!(
+            general.check_assignable(&patient),
+            Err(HospitalError::IncompatibleBedType.into())
+        );
+
+        let mut occupied = create_test_bed(hospital_id, BedType::Icu);
+        let other_patient = PatientId::new();
+        occupied.occupy(other_patient);
+        assert_eq!(
+            occupied.check_assignable(&patient),
+            Err(HospitalError::BedOccupied {
+                patient_id: other_patient
+            }
+            .into())
+        );
+    }
+
+    #[test]
+    fn test_pediatric_beds_by_age() {
+        let hospital_id = HospitalId::new();
+        let bed = create_test_bed(hospital_id, BedType::Pediatric);
+
+        let child = create_test_patient(hospital_id, 7, TriageLevel::Low);
+        let adult = create_test_patient(hospital_id, 30, TriageLevel::Low);
+        assert!(bed.suits(&child));
+        assert!(!bed.suits(&adult));
+    }
+
+    #[test]
+    fn test_bed_workflow() {
+        let mut bed = create_test_bed(HospitalId::new(), BedType::General);
+        bed.occupy(PatientId::new());
+        assert!(bed.update_status(BedStatus::Available).is_err()); // Must be cleaned first
+
+        bed.release();
+        assert_eq!(bed.status, BedStatus::Cleaning);
+        assert_eq!(bed.current_patient_id, None);
+        assert!(bed.update_status(BedStatus::Available).is_ok());
+        assert!(bed.update_status(BedStatus::Occupied).is_err()); // Only via occupy
+    }
+
+    #[test]
+    fn test_serialization() {
+        let bed = create_test_bed(HospitalId::new(), BedType::Isolation);
+        let json = serde_json::to_string(&bed).unwrap();
+        let deserialized: Bed = serde_json::from_str(&json).unwrap();
+        assert_eq!(bed, deserialized);
+    }
+}
@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::enums::{ConsentMethod, ConsentType, ContactRelationship};
+use crate::ids::{HospitalId, PatientId, UserId};
+
+/// Documented consent for a patient's care. `relationship` is `None` when
+/// the patient consented for themselves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Consent {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub consent_type: ConsentType,
+    pub granted_by: String,
+    pub relationship: Option<ContactRelationship>,
+    pub scope: Option<String>, // What exactly was consented to, e.g. a named procedure
+    pub method: ConsentMethod,
+    pub witness: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub recorded_by: UserId,
+    pub recorded_at: DateTime<Utc>,
+    pub withdrawn_by: Option<UserId>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    pub withdrawal_reason: Option<String>,
+}
+
+impl Consent {
+    /// Record a new consent
+    pub fn new(
+        patient_id: PatientId,
+        hospital_id: HospitalId,
+        consent_type: ConsentType,
+        granted_by: String,
+        relationship: Option<ContactRelationship>,
+        method: ConsentMethod,
+        recorded_by: UserId,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            patient_id,
+            hospital_id,
+            consent_type,
+            granted_by,
+            relationship,
+            scope: None,
+            method,
+            witness: None,
+            expires_at: None,
+            recorded_by,
+            recorded_at: Utc::now(),
+            withdrawn_by: None,
+            withdrawn_at: None,
+            withdrawal_reason: None,
+        }
+    }
+
+    /// Check if the consent still stands: not withdrawn and not expired
+    pub fn is_active(&self, at: DateTime<Utc>) -> bool {
+        self.withdrawn_at.is_none() && self.expires_at.is_none_or(|expires_at| at < expires_at)
+    }
+
+    /// Check if the consent was given on behalf of a minor by someone
+    /// entitled to, or given without anyone in an emergency
+    pub fn is_valid_for_minor(&self) -> bool {
+        self.method == ConsentMethod::EmergencyImplied
+            || self
+                .relationship
+                .is_some_and(|relationship| relationship.can_consent_for_minor())
+    }
+
+    /// Withdraw the consent. Returns false if it was already withdrawn.
+    pub fn withdraw(&mut self, by: UserId, reason: String) -> bool {
+        if self.withdrawn_at.is_some() {
+            return false;
+        }
+        self.withdrawn_by = Some(by);
+        self.withdrawn_at = Some(Utc::now());
+        self.withdrawal_reason = Some(reason);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn consent(relationship: Option<ContactRelationship>) -> Consent {
+        Consent::new(
+            PatientId::new(),
+            HospitalId::new(),
+            ConsentType::Discharge,
+            "Fatima Al-Rashid".to_string(),
+            relationship,
+            ConsentMethod::Written,
+            UserId::new(),
+        )
+    }
+
+    #[test]
+    fn test_active_until_expired_or_withdrawn() {
+        let now = Utc::now();
+        let mut consent = consent(None);
+        consent.expires_at = Some(now + Duration::hours(24));
+        assert!(consent.is_active(now));
+        assert!(!consent.is_active(now + Duration::hours(25)));
+
+        assert!(consent.withdraw(UserId::new(), "Changed their mind".to_string()));
+        assert!(!consent.withdraw(UserId::new(), "Again".to_string()));
+        assert!(!consent.is_active(now));
+    }
+
+    #[test]
+    fn test_valid_for_minor() {
+        assert!(consent(Some(ContactRelationship::Parent)).is_valid_for_minor());
+        assert!(!consent(Some(ContactRelationship::Sibling)).is_valid_for_minor());
+        assert!(!consent(None).is_valid_for_minor());
+
+        let mut implied = consent(None);
+        implied.method = ConsentMethod::EmergencyImplied;
+        assert!(implied.is_valid_for_minor());
+    }
+}
@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use lib_utils::time::format_dual_date;
+
+use crate::entities::Patient;
+use crate::enums::{DischargeDisposition, Locale};
+use crate::ids::{HospitalId, PatientId, UserId};
+
+/// The clinical record written when a patient is discharged. `document` is
+/// the rendered summary handed to the patient, kept as it was issued.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct DischargeSummary {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub diagnosis: String,
+    pub disposition: DischargeDisposition,
+    pub instructions: String, // Medication, wound care, warning signs, ...
+    pub follow_up: Option<String>, // e.g. "Cardiology clinic in 2 weeks"
+    pub guardian_consent_by: Option<String>, // Consenting parent or guardian of a minor
+    pub document: String,
+    pub discharged_by: UserId,
+    pub discharged_at: DateTime<Utc>,
+}
+
+impl DischargeSummary {
+    /// Render the summary document for a patient as plain text
+    pub fn render_document(&self, patient: &Patient) -> String {
+        let allergies = patient.get_allergies();
+        let mut lines = vec![
+            "DISCHARGE SUMMARY".to_string(),
+            format!(
+                "Patient: {} ({})",
+                patient.display_name(),
+                patient.patient_number
+            ),
+            format!("Age: {}, {}", patient.age, patient.gender),
+            format!("Arrived: {}", document_timestamp(patient.created_at)),
+            format!("Discharged: {}", document_timestamp(self.discharged_at)),
+            format!("Presenting complaint: {}", patient.chief_complaint),
+            format!("Diagnosis: {}", self.diagnosis),
+            format!("Disposition: {}", self.disposition),
+            format!(
+                "Allergies: {}",
+                if allergies.is_empty() {
+                    "None recorded".to_string()
+                } else {
+                    allergies.join(", ")
+                }
+            ),
+            String::new(),
+            "Instructions:".to_string(),
+            self.instructions.clone(),
+        ];
+        if let Some(follow_up) = &self.follow_up {
+            lines.push(String::new());
+            lines.push(format!("Follow-up: {}", follow_up));
+        }
+        if let Some(guardian) = &self.guardian_consent_by {
+            lines.push(format!("Discharge consented by guardian: {}", guardian));
+        }
+        if self.disposition.is_against_advice() {
+            lines.push(String::new());
+            lines.push(
+                "The patient left against medical advice after the risks were explained."
+                    .to_string(),
+            );
+        }
+        lines.join("\n")
+    }
+}
+
+/// Documents handed to patients carry the date in both calendars
+fn document_timestamp(at: DateTime<Utc>) -> String {
+    format!(
+        "{}, {}",
+        format_dual_date(at.date_naive(), Locale::En),
+        at.format("%H:%M UTC")
+    )
+}
@@ -0,0 +1,274 @@
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+
+use super::jsonb::impl_jsonb;
+use crate::enums::ContactRelationship;
+use crate::errors::{Validate, ValidationErrors};
+
+const MAX_CONTACTS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmergencyContact {
+    pub name: String,
+    pub relationship: ContactRelationship,
+    pub phone_number: String, // International format, e.g. "+971501234567"
+    pub email: Option<String>,
+    #[serde(default)]
+    pub is_primary: bool,
+    #[serde(default)]
+    pub sms_updates_consent: bool, // Agreed to receive status updates by SMS
+}
+
+/// A patient's emergency contacts in order of preference, stored as a JSONB array
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EmergencyContacts(pub Vec<EmergencyContact>);
+
+impl EmergencyContact {
+    /// Check if the contact can be called
+    pub fn is_reachable(&self) -> bool {
+        is_valid_phone_number(&self.phone_number)
+    }
+
+    /// Validate the contact, prefixing messages with `label`
+    pub fn validate(&self, label: &str) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        if self.name.trim().is_empty() {
+            errors.add("name", "required", format!("{} name is required", label));
+        }
+
+        if self.phone_number.trim().is_empty() {
+            errors.add(
+                "phone_number",
+                "required",
+                format!("{} phone is required", label),
+            );
+        } else if !is_valid_phone_number(&self.phone_number) {
+            errors.add(
+                "phone_number",
+                "invalid_format",
+                format!(
+                    "{} phone must be 7-15 digits with an optional leading +",
+                    label
+                ),
+            );
+        }
+
+        if self
+            .email
+            .as_ref()
+            .is_some_and(|email| !email.is_empty() && !email.contains('@'))
+        {
+            errors.add(
+                "email",
+                "invalid_format",
+                format!("{} email is invalid", label),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+impl EmergencyContacts {
+    pub fn new(contacts: Vec<EmergencyContact>) -> Self {
+        Self(contacts)
+    }
+
+    /// The contact to call first: the one marked primary, otherwise the
+    /// first in the list
+    pub fn primary(&self) -> Option<&EmergencyContact> {
+        self.0
+            .iter()
+            .find(|contact| contact.is_primary)
+            .or_else(|| self.0.first())
+    }
+
+    /// Check if at least one contact can be called
+    pub fn has_reachable_contact(&self) -> bool {
+        self.0.iter().any(EmergencyContact::is_reachable)
+    }
+
+    /// Contacts that agreed to receive status updates by SMS
+    pub fn sms_recipients(&self) -> impl Iterator<Item = &EmergencyContact> {
+        self.0
+            .iter()
+            .filter(|contact| contact.sms_updates_consent && contact.is_reachable())
+    }
+
+    /// Convert the column as stored before contacts were typed: `{}` for
+    /// none, a single contact object, or already a list, with free-text
+    /// relationships such as "Wife"
+    pub fn from_legacy_json(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        let mut contacts = match value {
+            serde_json::Value::Null => return Ok(Self::default()),
+            serde_json::Value::Object(map) if map.is_empty() => return Ok(Self::default()),
+            serde_json::Value::Object(map) => vec![serde_json::Value::Object(map)],
+            serde_json::Value::Array(contacts) => contacts,
+            value => return serde_json::from_value(value),
+        };
+
+        for contact in &mut contacts {
+            if let Some(relationship) = contact.get_mut("relationship") {
+                if let Some(label) = relationship.as_str() {
+                    *relationship = serde_json::to_value(ContactRelationship::from_label(label))?;
+                }
+            }
+        }
+        serde_json::from_value(serde_json::Value::Array(contacts))
+    }
+}
+
+impl Validate for EmergencyContacts {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        for (i, contact) in self.0.iter().enumerate() {
+            if let Err(contact_errors) = contact.validate(&format!("Emergency contact {}", i + 1)) {
+                errors.extend_nested(&format!("[{}]", i), contact_errors);
+            }
+        }
+
+        if self.0.len() > MAX_CONTACTS {
+            errors.add_with_value(
+                "",
+                "too_many",
+                format!("At most {} emergency contacts are allowed", MAX_CONTACTS),
+                self.0.len(),
+            );
+        }
+        if self.0.iter().filter(|contact| contact.is_primary).count() > 1 {
+            errors.add("", "duplicate", "Only one emergency contact can be primary");
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Deref for EmergencyContacts {
+    type Target = [EmergencyContact];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<EmergencyContact>> for EmergencyContacts {
+    fn from(contacts: Vec<EmergencyContact>) -> Self {
+        Self(contacts)
+    }
+}
+
+impl_jsonb!(EmergencyContacts, EmergencyContacts::from_legacy_json);
+
+pub(crate) fn is_valid_phone_number(phone_number: &str) -> bool {
+    let digits = phone_number
+        .trim()
+        .strip_prefix('+')
+        .unwrap_or(phone_number.trim())
+        .replace([' ', '-'], "");
+    (7..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn contact() -> EmergencyContact {
+        EmergencyContact {
+            name: "Fatima Al-Rashid".to_string(),
+            relationship: ContactRelationship::Spouse,
+            phone_number: "+971 50 123 4567".to_string(),
+            email: Some("fatima@email.com".to_string()),
+            is_primary: false,
+            sms_updates_consent: true,
+        }
+    }
+
+    fn brother() -> EmergencyContact {
+        EmergencyContact {
+            name: "Omar Al-Rashid".to_string(),
+            relationship: ContactRelationship::Sibling,
+            phone_number: "+971 55 765 4321".to_string(),
+            email: None,
+            is_primary: true,
+            sms_updates_consent: false,
+        }
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(EmergencyContacts::new(vec![contact()]).validate().is_ok());
+
+        let mut invalid = contact();
+        invalid.name = " ".to_string();
+        invalid.phone_number = "call the hotel".to_string();
+        let errors = EmergencyContacts::new(vec![contact(), invalid])
+            .validate()
+            .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| e.message.starts_with("Emergency contact 2")));
+        assert_eq!(errors[0].field, "[1].name");
+        assert_eq!(errors[1].field, "[1].phone_number");
+
+        let mut second_primary = brother();
+        second_primary.name = "Layla Al-Rashid".to_string();
+        let errors = EmergencyContacts::new(vec![brother(), second_primary])
+            .validate()
+            .unwrap_err();
+        assert!(errors[0].message.contains("one emergency contact can be primary"));
+    }
+
+    #[test]
+    fn test_primary_and_reachability() {
+        let contacts = EmergencyContacts::new(vec![contact(), brother()]);
+        assert_eq!(contacts.primary(), Some(&brother()));
+        assert_eq!(
+            EmergencyContacts::new(vec![contact()]).primary(),
+            Some(&contact())
+        );
+        assert!(contacts.has_reachable_contact());
+        assert_eq!(contacts.sms_recipients().count(), 1);
+
+        let mut unreachable = contact();
+        unreachable.phone_number = "unknown".to_string();
+        assert!(!EmergencyContacts::new(vec![unreachable]).has_reachable_contact());
+        assert!(!EmergencyContacts::default().has_reachable_contact());
+    }
+
+    #[test]
+    fn test_legacy_conversion() {
+        assert!(EmergencyContacts::from_legacy_json(json!({}))
+            .unwrap()
+            .is_empty());
+
+        let single = json!({
+            "name": "Fatima Al-Rashid",
+            "relationship": "Wife",
+            "phone_number": "+971 50 123 4567",
+            "email": null,
+        });
+        let contacts = EmergencyContacts::from_legacy_json(single.clone()).unwrap();
+        let converted = contacts.primary().unwrap();
+        assert_eq!(converted.relationship, ContactRelationship::Spouse);
+        assert!(!converted.sms_updates_consent);
+
+        let list = EmergencyContacts::from_legacy_json(json!([single])).unwrap();
+        assert_eq!(list, contacts);
+
+        assert!(EmergencyContacts::from_legacy_json(json!({"name": 7})).is_err());
+    }
+
+    #[test]
+    fn test_serializes_as_list() {
+        let contacts = EmergencyContacts::new(vec![contact(), brother()]);
+        let json = serde_json::to_value(&contacts).unwrap();
+        assert!(json.is_array());
+        assert_eq!(
+            EmergencyContacts::from_legacy_json(json).unwrap(),
+            contacts
+        );
+    }
+}
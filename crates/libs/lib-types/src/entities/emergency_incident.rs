@@ -0,0 +1,252 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::enums::{IncidentSeverity, IncidentStatus, IncidentType};
+use crate::errors::IncidentError;
+use crate::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+/// An emergency scene managed as one unit, e.g. a mass-casualty incident
+/// whose patients are spread over several ambulances and hospitals
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct EmergencyIncident {
+    pub id: Uuid,
+    pub incident_type: IncidentType,
+    pub severity: IncidentSeverity,
+    pub status: IncidentStatus,
+    pub location: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub description: Option<String>,
+    pub commander_id: Option<UserId>, // User in charge of the scene
+    pub coordinating_hospital_id: HospitalId,
+    pub hospital_ids: Vec<HospitalId>, // Receiving hospitals, including the coordinating one
+    pub ambulance_ids: Vec<AmbulanceId>,
+    pub patient_ids: Vec<PatientId>,
+    pub reported_by: UserId,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EmergencyIncident {
+    /// Create a newly reported incident coordinated by `coordinating_hospital_id`
+    pub fn new(
+        incident_type: IncidentType,
+        severity: IncidentSeverity,
+        location: String,
+        coordinating_hospital_id: HospitalId,
+        reported_by: UserId,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            incident_type,
+            severity,
+            status: IncidentStatus::Reported,
+            location,
+            latitude: None,
+            longitude: None,
+            description: None,
+            commander_id: None,
+            coordinating_hospital_id,
+            hospital_ids: vec![coordinating_hospital_id],
+            ambulance_ids: Vec::new(),
+            patient_ids: Vec::new(),
+            reported_by,
+            closed_at: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status.is_open()
+    }
+
+    /// Number of patients linked to the incident
+    pub fn casualty_count(&self) -> usize {
+        self.patient_ids.len()
+    }
+
+    /// Check if the incident must be run under the mass-casualty plan, either
+    /// as declared or because of the number of patients
+    pub fn is_mass_casualty(&self) -> bool {
+        self.severity.is_mass_casualty()
+            || IncidentSeverity::for_casualty_count(self.casualty_count()).is_mass_casualty()
+    }
+
+    /// Check if the patient count has outgrown the declared severity
+    pub fn needs_escalation(&self) -> bool {
+        IncidentSeverity::for_casualty_count(self.casualty_count()) > self.severity
+    }
+
+    /// Update incident status following the incident workflow
+    pub fn update_status(&mut self, new_status: IncidentStatus) -> Result<(), IncidentError> {
+        if !self.status.next_statuses().contains(&new_status) {
+            return Err(IncidentError::InvalidStatusTransition {
+                current: self.status,
+                requested: new_status,
+            });
+        }
+        let now = Utc::now();
+        self.status = new_status;
+        self.closed_at = (!new_status.is_open()).then_some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Change the declared severity of an open incident
+    pub fn update_severity(&mut self, severity: IncidentSeverity) -> Result<(), IncidentError> {
+        self.ensure_open()?;
+        self.severity = severity;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Hand command of the scene to another user
+    pub fn assign_commander(&mut self, user_id: UserId) -> Result<(), IncidentError> {
+        self.ensure_open()?;
+        self.commander_id = Some(user_id);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Record the GPS position of the scene
+    pub fn set_position(&mut self, latitude: f64, longitude: f64) -> Result<(), IncidentError> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(IncidentError::InvalidLocation {
+                latitude,
+                longitude,
+            });
+        }
+        self.latitude = Some(latitude);
+        self.longitude = Some(longitude);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Link patients, ambulances and receiving hospitals to the incident;
+    /// ids that are already linked are ignored
+    pub fn link(
+        &mut self,
+        patient_ids: &[PatientId],
+        ambulance_ids: &[AmbulanceId],
+        hospital_ids: &[HospitalId],
+    ) -> Result<(), IncidentError> {
+        self.ensure_open()?;
+        add_unique(&mut self.patient_ids, patient_ids);
+        add_unique(&mut self.ambulance_ids, ambulance_ids);
+        add_unique(&mut self.hospital_ids, hospital_ids);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Get the GPS position as (latitude, longitude), if known
+    pub fn position(&self) -> Option<(f64, f64)> {
+        self.latitude.zip(self.longitude)
+    }
+
+    fn ensure_open(&self) -> Result<(), IncidentError> {
+        if self.is_open() {
+            Ok(())
+        } else {
+            Err(IncidentError::Closed {
+                incident_id: self.id,
+            })
+        }
+    }
+}
+
+fn add_unique<T: Copy + PartialEq>(ids: &mut Vec<T>, new_ids: &[T]) {
+    for id in new_ids {
+        if !ids.contains(id) {
+            ids.push(*id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_incident() -> EmergencyIncident {
+        EmergencyIncident::new(
+            IncidentType::TrafficCollision,
+            IncidentSeverity::Major,
+            "Sheikh Zayed Road, Interchange 4".to_string(),
+            HospitalId::new(),
+            UserId::new(),
+        )
+    }
+
+    #[test]
+    fn test_incident_creation() {
+        let incident = create_test_incident();
+        assert_eq!(incident.status, IncidentStatus::Reported);
+        assert_eq!(
+            incident.hospital_ids,
+            vec![incident.coordinating_hospital_id]
+        );
+        assert_eq!(incident.casualty_count(), 0);
+        assert!(!incident.is_mass_casualty());
+    }
+
+    #[test]
+    fn test_status_workflow() {
+        let mut incident = create_test_incident();
+        assert!(incident.update_status(IncidentStatus::Active).is_ok());
+        assert!(incident.update_status(IncidentStatus::Closed).is_err()); // Contain first
+        assert!(incident.update_status(IncidentStatus::Contained).is_ok());
+        assert!(incident.update_status(IncidentStatus::Closed).is_ok());
+        assert!(incident.closed_at.is_some());
+
+        assert_eq!(
+            incident.assign_commander(UserId::new()),
+            Err(IncidentError::Closed {
+                incident_id: incident.id
+            })
+        );
+    }
+
+    #[test]
+    fn test_linking_resources() {
+        let mut incident = create_test_incident();
+        let patients: Vec<PatientId> = (0..12).map(|_| PatientId::new()).collect();
+        let ambulance = AmbulanceId::new();
+        let coordinating = incident.coordinating_hospital_id;
+
+        assert!(incident
+            .link(&patients[..6], &[ambulance], &[coordinating])
+            .is_ok());
+        assert!(incident.link(&patients, &[ambulance], &[]).is_ok());
+
+        assert_eq!(incident.casualty_count(), 12);
+        assert_eq!(incident.ambulance_ids, vec![ambulance]);
+        assert_eq!(incident.hospital_ids, vec![coordinating]);
+        assert!(incident.is_mass_casualty());
+        assert!(incident.needs_escalation());
+
+        assert!(incident
+            .update_severity(IncidentSeverity::MassCasualty)
+            .is_ok());
+        assert!(!incident.needs_escalation());
+    }
+
+    #[test]
+    fn test_position() {
+        let mut incident = create_test_incident();
+        assert!(incident.set_position(25.1124, 55.1390).is_ok());
+        assert_eq!(incident.position(), Some((25.1124, 55.1390)));
+        assert!(incident.set_position(25.1124, 190.0).is_err());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let incident = create_test_incident();
+        let json = serde_json::to_string(&incident).unwrap();
+        let deserialized: EmergencyIncident = serde_json::from_str(&json).unwrap();
+        assert_eq!(incident, deserialized);
+    }
+}
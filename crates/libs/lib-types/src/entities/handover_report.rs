@@ -0,0 +1,136 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::Patient;
+use crate::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+/// Structured ambulance-to-ED handover in the ATMIST format: Age, Time of
+/// incident, Mechanism, Injuries, Signs and Treatment given. Written by the
+/// crew and acknowledged by the receiving staff member, at which point
+/// responsibility for the patient passes to the hospital.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct HandoverReport {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId, // Receiving hospital
+    pub ambulance_id: AmbulanceId,
+    pub age_years: i32,
+    pub time_of_incident: Option<DateTime<Utc>>,
+    pub mechanism: String, // e.g. "Motorcyclist vs car, ~60 km/h, helmeted"
+    pub injuries: String,
+    pub signs: String, // Vital signs and their trend on scene and en route
+    pub treatment: String,
+    pub created_by: UserId,
+    pub created_at: DateTime<Utc>,
+    pub acknowledged_by: Option<UserId>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+impl HandoverReport {
+    /// Start a handover for a patient brought in by `ambulance_id`
+    pub fn new(
+        patient: &Patient,
+        ambulance_id: AmbulanceId,
+        mechanism: String,
+        injuries: String,
+        signs: String,
+        treatment: String,
+        created_by: UserId,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            patient_id: patient.id,
+            hospital_id: patient.hospital_id,
+            ambulance_id,
+            age_years: patient.age,
+            time_of_incident: patient.incident_time,
+            mechanism,
+            injuries,
+            signs,
+            treatment,
+            created_by,
+            created_at: Utc::now(),
+            acknowledged_by: None,
+            acknowledged_at: None,
+        }
+    }
+
+    /// Check if receiving staff have yet to take the handover
+    pub fn is_pending(&self) -> bool {
+        self.acknowledged_at.is_none()
+    }
+
+    /// Time from the report to its acknowledgement, or until `now` while
+    /// pending
+    pub fn waiting_time(&self, now: DateTime<Utc>) -> Duration {
+        self.acknowledged_at.unwrap_or(now) - self.created_at
+    }
+
+    /// Record that receiving staff took the handover. Returns false if it
+    /// had already been acknowledged.
+    pub fn acknowledge(&mut self, by: UserId) -> bool {
+        if self.acknowledged_at.is_some() {
+            return false;
+        }
+        self.acknowledged_by = Some(by);
+        self.acknowledged_at = Some(Utc::now());
+        true
+    }
+
+    /// Render the handover as the ATMIST lines read out at the bedside
+    pub fn atmist(&self) -> String {
+        let time = self
+            .time_of_incident
+            .map(|time| time.format("%H:%M UTC").to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        format!(
+            "A: {} years\nT: {}\nM: {}\nI: {}\nS: {}\nT: {}",
+            self.age_years, time, self.mechanism, self.injuries, self.signs, self.treatment
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Gender, TriageLevel};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_atmist_and_acknowledge() {
+        let patient = Patient::new(
+            "P-0001".to_string(),
+            None,
+            "Omar".to_string(),
+            "Saeed".to_string(),
+            27,
+            Gender::Male,
+            "Road traffic collision".to_string(),
+            TriageLevel::High,
+            HospitalId::new(),
+            None,
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 14, 5, 0).unwrap()),
+        );
+        let mut report = HandoverReport::new(
+            &patient,
+            AmbulanceId::new(),
+            "Motorcyclist vs car, ~60 km/h".to_string(),
+            "Deformed right femur".to_string(),
+            "HR 118, BP 96/60, GCS 15".to_string(),
+            "Traction splint, fentanyl 50 mcg IN".to_string(),
+            UserId::new(),
+        );
+
+        let atmist = report.atmist();
+        assert!(atmist.starts_with("A: 27 years\nT: 14:05 UTC\nM: Motorcyclist"));
+        assert!(atmist.ends_with("T: Traction splint, fentanyl 50 mcg IN"));
+
+        assert!(report.is_pending());
+        assert!(report.acknowledge(UserId::new()));
+        assert!(!report.acknowledge(UserId::new()));
+        assert!(!report.is_pending());
+        assert!(report.waiting_time(Utc::now() + Duration::hours(1)) < Duration::minutes(1));
+    }
+}
@@ -1,42 +1,51 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use uuid::Uuid;
+
+use lib_utils::location::GeoPoint;
+
+use crate::enums::{HospitalStatus, HospitalType};
+use crate::errors::HospitalError;
+use crate::ids::{HospitalId, UserId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
 pub struct Hospital {
-    pub id: Uuid,
+    pub id: HospitalId,
     pub name: String,
     pub license_number: String,
-    // Note: Using string for location for now, have to upgrate to PostGIS later
-    pub location: String,
+    pub location: GeoPoint,
     pub address: String,
     pub phone_number: String,
     pub email: String,
     pub total_beds: i32,
     pub available_beds: i32,
     pub specialties: serde_json::Value, // JSON arrray of specialties
-    pub hospital_type: String, // e.g. "Public", "Specialized", "Private"
-    pub status: String, // Active, Maintenance, Emergency Only
+    pub hospital_type: HospitalType,
+    pub status: HospitalStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub created_by: Option<UserId>, // None when written by the system
+    pub updated_by: Option<UserId>,
+    pub version: i64, // Bumped on every write to the row
+    pub deleted_at: Option<DateTime<Utc>>, // Set while soft-deleted
+    pub deleted_by: Option<UserId>,
 }
 
 impl Hospital {
     pub fn new(
         name: String,
         license_number: String,
-        location: String,
+        location: GeoPoint,
         address: String,
         phone_number: String,
         email: String,
         total_beds: i32,
         specialties: Vec<String>,
-        hospital_type: String,
+        hospital_type: HospitalType,
     ) -> Self {
         let now = Utc::now();
         Self {
-            id: Uuid::new_v4(),
+            id: HospitalId::new(),
             name,
             license_number,
             location,
@@ -47,12 +56,22 @@ impl Hospital {
             available_beds: total_beds,
             specialties: serde_json::to_value(specialties).unwrap_or(serde_json::Value::Array(vec![])),
             hospital_type,
-            status: "Active".to_string(),
+            status: HospitalStatus::Active,
             created_at: now,
             updated_at: now,
+            created_by: None,
+            updated_by: None,
+            version: 1,
+            deleted_at: None,
+            deleted_by: None,
         }
     }
 
+    /// Check if the hospital has been soft-deleted
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     pub fn occupancy_percentage(&self) -> f64 {
         if self.total_beds == 0 {
             return 0.0;
@@ -103,6 +122,28 @@ impl Hospital {
             .any(|s| s.eq_ignore_ascii_case(specialty))
     }
 
+    /// Distance from a point to the hospital in kilometres
+    pub fn distance_km(&self, from: &GeoPoint) -> f64 {
+        self.location.distance_km(from)
+    }
+
+    /// Change the bed count; available beds cannot exceed the total
+    pub fn update_capacity(
+        &mut self,
+        total_beds: i32,
+        available_beds: i32,
+    ) -> Result<(), HospitalError> {
+        if total_beds < 0 || !(0..=total_beds).contains(&available_beds) {
+            return Err(HospitalError::InvalidCapacityUpdate {
+                requested: available_beds,
+            });
+        }
+        self.total_beds = total_beds;
+        self.available_beds = available_beds;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     pub fn update_available_beds(&mut self, available_beds: i32) {
         self.available_beds = available_beds.max(0).min(self.total_beds);
         self.updated_at = Utc::now();
@@ -128,13 +169,13 @@ mod tests {
         Hospital::new(
             "Dubai Hospital".to_string(),
             "DHA-001".to_string(),
-            "25.2697,55.3094".to_string(), // Dubai coordinates
+            GeoPoint::new(25.2697, 55.3094).unwrap(), // Dubai coordinates
             "Oud Metha, Dubai, UAE".to_string(),
             "+97143193000".to_string(),
             "info@dubaihospital.ae".to_string(),
             100,
             vec!["Emergency Medicine".to_string(), "Cardiology".to_string()],
-            "Public".to_string(),
+            HospitalType::Public,
         )
     }
 
@@ -211,6 +252,29 @@ mod tests {
         assert_eq!(hospital.available_beds, 100);
     }
 
+    #[test]
+    fn test_capacity_update() {
+        let mut hospital = create_test_hospital();
+        assert!(hospital.update_capacity(120, 80).is_ok());
+        assert_eq!((hospital.total_beds, hospital.available_beds), (120, 80));
+
+        assert_eq!(
+            hospital.update_capacity(50, 80),
+            Err(HospitalError::InvalidCapacityUpdate { requested: 80 })
+        );
+        assert_eq!(hospital.total_beds, 120);
+    }
+
+    #[test]
+    fn test_distance() {
+        let hospital = create_test_hospital();
+        assert_eq!(hospital.distance_km(&hospital.location), 0.0);
+
+        let jumeirah = GeoPoint::new(25.2048, 55.2708).unwrap();
+        let distance = hospital.distance_km(&jumeirah);
+        assert!((7.0..9.0).contains(&distance), "got {}", distance);
+    }
+
     #[test]
     fn test_serialization() {
         let hospital = create_test_hospital();
@@ -0,0 +1,251 @@
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::is_valid_emirates_id;
+
+use super::jsonb::impl_jsonb;
+use crate::enums::IdentifierScheme;
+use crate::errors::{Validate, ValidationErrors};
+
+const MAX_IDENTIFIERS: usize = 10;
+const MAX_ALIAS_LENGTH: usize = 50;
+
+/// An identifier a patient is known by, such as an Emirates ID or passport
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Identifier {
+    pub scheme: IdentifierScheme,
+    pub value: String,
+}
+
+/// A patient's identifiers, stored as a JSONB array
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Identifiers(pub Vec<Identifier>);
+
+impl Identifier {
+    pub fn new(scheme: IdentifierScheme, value: impl Into<String>) -> Self {
+        Self {
+            scheme,
+            value: value.into(),
+        }
+    }
+
+    /// The identifier in its stored form: Emirates IDs as
+    /// `784-YYYY-NNNNNNN-C`, other schemes trimmed and upper-cased without
+    /// spaces
+    pub fn normalized(&self) -> Self {
+        let value = match self.scheme {
+            IdentifierScheme::EmiratesId => {
+                let digits: String = self.value.chars().filter(char::is_ascii_digit).collect();
+                if digits.len() == 15 {
+                    format!(
+                        "{}-{}-{}-{}",
+                        &digits[..3],
+                        &digits[3..7],
+                        &digits[7..14],
+                        &digits[14..]
+                    )
+                } else {
+                    self.value.trim().to_string()
+                }
+            }
+            IdentifierScheme::Unknown => self.value.trim().to_uppercase(),
+            _ => self
+                .value
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+                .to_uppercase(),
+        };
+        Self::new(self.scheme, value)
+    }
+}
+
+impl Validate for Identifier {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        let value = self.normalized().value;
+
+        if value.is_empty() {
+            errors.add("value", "required", format!("{} is required", self.scheme));
+            return errors.into_result();
+        }
+
+        let (valid, expected) = match self.scheme {
+            IdentifierScheme::EmiratesId => (
+                is_valid_emirates_id(&value),
+                "784-YYYY-NNNNNNN-C with a valid check digit",
+            ),
+            IdentifierScheme::Passport => (
+                (6..=9).contains(&value.len()) && value.chars().all(|c| c.is_ascii_alphanumeric()),
+                "6-9 letters and digits",
+            ),
+            IdentifierScheme::GccId => (
+                (8..=12).contains(&value.len()) && value.chars().all(|c| c.is_ascii_digit()),
+                "8-12 digits",
+            ),
+            IdentifierScheme::Mrn => (
+                (3..=20).contains(&value.len())
+                    && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'),
+                "3-20 letters, digits or dashes",
+            ),
+            IdentifierScheme::Unknown => (
+                value.chars().count() <= MAX_ALIAS_LENGTH,
+                "at most 50 characters",
+            ),
+        };
+        if !valid {
+            errors.add(
+                "value",
+                "invalid_format",
+                format!("{} must be {}", self.scheme, expected),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Identifiers {
+    pub fn new(identifiers: Vec<Identifier>) -> Self {
+        Self(identifiers)
+    }
+
+    /// Normalize every identifier and drop repeats, keeping the first
+    pub fn normalized(&self) -> Self {
+        let mut identifiers: Vec<Identifier> = Vec::with_capacity(self.0.len());
+        for identifier in self.0.iter().map(Identifier::normalized) {
+            if !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+        Self(identifiers)
+    }
+
+    /// Get the first identifier of a scheme
+    pub fn find(&self, scheme: IdentifierScheme) -> Option<&Identifier> {
+        self.0.iter().find(|identifier| identifier.scheme == scheme)
+    }
+
+    /// Set the identifier of a scheme, replacing any existing one, or
+    /// remove it when `value` is `None`
+    pub fn set(&mut self, scheme: IdentifierScheme, value: Option<String>) {
+        self.0.retain(|identifier| identifier.scheme != scheme);
+        if let Some(value) = value {
+            self.0.push(Identifier::new(scheme, value).normalized());
+        }
+    }
+}
+
+impl Validate for Identifiers {
+    /// A patient has at most one Emirates ID
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        for (i, identifier) in self.0.iter().enumerate() {
+            if let Err(identifier_errors) = identifier.validate() {
+                errors.extend_nested(&format!("[{}]", i), identifier_errors);
+            }
+        }
+
+        if self.0.len() > MAX_IDENTIFIERS {
+            errors.add_with_value(
+                "",
+                "too_many",
+                format!("At most {} identifiers are allowed", MAX_IDENTIFIERS),
+                self.0.len(),
+            );
+        }
+        let emirates_ids = self
+            .normalized()
+            .iter()
+            .filter(|identifier| identifier.scheme == IdentifierScheme::EmiratesId)
+            .count();
+        if emirates_ids > 1 {
+            errors.add("", "duplicate", "A patient can only have one Emirates ID");
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Deref for Identifiers {
+    type Target = [Identifier];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<Identifier>> for Identifiers {
+    fn from(identifiers: Vec<Identifier>) -> Self {
+        Self(identifiers)
+    }
+}
+
+impl_jsonb!(Identifiers, serde_json::from_value);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalization() {
+        let id = Identifier::new(IdentifierScheme::EmiratesId, "784199012345676").normalized();
+        assert_eq!(id.value, "784-1990-1234567-6");
+
+        let passport = Identifier::new(IdentifierScheme::Passport, " n1234 567 ").normalized();
+        assert_eq!(passport.value, "N1234567");
+    }
+
+    #[test]
+    fn test_per_scheme_validation() {
+        let valid = [
+            Identifier::new(IdentifierScheme::EmiratesId, "784-1990-1234567-6"),
+            Identifier::new(IdentifierScheme::Passport, "N1234567"),
+            Identifier::new(IdentifierScheme::GccId, "1012345678"),
+            Identifier::new(IdentifierScheme::Mrn, "RH-000123"),
+            Identifier::new(IdentifierScheme::Unknown, "Trauma Alpha 07"),
+        ];
+        for identifier in &valid {
+            assert!(identifier.validate().is_ok(), "{:?}", identifier);
+        }
+
+        let invalid = [
+            Identifier::new(IdentifierScheme::EmiratesId, "123-1990-1234567-1"),
+            Identifier::new(IdentifierScheme::Passport, "N12"),
+            Identifier::new(IdentifierScheme::GccId, "10123A5678"),
+            Identifier::new(IdentifierScheme::Mrn, "RH/000123"),
+            Identifier::new(IdentifierScheme::Unknown, "  "),
+        ];
+        for identifier in &invalid {
+            assert!(identifier.validate().is_err(), "{:?}", identifier);
+        }
+    }
+
+    #[test]
+    fn test_list_validation() {
+        let mut identifiers = Identifiers::new(vec![
+            Identifier::new(IdentifierScheme::EmiratesId, "784-1990-1234567-6"),
+            Identifier::new(IdentifierScheme::Passport, "N12"),
+        ]);
+        let errors = identifiers.validate().unwrap_err();
+        assert_eq!(errors[0].field, "[1].value");
+
+        identifiers.set(IdentifierScheme::Passport, Some("n1234567".to_string()));
+        identifiers.0.push(Identifier::new(
+            IdentifierScheme::EmiratesId,
+            "784-1985-7654321-3",
+        ));
+        assert!(identifiers.validate().unwrap_err()[0]
+            .message
+            .contains("one Emirates ID"));
+
+        identifiers.set(IdentifierScheme::EmiratesId, None);
+        assert_eq!(identifiers.find(IdentifierScheme::EmiratesId), None);
+        assert_eq!(
+            identifiers.find(IdentifierScheme::Passport).unwrap().value,
+            "N1234567"
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use super::jsonb::impl_jsonb;
+use crate::errors::{Validate, ValidationErrors};
+
+/// Insurance coverage of a patient, stored as JSONB (NULL when uninsured or unknown)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsuranceInfo {
+    pub provider: String,
+    pub policy_number: String,
+    pub group_number: Option<String>,
+    pub member_id: String,
+    pub valid_until: Option<NaiveDate>,
+}
+
+impl InsuranceInfo {
+    /// Check if the policy covers treatment on `date`
+    pub fn is_valid_on(&self, date: NaiveDate) -> bool {
+        self.valid_until
+            .is_none_or(|valid_until| date <= valid_until)
+    }
+
+    /// Convert the column as stored before insurance was typed, where `{}`
+    /// meant no insurance
+    pub fn from_legacy_json(value: serde_json::Value) -> Result<Option<Self>, serde_json::Error> {
+        match value {
+            serde_json::Value::Null => Ok(None),
+            serde_json::Value::Object(map) if map.is_empty() => Ok(None),
+            value => serde_json::from_value(value).map(Some),
+        }
+    }
+}
+
+impl Validate for InsuranceInfo {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        rules::required(
+            &mut errors,
+            "provider",
+            &self.provider,
+            "Insurance provider is required",
+        );
+        rules::required(
+            &mut errors,
+            "policy_number",
+            &self.policy_number,
+            "Insurance policy number is required",
+        );
+        rules::required(
+            &mut errors,
+            "member_id",
+            &self.member_id,
+            "Insurance member ID is required",
+        );
+
+        errors.into_result()
+    }
+}
+
+impl_jsonb!(InsuranceInfo, decode_column);
+
+/// Uninsured patients are stored as NULL, which never reaches this
+fn decode_column(value: serde_json::Value) -> Result<InsuranceInfo, sqlx::error::BoxDynError> {
+    InsuranceInfo::from_legacy_json(value)?.ok_or_else(|| "empty insurance record".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn insurance() -> InsuranceInfo {
+        InsuranceInfo {
+            provider: "Dubai Health Insurance".to_string(),
+            policy_number: "DH123456".to_string(),
+            group_number: None,
+            member_id: "MEM789".to_string(),
+            valid_until: NaiveDate::from_ymd_opt(2025, 12, 31),
+        }
+    }
+
+    #[test]
+    fn test_validity() {
+        let insurance = insurance();
+        assert!(insurance.validate().is_ok());
+        assert!(insurance.is_valid_on(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()));
+        assert!(!insurance.is_valid_on(NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()));
+
+        let mut invalid = insurance;
+        invalid.member_id = "".to_string();
+        assert_eq!(invalid.validate().unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn test_legacy_conversion() {
+        assert_eq!(InsuranceInfo::from_legacy_json(json!({})).unwrap(), None);
+
+        let legacy = json!({
+            "provider": "Dubai Health Insurance",
+            "policy_number": "DH123456",
+            "member_id": "MEM789",
+        });
+        let converted = InsuranceInfo::from_legacy_json(legacy).unwrap().unwrap();
+        assert_eq!(converted.valid_until, None);
+        assert!(InsuranceInfo::from_legacy_json(json!({"provider": "X"})).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+/// Map a serde type to a JSONB column. Stored values are read with
+/// `$from_json`, which should also accept older shapes of the column.
+macro_rules! impl_jsonb {
+    ($ty:ty, $from_json:path) => {
+        impl sqlx::Type<sqlx::Postgres> for $ty {
+            fn type_info() -> sqlx::postgres::PgTypeInfo {
+                <sqlx::types::Json<Self> as sqlx::Type<sqlx::Postgres>>::type_info()
+            }
+
+            fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+                <sqlx::types::Json<Self> as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+            }
+        }
+
+        impl<'q> sqlx::Encode<'q, sqlx::Postgres> for $ty {
+            fn encode_by_ref(
+                &self,
+                buf: &mut sqlx::postgres::PgArgumentBuffer,
+            ) -> sqlx::encode::IsNull {
+                sqlx::Encode::<sqlx::Postgres>::encode_by_ref(&sqlx::types::Json(self), buf)
+            }
+        }
+
+        impl<'r> sqlx::Decode<'r, sqlx::Postgres> for $ty {
+            fn decode(
+                value: sqlx::postgres::PgValueRef<'r>,
+            ) -> Result<Self, sqlx::error::BoxDynError> {
+                let sqlx::types::Json(json) =
+                    <sqlx::types::Json<serde_json::Value> as sqlx::Decode<sqlx::Postgres>>::decode(
+                        value,
+                    )?;
+                $from_json(json).map_err(Into::into)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_jsonb;
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::enums::LabOrderStatus;
+use crate::ids::{HospitalId, PatientId, UserId};
+
+/// A request to the laboratory for a panel of tests, e.g. "U&E" or "CBC"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct LabOrder {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub panel: String,
+    pub status: LabOrderStatus,
+    pub notes: Option<String>,
+    pub ordered_by: UserId,
+    pub ordered_at: DateTime<Utc>,
+    pub resulted_at: Option<DateTime<Utc>>,
+}
+
+impl LabOrder {
+    /// Create a new lab order
+    pub fn new(
+        patient_id: PatientId,
+        hospital_id: HospitalId,
+        panel: String,
+        ordered_by: UserId,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            patient_id,
+            hospital_id,
+            panel,
+            status: LabOrderStatus::Ordered,
+            notes: None,
+            ordered_by,
+            ordered_at: Utc::now(),
+            resulted_at: None,
+        }
+    }
+
+    /// Get the turnaround time from order to first result
+    pub fn turnaround(&self) -> Option<chrono::Duration> {
+        self.resulted_at
+            .map(|resulted_at| resulted_at - self.ordered_at)
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::entities::LabOrder;
+use crate::enums::AbnormalFlag;
+use crate::ids::{HospitalId, PatientId, UserId};
+
+/// A single analyte result reported against a lab order, e.g. K+ 4.2 mmol/L
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct LabResult {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub analyte: String,
+    pub value: f64,
+    pub units: String,
+    pub reference_low: Option<f64>,
+    pub reference_high: Option<f64>,
+    pub flag: AbnormalFlag,
+    pub resulted_at: DateTime<Utc>,
+    pub acknowledged_by: Option<UserId>, // Clinician who was told of a critical value
+    pub acknowledged_at: Option<DateTime<Utc>>,
+}
+
+impl LabResult {
+    /// Create a new result for an order; the flag is worked out by the caller
+    pub fn new(
+        order: &LabOrder,
+        analyte: String,
+        value: f64,
+        units: String,
+        flag: AbnormalFlag,
+        resulted_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            order_id: order.id,
+            patient_id: order.patient_id,
+            hospital_id: order.hospital_id,
+            analyte,
+            value,
+            units,
+            reference_low: None,
+            reference_high: None,
+            flag,
+            resulted_at,
+            acknowledged_by: None,
+            acknowledged_at: None,
+        }
+    }
+
+    /// Get the reference range as printed on the report, e.g. "3.5-5.1"
+    pub fn reference_range(&self) -> Option<String> {
+        match (self.reference_low, self.reference_high) {
+            (Some(low), Some(high)) => Some(format!("{}-{}", low, high)),
+            (Some(low), None) => Some(format!(">{}", low)),
+            (None, Some(high)) => Some(format!("<{}", high)),
+            (None, None) => None,
+        }
+    }
+
+    /// Get the value as shown to clinicians, e.g. "K 7.1 mmol/L (HH)"
+    pub fn display_value(&self) -> String {
+        let mut display = format!("{} {} {}", self.analyte, self.value, self.units);
+        if self.flag.is_abnormal() {
+            display.push_str(&format!(" ({})", self.flag.code()));
+        }
+        display
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.flag.is_critical()
+    }
+
+    /// Check if the result is critical and no clinician has acknowledged it
+    pub fn needs_acknowledgement(&self) -> bool {
+        self.is_critical() && self.acknowledged_at.is_none()
+    }
+
+    /// Record that a clinician was told of the result. Returns false if it
+    /// had already been acknowledged.
+    pub fn acknowledge(&mut self, by: UserId) -> bool {
+        if self.acknowledged_at.is_some() {
+            return false;
+        }
+        self.acknowledged_by = Some(by);
+        self.acknowledged_at = Some(Utc::now());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn potassium(value: f64, flag: AbnormalFlag) -> LabResult {
+        let order = LabOrder::new(
+            PatientId::new(),
+            HospitalId::new(),
+            "U&E".to_string(),
+            UserId::new(),
+        );
+        let mut result = LabResult::new(
+            &order,
+            "K".to_string(),
+            value,
+            "mmol/L".to_string(),
+            flag,
+            Utc::now(),
+        );
+        result.reference_low = Some(3.5);
+        result.reference_high = Some(5.1);
+        result
+    }
+
+    #[test]
+    fn test_display() {
+        let result = potassium(7.1, AbnormalFlag::CriticalHigh);
+        assert_eq!(result.display_value(), "K 7.1 mmol/L (HH)");
+        assert_eq!(result.reference_range().unwrap(), "3.5-5.1");
+        assert_eq!(
+            potassium(4.2, AbnormalFlag::Normal).display_value(),
+            "K 4.2 mmol/L"
+        );
+    }
+
+    #[test]
+    fn test_acknowledge_once() {
+        let mut result = potassium(7.1, AbnormalFlag::CriticalHigh);
+        assert!(result.needs_acknowledgement());
+
+        let clinician = UserId::new();
+        assert!(result.acknowledge(clinician));
+        assert!(!result.acknowledge(UserId::new()));
+        assert_eq!(result.acknowledged_by, Some(clinician));
+        assert!(!result.needs_acknowledgement());
+    }
+}
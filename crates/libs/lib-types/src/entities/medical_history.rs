@@ -0,0 +1,332 @@
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use lib_utils::validation::rules;
+
+use super::jsonb::impl_jsonb;
+use crate::errors::{Validate, ValidationErrors};
+
+const MAX_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MedicalCondition {
+    pub name: String,
+    pub icd10_code: Option<String>, // e.g. "I10" for essential hypertension
+    pub diagnosed_on: Option<NaiveDate>,
+    #[serde(default)]
+    pub chronic: bool,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Surgery {
+    pub procedure: String,
+    pub performed_on: Option<NaiveDate>,
+    pub hospital: Option<String>,
+    pub notes: Option<String>,
+}
+
+/// A medication from the patient's history (taken before this visit)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MedicationEntry {
+    pub name: String,
+    pub dosage: Option<String>,    // e.g. "5 mg"
+    pub frequency: Option<String>, // e.g. "twice daily"
+    #[serde(default = "default_active")]
+    pub active: bool, // Still being taken
+}
+
+/// A patient's past conditions, surgeries and medications, stored as JSONB
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MedicalHistory {
+    #[serde(default)]
+    pub conditions: Vec<MedicalCondition>,
+    #[serde(default)]
+    pub surgeries: Vec<Surgery>,
+    #[serde(default)]
+    pub medications: Vec<MedicationEntry>,
+    pub notes: Option<String>, // Free text that does not fit the lists
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl MedicalCondition {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            icd10_code: None,
+            diagnosed_on: None,
+            chronic: false,
+            notes: None,
+        }
+    }
+}
+
+impl MedicationEntry {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            dosage: None,
+            frequency: None,
+            active: true,
+        }
+    }
+}
+
+impl MedicalHistory {
+    /// History holding only free-text notes
+    pub fn from_notes(notes: impl Into<String>) -> Self {
+        Self {
+            notes: Some(notes.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+            && self.surgeries.is_empty()
+            && self.medications.is_empty()
+            && self
+                .notes
+                .as_ref()
+                .is_none_or(|notes| notes.trim().is_empty())
+    }
+
+    /// Medications the patient is currently taking
+    pub fn active_medications(&self) -> impl Iterator<Item = &MedicationEntry> {
+        self.medications.iter().filter(|m| m.active)
+    }
+
+    pub fn chronic_conditions(&self) -> impl Iterator<Item = &MedicalCondition> {
+        self.conditions.iter().filter(|c| c.chronic)
+    }
+
+    /// Convert the column as stored before the history was typed: `{}` for
+    /// none, a free-text string, or lists holding plain names
+    pub fn from_legacy_json(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        match value {
+            serde_json::Value::Null => Ok(Self::default()),
+            serde_json::Value::String(notes) if notes.trim().is_empty() => Ok(Self::default()),
+            serde_json::Value::String(notes) => Ok(Self::from_notes(notes)),
+            serde_json::Value::Object(mut map) => {
+                for (list, key) in [
+                    ("conditions", "name"),
+                    ("surgeries", "procedure"),
+                    ("medications", "name"),
+                ] {
+                    if let Some(serde_json::Value::Array(entries)) = map.get_mut(list) {
+                        for entry in entries.iter_mut() {
+                            if let serde_json::Value::String(name) = entry {
+                                *entry = serde_json::json!({ key: name });
+                            }
+                        }
+                    }
+                }
+                serde_json::from_value(serde_json::Value::Object(map))
+            }
+            value => serde_json::from_value(value),
+        }
+    }
+}
+
+impl Validate for MedicalHistory {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        for (field, len) in [
+            ("conditions", self.conditions.len()),
+            ("surgeries", self.surgeries.len()),
+            ("medications", self.medications.len()),
+        ] {
+            if len > MAX_ENTRIES {
+                errors.add_with_value(
+                    field,
+                    "too_many",
+                    format!(
+                        "Medical history lists are limited to {} entries",
+                        MAX_ENTRIES
+                    ),
+                    len,
+                );
+            }
+        }
+
+        rules::each(&mut errors, "conditions", &self.conditions);
+        rules::each(&mut errors, "surgeries", &self.surgeries);
+        rules::each(&mut errors, "medications", &self.medications);
+
+        errors.into_result()
+    }
+}
+
+impl Validate for MedicalCondition {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        rules::required(
+            &mut errors,
+            "name",
+            &self.name,
+            "Condition name is required",
+        );
+        if self
+            .icd10_code
+            .as_deref()
+            .is_some_and(|code| !is_valid_icd10_code(code))
+        {
+            errors.add(
+                "icd10_code",
+                "invalid_format",
+                format!("Invalid ICD-10 code for {}", self.name),
+            );
+        }
+        if is_in_future(self.diagnosed_on) {
+            errors.add(
+                "diagnosed_on",
+                "in_future",
+                format!("Diagnosis date of {} is in the future", self.name),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for Surgery {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        rules::required(
+            &mut errors,
+            "procedure",
+            &self.procedure,
+            "Surgery procedure is required",
+        );
+        if is_in_future(self.performed_on) {
+            errors.add(
+                "performed_on",
+                "in_future",
+                format!("Date of {} is in the future", self.procedure),
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+impl Validate for MedicationEntry {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        rules::required(
+            &mut errors,
+            "name",
+            &self.name,
+            "Medication name is required",
+        );
+        errors.into_result()
+    }
+}
+
+impl_jsonb!(MedicalHistory, MedicalHistory::from_legacy_json);
+
+fn is_in_future(date: Option<NaiveDate>) -> bool {
+    date.is_some_and(|date| date > Utc::now().date_naive())
+}
+
+/// Check the shape of an ICD-10 code: a letter, two digits and an optional
+/// subdivision (e.g. "E11" or "E11.9")
+fn is_valid_icd10_code(code: &str) -> bool {
+    let (category, subdivision) = code.split_once('.').unwrap_or((code, ""));
+    let mut chars = category.chars();
+    category.len() == 3
+        && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_alphanumeric())
+        && subdivision.len() <= 4
+        && subdivision.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn history() -> MedicalHistory {
+        MedicalHistory {
+            conditions: vec![MedicalCondition {
+                icd10_code: Some("I10".to_string()),
+                chronic: true,
+                ..MedicalCondition::new("Hypertension")
+            }],
+            surgeries: vec![Surgery {
+                procedure: "Appendectomy".to_string(),
+                performed_on: NaiveDate::from_ymd_opt(2015, 3, 2),
+                hospital: Some("Rashid Hospital".to_string()),
+                notes: None,
+            }],
+            medications: vec![
+                MedicationEntry {
+                    dosage: Some("5 mg".to_string()),
+                    ..MedicationEntry::new("Amlodipine")
+                },
+                MedicationEntry {
+                    active: false,
+                    ..MedicationEntry::new("Ibuprofen")
+                },
+            ],
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_queries() {
+        let history = history();
+        assert!(!history.is_empty());
+        assert_eq!(history.active_medications().count(), 1);
+        assert_eq!(history.chronic_conditions().count(), 1);
+        assert!(MedicalHistory::default().is_empty());
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(history().validate().is_ok());
+
+        let mut invalid = history();
+        invalid.conditions[0].icd10_code = Some("hypertension".to_string());
+        invalid.surgeries[0].performed_on = Some(Utc::now().date_naive() + chrono::Days::new(3));
+        invalid.medications.push(MedicationEntry::new(" "));
+
+        let errors = invalid.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.has_field("conditions[0].icd10_code"));
+        assert!(errors.has_field("surgeries[0].performed_on"));
+        assert!(errors.has_field("medications[2].name"));
+    }
+
+    #[test]
+    fn test_legacy_conversion() {
+        assert!(MedicalHistory::from_legacy_json(json!({}))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            MedicalHistory::from_legacy_json(json!("Hypertension")).unwrap(),
+            MedicalHistory::from_notes("Hypertension")
+        );
+
+        let legacy = json!({
+            "conditions": ["Diabetes", {"name": "Asthma", "chronic": true}],
+            "medications": ["Metformin"],
+        });
+        let history = MedicalHistory::from_legacy_json(legacy).unwrap();
+        assert_eq!(history.conditions[0], MedicalCondition::new("Diabetes"));
+        assert!(history.conditions[1].chronic);
+        assert_eq!(history.medications, vec![MedicationEntry::new("Metformin")]);
+
+        let round_trip = serde_json::to_value(&history).unwrap();
+        assert_eq!(
+            MedicalHistory::from_legacy_json(round_trip).unwrap(),
+            history
+        );
+    }
+}
@@ -3,13 +3,14 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-use crate::enums::AvailabilityStatus;
+use crate::enums::{AvailabilityStatus, SeniorityLevel};
+use crate::ids::{HospitalId, UserId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
 pub struct MedicalStaff {
     pub id: Uuid,
-    pub user_id: Uuid, // Foreign key to User table
-    pub hospital_id: Uuid,
+    pub user_id: UserId, // Foreign key to User table
+    pub hospital_id: HospitalId,
     pub staff_id: String, // Hospital-specific staff ID
     pub specialty: String,
     pub availability_status: AvailabilityStatus,
@@ -17,21 +18,25 @@ pub struct MedicalStaff {
     pub certifications: serde_json::Value, // JSON array of certifications
     pub shift_schedule: serde_json::Value, // JSON object with shift information
     pub department: String,
-    pub seniority_level: String, // "Junior", "Senior", "Consultant", "Director"
+    pub seniority_level: SeniorityLevel,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub created_by: Option<UserId>, // None when written by the system
+    pub updated_by: Option<UserId>,
+    pub deleted_at: Option<DateTime<Utc>>, // Set while soft-deleted
+    pub deleted_by: Option<UserId>,
 }
 
 impl MedicalStaff {
     /// Create new medical staff record
     pub fn new(
-        user_id: Uuid,
-        hospital_id: Uuid,
+        user_id: UserId,
+        hospital_id: HospitalId,
         staff_id: String,
         specialty: String,
         license_number: String,
         department: String,
-        seniority_level: String,
+        seniority_level: SeniorityLevel,
         certifications: Vec<String>,
     ) -> Self {
         let now = Utc::now();
@@ -49,6 +54,10 @@ impl MedicalStaff {
             seniority_level,
             created_at: now,
             updated_at: now,
+            created_by: None,
+            updated_by: None,
+            deleted_at: None,
+            deleted_by: None,
         }
     }
 
@@ -57,6 +66,11 @@ impl MedicalStaff {
         self.availability_status.can_take_assignment()
     }
 
+    /// Check if the record has been soft-deleted
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     /// Check if staff is currently working
     pub fn is_working(&self) -> bool {
         self.availability_status.is_working()
@@ -71,12 +85,11 @@ impl MedicalStaff {
     /// Get assignment priority (lower is better)
     pub fn assignment_priority(&self) -> u8 {
         let availability_priority = self.availability_status.assignment_priority();
-        let seniority_bonus = match self.seniority_level.as_str() {
-            "Director" => 0,
-            "Consultant" => 1,
-            "Senior" => 2,
-            "Junior" => 3,
-            _ => 4,
+        let seniority_bonus = match self.seniority_level {
+            SeniorityLevel::Director => 0,
+            SeniorityLevel::Consultant => 1,
+            SeniorityLevel::Senior => 2,
+            SeniorityLevel::Junior => 3,
         };
          (availability_priority * 10) + seniority_bonus
     }
@@ -118,18 +131,12 @@ impl MedicalStaff {
 
     /// Check if staff is senior level or above
     pub fn is_senior(&self) -> bool {
-        matches!(
-            self.seniority_level.as_str(),
-            "Senior" | "Consultant" | "Director"
-        )
+        self.seniority_level.is_senior()
     }
 
     /// Check if staff can supervise others
     pub fn can_supervise(&self) -> bool {
-        matches!(
-            self.seniority_level.as_str(),
-            "Consultant" | "Director"
-        )
+        self.seniority_level.can_supervise()
     }
 }
 
@@ -139,13 +146,13 @@ mod tests {
 
     fn create_test_staff() -> MedicalStaff {
         MedicalStaff::new(
-            Uuid::new_v4(),
-            Uuid::new_v4(),
+            UserId::new(),
+            HospitalId::new(),
             "STAFF-001".to_string(),
             "Emergency Medicine".to_string(),
             "LIC-EM-12345".to_string(),
             "Emergency Department".to_string(),
-            "Senior".to_string(),
+            SeniorityLevel::Senior,
             vec!["ACLS".to_string(), "PALS".to_string()],
         )
     }
@@ -186,11 +193,11 @@ mod tests {
     #[test]
     fn test_assignment_priority() {
         let mut director = create_test_staff();
-        director.seniority_level = "Director".to_string();
+        director.seniority_level = SeniorityLevel::Director;
         director.availability_status = AvailabilityStatus::Available;
         
         let mut junior = create_test_staff();
-        junior.seniority_level = "Junior".to_string();
+        junior.seniority_level = SeniorityLevel::Junior;
         junior.availability_status = AvailabilityStatus::Available;
         
         assert!(director.assignment_priority() < junior.assignment_priority());
@@ -234,22 +241,22 @@ mod tests {
         let mut staff = create_test_staff();
         
         // Senior
-        staff.seniority_level = "Senior".to_string();
+        staff.seniority_level = SeniorityLevel::Senior;
         assert!(staff.is_senior());
         assert!(!staff.can_supervise());
         
         // Consultant
-        staff.seniority_level = "Consultant".to_string();
+        staff.seniority_level = SeniorityLevel::Consultant;
         assert!(staff.is_senior());
         assert!(staff.can_supervise());
         
         // Director
-        staff.seniority_level = "Director".to_string();
+        staff.seniority_level = SeniorityLevel::Director;
         assert!(staff.is_senior());
         assert!(staff.can_supervise());
         
         // Junior
-        staff.seniority_level = "Junior".to_string();
+        staff.seniority_level = SeniorityLevel::Junior;
         assert!(!staff.is_senior());
         assert!(!staff.can_supervise());
     }
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::enums::MedicationRoute;
+use crate::ids::{HospitalId, PatientId, UserId};
+
+/// A medication prescribed to a patient during their stay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Medication {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub name: String,
+    pub dosage: String, // e.g. "500 mg"
+    pub route: MedicationRoute,
+    pub frequency: String, // e.g. "every 8 hours"
+    pub notes: Option<String>,
+    pub prescribed_by: UserId,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Medication {
+    /// Create a new prescription
+    pub fn new(
+        patient_id: PatientId,
+        hospital_id: HospitalId,
+        name: String,
+        dosage: String,
+        route: MedicationRoute,
+        frequency: String,
+        prescribed_by: UserId,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            patient_id,
+            hospital_id,
+            name,
+            dosage,
+            route,
+            frequency,
+            notes: None,
+            prescribed_by,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Get the line shown on the medication chart, e.g. "Amoxicillin 500 mg PO every 8 hours"
+    pub fn chart_line(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.name,
+            self.dosage,
+            self.route.abbreviation(),
+            self.frequency
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_line() {
+        let medication = Medication::new(
+            PatientId::new(),
+            HospitalId::new(),
+            "Amoxicillin".to_string(),
+            "500 mg".to_string(),
+            MedicationRoute::Oral,
+            "every 8 hours".to_string(),
+            UserId::new(),
+        );
+        assert_eq!(
+            medication.chart_line(),
+            "Amoxicillin 500 mg PO every 8 hours"
+        );
+    }
+}
@@ -1,13 +1,56 @@
 // pub mod entities;
 
+mod jsonb;
+
 pub mod user;
 pub mod hospital;
 pub mod patient;
 pub mod medical_staff;
 pub mod patient_vitals;
+pub mod service_account;
+pub mod user_device;
+pub mod auth_audit;
+pub mod ambulance;
+pub mod bed;
+pub mod emergency_contact;
+pub mod medical_history;
+pub mod insurance_info;
+pub mod emergency_incident;
+pub mod medication;
+pub mod triage_assessment;
+pub mod patient_transfer;
+pub mod discharge_summary;
+pub mod identifier;
+pub mod attachment;
+pub mod lab_order;
+pub mod lab_result;
+pub mod consent;
+pub mod handover_report;
+pub mod patient_event;
 
 pub use user::{User, UserProfile};
 pub use hospital::Hospital;
 pub use patient::Patient;
 pub use medical_staff::MedicalStaff;
-pub use patient_vitals::{PatientVitals, VitalStatus};
+pub use patient_vitals::{ClinicalRisk, Deterioration, News2Score, PatientVitals, VitalStatus};
+pub use service_account::ServiceAccount;
+pub use user_device::UserDevice;
+pub use auth_audit::AuthAuditEntry;
+pub use ambulance::Ambulance;
+pub use bed::Bed;
+pub use emergency_contact::{EmergencyContact, EmergencyContacts};
+pub use medical_history::{MedicationEntry, MedicalCondition, MedicalHistory, Surgery};
+pub use insurance_info::InsuranceInfo;
+pub use emergency_incident::EmergencyIncident;
+pub use medication::Medication;
+pub use triage_assessment::TriageAssessment;
+pub use patient_transfer::PatientTransfer;
+
+pub use discharge_summary::DischargeSummary;
+pub use identifier::{Identifier, Identifiers};
+pub use attachment::{Attachment, NewAttachment};
+pub use lab_order::LabOrder;
+pub use lab_result::LabResult;
+pub use consent::Consent;
+pub use handover_report::HandoverReport;
+pub use patient_event::PatientEvent;
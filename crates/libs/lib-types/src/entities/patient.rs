@@ -3,32 +3,53 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-use crate::enums::{PatientStatus, TriageLevel};
+use lib_utils::location::GeoPoint;
+use lib_utils::reference_ranges::{PatientRanges, ReferenceRanges};
+
+use crate::entities::{
+    Bed, Consent, DischargeSummary, EmergencyContacts, Identifiers, InsuranceInfo, MedicalHistory,
+    TriageAssessment,
+};
+use crate::enums::{
+    AgeBand, BloodType, ConsentType, DischargeDisposition, Gender, IdentifierScheme,
+    IsolationPrecaution, PatientStatus, TriageLevel,
+};
+use crate::errors::{AppError, PatientError};
+use crate::ids::{AmbulanceId, BedId, HospitalId, PatientId, UserId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
 pub struct Patient {
-    pub id: Uuid,
+    pub id: PatientId,
     pub patient_number: String,
-    pub national_id: Option<String>, // Emirates ID or other national ID
+    pub national_id: Option<String>, // Emirates ID, mirrored from `identifiers`
+    pub identifiers: Identifiers,     // Emirates ID, passport, MRN, alias, ...
     pub first_name: String,
     pub last_name: String,
     pub age: i32,
-    pub gender: String, // "Male", "Female", "Other"
+    pub gender: Gender,
     pub chief_complaint: String,
     pub triage_level: TriageLevel,
     pub status: PatientStatus,
-    pub hospital_id: Uuid,
+    pub hospital_id: HospitalId,
     pub assigned_staff_id: Option<Uuid>,
-    pub ambulance_id: Option<Uuid>,
-    pub bed_id: Option<Uuid>,
-    pub emergency_contacts: serde_json::Value, // JSON object with contact info
-    pub medical_history: serde_json::Value,    // JSON object with medical history
+    pub ambulance_id: Option<AmbulanceId>,
+    pub bed_id: Option<BedId>,
+    pub emergency_contacts: EmergencyContacts,
+    pub medical_history: MedicalHistory,
     pub allergies: serde_json::Value,          // JSON array of allergies
-    pub insurance_info: serde_json::Value,     // JSON object with insurance details
-    pub incident_location: Option<String>,     // Location where incident occurred
+    pub insurance_info: Option<InsuranceInfo>, // None when uninsured or unknown
+    pub incident_location: Option<GeoPoint>,   // Where the incident occurred
     pub incident_time: Option<DateTime<Utc>>,
+    pub blood_type: Option<BloodType>, // None until typed or documented
+    pub dnr: bool,                     // Do not resuscitate
+    pub dnr_recorded_by: Option<UserId>,
+    pub dnr_recorded_at: Option<DateTime<Utc>>,
+    pub isolation_precautions: Vec<IsolationPrecaution>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub created_by: Option<UserId>, // None when written by the system
+    pub updated_by: Option<UserId>,
+    pub version: i64, // Bumped on every write to the row
 }
 
 impl Patient {
@@ -39,18 +60,23 @@ impl Patient {
         first_name: String,
         last_name: String,
         age: i32,
-        gender: String,
+        gender: Gender,
         chief_complaint: String,
         triage_level: TriageLevel,
-        hospital_id: Uuid,
-        incident_location: Option<String>,
+        hospital_id: HospitalId,
+        incident_location: Option<GeoPoint>,
         incident_time: Option<DateTime<Utc>>,
     ) -> Self {
         let now = Utc::now();
+        let mut identifiers = Identifiers::default();
+        if let Some(ref national_id) = national_id {
+            identifiers.set(IdentifierScheme::EmiratesId, Some(national_id.clone()));
+        }
         Self {
-            id: Uuid::new_v4(),
+            id: PatientId::new(),
             patient_number,
             national_id,
+            identifiers,
             first_name,
             last_name,
             age,
@@ -62,14 +88,22 @@ impl Patient {
             assigned_staff_id: None,
             ambulance_id: None,
             bed_id: None,
-            emergency_contacts: serde_json::Value::Object(serde_json::Map::new()),
-            medical_history: serde_json::Value::Object(serde_json::Map::new()),
+            emergency_contacts: EmergencyContacts::default(),
+            medical_history: MedicalHistory::default(),
             allergies: serde_json::Value::Array(vec![]),
-            insurance_info: serde_json::Value::Object(serde_json::Map::new()),
+            insurance_info: None,
             incident_location,
             incident_time,
+            blood_type: None,
+            dnr: false,
+            dnr_recorded_by: None,
+            dnr_recorded_at: None,
+            isolation_precautions: Vec::new(),
             created_at: now,
             updated_at: now,
+            created_by: None,
+            updated_by: None,
+            version: 1,
         }
     }
 
@@ -93,6 +127,21 @@ impl Patient {
         self.status.is_at_hospital()
     }
 
+    /// Get the age band used for vital sign reference ranges
+    pub fn age_band(&self) -> AgeBand {
+        AgeBand::from_age_years(self.age)
+    }
+
+    /// Get the rows of a reference range table that apply to this patient
+    pub fn reference_ranges<'a>(&self, ranges: &'a ReferenceRanges) -> PatientRanges<'a> {
+        ranges.for_patient(self.age_band(), self.gender.sex())
+    }
+
+    /// Check if patient is a minor (under 18)
+    pub fn is_minor(&self) -> bool {
+        self.age < 18
+    }
+
     /// Check if patient is emergency level
     pub fn is_emergency(&self) -> bool {
         self.triage_level.is_emergency()
@@ -103,13 +152,153 @@ impl Patient {
         self.triage_level.priority()
     }
 
-    /// Update patient status
-    pub fn update_status(&mut self, new_status: PatientStatus) {
-        let next_statuses = self.status.next_statuses();
-        if next_statuses.contains(&new_status) || next_statuses.is_empty() {
-            self.status = new_status;
-            self.updated_at = Utc::now();
+    /// Update patient status following the patient workflow
+    pub fn update_status(&mut self, new_status: PatientStatus) -> Result<(), PatientError> {
+        if !self.status.can_transition_to(new_status) {
+            return Err(PatientError::InvalidStatusTransition {
+                current: self.status,
+                requested: new_status,
+            });
+        }
+        self.status = new_status;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Re-triage the patient, keeping `triage_level` as the latest value
+    pub fn retriage(
+        &mut self,
+        triage_level: TriageLevel,
+        rationale: String,
+        assessed_by: UserId,
+    ) -> Result<TriageAssessment, PatientError> {
+        if self.status.is_terminal() {
+            return Err(PatientError::InvalidData {
+                field: "triage_level".to_string(),
+                reason: format!("patient is already {}", self.status),
+            });
+        }
+
+        let assessment = TriageAssessment {
+            previous_level: Some(self.triage_level),
+            triage_level,
+            ..TriageAssessment::initial(self, rationale, assessed_by)
+        };
+        self.triage_level = triage_level;
+        self.updated_at = assessment.assessed_at;
+        Ok(assessment)
+    }
+
+    /// Find the consent that covers `consent_type` for a minor: an active
+    /// consent from a parent or guardian, or implied emergency consent.
+    /// Adults consent for themselves at the bedside, so `None` is returned
+    /// for them.
+    pub fn require_consent<'a>(
+        &self,
+        consents: &'a [Consent],
+        consent_type: ConsentType,
+        at: DateTime<Utc>,
+    ) -> Result<Option<&'a Consent>, PatientError> {
+        if !self.is_minor() {
+            return Ok(None);
+        }
+
+        consents
+            .iter()
+            .find(|consent| {
+                consent.patient_id == self.id
+                    && consent.consent_type == consent_type
+                    && consent.is_active(at)
+                    && consent.is_valid_for_minor()
+            })
+            .map(Some)
+            .ok_or(PatientError::MinorConsentRequired)
+    }
+
+    /// Discharge the patient and write their discharge summary. A critical
+    /// patient can only leave against medical advice, and a minor needs a
+    /// recorded discharge consent from a parent or guardian. The bed is
+    /// given up; the caller releases it for cleaning.
+    pub fn discharge(
+        &mut self,
+        diagnosis: String,
+        disposition: DischargeDisposition,
+        instructions: String,
+        follow_up: Option<String>,
+        consents: &[Consent],
+        discharged_by: UserId,
+    ) -> Result<DischargeSummary, PatientError> {
+        if !self.status.can_transition_to(PatientStatus::Discharged) {
+            return Err(PatientError::InvalidStatusTransition {
+                current: self.status,
+                requested: PatientStatus::Discharged,
+            });
+        }
+        if self.triage_level == TriageLevel::Critical && !disposition.is_against_advice() {
+            return Err(PatientError::CriticalConditionDischarge);
+        }
+
+        let guardian_consent_by = self
+            .require_consent(consents, ConsentType::Discharge, Utc::now())?
+            .map(|consent| consent.granted_by.clone());
+
+        self.update_status(PatientStatus::Discharged)?;
+        self.bed_id = None;
+
+        let mut summary = DischargeSummary {
+            id: Uuid::new_v4(),
+            patient_id: self.id,
+            hospital_id: self.hospital_id,
+            diagnosis,
+            disposition,
+            instructions,
+            follow_up,
+            guardian_consent_by,
+            document: String::new(),
+            discharged_by,
+            discharged_at: self.updated_at,
+        };
+        summary.document = summary.render_document(self);
+        Ok(summary)
+    }
+
+    /// Record or withdraw a do-not-resuscitate order, keeping who recorded
+    /// it. Returns false when the order is unchanged.
+    pub fn record_dnr(&mut self, dnr: bool, recorded_by: UserId) -> bool {
+        if self.dnr == dnr {
+            return false;
         }
+        let now = Utc::now();
+        self.dnr = dnr;
+        self.dnr_recorded_by = Some(recorded_by);
+        self.dnr_recorded_at = Some(now);
+        self.updated_at = now;
+        true
+    }
+
+    /// Check if the patient needs a negative pressure room
+    pub fn requires_negative_pressure(&self) -> bool {
+        self.isolation_precautions
+            .iter()
+            .any(IsolationPrecaution::requires_negative_pressure)
+    }
+
+    /// Short alerts shown next to the patient's name, most critical first
+    pub fn badges(&self) -> Vec<String> {
+        let mut badges = Vec::new();
+        if self.dnr {
+            badges.push("DNR".to_string());
+        }
+        for precaution in &self.isolation_precautions {
+            badges.push(format!("{} isolation", precaution));
+        }
+        if !self.get_allergies().is_empty() {
+            badges.push("Allergies".to_string());
+        }
+        if let Some(blood_type) = self.blood_type {
+            badges.push(blood_type.to_string());
+        }
+        badges
     }
 
     /// Assign to medical staff
@@ -119,20 +308,30 @@ impl Patient {
     }
 
     /// Assign to ambulance
-    pub fn assign_ambulance(&mut self, ambulance_id: Uuid) {
+    pub fn assign_ambulance(&mut self, ambulance_id: AmbulanceId) {
         self.ambulance_id = Some(ambulance_id);
         self.updated_at = Utc::now();
     }
 
-    /// Assign to bed
-    pub fn assign_bed(&mut self, bed_id: Uuid) {
-        self.bed_id = Some(bed_id);
+    /// Assign to bed, checking that the bed is free, in the patient's
+    /// hospital and suitable for the patient
+    pub fn assign_bed(&mut self, bed: &mut Bed) -> Result<(), AppError> {
+        bed.check_assignable(self)?;
+        bed.occupy(self.id);
+        self.bed_id = Some(bed.id);
         self.updated_at = Utc::now();
+        Ok(())
     }
 
-    /// Check if patient is anonymous (no national ID)
+    /// Check if patient is anonymous (no national ID and no identifier other
+    /// than an unidentified-patient alias)
     pub fn is_anonymous(&self) -> bool {
-        self.national_id.is_none() || self.national_id.as_ref().unwrap().is_empty()
+        let has_national_id = self.national_id.as_ref().is_some_and(|id| !id.is_empty());
+        let has_identifier = self
+            .identifiers
+            .iter()
+            .any(|identifier| identifier.scheme != IdentifierScheme::Unknown);
+        !has_national_id && !has_identifier
     }
 
     /// Get allergies as vector
@@ -171,19 +370,20 @@ impl Patient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::enums::{BedType, ConsentMethod, ContactRelationship};
 
     fn create_test_patient() -> Patient {
         Patient::new(
             "PAT-001".to_string(),
-            Some("784-1990-1234567-1".to_string()),
+            Some("784-1990-1234567-6".to_string()),
             "Ahmed".to_string(),
             "Al-Rashid".to_string(),
             45,
-            "Male".to_string(),
+            Gender::Male,
             "Chest Pain".to_string(),
             TriageLevel::Critical,
-            Uuid::new_v4(),
-            Some("Sheikh Zayed Road, Dubai".to_string()),
+            HospitalId::new(),
+            Some(GeoPoint::new(25.1124, 55.1390).unwrap()),
             Some(Utc::now()),
         )
     }
@@ -202,9 +402,15 @@ mod tests {
     fn test_anonymous_patient() {
         let mut patient = create_test_patient();
         patient.national_id = None;
+        patient.identifiers.set(IdentifierScheme::EmiratesId, None);
         
         assert!(patient.is_anonymous());
         assert_eq!(patient.display_name(), "Anonymous Patient (PAT-001)");
+
+        patient.identifiers.set(IdentifierScheme::Unknown, Some("Trauma Alpha 07".to_string()));
+        assert!(patient.is_anonymous());
+        patient.identifiers.set(IdentifierScheme::Passport, Some("N1234567".to_string()));
+        assert!(!patient.is_anonymous());
     }
 
     #[test]
@@ -212,21 +418,130 @@ mod tests {
         let mut patient = create_test_patient();
         
         // Valid status progression
-        patient.update_status(PatientStatus::EnRoute);
+        patient.update_status(PatientStatus::EnRoute).unwrap();
         assert_eq!(patient.status, PatientStatus::EnRoute);
         
         // Invalid status jump should not work
         let original_status = patient.status;
-        patient.update_status(PatientStatus::Discharged); // Can't go directly from EnRoute to Discharged
+        assert!(patient.update_status(PatientStatus::Discharged).is_err()); // Can't go directly from EnRoute to Discharged
         assert_eq!(patient.status, original_status);
+
+        // Terminal statuses cannot be left
+        patient.update_status(PatientStatus::Deceased).unwrap();
+        assert_eq!(
+            patient.update_status(PatientStatus::Arrived),
+            Err(PatientError::InvalidStatusTransition {
+                current: PatientStatus::Deceased,
+                requested: PatientStatus::Arrived,
+            })
+        );
+    }
+
+    #[test]
+    fn test_retriage() {
+        let mut patient = create_test_patient();
+        let staff_id = UserId::new();
+
+        let assessment = patient
+            .retriage(TriageLevel::High, "SpO2 dropping".to_string(), staff_id)
+            .unwrap();
+        assert_eq!(patient.triage_level, TriageLevel::High);
+        assert_eq!(assessment.previous_level, Some(TriageLevel::Critical));
+        assert!(assessment.is_downgrade());
+        assert_eq!(assessment.assessed_by, staff_id);
+
+        patient.update_status(PatientStatus::Deceased).unwrap();
+        assert!(patient
+            .retriage(TriageLevel::Critical, "".to_string(), staff_id)
+            .is_err());
+    }
+
+    #[test]
+    fn test_discharge_guards() {
+        let mut patient = create_test_patient();
+        patient.status = PatientStatus::Admitted;
+        let discharge = |patient: &mut Patient, disposition, consents: &[Consent]| {
+            patient.discharge(
+                "Stable angina".to_string(),
+                disposition,
+                "Rest, aspirin 81 mg daily".to_string(),
+                Some("Cardiology clinic in 2 weeks".to_string()),
+                consents,
+                UserId::new(),
+            )
+        };
+
+        // Critical patients can only leave against medical advice
+        assert_eq!(
+            discharge(&mut patient, DischargeDisposition::Home, &[]),
+            Err(PatientError::CriticalConditionDischarge)
+        );
+
+        patient.triage_level = TriageLevel::Low;
+        patient.age = 15;
+        let (patient_id, hospital_id) = (patient.id, patient.hospital_id);
+        let consent = |consent_type, relationship| {
+            Consent::new(
+                patient_id,
+                hospital_id,
+                consent_type,
+                "Fatima Al-Rashid".to_string(),
+                Some(relationship),
+                ConsentMethod::Written,
+                UserId::new(),
+            )
+        };
+        let mut consents = vec![
+            consent(ConsentType::Discharge, ContactRelationship::Sibling),
+            consent(ConsentType::Treatment, ContactRelationship::Parent),
+            consent(ConsentType::Discharge, ContactRelationship::Parent),
+        ];
+        consents[2].withdraw(UserId::new(), "Wants a second opinion".to_string());
+        assert_eq!(
+            discharge(&mut patient, DischargeDisposition::Home, &consents),
+            Err(PatientError::MinorConsentRequired)
+        );
+
+        consents.push(consent(ConsentType::Discharge, ContactRelationship::Guardian));
+        let summary = discharge(&mut patient, DischargeDisposition::Home, &consents).unwrap();
+        assert_eq!(patient.status, PatientStatus::Discharged);
+        assert_eq!(
+            summary.guardian_consent_by.as_deref(),
+            Some("Fatima Al-Rashid")
+        );
+        assert!(summary.document.contains("Diagnosis: Stable angina"));
+        assert!(summary
+            .document
+            .contains("Follow-up: Cardiology clinic in 2 weeks"));
+
+        // Already discharged
+        assert!(matches!(
+            discharge(&mut patient, DischargeDisposition::Home, &consents),
+            Err(PatientError::InvalidStatusTransition { .. })
+        ));
+
+        // Adults need no recorded consent
+        let mut adult = create_test_patient();
+        adult.status = PatientStatus::Admitted;
+        adult.triage_level = TriageLevel::Low;
+        assert!(discharge(&mut adult, DischargeDisposition::Home, &[])
+            .unwrap()
+            .guardian_consent_by
+            .is_none());
     }
 
     #[test]
     fn test_assignments() {
         let mut patient = create_test_patient();
         let staff_id = Uuid::new_v4();
-        let ambulance_id = Uuid::new_v4();
-        let bed_id = Uuid::new_v4();
+        let ambulance_id = AmbulanceId::new();
+        let mut bed = Bed::new(
+            patient.hospital_id,
+            "ER".to_string(),
+            "1".to_string(),
+            "A".to_string(),
+            BedType::Icu,
+        );
         
         patient.assign_staff(staff_id);
         assert_eq!(patient.assigned_staff_id, Some(staff_id));
@@ -234,8 +549,26 @@ mod tests {
         patient.assign_ambulance(ambulance_id);
         assert_eq!(patient.ambulance_id, Some(ambulance_id));
         
-        patient.assign_bed(bed_id);
-        assert_eq!(patient.bed_id, Some(bed_id));
+        patient.assign_bed(&mut bed).unwrap();
+        assert_eq!(patient.bed_id, Some(bed.id));
+        assert_eq!(bed.current_patient_id, Some(patient.id));
+    }
+
+    #[test]
+    fn test_bed_assignment_validated() {
+        let mut patient = create_test_patient();
+        let mut general = Bed::new(
+            patient.hospital_id,
+            "Ward 2".to_string(),
+            "12".to_string(),
+            "A".to_string(),
+            BedType::General,
+        );
+
+        // A critical patient cannot go to a general ward bed
+        assert!(patient.assign_bed(&mut general).is_err());
+        assert_eq!(patient.bed_id, None);
+        assert!(general.is_available());
     }
 
     #[test]
@@ -261,22 +594,22 @@ mod tests {
         assert!(!patient.is_at_hospital());
         
         // En route
-        patient.update_status(PatientStatus::EnRoute);
+        patient.update_status(PatientStatus::EnRoute).unwrap();
         assert!(patient.is_in_transport());
         assert!(!patient.is_at_hospital());
         
         // Arrived
-        patient.update_status(PatientStatus::Arrived);
+        patient.update_status(PatientStatus::Arrived).unwrap();
         assert!(!patient.is_in_transport());
         assert!(patient.is_at_hospital());
         
         // Admitted
-        patient.update_status(PatientStatus::Admitted);
+        patient.update_status(PatientStatus::Admitted).unwrap();
         assert!(patient.is_at_hospital());
         assert!(patient.is_active());
         
         // Discharged
-        patient.update_status(PatientStatus::Discharged);
+        patient.update_status(PatientStatus::Discharged).unwrap();
         assert!(!patient.is_active());
     }
 
@@ -284,19 +617,40 @@ mod tests {
     fn test_priority_ordering() {
         let critical = Patient::new(
             "PAT-001".to_string(), None, "Test".to_string(), "Critical".to_string(),
-            30, "Male".to_string(), "Critical".to_string(), TriageLevel::Critical,
-            Uuid::new_v4(), None, None
+            30, Gender::Male, "Critical".to_string(), TriageLevel::Critical,
+            HospitalId::new(), None, None
         );
         
         let low = Patient::new(
             "PAT-002".to_string(), None, "Test".to_string(), "Low".to_string(),
-            30, "Male".to_string(), "Low".to_string(), TriageLevel::Low,
-            Uuid::new_v4(), None, None
+            30, Gender::Male, "Low".to_string(), TriageLevel::Low,
+            HospitalId::new(), None, None
         );
         
         assert!(critical.priority() < low.priority());
     }
 
+    #[test]
+    fn test_dnr_and_badges() {
+        let mut patient = create_test_patient();
+        patient.allergies = serde_json::json!([]);
+        assert!(patient.badges().is_empty());
+
+        let clinician = UserId::new();
+        assert!(patient.record_dnr(true, clinician));
+        assert!(!patient.record_dnr(true, UserId::new()));
+        assert_eq!(patient.dnr_recorded_by, Some(clinician));
+
+        patient.blood_type = Some(BloodType::ONegative);
+        patient.isolation_precautions = vec![IsolationPrecaution::Airborne];
+        patient.add_allergy("Penicillin".to_string());
+        assert!(patient.requires_negative_pressure());
+        assert_eq!(
+            patient.badges(),
+            vec!["DNR", "Airborne isolation", "Allergies", "O-"]
+        );
+    }
+
     #[test]
     fn test_serialization() {
         let patient = create_test_patient();
@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::entities::Patient;
+use crate::enums::{PatientEventKind, PatientStatus, TriageLevel};
+use crate::ids::{AmbulanceId, BedId, HospitalId, PatientId, UserId};
+
+/// One change to a patient, kept so the course of a stay can be replayed.
+/// Statuses and triage levels are stored by their API names, assignments
+/// by the assigned id; `None` means unset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct PatientEvent {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub kind: PatientEventKind,
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+    pub reason: Option<String>,
+    pub actor_id: Option<UserId>, // None when made by the system
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl PatientEvent {
+    /// Record a change already applied to `patient`
+    fn new(
+        patient: &Patient,
+        kind: PatientEventKind,
+        from_value: Option<String>,
+        to_value: Option<String>,
+        actor_id: Option<UserId>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            patient_id: patient.id,
+            hospital_id: patient.hospital_id,
+            kind,
+            from_value,
+            to_value,
+            reason: None,
+            actor_id,
+            occurred_at: patient.updated_at,
+        }
+    }
+
+    pub fn status_changed(
+        patient: &Patient,
+        previous: PatientStatus,
+        actor_id: Option<UserId>,
+    ) -> Self {
+        Self::new(
+            patient,
+            PatientEventKind::StatusChanged,
+            api_name(previous),
+            api_name(patient.status),
+            actor_id,
+        )
+    }
+
+    pub fn triage_changed(
+        patient: &Patient,
+        previous: TriageLevel,
+        actor_id: Option<UserId>,
+    ) -> Self {
+        Self::new(
+            patient,
+            PatientEventKind::TriageChanged,
+            api_name(previous),
+            api_name(patient.triage_level),
+            actor_id,
+        )
+    }
+
+    pub fn staff_assigned(
+        patient: &Patient,
+        previous: Option<Uuid>,
+        actor_id: Option<UserId>,
+    ) -> Self {
+        Self::new(
+            patient,
+            PatientEventKind::StaffAssigned,
+            previous.map(|id| id.to_string()),
+            patient.assigned_staff_id.map(|id| id.to_string()),
+            actor_id,
+        )
+    }
+
+    pub fn ambulance_assigned(
+        patient: &Patient,
+        previous: Option<AmbulanceId>,
+        actor_id: Option<UserId>,
+    ) -> Self {
+        Self::new(
+            patient,
+            PatientEventKind::AmbulanceAssigned,
+            previous.map(|id| id.to_string()),
+            patient.ambulance_id.map(|id| id.to_string()),
+            actor_id,
+        )
+    }
+
+    pub fn bed_assigned(
+        patient: &Patient,
+        previous: Option<BedId>,
+        actor_id: Option<UserId>,
+    ) -> Self {
+        Self::new(
+            patient,
+            PatientEventKind::BedAssigned,
+            previous.map(|id| id.to_string()),
+            patient.bed_id.map(|id| id.to_string()),
+            actor_id,
+        )
+    }
+
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Check if the event records an actual change
+    pub fn is_change(&self) -> bool {
+        self.from_value != self.to_value
+    }
+}
+
+/// The name an enum value has in the API, e.g. `en_route`
+fn api_name(value: impl Serialize) -> Option<String> {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => Some(name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::Gender;
+
+    #[test]
+    fn test_status_event_uses_api_names() {
+        let mut patient = Patient::new(
+            "P-1".to_string(),
+            None,
+            "Test".to_string(),
+            "Patient".to_string(),
+            40,
+            Gender::Female,
+            "Chest pain".to_string(),
+            TriageLevel::High,
+            HospitalId::new(),
+            None,
+            None,
+        );
+        patient.status = PatientStatus::EnRoute;
+
+        let event = PatientEvent::status_changed(&patient, PatientStatus::Dispatched, None)
+            .with_reason("Crew on the way");
+        assert_eq!(event.from_value.as_deref(), Some("dispatched"));
+        assert_eq!(event.to_value.as_deref(), Some("en_route"));
+        assert_eq!(event.reason.as_deref(), Some("Crew on the way"));
+        assert!(event.is_change());
+        assert!(!PatientEvent::staff_assigned(&patient, None, None).is_change());
+    }
+}
@@ -0,0 +1,239 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use super::Patient;
+use crate::enums::{PatientStatus, TransferStatus};
+use crate::errors::PatientError;
+use crate::ids::{AmbulanceId, HospitalId, PatientId, UserId};
+
+/// Move of a patient to another hospital, e.g. for a specialty the origin
+/// lacks. The destination accepts or rejects the request and completes it
+/// when the patient arrives.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct PatientTransfer {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub origin_hospital_id: HospitalId,
+    pub destination_hospital_id: HospitalId,
+    pub reason: String,
+    pub status: TransferStatus,
+    pub requested_by: UserId,
+    pub responded_by: Option<UserId>, // Destination user who accepted or rejected
+    pub rejection_reason: Option<String>,
+    pub ambulance_id: Option<AmbulanceId>, // Transport, once arranged
+    pub requested_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PatientTransfer {
+    /// Request the transfer of a patient to `destination_hospital_id`. Only
+    /// patients under treatment or admitted can be transferred.
+    pub fn request(
+        patient: &Patient,
+        destination_hospital_id: HospitalId,
+        reason: String,
+        requested_by: UserId,
+    ) -> Result<Self, PatientError> {
+        if destination_hospital_id == patient.hospital_id {
+            return Err(transfer_failed("destination must be a different hospital"));
+        }
+        if !patient.status.can_transition_to(PatientStatus::Transferred) {
+            return Err(transfer_failed(format!(
+                "patient is {}",
+                patient.status.display_name().to_lowercase()
+            )));
+        }
+
+        let now = Utc::now();
+        Ok(Self {
+            id: Uuid::new_v4(),
+            patient_id: patient.id,
+            origin_hospital_id: patient.hospital_id,
+            destination_hospital_id,
+            reason,
+            status: TransferStatus::Requested,
+            requested_by,
+            responded_by: None,
+            rejection_reason: None,
+            ambulance_id: None,
+            requested_at: now,
+            responded_at: None,
+            completed_at: None,
+            updated_at: now,
+        })
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status.is_open()
+    }
+
+    /// Accept the transfer at the destination, optionally with the ambulance
+    /// that will carry the patient
+    pub fn accept(
+        &mut self,
+        accepted_by: UserId,
+        ambulance_id: Option<AmbulanceId>,
+    ) -> Result<(), PatientError> {
+        self.transition(TransferStatus::Accepted)?;
+        self.responded_by = Some(accepted_by);
+        self.responded_at = Some(self.updated_at);
+        self.ambulance_id = ambulance_id;
+        Ok(())
+    }
+
+    /// Decline the transfer at the destination
+    pub fn reject(&mut self, rejected_by: UserId, reason: String) -> Result<(), PatientError> {
+        self.transition(TransferStatus::Rejected)?;
+        self.responded_by = Some(rejected_by);
+        self.responded_at = Some(self.updated_at);
+        self.rejection_reason = Some(reason);
+        Ok(())
+    }
+
+    /// Record the patient's arrival at the destination. The patient becomes
+    /// a new arrival there, without the origin's bed or staff assignment.
+    pub fn complete(&mut self, patient: &mut Patient) -> Result<(), PatientError> {
+        if patient.id != self.patient_id || patient.hospital_id != self.origin_hospital_id {
+            return Err(transfer_failed(
+                "patient is no longer at the origin hospital",
+            ));
+        }
+        self.transition(TransferStatus::Completed)?;
+        self.completed_at = Some(self.updated_at);
+
+        patient.hospital_id = self.destination_hospital_id;
+        patient.status = PatientStatus::Arrived;
+        patient.bed_id = None;
+        patient.assigned_staff_id = None;
+        patient.ambulance_id = self.ambulance_id;
+        patient.updated_at = self.updated_at;
+        Ok(())
+    }
+
+    fn transition(&mut self, new_status: TransferStatus) -> Result<(), PatientError> {
+        if !self.status.next_statuses().contains(&new_status) {
+            return Err(transfer_failed(format!(
+                "cannot move a {} transfer to {}",
+                self.status.display_name().to_lowercase(),
+                new_status.display_name().to_lowercase()
+            )));
+        }
+        self.status = new_status;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+fn transfer_failed(reason: impl Into<String>) -> PatientError {
+    PatientError::TransferFailed {
+        reason: reason.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{Gender, TriageLevel};
+    use crate::ids::BedId;
+
+    fn create_test_patient() -> Patient {
+        let mut patient = Patient::new(
+            "ER-2024-0001".to_string(),
+            None,
+            "Ahmed".to_string(),
+            "Al Mansoori".to_string(),
+            45,
+            Gender::Male,
+            "Chest pain".to_string(),
+            TriageLevel::High,
+            HospitalId::new(),
+            None,
+            None,
+        );
+        patient.status = PatientStatus::InTreatment;
+        patient.bed_id = Some(BedId::new());
+        patient
+    }
+
+    #[test]
+    fn test_transfer_workflow() {
+        let mut patient = create_test_patient();
+        let origin = patient.hospital_id;
+        let destination = HospitalId::new();
+        let mut transfer = PatientTransfer::request(
+            &patient,
+            destination,
+            "Needs cardiac catheterization".to_string(),
+            UserId::new(),
+        )
+        .unwrap();
+        assert_eq!(transfer.status, TransferStatus::Requested);
+        assert_eq!(transfer.origin_hospital_id, origin);
+
+        // The patient cannot arrive before the destination agreed
+        assert!(transfer.complete(&mut patient).is_err());
+
+        let ambulance_id = AmbulanceId::new();
+        transfer.accept(UserId::new(), Some(ambulance_id)).unwrap();
+        assert!(transfer.responded_at.is_some());
+
+        transfer.complete(&mut patient).unwrap();
+        assert_eq!(transfer.status, TransferStatus::Completed);
+        assert_eq!(patient.hospital_id, destination);
+        assert_eq!(patient.status, PatientStatus::Arrived);
+        assert_eq!(patient.bed_id, None);
+        assert_eq!(patient.ambulance_id, Some(ambulance_id));
+        assert!(!transfer.is_open());
+    }
+
+    #[test]
+    fn test_rejected_transfer_is_final() {
+        let patient = create_test_patient();
+        let mut transfer = PatientTransfer::request(
+            &patient,
+            HospitalId::new(),
+            "Burns unit".to_string(),
+            UserId::new(),
+        )
+        .unwrap();
+
+        transfer
+            .reject(UserId::new(), "No burns beds".to_string())
+            .unwrap();
+        assert_eq!(transfer.rejection_reason.as_deref(), Some("No burns beds"));
+        assert!(matches!(
+            transfer.accept(UserId::new(), None),
+            Err(PatientError::TransferFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_request_checks_patient() {
+        let mut patient = create_test_patient();
+        let result = PatientTransfer::request(
+            &patient,
+            patient.hospital_id,
+            "Same hospital".to_string(),
+            UserId::new(),
+        );
+        assert!(matches!(result, Err(PatientError::TransferFailed { .. })));
+
+        patient.status = PatientStatus::Discharged;
+        let result = PatientTransfer::request(
+            &patient,
+            HospitalId::new(),
+            "Follow-up".to_string(),
+            UserId::new(),
+        );
+        assert_eq!(
+            result,
+            Err(PatientError::TransferFailed {
+                reason: "patient is discharged".to_string()
+            })
+        );
+    }
+}
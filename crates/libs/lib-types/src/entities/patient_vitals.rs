@@ -3,19 +3,31 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
-use crate::enums::TriageLevel;
+use lib_utils::reference_ranges::{Measurement, PatientRanges, RangeStatus};
+use lib_utils::validation::rules;
+
+use crate::enums::{ConsciousnessLevel, TriageLevel};
+use crate::errors::{Validate, ValidationErrors};
+use crate::ids::{PatientId, UserId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
 pub struct PatientVitals {
     pub id: Uuid,
-    pub patient_id: Uuid,
-    pub recorded_by: Uuid, // User ID who recorded the vitals
+    pub patient_id: PatientId,
+    pub recorded_by: UserId, // User ID who recorded the vitals
     pub systolic_bp: Option<i32>,
     pub diastolic_bp: Option<i32>,
     pub heart_rate: Option<i32>,
     pub oxygen_saturation: Option<i32>,
     pub temperature: Option<f32>, // Celsius
     pub respiratory_rate: Option<i32>,
+    pub consciousness_level: Option<ConsciousnessLevel>, // ACVPU
+    pub on_supplemental_oxygen: bool,
+    pub gcs_eye: Option<i32>,    // Glasgow Coma Scale eye opening, 1-4
+    pub gcs_verbal: Option<i32>, // Glasgow Coma Scale verbal response, 1-5
+    pub gcs_motor: Option<i32>,  // Glasgow Coma Scale motor response, 1-6
+    pub pain_score: Option<i32>, // 0-10 numeric rating scale
+    pub blood_glucose: Option<f32>, // mmol/L
     pub weight: Option<f32>, // Kilograms
     pub device_id: Option<String>,
     pub additional_measurements: serde_json::Value, // JSON for other measurements
@@ -27,8 +39,8 @@ pub struct PatientVitals {
 impl PatientVitals {
     /// Create new vital signs record
     pub fn new(
-        patient_id: Uuid,
-        recorded_by: Uuid,
+        patient_id: PatientId,
+        recorded_by: UserId,
     ) -> Self {
         let now = Utc::now();
         Self {
@@ -41,6 +53,13 @@ impl PatientVitals {
             oxygen_saturation: None,
             temperature: None,
             respiratory_rate: None,
+            consciousness_level: None,
+            on_supplemental_oxygen: false,
+            gcs_eye: None,
+            gcs_verbal: None,
+            gcs_motor: None,
+            pain_score: None,
+            blood_glucose: None,
             weight: None,
             device_id: None,
             additional_measurements: serde_json::Value::Object(serde_json::Map::new()),
@@ -64,15 +83,29 @@ impl PatientVitals {
         }
     }
 
-    /// Assess blood pressure status
-    pub fn bp_assessment(&self) -> VitalStatus {
+    /// Set Glasgow Coma Scale components
+    pub fn set_gcs(&mut self, eye: i32, verbal: i32, motor: i32) {
+        self.gcs_eye = Some(eye);
+        self.gcs_verbal = Some(verbal);
+        self.gcs_motor = Some(motor);
+    }
+
+    /// Get total Glasgow Coma Scale (3-15), once all components are recorded
+    pub fn gcs_total(&self) -> Option<i32> {
+        Some(self.gcs_eye? + self.gcs_verbal? + self.gcs_motor?)
+    }
+
+    /// Assess blood pressure status against the patient's reference ranges
+    pub fn bp_assessment(&self, ranges: &PatientRanges) -> VitalStatus {
         match self.blood_pressure() {
             Some((sys, dia)) => {
-                if sys >= 180 || dia >= 120 {
+                let systolic = ranges.classify(Measurement::SystolicBp, sys as f32);
+                let diastolic = ranges.classify(Measurement::DiastolicBp, dia as f32);
+                if systolic.is_critical() || diastolic.is_critical() {
                     VitalStatus::Critical
-                } else if sys >= 140 || dia >= 90 {
+                } else if systolic == RangeStatus::High || diastolic == RangeStatus::High {
                     VitalStatus::High
-                } else if sys < 90 || dia < 60 {
+                } else if systolic == RangeStatus::Low || diastolic == RangeStatus::Low {
                     VitalStatus::Low
                 } else {
                     VitalStatus::Normal
@@ -82,62 +115,76 @@ impl PatientVitals {
         }
     }
 
-    /// Assess heart rate status
-    pub fn hr_assessment(&self) -> VitalStatus {
-        match self.heart_rate {
-            Some(hr) => {
-                if hr < 50 || hr > 120 {
-                    VitalStatus::Critical
-                } else if hr < 60 || hr > 100 {
-                    VitalStatus::High
-                } else {
-                    VitalStatus::Normal
-                }
-            }
-            None => VitalStatus::Unknown,
-        }
+    /// Assess heart rate status against the patient's reference ranges
+    pub fn hr_assessment(&self, ranges: &PatientRanges) -> VitalStatus {
+        let rate = self.heart_rate.map(|hr| hr as f32);
+        assess(ranges, Measurement::HeartRate, rate)
     }
 
-    /// Assess oxygen saturation status
-    pub fn o2_assessment(&self) -> VitalStatus {
-        match self.oxygen_saturation {
-            Some(o2) => {
-                if o2 < 90 {
-                    VitalStatus::Critical
-                } else if o2 < 95 {
-                    VitalStatus::High
-                } else {
-                    VitalStatus::Normal
-                }
-            }
+    /// Assess respiratory rate status against the patient's reference ranges
+    pub fn rr_assessment(&self, ranges: &PatientRanges) -> VitalStatus {
+        let rate = self.respiratory_rate.map(|rr| rr as f32);
+        assess(ranges, Measurement::RespiratoryRate, rate)
+    }
+
+    /// Assess oxygen saturation status against the patient's reference ranges
+    pub fn o2_assessment(&self, ranges: &PatientRanges) -> VitalStatus {
+        let saturation = self.oxygen_saturation.map(|o2| o2 as f32);
+        assess(ranges, Measurement::OxygenSaturation, saturation)
+    }
+
+    /// Assess temperature status against the patient's reference ranges
+    pub fn temp_assessment(&self, ranges: &PatientRanges) -> VitalStatus {
+        assess(ranges, Measurement::Temperature, self.temperature)
+    }
+
+    /// Assess level of consciousness from the total GCS: 13-14 is a mild,
+    /// 9-12 a moderate and 8 or less a severe impairment
+    pub fn gcs_assessment(&self) -> VitalStatus {
+        match self.gcs_total() {
+            Some(15) => VitalStatus::Normal,
+            Some(13..=14) => VitalStatus::Low,
+            Some(9..=12) => VitalStatus::High,
+            Some(_) => VitalStatus::Critical,
             None => VitalStatus::Unknown,
         }
     }
 
-    /// Assess temperature status
-    pub fn temp_assessment(&self) -> VitalStatus {
-        match self.temperature {
-            Some(temp) => {
-                if temp < 35.0 || temp > 40.0 {
-                    VitalStatus::Critical
-                } else if temp < 36.0 || temp > 38.5 {
-                    VitalStatus::High
-                } else {
-                    VitalStatus::Normal
-                }
-            }
+    /// Assess pain score; severe pain (7-10) needs prompt analgesia
+    pub fn pain_assessment(&self) -> VitalStatus {
+        match self.pain_score {
+            Some(7..) => VitalStatus::High,
+            Some(_) => VitalStatus::Normal,
             None => VitalStatus::Unknown,
         }
     }
 
-    /// Get overall vital status (worst of all vitals)
-    pub fn overall_assessment(&self) -> VitalStatus {
-        let assessments = [
-            self.bp_assessment(),
-            self.hr_assessment(),
-            self.o2_assessment(),
-            self.temp_assessment(),
+    /// Assess blood glucose status against the patient's reference ranges
+    pub fn glucose_assessment(&self, ranges: &PatientRanges) -> VitalStatus {
+        assess(ranges, Measurement::BloodGlucose, self.blood_glucose)
+    }
+
+    /// Get overall vital status (worst of all vitals). Respiratory rate,
+    /// GCS, pain and glucose only count when recorded.
+    pub fn overall_assessment(&self, ranges: &PatientRanges) -> VitalStatus {
+        let mut assessments = vec![
+            self.bp_assessment(ranges),
+            self.hr_assessment(ranges),
+            self.o2_assessment(ranges),
+            self.temp_assessment(ranges),
         ];
+        if self.respiratory_rate.is_some() {
+            assessments.push(self.rr_assessment(ranges));
+        }
+        if self.gcs_total().is_some() {
+            assessments.push(self.gcs_assessment());
+        }
+        if self.pain_score.is_some() {
+            assessments.push(self.pain_assessment());
+        }
+        if self.blood_glucose.is_some() {
+            assessments.push(self.glucose_assessment(ranges));
+        }
 
         if assessments.iter().any(|&s| s == VitalStatus::Critical) {
             VitalStatus::Critical
@@ -152,21 +199,120 @@ impl PatientVitals {
         }
     }
 
-    /// Suggest triage level based on vitals
-    pub fn suggested_triage(&self) -> Option<TriageLevel> {
-        match self.overall_assessment() {
-            VitalStatus::Critical => Some(TriageLevel::Critical),
-            VitalStatus::High => Some(TriageLevel::High),
-            VitalStatus::Low => Some(TriageLevel::Medium),
-            VitalStatus::Normal => Some(TriageLevel::Low),
-            VitalStatus::Unknown => None,
+    /// Compute the NEWS2 early warning score (SpO2 scale 1). Returns `None`
+    /// until respiratory rate, SpO2, systolic BP, pulse, consciousness and
+    /// temperature are all recorded.
+    pub fn news2_score(&self) -> Option<News2Score> {
+        let parameters = [
+            news2_respiratory_rate(self.respiratory_rate?),
+            news2_oxygen_saturation(self.oxygen_saturation?),
+            if self.on_supplemental_oxygen { 2 } else { 0 },
+            news2_systolic_bp(self.systolic_bp?),
+            news2_pulse(self.heart_rate?),
+            if self.consciousness_level?.is_alert() { 0 } else { 3 },
+            news2_temperature(self.temperature?),
+        ];
+
+        let total: u8 = parameters.iter().sum();
+        let risk = if total >= 7 {
+            ClinicalRisk::High
+        } else if total >= 5 {
+            ClinicalRisk::Medium
+        } else if parameters.contains(&3) {
+            ClinicalRisk::LowMedium // A single parameter in the red zone
+        } else {
+            ClinicalRisk::Low
+        };
+
+        Some(News2Score { total, risk })
+    }
+
+    /// Suggest triage level from the NEWS2 risk band, escalated for a
+    /// reduced GCS or critical glucose which NEWS2 does not capture. NEWS2
+    /// is only validated for adults, so children are triaged on their
+    /// age-adjusted vitals instead.
+    pub fn suggested_triage(&self, ranges: &PatientRanges) -> Option<TriageLevel> {
+        if ranges.age_band.is_pediatric() {
+            return match self.overall_assessment(ranges) {
+                VitalStatus::Critical => Some(TriageLevel::Critical),
+                VitalStatus::High => Some(TriageLevel::High),
+                VitalStatus::Low => Some(TriageLevel::Medium),
+                VitalStatus::Normal => Some(TriageLevel::Low),
+                VitalStatus::Unknown => None,
+            };
+        }
+
+        let from_news2 = self.news2_score().map(|news2| match news2.risk {
+            ClinicalRisk::High => TriageLevel::Critical,
+            ClinicalRisk::Medium | ClinicalRisk::LowMedium => TriageLevel::High,
+            ClinicalRisk::Low if news2.total > 0 => TriageLevel::Medium,
+            ClinicalRisk::Low => TriageLevel::Low,
+        });
+        let neuro = if self.gcs_assessment() == VitalStatus::Critical
+            || self.glucose_assessment(ranges) == VitalStatus::Critical
+        {
+            Some(TriageLevel::Critical)
+        } else if self.gcs_assessment() == VitalStatus::High {
+            Some(TriageLevel::High)
+        } else {
+            None
+        };
+
+        match (from_news2, neuro) {
+            (Some(from_news2), Some(neuro)) => Some(from_news2.min(neuro)),
+            (from_news2, neuro) => from_news2.or(neuro),
+        }
+    }
+
+    /// Get the recorded value of a measurement, in the units of its
+    /// reference range
+    pub fn measurement(&self, measurement: Measurement) -> Option<f32> {
+        match measurement {
+            Measurement::HeartRate => self.heart_rate.map(|hr| hr as f32),
+            Measurement::SystolicBp => self.systolic_bp.map(|sys| sys as f32),
+            Measurement::DiastolicBp => self.diastolic_bp.map(|dia| dia as f32),
+            Measurement::RespiratoryRate => self.respiratory_rate.map(|rr| rr as f32),
+            Measurement::OxygenSaturation => self.oxygen_saturation.map(|o2| o2 as f32),
+            Measurement::Temperature => self.temperature,
+            Measurement::BloodGlucose => self.blood_glucose,
         }
     }
 
+    /// Find what in this reading needs a clinician to see the patient now:
+    /// measurements past a critical limit, a GCS of 8 or less and a NEWS2
+    /// score in the high risk band. Empty when nothing does.
+    pub fn deterioration(&self, ranges: &PatientRanges) -> Vec<Deterioration> {
+        let mut findings: Vec<Deterioration> = Measurement::ALL
+            .into_iter()
+            .filter_map(|measurement| {
+                let value = self.measurement(measurement)?;
+                let status = ranges.classify(measurement, value);
+                status.is_critical().then_some(Deterioration::CriticalValue {
+                    measurement,
+                    value,
+                    status,
+                })
+            })
+            .collect();
+        if let Some(total) = self
+            .gcs_total()
+            .filter(|_| self.gcs_assessment() == VitalStatus::Critical)
+        {
+            findings.push(Deterioration::ReducedGcs { total });
+        }
+        if let Some(score) = self
+            .news2_score()
+            .filter(|news2| news2.risk == ClinicalRisk::High)
+        {
+            findings.push(Deterioration::HighNews2 { score });
+        }
+        findings
+    }
+
     /// Check if vitals indicate emergency
-    pub fn is_emergency(&self) -> bool {
+    pub fn is_emergency(&self, ranges: &PatientRanges) -> bool {
         matches!(
-            self.overall_assessment(),
+            self.overall_assessment(ranges),
             VitalStatus::Critical | VitalStatus::High
         )
     }
@@ -197,6 +343,177 @@ impl PatientVitals {
     }
 }
 
+impl Validate for PatientVitals {
+    /// Neuro, pain and glucose measurements must be on their scales
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+
+        let components = [
+            ("eye", self.gcs_eye, 4),
+            ("verbal", self.gcs_verbal, 5),
+            ("motor", self.gcs_motor, 6),
+        ];
+        for (name, score, max) in components {
+            if let Some(score) = score {
+                rules::range(
+                    &mut errors,
+                    &format!("gcs_{}", name),
+                    score,
+                    1..=max,
+                    format!("GCS {} score must be between 1 and {}", name, max),
+                );
+            }
+        }
+
+        if let Some(score) = self.pain_score {
+            rules::range(
+                &mut errors,
+                "pain_score",
+                score,
+                0..=10,
+                "Pain score must be between 0 and 10",
+            );
+        }
+
+        if let Some(glucose) = self.blood_glucose {
+            rules::range(
+                &mut errors,
+                "blood_glucose",
+                glucose,
+                0.5..=60.0,
+                "Blood glucose must be between 0.5 and 60 mmol/L",
+            );
+        }
+
+        errors.into_result()
+    }
+}
+
+/// A finding in a set of vital signs that the patient is deteriorating
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Deterioration {
+    CriticalValue {
+        measurement: Measurement,
+        value: f32,
+        status: RangeStatus, // Critically low or high
+    },
+    ReducedGcs {
+        total: i32,
+    },
+    HighNews2 {
+        score: News2Score,
+    },
+}
+
+impl std::fmt::Display for Deterioration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Deterioration::CriticalValue {
+                measurement,
+                value,
+                status,
+            } => write!(
+                f,
+                "{} {} {} (critically {})",
+                measurement,
+                value,
+                measurement.unit(),
+                if *status == RangeStatus::CriticalLow {
+                    "low"
+                } else {
+                    "high"
+                }
+            ),
+            Deterioration::ReducedGcs { total } => write!(f, "GCS {}", total),
+            Deterioration::HighNews2 { score } => write!(f, "NEWS2 {} (high risk)", score.total),
+        }
+    }
+}
+
+/// Clinical risk band of a NEWS2 score, deciding how urgently the patient
+/// needs a clinical review
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClinicalRisk {
+    Low,
+    LowMedium,
+    Medium,
+    High,
+}
+
+/// Aggregate National Early Warning Score 2 (0-20) and its risk band
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct News2Score {
+    pub total: u8,
+    pub risk: ClinicalRisk,
+}
+
+fn news2_respiratory_rate(rate: i32) -> u8 {
+    match rate {
+        ..=8 => 3,
+        9..=11 => 1,
+        12..=20 => 0,
+        21..=24 => 2,
+        _ => 3,
+    }
+}
+
+fn news2_oxygen_saturation(saturation: i32) -> u8 {
+    match saturation {
+        ..=91 => 3,
+        92..=93 => 2,
+        94..=95 => 1,
+        _ => 0,
+    }
+}
+
+fn news2_systolic_bp(systolic: i32) -> u8 {
+    match systolic {
+        ..=90 => 3,
+        91..=100 => 2,
+        101..=110 => 1,
+        111..=219 => 0,
+        _ => 3,
+    }
+}
+
+fn news2_pulse(pulse: i32) -> u8 {
+    match pulse {
+        ..=40 => 3,
+        41..=50 => 1,
+        51..=90 => 0,
+        91..=110 => 1,
+        111..=130 => 2,
+        _ => 3,
+    }
+}
+
+fn news2_temperature(celsius: f32) -> u8 {
+    if celsius <= 35.0 {
+        3
+    } else if celsius <= 36.0 {
+        1
+    } else if celsius <= 38.0 {
+        0
+    } else if celsius <= 39.0 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Assess one measurement. Values outside the normal range either way
+/// count as `High`; only blood pressure tells low values apart.
+fn assess(ranges: &PatientRanges, measurement: Measurement, value: Option<f32>) -> VitalStatus {
+    match value.map(|value| ranges.classify(measurement, value)) {
+        Some(RangeStatus::CriticalLow | RangeStatus::CriticalHigh) => VitalStatus::Critical,
+        Some(RangeStatus::Low | RangeStatus::High) => VitalStatus::High,
+        Some(RangeStatus::Normal) => VitalStatus::Normal,
+        None => VitalStatus::Unknown,
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VitalStatus {
     Critical,
@@ -220,15 +537,27 @@ impl VitalStatus {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::LazyLock;
+
+    use lib_utils::reference_ranges::{ReferenceRanges, Sex};
+
     use super::*;
+    use crate::enums::AgeBand;
+
+    static RANGES: LazyLock<ReferenceRanges> = LazyLock::new(ReferenceRanges::builtin);
+
+    fn ranges(age_band: AgeBand) -> PatientRanges<'static> {
+        RANGES.for_patient(age_band, None)
+    }
 
     fn create_test_vitals() -> PatientVitals {
-        let mut vitals = PatientVitals::new(Uuid::new_v4(), Uuid::new_v4());
+        let mut vitals = PatientVitals::new(PatientId::new(), UserId::new());
         vitals.set_blood_pressure(120, 80);
         vitals.heart_rate = Some(75);
         vitals.oxygen_saturation = Some(98);
         vitals.temperature = Some(37.0);
         vitals.respiratory_rate = Some(16);
+        vitals.consciousness_level = Some(ConsciousnessLevel::Alert);
         vitals
     }
 
@@ -246,19 +575,31 @@ mod tests {
         
         // Normal BP
         vitals.set_blood_pressure(120, 80);
-        assert_eq!(vitals.bp_assessment(), VitalStatus::Normal);
-        
+        assert_eq!(
+            vitals.bp_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Normal
+        );
+
         // High BP
         vitals.set_blood_pressure(150, 95);
-        assert_eq!(vitals.bp_assessment(), VitalStatus::High);
-        
+        assert_eq!(
+            vitals.bp_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::High
+        );
+
         // Critical BP
         vitals.set_blood_pressure(190, 125);
-        assert_eq!(vitals.bp_assessment(), VitalStatus::Critical);
-        
+        assert_eq!(
+            vitals.bp_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Critical
+        );
+
         // Low BP
         vitals.set_blood_pressure(85, 55);
-        assert_eq!(vitals.bp_assessment(), VitalStatus::Low);
+        assert_eq!(
+            vitals.bp_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Low
+        );
     }
 
     #[test]
@@ -267,15 +608,24 @@ mod tests {
         
         // Normal HR
         vitals.heart_rate = Some(75);
-        assert_eq!(vitals.hr_assessment(), VitalStatus::Normal);
-        
+        assert_eq!(
+            vitals.hr_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Normal
+        );
+
         // High HR
         vitals.heart_rate = Some(110);
-        assert_eq!(vitals.hr_assessment(), VitalStatus::High);
-        
+        assert_eq!(
+            vitals.hr_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::High
+        );
+
         // Critical HR
         vitals.heart_rate = Some(45);
-        assert_eq!(vitals.hr_assessment(), VitalStatus::Critical);
+        assert_eq!(
+            vitals.hr_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Critical
+        );
     }
 
     #[test]
@@ -284,15 +634,24 @@ mod tests {
         
         // Normal O2
         vitals.oxygen_saturation = Some(98);
-        assert_eq!(vitals.o2_assessment(), VitalStatus::Normal);
-        
+        assert_eq!(
+            vitals.o2_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Normal
+        );
+
         // High concern O2
         vitals.oxygen_saturation = Some(92);
-        assert_eq!(vitals.o2_assessment(), VitalStatus::High);
-        
+        assert_eq!(
+            vitals.o2_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::High
+        );
+
         // Critical O2
         vitals.oxygen_saturation = Some(85);
-        assert_eq!(vitals.o2_assessment(), VitalStatus::Critical);
+        assert_eq!(
+            vitals.o2_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Critical
+        );
     }
 
     #[test]
@@ -301,15 +660,24 @@ mod tests {
         
         // Normal temp
         vitals.temperature = Some(37.0);
-        assert_eq!(vitals.temp_assessment(), VitalStatus::Normal);
-        
+        assert_eq!(
+            vitals.temp_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Normal
+        );
+
         // High temp (fever)
         vitals.temperature = Some(39.5);
-        assert_eq!(vitals.temp_assessment(), VitalStatus::High);
-        
+        assert_eq!(
+            vitals.temp_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::High
+        );
+
         // Critical temp
         vitals.temperature = Some(41.0);
-        assert_eq!(vitals.temp_assessment(), VitalStatus::Critical);
+        assert_eq!(
+            vitals.temp_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Critical
+        );
     }
 
     #[test]
@@ -317,16 +685,25 @@ mod tests {
         let mut vitals = create_test_vitals();
         
         // All normal
-        assert_eq!(vitals.overall_assessment(), VitalStatus::Normal);
-        
+        assert_eq!(
+            vitals.overall_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Normal
+        );
+
         // One critical makes overall critical
         vitals.heart_rate = Some(40); // Critical
-        assert_eq!(vitals.overall_assessment(), VitalStatus::Critical);
-        
+        assert_eq!(
+            vitals.overall_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Critical
+        );
+
         // One high makes overall high (if no critical)
         vitals.heart_rate = Some(75); // Back to normal
         vitals.temperature = Some(39.0); // High
-        assert_eq!(vitals.overall_assessment(), VitalStatus::High);
+        assert_eq!(
+            vitals.overall_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::High
+        );
     }
 
     #[test]
@@ -334,12 +711,259 @@ mod tests {
         let mut vitals = create_test_vitals();
         
         // Normal vitals suggest low triage
-        assert_eq!(vitals.suggested_triage(), Some(TriageLevel::Low));
-        
-        // Critical vitals suggest critical triage
+        assert_eq!(
+            vitals.suggested_triage(&ranges(AgeBand::Adult)),
+            Some(TriageLevel::Low)
+        );
+
+        // A single red parameter needs an urgent review
         vitals.oxygen_saturation = Some(85);
-        assert_eq!(vitals.suggested_triage(), Some(TriageLevel::Critical));
-        assert!(vitals.is_emergency());
+        assert_eq!(
+            vitals.suggested_triage(&ranges(AgeBand::Adult)),
+            Some(TriageLevel::High)
+        );
+        assert!(vitals.is_emergency(&ranges(AgeBand::Adult)));
+
+        // High NEWS2 risk suggests critical triage
+        vitals.heart_rate = Some(135);
+        vitals.respiratory_rate = Some(26);
+        assert_eq!(
+            vitals.suggested_triage(&ranges(AgeBand::Adult)),
+            Some(TriageLevel::Critical)
+        );
+
+        // No suggestion without a complete NEWS2
+        vitals.consciousness_level = None;
+        assert_eq!(vitals.suggested_triage(&ranges(AgeBand::Adult)), None);
+    }
+
+    #[test]
+    fn test_deterioration() {
+        let mut vitals = create_test_vitals();
+        assert!(vitals.deterioration(&ranges(AgeBand::Adult)).is_empty());
+
+        vitals.heart_rate = Some(35);
+        vitals.set_gcs(2, 2, 4);
+        let findings = vitals.deterioration(&ranges(AgeBand::Adult));
+        assert_eq!(
+            findings,
+            vec![
+                Deterioration::CriticalValue {
+                    measurement: Measurement::HeartRate,
+                    value: 35.0,
+                    status: RangeStatus::CriticalLow,
+                },
+                Deterioration::ReducedGcs { total: 8 },
+            ]
+        );
+        assert_eq!(findings[0].to_string(), "Heart rate 35 bpm (critically low)");
+
+        // Abnormal values alone are not a deterioration, a high NEWS2 is
+        let mut vitals = create_test_vitals();
+        vitals.respiratory_rate = Some(26);
+        vitals.oxygen_saturation = Some(91);
+        vitals.heart_rate = Some(125);
+        let findings = vitals.deterioration(&ranges(AgeBand::Adult));
+        assert!(matches!(
+            findings.last(),
+            Some(Deterioration::HighNews2 { score }) if score.total >= 7
+        ));
+    }
+
+    #[test]
+    fn test_age_adjusted_assessment() {
+        // Normal for a 6 month old, alarming in an adult
+        let mut infant = create_test_vitals();
+        infant.set_blood_pressure(85, 50);
+        infant.heart_rate = Some(140);
+        infant.respiratory_rate = Some(40);
+        infant.consciousness_level = None;
+        assert_eq!(
+            infant.overall_assessment(&ranges(AgeBand::Infant)),
+            VitalStatus::Normal
+        );
+        assert_eq!(
+            infant.hr_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Critical
+        );
+        assert_eq!(
+            infant.suggested_triage(&ranges(AgeBand::Infant)),
+            Some(TriageLevel::Low)
+        );
+
+        // Normal adult pressure is high for a child
+        let mut child = create_test_vitals();
+        child.set_blood_pressure(125, 80);
+        child.heart_rate = Some(100);
+        child.respiratory_rate = Some(22);
+        assert_eq!(
+            child.bp_assessment(&ranges(AgeBand::Child)),
+            VitalStatus::High
+        );
+        assert_eq!(
+            child.rr_assessment(&ranges(AgeBand::Child)),
+            VitalStatus::Normal
+        );
+        assert_eq!(
+            child.rr_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::High
+        );
+
+        // Any fever in a neonate is critical
+        let mut neonate = create_test_vitals();
+        neonate.temperature = Some(38.1);
+        assert_eq!(
+            neonate.temp_assessment(&ranges(AgeBand::Neonate)),
+            VitalStatus::Critical
+        );
+        assert_eq!(
+            neonate.temp_assessment(&ranges(AgeBand::Child)),
+            VitalStatus::Normal
+        );
+
+        // Older patients tolerate low pressure worse
+        let mut elderly = create_test_vitals();
+        elderly.set_blood_pressure(95, 65);
+        assert_eq!(
+            elderly.bp_assessment(&ranges(AgeBand::Elderly)),
+            VitalStatus::Low
+        );
+        assert_eq!(
+            elderly.bp_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Normal
+        );
+    }
+
+    #[test]
+    fn test_tuned_reference_ranges() {
+        let json = r#"[{"measurement": "heart_rate", "age_band": "adult", "sex": "female",
+            "critical_low": 40, "normal_low": 50, "normal_high": 110, "critical_high": 130}]"#;
+        let tuned = ReferenceRanges::from_json(json).unwrap();
+
+        let mut vitals = create_test_vitals();
+        vitals.heart_rate = Some(105);
+        let female = tuned.for_patient(AgeBand::Adult, Some(Sex::Female));
+        let male = tuned.for_patient(AgeBand::Adult, Some(Sex::Male));
+        assert_eq!(vitals.hr_assessment(&female), VitalStatus::Normal);
+        assert_eq!(vitals.hr_assessment(&male), VitalStatus::High);
+    }
+
+    #[test]
+    fn test_news2_score() {
+        let mut vitals = create_test_vitals();
+        assert_eq!(
+            vitals.news2_score(),
+            Some(News2Score { total: 0, risk: ClinicalRisk::Low })
+        );
+
+        vitals.respiratory_rate = Some(22); // 2
+        vitals.oxygen_saturation = Some(95); // 1
+        vitals.on_supplemental_oxygen = true; // 2
+        assert_eq!(
+            vitals.news2_score(),
+            Some(News2Score { total: 5, risk: ClinicalRisk::Medium })
+        );
+
+        vitals.set_blood_pressure(105, 70); // 1
+        vitals.heart_rate = Some(115); // 2
+        vitals.temperature = Some(38.5); // 1
+        vitals.consciousness_level = Some(ConsciousnessLevel::NewConfusion); // 3
+        assert_eq!(
+            vitals.news2_score(),
+            Some(News2Score { total: 12, risk: ClinicalRisk::High })
+        );
+
+        let mut red_flag = create_test_vitals();
+        red_flag.consciousness_level = Some(ConsciousnessLevel::Voice);
+        assert_eq!(
+            red_flag.news2_score(),
+            Some(News2Score { total: 3, risk: ClinicalRisk::LowMedium })
+        );
+    }
+
+    #[test]
+    fn test_neuro_assessment() {
+        let mut vitals = create_test_vitals();
+        vitals.set_gcs(4, 5, 6);
+        assert_eq!(vitals.gcs_total(), Some(15));
+        assert_eq!(vitals.gcs_assessment(), VitalStatus::Normal);
+
+        vitals.set_gcs(3, 4, 6);
+        assert_eq!(vitals.gcs_assessment(), VitalStatus::Low);
+
+        // Severe head injury with otherwise normal vitals
+        vitals.set_gcs(2, 2, 4);
+        assert_eq!(vitals.gcs_assessment(), VitalStatus::Critical);
+        assert_eq!(
+            vitals.overall_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Critical
+        );
+        assert_eq!(
+            vitals.suggested_triage(&ranges(AgeBand::Adult)),
+            Some(TriageLevel::Critical)
+        );
+
+        // Still suggested without a complete NEWS2
+        vitals.consciousness_level = None;
+        vitals.set_gcs(3, 3, 5);
+        assert_eq!(
+            vitals.suggested_triage(&ranges(AgeBand::Adult)),
+            Some(TriageLevel::High)
+        );
+
+        vitals.gcs_motor = None;
+        assert_eq!(vitals.gcs_total(), None);
+        assert_eq!(vitals.suggested_triage(&ranges(AgeBand::Adult)), None);
+    }
+
+    #[test]
+    fn test_pain_and_glucose_assessment() {
+        let mut vitals = create_test_vitals();
+        vitals.pain_score = Some(3);
+        vitals.blood_glucose = Some(5.5);
+        assert_eq!(
+            vitals.overall_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Normal
+        );
+
+        vitals.pain_score = Some(8);
+        assert_eq!(vitals.pain_assessment(), VitalStatus::High);
+        assert_eq!(
+            vitals.overall_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::High
+        );
+
+        // Hypoglycaemia is an emergency even when NEWS2 is zero
+        vitals.pain_score = None;
+        vitals.blood_glucose = Some(2.4);
+        assert_eq!(
+            vitals.glucose_assessment(&ranges(AgeBand::Adult)),
+            VitalStatus::Critical
+        );
+        assert_eq!(
+            vitals.glucose_assessment(&ranges(AgeBand::Neonate)),
+            VitalStatus::High
+        );
+        assert_eq!(
+            vitals.suggested_triage(&ranges(AgeBand::Adult)),
+            Some(TriageLevel::Critical)
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut vitals = create_test_vitals();
+        vitals.set_gcs(4, 5, 6);
+        vitals.pain_score = Some(10);
+        vitals.blood_glucose = Some(6.0);
+        assert!(vitals.validate().is_ok());
+
+        vitals.set_gcs(0, 6, 6);
+        vitals.pain_score = Some(11);
+        let errors = vitals.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].field, "gcs_eye");
+        assert!(errors[0].message.contains("GCS eye"));
     }
 
     #[test]
@@ -351,7 +975,7 @@ mod tests {
 
     #[test]
     fn test_completeness() {
-        let mut vitals = PatientVitals::new(Uuid::new_v4(), Uuid::new_v4());
+        let mut vitals = PatientVitals::new(PatientId::new(), UserId::new());
         assert!(!vitals.is_complete());
         
         vitals.set_blood_pressure(120, 80);
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::enums::ServiceScope;
+use crate::errors::AuthError;
+use crate::ids::HospitalId;
+
+/// Non-human principal (e.g. the dispatch optimizer) that authenticates with
+/// the client-credentials grant
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct ServiceAccount {
+    pub id: Uuid,
+    pub client_id: String,
+    pub client_secret_hash: String,
+    pub name: String,
+    pub hospital_id: Option<HospitalId>, // None for network-wide services
+    pub scopes: Vec<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ServiceAccount {
+    /// Create a new service account (for creation, before database insert)
+    pub fn new(
+        client_id: String,
+        client_secret_hash: String,
+        name: String,
+        hospital_id: Option<HospitalId>,
+        scopes: &[ServiceScope],
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            client_id,
+            client_secret_hash,
+            name,
+            hospital_id,
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            last_used_at: None,
+        }
+    }
+
+    /// Scopes the account may request (unknown stored values are ignored)
+    pub fn allowed_scopes(&self) -> Vec<ServiceScope> {
+        self.scopes.iter().filter_map(|s| s.parse().ok()).collect()
+    }
+
+    /// Resolve the scopes for a token: all allowed scopes when none are
+    /// requested, otherwise the requested ones if the account holds them all
+    pub fn grant_scopes(
+        &self,
+        requested: Option<Vec<ServiceScope>>,
+    ) -> Result<Vec<ServiceScope>, AuthError> {
+        let allowed = self.allowed_scopes();
+        let Some(requested) = requested else {
+            return Ok(allowed);
+        };
+
+        if let Some(scope) = requested.iter().find(|scope| !allowed.contains(scope)) {
+            return Err(AuthError::InvalidScope {
+                scope: scope.to_string(),
+            });
+        }
+        Ok(requested)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grant_scopes() {
+        let account = ServiceAccount::new(
+            "dispatch-optimizer".to_string(),
+            "hash".to_string(),
+            "Dispatch optimizer".to_string(),
+            None,
+            &[ServiceScope::BedsRead, ServiceScope::DispatchWrite],
+        );
+
+        assert_eq!(account.grant_scopes(None).unwrap().len(), 2);
+        assert_eq!(
+            account.grant_scopes(Some(vec![ServiceScope::BedsRead])),
+            Ok(vec![ServiceScope::BedsRead])
+        );
+        assert_eq!(
+            account.grant_scopes(Some(vec![ServiceScope::ReportsRead])),
+            Err(AuthError::InvalidScope {
+                scope: "reports:read".to_string()
+            })
+        );
+    }
+}
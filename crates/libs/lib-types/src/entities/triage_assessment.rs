@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::entities::Patient;
+use crate::enums::TriageLevel;
+use crate::ids::{HospitalId, PatientId, UserId};
+
+/// One triage or re-triage of a patient. The latest assessment is copied
+/// to `Patient::triage_level`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct TriageAssessment {
+    pub id: Uuid,
+    pub patient_id: PatientId,
+    pub hospital_id: HospitalId,
+    pub triage_level: TriageLevel,
+    pub previous_level: Option<TriageLevel>, // None for the first assessment
+    pub rationale: String,
+    pub assessed_by: UserId,
+    pub assessed_at: DateTime<Utc>,
+}
+
+impl TriageAssessment {
+    /// Record the level a patient was given on registration; re-triage
+    /// goes through `Patient::retriage`
+    pub fn initial(patient: &Patient, rationale: String, assessed_by: UserId) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            patient_id: patient.id,
+            hospital_id: patient.hospital_id,
+            triage_level: patient.triage_level,
+            previous_level: None,
+            rationale,
+            assessed_by,
+            assessed_at: Utc::now(),
+        }
+    }
+
+    /// Check if the patient was moved to a more acute level
+    pub fn is_escalation(&self) -> bool {
+        self.previous_level
+            .is_some_and(|previous| self.triage_level < previous)
+    }
+
+    /// Check if the patient was moved to a less acute level
+    pub fn is_downgrade(&self) -> bool {
+        self.previous_level
+            .is_some_and(|previous| self.triage_level > previous)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::Gender;
+
+    #[test]
+    fn test_initial_assessment() {
+        let patient = Patient::new(
+            "P-0001".to_string(),
+            None,
+            "Ahmed".to_string(),
+            "Hassan".to_string(),
+            52,
+            Gender::Male,
+            "Chest pain".to_string(),
+            TriageLevel::Medium,
+            HospitalId::new(),
+            None,
+            None,
+        );
+        let assessment =
+            TriageAssessment::initial(&patient, "Stable, ECG pending".to_string(), UserId::new());
+        assert_eq!(assessment.triage_level, TriageLevel::Medium);
+        assert_eq!(assessment.previous_level, None);
+        assert_eq!(assessment.hospital_id, patient.hospital_id);
+        assert!(!assessment.is_escalation() && !assessment.is_downgrade());
+    }
+}
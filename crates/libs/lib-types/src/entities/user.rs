@@ -1,18 +1,18 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
-use uuid::Uuid;
 
 use crate::enums::UserRole;
+use crate::ids::{HospitalId, UserId};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
 pub struct User {
-    pub id: Uuid,
+    pub id: UserId,
     pub username: String,
     pub email: String,
     pub password_hash: String,
     pub role: UserRole,
-    pub hospital_id: Uuid,
+    pub hospital_id: HospitalId,
     pub first_name: String,
     pub last_name: String,
     pub phone_number: Option<String>,
@@ -28,14 +28,14 @@ impl User {
         email: String,
         password_hash: String,
         role: UserRole,
-        hospital_id: Uuid,
+        hospital_id: HospitalId,
         first_name: String,
         last_name: String,
         phone_number: Option<String>,
     ) -> Self {
         let now = Utc::now();
         Self {
-            id: Uuid::new_v4(),
+            id: UserId::new(),
             username,
             email,
             password_hash,
@@ -54,6 +54,11 @@ impl User {
         format!("{} {}", self.first_name, self.last_name)
     }
 
+    /// Sanitize username (trim whitespace, lowercase), as logins are keyed
+    pub fn sanitized_username(&self) -> String {
+        self.username.trim().to_lowercase()
+    }
+
     pub fn is_admin(&self) -> bool {
         self.role.is_admin()
     }
@@ -62,7 +67,7 @@ impl User {
         self.role.can_access_patients()
     }
 
-    pub fn same_hospital(&self, hospital_id: Uuid) -> bool {
+    pub fn same_hospital(&self, hospital_id: HospitalId) -> bool {
         self.hospital_id == hospital_id
     }
 
@@ -74,11 +79,11 @@ impl User {
 // User without sensitive data (for API responses)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UserProfile {
-    pub id: Uuid,
+    pub id: UserId,
     pub username: String,
     pub email: String,
     pub role: UserRole,
-    pub hospital_id: Uuid,
+    pub hospital_id: HospitalId,
     pub first_name: String,
     pub last_name: String,
     pub phone_number: Option<String>,
@@ -113,7 +118,7 @@ mod tests {
             "ahmed@dubaihospital.ae".to_string(),
             "hashed_password".to_string(),
             UserRole::ErDirector,
-            Uuid::new_v4(),
+            HospitalId::new(),
             "Ahmed".to_string(),
             "Al-Mansoori".to_string(),
             Some("+971501234567".to_string()),
@@ -136,7 +141,7 @@ mod tests {
             "director@hospital.ae".to_string(),
             "hash".to_string(),
             UserRole::ErDirector,
-            Uuid::new_v4(),
+            HospitalId::new(),
             "Dr".to_string(),
             "Director".to_string(),
             None,
@@ -147,7 +152,7 @@ mod tests {
             "paramedic@hospital.ae".to_string(),
             "hash".to_string(),
             UserRole::Paramedic,
-            Uuid::new_v4(),
+            HospitalId::new(),
             "John".to_string(),
             "Paramedic".to_string(),
             None,
@@ -164,7 +169,7 @@ mod tests {
             "admin@system.ae".to_string(),
             "hash".to_string(),
             UserRole::Admin,
-            Uuid::new_v4(),
+            HospitalId::new(),
             "System".to_string(),
             "Admin".to_string(),
             None,
@@ -175,7 +180,7 @@ mod tests {
             "nurse@hospital.ae".to_string(),
             "hash".to_string(),
             UserRole::Nurse,
-            Uuid::new_v4(),
+            HospitalId::new(),
             "Sarah".to_string(),
             "Nurse".to_string(),
             None,
@@ -187,8 +192,8 @@ mod tests {
 
     #[test]
     fn test_hospital_affiliation() {
-        let hospital_id = Uuid::new_v4();
-        let other_hospital_id = Uuid::new_v4();
+        let hospital_id = HospitalId::new();
+        let other_hospital_id = HospitalId::new();
         
         let user = User::new(
             "test".to_string(),
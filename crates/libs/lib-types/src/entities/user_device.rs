@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::ids::UserId;
+
+/// Device a user has logged in from, identified by its fingerprint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct UserDevice {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub fingerprint: String,
+    pub name: Option<String>, // Label chosen by the user when trusting the device
+    pub user_agent: Option<String>,
+    pub last_ip: Option<String>,
+    pub trusted_until: Option<DateTime<Utc>>, // Trusted devices skip MFA until then
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+impl UserDevice {
+    /// Check if the device is trusted at the given time
+    pub fn is_trusted_at(&self, now: DateTime<Utc>) -> bool {
+        self.trusted_until.is_some_and(|until| now < until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trust_expires() {
+        let now = Utc::now();
+        let mut device = UserDevice {
+            id: Uuid::new_v4(),
+            user_id: UserId::new(),
+            fingerprint: "ab".repeat(32),
+            name: None,
+            user_agent: Some("DubaiEMS/2.4".to_string()),
+            last_ip: Some("10.20.0.5".to_string()),
+            trusted_until: None,
+            created_at: now,
+            last_seen_at: now,
+        };
+        assert!(!device.is_trusted_at(now));
+
+        device.trusted_until = Some(now + chrono::Duration::days(30));
+        assert!(device.is_trusted_at(now));
+        assert!(!device.is_trusted_at(now + chrono::Duration::days(30)));
+    }
+}
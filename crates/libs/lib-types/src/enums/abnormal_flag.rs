@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// How a lab result compares to its reference range and critical limits.
+/// Analyzers report these as the HL7 codes N, L, H, LL and HH.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Type,
+)]
+#[sqlx(type_name = "abnormal_flag", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AbnormalFlag {
+    #[serde(alias = "N")]
+    Normal,
+    #[serde(alias = "L")]
+    Low,
+    #[serde(alias = "H")]
+    High,
+    #[serde(alias = "LL")]
+    CriticalLow,
+    #[serde(alias = "HH")]
+    CriticalHigh,
+}
+
+impl AbnormalFlag {
+    /// Get display name for abnormal flag
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AbnormalFlag::Normal => "Normal",
+            AbnormalFlag::Low => "Low",
+            AbnormalFlag::High => "High",
+            AbnormalFlag::CriticalLow => "Critical low",
+            AbnormalFlag::CriticalHigh => "Critical high",
+        }
+    }
+
+    /// Get the HL7 abnormal flag code
+    pub fn code(&self) -> &'static str {
+        match self {
+            AbnormalFlag::Normal => "N",
+            AbnormalFlag::Low => "L",
+            AbnormalFlag::High => "H",
+            AbnormalFlag::CriticalLow => "LL",
+            AbnormalFlag::CriticalHigh => "HH",
+        }
+    }
+
+    /// Check if the result is outside the reference range
+    pub fn is_abnormal(&self) -> bool {
+        !matches!(self, AbnormalFlag::Normal)
+    }
+
+    /// Check if the result is life-threatening and must be reported to a
+    /// clinician straight away
+    pub fn is_critical(&self) -> bool {
+        matches!(self, AbnormalFlag::CriticalLow | AbnormalFlag::CriticalHigh)
+    }
+}
+
+impl std::fmt::Display for AbnormalFlag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hl7_codes() {
+        let flag: AbnormalFlag = serde_json::from_str("\"HH\"").unwrap();
+        assert_eq!(flag, AbnormalFlag::CriticalHigh);
+        assert_eq!(flag.code(), "HH");
+        assert!(flag.is_critical());
+        assert!(AbnormalFlag::Low.is_abnormal());
+        assert!(!AbnormalFlag::Low.is_critical());
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "ambulance_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AmbulanceStatus {
+    Available,
+    Dispatched,
+    EnRoute,
+    AtScene,
+    Transporting,
+}
+
+impl AmbulanceStatus {
+    /// Get display name for ambulance status
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AmbulanceStatus::Available => "Available",
+            AmbulanceStatus::Dispatched => "Dispatched",
+            AmbulanceStatus::EnRoute => "En Route",
+            AmbulanceStatus::AtScene => "At Scene",
+            AmbulanceStatus::Transporting => "Transporting",
+        }
+    }
+
+    /// Get next possible statuses from current status (a mission can be
+    /// called off at any point before transport starts)
+    pub fn next_statuses(&self) -> Vec<AmbulanceStatus> {
+        match self {
+            AmbulanceStatus::Available => vec![AmbulanceStatus::Dispatched],
+            AmbulanceStatus::Dispatched => {
+                vec![AmbulanceStatus::EnRoute, AmbulanceStatus::Available]
+            }
+            AmbulanceStatus::EnRoute => vec![AmbulanceStatus::AtScene, AmbulanceStatus::Available],
+            AmbulanceStatus::AtScene => {
+                vec![AmbulanceStatus::Transporting, AmbulanceStatus::Available]
+            }
+            AmbulanceStatus::Transporting => vec![AmbulanceStatus::Available],
+        }
+    }
+
+    /// Check if the ambulance can be dispatched to a new incident
+    pub fn is_available(&self) -> bool {
+        matches!(self, AmbulanceStatus::Available)
+    }
+
+    /// Check if the ambulance is busy with an incident
+    pub fn is_on_mission(&self) -> bool {
+        !self.is_available()
+    }
+}
+
+impl std::fmt::Display for AmbulanceStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_workflow() {
+        assert_eq!(
+            AmbulanceStatus::Available.next_statuses(),
+            vec![AmbulanceStatus::Dispatched]
+        );
+        assert!(AmbulanceStatus::AtScene
+            .next_statuses()
+            .contains(&AmbulanceStatus::Transporting));
+        assert_eq!(
+            AmbulanceStatus::Transporting.next_statuses(),
+            vec![AmbulanceStatus::Available]
+        );
+    }
+
+    #[test]
+    fn test_availability() {
+        assert!(AmbulanceStatus::Available.is_available());
+        assert!(AmbulanceStatus::EnRoute.is_on_mission());
+        assert!(!AmbulanceStatus::Transporting.is_available());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&AmbulanceStatus::AtScene).unwrap();
+        assert_eq!(json, "\"at_scene\"");
+        assert_eq!(AmbulanceStatus::AtScene.to_string(), "At Scene");
+    }
+}
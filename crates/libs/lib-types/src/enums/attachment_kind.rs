@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// What a file attached to a patient record is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "attachment_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    Photo,       // e.g. scene, wound or medication packaging
+    Ecg,         // 12-lead ECG, as a printout scan or HL7 aECG export
+    ConsentForm, // Signed consent
+    Report,      // e.g. referral letter or external imaging report
+}
+
+impl AttachmentKind {
+    /// Get display name for attachment kind
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AttachmentKind::Photo => "Photo",
+            AttachmentKind::Ecg => "ECG",
+            AttachmentKind::ConsentForm => "Consent form",
+            AttachmentKind::Report => "Report",
+        }
+    }
+
+    /// Content types accepted for this kind of attachment
+    pub fn allowed_content_types(&self) -> &'static [&'static str] {
+        match self {
+            AttachmentKind::Photo => &["image/jpeg", "image/png", "image/heic"],
+            AttachmentKind::Ecg => &[
+                "application/pdf",
+                "image/jpeg",
+                "image/png",
+                "application/xml",
+            ],
+            AttachmentKind::ConsentForm => &["application/pdf", "image/jpeg", "image/png"],
+            AttachmentKind::Report => &["application/pdf"],
+        }
+    }
+}
+
+impl std::fmt::Display for AttachmentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_content_types() {
+        assert!(AttachmentKind::Ecg
+            .allowed_content_types()
+            .contains(&"application/xml"));
+        assert!(!AttachmentKind::Photo
+            .allowed_content_types()
+            .contains(&"application/pdf"));
+        assert_eq!(
+            serde_json::to_string(&AttachmentKind::ConsentForm).unwrap(),
+            "\"consent_form\""
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+use crate::errors::AuthError;
+
+/// Kind of authentication event recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "auth_event", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuthEvent {
+    Login,
+    LoginFailed,
+    AccountLocked,
+    Logout,
+    TokenRefresh,
+    ServiceToken,
+    AccessDenied,
+    InvalidToken,
+}
+
+/// Whether an audited authentication event succeeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "auth_outcome", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuthOutcome {
+    Success,
+    Failure,
+}
+
+impl AuthEvent {
+    /// Get the audit event for a rejected request
+    pub fn for_error(error: &AuthError) -> Self {
+        match error {
+            AuthError::AccountLocked => AuthEvent::AccountLocked,
+            AuthError::InsufficientPermissions | AuthError::HospitalAccessDenied { .. } => {
+                AuthEvent::AccessDenied
+            }
+            AuthError::InvalidToken
+            | AuthError::TokenExpired
+            | AuthError::MissingToken
+            | AuthError::SessionTerminated => AuthEvent::InvalidToken,
+            _ => AuthEvent::LoginFailed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ids::HospitalId;
+
+    #[test]
+    fn test_event_for_error() {
+        assert_eq!(
+            AuthEvent::for_error(&AuthError::InvalidCredentials),
+            AuthEvent::LoginFailed
+        );
+        assert_eq!(
+            AuthEvent::for_error(&AuthError::AccountLocked),
+            AuthEvent::AccountLocked
+        );
+        assert_eq!(
+            AuthEvent::for_error(&AuthError::HospitalAccessDenied {
+                hospital_id: HospitalId::new()
+            }),
+            AuthEvent::AccessDenied
+        );
+        assert_eq!(
+            AuthEvent::for_error(&AuthError::InvalidToken),
+            AuthEvent::InvalidToken
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        assert_eq!(
+            serde_json::to_string(&AuthEvent::LoginFailed).unwrap(),
+            "\"login_failed\""
+        );
+        assert_eq!(
+            serde_json::to_string(&AuthOutcome::Success).unwrap(),
+            "\"success\""
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "bed_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BedStatus {
+    Available,
+    Occupied,
+    Cleaning,
+    OutOfService,
+}
+
+impl BedStatus {
+    /// Get display name for bed status
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BedStatus::Available => "Available",
+            BedStatus::Occupied => "Occupied",
+            BedStatus::Cleaning => "Cleaning",
+            BedStatus::OutOfService => "Out of Service",
+        }
+    }
+
+    /// Get next possible statuses from current status (an occupied bed must
+    /// be cleaned before the next patient)
+    pub fn next_statuses(&self) -> Vec<BedStatus> {
+        match self {
+            BedStatus::Available => vec![BedStatus::Occupied, BedStatus::OutOfService],
+            BedStatus::Occupied => vec![BedStatus::Cleaning],
+            BedStatus::Cleaning => vec![BedStatus::Available, BedStatus::OutOfService],
+            BedStatus::OutOfService => vec![BedStatus::Cleaning, BedStatus::Available],
+        }
+    }
+
+    /// Check if a patient can be placed in the bed
+    pub fn is_assignable(&self) -> bool {
+        matches!(self, BedStatus::Available)
+    }
+
+    /// Check if the bed counts towards hospital capacity
+    pub fn is_in_service(&self) -> bool {
+        !matches!(self, BedStatus::OutOfService)
+    }
+}
+
+impl std::fmt::Display for BedStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_workflow() {
+        assert_eq!(
+            BedStatus::Occupied.next_statuses(),
+            vec![BedStatus::Cleaning]
+        );
+        assert!(BedStatus::Cleaning
+            .next_statuses()
+            .contains(&BedStatus::Available));
+        assert!(!BedStatus::Occupied
+            .next_statuses()
+            .contains(&BedStatus::Available));
+    }
+
+    #[test]
+    fn test_assignable() {
+        assert!(BedStatus::Available.is_assignable());
+        assert!(!BedStatus::Cleaning.is_assignable());
+        assert!(!BedStatus::OutOfService.is_in_service());
+        assert!(BedStatus::Occupied.is_in_service());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&BedStatus::OutOfService).unwrap();
+        assert_eq!(json, "\"out_of_service\"");
+    }
+}
@@ -31,7 +31,10 @@ impl BedType {
         match (self, triage_level) {
             (BedType::Icu, TriageLevel::Critical) => true,
             (BedType::Emergency, TriageLevel::Critical | TriageLevel::High) => true,
-            (BedType::General, TriageLevel::Medium | TriageLevel::Low) => true,
+            (
+                BedType::General,
+                TriageLevel::Medium | TriageLevel::Low | TriageLevel::NonUrgent,
+            ) => true,
             (BedType::Isolation, _) => true, // Isolation beds can take any patient if needed
             (BedType::Pediatric, _) => false, // Pediatric beds need age check, not just triage
             _ => false,
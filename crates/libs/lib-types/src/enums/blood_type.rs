@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// ABO/Rh blood group of a patient
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "blood_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum BloodType {
+    #[serde(alias = "A+")]
+    APositive,
+    #[serde(alias = "A-")]
+    ANegative,
+    #[serde(alias = "B+")]
+    BPositive,
+    #[serde(alias = "B-")]
+    BNegative,
+    #[serde(alias = "AB+")]
+    AbPositive,
+    #[serde(alias = "AB-")]
+    AbNegative,
+    #[serde(alias = "O+")]
+    OPositive,
+    #[serde(alias = "O-")]
+    ONegative,
+}
+
+impl BloodType {
+    /// Get display name for blood type
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            BloodType::APositive => "A+",
+            BloodType::ANegative => "A-",
+            BloodType::BPositive => "B+",
+            BloodType::BNegative => "B-",
+            BloodType::AbPositive => "AB+",
+            BloodType::AbNegative => "AB-",
+            BloodType::OPositive => "O+",
+            BloodType::ONegative => "O-",
+        }
+    }
+
+    fn has_a(&self) -> bool {
+        matches!(
+            self,
+            BloodType::APositive
+                | BloodType::ANegative
+                | BloodType::AbPositive
+                | BloodType::AbNegative
+        )
+    }
+
+    fn has_b(&self) -> bool {
+        matches!(
+            self,
+            BloodType::BPositive
+                | BloodType::BNegative
+                | BloodType::AbPositive
+                | BloodType::AbNegative
+        )
+    }
+
+    /// Check if the blood group is Rh(D) positive
+    pub fn is_rh_positive(&self) -> bool {
+        matches!(
+            self,
+            BloodType::APositive
+                | BloodType::BPositive
+                | BloodType::AbPositive
+                | BloodType::OPositive
+        )
+    }
+
+    /// Check if red cells from `donor` are compatible with this recipient
+    pub fn can_receive_from(&self, donor: BloodType) -> bool {
+        (!donor.has_a() || self.has_a())
+            && (!donor.has_b() || self.has_b())
+            && (!donor.is_rh_positive() || self.is_rh_positive())
+    }
+}
+
+impl std::fmt::Display for BloodType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        assert_eq!(
+            serde_json::to_string(&BloodType::AbNegative).unwrap(),
+            "\"ab_negative\""
+        );
+        assert_eq!(
+            serde_json::from_str::<BloodType>("\"O-\"").unwrap(),
+            BloodType::ONegative
+        );
+        assert_eq!(BloodType::AbPositive.to_string(), "AB+");
+    }
+
+    #[test]
+    fn test_red_cell_compatibility() {
+        assert!(BloodType::AbPositive.can_receive_from(BloodType::ONegative));
+        assert!(BloodType::APositive.can_receive_from(BloodType::ONegative));
+        assert!(BloodType::ANegative.can_receive_from(BloodType::ONegative));
+        assert!(!BloodType::ANegative.can_receive_from(BloodType::APositive));
+        assert!(!BloodType::OPositive.can_receive_from(BloodType::APositive));
+        assert!(!BloodType::BPositive.can_receive_from(BloodType::AbPositive));
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// Level of consciousness on the ACVPU scale (AVPU with new confusion)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "consciousness_level", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ConsciousnessLevel {
+    Alert,
+    NewConfusion,
+    Voice,
+    Pain,
+    Unresponsive,
+}
+
+impl ConsciousnessLevel {
+    /// Get display name for consciousness level
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ConsciousnessLevel::Alert => "Alert",
+            ConsciousnessLevel::NewConfusion => "New Confusion",
+            ConsciousnessLevel::Voice => "Responds to Voice",
+            ConsciousnessLevel::Pain => "Responds to Pain",
+            ConsciousnessLevel::Unresponsive => "Unresponsive",
+        }
+    }
+
+    /// Get the ACVPU letter
+    pub fn code(&self) -> char {
+        match self {
+            ConsciousnessLevel::Alert => 'A',
+            ConsciousnessLevel::NewConfusion => 'C',
+            ConsciousnessLevel::Voice => 'V',
+            ConsciousnessLevel::Pain => 'P',
+            ConsciousnessLevel::Unresponsive => 'U',
+        }
+    }
+
+    pub fn is_alert(&self) -> bool {
+        matches!(self, ConsciousnessLevel::Alert)
+    }
+}
+
+impl std::fmt::Display for ConsciousnessLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&ConsciousnessLevel::NewConfusion).unwrap();
+        assert_eq!(json, "\"new_confusion\"");
+        assert_eq!(ConsciousnessLevel::Pain.code(), 'P');
+    }
+}
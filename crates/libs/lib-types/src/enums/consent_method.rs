@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// How consent was given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "consent_method", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentMethod {
+    Written,
+    Electronic,
+    Verbal,
+    Telephone,
+    EmergencyImplied, // No one able to consent; treatment judged immediately necessary
+}
+
+impl ConsentMethod {
+    /// Get display name for consent method
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ConsentMethod::Written => "Written",
+            ConsentMethod::Electronic => "Electronic",
+            ConsentMethod::Verbal => "Verbal",
+            ConsentMethod::Telephone => "Telephone",
+            ConsentMethod::EmergencyImplied => "Implied (emergency)",
+        }
+    }
+
+    /// Check if a second member of staff must witness the consent
+    pub fn requires_witness(&self) -> bool {
+        matches!(
+            self,
+            ConsentMethod::Verbal | ConsentMethod::Telephone | ConsentMethod::EmergencyImplied
+        )
+    }
+}
+
+impl std::fmt::Display for ConsentMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requires_witness() {
+        assert!(ConsentMethod::Telephone.requires_witness());
+        assert!(ConsentMethod::EmergencyImplied.requires_witness());
+        assert!(!ConsentMethod::Written.requires_witness());
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// What a patient, or someone on their behalf, consented to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "consent_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ConsentType {
+    Treatment, // General consent to examination and treatment
+    Procedure, // A named procedure, e.g. "Closed reduction of left wrist"
+    BloodTransfusion,
+    Transfer,
+    Discharge,
+    DataSharing, // Sharing records outside the treating hospital
+}
+
+impl ConsentType {
+    /// Get display name for consent type
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ConsentType::Treatment => "Treatment",
+            ConsentType::Procedure => "Procedure",
+            ConsentType::BloodTransfusion => "Blood transfusion",
+            ConsentType::Transfer => "Transfer",
+            ConsentType::Discharge => "Discharge",
+            ConsentType::DataSharing => "Data sharing",
+        }
+    }
+
+    /// Check if the consent must say exactly what it covers
+    pub fn requires_scope(&self) -> bool {
+        matches!(self, ConsentType::Procedure | ConsentType::DataSharing)
+    }
+}
+
+impl std::fmt::Display for ConsentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&ConsentType::BloodTransfusion).unwrap();
+        assert_eq!(json, "\"blood_transfusion\"");
+        assert!(ConsentType::Procedure.requires_scope());
+        assert!(!ConsentType::Discharge.requires_scope());
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// Relationship of an emergency contact or consenting person to the patient
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "contact_relationship", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ContactRelationship {
+    Spouse,
+    Parent,
+    Child,
+    Sibling,
+    Guardian,
+    Relative,
+    Friend,
+    Caregiver,
+    Other,
+}
+
+impl ContactRelationship {
+    /// Get display name for contact relationship
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ContactRelationship::Spouse => "Spouse",
+            ContactRelationship::Parent => "Parent",
+            ContactRelationship::Child => "Child",
+            ContactRelationship::Sibling => "Sibling",
+            ContactRelationship::Guardian => "Guardian",
+            ContactRelationship::Relative => "Relative",
+            ContactRelationship::Friend => "Friend",
+            ContactRelationship::Caregiver => "Caregiver",
+            ContactRelationship::Other => "Other",
+        }
+    }
+
+    /// Map a free-text relationship (as stored before it was an enum, e.g.
+    /// "Wife") to the closest relationship
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "spouse" | "wife" | "husband" | "partner" => ContactRelationship::Spouse,
+            "parent" | "mother" | "father" | "mom" | "dad" => ContactRelationship::Parent,
+            "child" | "son" | "daughter" => ContactRelationship::Child,
+            "sibling" | "brother" | "sister" => ContactRelationship::Sibling,
+            "guardian" | "legal_guardian" => ContactRelationship::Guardian,
+            "relative" | "grandparent" | "grandmother" | "grandfather" | "uncle" | "aunt"
+            | "cousin" | "nephew" | "niece" => ContactRelationship::Relative,
+            "friend" => ContactRelationship::Friend,
+            "caregiver" | "carer" | "nurse" | "maid" => ContactRelationship::Caregiver,
+            _ => ContactRelationship::Other,
+        }
+    }
+
+    /// Check if the contact can give consent for a minor patient
+    pub fn can_consent_for_minor(&self) -> bool {
+        matches!(
+            self,
+            ContactRelationship::Parent | ContactRelationship::Guardian
+        )
+    }
+}
+
+impl std::fmt::Display for ContactRelationship {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_label() {
+        assert_eq!(
+            ContactRelationship::from_label("Wife"),
+            ContactRelationship::Spouse
+        );
+        assert_eq!(
+            ContactRelationship::from_label(" legal guardian "),
+            ContactRelationship::Guardian
+        );
+        assert_eq!(
+            ContactRelationship::from_label("Neighbour"),
+            ContactRelationship::Other
+        );
+    }
+
+    #[test]
+    fn test_minor_consent() {
+        assert!(ContactRelationship::Guardian.can_consent_for_minor());
+        assert!(!ContactRelationship::Sibling.can_consent_for_minor());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&ContactRelationship::Caregiver).unwrap();
+        assert_eq!(json, "\"caregiver\"");
+    }
+}
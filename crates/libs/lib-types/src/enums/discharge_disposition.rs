@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// Where a patient goes when discharged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "discharge_disposition", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DischargeDisposition {
+    Home,
+    HomeWithCare, // Home with community nursing or a caregiver
+    NursingFacility,
+    Rehabilitation,
+    AgainstMedicalAdvice,
+}
+
+impl DischargeDisposition {
+    /// Get display name for discharge disposition
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            DischargeDisposition::Home => "Home",
+            DischargeDisposition::HomeWithCare => "Home with care",
+            DischargeDisposition::NursingFacility => "Nursing facility",
+            DischargeDisposition::Rehabilitation => "Rehabilitation",
+            DischargeDisposition::AgainstMedicalAdvice => "Against medical advice",
+        }
+    }
+
+    /// Check if the patient leaves against the clinician's advice, which is
+    /// allowed even when their condition is critical
+    pub fn is_against_advice(&self) -> bool {
+        matches!(self, DischargeDisposition::AgainstMedicalAdvice)
+    }
+}
+
+impl std::fmt::Display for DischargeDisposition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&DischargeDisposition::HomeWithCare).unwrap();
+        assert_eq!(json, "\"home_with_care\"");
+        assert_eq!(
+            DischargeDisposition::AgainstMedicalAdvice.to_string(),
+            "Against medical advice"
+        );
+    }
+}
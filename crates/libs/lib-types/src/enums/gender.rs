@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+use lib_utils::reference_ranges::Sex;
+
+/// Gender of a patient. Accepts the capitalised values stored before this
+/// was an enum (e.g. "Male").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "gender", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum Gender {
+    #[serde(alias = "Male")]
+    Male,
+    #[serde(alias = "Female")]
+    Female,
+    #[serde(alias = "Other")]
+    Other,
+}
+
+impl Gender {
+    /// Get display name for gender
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Gender::Male => "Male",
+            Gender::Female => "Female",
+            Gender::Other => "Other",
+        }
+    }
+
+    /// Get the sex used to pick sex-specific reference ranges, if any
+    pub fn sex(&self) -> Option<Sex> {
+        match self {
+            Gender::Male => Some(Sex::Male),
+            Gender::Female => Some(Sex::Female),
+            Gender::Other => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Gender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        assert_eq!(
+            serde_json::to_string(&Gender::Female).unwrap(),
+            "\"female\""
+        );
+        assert_eq!(
+            serde_json::from_str::<Gender>("\"Male\"").unwrap(),
+            Gender::Male
+        );
+        assert!(serde_json::from_str::<Gender>("\"Invalid\"").is_err());
+    }
+}
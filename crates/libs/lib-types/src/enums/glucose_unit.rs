@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::units::{MgPerDl, MmolPerL};
+
+/// Unit a blood glucose reading was taken in. Readings are stored in mmol/L.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlucoseUnit {
+    #[default]
+    MmolPerL,
+    MgPerDl,
+}
+
+impl GlucoseUnit {
+    /// Get the unit symbol
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            GlucoseUnit::MmolPerL => MmolPerL::SYMBOL,
+            GlucoseUnit::MgPerDl => MgPerDl::SYMBOL,
+        }
+    }
+
+    /// Convert a reading in this unit to mmol/L
+    pub fn to_mmol_per_l(&self, value: f32) -> MmolPerL {
+        match self {
+            GlucoseUnit::MmolPerL => MmolPerL(value),
+            GlucoseUnit::MgPerDl => MgPerDl(value).into(),
+        }
+    }
+}
+
+impl std::fmt::Display for GlucoseUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_mmol_per_l() {
+        assert_eq!(GlucoseUnit::MgPerDl.to_mmol_per_l(90.0), MmolPerL(5.0));
+        let json = serde_json::to_string(&GlucoseUnit::MgPerDl).unwrap();
+        assert_eq!(json, "\"mg_per_dl\"");
+    }
+}
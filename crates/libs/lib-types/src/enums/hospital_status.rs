@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+use crate::enums::TriageLevel;
+
+/// Operating status of a hospital
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "hospital_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum HospitalStatus {
+    #[serde(alias = "Active")]
+    Active,
+    #[serde(alias = "Maintenance")]
+    Maintenance,
+    #[serde(alias = "Emergency Only")]
+    EmergencyOnly, // Only emergency-level patients are taken
+}
+
+impl HospitalStatus {
+    /// Get display name for hospital status
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            HospitalStatus::Active => "Active",
+            HospitalStatus::Maintenance => "Maintenance",
+            HospitalStatus::EmergencyOnly => "Emergency Only",
+        }
+    }
+
+    /// Check if the hospital takes all new patients
+    pub fn is_accepting_patients(&self) -> bool {
+        matches!(self, HospitalStatus::Active)
+    }
+
+    /// Check if the hospital takes a patient of the given triage level
+    pub fn accepts(&self, triage_level: TriageLevel) -> bool {
+        match self {
+            HospitalStatus::Active => true,
+            HospitalStatus::EmergencyOnly => triage_level.is_emergency(),
+            HospitalStatus::Maintenance => false,
+        }
+    }
+}
+
+impl std::fmt::Display for HospitalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepts() {
+        assert!(HospitalStatus::Active.accepts(TriageLevel::Low));
+        assert!(HospitalStatus::EmergencyOnly.accepts(TriageLevel::Critical));
+        assert!(!HospitalStatus::EmergencyOnly.accepts(TriageLevel::Medium));
+        assert!(!HospitalStatus::Maintenance.accepts(TriageLevel::Critical));
+    }
+
+    #[test]
+    fn test_serialization() {
+        assert_eq!(
+            serde_json::to_string(&HospitalStatus::EmergencyOnly).unwrap(),
+            "\"emergency_only\""
+        );
+        assert_eq!(
+            serde_json::from_str::<HospitalStatus>("\"Emergency Only\"").unwrap(),
+            HospitalStatus::EmergencyOnly
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// Ownership and role of a hospital
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "hospital_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum HospitalType {
+    #[serde(alias = "Public")]
+    Public,
+    #[serde(alias = "Private")]
+    Private,
+    #[serde(alias = "Specialized")]
+    Specialized, // Single-specialty centres, e.g. cardiac or burns
+}
+
+impl HospitalType {
+    /// Get display name for hospital type
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            HospitalType::Public => "Public",
+            HospitalType::Private => "Private",
+            HospitalType::Specialized => "Specialized",
+        }
+    }
+}
+
+impl std::fmt::Display for HospitalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        assert_eq!(
+            serde_json::to_string(&HospitalType::Specialized).unwrap(),
+            "\"specialized\""
+        );
+        assert_eq!(
+            serde_json::from_str::<HospitalType>("\"Private\"").unwrap(),
+            HospitalType::Private
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of identifier a patient can be known by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierScheme {
+    EmiratesId,
+    Passport,
+    GccId,   // National ID of another GCC state
+    Mrn,     // Medical record number issued by the hospital
+    Unknown, // Temporary alias for an unidentified patient, e.g. "TRAUMA-ALPHA-07"
+}
+
+impl IdentifierScheme {
+    /// Get display name for identifier scheme
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            IdentifierScheme::EmiratesId => "Emirates ID",
+            IdentifierScheme::Passport => "Passport",
+            IdentifierScheme::GccId => "GCC ID",
+            IdentifierScheme::Mrn => "MRN",
+            IdentifierScheme::Unknown => "Unidentified patient alias",
+        }
+    }
+
+    /// Check if values are only unique within the issuing hospital
+    pub fn is_hospital_scoped(&self) -> bool {
+        matches!(self, IdentifierScheme::Mrn | IdentifierScheme::Unknown)
+    }
+}
+
+impl std::fmt::Display for IdentifierScheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        assert_eq!(
+            serde_json::to_string(&IdentifierScheme::EmiratesId).unwrap(),
+            "\"emirates_id\""
+        );
+        assert!(IdentifierScheme::Mrn.is_hospital_scoped());
+        assert!(!IdentifierScheme::Passport.is_hospital_scoped());
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// Incident scale, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "incident_severity", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentSeverity {
+    Minor,
+    Major,
+    MassCasualty,
+    Disaster,
+}
+
+impl IncidentSeverity {
+    /// Get display name for incident severity
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            IncidentSeverity::Minor => "Minor",
+            IncidentSeverity::Major => "Major",
+            IncidentSeverity::MassCasualty => "Mass Casualty",
+            IncidentSeverity::Disaster => "Disaster",
+        }
+    }
+
+    /// Lowest severity matching a number of casualties
+    pub fn for_casualty_count(count: usize) -> Self {
+        match count {
+            0..=4 => IncidentSeverity::Minor,
+            5..=9 => IncidentSeverity::Major,
+            10..=99 => IncidentSeverity::MassCasualty,
+            _ => IncidentSeverity::Disaster,
+        }
+    }
+
+    /// Check if the incident overwhelms normal emergency capacity and must be
+    /// run under the mass-casualty plan
+    pub fn is_mass_casualty(&self) -> bool {
+        *self >= IncidentSeverity::MassCasualty
+    }
+}
+
+impl std::fmt::Display for IncidentSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_for_casualties() {
+        assert_eq!(
+            IncidentSeverity::for_casualty_count(0),
+            IncidentSeverity::Minor
+        );
+        assert_eq!(
+            IncidentSeverity::for_casualty_count(7),
+            IncidentSeverity::Major
+        );
+        assert_eq!(
+            IncidentSeverity::for_casualty_count(10),
+            IncidentSeverity::MassCasualty
+        );
+        assert_eq!(
+            IncidentSeverity::for_casualty_count(250),
+            IncidentSeverity::Disaster
+        );
+    }
+
+    #[test]
+    fn test_mass_casualty() {
+        assert!(IncidentSeverity::Disaster.is_mass_casualty());
+        assert!(!IncidentSeverity::Major.is_mass_casualty());
+        assert!(IncidentSeverity::Major < IncidentSeverity::MassCasualty);
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&IncidentSeverity::MassCasualty).unwrap();
+        assert_eq!(json, "\"mass_casualty\"");
+    }
+}
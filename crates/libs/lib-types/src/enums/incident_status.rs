@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "incident_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentStatus {
+    Reported,
+    Active,
+    Contained,
+    Closed,
+}
+
+impl IncidentStatus {
+    /// Get display name for incident status
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            IncidentStatus::Reported => "Reported",
+            IncidentStatus::Active => "Active",
+            IncidentStatus::Contained => "Contained",
+            IncidentStatus::Closed => "Closed",
+        }
+    }
+
+    /// Get next possible statuses from current status (a report can turn out
+    /// to be a false alarm, and a contained scene can flare up again)
+    pub fn next_statuses(&self) -> Vec<IncidentStatus> {
+        match self {
+            IncidentStatus::Reported => vec![IncidentStatus::Active, IncidentStatus::Closed],
+            IncidentStatus::Active => vec![IncidentStatus::Contained],
+            IncidentStatus::Contained => vec![IncidentStatus::Active, IncidentStatus::Closed],
+            IncidentStatus::Closed => vec![],
+        }
+    }
+
+    /// Check if the incident is still being managed
+    pub fn is_open(&self) -> bool {
+        !matches!(self, IncidentStatus::Closed)
+    }
+}
+
+impl std::fmt::Display for IncidentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_workflow() {
+        assert!(IncidentStatus::Reported
+            .next_statuses()
+            .contains(&IncidentStatus::Closed));
+        assert!(!IncidentStatus::Active
+            .next_statuses()
+            .contains(&IncidentStatus::Closed));
+        assert!(IncidentStatus::Closed.next_statuses().is_empty());
+        assert!(!IncidentStatus::Closed.is_open());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&IncidentStatus::Contained).unwrap();
+        assert_eq!(json, "\"contained\"");
+        assert_eq!(IncidentStatus::Active.to_string(), "Active");
+    }
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "incident_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentType {
+    TrafficCollision,
+    Fire,
+    StructuralCollapse,
+    HazardousMaterials,
+    Explosion,
+    MassGathering,
+    NaturalDisaster,
+    Other,
+}
+
+impl IncidentType {
+    /// Get display name for incident type
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            IncidentType::TrafficCollision => "Traffic Collision",
+            IncidentType::Fire => "Fire",
+            IncidentType::StructuralCollapse => "Structural Collapse",
+            IncidentType::HazardousMaterials => "Hazardous Materials",
+            IncidentType::Explosion => "Explosion",
+            IncidentType::MassGathering => "Mass Gathering",
+            IncidentType::NaturalDisaster => "Natural Disaster",
+            IncidentType::Other => "Other",
+        }
+    }
+
+    /// Check if casualties must be decontaminated before entering the hospital
+    pub fn requires_decontamination(&self) -> bool {
+        matches!(self, IncidentType::HazardousMaterials)
+    }
+}
+
+impl std::fmt::Display for IncidentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decontamination() {
+        assert!(IncidentType::HazardousMaterials.requires_decontamination());
+        assert!(!IncidentType::TrafficCollision.requires_decontamination());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&IncidentType::StructuralCollapse).unwrap();
+        assert_eq!(json, "\"structural_collapse\"");
+        assert_eq!(
+            IncidentType::HazardousMaterials.to_string(),
+            "Hazardous Materials"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::{PgHasArrayType, PgTypeInfo};
+use sqlx::Type;
+
+/// Transmission-based infection control precautions, on top of the
+/// standard precautions every patient gets
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Type,
+)]
+#[sqlx(type_name = "isolation_precaution", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationPrecaution {
+    Contact,        // e.g. MRSA, scabies
+    EntericContact, // e.g. C. difficile, norovirus; soap and water, no alcohol gel
+    Droplet,        // e.g. influenza, pertussis
+    Airborne,       // e.g. tuberculosis, measles; negative pressure room
+    Protective,     // Neutropenic or immunocompromised patient
+}
+
+impl IsolationPrecaution {
+    /// Get display name for isolation precaution
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            IsolationPrecaution::Contact => "Contact",
+            IsolationPrecaution::EntericContact => "Enteric contact",
+            IsolationPrecaution::Droplet => "Droplet",
+            IsolationPrecaution::Airborne => "Airborne",
+            IsolationPrecaution::Protective => "Protective",
+        }
+    }
+
+    /// Check if the patient needs a negative pressure room
+    pub fn requires_negative_pressure(&self) -> bool {
+        matches!(self, IsolationPrecaution::Airborne)
+    }
+}
+
+impl PgHasArrayType for IsolationPrecaution {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("_isolation_precaution")
+    }
+}
+
+impl std::fmt::Display for IsolationPrecaution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialization() {
+        assert_eq!(
+            serde_json::to_string(&IsolationPrecaution::EntericContact).unwrap(),
+            "\"enteric_contact\""
+        );
+        assert!(IsolationPrecaution::Airborne.requires_negative_pressure());
+        assert!(!IsolationPrecaution::Droplet.requires_negative_pressure());
+    }
+}
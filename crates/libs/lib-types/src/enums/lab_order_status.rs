@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "lab_order_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum LabOrderStatus {
+    Ordered,
+    Resulted, // At least one result received; more may follow
+    Cancelled,
+}
+
+impl LabOrderStatus {
+    /// Get display name for lab order status
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LabOrderStatus::Ordered => "Ordered",
+            LabOrderStatus::Resulted => "Resulted",
+            LabOrderStatus::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Check if results can still be received for the order
+    pub fn accepts_results(&self) -> bool {
+        !matches!(self, LabOrderStatus::Cancelled)
+    }
+}
+
+impl std::fmt::Display for LabOrderStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepts_results() {
+        assert!(LabOrderStatus::Ordered.accepts_results());
+        assert!(LabOrderStatus::Resulted.accepts_results());
+        assert!(!LabOrderStatus::Cancelled.accepts_results());
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// How a prescribed medication is given
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "medication_route", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MedicationRoute {
+    Oral,
+    Sublingual,
+    Intravenous,
+    Intramuscular,
+    Subcutaneous,
+    Inhaled,
+    Topical,
+    Rectal,
+}
+
+impl MedicationRoute {
+    /// Get display name for medication route
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            MedicationRoute::Oral => "Oral",
+            MedicationRoute::Sublingual => "Sublingual",
+            MedicationRoute::Intravenous => "Intravenous",
+            MedicationRoute::Intramuscular => "Intramuscular",
+            MedicationRoute::Subcutaneous => "Subcutaneous",
+            MedicationRoute::Inhaled => "Inhaled",
+            MedicationRoute::Topical => "Topical",
+            MedicationRoute::Rectal => "Rectal",
+        }
+    }
+
+    /// Get the abbreviation used on medication charts
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            MedicationRoute::Oral => "PO",
+            MedicationRoute::Sublingual => "SL",
+            MedicationRoute::Intravenous => "IV",
+            MedicationRoute::Intramuscular => "IM",
+            MedicationRoute::Subcutaneous => "SC",
+            MedicationRoute::Inhaled => "INH",
+            MedicationRoute::Topical => "TOP",
+            MedicationRoute::Rectal => "PR",
+        }
+    }
+
+    /// Check if the medication is injected
+    pub fn is_parenteral(&self) -> bool {
+        matches!(
+            self,
+            MedicationRoute::Intravenous
+                | MedicationRoute::Intramuscular
+                | MedicationRoute::Subcutaneous
+        )
+    }
+}
+
+impl std::fmt::Display for MedicationRoute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parenteral_routes() {
+        assert!(MedicationRoute::Intravenous.is_parenteral());
+        assert!(!MedicationRoute::Oral.is_parenteral());
+        assert_eq!(MedicationRoute::Rectal.abbreviation(), "PR");
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&MedicationRoute::Intramuscular).unwrap();
+        assert_eq!(json, "\"intramuscular\"");
+    }
+}
@@ -5,9 +5,67 @@ pub mod triage_level;
 pub mod patient_status;
 pub mod availability_status;
 pub mod bed_type;
+pub mod bed_status;
+pub mod service_scope;
+pub mod auth_event;
+pub mod ambulance_status;
+pub mod incident_type;
+pub mod incident_severity;
+pub mod incident_status;
+pub mod contact_relationship;
+pub mod medication_route;
+pub mod consciousness_level;
+pub mod gender;
+pub mod hospital_type;
+pub mod hospital_status;
+pub mod seniority_level;
+pub mod temperature_unit;
+pub mod glucose_unit;
+pub mod weight_unit;
+pub mod transfer_status;
+pub mod discharge_disposition;
+pub mod blood_type;
+pub mod isolation_precaution;
+pub mod identifier_scheme;
+pub mod attachment_kind;
+pub mod lab_order_status;
+pub mod abnormal_flag;
+pub mod consent_type;
+pub mod consent_method;
+pub mod patient_event_kind;
 
 pub use user_role::UserRole;
 pub use triage_level::TriageLevel;
 pub use patient_status::PatientStatus;
 pub use availability_status::AvailabilityStatus;
-pub use bed_type::BedType;
\ No newline at end of file
+pub use bed_type::BedType;
+pub use bed_status::BedStatus;
+pub use service_scope::ServiceScope;
+pub use auth_event::{AuthEvent, AuthOutcome};
+pub use ambulance_status::AmbulanceStatus;
+pub use incident_type::IncidentType;
+pub use incident_severity::IncidentSeverity;
+pub use incident_status::IncidentStatus;
+pub use contact_relationship::ContactRelationship;
+pub use medication_route::MedicationRoute;
+pub use consciousness_level::ConsciousnessLevel;
+pub use lib_utils::reference_ranges::AgeBand;
+pub use gender::Gender;
+pub use hospital_type::HospitalType;
+pub use hospital_status::HospitalStatus;
+pub use seniority_level::SeniorityLevel;
+pub use temperature_unit::TemperatureUnit;
+pub use glucose_unit::GlucoseUnit;
+pub use weight_unit::WeightUnit;
+pub use lib_utils::format::Locale;
+pub use transfer_status::TransferStatus;
+pub use discharge_disposition::DischargeDisposition;
+pub use blood_type::BloodType;
+pub use isolation_precaution::IsolationPrecaution;
+pub use identifier_scheme::IdentifierScheme;
+pub use attachment_kind::AttachmentKind;
+pub use lab_order_status::LabOrderStatus;
+pub use abnormal_flag::AbnormalFlag;
+pub use consent_type::ConsentType;
+pub use consent_method::ConsentMethod;
+pub use patient_event_kind::PatientEventKind;
\ No newline at end of file
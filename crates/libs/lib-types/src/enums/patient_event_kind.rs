@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// Kind of change recorded in a patient's event log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "patient_event_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PatientEventKind {
+    StatusChanged,
+    TriageChanged,
+    StaffAssigned,
+    AmbulanceAssigned,
+    BedAssigned,
+    VitalsRecorded,
+}
+
+impl PatientEventKind {
+    /// The name of the kind in the API, e.g. `status_changed`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PatientEventKind::StatusChanged => "status_changed",
+            PatientEventKind::TriageChanged => "triage_changed",
+            PatientEventKind::StaffAssigned => "staff_assigned",
+            PatientEventKind::AmbulanceAssigned => "ambulance_assigned",
+            PatientEventKind::BedAssigned => "bed_assigned",
+            PatientEventKind::VitalsRecorded => "vitals_recorded",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_names() {
+        for kind in [
+            PatientEventKind::StatusChanged,
+            PatientEventKind::BedAssigned,
+            PatientEventKind::VitalsRecorded,
+        ] {
+            assert_eq!(serde_json::to_value(kind).unwrap(), kind.as_str());
+        }
+    }
+}
@@ -8,8 +8,14 @@ pub enum PatientStatus {
     Dispatched,
     EnRoute,
     Arrived,
+    InTriage,
+    InTreatment,
+    AwaitingResults,
     Admitted,
     Discharged,
+    Transferred,
+    Deceased,
+    LeftWithoutBeingSeen,
 }
 
 impl PatientStatus {
@@ -19,22 +25,72 @@ impl PatientStatus {
             PatientStatus::Dispatched => "Dispatched",
             PatientStatus::EnRoute => "En Route",
             PatientStatus::Arrived => "Arrived",
+            PatientStatus::InTriage => "In Triage",
+            PatientStatus::InTreatment => "In Treatment",
+            PatientStatus::AwaitingResults => "Awaiting Results",
             PatientStatus::Admitted => "Admitted",
             PatientStatus::Discharged => "Discharged",
+            PatientStatus::Transferred => "Transferred",
+            PatientStatus::Deceased => "Deceased",
+            PatientStatus::LeftWithoutBeingSeen => "Left Without Being Seen",
         }
     }
 
-    /// Get next possible statuses from current status
+    /// Get next possible statuses from current status. Critical arrivals may
+    /// skip triage, and a patient can die at any point before leaving.
     pub fn next_statuses(&self) -> Vec<PatientStatus> {
         match self {
-            PatientStatus::Dispatched => vec![PatientStatus::EnRoute],
-            PatientStatus::EnRoute => vec![PatientStatus::Arrived],
-            PatientStatus::Arrived => vec![PatientStatus::Admitted],
-            PatientStatus::Admitted => vec![PatientStatus::Discharged],
-            PatientStatus::Discharged => vec![], // Terminal status
+            PatientStatus::Dispatched => vec![PatientStatus::EnRoute, PatientStatus::Deceased],
+            PatientStatus::EnRoute => vec![PatientStatus::Arrived, PatientStatus::Deceased],
+            PatientStatus::Arrived => vec![
+                PatientStatus::InTriage,
+                PatientStatus::InTreatment,
+                PatientStatus::Admitted,
+                PatientStatus::LeftWithoutBeingSeen,
+                PatientStatus::Deceased,
+            ],
+            PatientStatus::InTriage => vec![
+                PatientStatus::InTreatment,
+                PatientStatus::Discharged,
+                PatientStatus::LeftWithoutBeingSeen,
+                PatientStatus::Deceased,
+            ],
+            PatientStatus::InTreatment => vec![
+                PatientStatus::AwaitingResults,
+                PatientStatus::Admitted,
+                PatientStatus::Discharged,
+                PatientStatus::Transferred,
+                PatientStatus::Deceased,
+            ],
+            PatientStatus::AwaitingResults => vec![
+                PatientStatus::InTreatment,
+                PatientStatus::Admitted,
+                PatientStatus::Discharged,
+                PatientStatus::Transferred,
+                PatientStatus::Deceased,
+            ],
+            PatientStatus::Admitted => vec![
+                PatientStatus::Discharged,
+                PatientStatus::Transferred,
+                PatientStatus::Deceased,
+            ],
+            PatientStatus::Discharged
+            | PatientStatus::Transferred
+            | PatientStatus::Deceased
+            | PatientStatus::LeftWithoutBeingSeen => vec![], // Terminal statuses
         }
     }
 
+    /// Check if the status can change to `next`
+    pub fn can_transition_to(&self, next: PatientStatus) -> bool {
+        self.next_statuses().contains(&next)
+    }
+
+    /// Check if the patient's visit has ended
+    pub fn is_terminal(&self) -> bool {
+        self.next_statuses().is_empty()
+    }
+
     /// Check if status indicates patient is in transport
     pub fn is_in_transport(&self) -> bool {
         matches!(self, PatientStatus::Dispatched | PatientStatus::EnRoute)
@@ -44,23 +100,34 @@ impl PatientStatus {
     pub fn is_at_hospital(&self) -> bool {
         matches!(
             self,
-            PatientStatus::Arrived | PatientStatus::Admitted | PatientStatus::Discharged
+            PatientStatus::Arrived
+                | PatientStatus::InTriage
+                | PatientStatus::InTreatment
+                | PatientStatus::AwaitingResults
+                | PatientStatus::Admitted
+                | PatientStatus::Discharged
         )
     }
 
     /// Check if patient is currently receiving care
     pub fn is_active(&self) -> bool {
-        !matches!(self, PatientStatus::Discharged)
+        !self.is_terminal()
     }
 
-    /// Get status workflow order
+    /// Get status workflow order (every way of leaving shares the last step)
     pub fn workflow_order(&self) -> u8 {
         match self {
             PatientStatus::Dispatched => 1,
             PatientStatus::EnRoute => 2,
             PatientStatus::Arrived => 3,
-            PatientStatus::Admitted => 4,
-            PatientStatus::Discharged => 5,
+            PatientStatus::InTriage => 4,
+            PatientStatus::InTreatment => 5,
+            PatientStatus::AwaitingResults => 6,
+            PatientStatus::Admitted => 7,
+            PatientStatus::Discharged
+            | PatientStatus::Transferred
+            | PatientStatus::Deceased
+            | PatientStatus::LeftWithoutBeingSeen => 8,
         }
     }
 }
@@ -77,10 +144,36 @@ mod tests {
 
     #[test]
     fn test_status_workflow() {
-        assert_eq!(PatientStatus::Dispatched.next_statuses(), vec![PatientStatus::EnRoute]);
-        assert_eq!(PatientStatus::EnRoute.next_statuses(), vec![PatientStatus::Arrived]);
-        assert_eq!(PatientStatus::Admitted.next_statuses(), vec![PatientStatus::Discharged]);
-        assert!(PatientStatus::Discharged.next_statuses().is_empty());
+        assert_eq!(
+            PatientStatus::Dispatched.next_statuses(),
+            vec![PatientStatus::EnRoute, PatientStatus::Deceased]
+        );
+        assert!(PatientStatus::EnRoute.can_transition_to(PatientStatus::Arrived));
+        assert!(PatientStatus::Arrived.can_transition_to(PatientStatus::InTreatment)); // Bypassing triage
+        assert!(PatientStatus::AwaitingResults.can_transition_to(PatientStatus::InTreatment));
+        assert!(PatientStatus::Admitted.can_transition_to(PatientStatus::Discharged));
+        assert!(!PatientStatus::EnRoute.can_transition_to(PatientStatus::Discharged));
+        assert!(!PatientStatus::Admitted.can_transition_to(PatientStatus::LeftWithoutBeingSeen));
+    }
+
+    #[test]
+    fn test_terminal_statuses() {
+        for status in [
+            PatientStatus::Discharged,
+            PatientStatus::Transferred,
+            PatientStatus::Deceased,
+            PatientStatus::LeftWithoutBeingSeen,
+        ] {
+            assert!(status.is_terminal());
+            assert!(!status.is_active());
+        }
+        assert!(!PatientStatus::AwaitingResults.is_terminal());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&PatientStatus::LeftWithoutBeingSeen).unwrap();
+        assert_eq!(json, "\"left_without_being_seen\"");
     }
 
     #[test]
@@ -97,7 +190,9 @@ mod tests {
         assert!(!PatientStatus::EnRoute.is_at_hospital());
         assert!(PatientStatus::Arrived.is_at_hospital());
         assert!(PatientStatus::Admitted.is_at_hospital());
+        assert!(PatientStatus::AwaitingResults.is_at_hospital());
         assert!(PatientStatus::Discharged.is_at_hospital());
+        assert!(!PatientStatus::Transferred.is_at_hospital());
     }
 
     #[test]
@@ -111,5 +206,6 @@ mod tests {
     fn test_workflow_order() {
         assert!(PatientStatus::Dispatched.workflow_order() < PatientStatus::EnRoute.workflow_order());
         assert!(PatientStatus::Arrived.workflow_order() < PatientStatus::Admitted.workflow_order());
+        assert!(PatientStatus::InTriage.workflow_order() < PatientStatus::InTreatment.workflow_order());
     }
 }
\ No newline at end of file
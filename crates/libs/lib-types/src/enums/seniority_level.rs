@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+/// Seniority of a medical staff member, ordered from most junior
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "seniority_level", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SeniorityLevel {
+    #[serde(alias = "Junior")]
+    Junior,
+    #[serde(alias = "Senior")]
+    Senior,
+    #[serde(alias = "Consultant")]
+    Consultant,
+    #[serde(alias = "Director")]
+    Director,
+}
+
+impl SeniorityLevel {
+    /// Get display name for seniority level
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SeniorityLevel::Junior => "Junior",
+            SeniorityLevel::Senior => "Senior",
+            SeniorityLevel::Consultant => "Consultant",
+            SeniorityLevel::Director => "Director",
+        }
+    }
+
+    pub fn is_senior(&self) -> bool {
+        *self >= SeniorityLevel::Senior
+    }
+
+    /// Check if staff at this level can supervise others
+    pub fn can_supervise(&self) -> bool {
+        *self >= SeniorityLevel::Consultant
+    }
+}
+
+impl std::fmt::Display for SeniorityLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ordering() {
+        assert!(SeniorityLevel::Junior < SeniorityLevel::Director);
+        assert!(SeniorityLevel::Senior.is_senior());
+        assert!(!SeniorityLevel::Senior.can_supervise());
+        assert!(SeniorityLevel::Consultant.can_supervise());
+    }
+
+    #[test]
+    fn test_serialization() {
+        assert_eq!(
+            serde_json::from_str::<SeniorityLevel>("\"Consultant\"").unwrap(),
+            SeniorityLevel::Consultant
+        );
+        assert_eq!(
+            serde_json::to_string(&SeniorityLevel::Junior).unwrap(),
+            "\"junior\""
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Permissions that can be granted to a service account.
+///
+/// Scopes are deliberately narrow: service accounts never receive a
+/// `UserRole`, so they cannot reach routes meant for human staff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ServiceScope {
+    #[serde(rename = "beds:read")]
+    BedsRead,
+    #[serde(rename = "dispatch:read")]
+    DispatchRead,
+    #[serde(rename = "dispatch:write")]
+    DispatchWrite,
+    #[serde(rename = "reports:read")]
+    ReportsRead,
+}
+
+impl ServiceScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceScope::BedsRead => "beds:read",
+            ServiceScope::DispatchRead => "dispatch:read",
+            ServiceScope::DispatchWrite => "dispatch:write",
+            ServiceScope::ReportsRead => "reports:read",
+        }
+    }
+
+    pub fn all() -> Vec<ServiceScope> {
+        vec![
+            ServiceScope::BedsRead,
+            ServiceScope::DispatchRead,
+            ServiceScope::DispatchWrite,
+            ServiceScope::ReportsRead,
+        ]
+    }
+
+    /// Parse an OAuth-style space-delimited scope string
+    pub fn parse_list(scopes: &str) -> Result<Vec<ServiceScope>, String> {
+        scopes.split_whitespace().map(str::parse).collect()
+    }
+}
+
+impl fmt::Display for ServiceScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ServiceScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ServiceScope::all()
+            .into_iter()
+            .find(|scope| scope.as_str() == s)
+            .ok_or_else(|| s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for scope in ServiceScope::all() {
+            assert_eq!(scope.as_str().parse::<ServiceScope>(), Ok(scope));
+            let json = serde_json::to_string(&scope).unwrap();
+            assert_eq!(json, format!("\"{}\"", scope));
+        }
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            ServiceScope::parse_list("beds:read  dispatch:write"),
+            Ok(vec![ServiceScope::BedsRead, ServiceScope::DispatchWrite])
+        );
+        assert_eq!(
+            ServiceScope::parse_list("beds:read patients:write"),
+            Err("patients:write".to_string())
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::units::{Celsius, Fahrenheit};
+
+/// Unit a temperature was measured in. Temperatures are stored in Celsius.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// Get the unit symbol
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => Celsius::SYMBOL,
+            TemperatureUnit::Fahrenheit => Fahrenheit::SYMBOL,
+        }
+    }
+
+    /// Convert a temperature in this unit to Celsius
+    pub fn to_celsius(&self, value: f32) -> Celsius {
+        match self {
+            TemperatureUnit::Celsius => Celsius(value),
+            TemperatureUnit::Fahrenheit => Fahrenheit(value).into(),
+        }
+    }
+}
+
+impl std::fmt::Display for TemperatureUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_celsius() {
+        assert_eq!(TemperatureUnit::Celsius.to_celsius(37.0), Celsius(37.0));
+        assert!((TemperatureUnit::Fahrenheit.to_celsius(98.6).value() - 37.0).abs() < 0.01);
+    }
+}
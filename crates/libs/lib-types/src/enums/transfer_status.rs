@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[sqlx(type_name = "transfer_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    Requested,
+    Accepted,
+    Rejected,
+    Completed,
+}
+
+impl TransferStatus {
+    /// Get display name for transfer status
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            TransferStatus::Requested => "Requested",
+            TransferStatus::Accepted => "Accepted",
+            TransferStatus::Rejected => "Rejected",
+            TransferStatus::Completed => "Completed",
+        }
+    }
+
+    /// Get next possible statuses from current status. The receiving
+    /// hospital answers a request; an accepted transfer completes when the
+    /// patient arrives.
+    pub fn next_statuses(&self) -> Vec<TransferStatus> {
+        match self {
+            TransferStatus::Requested => vec![TransferStatus::Accepted, TransferStatus::Rejected],
+            TransferStatus::Accepted => vec![TransferStatus::Completed],
+            TransferStatus::Rejected | TransferStatus::Completed => vec![],
+        }
+    }
+
+    /// Check if the transfer is still in progress
+    pub fn is_open(&self) -> bool {
+        matches!(self, TransferStatus::Requested | TransferStatus::Accepted)
+    }
+}
+
+impl std::fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_workflow() {
+        assert!(TransferStatus::Requested
+            .next_statuses()
+            .contains(&TransferStatus::Rejected));
+        assert!(!TransferStatus::Requested
+            .next_statuses()
+            .contains(&TransferStatus::Completed));
+        assert!(TransferStatus::Completed.next_statuses().is_empty());
+        assert!(TransferStatus::Accepted.is_open());
+        assert!(!TransferStatus::Rejected.is_open());
+    }
+
+    #[test]
+    fn test_serialization() {
+        let json = serde_json::to_string(&TransferStatus::Accepted).unwrap();
+        assert_eq!(json, "\"accepted\"");
+        assert_eq!(TransferStatus::Requested.to_string(), "Requested");
+    }
+}
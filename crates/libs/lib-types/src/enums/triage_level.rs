@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
 
+/// Triage acuity on the five-level Emergency Severity Index (ESI) scale,
+/// where the discriminant is the ESI level
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
 #[sqlx(type_name = "triage_level", rename_all = "snake_case")]
 #[serde(rename_all = "snake_case")]
@@ -9,6 +11,7 @@ pub enum TriageLevel {
     High = 2,
     Medium = 3,
     Low = 4,
+    NonUrgent = 5,
 }
 
 impl TriageLevel {
@@ -18,6 +21,34 @@ impl TriageLevel {
             TriageLevel::High => "High",
             TriageLevel::Medium => "Medium",
             TriageLevel::Low => "Low",
+            TriageLevel::NonUrgent => "Non-Urgent",
+        }
+    }
+
+    /// Get the ESI name of the level
+    pub fn esi_name(&self) -> &'static str {
+        match self {
+            TriageLevel::Critical => "Resuscitation",
+            TriageLevel::High => "Emergent",
+            TriageLevel::Medium => "Urgent",
+            TriageLevel::Low => "Less Urgent",
+            TriageLevel::NonUrgent => "Non-Urgent",
+        }
+    }
+
+    /// Get the ESI level, 1 (most acute) to 5
+    pub fn esi_level(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_esi_level(level: u8) -> Option<TriageLevel> {
+        match level {
+            1 => Some(TriageLevel::Critical),
+            2 => Some(TriageLevel::High),
+            3 => Some(TriageLevel::Medium),
+            4 => Some(TriageLevel::Low),
+            5 => Some(TriageLevel::NonUrgent),
+            _ => None,
         }
     }
 
@@ -27,6 +58,7 @@ impl TriageLevel {
             TriageLevel::High => "#f39c12",     // Orange
             TriageLevel::Medium => "#f1c40f",   // Yellow
             TriageLevel::Low => "#2ecc71",      // Green
+            TriageLevel::NonUrgent => "#3498db", // Blue
         }
     }
 
@@ -44,6 +76,7 @@ impl TriageLevel {
             TriageLevel::High,
             TriageLevel::Medium,
             TriageLevel::Low,
+            TriageLevel::NonUrgent,
         ]
     }
 }
@@ -66,5 +99,18 @@ mod tests {
         
         let deserialized: TriageLevel = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, level);
+
+        let json = serde_json::to_string(&TriageLevel::NonUrgent).unwrap();
+        assert_eq!(json, "\"non_urgent\"");
+    }
+
+    #[test]
+    fn test_esi_levels() {
+        for level in TriageLevel::all_in_priority_order() {
+            assert_eq!(TriageLevel::from_esi_level(level.esi_level()), Some(level));
+        }
+        assert_eq!(TriageLevel::Critical.esi_name(), "Resuscitation");
+        assert!(TriageLevel::Low < TriageLevel::NonUrgent);
+        assert_eq!(TriageLevel::from_esi_level(6), None);
     }
 }
\ No newline at end of file
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use lib_utils::units::{Kilograms, Pounds};
+
+/// Unit a weight was measured in. Weights are stored in kilograms.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightUnit {
+    #[default]
+    Kilograms,
+    Pounds,
+}
+
+impl WeightUnit {
+    /// Get the unit symbol
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            WeightUnit::Kilograms => Kilograms::SYMBOL,
+            WeightUnit::Pounds => Pounds::SYMBOL,
+        }
+    }
+
+    /// Convert a weight in this unit to kilograms
+    pub fn to_kilograms(&self, value: f32) -> Kilograms {
+        match self {
+            WeightUnit::Kilograms => Kilograms(value),
+            WeightUnit::Pounds => Pounds(value).into(),
+        }
+    }
+}
+
+impl std::fmt::Display for WeightUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_kilograms() {
+        assert_eq!(WeightUnit::Kilograms.to_kilograms(70.0), Kilograms(70.0));
+        assert!((WeightUnit::Pounds.to_kilograms(220.0).value() - 99.79).abs() < 0.01);
+    }
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::enums::AmbulanceStatus;
+use crate::ids::AmbulanceId;
+
+#[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AmbulanceError {
+    #[error("Ambulance not found: {ambulance_id}")]
+    NotFound { ambulance_id: AmbulanceId },
+
+    #[error("Ambulance call sign already in use: {call_sign}")]
+    CallSignTaken { call_sign: String },
+
+    #[error("Ambulance is not available for dispatch - status: {status}")]
+    NotAvailable { status: AmbulanceStatus },
+
+    #[error("Cannot update ambulance status from {current} to {requested}")]
+    InvalidStatusTransition {
+        current: AmbulanceStatus,
+        requested: AmbulanceStatus,
+    },
+
+    #[error("Ambulance has no crew assigned")]
+    NoCrewAssigned,
+
+    #[error("Invalid GPS position: {latitude}, {longitude}")]
+    InvalidLocation { latitude: f64, longitude: f64 },
+
+    #[error("Ambulance GPS position is stale - last update: {last_update}")]
+    StaleLocation { last_update: String },
+
+    #[error("Ambulance is not on a mission - status: {status}")]
+    NotOnMission { status: AmbulanceStatus },
+}
+
+impl AmbulanceError {
+    /// Get HTTP status code for this error
+    pub fn status_code(&self) -> u16 {
+        match self {
+            AmbulanceError::NotFound { .. } => 404,
+            AmbulanceError::CallSignTaken { .. } => 409,
+            AmbulanceError::NotAvailable { .. } => 409, // Conflict
+            AmbulanceError::InvalidStatusTransition { .. } => 422,
+            AmbulanceError::NoCrewAssigned => 422,
+            AmbulanceError::InvalidLocation { .. } => 400,
+            AmbulanceError::StaleLocation { .. } => 409,
+            AmbulanceError::NotOnMission { .. } => 409,
+        }
+    }
+
+    /// Get error code for client identification
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            AmbulanceError::NotFound { .. } => "AMBULANCE_NOT_FOUND",
+            AmbulanceError::CallSignTaken { .. } => "AMBULANCE_CALL_SIGN_TAKEN",
+            AmbulanceError::NotAvailable { .. } => "AMBULANCE_NOT_AVAILABLE",
+            AmbulanceError::InvalidStatusTransition { .. } => "AMBULANCE_INVALID_STATUS_TRANSITION",
+            AmbulanceError::NoCrewAssigned => "AMBULANCE_NO_CREW",
+            AmbulanceError::InvalidLocation { .. } => "AMBULANCE_INVALID_LOCATION",
+            AmbulanceError::StaleLocation { .. } => "AMBULANCE_STALE_LOCATION",
+            AmbulanceError::NotOnMission { .. } => "AMBULANCE_NOT_ON_MISSION",
+        }
+    }
+
+    /// Check if dispatch should be retried with another ambulance
+    pub fn is_dispatch_conflict(&self) -> bool {
+        matches!(
+            self,
+            AmbulanceError::NotAvailable { .. } | AmbulanceError::NoCrewAssigned
+        )
+    }
+
+    /// Get user-friendly message
+    pub fn user_message(&self) -> String {
+        match self {
+            AmbulanceError::NotFound { .. } => "Ambulance not found".to_string(),
+            AmbulanceError::NotAvailable { status } => {
+                format!(
+                    "Ambulance is currently {}. Please dispatch another unit",
+                    status.display_name().to_lowercase()
+                )
+            }
+            AmbulanceError::NoCrewAssigned => {
+                "Ambulance cannot be dispatched without a crew".to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ambulance_error_status_codes() {
+        assert_eq!(
+            AmbulanceError::NotFound {
+                ambulance_id: AmbulanceId::new()
+            }
+            .status_code(),
+            404
+        );
+        assert_eq!(
+            AmbulanceError::NotAvailable {
+                status: AmbulanceStatus::AtScene
+            }
+            .status_code(),
+            409
+        );
+        assert_eq!(
+            AmbulanceError::NoCrewAssigned.error_code(),
+            "AMBULANCE_NO_CREW"
+        );
+    }
+
+    #[test]
+    fn test_dispatch_conflicts() {
+        assert!(AmbulanceError::NoCrewAssigned.is_dispatch_conflict());
+        assert!(!AmbulanceError::InvalidLocation {
+            latitude: 91.0,
+            longitude: 55.3
+        }
+        .is_dispatch_conflict());
+    }
+
+    #[test]
+    fn test_user_messages() {
+        let error = AmbulanceError::NotAvailable {
+            status: AmbulanceStatus::Transporting,
+        };
+        assert!(error.user_message().contains("transporting"));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let error = AmbulanceError::InvalidStatusTransition {
+            current: AmbulanceStatus::Available,
+            requested: AmbulanceStatus::AtScene,
+        };
+        let json = serde_json::to_string(&error).unwrap();
+        let deserialized: AmbulanceError = serde_json::from_str(&json).unwrap();
+        assert_eq!(error, deserialized);
+    }
+}
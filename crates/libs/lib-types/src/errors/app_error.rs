@@ -1,7 +1,15 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use super::{AuthError, PatientError, HospitalError};
+use lib_utils::format::Humanizer;
+use lib_utils::retry::Retryable;
+
+use super::{AmbulanceError, AuthError, IncidentError, PatientError, HospitalError, ValidationErrors};
+use crate::enums::Locale;
+
+// Postgres SQLSTATEs of transactions that may succeed when retried
+const SERIALIZATION_FAILURE: &str = "40001";
+const DEADLOCK_DETECTED: &str = "40P01";
 
 #[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AppError {
@@ -14,11 +22,26 @@ pub enum AppError {
     #[error("Hospital management error: {0}")]
     Hospital(#[from] HospitalError),
 
+    #[error("Ambulance management error: {0}")]
+    Ambulance(#[from] AmbulanceError),
+
+    #[error("Incident management error: {0}")]
+    Incident(#[from] IncidentError),
+
     #[error("Database error: {message}")]
-    Database { message: String },
+    Database {
+        message: String,
+        #[serde(default)]
+        retryable: bool, // Transient, e.g. a serialization failure or dropped connection
+    },
 
     #[error("Validation error: {field} - {message}")]
-    Validation { field: String, message: String },
+    Validation {
+        field: String,
+        message: String,
+        #[serde(default)]
+        errors: ValidationErrors, // Per-field detail for the response body
+    },
 
     #[error("Configuration error: {message}")]
     Configuration { message: String },
@@ -41,6 +64,9 @@ pub enum AppError {
     #[error("Invalid request format: {message}")]
     BadRequest { message: String },
 
+    #[error("{resource} not found")]
+    NotFound { resource: String },
+
     #[error("Resource conflict: {message}")]
     Conflict { message: String },
 
@@ -58,6 +84,8 @@ impl AppError {
             AppError::Auth(auth_error) => auth_error.status_code(),
             AppError::Patient(patient_error) => patient_error.status_code(),
             AppError::Hospital(hospital_error) => hospital_error.status_code(),
+            AppError::Ambulance(ambulance_error) => ambulance_error.status_code(),
+            AppError::Incident(incident_error) => incident_error.status_code(),
             AppError::Database { .. } => 500,
             AppError::Validation { .. } => 400,
             AppError::Configuration { .. } => 500,
@@ -67,6 +95,7 @@ impl AppError {
             AppError::ServiceUnavailable => 503,
             AppError::Timeout => 504, // Gateway Timeout
             AppError::BadRequest { .. } => 400,
+            AppError::NotFound { .. } => 404,
             AppError::Conflict { .. } => 409,
             AppError::NotImplemented { .. } => 501,
             AppError::Maintenance => 503,
@@ -79,6 +108,8 @@ impl AppError {
             AppError::Auth(auth_error) => auth_error.error_code().to_string(),
             AppError::Patient(patient_error) => patient_error.error_code().to_string(),
             AppError::Hospital(hospital_error) => hospital_error.error_code().to_string(),
+            AppError::Ambulance(ambulance_error) => ambulance_error.error_code().to_string(),
+            AppError::Incident(incident_error) => incident_error.error_code().to_string(),
             AppError::Database { .. } => "DATABASE_ERROR".to_string(),
             AppError::Validation { .. } => "VALIDATION_ERROR".to_string(),
             AppError::Configuration { .. } => "CONFIGURATION_ERROR".to_string(),
@@ -88,6 +119,7 @@ impl AppError {
             AppError::ServiceUnavailable => "SERVICE_UNAVAILABLE".to_string(),
             AppError::Timeout => "REQUEST_TIMEOUT".to_string(),
             AppError::BadRequest { .. } => "BAD_REQUEST".to_string(),
+            AppError::NotFound { .. } => "NOT_FOUND".to_string(),
             AppError::Conflict { .. } => "RESOURCE_CONFLICT".to_string(),
             AppError::NotImplemented { .. } => "NOT_IMPLEMENTED".to_string(),
             AppError::Maintenance => "SYSTEM_MAINTENANCE".to_string(),
@@ -101,7 +133,10 @@ impl AppError {
             AppError::ServiceUnavailable
                 | AppError::Timeout
                 | AppError::ExternalService { .. }
-                | AppError::Database { .. }
+                | AppError::Database {
+                    retryable: true,
+                    ..
+                }
         )
     }
 
@@ -122,11 +157,16 @@ impl AppError {
             AppError::Auth(auth_error) => auth_error.user_message(),
             AppError::Patient(patient_error) => patient_error.user_message(),
             AppError::Hospital(hospital_error) => hospital_error.user_message(),
-            AppError::Validation { field, message } => {
+            AppError::Ambulance(ambulance_error) => ambulance_error.user_message(),
+            AppError::Incident(incident_error) => incident_error.user_message(),
+            AppError::Validation { field, message, .. } => {
                 format!("Invalid {}: {}", field, message)
             }
             AppError::RateLimit { retry_after } => {
-                format!("Too many requests. Please try again in {} seconds", retry_after)
+                format!(
+                    "Too many requests. Please try again {}",
+                    retry_in(*retry_after, Locale::En)
+                )
             }
             AppError::ServiceUnavailable => {
                 "Service is temporarily unavailable. Please try again later".to_string()
@@ -143,11 +183,65 @@ impl AppError {
         }
     }
 
+    /// Get the user-friendly message in the given language
+    pub fn localized_message(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.user_message(),
+            Locale::Ar => self.arabic_message(),
+        }
+    }
+
+    fn arabic_message(&self) -> String {
+        match self {
+            AppError::Auth(auth_error) => auth_error.localized_message(Locale::Ar),
+            AppError::Patient(patient_error) => patient_error.localized_message(Locale::Ar),
+            AppError::Hospital(hospital_error) => hospital_error.localized_message(Locale::Ar),
+            // Not translated yet
+            AppError::Ambulance(ambulance_error) => ambulance_error.user_message(),
+            AppError::Incident(incident_error) => incident_error.user_message(),
+            AppError::Database { .. } => {
+                "حدث خطأ في قاعدة البيانات. يرجى المحاولة لاحقاً".to_string()
+            }
+            AppError::Validation { field, message, .. } => {
+                format!("قيمة غير صالحة في {}: {}", field, message)
+            }
+            AppError::Configuration { .. } => "خطأ في إعدادات النظام".to_string(),
+            AppError::ExternalService { service, .. } => {
+                format!("تعذر الاتصال بالخدمة الخارجية: {}", service)
+            }
+            AppError::RateLimit { retry_after } => {
+                format!(
+                    "طلبات كثيرة جداً. يرجى المحاولة {}",
+                    retry_in(*retry_after, Locale::Ar)
+                )
+            }
+            AppError::Internal => {
+                "حدث خطأ غير متوقع. يرجى التواصل مع الدعم إذا استمرت المشكلة".to_string()
+            }
+            AppError::ServiceUnavailable => {
+                "الخدمة غير متاحة مؤقتاً. يرجى المحاولة لاحقاً".to_string()
+            }
+            AppError::Timeout => "انتهت مهلة الطلب. يرجى المحاولة مرة أخرى".to_string(),
+            AppError::BadRequest { message } => format!("صيغة الطلب غير صالحة: {}", message),
+            AppError::NotFound { resource } => format!("لم يتم العثور على {}", resource),
+            AppError::Conflict { message } => format!("تعارض في البيانات: {}", message),
+            AppError::NotImplemented { feature } => {
+                format!("الميزة غير متوفرة بعد: {}", feature)
+            }
+            AppError::Maintenance => "النظام قيد الصيانة. يرجى المحاولة لاحقاً".to_string(),
+        }
+    }
+
     /// Create validation error
     pub fn validation_error(field: impl Into<String>, message: impl Into<String>) -> Self {
+        let field = field.into();
+        let message = message.into();
+        let mut errors = ValidationErrors::new();
+        errors.add(field.clone(), "invalid", message.clone());
         Self::Validation {
-            field: field.into(),
-            message: message.into(),
+            field,
+            message,
+            errors,
         }
     }
 
@@ -155,6 +249,22 @@ impl AppError {
     pub fn database_error(message: impl Into<String>) -> Self {
         Self::Database {
             message: message.into(),
+            retryable: false,
+        }
+    }
+
+    /// Map a database error, reporting a missing row as `resource` not found
+    pub fn from_sqlx(error: sqlx::Error, resource: impl Into<String>) -> Self {
+        match error {
+            sqlx::Error::RowNotFound => Self::not_found(resource),
+            error => error.into(),
+        }
+    }
+
+    /// Create not found error
+    pub fn not_found(resource: impl Into<String>) -> Self {
+        Self::NotFound {
+            resource: resource.into(),
         }
     }
 
@@ -167,24 +277,128 @@ impl AppError {
     }
 }
 
+/// When to retry a rate-limited request, e.g. `in 2 minutes`
+fn retry_in(retry_after: u64, locale: Locale) -> String {
+    Humanizer::new(locale).relative(chrono::Duration::seconds(retry_after as i64))
+}
+
+impl Retryable for AppError {
+    fn is_retryable(&self) -> bool {
+        AppError::is_retryable(self)
+    }
+
+    fn timed_out(_after: std::time::Duration) -> Self {
+        AppError::Timeout
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        let first_field = errors.iter().next().map(|error| error.field.clone());
+        let field = match first_field {
+            Some(field) if errors.iter().all(|error| error.field == field) => field,
+            _ => "request".to_string(),
+        };
+        Self::Validation {
+            field,
+            message: errors.to_string(),
+            errors,
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::RowNotFound => Self::not_found("Record"),
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed => Self::ServiceUnavailable,
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => Self::Conflict {
+                message: "Record already exists".to_string(),
+            },
+            sqlx::Error::Database(db_error)
+                if matches!(
+                    db_error.code().as_deref(),
+                    Some(SERIALIZATION_FAILURE | DEADLOCK_DETECTED)
+                ) =>
+            {
+                Self::Database {
+                    message: error.to_string(),
+                    retryable: true,
+                }
+            }
+            sqlx::Error::Io(_) => Self::Database {
+                message: error.to_string(),
+                retryable: true,
+            },
+            _ => Self::database_error(error.to_string()),
+        }
+    }
+}
+
 /// API Error Response structure for JSON responses
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApiErrorResponse {
     pub error: String,
     pub error_code: String,
-    pub message: String,
+    pub message: String, // In the requested language
+    pub locale: Locale,
+    pub messages: LocalizedMessages, // Both languages, for bilingual UIs
+    #[serde(default, skip_serializing_if = "ValidationErrors::is_empty")]
+    pub errors: ValidationErrors, // Rejected fields of a validation error
     pub details: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>, // Matches the request's server logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>, // Identifies this request among those of a correlation
     pub timestamp: String,
 }
 
+/// A user-facing message in every supported language
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalizedMessages {
+    pub en: String,
+    pub ar: String,
+}
+
+impl LocalizedMessages {
+    pub fn from_app_error(error: &AppError) -> Self {
+        Self {
+            en: error.localized_message(Locale::En),
+            ar: error.localized_message(Locale::Ar),
+        }
+    }
+
+    pub fn get(&self, locale: Locale) -> &str {
+        match locale {
+            Locale::En => &self.en,
+            Locale::Ar => &self.ar,
+        }
+    }
+}
+
 impl ApiErrorResponse {
-    /// Create from AppError
+    /// Create from AppError, with the message in English
     pub fn from_app_error(error: &AppError) -> Self {
+        Self::from_app_error_localized(error, Locale::default())
+    }
+
+    /// Create from AppError, with the message in the given language
+    pub fn from_app_error_localized(error: &AppError, locale: Locale) -> Self {
+        let messages = LocalizedMessages::from_app_error(error);
+        let errors = match error {
+            AppError::Validation { errors, .. } => errors.clone(),
+            _ => ValidationErrors::new(),
+        };
         Self {
             error: error.to_string(),
             error_code: error.error_code(),
-            message: error.user_message(),
+            message: messages.get(locale).to_string(),
+            locale,
+            messages,
+            errors,
             details: None,
+            correlation_id: None,
+            request_id: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
     }
@@ -194,6 +408,18 @@ impl ApiErrorResponse {
         self.details = Some(details);
         self
     }
+
+    /// Add the correlation id of the failed request
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Add the id of the failed request
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
 }
 
 #[cfg(test)]
@@ -209,6 +435,7 @@ mod tests {
         assert_eq!(validation_error.status_code(), 400);
 
         assert_eq!(AppError::RateLimit { retry_after: 60 }.status_code(), 429);
+        assert_eq!(AppError::not_found("Session").status_code(), 404);
     }
 
     #[test]
@@ -225,6 +452,27 @@ mod tests {
         assert!(AppError::ServiceUnavailable.is_retryable());
         assert!(AppError::Timeout.is_retryable());
         assert!(!AppError::Auth(AuthError::InvalidCredentials).is_retryable());
+        assert!(!AppError::database_error("syntax error").is_retryable());
+    }
+
+    #[test]
+    fn test_sqlx_error_mapping() {
+        assert_eq!(
+            AppError::from(sqlx::Error::RowNotFound),
+            AppError::not_found("Record")
+        );
+        assert_eq!(
+            AppError::from_sqlx(sqlx::Error::RowNotFound, "Patient"),
+            AppError::not_found("Patient")
+        );
+        assert_eq!(
+            AppError::from(sqlx::Error::PoolTimedOut),
+            AppError::ServiceUnavailable
+        );
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        assert!(AppError::from(sqlx::Error::Io(io_error)).is_retryable());
+        assert!(!AppError::from(sqlx::Error::ColumnNotFound("age".to_string())).is_retryable());
     }
 
     #[test]
@@ -251,6 +499,47 @@ mod tests {
         assert_eq!(response.error_code, "VALIDATION_ERROR");
         assert!(response.message.contains("Invalid username"));
         assert!(!response.timestamp.is_empty());
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("correlation_id").is_none());
+        let json = serde_json::to_value(response.with_correlation_id("ambulance-7f3a")).unwrap();
+        assert_eq!(json["correlation_id"], "ambulance-7f3a");
+        assert!(json.get("request_id").is_none());
+    }
+
+    #[test]
+    fn test_validation_errors_conversion() {
+        let mut errors = ValidationErrors::new();
+        errors.add("first_name", "required", "First name is required");
+        errors.add("age", "out_of_range", "Age must be between 0 and 150");
+
+        let error: AppError = errors.into();
+        assert_eq!(error.status_code(), 400);
+        assert!(error.user_message().contains("First name is required; Age"));
+
+        let response = ApiErrorResponse::from_app_error(&error);
+        assert_eq!(response.errors.len(), 2);
+        assert_eq!(response.errors[1].field, "age");
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["errors"][0]["code"], "required");
+        let json = serde_json::to_value(ApiErrorResponse::from_app_error(&AppError::Internal)).unwrap();
+        assert!(json.get("errors").is_none());
+    }
+
+    #[test]
+    fn test_localized_response() {
+        let error = AppError::Auth(AuthError::TokenExpired);
+        let response = ApiErrorResponse::from_app_error_localized(&error, Locale::Ar);
+
+        assert_eq!(response.locale, Locale::Ar);
+        assert_eq!(response.message, response.messages.ar);
+        assert!(response.messages.en.contains("session has expired"));
+        assert_ne!(response.messages.ar, response.messages.en);
+
+        let error = AppError::RateLimit { retry_after: 30 };
+        assert!(error.localized_message(Locale::Ar).contains("30"));
+        assert_eq!(error.localized_message(Locale::En), error.user_message());
     }
 
     #[test]
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use uuid::Uuid;
+
+use crate::enums::Locale;
+use crate::ids::HospitalId;
 
 #[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AuthError {
@@ -26,7 +28,7 @@ pub enum AuthError {
     InsufficientPermissions,
 
     #[error("User is not assigned to hospital: {hospital_id}")]
-    HospitalAccessDenied { hospital_id: Uuid },
+    HospitalAccessDenied { hospital_id: HospitalId },
 
     #[error("Password does not meet security requirements: {reason}")]
     WeakPassword { reason: String },
@@ -45,6 +47,12 @@ pub enum AuthError {
 
     #[error("Password reset required")]
     PasswordResetRequired,
+
+    #[error("Re-authentication required within the last {max_age_seconds} seconds")]
+    ReauthenticationRequired { max_age_seconds: u64 },
+
+    #[error("Scope not granted to this client: {scope}")]
+    InvalidScope { scope: String },
 }
 
 impl AuthError {
@@ -65,6 +73,8 @@ impl AuthError {
             AuthError::MfaRequired => 428, // Precondition Required
             AuthError::InvalidMfaCode => 400,
             AuthError::PasswordResetRequired => 428,
+            AuthError::ReauthenticationRequired { .. } => 428,
+            AuthError::InvalidScope { .. } => 400,
         }
     }
 
@@ -85,6 +95,8 @@ impl AuthError {
             AuthError::MfaRequired => "AUTH_MFA_REQUIRED",
             AuthError::InvalidMfaCode => "AUTH_INVALID_MFA_CODE",
             AuthError::PasswordResetRequired => "AUTH_PASSWORD_RESET_REQUIRED",
+            AuthError::ReauthenticationRequired { .. } => "AUTH_REAUTHENTICATION_REQUIRED",
+            AuthError::InvalidScope { .. } => "AUTH_INVALID_SCOPE",
         }
     }
 
@@ -119,9 +131,58 @@ impl AuthError {
             AuthError::MfaRequired => {
                 "Multi-factor authentication is required to continue".to_string()
             }
+            AuthError::ReauthenticationRequired { .. } => {
+                "Please confirm your password to continue".to_string()
+            }
             _ => self.to_string(),
         }
     }
+
+    /// Get the user-friendly message in the given language
+    pub fn localized_message(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.user_message(),
+            Locale::Ar => self.arabic_message(),
+        }
+    }
+
+    fn arabic_message(&self) -> String {
+        match self {
+            AuthError::InvalidCredentials | AuthError::UserNotFound { .. } => {
+                "اسم المستخدم أو كلمة المرور غير صحيحة".to_string()
+            }
+            AuthError::AccountDisabled { .. } => {
+                "تم تعطيل حسابك. يرجى التواصل مع المسؤول".to_string()
+            }
+            AuthError::InvalidToken => "رمز الدخول غير صالح أو منتهي الصلاحية".to_string(),
+            AuthError::TokenExpired => {
+                "انتهت صلاحية جلستك. يرجى تسجيل الدخول مرة أخرى".to_string()
+            }
+            AuthError::MissingToken => "رمز المصادقة مفقود".to_string(),
+            AuthError::InsufficientPermissions => {
+                "ليست لديك صلاحية لتنفيذ هذه العملية".to_string()
+            }
+            AuthError::HospitalAccessDenied { .. } => "لست مُعيّناً لهذا المستشفى".to_string(),
+            AuthError::WeakPassword { reason } => {
+                format!("كلمة المرور لا تستوفي المتطلبات: {}", reason)
+            }
+            AuthError::AccountLocked => {
+                "الحساب مقفل مؤقتاً بسبب محاولات تسجيل دخول فاشلة متعددة".to_string()
+            }
+            AuthError::SessionTerminated => {
+                "تم إنهاء الجلسة. يرجى تسجيل الدخول مرة أخرى".to_string()
+            }
+            AuthError::MfaRequired => "المصادقة متعددة العوامل مطلوبة للمتابعة".to_string(),
+            AuthError::InvalidMfaCode => "رمز المصادقة متعددة العوامل غير صحيح".to_string(),
+            AuthError::PasswordResetRequired => "يجب إعادة تعيين كلمة المرور".to_string(),
+            AuthError::ReauthenticationRequired { .. } => {
+                "يرجى تأكيد كلمة المرور للمتابعة".to_string()
+            }
+            AuthError::InvalidScope { scope } => {
+                format!("النطاق غير ممنوح لهذا العميل: {}", scope)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -134,6 +195,10 @@ mod tests {
         assert_eq!(AuthError::InsufficientPermissions.status_code(), 403);
         assert_eq!(AuthError::AccountLocked.status_code(), 423);
         assert_eq!(AuthError::MfaRequired.status_code(), 428);
+        assert_eq!(
+            AuthError::ReauthenticationRequired { max_age_seconds: 300 }.status_code(),
+            428
+        );
     }
 
     #[test]
@@ -171,7 +236,7 @@ mod tests {
     #[test]
     fn test_serialization() {
         let error = AuthError::HospitalAccessDenied {
-            hospital_id: Uuid::new_v4(),
+            hospital_id: HospitalId::new(),
         };
         let json = serde_json::to_string(&error).unwrap();
         let deserialized: AuthError = serde_json::from_str(&json).unwrap();
@@ -1,11 +1,13 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use uuid::Uuid;
+
+use crate::enums::{BedStatus, Locale};
+use crate::ids::{BedId, HospitalId, PatientId};
 
 #[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
 pub enum HospitalError {
     #[error("Hospital not found: {hospital_id}")]
-    NotFound { hospital_id: Uuid },
+    NotFound { hospital_id: HospitalId },
 
     #[error("Hospital is at full capacity - no beds available")]
     AtCapacity,
@@ -17,14 +19,20 @@ pub enum HospitalError {
     SpecialtyNotAvailable { specialty: String },
 
     #[error("Bed not found: {bed_id}")]
-    BedNotFound { bed_id: Uuid },
+    BedNotFound { bed_id: BedId },
 
     #[error("Bed is already occupied by patient: {patient_id}")]
-    BedOccupied { patient_id: Uuid },
+    BedOccupied { patient_id: PatientId },
 
     #[error("Invalid bed type for patient triage level")]
     IncompatibleBedType,
 
+    #[error("Cannot update bed status from {current} to {requested}")]
+    InvalidBedStatusTransition {
+        current: BedStatus,
+        requested: BedStatus,
+    },
+
     #[error("Equipment not available: {equipment_type}")]
     EquipmentNotAvailable { equipment_type: String },
 
@@ -61,6 +69,7 @@ impl HospitalError {
             HospitalError::BedNotFound { .. } => 404,
             HospitalError::BedOccupied { .. } => 409, // Conflict
             HospitalError::IncompatibleBedType => 422,
+            HospitalError::InvalidBedStatusTransition { .. } => 422,
             HospitalError::EquipmentNotAvailable { .. } => 503,
             HospitalError::NetworkCommunicationFailed { .. } => 502, // Bad Gateway
             HospitalError::StaleCapacityData { .. } => 409,
@@ -82,6 +91,7 @@ impl HospitalError {
             HospitalError::BedNotFound { .. } => "BED_NOT_FOUND",
             HospitalError::BedOccupied { .. } => "BED_OCCUPIED",
             HospitalError::IncompatibleBedType => "INCOMPATIBLE_BED_TYPE",
+            HospitalError::InvalidBedStatusTransition { .. } => "BED_INVALID_STATUS_TRANSITION",
             HospitalError::EquipmentNotAvailable { .. } => "EQUIPMENT_NOT_AVAILABLE",
             HospitalError::NetworkCommunicationFailed { .. } => "NETWORK_COMMUNICATION_FAILED",
             HospitalError::StaleCapacityData { .. } => "STALE_CAPACITY_DATA",
@@ -138,6 +148,61 @@ impl HospitalError {
             _ => self.to_string(),
         }
     }
+
+    /// Get the user-friendly message in the given language
+    pub fn localized_message(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.user_message(),
+            Locale::Ar => self.arabic_message(),
+        }
+    }
+
+    fn arabic_message(&self) -> String {
+        match self {
+            HospitalError::NotFound { .. } => "لم يتم العثور على المستشفى".to_string(),
+            HospitalError::AtCapacity => {
+                "المستشفى ممتلئ بالكامل. يرجى تجربة مستشفى آخر".to_string()
+            }
+            HospitalError::NotAcceptingPatients { status } => {
+                format!("المستشفى لا يستقبل مرضى حالياً ({})", status)
+            }
+            HospitalError::SpecialtyNotAvailable { specialty } => {
+                format!("لا تتوفر خدمات {} في هذا المستشفى", specialty)
+            }
+            HospitalError::BedNotFound { .. } => "لم يتم العثور على السرير".to_string(),
+            HospitalError::BedOccupied { .. } => "السرير المحدد مشغول بالفعل".to_string(),
+            HospitalError::IncompatibleBedType => {
+                "نوع السرير غير مناسب لمستوى فرز المريض".to_string()
+            }
+            HospitalError::InvalidBedStatusTransition { current, requested } => {
+                format!("لا يمكن تغيير حالة السرير من {} إلى {}", current, requested)
+            }
+            HospitalError::EquipmentNotAvailable { equipment_type } => {
+                format!("{} غير متوفر حالياً", equipment_type)
+            }
+            HospitalError::NetworkCommunicationFailed { .. } => {
+                "تعذر الاتصال بشبكة المستشفيات".to_string()
+            }
+            HospitalError::StaleCapacityData { last_update } => {
+                format!("بيانات السعة قديمة - آخر تحديث: {}", last_update)
+            }
+            HospitalError::InvalidCapacityUpdate { requested } => {
+                format!("لا يمكن تحديث سعة المستشفى - عدد أسرة غير صالح: {}", requested)
+            }
+            HospitalError::UnderMaintenance => {
+                "المستشفى تحت الصيانة - حالات الطوارئ فقط".to_string()
+            }
+            HospitalError::TransferProtocolViolation { reason } => {
+                format!("مخالفة لبروتوكول النقل: {}", reason)
+            }
+            HospitalError::LicenseValidationFailed => {
+                "فشل التحقق من ترخيص المستشفى".to_string()
+            }
+            HospitalError::RegionalRestrictions => {
+                "تنطبق قيود إقليمية على هذا المستشفى".to_string()
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -148,7 +213,7 @@ mod tests {
     fn test_hospital_error_status_codes() {
         assert_eq!(
             HospitalError::NotFound {
-                hospital_id: Uuid::new_v4()
+                hospital_id: HospitalId::new()
             }
             .status_code(),
             404
@@ -179,7 +244,7 @@ mod tests {
     fn test_capacity_issues() {
         assert!(HospitalError::AtCapacity.is_capacity_issue());
         assert!(HospitalError::BedOccupied {
-            patient_id: Uuid::new_v4()
+            patient_id: PatientId::new()
         }
         .is_capacity_issue());
         assert!(!HospitalError::LicenseValidationFailed.is_capacity_issue());
@@ -203,7 +268,7 @@ mod tests {
     #[test]
     fn test_serialization() {
         let error = HospitalError::BedOccupied {
-            patient_id: Uuid::new_v4(),
+            patient_id: PatientId::new(),
         };
         let json = serde_json::to_string(&error).unwrap();
         let deserialized: HospitalError = serde_json::from_str(&json).unwrap();
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::enums::IncidentStatus;
+use crate::ids::UserId;
+
+#[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IncidentError {
+    #[error("Incident not found: {incident_id}")]
+    NotFound { incident_id: Uuid },
+
+    #[error("Incident is closed: {incident_id}")]
+    Closed { incident_id: Uuid },
+
+    #[error("Cannot update incident status from {current} to {requested}")]
+    InvalidStatusTransition {
+        current: IncidentStatus,
+        requested: IncidentStatus,
+    },
+
+    #[error("Incident commander is not an active staff member: {user_id}")]
+    InvalidCommander { user_id: UserId },
+
+    #[error("Invalid GPS position: {latitude}, {longitude}")]
+    InvalidLocation { latitude: f64, longitude: f64 },
+}
+
+impl IncidentError {
+    /// Get HTTP status code for this error
+    pub fn status_code(&self) -> u16 {
+        match self {
+            IncidentError::NotFound { .. } => 404,
+            IncidentError::Closed { .. } => 409, // Conflict
+            IncidentError::InvalidStatusTransition { .. } => 422,
+            IncidentError::InvalidCommander { .. } => 422,
+            IncidentError::InvalidLocation { .. } => 400,
+        }
+    }
+
+    /// Get error code for client identification
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            IncidentError::NotFound { .. } => "INCIDENT_NOT_FOUND",
+            IncidentError::Closed { .. } => "INCIDENT_CLOSED",
+            IncidentError::InvalidStatusTransition { .. } => "INCIDENT_INVALID_STATUS_TRANSITION",
+            IncidentError::InvalidCommander { .. } => "INCIDENT_INVALID_COMMANDER",
+            IncidentError::InvalidLocation { .. } => "INCIDENT_INVALID_LOCATION",
+        }
+    }
+
+    /// Get user-friendly message
+    pub fn user_message(&self) -> String {
+        match self {
+            IncidentError::NotFound { .. } => "Incident not found".to_string(),
+            IncidentError::Closed { .. } => {
+                "Incident has been closed and can no longer be changed".to_string()
+            }
+            IncidentError::InvalidCommander { .. } => {
+                "Incident commander must be an active staff member".to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incident_error_status_codes() {
+        let incident_id = Uuid::new_v4();
+        assert_eq!(IncidentError::NotFound { incident_id }.status_code(), 404);
+        assert_eq!(IncidentError::Closed { incident_id }.status_code(), 409);
+        assert_eq!(
+            IncidentError::Closed { incident_id }.error_code(),
+            "INCIDENT_CLOSED"
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let error = IncidentError::InvalidStatusTransition {
+            current: IncidentStatus::Closed,
+            requested: IncidentStatus::Active,
+        };
+        let json = serde_json::to_string(&error).unwrap();
+        let deserialized: IncidentError = serde_json::from_str(&json).unwrap();
+        assert_eq!(error, deserialized);
+    }
+}
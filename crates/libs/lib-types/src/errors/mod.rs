@@ -2,10 +2,15 @@
 pub mod auth_error;
 pub mod patient_error;
 pub mod hospital_error;
+pub mod ambulance_error;
+pub mod incident_error;
 pub mod app_error;
 
 // Re-exports for convenience
 pub use auth_error::AuthError;
 pub use patient_error::PatientError;
 pub use hospital_error::HospitalError;
-pub use app_error::{AppError, ApiErrorResponse};
\ No newline at end of file
+pub use ambulance_error::AmbulanceError;
+pub use incident_error::IncidentError;
+pub use app_error::{AppError, ApiErrorResponse, LocalizedMessages};
+pub use lib_utils::validation::{FieldError, Validate, ValidationErrors};
\ No newline at end of file
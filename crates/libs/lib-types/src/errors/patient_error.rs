@@ -2,16 +2,25 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::enums::{PatientStatus, TriageLevel};
+use lib_utils::validation::mask_emirates_id;
+
+use crate::enums::{IdentifierScheme, Locale, PatientStatus, TriageLevel};
+use crate::ids::{BedId, HospitalId, PatientId};
 
 #[derive(Debug, Error, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PatientError {
     #[error("Patient not found: {patient_id}")]
-    NotFound { patient_id: Uuid },
+    NotFound { patient_id: PatientId },
 
-    #[error("Patient already exists with ID: {national_id}")]
+    #[error("Patient already exists with ID: {}", mask_emirates_id(national_id))]
     AlreadyExists { national_id: String },
 
+    #[error("Another patient already has this {scheme}")]
+    DuplicateIdentifier { scheme: IdentifierScheme },
+
+    #[error("No patient found with this {scheme}")]
+    IdentifierNotFound { scheme: IdentifierScheme },
+
     #[error("Invalid patient data: {field} - {reason}")]
     InvalidData { field: String, reason: String },
 
@@ -22,7 +31,7 @@ pub enum PatientError {
     },
 
     #[error("Patient is not assigned to this hospital: {hospital_id}")]
-    HospitalMismatch { hospital_id: Uuid },
+    HospitalMismatch { hospital_id: HospitalId },
 
     #[error("Patient is already assigned to staff member: {staff_id}")]
     AlreadyAssigned { staff_id: Uuid },
@@ -31,7 +40,7 @@ pub enum PatientError {
     StaffNotAvailable { staff_id: Uuid },
 
     #[error("Patient bed assignment failed - bed not available: {bed_id}")]
-    BedNotAvailable { bed_id: Uuid },
+    BedNotAvailable { bed_id: BedId },
 
     #[error("Triage level change not permitted: {from} to {to} - requires senior staff approval")]
     TriageChangeNotPermitted { from: TriageLevel, to: TriageLevel },
@@ -67,6 +76,8 @@ impl PatientError {
         match self {
             PatientError::NotFound { .. } => 404,
             PatientError::AlreadyExists { .. } => 409, // Conflict
+            PatientError::DuplicateIdentifier { .. } => 409,
+            PatientError::IdentifierNotFound { .. } => 404,
             PatientError::InvalidData { .. } => 400,
             PatientError::InvalidStatusTransition { .. } => 422, // Unprocessable Entity
             PatientError::HospitalMismatch { .. } => 403,
@@ -90,6 +101,8 @@ impl PatientError {
         match self {
             PatientError::NotFound { .. } => "PATIENT_NOT_FOUND",
             PatientError::AlreadyExists { .. } => "PATIENT_ALREADY_EXISTS",
+            PatientError::DuplicateIdentifier { .. } => "PATIENT_DUPLICATE_IDENTIFIER",
+            PatientError::IdentifierNotFound { .. } => "PATIENT_IDENTIFIER_NOT_FOUND",
             PatientError::InvalidData { .. } => "PATIENT_INVALID_DATA",
             PatientError::InvalidStatusTransition { .. } => "PATIENT_INVALID_STATUS_TRANSITION",
             PatientError::HospitalMismatch { .. } => "PATIENT_HOSPITAL_MISMATCH",
@@ -144,6 +157,75 @@ impl PatientError {
             _ => self.to_string(),
         }
     }
+
+    /// Get the user-friendly message in the given language
+    pub fn localized_message(&self, locale: Locale) -> String {
+        match locale {
+            Locale::En => self.user_message(),
+            Locale::Ar => self.arabic_message(),
+        }
+    }
+
+    fn arabic_message(&self) -> String {
+        match self {
+            PatientError::NotFound { .. } => "لم يتم العثور على سجل المريض".to_string(),
+            PatientError::AlreadyExists { .. } => {
+                "يوجد مريض مسجل بنفس رقم الهوية".to_string()
+            }
+            PatientError::DuplicateIdentifier { .. } => {
+                "يوجد مريض آخر مسجل بنفس المعرّف".to_string()
+            }
+            PatientError::IdentifierNotFound { .. } => {
+                "لم يتم العثور على مريض بهذا المعرّف".to_string()
+            }
+            PatientError::InvalidData { field, reason } => {
+                format!("بيانات المريض غير صالحة: {} - {}", field, reason)
+            }
+            PatientError::InvalidStatusTransition { current, requested } => {
+                format!("لا يمكن تغيير حالة المريض من {} إلى {}", current, requested)
+            }
+            PatientError::HospitalMismatch { .. } => {
+                "المريض غير مسجل في هذا المستشفى".to_string()
+            }
+            PatientError::AlreadyAssigned { .. } => {
+                "المريض مُعيّن بالفعل لأحد أفراد الطاقم".to_string()
+            }
+            PatientError::StaffNotAvailable { .. } => {
+                "عضو الطاقم المحدد غير متاح للتكليف".to_string()
+            }
+            PatientError::BedNotAvailable { .. } => "السرير المحدد غير متاح".to_string(),
+            PatientError::TriageChangeNotPermitted { from, to } => {
+                format!(
+                    "تغيير مستوى الفرز من {} إلى {} يتطلب موافقة طاقم أقدم",
+                    from, to
+                )
+            }
+            PatientError::CriticalConditionDischarge => {
+                "لا يمكن خروج المريض وهو في حالة حرجة".to_string()
+            }
+            PatientError::UnpaidBillsDischarge => {
+                "لا يمكن خروج المريض لوجود فواتير غير مدفوعة".to_string()
+            }
+            PatientError::InvalidVitalSigns => {
+                "العلامات الحيوية للمريض غير مكتملة أو غير صالحة".to_string()
+            }
+            PatientError::MinorConsentRequired => {
+                "موافقة ولي الأمر مطلوبة للمرضى دون 18 عاماً".to_string()
+            }
+            PatientError::AllergyConflict { medication } => {
+                format!("المريض لديه حساسية من {}", medication)
+            }
+            PatientError::IncompleteHistory => {
+                "التاريخ الطبي للمريض غير مكتمل لهذا الإجراء".to_string()
+            }
+            PatientError::TransferFailed { reason } => {
+                format!("فشل نقل المريض: {}", reason)
+            }
+            PatientError::EmergencyContactRequired => {
+                "معلومات جهة اتصال الطوارئ مطلوبة للمرضى في حالة حرجة".to_string()
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -154,7 +236,7 @@ mod tests {
     fn test_patient_error_status_codes() {
         assert_eq!(
             PatientError::NotFound {
-                patient_id: Uuid::new_v4()
+                patient_id: PatientId::new()
             }
             .status_code(),
             404
@@ -173,7 +255,7 @@ mod tests {
     fn test_patient_error_codes() {
         assert_eq!(
             PatientError::NotFound {
-                patient_id: Uuid::new_v4()
+                patient_id: PatientId::new()
             }
             .error_code(),
             "PATIENT_NOT_FOUND"
@@ -196,7 +278,7 @@ mod tests {
         }
         .is_critical());
         assert!(!PatientError::NotFound {
-            patient_id: Uuid::new_v4()
+            patient_id: PatientId::new()
         }
         .is_critical());
     }
@@ -0,0 +1,110 @@
+//! Typed identifiers, so an id of one kind of record cannot be passed where
+//! another is expected. They serialize and are stored as plain UUIDs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+macro_rules! define_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+            sqlx::Type,
+        )]
+        #[serde(transparent)]
+        #[sqlx(transparent)]
+        pub struct $name(Uuid);
+
+        impl $name {
+            /// Generate a new random id
+            pub fn new() -> Self {
+                Self(Uuid::new_v4())
+            }
+
+            pub const fn from_uuid(uuid: Uuid) -> Self {
+                Self(uuid)
+            }
+
+            pub const fn as_uuid(&self) -> Uuid {
+                self.0
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl From<Uuid> for $name {
+            fn from(uuid: Uuid) -> Self {
+                Self(uuid)
+            }
+        }
+
+        impl From<$name> for Uuid {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = uuid::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Uuid::parse_str(s).map(Self)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    };
+}
+
+define_id!(
+    /// Id of a `Patient`
+    PatientId
+);
+define_id!(
+    /// Id of a `Hospital`
+    HospitalId
+);
+define_id!(
+    /// Id of a `User`
+    UserId
+);
+define_id!(
+    /// Id of a `Bed`
+    BedId
+);
+define_id!(
+    /// Id of an `Ambulance`
+    AmbulanceId
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_as_uuid() {
+        let uuid = Uuid::new_v4();
+        let id = PatientId::from(uuid);
+        assert_eq!(
+            serde_json::to_value(id).unwrap(),
+            serde_json::json!(uuid.to_string())
+        );
+        assert_eq!(
+            serde_json::from_value::<PatientId>(serde_json::json!(uuid.to_string())).unwrap(),
+            id
+        );
+        assert_eq!(id.to_string().parse::<PatientId>().unwrap(), id);
+        assert_eq!(Uuid::from(id), uuid);
+    }
+}
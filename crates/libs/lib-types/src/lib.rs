@@ -4,9 +4,14 @@ pub mod entities;
 pub mod dtos;
 pub mod enums;
 pub mod errors;
+pub mod ids;
 
 // Re-exports for convenience
 pub use entities::*;
 pub use dtos::*;
 pub use enums::*;
 pub use errors::*;
+pub use ids::*;
+
+// Entity and DTO modules share some names; at the crate root they mean the entity module
+pub use entities::{ambulance, attachment, consent, hospital, patient};
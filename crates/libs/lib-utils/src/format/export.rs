@@ -0,0 +1,308 @@
+//! Writers for data exports: CSV for spreadsheets and NDJSON (one JSON
+//! value per line) for other systems. Both write a record at a time, so an
+//! export can be streamed out page by page instead of built in memory.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use serde::{Deserialize, Serialize};
+
+/// UTF-8 byte order mark. Without it Excel reads a CSV file in the local
+/// code page, which garbles Arabic names.
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Formats data can be exported in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+
+    /// A writer for this format. `bom` only applies to CSV.
+    pub fn writer<W: Write>(&self, writer: W, bom: bool) -> ExportWriter<W> {
+        match self {
+            ExportFormat::Csv => ExportWriter::Csv(CsvWriter::new(writer).with_bom(bom)),
+            ExportFormat::Ndjson => ExportWriter::Ndjson(NdjsonWriter::new(writer)),
+        }
+    }
+}
+
+/// A record that can be written as a CSV row
+pub trait CsvRecord {
+    /// Column names, in the order `fields` gives the values
+    fn header() -> &'static [&'static str];
+
+    fn fields(&self) -> Vec<String>;
+}
+
+/// Quote a field if it holds a comma, quote or line break, doubling any
+/// quotes inside it (RFC 4180)
+pub fn escape_csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Writes RFC 4180 CSV: escaped fields, rows ended with CRLF
+#[derive(Debug)]
+pub struct CsvWriter<W> {
+    writer: W,
+    header: bool,
+    bom: bool,
+    started: bool, // Anything written yet, BOM included
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// A writer that starts with a header row and no BOM
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            header: true,
+            bom: false,
+            started: false,
+        }
+    }
+
+    /// Whether to write a header row; turn it off when appending to
+    /// existing output
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Whether to start the output with a UTF-8 BOM, for files opened in
+    /// Excel
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Write the header row, unless headers are turned off. Call it first
+    /// so that an export with no records still names its columns.
+    pub fn write_header(&mut self, names: &[&str]) -> io::Result<()> {
+        if self.header {
+            self.write_row(names)
+        } else {
+            self.start()
+        }
+    }
+
+    /// Write one row of fields
+    pub fn write_row<I, S>(&mut self, fields: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.start()?;
+        for (i, field) in fields.into_iter().enumerate() {
+            if i > 0 {
+                self.writer.write_all(b",")?;
+            }
+            self.writer
+                .write_all(escape_csv_field(field.as_ref()).as_bytes())?;
+        }
+        self.writer.write_all(b"\r\n")
+    }
+
+    /// Write a record, after the header row if nothing has been written yet
+    pub fn write_record<R: CsvRecord>(&mut self, record: &R) -> io::Result<()> {
+        if !self.started {
+            self.write_header(R::header())?;
+        }
+        self.write_row(record.fields())
+    }
+
+    /// The underlying writer, e.g. to drain a buffer between chunks
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    fn start(&mut self) -> io::Result<()> {
+        if !self.started {
+            self.started = true;
+            if self.bom {
+                self.writer.write_all(UTF8_BOM)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes each value as a line of JSON. Line breaks inside strings are
+/// escaped, so every record is exactly one line.
+#[derive(Debug)]
+pub struct NdjsonWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn write<T: Serialize + ?Sized>(&mut self, value: &T) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, value)?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// The underlying writer, e.g. to drain a buffer between chunks
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// A CSV or NDJSON writer, for exports whose format the caller picks
+#[derive(Debug)]
+pub enum ExportWriter<W> {
+    Csv(CsvWriter<W>),
+    Ndjson(NdjsonWriter<W>),
+}
+
+impl<W: Write> ExportWriter<W> {
+    /// Write the CSV header row. NDJSON has none.
+    pub fn write_header<R: CsvRecord>(&mut self) -> io::Result<()> {
+        match self {
+            ExportWriter::Csv(writer) => writer.write_header(R::header()),
+            ExportWriter::Ndjson(_) => Ok(()),
+        }
+    }
+
+    pub fn write_record<R: CsvRecord + Serialize>(&mut self, record: &R) -> io::Result<()> {
+        match self {
+            ExportWriter::Csv(writer) => writer.write_record(record),
+            ExportWriter::Ndjson(writer) => writer.write(record),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        match self {
+            ExportWriter::Csv(writer) => writer.get_mut(),
+            ExportWriter::Ndjson(writer) => writer.get_mut(),
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        match self {
+            ExportWriter::Csv(writer) => writer.into_inner(),
+            ExportWriter::Ndjson(writer) => writer.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: &'static str,
+        note: &'static str,
+    }
+
+    impl CsvRecord for Row {
+        fn header() -> &'static [&'static str] {
+            &["name", "note"]
+        }
+
+        fn fields(&self) -> Vec<String> {
+            vec![self.name.to_string(), self.note.to_string()]
+        }
+    }
+
+    const ROWS: [Row; 2] = [
+        Row {
+            name: "Ahmed, Ali",
+            note: "said \"chest pain\"",
+        },
+        Row {
+            name: "فاطمة",
+            note: "line one\nline two",
+        },
+    ];
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("plain"), "plain");
+        assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv_field("two\r\nlines"), "\"two\r\nlines\"");
+    }
+
+    #[test]
+    fn test_csv_writer() {
+        let mut writer = CsvWriter::new(Vec::new()).with_bom(true);
+        for row in &ROWS {
+            writer.write_record(row).unwrap();
+        }
+        let output = writer.into_inner();
+        assert!(output.starts_with(UTF8_BOM));
+        assert_eq!(
+            String::from_utf8(output[UTF8_BOM.len()..].to_vec()).unwrap(),
+            "name,note\r\n\
+             \"Ahmed, Ali\",\"said \"\"chest pain\"\"\"\r\n\
+             فاطمة,\"line one\nline two\"\r\n"
+        );
+
+        // Draining the buffer between chunks writes the header only once
+        let mut writer = CsvWriter::new(Vec::new()).with_header(false);
+        writer.write_record(&ROWS[0]).unwrap();
+        std::mem::take(writer.get_mut());
+        writer.write_record(&ROWS[1]).unwrap();
+        assert!(String::from_utf8(writer.into_inner())
+            .unwrap()
+            .starts_with("فاطمة,"));
+
+        let mut writer = ExportFormat::Csv.writer(Vec::new(), false);
+        writer.write_header::<Row>().unwrap();
+        assert_eq!(writer.into_inner(), b"name,note\r\n");
+    }
+
+    #[test]
+    fn test_ndjson_writer() {
+        let mut writer = ExportFormat::Ndjson.writer(Vec::new(), true);
+        writer.write_header::<Row>().unwrap();
+        for row in &ROWS {
+            writer.write_record(row).unwrap();
+        }
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let second: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second["note"], "line one\nline two");
+    }
+}
@@ -0,0 +1,255 @@
+//! Durations and relative times written out for people, e.g. `5 minutes
+//! ago` or `منذ ٥ دقائق`
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::Locale;
+
+/// Digits numbers are written with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Numerals {
+    /// 0123456789, the usual choice in the UAE for either language
+    #[default]
+    Western,
+    /// ٠١٢٣٤٥٦٧٨٩
+    EasternArabic,
+}
+
+impl Numerals {
+    /// Write a number with these digits
+    pub fn format(&self, number: i64) -> String {
+        let digits = number.to_string();
+        match self {
+            Numerals::Western => digits,
+            Numerals::EasternArabic => digits
+                .chars()
+                .map(|c| match c.to_digit(10) {
+                    Some(digit) => char::from_u32('٠' as u32 + digit).unwrap_or(c),
+                    None => c,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+/// Arabic forms of a unit: alone for one, the dual (nominative and
+/// genitive) for two, the plural for 3-10 and the singular accusative for
+/// 11-99
+struct ArabicForms {
+    singular: &'static str,
+    dual: &'static str,
+    dual_genitive: &'static str,
+    plural: &'static str,
+    accusative: &'static str,
+}
+
+impl Unit {
+    const ALL: [Unit; 4] = [Unit::Day, Unit::Hour, Unit::Minute, Unit::Second];
+
+    fn seconds(&self) -> i64 {
+        match self {
+            Unit::Day => 86_400,
+            Unit::Hour => 3_600,
+            Unit::Minute => 60,
+            Unit::Second => 1,
+        }
+    }
+
+    fn english(&self) -> (&'static str, &'static str) {
+        match self {
+            Unit::Day => ("day", "days"),
+            Unit::Hour => ("hour", "hours"),
+            Unit::Minute => ("minute", "minutes"),
+            Unit::Second => ("second", "seconds"),
+        }
+    }
+
+    fn arabic(&self) -> ArabicForms {
+        match self {
+            Unit::Day => ArabicForms {
+                singular: "يوم",
+                dual: "يومان",
+                dual_genitive: "يومين",
+                plural: "أيام",
+                accusative: "يومًا",
+            },
+            Unit::Hour => ArabicForms {
+                singular: "ساعة",
+                dual: "ساعتان",
+                dual_genitive: "ساعتين",
+                plural: "ساعات",
+                accusative: "ساعة",
+            },
+            Unit::Minute => ArabicForms {
+                singular: "دقيقة",
+                dual: "دقيقتان",
+                dual_genitive: "دقيقتين",
+                plural: "دقائق",
+                accusative: "دقيقة",
+            },
+            Unit::Second => ArabicForms {
+                singular: "ثانية",
+                dual: "ثانيتان",
+                dual_genitive: "ثانيتين",
+                plural: "ثوانٍ",
+                accusative: "ثانية",
+            },
+        }
+    }
+}
+
+/// Writes durations and relative times in a language
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Humanizer {
+    locale: Locale,
+    numerals: Numerals,
+}
+
+impl Humanizer {
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            numerals: Numerals::default(),
+        }
+    }
+
+    pub fn with_numerals(mut self, numerals: Numerals) -> Self {
+        self.numerals = numerals;
+        self
+    }
+
+    /// Write a duration with its two largest units, e.g. `2 hours 15
+    /// minutes`. The sign is ignored.
+    pub fn duration(&self, duration: Duration) -> String {
+        let mut remaining = duration.num_seconds().abs();
+        let mut parts = Vec::new();
+        for unit in Unit::ALL {
+            let count = remaining / unit.seconds();
+            remaining %= unit.seconds();
+            if count > 0 || !parts.is_empty() {
+                parts.push((count, unit));
+            }
+        }
+        let parts: Vec<String> = match parts.as_slice() {
+            [] => vec![self.quantity(0, Unit::Second, false)],
+            [(count, unit)] => vec![self.quantity(*count, *unit, false)],
+            [(count, unit), (next, next_unit), ..] => {
+                let mut parts = vec![self.quantity(*count, *unit, false)];
+                if *next > 0 {
+                    parts.push(self.quantity(*next, *next_unit, false));
+                }
+                parts
+            }
+        };
+        let separator = match self.locale {
+            Locale::En => " ",
+            Locale::Ar => " و",
+        };
+        parts.join(separator)
+    }
+
+    /// Write how far `offset` is from now in its largest unit: `5 minutes
+    /// ago` when negative, `in 5 minutes` when positive
+    pub fn relative(&self, offset: Duration) -> String {
+        let seconds = offset.num_seconds();
+        let Some(unit) = Unit::ALL
+            .into_iter()
+            .find(|unit| seconds.abs() >= unit.seconds())
+        else {
+            return match self.locale {
+                Locale::En => "just now".to_string(),
+                Locale::Ar => "الآن".to_string(),
+            };
+        };
+        let quantity = self.quantity(seconds.abs() / unit.seconds(), unit, true);
+        match (self.locale, seconds < 0) {
+            (Locale::En, true) => format!("{} ago", quantity),
+            (Locale::En, false) => format!("in {}", quantity),
+            (Locale::Ar, true) => format!("منذ {}", quantity),
+            (Locale::Ar, false) => format!("خلال {}", quantity),
+        }
+    }
+
+    /// Write how long ago `at` was, e.g. `5 minutes ago`
+    pub fn since(&self, at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+        self.relative(at - now)
+    }
+
+    /// A count of a unit. In Arabic one and two are written with the noun
+    /// alone, and `genitive` picks the dual's form after a preposition.
+    fn quantity(&self, count: i64, unit: Unit, genitive: bool) -> String {
+        let number = self.numerals.format(count);
+        match self.locale {
+            Locale::En => {
+                let (singular, plural) = unit.english();
+                format!("{} {}", number, if count == 1 { singular } else { plural })
+            }
+            Locale::Ar => {
+                let forms = unit.arabic();
+                match (count, count % 100) {
+                    (1, _) => forms.singular.to_string(),
+                    (2, _) if genitive => forms.dual_genitive.to_string(),
+                    (2, _) => forms.dual.to_string(),
+                    (_, 3..=10) => format!("{} {}", number, forms.plural),
+                    (_, 11..=99) => format!("{} {}", number, forms.accusative),
+                    _ => format!("{} {}", number, forms.singular),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english() {
+        let humanizer = Humanizer::new(Locale::En);
+        assert_eq!(humanizer.relative(Duration::minutes(-5)), "5 minutes ago");
+        assert_eq!(humanizer.relative(Duration::seconds(-1)), "1 second ago");
+        assert_eq!(humanizer.relative(Duration::seconds(90)), "in 1 minute");
+        assert_eq!(humanizer.relative(Duration::zero()), "just now");
+        assert_eq!(
+            humanizer.duration(Duration::minutes(135)),
+            "2 hours 15 minutes"
+        );
+        assert_eq!(humanizer.duration(Duration::minutes(-120)), "2 hours");
+        assert_eq!(humanizer.duration(Duration::seconds(86_405)), "1 day");
+        assert_eq!(humanizer.duration(Duration::zero()), "0 seconds");
+
+        let now = Utc::now();
+        assert_eq!(
+            humanizer.since(now - Duration::hours(3), now),
+            "3 hours ago"
+        );
+    }
+
+    #[test]
+    fn test_arabic() {
+        let humanizer = Humanizer::new(Locale::Ar);
+        assert_eq!(humanizer.relative(Duration::minutes(-5)), "منذ 5 دقائق");
+        assert_eq!(humanizer.relative(Duration::minutes(-1)), "منذ دقيقة");
+        assert_eq!(humanizer.relative(Duration::hours(-2)), "منذ ساعتين");
+        assert_eq!(humanizer.relative(Duration::seconds(30)), "خلال 30 ثانية");
+        assert_eq!(humanizer.relative(Duration::days(-11)), "منذ 11 يومًا");
+        assert_eq!(humanizer.relative(Duration::days(-100)), "منذ 100 يوم");
+        assert_eq!(
+            humanizer.duration(Duration::minutes(135)),
+            "ساعتان و15 دقيقة"
+        );
+
+        let eastern = humanizer.with_numerals(Numerals::EasternArabic);
+        assert_eq!(eastern.relative(Duration::minutes(-5)), "منذ ٥ دقائق");
+        assert_eq!(eastern.duration(Duration::minutes(45)), "٤٥ دقيقة");
+        assert_eq!(Numerals::EasternArabic.format(-1_200), "-١٢٠٠");
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+/// Language user-facing messages are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    En,
+    Ar,
+}
+
+impl Locale {
+    /// Get the ISO 639-1 language code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ar => "ar",
+        }
+    }
+
+    /// Get display name for the language, in that language
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Locale::En => "English",
+            Locale::Ar => "العربية",
+        }
+    }
+
+    /// Check if text in this language is written right to left
+    pub fn is_rtl(&self) -> bool {
+        matches!(self, Locale::Ar)
+    }
+
+    /// Match a language tag such as `ar-AE` on its primary language
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.trim().split(['-', '_']).next()?;
+        if language.eq_ignore_ascii_case("en") {
+            Some(Locale::En)
+        } else if language.eq_ignore_ascii_case("ar") {
+            Some(Locale::Ar)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tag() {
+        assert_eq!(Locale::from_tag("ar-AE"), Some(Locale::Ar));
+        assert_eq!(Locale::from_tag("AR"), Some(Locale::Ar));
+        assert_eq!(Locale::from_tag("en_GB"), Some(Locale::En));
+        assert_eq!(Locale::from_tag("fr-FR"), None);
+        assert_eq!(Locale::from_tag(""), None);
+        assert!(Locale::Ar.is_rtl());
+    }
+}
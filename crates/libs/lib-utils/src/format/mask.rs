@@ -0,0 +1,160 @@
+//! Masking of personal details in logs and exports. Each masker keeps just
+//! enough of a value to tell records apart, and `mask_text` finds and masks
+//! identifiers, email addresses and phone numbers inside free text.
+
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+use regex::{Captures, Regex};
+
+pub use crate::validation::mask_emirates_id;
+
+/// Field names that hold a person's name. Other names (`service_name`,
+/// `hostname`, ...) are left alone.
+const NAME_FIELDS: &[&str] = &[
+    "name",
+    "first_name",
+    "last_name",
+    "full_name",
+    "display_name",
+    "patient_name",
+    "contact_name",
+    "guardian_name",
+    "guardian_consent_by",
+];
+
+static EMIRATES_ID: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b784-?\d{4}-?\d{7}-?\d\b").unwrap());
+static EMAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap());
+// International numbers written with `+`, or UAE numbers written with the
+// leading 0. Local numbers may only be spaced, so that UUID segments are
+// not mistaken for them.
+static PHONE_NUMBER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\+\d{1,3}(?:[ -]?\d){6,12}\b|\b0[2-9](?: ?\d){7,8}\b").unwrap());
+
+/// Hide all but the last two digits of a phone number, keeping its layout,
+/// e.g. `+971 50 123 4567` becomes `+*** ** *** **67`
+pub fn mask_phone_number(value: &str) -> String {
+    let digits = value.chars().filter(char::is_ascii_digit).count();
+    let mut seen = 0;
+    value
+        .trim()
+        .chars()
+        .map(|c| {
+            if !c.is_ascii_digit() {
+                return c;
+            }
+            seen += 1;
+            if seen + 2 > digits {
+                c
+            } else {
+                '*'
+            }
+        })
+        .collect()
+}
+
+/// Keep the first letter of each part of a name, e.g. `Ahmed Al-Rashid`
+/// becomes `A**** A*-******`
+pub fn mask_name(value: &str) -> String {
+    value
+        .split_whitespace()
+        .map(|part| {
+            part.chars()
+                .enumerate()
+                .map(|(i, c)| if i > 0 && c.is_alphanumeric() { '*' } else { c })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Keep the first character of the mailbox and the domain, e.g.
+/// `ahmed.rashid@dha.gov.ae` becomes `a***@dha.gov.ae`. The mailbox is
+/// always shown as three stars so its length is not revealed.
+pub fn mask_email(value: &str) -> String {
+    match value.trim().split_once('@') {
+        Some((mailbox, domain)) => {
+            let first = mailbox.chars().next().map(String::from).unwrap_or_default();
+            format!("{}***@{}", first, domain)
+        }
+        None => mask_name(value),
+    }
+}
+
+/// Mask Emirates IDs, email addresses and phone numbers found in free text.
+/// Names cannot be recognised in free text, so they should be logged as
+/// fields rather than written into messages.
+pub fn mask_text(value: &str) -> Cow<'_, str> {
+    let mut text = Cow::Borrowed(value);
+    for (pattern, masker) in [
+        (&*EMIRATES_ID, mask_emirates_id as fn(&str) -> String),
+        (&*EMAIL, mask_email),
+        (&*PHONE_NUMBER, mask_phone_number),
+    ] {
+        if pattern.is_match(&text) {
+            let replaced = pattern.replace_all(&text, |captures: &Captures| masker(&captures[0]));
+            text = Cow::Owned(replaced.into_owned());
+        }
+    }
+    text
+}
+
+/// Mask a value according to the name of the field it is logged or
+/// exported under, falling back to `mask_text`
+pub fn mask_field<'a>(field: &str, value: &'a str) -> Cow<'a, str> {
+    let field = field.to_ascii_lowercase();
+    if field.contains("emirates_id") {
+        Cow::Owned(mask_emirates_id(value))
+    } else if field.contains("email") {
+        Cow::Owned(mask_email(value))
+    } else if field.contains("phone") {
+        Cow::Owned(mask_phone_number(value))
+    } else if NAME_FIELDS.contains(&field.as_str()) {
+        Cow::Owned(mask_name(value))
+    } else {
+        mask_text(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maskers() {
+        assert_eq!(mask_phone_number("+971 50 123 4567"), "+*** ** *** **67");
+        assert_eq!(mask_phone_number("0501234567"), "********67");
+        assert_eq!(mask_name("Ahmed Al-Rashid"), "A**** A*-******");
+        assert_eq!(mask_name("عائشة"), "ع****");
+        assert_eq!(mask_email("ahmed.rashid@dha.gov.ae"), "a***@dha.gov.ae");
+        assert_eq!(mask_email("not-an-email"), "n**-**-*****");
+    }
+
+    #[test]
+    fn test_mask_text() {
+        assert_eq!(
+            mask_text("Registered 784-1990-1234567-6, contact +971501234567"),
+            "Registered 784-****-****567-6, contact +**********67"
+        );
+        assert_eq!(
+            mask_text("Sent to ahmed@example.com and 04 123 4567"),
+            "Sent to a***@example.com and ** *** **67"
+        );
+
+        let untouched = "Patient 0523a1b2-0523-4123-8123-000000040001 triaged at 2024-03-14";
+        assert!(matches!(mask_text(untouched), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_mask_field() {
+        assert_eq!(mask_field("last_name", "Al-Rashid"), "A*-******");
+        assert_eq!(mask_field("contact_phone", "0501234567"), "********67");
+        assert_eq!(
+            mask_field("emirates_id", "784199012345676"),
+            "784-****-****567-6"
+        );
+        assert_eq!(mask_field("service_name", "web-server"), "web-server");
+    }
+}
@@ -0,0 +1,120 @@
+//! A `tracing` field formatter that masks personal details before they are
+//! written, so logs can be kept at production verbosity without leaking
+//! patient information
+
+use std::fmt::{self, Debug};
+
+use tracing::field::{Field, Visit};
+use tracing_subscriber::field::RecordFields;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::FormatFields;
+
+use super::mask::mask_field;
+
+/// Formats event and span fields like the default formatter, masking each
+/// value with `mask_field`. Install with
+/// `tracing_subscriber::fmt::layer().fmt_fields(MaskedFields)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaskedFields;
+
+impl<'writer> FormatFields<'writer> for MaskedFields {
+    fn format_fields<R: RecordFields>(&self, writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = MaskingVisitor {
+            writer,
+            is_empty: true,
+            result: Ok(()),
+        };
+        fields.record(&mut visitor);
+        visitor.result
+    }
+}
+
+struct MaskingVisitor<'writer> {
+    writer: Writer<'writer>,
+    is_empty: bool,
+    result: fmt::Result,
+}
+
+impl MaskingVisitor<'_> {
+    fn write(&mut self, field: &Field, value: &str, quoted: bool) {
+        if self.result.is_err() {
+            return;
+        }
+        let masked = mask_field(field.name(), value);
+        let separator = if self.is_empty { "" } else { " " };
+        self.is_empty = false;
+        self.result = match (field.name(), quoted) {
+            ("message", _) => write!(self.writer, "{}{}", separator, masked),
+            (name, true) => write!(self.writer, "{}{}={:?}", separator, name, masked),
+            (name, false) => write!(self.writer, "{}{}={}", separator, name, masked),
+        };
+    }
+}
+
+impl Visit for MaskingVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.write(field, value, true);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.write(field, &format!("{:?}", value), false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use tracing_subscriber::fmt::MakeWriter;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Captured {
+        type Writer = Captured;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_fields_are_masked() {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::fmt()
+            .fmt_fields(MaskedFields)
+            .with_writer(captured.clone())
+            .with_ansi(false)
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(
+                first_name = "Ahmed",
+                phone_number = "+971501234567",
+                patient_count = 3,
+                "Registered 784-1990-1234567-6"
+            );
+        });
+
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("Registered 784-****-****567-6"));
+        assert!(output.contains("first_name=\"A****\""));
+        assert!(output.contains("phone_number=\"+**********67\""));
+        assert!(output.contains("patient_count=3"));
+        assert!(!output.contains("Ahmed"));
+    }
+}
@@ -1 +1,15 @@
-// pub mod format;
+//! Formatting of user-facing text and data exports
+
+pub mod export;
+pub mod humanize;
+pub mod locale;
+pub mod mask;
+pub mod masked_fields;
+
+pub use export::{
+    escape_csv_field, CsvRecord, CsvWriter, ExportFormat, ExportWriter, NdjsonWriter, UTF8_BOM,
+};
+pub use humanize::{Humanizer, Numerals};
+pub use locale::Locale;
+pub use mask::{mask_email, mask_field, mask_name, mask_phone_number, mask_text};
+pub use masked_fields::MaskedFields;
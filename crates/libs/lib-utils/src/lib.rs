@@ -4,9 +4,17 @@ pub mod time;
 pub mod validation;
 pub mod location;
 pub mod format;
+pub mod units;
+pub mod retry;
+pub mod rate_limit;
+pub mod reference_ranges;
 
 // Re-exports for convenience
 pub use time::*;
 pub use validation::*;
 pub use location::*;
 pub use format::*;
+pub use units::*;
+pub use retry::*;
+pub use rate_limit::*;
+pub use reference_ranges::*;
@@ -0,0 +1,419 @@
+//! Polygon geofences, e.g. the DHA catchment zones incidents are routed by
+
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use super::{GeoPoint, EARTH_RADIUS_KM};
+
+#[derive(Debug, Error)]
+pub enum GeofenceError {
+    #[error("Failed to read zones from {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid GeoJSON: {0}")]
+    InvalidGeoJson(String),
+
+    #[error("Zone {zone}: {reason}")]
+    InvalidZone { zone: String, reason: String },
+}
+
+/// A polygon with an outer ring and optional holes. Rings are lists of
+/// points; the closing point of GeoJSON rings may be left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    exterior: Vec<GeoPoint>,
+    holes: Vec<Vec<GeoPoint>>,
+}
+
+impl Polygon {
+    /// Create a polygon, rejecting rings with fewer than three corners or
+    /// out of range points
+    pub fn new(exterior: Vec<GeoPoint>, holes: Vec<Vec<GeoPoint>>) -> Result<Self, String> {
+        for ring in std::iter::once(&exterior).chain(&holes) {
+            let mut corners = ring.clone();
+            corners.dedup();
+            if corners.len() > 1 && corners.first() == corners.last() {
+                corners.pop();
+            }
+            if corners.len() < 3 {
+                return Err("rings need at least three corners".to_string());
+            }
+            if let Some(point) = ring.iter().find(|point| !point.is_valid()) {
+                return Err(format!("coordinates out of range: {}", point));
+            }
+        }
+        Ok(Self { exterior, holes })
+    }
+
+    /// Check if the point is inside the outer ring and outside every hole.
+    /// Points exactly on an edge may fall either way.
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        ring_contains(&self.exterior, point)
+            && !self.holes.iter().any(|hole| ring_contains(hole, point))
+    }
+
+    /// Distance in kilometres from the point to the polygon, 0 inside it
+    pub fn distance_km(&self, point: &GeoPoint) -> f64 {
+        if self.contains(point) {
+            return 0.0;
+        }
+        std::iter::once(&self.exterior)
+            .chain(&self.holes)
+            .flat_map(|ring| edges(ring))
+            .map(|(a, b)| segment_distance_km(point, a, b))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// A named area made of one or more polygons
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeofenceZone {
+    pub id: String,
+    pub name: String,
+    pub polygons: Vec<Polygon>,
+}
+
+impl GeofenceZone {
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        self.polygons.iter().any(|polygon| polygon.contains(point))
+    }
+
+    /// Distance in kilometres from the point to the zone, 0 inside it
+    pub fn distance_km(&self, point: &GeoPoint) -> f64 {
+        self.polygons
+            .iter()
+            .map(|polygon| polygon.distance_km(point))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// The zone a point was classified into
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZoneMatch<'a> {
+    pub zone: &'a GeofenceZone,
+    pub distance_km: f64, // 0 when the point is inside the zone
+}
+
+impl ZoneMatch<'_> {
+    pub fn is_inside(&self) -> bool {
+        self.distance_km == 0.0
+    }
+}
+
+/// A set of zones, checked in the order they were defined
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZoneRegistry {
+    zones: Vec<GeofenceZone>,
+}
+
+impl ZoneRegistry {
+    pub fn new(zones: Vec<GeofenceZone>) -> Self {
+        Self { zones }
+    }
+
+    /// Read zones from a GeoJSON file, see [`ZoneRegistry::from_geojson`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, GeofenceError> {
+        let path = path.as_ref();
+        let geojson = std::fs::read_to_string(path).map_err(|source| GeofenceError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_geojson(&geojson)
+    }
+
+    /// Parse a GeoJSON FeatureCollection with one Polygon or MultiPolygon
+    /// feature per zone. The zone id is the `id` property, or else the
+    /// feature id; the name is the `name` property, or else the id.
+    pub fn from_geojson(geojson: &str) -> Result<Self, GeofenceError> {
+        let collection: FeatureCollection = serde_json::from_str(geojson)
+            .map_err(|e| GeofenceError::InvalidGeoJson(e.to_string()))?;
+
+        let zones = collection
+            .features
+            .into_iter()
+            .enumerate()
+            .map(|(index, feature)| feature.into_zone(index))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(duplicate) = zones
+            .iter()
+            .enumerate()
+            .find(|(i, zone)| zones[..*i].iter().any(|other| other.id == zone.id))
+        {
+            return Err(GeofenceError::InvalidZone {
+                zone: duplicate.1.id.clone(),
+                reason: "defined more than once".to_string(),
+            });
+        }
+        Ok(Self::new(zones))
+    }
+
+    pub fn zones(&self) -> &[GeofenceZone] {
+        &self.zones
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    /// The first zone containing the point
+    pub fn zone_at(&self, point: &GeoPoint) -> Option<&GeofenceZone> {
+        self.zones.iter().find(|zone| zone.contains(point))
+    }
+
+    /// The zone closest to the point, which is one containing it if any
+    pub fn nearest(&self, point: &GeoPoint) -> Option<ZoneMatch<'_>> {
+        self.zones
+            .iter()
+            .map(|zone| ZoneMatch {
+                zone,
+                distance_km: zone.distance_km(point),
+            })
+            .min_by(|a, b| a.distance_km.total_cmp(&b.distance_km))
+    }
+
+    /// The zone the point is in, or else the nearest one within
+    /// `max_distance_km`, e.g. for an incident just outside every zone
+    pub fn classify(&self, point: &GeoPoint, max_distance_km: f64) -> Option<ZoneMatch<'_>> {
+        if let Some(zone) = self.zone_at(point) {
+            return Some(ZoneMatch {
+                zone,
+                distance_km: 0.0,
+            });
+        }
+        self.nearest(point)
+            .filter(|found| found.distance_km <= max_distance_km)
+    }
+}
+
+/// Even-odd ray casting in the plane of longitude and latitude, which is
+/// accurate enough for zones the size of a city
+fn ring_contains(ring: &[GeoPoint], point: &GeoPoint) -> bool {
+    let mut inside = false;
+    for (a, b) in edges(ring) {
+        if (a.lat > point.lat) != (b.lat > point.lat) {
+            let crossing = a.lon + (point.lat - a.lat) / (b.lat - a.lat) * (b.lon - a.lon);
+            if point.lon < crossing {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Pairs of consecutive corners, including the closing edge
+fn edges(ring: &[GeoPoint]) -> impl Iterator<Item = (&GeoPoint, &GeoPoint)> {
+    ring.iter().zip(ring.iter().cycle().skip(1))
+}
+
+/// Distance from `point` to the segment `a`-`b`, on a flat projection
+/// centred on the point
+fn segment_distance_km(point: &GeoPoint, a: &GeoPoint, b: &GeoPoint) -> f64 {
+    let scale = point.lat.to_radians().cos();
+    let project = |p: &GeoPoint| {
+        (
+            (p.lon - point.lon).to_radians() * scale * EARTH_RADIUS_KM,
+            (p.lat - point.lat).to_radians() * EARTH_RADIUS_KM,
+        )
+    };
+    let ((ax, ay), (bx, by)) = (project(a), project(b));
+    let (dx, dy) = (bx - ax, by - ay);
+    let length = dx * dx + dy * dy;
+    let t = if length == 0.0 {
+        0.0
+    } else {
+        (-(ax * dx + ay * dy) / length).clamp(0.0, 1.0)
+    };
+    (ax + t * dx).hypot(ay + t * dy)
+}
+
+#[derive(Deserialize)]
+struct FeatureCollection {
+    features: Vec<Feature>,
+}
+
+#[derive(Deserialize)]
+struct Feature {
+    #[serde(default)]
+    id: Option<Value>,
+    #[serde(default)]
+    properties: Option<Map<String, Value>>,
+    geometry: Geometry,
+}
+
+// Positions are [longitude, latitude], optionally followed by an altitude
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum Geometry {
+    Polygon {
+        coordinates: Vec<Vec<Vec<f64>>>,
+    },
+    MultiPolygon {
+        coordinates: Vec<Vec<Vec<Vec<f64>>>>,
+    },
+}
+
+impl Feature {
+    fn into_zone(self, index: usize) -> Result<GeofenceZone, GeofenceError> {
+        let text = |value: &Value| match value {
+            Value::String(text) => Some(text.clone()),
+            Value::Number(number) => Some(number.to_string()),
+            _ => None,
+        };
+        let property = |name: &str| self.properties.as_ref()?.get(name).and_then(text);
+
+        let id = property("id")
+            .or_else(|| self.id.as_ref().and_then(text))
+            .ok_or_else(|| GeofenceError::InvalidZone {
+                zone: format!("#{}", index + 1),
+                reason: "missing an id".to_string(),
+            })?;
+        let name = property("name").unwrap_or_else(|| id.clone());
+        let invalid = |reason: String| GeofenceError::InvalidZone {
+            zone: id.clone(),
+            reason,
+        };
+
+        let polygons = match self.geometry {
+            Geometry::Polygon { coordinates } => vec![coordinates],
+            Geometry::MultiPolygon { coordinates } => coordinates,
+        };
+        let polygons = polygons
+            .into_iter()
+            .map(|rings| {
+                let mut rings = rings
+                    .into_iter()
+                    .map(|ring| ring.iter().map(|position| to_point(position)).collect())
+                    .collect::<Result<Vec<Vec<GeoPoint>>, String>>()?;
+                if rings.is_empty() {
+                    return Err("polygon without rings".to_string());
+                }
+                let exterior = rings.remove(0);
+                Polygon::new(exterior, rings)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid)?;
+        if polygons.is_empty() {
+            return Err(invalid("no polygons".to_string()));
+        }
+
+        Ok(GeofenceZone { id, name, polygons })
+    }
+}
+
+fn to_point(position: &[f64]) -> Result<GeoPoint, String> {
+    match position {
+        [lon, lat, ..] => GeoPoint::new(*lat, *lon).map_err(|e| e.to_string()),
+        _ => Err("positions need a longitude and a latitude".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Two squares side by side; the western one has a hole in its middle
+    const ZONES: &str = r#"{
+        "type": "FeatureCollection",
+        "features": [
+            {
+                "type": "Feature",
+                "properties": {"id": "west", "name": "West Zone"},
+                "geometry": {
+                    "type": "Polygon",
+                    "coordinates": [
+                        [[55.0, 25.0], [55.1, 25.0], [55.1, 25.1], [55.0, 25.1], [55.0, 25.0]],
+                        [[55.04, 25.04], [55.06, 25.04], [55.06, 25.06], [55.04, 25.06]]
+                    ]
+                }
+            },
+            {
+                "type": "Feature",
+                "id": "east",
+                "geometry": {
+                    "type": "MultiPolygon",
+                    "coordinates": [
+                        [[[55.1, 25.0], [55.2, 25.0], [55.2, 25.1], [55.1, 25.1]]]
+                    ]
+                }
+            }
+        ]
+    }"#;
+
+    fn point(lat: f64, lon: f64) -> GeoPoint {
+        GeoPoint::new(lat, lon).unwrap()
+    }
+
+    #[test]
+    fn test_zone_at() {
+        let registry = ZoneRegistry::from_geojson(ZONES).unwrap();
+        assert_eq!(registry.zones().len(), 2);
+        assert_eq!(registry.zones()[1].name, "east");
+
+        let zone_at = |lat, lon| {
+            registry
+                .zone_at(&point(lat, lon))
+                .map(|zone| zone.id.as_str())
+        };
+        assert_eq!(zone_at(25.02, 55.02), Some("west"));
+        assert_eq!(zone_at(25.05, 55.05), None); // In the hole
+        assert_eq!(zone_at(25.05, 55.15), Some("east"));
+        assert_eq!(zone_at(25.2, 55.05), None);
+    }
+
+    #[test]
+    fn test_classify() {
+        let registry = ZoneRegistry::from_geojson(ZONES).unwrap();
+
+        let inside = registry.classify(&point(25.05, 55.15), 1.0).unwrap();
+        assert_eq!(inside.zone.id, "east");
+        assert!(inside.is_inside());
+
+        // About 1.1 km north of the western zone
+        let north = point(25.11, 55.05);
+        let nearest = registry.nearest(&north).unwrap();
+        assert_eq!(nearest.zone.id, "west");
+        assert!(
+            (nearest.distance_km - 1.11).abs() < 0.05,
+            "got {}",
+            nearest.distance_km
+        );
+        assert!(registry.classify(&north, 2.0).is_some());
+        assert!(registry.classify(&north, 0.5).is_none());
+
+        // The hole's edge is nearest, not the outer ring
+        let hole = registry.nearest(&point(25.05, 55.05)).unwrap();
+        assert!(
+            (hole.distance_km - 1.0).abs() < 0.05,
+            "got {}",
+            hole.distance_km
+        );
+    }
+
+    #[test]
+    fn test_invalid_geojson() {
+        assert!(matches!(
+            ZoneRegistry::from_geojson("[]"),
+            Err(GeofenceError::InvalidGeoJson(_))
+        ));
+
+        let line = r#"{"features": [{"id": "a", "geometry": {"type": "Polygon",
+            "coordinates": [[[55.0, 25.0], [55.1, 25.0], [55.0, 25.0]]]}}]}"#;
+        assert!(matches!(
+            ZoneRegistry::from_geojson(line),
+            Err(GeofenceError::InvalidZone { zone, .. }) if zone == "a"
+        ));
+
+        let unnamed = r#"{"features": [{"geometry": {"type": "Polygon",
+            "coordinates": [[[55.0, 25.0], [55.1, 25.0], [55.1, 25.1]]]}}]}"#;
+        assert!(matches!(
+            ZoneRegistry::from_geojson(unnamed),
+            Err(GeofenceError::InvalidZone { zone, .. }) if zone == "#1"
+        ));
+    }
+}
@@ -0,0 +1,283 @@
+//! Geohashes: positions written as base-32 strings naming a grid cell, so
+//! nearby positions share a prefix. Used to bucket ambulance positions for
+//! nearest-unit searches; a search covers a cell and its eight neighbours,
+//! since a nearby unit may sit just across a cell edge.
+
+use thiserror::Error;
+
+use super::GeoPoint;
+
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Longest geohash handled, a cell of a few centimetres
+pub const MAX_PRECISION: usize = 12;
+
+/// Kilometres per degree of latitude
+const KM_PER_DEGREE: f64 = 111.32;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GeohashError {
+    #[error("Geohash is empty")]
+    Empty,
+
+    #[error("Geohash is longer than {MAX_PRECISION} characters")]
+    TooLong,
+
+    #[error("Geohash contains invalid character '{0}'")]
+    InvalidCharacter(char),
+}
+
+/// The area a geohash names
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeohashBounds {
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lon: f64,
+    pub max_lon: f64,
+}
+
+impl GeohashBounds {
+    pub fn center(&self) -> GeoPoint {
+        GeoPoint {
+            lat: (self.min_lat + self.max_lat) / 2.0,
+            lon: (self.min_lon + self.max_lon) / 2.0,
+        }
+    }
+
+    pub fn contains(&self, point: &GeoPoint) -> bool {
+        (self.min_lat..=self.max_lat).contains(&point.lat)
+            && (self.min_lon..=self.max_lon).contains(&point.lon)
+    }
+}
+
+/// A neighbouring cell's direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Cells moved north and east
+    fn offset(&self) -> (f64, f64) {
+        match self {
+            Direction::North => (1.0, 0.0),
+            Direction::NorthEast => (1.0, 1.0),
+            Direction::East => (0.0, 1.0),
+            Direction::SouthEast => (-1.0, 1.0),
+            Direction::South => (-1.0, 0.0),
+            Direction::SouthWest => (-1.0, -1.0),
+            Direction::West => (0.0, -1.0),
+            Direction::NorthWest => (1.0, -1.0),
+        }
+    }
+}
+
+/// The geohash of the cell containing `point`, `precision` characters long
+/// (capped at `MAX_PRECISION`)
+pub fn encode(point: &GeoPoint, precision: usize) -> String {
+    let (mut lat_range, mut lon_range) = ((-90.0, 90.0), (-180.0, 180.0));
+    let mut hash = String::with_capacity(precision);
+    let mut bits = 0;
+    let mut index = 0;
+    let mut is_lon = true;
+    while hash.len() < precision.min(MAX_PRECISION) {
+        let (range, value) = if is_lon {
+            (&mut lon_range, point.lon)
+        } else {
+            (&mut lat_range, point.lat)
+        };
+        let mid = (range.0 + range.1) / 2.0;
+        index <<= 1;
+        if value >= mid {
+            index |= 1;
+            range.0 = mid;
+        } else {
+            range.1 = mid;
+        }
+        is_lon = !is_lon;
+
+        bits += 1;
+        if bits == 5 {
+            hash.push(BASE32[index] as char);
+            bits = 0;
+            index = 0;
+        }
+    }
+    hash
+}
+
+/// The area a geohash names. Upper-case hashes are accepted.
+pub fn decode_bounds(hash: &str) -> Result<GeohashBounds, GeohashError> {
+    if hash.is_empty() {
+        return Err(GeohashError::Empty);
+    }
+    if hash.len() > MAX_PRECISION {
+        return Err(GeohashError::TooLong);
+    }
+
+    let (mut lat_range, mut lon_range) = ((-90.0, 90.0), (-180.0, 180.0));
+    let mut is_lon = true;
+    for c in hash.chars() {
+        let index = BASE32
+            .iter()
+            .position(|&b| b as char == c.to_ascii_lowercase())
+            .ok_or(GeohashError::InvalidCharacter(c))?;
+        for bit in (0..5).rev() {
+            let range = if is_lon {
+                &mut lon_range
+            } else {
+                &mut lat_range
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            if (index >> bit) & 1 == 1 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            is_lon = !is_lon;
+        }
+    }
+
+    Ok(GeohashBounds {
+        min_lat: lat_range.0,
+        max_lat: lat_range.1,
+        min_lon: lon_range.0,
+        max_lon: lon_range.1,
+    })
+}
+
+/// The centre of the cell a geohash names
+pub fn decode(hash: &str) -> Result<GeoPoint, GeohashError> {
+    decode_bounds(hash).map(|bounds| bounds.center())
+}
+
+/// The adjacent cell of the same size in `direction`, wrapping around the
+/// antimeridian. Cells at a pole have no neighbour beyond it.
+pub fn neighbor(hash: &str, direction: Direction) -> Result<Option<String>, GeohashError> {
+    let bounds = decode_bounds(hash)?;
+    let center = bounds.center();
+    let (north, east) = direction.offset();
+
+    let lat = center.lat + north * (bounds.max_lat - bounds.min_lat);
+    if !(-90.0..=90.0).contains(&lat) {
+        return Ok(None);
+    }
+    let lon = center.lon + east * (bounds.max_lon - bounds.min_lon);
+    let lon = (lon + 180.0).rem_euclid(360.0) - 180.0;
+    Ok(Some(encode(&GeoPoint { lat, lon }, hash.len())))
+}
+
+/// The up to eight cells around a geohash
+pub fn neighbors(hash: &str) -> Result<Vec<String>, GeohashError> {
+    let mut cells = Vec::with_capacity(Direction::ALL.len());
+    for direction in Direction::ALL {
+        cells.extend(neighbor(hash, direction)?);
+    }
+    Ok(cells)
+}
+
+/// The longest geohash whose cells are at least `radius_km` across at
+/// latitude `lat`, so that a cell and its neighbours cover every position
+/// within the radius of a point in the cell
+pub fn precision_for_radius(radius_km: f64, lat: f64) -> usize {
+    let km_per_degree_lon = KM_PER_DEGREE * lat.to_radians().cos();
+    (1..=MAX_PRECISION)
+        .rev()
+        .find(|&precision| {
+            let lon_bits = (5 * precision).div_ceil(2) as i32;
+            let lat_bits = (5 * precision / 2) as i32;
+            let height_km = 180.0 / 2f64.powi(lat_bits) * KM_PER_DEGREE;
+            let width_km = 360.0 / 2f64.powi(lon_bits) * km_per_degree_lon;
+            height_km.min(width_km) >= radius_km
+        })
+        .unwrap_or(1)
+}
+
+/// The cell containing `point` and its neighbours, sized so that together
+/// they cover every position within `radius_km`
+pub fn covering(point: &GeoPoint, radius_km: f64) -> Vec<String> {
+    let hash = encode(point, precision_for_radius(radius_km, point.lat));
+    let mut cells = neighbors(&hash).unwrap_or_default();
+    cells.insert(0, hash);
+    cells
+}
+
+impl GeoPoint {
+    /// The geohash of the cell containing this point
+    pub fn geohash(&self, precision: usize) -> String {
+        encode(self, precision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let point = GeoPoint::new(57.64911, 10.40744).unwrap();
+        assert_eq!(encode(&point, 11), "u4pruydqqvj");
+        assert_eq!(point.geohash(5), "u4pru");
+
+        let bounds = decode_bounds("u4pruydqqvj").unwrap();
+        assert!(bounds.contains(&point));
+        assert!(decode("U4PRU").unwrap().distance_km(&point) < 3.0);
+
+        assert_eq!(decode(""), Err(GeohashError::Empty));
+        assert_eq!(decode("u4a"), Err(GeohashError::InvalidCharacter('a')));
+        assert_eq!(decode(&"u".repeat(13)), Err(GeohashError::TooLong));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let hash = GeoPoint::new(25.2048, 55.2708).unwrap().geohash(6);
+        let north = neighbor(&hash, Direction::North).unwrap().unwrap();
+        assert_ne!(north, hash);
+        assert_eq!(
+            neighbor(&north, Direction::South).unwrap(),
+            Some(hash.clone())
+        );
+
+        let cells = neighbors(&hash).unwrap();
+        assert_eq!(cells.len(), 8);
+        assert!(cells.iter().all(|cell| cell.len() == 6 && cell != &hash));
+
+        // Wraps across the antimeridian, stops at the poles
+        let east_edge = GeoPoint::new(0.0, 179.99).unwrap().geohash(4);
+        let wrapped = neighbor(&east_edge, Direction::East).unwrap().unwrap();
+        assert!(decode(&wrapped).unwrap().lon < -179.0);
+        let pole = GeoPoint::new(89.99, 0.0).unwrap().geohash(3);
+        assert_eq!(neighbor(&pole, Direction::North).unwrap(), None);
+        assert_eq!(neighbors(&pole).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_covering() {
+        let dubai = GeoPoint::new(25.2048, 55.2708).unwrap();
+        assert_eq!(precision_for_radius(2.0, dubai.lat), 5);
+        assert_eq!(precision_for_radius(20.0, dubai.lat), 3);
+
+        let cells = covering(&dubai, 2.0);
+        assert_eq!(cells.len(), 9);
+        let nearby = GeoPoint::new(25.2148, 55.2808).unwrap();
+        assert!(cells.contains(&nearby.geohash(5)));
+    }
+}
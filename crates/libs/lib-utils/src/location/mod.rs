@@ -1 +1,280 @@
-// pub mod location;
+//! Geographic coordinates, distances and travel time estimates
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, Timelike, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef};
+use sqlx::{Decode, Encode, Postgres, Type};
+use thiserror::Error;
+
+pub mod geofence;
+pub mod geohash;
+
+pub use geofence::{GeofenceError, GeofenceZone, Polygon, ZoneMatch, ZoneRegistry};
+pub use geohash::{GeohashBounds, GeohashError};
+
+/// Mean radius of the earth, used for great-circle distances
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum GeoPointError {
+    #[error("Location must be \"latitude,longitude\", got \"{0}\"")]
+    InvalidFormat(String),
+
+    #[error("Coordinates out of range: {lat},{lon}")]
+    OutOfRange { lat: f64, lon: f64 },
+}
+
+/// A WGS84 position in decimal degrees. Serializes as `{"lat": .., "lon": ..}`
+/// and also accepts the `"latitude,longitude"` text it is stored as.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GeoPoint {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl GeoPoint {
+    /// Create a point, rejecting out of range coordinates
+    pub fn new(lat: f64, lon: f64) -> Result<Self, GeoPointError> {
+        let point = Self { lat, lon };
+        if point.is_valid() {
+            Ok(point)
+        } else {
+            Err(GeoPointError::OutOfRange { lat, lon })
+        }
+    }
+
+    /// Check the coordinates are finite and within range
+    pub fn is_valid(&self) -> bool {
+        (-90.0..=90.0).contains(&self.lat) && (-180.0..=180.0).contains(&self.lon)
+    }
+
+    /// Great-circle distance to another point in kilometres
+    pub fn distance_km(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.lon - self.lon).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+    }
+
+    /// Initial bearing of the great circle to another point, in degrees
+    /// clockwise from true north (0 to 360)
+    pub fn bearing_deg(&self, other: &GeoPoint) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let d_lon = (other.lon - self.lon).to_radians();
+
+        let y = d_lon.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+        y.atan2(x).to_degrees().rem_euclid(360.0)
+    }
+
+    /// Split `"latitude,longitude"` without checking ranges
+    fn parse_unchecked(s: &str) -> Result<Self, GeoPointError> {
+        let invalid = || GeoPointError::InvalidFormat(s.to_string());
+        let (lat, lon) = s.split_once(',').ok_or_else(invalid)?;
+        Ok(Self {
+            lat: lat.trim().parse().map_err(|_| invalid())?,
+            lon: lon.trim().parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl FromStr for GeoPoint {
+    type Err = GeoPointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let point = Self::parse_unchecked(s)?;
+        Self::new(point.lat, point.lon)
+    }
+}
+
+impl fmt::Display for GeoPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.lat, self.lon)
+    }
+}
+
+/// Estimates road travel time from the straight-line distance, with an
+/// average urban speed slowed down in rush hours and sped up at night
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EtaEstimator {
+    pub average_speed_kmh: f64, // Off-peak average, including junctions
+    pub road_factor: f64,       // Road kilometres per straight-line kilometre
+    pub peak_factor: f64,       // Travel time multiplier 07:00-10:00 and 17:00-20:00
+    pub night_factor: f64,      // Travel time multiplier 22:00-06:00
+    pub utc_offset_hours: i32,  // Local time the hours above are in
+}
+
+impl Default for EtaEstimator {
+    fn default() -> Self {
+        Self {
+            average_speed_kmh: 45.0,
+            road_factor: 1.3,
+            peak_factor: 1.5,
+            night_factor: 0.8,
+            utc_offset_hours: 4, // Gulf Standard Time, no daylight saving
+        }
+    }
+}
+
+impl EtaEstimator {
+    /// Check the speed and factors are positive and the offset is a real one
+    pub fn is_valid(&self) -> bool {
+        [
+            self.average_speed_kmh,
+            self.road_factor,
+            self.peak_factor,
+            self.night_factor,
+        ]
+        .iter()
+        .all(|value| value.is_finite() && *value > 0.0)
+            && (-12..=14).contains(&self.utc_offset_hours)
+    }
+
+    /// Travel time multiplier for a journey starting at `at`
+    pub fn time_of_day_factor(&self, at: DateTime<Utc>) -> f64 {
+        let hour = FixedOffset::east_opt(self.utc_offset_hours * 3600)
+            .map_or(at.hour(), |offset| at.with_timezone(&offset).hour());
+        match hour {
+            7..=9 | 17..=19 => self.peak_factor,
+            22..=23 | 0..=5 => self.night_factor,
+            _ => 1.0,
+        }
+    }
+
+    /// Minutes, to the nearest minute, to cover `distance_km` in a straight
+    /// line when leaving at `at`
+    pub fn estimate_minutes(&self, distance_km: f64, at: DateTime<Utc>) -> i32 {
+        let hours = distance_km * self.road_factor / self.average_speed_kmh;
+        (hours * 60.0 * self.time_of_day_factor(at)).round() as i32
+    }
+
+    /// Minutes from one point to another when leaving at `at`
+    pub fn eta_minutes(&self, from: &GeoPoint, to: &GeoPoint, at: DateTime<Utc>) -> i32 {
+        self.estimate_minutes(from.distance_km(to), at)
+    }
+}
+
+// Ranges are checked by request validation so they surface as field errors
+impl<'de> Deserialize<'de> for GeoPoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Object { lat: f64, lon: f64 },
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => Self::parse_unchecked(&text).map_err(serde::de::Error::custom),
+            Repr::Object { lat, lon } => Ok(Self { lat, lon }),
+        }
+    }
+}
+
+// Stored as "latitude,longitude" text
+impl Type<Postgres> for GeoPoint {
+    fn type_info() -> PgTypeInfo {
+        <str as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'q> Encode<'q, Postgres> for GeoPoint {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <String as Encode<'q, Postgres>>::encode(self.to_string(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for GeoPoint {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(<&str as Decode<'r, Postgres>>::decode(value)?.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing() {
+        let point: GeoPoint = " 25.2697 , 55.3094 ".parse().unwrap();
+        assert_eq!((point.lat, point.lon), (25.2697, 55.3094));
+        assert_eq!(point.to_string(), "25.2697,55.3094");
+
+        assert!(matches!(
+            "Oud Metha".parse::<GeoPoint>(),
+            Err(GeoPointError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            "95.0,55.3".parse::<GeoPoint>(),
+            Err(GeoPointError::OutOfRange { .. })
+        ));
+        assert!(GeoPoint::new(25.2, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let point = GeoPoint::new(25.2697, 55.3094).unwrap();
+        let json = serde_json::to_value(point).unwrap();
+        assert_eq!(json, serde_json::json!({"lat": 25.2697, "lon": 55.3094}));
+        assert_eq!(serde_json::from_value::<GeoPoint>(json).unwrap(), point);
+        assert_eq!(
+            serde_json::from_value::<GeoPoint>(serde_json::json!("25.2697,55.3094")).unwrap(),
+            point
+        );
+        assert!(serde_json::from_value::<GeoPoint>(serde_json::json!("Oud Metha")).is_err());
+    }
+
+    #[test]
+    fn test_distance() {
+        let rashid = GeoPoint::new(25.2358, 55.3160).unwrap();
+        let abu_dhabi = GeoPoint::new(24.4539, 54.3773).unwrap();
+        let distance = rashid.distance_km(&abu_dhabi);
+        assert!((distance - 129.0).abs() < 2.0, "got {}", distance);
+        assert_eq!(rashid.distance_km(&rashid), 0.0);
+    }
+
+    #[test]
+    fn test_bearing() {
+        let origin = GeoPoint::new(25.0, 55.0).unwrap();
+        let bearing = |lat, lon| origin.bearing_deg(&GeoPoint::new(lat, lon).unwrap());
+        assert!(bearing(26.0, 55.0).abs() < 1e-9);
+        assert!((bearing(25.0, 56.0) - 90.0).abs() < 1.0);
+        assert!((bearing(24.0, 55.0) - 180.0).abs() < 1e-9);
+        assert!((bearing(25.0, 54.0) - 270.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_eta() {
+        let eta = EtaEstimator::default();
+        let at = |hour| {
+            DateTime::parse_from_rfc3339(&format!("2024-03-04T{:02}:30:00+04:00", hour))
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        // 30 km at 45 km/h with 1.3 road km per km: 52 minutes off peak
+        assert_eq!(eta.estimate_minutes(30.0, at(12)), 52);
+        assert_eq!(eta.estimate_minutes(30.0, at(8)), 78);
+        assert_eq!(eta.estimate_minutes(30.0, at(23)), 42); // 41.6
+        assert_eq!(eta.estimate_minutes(0.0, at(12)), 0);
+
+        assert!(eta.is_valid());
+        assert!(!EtaEstimator {
+            average_speed_kmh: 0.0,
+            ..eta
+        }
+        .is_valid());
+    }
+}
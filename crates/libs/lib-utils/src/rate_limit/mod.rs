@@ -0,0 +1,7 @@
+//! Token-bucket rate limiting, in memory or shared through Redis
+
+pub mod redis_buckets;
+pub mod token_bucket;
+
+pub use redis_buckets::RedisTokenBuckets;
+pub use token_bucket::{BucketConfig, Decision, MemoryTokenBuckets, TokenBucket, TokenBuckets};
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use redis::RedisError;
+
+/// Buckets kept in memory before full ones are forgotten
+const MAX_IDLE_BUCKETS: usize = 10_000;
+
+/// Size and refill rate of a token bucket. A full bucket allows a burst of
+/// `capacity` requests; after that requests are allowed at the refill rate,
+/// which must be positive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketConfig {
+    pub capacity: f64,
+    pub refill_per_second: f64,
+}
+
+impl BucketConfig {
+    pub fn new(capacity: f64, refill_per_second: f64) -> Self {
+        Self {
+            capacity,
+            refill_per_second,
+        }
+    }
+
+    /// A bucket allowing `requests` per minute, all of them at once if
+    /// need be
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(f64::from(requests), f64::from(requests) / 60.0)
+    }
+}
+
+/// Outcome of taking tokens from a bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decision {
+    pub allowed: bool,
+    pub remaining: f64,
+    pub retry_after: Duration, // Until enough tokens have refilled; zero if allowed
+}
+
+impl Decision {
+    /// Whole requests of cost one left in the bucket, for rate-limit headers
+    pub fn remaining_requests(&self) -> u64 {
+        self.remaining.max(0.0).floor() as u64
+    }
+}
+
+/// A single token bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket
+    pub fn new(config: &BucketConfig, now: Instant) -> Self {
+        Self {
+            tokens: config.capacity,
+            updated: now,
+        }
+    }
+
+    /// Refill the bucket for the time since it was last used, then take
+    /// `cost` tokens if there are enough. A cost above the capacity is never
+    /// allowed.
+    pub fn try_take(&mut self, config: &BucketConfig, cost: f64, now: Instant) -> Decision {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * config.refill_per_second).min(config.capacity);
+        self.updated = now;
+
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            Decision {
+                allowed: true,
+                remaining: self.tokens,
+                retry_after: Duration::ZERO,
+            }
+        } else {
+            Decision {
+                allowed: false,
+                remaining: self.tokens,
+                retry_after: Duration::from_secs_f64(
+                    (cost - self.tokens) / config.refill_per_second,
+                ),
+            }
+        }
+    }
+
+    /// Check if the bucket has refilled completely, so forgetting it loses
+    /// nothing
+    fn is_full(&self, config: &BucketConfig, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens + elapsed * config.refill_per_second >= config.capacity
+    }
+}
+
+/// Token buckets keyed by caller, e.g. a user, API key or IP address
+#[async_trait]
+pub trait TokenBuckets: Send + Sync {
+    /// Take `cost` tokens from the bucket for `key`, creating it full
+    async fn try_take(
+        &self,
+        key: &str,
+        config: &BucketConfig,
+        cost: f64,
+    ) -> Result<Decision, RedisError>;
+}
+
+/// Token buckets held by this process, for a single instance or for
+/// throttling its own outbound calls
+#[derive(Debug, Default)]
+pub struct MemoryTokenBuckets {
+    buckets: Mutex<HashMap<String, (BucketConfig, TokenBucket)>>,
+}
+
+impl MemoryTokenBuckets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take tokens as of `now`
+    pub fn try_take_at(
+        &self,
+        key: &str,
+        config: &BucketConfig,
+        cost: f64,
+        now: Instant,
+    ) -> Decision {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_IDLE_BUCKETS && !buckets.contains_key(key) {
+            buckets.retain(|_, (config, bucket)| !bucket.is_full(config, now));
+        }
+
+        let (stored_config, bucket) = buckets
+            .entry(key.to_string())
+            .or_insert_with(|| (*config, TokenBucket::new(config, now)));
+        *stored_config = *config;
+        bucket.try_take(config, cost, now)
+    }
+}
+
+#[async_trait]
+impl TokenBuckets for MemoryTokenBuckets {
+    async fn try_take(
+        &self,
+        key: &str,
+        config: &BucketConfig,
+        cost: f64,
+    ) -> Result<Decision, RedisError> {
+        Ok(self.try_take_at(key, config, cost, Instant::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_refuse() {
+        let config = BucketConfig::new(5.0, 1.0);
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(&config, start);
+
+        for _ in 0..5 {
+            assert!(bucket.try_take(&config, 1.0, start).allowed);
+        }
+        let refused = bucket.try_take(&config, 1.0, start);
+        assert!(!refused.allowed);
+        assert_eq!(refused.retry_after, Duration::from_secs(1));
+        assert_eq!(refused.remaining_requests(), 0);
+
+        let costly = bucket.try_take(&config, 3.0, start + Duration::from_secs(1));
+        assert!(!costly.allowed);
+        assert_eq!(costly.retry_after, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_steady_state() {
+        let config = BucketConfig::per_minute(60);
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(&config, start);
+        for _ in 0..60 {
+            assert!(bucket.try_take(&config, 1.0, start).allowed);
+        }
+
+        // Once drained, one request per second gets through
+        let allowed = (1..=120)
+            .map(|tick| start + Duration::from_millis(500 * tick))
+            .filter(|now| bucket.try_take(&config, 1.0, *now).allowed)
+            .count();
+        assert_eq!(allowed, 60);
+
+        // Idle time refills the bucket, but never past its capacity
+        let later = start + Duration::from_secs(3600);
+        assert_eq!(bucket.try_take(&config, 0.0, later).remaining, 60.0);
+    }
+
+    #[test]
+    fn test_buckets_are_kept_per_key() {
+        let buckets = MemoryTokenBuckets::new();
+        let config = BucketConfig::new(1.0, 0.1);
+        let now = Instant::now();
+
+        assert!(buckets.try_take_at("user:1", &config, 1.0, now).allowed);
+        assert!(!buckets.try_take_at("user:1", &config, 1.0, now).allowed);
+        assert!(buckets.try_take_at("user:2", &config, 1.0, now).allowed);
+    }
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// Age group used to pick vital sign reference ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgeBand {
+    Neonate,    // First 28 days
+    Infant,     // Under 1 year
+    Child,      // 1-12 years
+    Adolescent, // 13-17 years
+    Adult,      // 18-64 years
+    Elderly,    // 65 and over
+}
+
+impl AgeBand {
+    /// Get the band for an age in whole years. Neonates cannot be told
+    /// apart from older infants this way; use `from_age_days` when the date
+    /// of birth is known.
+    pub fn from_age_years(years: i32) -> Self {
+        match years {
+            ..=0 => AgeBand::Infant,
+            1..=12 => AgeBand::Child,
+            13..=17 => AgeBand::Adolescent,
+            18..=64 => AgeBand::Adult,
+            _ => AgeBand::Elderly,
+        }
+    }
+
+    /// Get the band for an age in days
+    pub fn from_age_days(days: i64) -> Self {
+        match days {
+            ..=28 => AgeBand::Neonate,
+            29..=364 => AgeBand::Infant,
+            _ => Self::from_age_years(i32::try_from(days / 365).unwrap_or(i32::MAX)),
+        }
+    }
+
+    /// Get display name for age band
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            AgeBand::Neonate => "Neonate",
+            AgeBand::Infant => "Infant",
+            AgeBand::Child => "Child",
+            AgeBand::Adolescent => "Adolescent",
+            AgeBand::Adult => "Adult",
+            AgeBand::Elderly => "Elderly",
+        }
+    }
+
+    pub fn is_pediatric(&self) -> bool {
+        *self < AgeBand::Adult
+    }
+}
+
+impl std::fmt::Display for AgeBand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_bands() {
+        assert_eq!(AgeBand::from_age_years(0), AgeBand::Infant);
+        assert_eq!(AgeBand::from_age_years(12), AgeBand::Child);
+        assert_eq!(AgeBand::from_age_years(17), AgeBand::Adolescent);
+        assert_eq!(AgeBand::from_age_years(64), AgeBand::Adult);
+        assert_eq!(AgeBand::from_age_years(65), AgeBand::Elderly);
+
+        assert_eq!(AgeBand::from_age_days(10), AgeBand::Neonate);
+        assert_eq!(AgeBand::from_age_days(200), AgeBand::Infant);
+        assert_eq!(AgeBand::from_age_days(800), AgeBand::Child);
+    }
+
+    #[test]
+    fn test_pediatric() {
+        assert!(AgeBand::Adolescent.is_pediatric());
+        assert!(!AgeBand::Elderly.is_pediatric());
+    }
+}
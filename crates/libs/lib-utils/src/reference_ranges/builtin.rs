@@ -0,0 +1,100 @@
+//! The reference ranges the service ships with
+
+use super::{AgeBand, Bounds, Measurement, ReferenceRange};
+
+const OXYGEN_SATURATION: Bounds = Bounds::new(90.0, 95.0, 100.0, 100.0);
+const TEMPERATURE: Bounds = Bounds::new(35.0, 36.0, 38.5, 40.0);
+const BLOOD_GLUCOSE: Bounds = Bounds::new(3.0, 4.0, 11.0, 20.0);
+
+/// Ranges for everyone in each age band, in `Measurement::ALL` order
+const TABLE: [(AgeBand, [Bounds; 7]); 6] = [
+    (
+        AgeBand::Neonate,
+        [
+            Bounds::new(90.0, 100.0, 180.0, 200.0),
+            Bounds::new(50.0, 60.0, 90.0, 110.0),
+            Bounds::new(25.0, 30.0, 60.0, 75.0),
+            Bounds::new(25.0, 30.0, 60.0, 70.0),
+            OXYGEN_SATURATION,
+            // Any fever in a neonate needs immediate review
+            Bounds::new(35.5, 36.5, 37.5, 37.9),
+            // Neonates tolerate lower glucose in the first days of life
+            Bounds::new(2.0, 2.6, 8.0, 15.0),
+        ],
+    ),
+    (
+        AgeBand::Infant,
+        [
+            Bounds::new(90.0, 100.0, 160.0, 190.0),
+            Bounds::new(60.0, 70.0, 100.0, 120.0),
+            Bounds::new(30.0, 35.0, 65.0, 80.0),
+            Bounds::new(20.0, 30.0, 55.0, 65.0),
+            OXYGEN_SATURATION,
+            Bounds::new(35.0, 36.0, 38.0, 39.0),
+            BLOOD_GLUCOSE,
+        ],
+    ),
+    (
+        AgeBand::Child,
+        [
+            Bounds::new(60.0, 70.0, 130.0, 160.0),
+            Bounds::new(70.0, 80.0, 115.0, 135.0),
+            Bounds::new(35.0, 45.0, 75.0, 90.0),
+            Bounds::new(14.0, 18.0, 30.0, 40.0),
+            OXYGEN_SATURATION,
+            TEMPERATURE,
+            BLOOD_GLUCOSE,
+        ],
+    ),
+    (
+        AgeBand::Adolescent,
+        [
+            Bounds::new(50.0, 60.0, 110.0, 140.0),
+            Bounds::new(80.0, 90.0, 130.0, 160.0),
+            Bounds::new(40.0, 55.0, 85.0, 100.0),
+            Bounds::new(10.0, 12.0, 20.0, 28.0),
+            OXYGEN_SATURATION,
+            TEMPERATURE,
+            BLOOD_GLUCOSE,
+        ],
+    ),
+    (
+        AgeBand::Adult,
+        [
+            Bounds::new(50.0, 60.0, 100.0, 120.0),
+            Bounds::new(70.0, 90.0, 139.0, 179.0),
+            Bounds::new(40.0, 60.0, 89.0, 119.0),
+            Bounds::new(9.0, 12.0, 20.0, 24.0),
+            OXYGEN_SATURATION,
+            TEMPERATURE,
+            BLOOD_GLUCOSE,
+        ],
+    ),
+    // Older patients mount a weaker fever and tolerate low pressure worse
+    (
+        AgeBand::Elderly,
+        [
+            Bounds::new(50.0, 60.0, 100.0, 120.0),
+            Bounds::new(80.0, 100.0, 149.0, 179.0),
+            Bounds::new(40.0, 60.0, 89.0, 119.0),
+            Bounds::new(9.0, 12.0, 22.0, 24.0),
+            OXYGEN_SATURATION,
+            Bounds::new(35.0, 36.0, 38.0, 39.5),
+            BLOOD_GLUCOSE,
+        ],
+    ),
+];
+
+pub(super) fn ranges() -> impl Iterator<Item = ReferenceRange> {
+    TABLE.into_iter().flat_map(|(age_band, bounds)| {
+        Measurement::ALL
+            .into_iter()
+            .zip(bounds)
+            .map(move |(measurement, bounds)| ReferenceRange {
+                measurement,
+                age_band,
+                sex: None,
+                bounds,
+            })
+    })
+}
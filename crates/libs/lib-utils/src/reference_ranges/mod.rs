@@ -0,0 +1,371 @@
+//! Vital sign reference ranges by age band and sex. The built-in table is
+//! what the service ships with; clinical governance can override any row
+//! from a JSON file, so thresholds are tuned without a release.
+
+pub mod age_band;
+mod builtin;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::units::{Celsius, MmHg, MmolPerL};
+
+pub use age_band::AgeBand;
+
+#[derive(Debug, Error)]
+pub enum ReferenceRangeError {
+    #[error("Failed to read reference ranges from {path}: {source}")]
+    Io {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid reference ranges: {0}")]
+    InvalidJson(String),
+
+    #[error("{measurement} range for {age_band}: bounds must not decrease")]
+    Unordered {
+        measurement: Measurement,
+        age_band: AgeBand,
+    },
+}
+
+/// Sex a reference range applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+/// Vital signs with reference ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Measurement {
+    HeartRate,
+    SystolicBp,
+    DiastolicBp,
+    RespiratoryRate,
+    OxygenSaturation,
+    Temperature,
+    BloodGlucose,
+}
+
+impl Measurement {
+    pub const ALL: [Measurement; 7] = [
+        Measurement::HeartRate,
+        Measurement::SystolicBp,
+        Measurement::DiastolicBp,
+        Measurement::RespiratoryRate,
+        Measurement::OxygenSaturation,
+        Measurement::Temperature,
+        Measurement::BloodGlucose,
+    ];
+
+    /// Unit the bounds are in
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Measurement::HeartRate => "bpm",
+            Measurement::SystolicBp | Measurement::DiastolicBp => MmHg::SYMBOL,
+            Measurement::RespiratoryRate => "breaths/min",
+            Measurement::OxygenSaturation => "%",
+            Measurement::Temperature => Celsius::SYMBOL,
+            Measurement::BloodGlucose => MmolPerL::SYMBOL,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Measurement::HeartRate => "Heart rate",
+            Measurement::SystolicBp => "Systolic blood pressure",
+            Measurement::DiastolicBp => "Diastolic blood pressure",
+            Measurement::RespiratoryRate => "Respiratory rate",
+            Measurement::OxygenSaturation => "Oxygen saturation",
+            Measurement::Temperature => "Temperature",
+            Measurement::BloodGlucose => "Blood glucose",
+        }
+    }
+}
+
+impl std::fmt::Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+/// Where a value falls against its reference range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RangeStatus {
+    CriticalLow,
+    Low,
+    Normal,
+    High,
+    CriticalHigh,
+}
+
+impl RangeStatus {
+    pub fn is_critical(&self) -> bool {
+        matches!(self, RangeStatus::CriticalLow | RangeStatus::CriticalHigh)
+    }
+}
+
+/// Limits of one measurement: values outside the normal range are abnormal
+/// and values outside the critical range need immediate attention
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bounds {
+    pub critical_low: f32,
+    pub normal_low: f32,
+    pub normal_high: f32,
+    pub critical_high: f32,
+}
+
+impl Bounds {
+    pub const fn new(
+        critical_low: f32,
+        normal_low: f32,
+        normal_high: f32,
+        critical_high: f32,
+    ) -> Self {
+        Self {
+            critical_low,
+            normal_low,
+            normal_high,
+            critical_high,
+        }
+    }
+
+    /// Check that each bound is at least the one before it
+    pub fn is_ordered(&self) -> bool {
+        self.critical_low <= self.normal_low
+            && self.normal_low <= self.normal_high
+            && self.normal_high <= self.critical_high
+    }
+
+    pub fn classify(&self, value: f32) -> RangeStatus {
+        if value < self.critical_low {
+            RangeStatus::CriticalLow
+        } else if value > self.critical_high {
+            RangeStatus::CriticalHigh
+        } else if value < self.normal_low {
+            RangeStatus::Low
+        } else if value > self.normal_high {
+            RangeStatus::High
+        } else {
+            RangeStatus::Normal
+        }
+    }
+}
+
+/// One row of a reference range table. A row without a sex applies to
+/// everyone in the age band; a row for one sex takes precedence over it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceRange {
+    pub measurement: Measurement,
+    pub age_band: AgeBand,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sex: Option<Sex>,
+    #[serde(flatten)]
+    pub bounds: Bounds,
+}
+
+type RangeKey = (Measurement, AgeBand, Option<Sex>);
+
+/// A reference range table. Every measurement has a row for everyone in
+/// every age band, as the table always starts from the built-in one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceRanges {
+    ranges: HashMap<RangeKey, Bounds>,
+}
+
+impl Default for ReferenceRanges {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl ReferenceRanges {
+    /// The ranges the service ships with
+    pub fn builtin() -> Self {
+        let ranges = builtin::ranges()
+            .map(|range| ((range.measurement, range.age_band, range.sex), range.bounds))
+            .collect();
+        Self { ranges }
+    }
+
+    /// Replace or add rows, rejecting bounds that are out of order
+    pub fn with_overrides(
+        mut self,
+        overrides: impl IntoIterator<Item = ReferenceRange>,
+    ) -> Result<Self, ReferenceRangeError> {
+        for range in overrides {
+            if !range.bounds.is_ordered() {
+                return Err(ReferenceRangeError::Unordered {
+                    measurement: range.measurement,
+                    age_band: range.age_band,
+                });
+            }
+            self.ranges
+                .insert((range.measurement, range.age_band, range.sex), range.bounds);
+        }
+        Ok(self)
+    }
+
+    /// The built-in ranges with overrides read from a JSON file, see
+    /// [`ReferenceRanges::from_json`]
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReferenceRangeError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|source| ReferenceRangeError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_json(&json)
+    }
+
+    /// The built-in ranges with overrides from a JSON array of rows, e.g.
+    /// `[{"measurement": "heart_rate", "age_band": "adult", "sex": "female",
+    /// "critical_low": 45, "normal_low": 55, "normal_high": 100,
+    /// "critical_high": 130}]`
+    pub fn from_json(json: &str) -> Result<Self, ReferenceRangeError> {
+        let overrides: Vec<ReferenceRange> = serde_json::from_str(json)
+            .map_err(|e| ReferenceRangeError::InvalidJson(e.to_string()))?;
+        Self::builtin().with_overrides(overrides)
+    }
+
+    /// The bounds of a measurement for a patient, preferring a row for
+    /// their sex
+    pub fn bounds(&self, measurement: Measurement, age_band: AgeBand, sex: Option<Sex>) -> Bounds {
+        let key = |sex| (measurement, age_band, sex);
+        *sex.and_then(|sex| self.ranges.get(&key(Some(sex))))
+            .unwrap_or_else(|| &self.ranges[&key(None)])
+    }
+
+    /// The ranges that apply to one patient
+    pub fn for_patient(&self, age_band: AgeBand, sex: Option<Sex>) -> PatientRanges<'_> {
+        PatientRanges {
+            ranges: self,
+            age_band,
+            sex,
+        }
+    }
+
+    /// Every row, ordered by measurement, age band and sex
+    pub fn rows(&self) -> Vec<ReferenceRange> {
+        let mut rows: Vec<ReferenceRange> = self
+            .ranges
+            .iter()
+            .map(|(&(measurement, age_band, sex), &bounds)| ReferenceRange {
+                measurement,
+                age_band,
+                sex,
+                bounds,
+            })
+            .collect();
+        rows.sort_by_key(|row| (row.measurement, row.age_band, row.sex));
+        rows
+    }
+}
+
+/// The reference ranges for a patient of a given age band and sex
+#[derive(Debug, Clone, Copy)]
+pub struct PatientRanges<'a> {
+    ranges: &'a ReferenceRanges,
+    pub age_band: AgeBand,
+    pub sex: Option<Sex>,
+}
+
+impl PatientRanges<'_> {
+    pub fn bounds(&self, measurement: Measurement) -> Bounds {
+        self.ranges.bounds(measurement, self.age_band, self.sex)
+    }
+
+    pub fn classify(&self, measurement: Measurement, value: f32) -> RangeStatus {
+        self.bounds(measurement).classify(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_is_complete() {
+        let ranges = ReferenceRanges::builtin();
+        assert_eq!(ranges.rows().len(), Measurement::ALL.len() * 6);
+        assert!(ranges.rows().iter().all(|row| row.bounds.is_ordered()));
+
+        let adult = ranges.for_patient(AgeBand::Adult, Some(Sex::Female));
+        assert_eq!(
+            adult.classify(Measurement::HeartRate, 75.0),
+            RangeStatus::Normal
+        );
+        assert_eq!(
+            adult.classify(Measurement::HeartRate, 45.0),
+            RangeStatus::CriticalLow
+        );
+        assert_eq!(
+            adult.classify(Measurement::SystolicBp, 150.0),
+            RangeStatus::High
+        );
+        assert_eq!(
+            adult.classify(Measurement::BloodGlucose, 3.5),
+            RangeStatus::Low
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let json = r#"[
+            {"measurement": "heart_rate", "age_band": "adult", "sex": "female",
+             "critical_low": 45, "normal_low": 55, "normal_high": 100, "critical_high": 130},
+            {"measurement": "temperature", "age_band": "child",
+             "critical_low": 35, "normal_low": 36, "normal_high": 38, "critical_high": 39.5}
+        ]"#;
+        let ranges = ReferenceRanges::from_json(json).unwrap();
+
+        // The sex-specific row wins; others keep the row for everyone
+        let female = ranges.for_patient(AgeBand::Adult, Some(Sex::Female));
+        let male = ranges.for_patient(AgeBand::Adult, Some(Sex::Male));
+        assert_eq!(
+            female.classify(Measurement::HeartRate, 125.0),
+            RangeStatus::High
+        );
+        assert_eq!(
+            male.classify(Measurement::HeartRate, 125.0),
+            RangeStatus::CriticalHigh
+        );
+        assert_eq!(
+            ranges.bounds(Measurement::HeartRate, AgeBand::Adult, None),
+            ReferenceRanges::builtin().bounds(Measurement::HeartRate, AgeBand::Adult, None)
+        );
+
+        let child = ranges.for_patient(AgeBand::Child, None);
+        assert_eq!(
+            child.classify(Measurement::Temperature, 38.4),
+            RangeStatus::High
+        );
+    }
+
+    #[test]
+    fn test_invalid_overrides() {
+        let unordered = r#"[{"measurement": "heart_rate", "age_band": "adult",
+            "critical_low": 60, "normal_low": 50, "normal_high": 100, "critical_high": 120}]"#;
+        assert!(matches!(
+            ReferenceRanges::from_json(unordered),
+            Err(ReferenceRangeError::Unordered {
+                measurement: Measurement::HeartRate,
+                age_band: AgeBand::Adult
+            })
+        ));
+
+        let unknown = r#"[{"measurement": "pulse", "age_band": "adult",
+            "critical_low": 40, "normal_low": 50, "normal_high": 100, "critical_high": 120}]"#;
+        assert!(matches!(
+            ReferenceRanges::from_json(unknown),
+            Err(ReferenceRangeError::InvalidJson(_))
+        ));
+    }
+}
@@ -0,0 +1,257 @@
+//! Retrying fallible async work with exponential backoff, for calls to
+//! databases, Redis and outside services that fail transiently
+
+use std::fmt::Display;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use tracing::warn;
+
+/// An error that can tell whether the failed work is worth trying again
+pub trait Retryable {
+    fn is_retryable(&self) -> bool;
+
+    /// The error for an attempt that ran past its timeout
+    fn timed_out(after: Duration) -> Self;
+}
+
+/// How often and how patiently failed work is retried
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration, // Doubled per retry
+    pub max_delay: Duration,
+    pub max_elapsed: Option<Duration>, // Give up rather than wait past this
+    pub attempt_timeout: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            base_delay: Duration::from_millis(50),
+            max_delay: Duration::from_secs(2),
+            max_elapsed: None,
+            attempt_timeout: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry` (from zero): a random share of the
+    /// exponential backoff, so callers that failed together do not retry
+    /// together
+    pub fn delay(&self, retry: u32) -> Duration {
+        let backoff = self
+            .base_delay
+            .saturating_mul(1 << retry.min(16))
+            .min(self.max_delay);
+        backoff.mul_f64(rand::thread_rng().gen_range(0.0..=1.0))
+    }
+
+    /// Wait before retry number `retry` of work that began at `started`, or
+    /// `None` once the retries or the time allowed have run out
+    pub fn next_delay(&self, retry: u32, started: Instant) -> Option<Duration> {
+        if retry >= self.max_retries {
+            return None;
+        }
+        let delay = self.delay(retry);
+        match self.max_elapsed {
+            Some(max_elapsed) if started.elapsed() + delay > max_elapsed => None,
+            _ => Some(delay),
+        }
+    }
+
+    /// Run one attempt, failing it if it runs past `attempt_timeout`
+    pub async fn attempt<T, E, Fut>(&self, attempt: Fut) -> Result<T, E>
+    where
+        E: Retryable,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        match self.attempt_timeout {
+            Some(timeout) => with_timeout(timeout, attempt).await,
+            None => attempt.await,
+        }
+    }
+}
+
+/// Fail `work` with `Retryable::timed_out` if it does not finish in time
+pub async fn with_timeout<T, E, Fut>(timeout: Duration, work: Fut) -> Result<T, E>
+where
+    E: Retryable,
+    Fut: Future<Output = Result<T, E>>,
+{
+    tokio::time::timeout(timeout, work)
+        .await
+        .unwrap_or_else(|_| Err(E::timed_out(timeout)))
+}
+
+/// Run `operation`, retrying it after retryable errors. The operation must
+/// be safe to repeat after any of those.
+pub async fn retry<T, E, F, Fut>(policy: &RetryPolicy, operation: F) -> Result<T, E>
+where
+    E: Retryable + Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(policy, E::is_retryable, operation).await
+}
+
+/// Run `operation`, retrying it after errors `should_retry` accepts
+pub async fn retry_if<T, E, F, Fut, P>(
+    policy: &RetryPolicy,
+    should_retry: P,
+    mut operation: F,
+) -> Result<T, E>
+where
+    E: Retryable + Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+{
+    let started = Instant::now();
+    let mut retry = 0;
+    loop {
+        match policy.attempt(operation()).await {
+            Err(error) if should_retry(&error) => {
+                let Some(delay) = policy.next_delay(retry, started) else {
+                    return Err(error);
+                };
+                warn!(
+                    "Retrying in {:?} ({} of {}): {}",
+                    delay,
+                    retry + 1,
+                    policy.max_retries,
+                    error
+                );
+                tokio::time::sleep(delay).await;
+                retry += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        Unavailable,
+        Invalid,
+        TimedOut,
+    }
+
+    impl Display for TestError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    impl Retryable for TestError {
+        fn is_retryable(&self) -> bool {
+            matches!(self, TestError::Unavailable | TestError::TimedOut)
+        }
+
+        fn timed_out(_after: Duration) -> Self {
+            TestError::TimedOut
+        }
+    }
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_delay_stays_within_backoff() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(300),
+            ..Default::default()
+        };
+        for _ in 0..100 {
+            assert!(policy.delay(0) <= Duration::from_millis(100));
+            assert!(policy.delay(1) <= Duration::from_millis(200));
+            assert!(policy.delay(10) <= Duration::from_millis(300));
+        }
+    }
+
+    #[test]
+    fn test_next_delay_respects_limits() {
+        let started = Instant::now();
+        assert!(policy(2).next_delay(1, started).is_some());
+        assert!(policy(2).next_delay(2, started).is_none());
+
+        let policy = RetryPolicy {
+            max_elapsed: Some(Duration::from_secs(1)),
+            ..policy(5)
+        };
+        assert!(policy.next_delay(0, started).is_some());
+        let long_ago = started - Duration::from_secs(2);
+        assert!(policy.next_delay(0, long_ago).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retries_retryable_errors_only() {
+        let calls = AtomicU32::new(0);
+        let result = retry(&policy(2), || async {
+            match calls.fetch_add(1, Ordering::Relaxed) {
+                0 | 1 => Err(TestError::Unavailable),
+                _ => Ok(42),
+            }
+        })
+        .await;
+        assert_eq!(result, Ok(42));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry(&policy(2), || async {
+            calls.fetch_add(1, Ordering::Relaxed);
+            Err(TestError::Invalid)
+        })
+        .await;
+        assert_eq!(result, Err(TestError::Invalid));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = retry_if(
+            &policy(2),
+            |_| true,
+            || async {
+                calls.fetch_add(1, Ordering::Relaxed);
+                Err(TestError::Invalid)
+            },
+        )
+        .await;
+        assert_eq!(result, Err(TestError::Invalid));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_attempt_timeout() {
+        let policy = RetryPolicy {
+            attempt_timeout: Some(Duration::from_millis(10)),
+            ..policy(1)
+        };
+        let calls = AtomicU32::new(0);
+        let result = retry(&policy, || async {
+            if calls.fetch_add(1, Ordering::Relaxed) == 0 {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+            Ok::<_, TestError>("answered")
+        })
+        .await;
+        assert_eq!(result, Ok("answered"));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+}
@@ -0,0 +1,270 @@
+//! Hijri (Islamic) calendar dates, for official documents that carry both
+//! calendars. Uses the tabular calendar: a 30-year cycle of 354 and 355 day
+//! years counted from the civil epoch of 16 July 622 (Julian). Dates can
+//! differ by a day from the Umm al-Qura calendar or moon sighting, which
+//! cannot be computed arithmetically.
+
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::format::Locale;
+
+/// Julian day number of 1 Muharram 1 AH
+const EPOCH_JULIAN_DAY: i64 = 1_948_440;
+
+/// Offset from chrono's days-from-CE count to the Julian day number
+const CE_TO_JULIAN_DAY: i64 = 1_721_425;
+
+/// Latest year accepted, well past anything a record will carry
+const MAX_YEAR: u16 = 9_999;
+
+const MONTHS_EN: [&str; 12] = [
+    "Muharram",
+    "Safar",
+    "Rabi al-Awwal",
+    "Rabi al-Thani",
+    "Jumada al-Ula",
+    "Jumada al-Akhirah",
+    "Rajab",
+    "Sha'ban",
+    "Ramadan",
+    "Shawwal",
+    "Dhu al-Qadah",
+    "Dhu al-Hijjah",
+];
+
+const MONTHS_AR: [&str; 12] = [
+    "محرم",
+    "صفر",
+    "ربيع الأول",
+    "ربيع الآخر",
+    "جمادى الأولى",
+    "جمادى الآخرة",
+    "رجب",
+    "شعبان",
+    "رمضان",
+    "شوال",
+    "ذو القعدة",
+    "ذو الحجة",
+];
+
+const GREGORIAN_MONTHS_AR: [&str; 12] = [
+    "يناير",
+    "فبراير",
+    "مارس",
+    "أبريل",
+    "مايو",
+    "يونيو",
+    "يوليو",
+    "أغسطس",
+    "سبتمبر",
+    "أكتوبر",
+    "نوفمبر",
+    "ديسمبر",
+];
+
+/// A date in the tabular Hijri calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HijriDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl HijriDate {
+    /// The date for a year, month (1-12) and day, if it exists
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        let valid = (1..=MAX_YEAR).contains(&year)
+            && (1..=12).contains(&month)
+            && (1..=Self::days_in_month(year, month)).contains(&day);
+        valid.then_some(Self { year, month, day })
+    }
+
+    /// The Hijri date falling on a Gregorian date. Dates before the Hijri
+    /// epoch have none.
+    pub fn from_gregorian(date: NaiveDate) -> Option<Self> {
+        let julian_day = i64::from(date.num_days_from_ce()) + CE_TO_JULIAN_DAY;
+        if julian_day < EPOCH_JULIAN_DAY {
+            return None;
+        }
+        let year = (30 * (julian_day - EPOCH_JULIAN_DAY) + 10_646) / 10_631;
+        let year = u16::try_from(year).ok().filter(|year| *year <= MAX_YEAR)?;
+
+        let mut day_of_year = julian_day - Self::julian_day(year, 1, 1);
+        let mut month = 1;
+        while day_of_year >= i64::from(Self::days_in_month(year, month)) {
+            day_of_year -= i64::from(Self::days_in_month(year, month));
+            month += 1;
+        }
+        Self::new(year, month, day_of_year as u8 + 1)
+    }
+
+    /// The Gregorian date this date falls on
+    pub fn to_gregorian(&self) -> NaiveDate {
+        let days_from_ce = Self::julian_day(self.year, self.month, self.day) - CE_TO_JULIAN_DAY;
+        NaiveDate::from_num_days_from_ce_opt(days_from_ce as i32)
+            .expect("Hijri years up to 9999 fall within chrono's range")
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Check if a year has 355 days, which happens 11 times in each 30
+    /// year cycle
+    pub fn is_leap_year(year: u16) -> bool {
+        (14 + 11 * u32::from(year)) % 30 < 11
+    }
+
+    /// Odd months have 30 days and even months 29, except that the last
+    /// month of a leap year has 30
+    pub fn days_in_month(year: u16, month: u8) -> u8 {
+        if month % 2 == 1 || (month == 12 && Self::is_leap_year(year)) {
+            30
+        } else {
+            29
+        }
+    }
+
+    /// Get the month's name in a language
+    pub fn month_name(&self, locale: Locale) -> &'static str {
+        let index = usize::from(self.month - 1);
+        match locale {
+            Locale::En => MONTHS_EN[index],
+            Locale::Ar => MONTHS_AR[index],
+        }
+    }
+
+    /// Write the date out in a language, e.g. `3 Ramadan 1445 AH`
+    pub fn format(&self, locale: Locale) -> String {
+        let era = match locale {
+            Locale::En => "AH",
+            Locale::Ar => "هـ",
+        };
+        format!(
+            "{} {} {} {}",
+            self.day,
+            self.month_name(locale),
+            self.year,
+            era
+        )
+    }
+
+    fn julian_day(year: u16, month: u8, day: u8) -> i64 {
+        let (year, month) = (i64::from(year), i64::from(month));
+        i64::from(day)
+            + (59 * (month - 1) + 1) / 2
+            + (year - 1) * 354
+            + (3 + 11 * year) / 30
+            + EPOCH_JULIAN_DAY
+            - 1
+    }
+}
+
+impl fmt::Display for HijriDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(Locale::En))
+    }
+}
+
+/// Write a date out in both calendars, as official documents require, e.g.
+/// `14 March 2024 (4 Ramadan 1445 AH)`. Dates before the Hijri epoch are
+/// written in the Gregorian calendar only.
+pub fn format_dual_date(date: NaiveDate, locale: Locale) -> String {
+    let hijri = HijriDate::from_gregorian(date);
+    match locale {
+        Locale::En => {
+            let gregorian = format!("{} {}", date.format("%-d %B"), date.year());
+            match hijri {
+                Some(hijri) => format!("{} ({})", gregorian, hijri.format(locale)),
+                None => gregorian,
+            }
+        }
+        Locale::Ar => {
+            let gregorian = format!(
+                "{} {} {} م",
+                date.day(),
+                GREGORIAN_MONTHS_AR[date.month0() as usize],
+                date.year()
+            );
+            match hijri {
+                Some(hijri) => format!("{} الموافق {}", gregorian, hijri.format(locale)),
+                None => gregorian,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gregorian(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_conversion() {
+        let epoch = HijriDate::new(1, 1, 1).unwrap();
+        assert_eq!(epoch.to_gregorian(), gregorian(622, 7, 19));
+        assert_eq!(
+            HijriDate::from_gregorian(gregorian(622, 7, 19)),
+            Some(epoch)
+        );
+        assert_eq!(HijriDate::from_gregorian(gregorian(622, 7, 18)), None);
+
+        let ramadan = HijriDate::from_gregorian(gregorian(2024, 3, 11)).unwrap();
+        assert_eq!(
+            (ramadan.year(), ramadan.month(), ramadan.day()),
+            (1445, 9, 1)
+        );
+        assert_eq!(ramadan.to_string(), "1 Ramadan 1445 AH");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut date = gregorian(1990, 1, 1);
+        while date < gregorian(2040, 1, 1) {
+            let hijri = HijriDate::from_gregorian(date).unwrap();
+            assert_eq!(hijri.to_gregorian(), date);
+            date = date.succ_opt().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_month_lengths() {
+        assert!(HijriDate::is_leap_year(1445));
+        assert!(!HijriDate::is_leap_year(1446));
+        assert!(HijriDate::new(1445, 12, 30).is_some());
+        assert!(HijriDate::new(1446, 12, 30).is_none());
+        assert!(HijriDate::new(1446, 2, 30).is_none());
+        assert!(HijriDate::new(1446, 13, 1).is_none());
+        assert!(HijriDate::new(0, 1, 1).is_none());
+    }
+
+    #[test]
+    fn test_dual_date() {
+        let date = gregorian(2024, 3, 14);
+        assert_eq!(
+            format_dual_date(date, Locale::En),
+            "14 March 2024 (4 Ramadan 1445 AH)"
+        );
+        assert_eq!(
+            format_dual_date(date, Locale::Ar),
+            "14 مارس 2024 م الموافق 4 رمضان 1445 هـ"
+        );
+        assert_eq!(
+            format_dual_date(gregorian(600, 1, 1), Locale::En),
+            "1 January 600"
+        );
+    }
+}
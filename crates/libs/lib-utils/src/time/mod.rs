@@ -1 +1,5 @@
-// pub mod time;
+//! Calendars and date formatting
+
+pub mod hijri;
+
+pub use hijri::{format_dual_date, HijriDate};
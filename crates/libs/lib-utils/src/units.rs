@@ -0,0 +1,123 @@
+//! Measurement units, so a reading in one unit cannot be passed where
+//! another is expected. Vitals are stored in Celsius, kilograms, mmol/L and
+//! mmHg; devices that report in other units are converted on the way in.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Milligrams per decilitre in one millimole per litre of glucose
+const MG_PER_DL_PER_MMOL: f32 = 18.0;
+
+/// Pounds in one kilogram
+const POUNDS_PER_KG: f32 = 2.204_622_6;
+
+/// Millimetres of mercury in one kilopascal
+const MMHG_PER_KPA: f32 = 7.500_617;
+
+macro_rules! define_unit {
+    ($(#[$meta:meta])* $name:ident, $symbol:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub f32);
+
+        impl $name {
+            pub const SYMBOL: &'static str = $symbol;
+
+            pub const fn value(&self) -> f32 {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, Self::SYMBOL)
+            }
+        }
+    };
+}
+
+macro_rules! convert {
+    ($from:ident => $to:ident, |$value:ident| $body:expr) => {
+        impl From<$from> for $to {
+            fn from(reading: $from) -> Self {
+                let $value = reading.0;
+                Self($body)
+            }
+        }
+    };
+}
+
+define_unit!(
+    /// A temperature in degrees Celsius
+    Celsius,
+    "°C"
+);
+define_unit!(
+    /// A temperature in degrees Fahrenheit
+    Fahrenheit,
+    "°F"
+);
+define_unit!(
+    /// A weight in kilograms
+    Kilograms,
+    "kg"
+);
+define_unit!(
+    /// A weight in pounds
+    Pounds,
+    "lb"
+);
+define_unit!(
+    /// A blood glucose concentration in millimoles per litre
+    MmolPerL,
+    "mmol/L"
+);
+define_unit!(
+    /// A blood glucose concentration in milligrams per decilitre
+    MgPerDl,
+    "mg/dL"
+);
+define_unit!(
+    /// A pressure in millimetres of mercury
+    MmHg,
+    "mmHg"
+);
+define_unit!(
+    /// A pressure in kilopascals
+    Kilopascals,
+    "kPa"
+);
+
+convert!(Fahrenheit => Celsius, |value| (value - 32.0) * 5.0 / 9.0);
+convert!(Celsius => Fahrenheit, |value| value * 9.0 / 5.0 + 32.0);
+convert!(Pounds => Kilograms, |value| value / POUNDS_PER_KG);
+convert!(Kilograms => Pounds, |value| value * POUNDS_PER_KG);
+convert!(MgPerDl => MmolPerL, |value| value / MG_PER_DL_PER_MMOL);
+convert!(MmolPerL => MgPerDl, |value| value * MG_PER_DL_PER_MMOL);
+convert!(Kilopascals => MmHg, |value| value * MMHG_PER_KPA);
+convert!(MmHg => Kilopascals, |value| value / MMHG_PER_KPA);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert!(close(Celsius::from(Fahrenheit(98.6)).value(), 37.0));
+        assert!(close(Fahrenheit::from(Celsius(-40.0)).value(), -40.0));
+        assert!(close(Kilograms::from(Pounds(154.0)).value(), 69.85));
+        assert_eq!(MmolPerL::from(MgPerDl(90.0)), MmolPerL(5.0));
+        assert!(close(MmHg::from(Kilopascals(16.0)).value(), 120.01));
+
+        let weight = Kilograms(72.5);
+        assert!(close(Kilograms::from(Pounds::from(weight)).value(), 72.5));
+        assert_eq!(weight.to_string(), "72.5 kg");
+        assert_eq!(serde_json::to_string(&weight).unwrap(), "72.5");
+    }
+}
@@ -0,0 +1,212 @@
+//! Emirates ID numbers, `784-YYYY-NNNNNNN-C`: the UAE country code, the
+//! holder's birth year, a serial number and a Luhn check digit over the
+//! first 14 digits
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, Utc};
+use thiserror::Error;
+
+/// ISO 3166 numeric code of the UAE, the first segment of every ID
+const COUNTRY_CODE: &str = "784";
+
+/// Earliest birth year accepted as plausible
+const MIN_BIRTH_YEAR: u16 = 1900;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum EmiratesIdError {
+    #[error("Emirates ID must be 15 digits, written 784-YYYY-NNNNNNN-C or without dashes")]
+    InvalidFormat,
+
+    #[error("Emirates ID must start with 784")]
+    InvalidCountryCode,
+
+    #[error("Emirates ID birth year {0} is not plausible")]
+    ImplausibleBirthYear(u16),
+
+    #[error("Emirates ID check digit does not match")]
+    InvalidCheckDigit,
+}
+
+/// A structurally valid Emirates ID. Displays as `784-YYYY-NNNNNNN-C`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmiratesId {
+    birth_year: u16,
+    serial: u32,
+    check_digit: u8,
+}
+
+impl EmiratesId {
+    /// The ID for a birth year and serial number, with its check digit
+    pub fn new(birth_year: u16, serial: u32) -> Self {
+        let serial = serial % 10_000_000;
+        let payload = format!("{}{:04}{:07}", COUNTRY_CODE, birth_year % 10_000, serial);
+        Self {
+            birth_year,
+            serial,
+            check_digit: luhn_check_digit(&payload),
+        }
+    }
+
+    pub fn birth_year(&self) -> u16 {
+        self.birth_year
+    }
+
+    pub fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    /// The ID with all but the last four digits hidden, for logs and
+    /// screens that only need to tell IDs apart
+    pub fn masked(&self) -> String {
+        format!(
+            "{}-****-****{:03}-{}",
+            COUNTRY_CODE,
+            self.serial % 1_000,
+            self.check_digit
+        )
+    }
+}
+
+impl FromStr for EmiratesId {
+    type Err = EmiratesIdError;
+
+    /// Parse 15 digits, either plain or dashed as `784-YYYY-NNNNNNN-C`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let digits = if s.contains('-') {
+            let segments: Vec<&str> = s.split('-').collect();
+            let lengths: Vec<usize> = segments.iter().map(|segment| segment.len()).collect();
+            if lengths != [3, 4, 7, 1] {
+                return Err(EmiratesIdError::InvalidFormat);
+            }
+            segments.concat()
+        } else {
+            s.to_string()
+        };
+        if digits.len() != 15 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(EmiratesIdError::InvalidFormat);
+        }
+
+        if &digits[..3] != COUNTRY_CODE {
+            return Err(EmiratesIdError::InvalidCountryCode);
+        }
+        let birth_year: u16 = digits[3..7]
+            .parse()
+            .map_err(|_| EmiratesIdError::InvalidFormat)?;
+        let current_year = Utc::now().year() as u16;
+        if !(MIN_BIRTH_YEAR..=current_year).contains(&birth_year) {
+            return Err(EmiratesIdError::ImplausibleBirthYear(birth_year));
+        }
+        let check_digit = digits.as_bytes()[14] - b'0';
+        if luhn_check_digit(&digits[..14]) != check_digit {
+            return Err(EmiratesIdError::InvalidCheckDigit);
+        }
+
+        Ok(Self {
+            birth_year,
+            serial: digits[7..14]
+                .parse()
+                .map_err(|_| EmiratesIdError::InvalidFormat)?,
+            check_digit,
+        })
+    }
+}
+
+impl fmt::Display for EmiratesId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{:04}-{:07}-{}",
+            COUNTRY_CODE, self.birth_year, self.serial, self.check_digit
+        )
+    }
+}
+
+/// Check an Emirates ID's structure, birth year and check digit
+pub fn is_valid_emirates_id(value: &str) -> bool {
+    value.parse::<EmiratesId>().is_ok()
+}
+
+/// Hide all but the last four digits of an Emirates ID for display. Values
+/// that are not Emirates IDs are hidden except for their last four
+/// characters.
+pub fn mask_emirates_id(value: &str) -> String {
+    match value.parse::<EmiratesId>() {
+        Ok(id) => id.masked(),
+        Err(_) => {
+            let chars: Vec<char> = value.trim().chars().collect();
+            let shown = chars.len().saturating_sub(4);
+            chars
+                .iter()
+                .enumerate()
+                .map(|(i, c)| if i < shown { '*' } else { *c })
+                .collect()
+        }
+    }
+}
+
+/// The digit that makes `digits` followed by it pass the Luhn check
+pub fn luhn_check_digit(digits: &str) -> u8 {
+    let sum: u32 = digits
+        .chars()
+        .rev()
+        .filter_map(|c| c.to_digit(10))
+        .enumerate()
+        .map(|(i, digit)| match (i % 2 == 0, digit * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => digit,
+        })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luhn_check_digit() {
+        assert_eq!(luhn_check_digit("7992739871"), 3);
+        assert_eq!(luhn_check_digit("0"), 0);
+    }
+
+    #[test]
+    fn test_parsing() {
+        let id: EmiratesId = "784-1990-1234567-6".parse().unwrap();
+        assert_eq!((id.birth_year(), id.serial()), (1990, 1_234_567));
+        assert_eq!(id, "784199012345676".parse().unwrap());
+        assert_eq!(id, EmiratesId::new(1990, 1_234_567));
+        assert_eq!(id.to_string(), "784-1990-1234567-6");
+
+        let error = |value: &str| value.parse::<EmiratesId>().unwrap_err();
+        assert_eq!(
+            error("784-1990-1234567-1"),
+            EmiratesIdError::InvalidCheckDigit
+        );
+        assert_eq!(error("7841-990-1234567-6"), EmiratesIdError::InvalidFormat);
+        assert_eq!(error("78419901234567"), EmiratesIdError::InvalidFormat);
+        assert_eq!(error("invalid-id"), EmiratesIdError::InvalidFormat);
+        assert_eq!(
+            error("123-1990-1234567-6"),
+            EmiratesIdError::InvalidCountryCode
+        );
+        assert_eq!(
+            error(&EmiratesId::new(1850, 1).to_string()),
+            EmiratesIdError::ImplausibleBirthYear(1850)
+        );
+        assert_eq!(
+            error(&EmiratesId::new(2999, 1).to_string()),
+            EmiratesIdError::ImplausibleBirthYear(2999)
+        );
+    }
+
+    #[test]
+    fn test_masking() {
+        assert_eq!(mask_emirates_id("784199012345676"), "784-****-****567-6");
+        assert_eq!(mask_emirates_id("N1234567"), "****4567");
+        assert_eq!(mask_emirates_id("12"), "12");
+    }
+}
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+
+/// One rejected field of a request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String, // Path, e.g. `emergency_contacts[0].phone_number`
+    pub code: String,  // Machine-readable reason, e.g. `required`, `out_of_range`
+    pub message: String,
+    // Never set for identifiers, names or secrets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejected_value: Option<serde_json::Value>,
+}
+
+/// Every problem found while validating a request, so clients can show them
+/// next to the fields at once instead of one round trip per mistake
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a rejected field
+    pub fn add(&mut self, field: impl Into<String>, code: &str, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            code: code.to_string(),
+            message: message.into(),
+            rejected_value: None,
+        });
+    }
+
+    /// Record a rejected field along with the value that was sent. Only for
+    /// values that are safe to echo back, such as measurements and counts.
+    pub fn add_with_value(
+        &mut self,
+        field: impl Into<String>,
+        code: &str,
+        message: impl Into<String>,
+        value: impl Serialize,
+    ) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            code: code.to_string(),
+            message: message.into(),
+            rejected_value: serde_json::to_value(value).ok(),
+        });
+    }
+
+    /// Add the errors of a nested object, prefixing their field paths
+    pub fn extend_nested(&mut self, prefix: &str, nested: ValidationErrors) {
+        for mut error in nested.errors {
+            error.field = if error.field.is_empty() {
+                prefix.to_string()
+            } else if error.field.starts_with('[') {
+                format!("{}{}", prefix, error.field)
+            } else {
+                format!("{}.{}", prefix, error.field)
+            };
+            self.errors.push(error);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, FieldError> {
+        self.errors.iter()
+    }
+
+    /// Check if a field (or anything nested in it) was rejected
+    pub fn has_field(&self, field: &str) -> bool {
+        self.errors.iter().any(|error| {
+            error.field == field
+                || error
+                    .field
+                    .strip_prefix(field)
+                    .is_some_and(|rest| rest.starts_with(['.', '[']))
+        })
+    }
+
+    /// Get the messages of all errors
+    pub fn messages(&self) -> Vec<String> {
+        self.errors
+            .iter()
+            .map(|error| error.message.clone())
+            .collect()
+    }
+
+    /// `Ok` when nothing was rejected
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl std::ops::Index<usize> for ValidationErrors {
+    type Output = FieldError;
+
+    fn index(&self, index: usize) -> &FieldError {
+        &self.errors[index]
+    }
+}
+
+impl IntoIterator for ValidationErrors {
+    type Item = FieldError;
+    type IntoIter = std::vec::IntoIter<FieldError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl Extend<FieldError> for ValidationErrors {
+    fn extend<I: IntoIterator<Item = FieldError>>(&mut self, errors: I) {
+        self.errors.extend(errors);
+    }
+}
+
+impl std::fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.messages().join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collects_errors() {
+        let mut errors = ValidationErrors::new();
+        assert!(errors.clone().into_result().is_ok());
+
+        errors.add("first_name", "required", "First name is required");
+        errors.add_with_value("age", "out_of_range", "Age must be between 0 and 150", 200);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[1].rejected_value, Some(serde_json::json!(200)));
+        assert_eq!(
+            errors.to_string(),
+            "First name is required; Age must be between 0 and 150"
+        );
+        assert!(errors.into_result().is_err());
+    }
+
+    #[test]
+    fn test_nested_paths() {
+        let mut contact = ValidationErrors::new();
+        contact.add("phone_number", "invalid_format", "Invalid phone number");
+
+        let mut errors = ValidationErrors::new();
+        errors.extend_nested("emergency_contacts[0]", contact);
+        assert_eq!(errors[0].field, "emergency_contacts[0].phone_number");
+        assert!(errors.has_field("emergency_contacts"));
+        assert!(errors.has_field("emergency_contacts[0].phone_number"));
+        assert!(!errors.has_field("emergency"));
+    }
+
+    #[test]
+    fn test_serialization() {
+        let mut errors = ValidationErrors::new();
+        errors.add("email", "invalid_format", "Invalid email format");
+
+        let json = serde_json::to_value(&errors).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "field": "email",
+                "code": "invalid_format",
+                "message": "Invalid email format"
+            }])
+        );
+    }
+}
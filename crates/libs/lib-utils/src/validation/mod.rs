@@ -1 +1,23 @@
-// pub mod validation;
+//! Validation of requests, identifiers and other formatted values
+
+pub mod emirates_id;
+pub mod errors;
+pub mod rules;
+
+pub use emirates_id::{
+    is_valid_emirates_id, luhn_check_digit, mask_emirates_id, EmiratesId, EmiratesIdError,
+};
+pub use errors::{FieldError, ValidationErrors};
+pub use regex::Regex;
+
+/// A value that can check itself before it is acted on, reporting every
+/// rejected field at once
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+impl<T: Validate> Validate for Option<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.as_ref().map_or(Ok(()), Validate::validate)
+    }
+}
@@ -0,0 +1,229 @@
+//! Reusable field rules for `Validate` implementations. Each rule records
+//! an error with a standard code when the value is rejected and returns
+//! whether it passed, so later rules can be skipped for a field that has
+//! already failed.
+
+use std::ops::RangeInclusive;
+
+use regex::Regex;
+use serde::Serialize;
+
+use super::{Validate, ValidationErrors};
+
+/// The value must not be blank
+pub fn required(
+    errors: &mut ValidationErrors,
+    field: &str,
+    value: &str,
+    message: impl Into<String>,
+) -> bool {
+    let passed = !value.trim().is_empty();
+    if !passed {
+        errors.add(field, "required", message);
+    }
+    passed
+}
+
+/// The value must have between `min` and `max` characters
+pub fn length(
+    errors: &mut ValidationErrors,
+    field: &str,
+    value: &str,
+    min: usize,
+    max: usize,
+    message: impl Into<String>,
+) -> bool {
+    let count = value.chars().count();
+    if count < min {
+        errors.add(field, "too_short", message);
+        false
+    } else if count > max {
+        errors.add(field, "too_long", message);
+        false
+    } else {
+        true
+    }
+}
+
+/// The value must have at most `max` characters
+pub fn max_length(
+    errors: &mut ValidationErrors,
+    field: &str,
+    value: &str,
+    max: usize,
+    message: impl Into<String>,
+) -> bool {
+    length(errors, field, value, 0, max, message)
+}
+
+/// The value must lie within `range`. The value is echoed back, so only use
+/// this for measurements and counts.
+pub fn range<T: PartialOrd + Serialize>(
+    errors: &mut ValidationErrors,
+    field: &str,
+    value: T,
+    range: RangeInclusive<T>,
+    message: impl Into<String>,
+) -> bool {
+    let passed = range.contains(&value);
+    if !passed {
+        errors.add_with_value(field, "out_of_range", message, value);
+    }
+    passed
+}
+
+/// The value must match `pattern`, which should be anchored with `^` and `$`
+pub fn pattern(
+    errors: &mut ValidationErrors,
+    field: &str,
+    value: &str,
+    pattern: &Regex,
+    message: impl Into<String>,
+) -> bool {
+    let passed = pattern.is_match(value);
+    if !passed {
+        errors.add(field, "invalid_format", message);
+    }
+    passed
+}
+
+/// The value must be one of `allowed`
+pub fn one_of(
+    errors: &mut ValidationErrors,
+    field: &str,
+    value: &str,
+    allowed: &[&str],
+    message: impl Into<String>,
+) -> bool {
+    let passed = allowed.contains(&value);
+    if !passed {
+        errors.add(field, "not_allowed", message);
+    }
+    passed
+}
+
+/// Validate a nested value, reporting its errors under `field`
+pub fn nested<T: Validate + ?Sized>(errors: &mut ValidationErrors, field: &str, value: &T) -> bool {
+    match value.validate() {
+        Ok(()) => true,
+        Err(nested) => {
+            errors.extend_nested(field, nested);
+            false
+        }
+    }
+}
+
+/// Validate every item of a list, reporting errors under `field[index]`
+pub fn each<T: Validate>(errors: &mut ValidationErrors, field: &str, items: &[T]) -> bool {
+    items
+        .iter()
+        .enumerate()
+        .fold(true, |passed, (index, item)| {
+            nested(errors, &format!("{}[{}]", field, index), item) && passed
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Contact {
+        phone_number: String,
+    }
+
+    impl Validate for Contact {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            required(
+                &mut errors,
+                "phone_number",
+                &self.phone_number,
+                "Phone number is required",
+            );
+            errors.into_result()
+        }
+    }
+
+    #[test]
+    fn test_rules() {
+        let mut errors = ValidationErrors::new();
+        assert!(required(&mut errors, "name", "Amina", "Name is required"));
+        assert!(!required(&mut errors, "name", "  ", "Name is required"));
+        assert!(length(&mut errors, "name", "عائشة", 2, 5, "2-5 characters"));
+        assert!(!length(&mut errors, "code", "A", 2, 5, "2-5 characters"));
+        assert!(!length(
+            &mut errors,
+            "code",
+            "ABCDEF",
+            2,
+            5,
+            "2-5 characters"
+        ));
+        assert!(max_length(&mut errors, "code", "ABCDE", 5, "At most 5"));
+        assert!(range(&mut errors, "age", 30, 0..=150, "0-150"));
+        assert!(!range(&mut errors, "age", 200, 0..=150, "0-150"));
+        assert!(!one_of(
+            &mut errors,
+            "kind",
+            "fax",
+            &["email", "sms"],
+            "email or sms"
+        ));
+
+        let codes: Vec<&str> = errors.iter().map(|error| error.code.as_str()).collect();
+        assert_eq!(
+            codes,
+            [
+                "required",
+                "too_short",
+                "too_long",
+                "out_of_range",
+                "not_allowed"
+            ]
+        );
+        assert_eq!(errors[3].rejected_value, Some(serde_json::json!(200)));
+    }
+
+    #[test]
+    fn test_pattern() {
+        let call_sign = Regex::new("^[A-Z0-9-]{2,20}$").unwrap();
+        let mut errors = ValidationErrors::new();
+        assert!(pattern(
+            &mut errors,
+            "call_sign",
+            "DXB-07",
+            &call_sign,
+            "Invalid"
+        ));
+        assert!(!pattern(
+            &mut errors,
+            "call_sign",
+            "dxb DXB-07",
+            &call_sign,
+            "Invalid"
+        ));
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        let contacts = vec![
+            Contact {
+                phone_number: "+971501234567".to_string(),
+            },
+            Contact {
+                phone_number: String::new(),
+            },
+        ];
+        let mut errors = ValidationErrors::new();
+        assert!(!each(&mut errors, "emergency_contacts", &contacts));
+        assert_eq!(errors[0].field, "emergency_contacts[1].phone_number");
+
+        assert!(None::<Contact>.validate().is_ok());
+        assert!(Some(Contact {
+            phone_number: String::new()
+        })
+        .validate()
+        .is_err());
+    }
+}
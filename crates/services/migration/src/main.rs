@@ -1,12 +1,23 @@
 //! Database migration tool for Dubai Healthcare Emergency Response System
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use lib_core::config::DatabaseConfig;
+use lib_core::store::{run_migrations, Db};
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+
     println!("Running database migrations...");
-    
-    // TODO: Implement database migrations
-    
+
+    let config = DatabaseConfig::from_env()?;
+    let db = Db::new(config.create_pool().await?);
+
+    let applied = run_migrations(&db)
+        .await
+        .context("Failed to run database migrations")?;
+
+    println!("Migrations completed ({} applied)", applied);
+
     Ok(())
 }
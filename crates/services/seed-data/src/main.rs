@@ -1,12 +1,26 @@
 //! Seed data generator for Dubai Healthcare Emergency Response System
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use lib_core::config::{AppConfig, DatabaseConfig};
+use lib_core::seed::seed;
+use lib_core::store::Db;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    println!("Seeding development data...");
-    
-    // TODO: Implement data seeding
-    
+    dotenvy::dotenv().ok();
+
+    let environment = AppConfig::detect_environment();
+    let password = std::env::var("SEED_PASSWORD")
+        .context("SEED_PASSWORD must be set for the demo accounts")?;
+
+    println!("Seeding {} data...", environment.name());
+
+    let config = DatabaseConfig::from_env()?;
+    let db = Db::new(config.create_pool().await?);
+
+    let report = seed(&environment, &db, &password).await?;
+
+    println!("Seeding completed ({})", report);
+
     Ok(())
 }
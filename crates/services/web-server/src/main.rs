@@ -2,17 +2,21 @@
 //! Main entry point for the Axum web server
 
 use anyhow::Result;
-use tracing_subscriber;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, EnvFilter};
 
-mod server;
-mod web;
-mod extractors;
-mod responses;
+use lib_utils::format::MaskedFields;
+use web_server::server;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
+    // Initialize tracing; the filter is replaced once the configuration is loaded.
+    // Personal details in log fields are masked before they are written.
+    let (log_filter, log_filter_handle) = reload::Layer::new(EnvFilter::from_default_env());
+    tracing_subscriber::registry()
+        .with(log_filter)
+        .with(fmt::layer().fmt_fields(MaskedFields))
+        .init();
 
     // Load environment variables
     dotenvy::dotenv().ok();
@@ -20,7 +24,7 @@ async fn main() -> Result<()> {
     tracing::info!("Starting Dubai Healthcare Emergency Response System");
 
     // Start the server
-    server::start().await?;
+    server::start(log_filter_handle).await?;
 
     Ok(())
 }
@@ -1 +1,52 @@
 // pub mod responses;
+
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use tracing::warn;
+
+use lib_types::errors::{ApiErrorResponse, AppError};
+
+/// Handler error that renders as the standard JSON error body
+#[derive(Debug)]
+pub struct ApiError(pub AppError);
+
+/// Result type returned by handlers
+pub type ApiResult<T> = Result<T, ApiError>;
+
+impl<E> From<E> for ApiError
+where
+    E: Into<AppError>,
+{
+    fn from(error: E) -> Self {
+        Self(error.into())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        // Failures are logged by `mw_request_ctx`, with the correlation id
+        if let AppError::Auth(auth_error) = &self.0 {
+            if auth_error.is_security_sensitive() {
+                warn!("Security event: {}", auth_error);
+            }
+        }
+
+        let status =
+            StatusCode::from_u16(self.0.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = ApiErrorResponse::from_app_error(&self.0);
+
+        let mut response = (status, Json(body)).into_response();
+        if let AppError::RateLimit { retry_after } = self.0 {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        // Exposed to outer layers (e.g. the auth audit log, localization)
+        response.extensions_mut().insert(self.0.clone());
+        if let AppError::Auth(auth_error) = self.0 {
+            response.extensions_mut().insert(auth_error);
+        }
+        response
+    }
+}
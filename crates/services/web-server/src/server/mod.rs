@@ -1 +1,184 @@
 // pub mod server;
+
+pub mod outbox;
+pub mod reload;
+pub mod retention;
+pub mod state;
+pub mod tls;
+
+pub use outbox::OutboxDispatcher;
+pub use retention::RetentionJob;
+pub use reload::LogFilterHandle;
+pub use state::AppState;
+
+use anyhow::{Context, Result};
+use axum_server::tls_rustls::RustlsConfig;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::info;
+
+use lib_auth::jwt::JwtService;
+use lib_auth::lockout::{LoginAttemptStore, RedisLoginAttemptStore};
+use lib_auth::rbac::{RedisBreakGlassStore, RedisDelegationStore};
+use lib_auth::session::RedisSessionStore;
+use lib_core::config::{AppConfig, ConfigWatcher};
+use lib_core::storage::ObjectStorage;
+use lib_core::store::{
+    run_migrations, spawn_listener, EventBus, HospitalCache, IdempotencyStore,
+    PgHospitalResolver, PgPatientRepository,
+};
+use lib_utils::rate_limit::{RedisTokenBuckets, TokenBuckets};
+
+use crate::web;
+
+/// Load configuration, build shared state and serve the HTTP API.
+/// `log_filter` lets the configured log level replace the one from `main`.
+pub async fn start(log_filter: LogFilterHandle) -> Result<()> {
+    let config = AppConfig::load()
+        .await
+        .context("Failed to load configuration")?;
+    reload::apply_log_level(&log_filter, &config.logging.level);
+
+    let db = config.database.connect().await?;
+    if config.database.run_migrations {
+        let applied = run_migrations(&db)
+            .await
+            .context("Failed to run database migrations")?;
+        info!("Database schema up to date ({} migration(s) applied)", applied);
+    }
+
+    let diagnostics = config.diagnose().await;
+    info!("Startup checks ({}):\n{}", config.environment.name(), diagnostics);
+    diagnostics.ensure_ready()?;
+
+    let key_ring = config.jwt.key_ring()?;
+    info!(
+        "JWT signing with {} (kid: {})",
+        key_ring.algorithm(),
+        key_ring.signing_kid()
+    );
+    let jwt = JwtService::new(
+        key_ring,
+        config.jwt.issuer.clone(),
+        config.jwt.audience.clone(),
+        config.jwt.expiration_seconds,
+    )
+    .with_service_expiration(config.jwt.service_expiration_seconds);
+
+    let password_policy = config.security.password_policy()?;
+    let password_hasher = config.security.password_hasher()?;
+    let url_signer = config.security.url_signer();
+    let ip_allowlists = config.server.ip_allowlists()?;
+    let attachments = config.storage.attachment_storage()?;
+    let catchment_zones = config.healthcare.catchment.zones()?;
+    if let Some(ref zones) = catchment_zones {
+        info!("Loaded {} catchment zones", zones.zones().len());
+    }
+    let reference_ranges = config.healthcare.reference_ranges()?;
+    if let Some(ref path) = config.healthcare.reference_ranges_path {
+        info!("Loaded reference range overrides from {}", path);
+    }
+
+    db.spawn_health_checks();
+    let events = EventBus::new();
+    spawn_listener(&db, events.clone());
+
+    let redis = config.redis.connect().await?;
+    redis.spawn_health_checks();
+    let sessions = RedisSessionStore::new(redis.clone(), config.healthcare.session_timeout());
+    let login_attempts: Arc<dyn LoginAttemptStore> = Arc::new(RedisLoginAttemptStore::new(
+        redis.clone(),
+        config.security.lockout_policy(),
+    ));
+    let break_glass = RedisBreakGlassStore::new(redis.clone());
+    let delegations = RedisDelegationStore::new(redis.clone());
+    let hospital_cache = HospitalCache::new(redis.clone(), config.redis.hospital_cache_ttl());
+    let rate_limits: Arc<dyn TokenBuckets> = Arc::new(RedisTokenBuckets::new(redis.clone()));
+    let idempotency = IdempotencyStore::new(redis.clone(), config.redis.idempotency_ttl());
+
+    // Events committed with the changes they describe, delivered from here
+    OutboxDispatcher::new(
+        db.clone(),
+        redis,
+        config.healthcare.notification_webhooks.clone(),
+    )?
+    .spawn();
+
+    if config.retention.enabled {
+        RetentionJob::new(db.clone(), config.retention.clone()).spawn();
+    }
+
+    let addr: SocketAddr = format!("{}:{}", config.server.host, config.server.port)
+        .parse()
+        .context("Invalid server address")?;
+    let tls = config.server.tls.clone();
+
+    // Reloadable settings follow the configuration files and SIGHUP,
+    // secrets follow rotations in the secrets manager
+    let secret_provider = config.secrets.provider()?;
+    let watcher = Arc::new(ConfigWatcher::new(config, AppConfig::config_dir()));
+    reload::spawn_subscribers(
+        watcher.subscribe(),
+        log_filter,
+        login_attempts.clone(),
+        db.clone(),
+    );
+    watcher.clone().spawn();
+    if let Some(provider) = secret_provider {
+        watcher.clone().spawn_secret_rotation(provider);
+    }
+
+    let state = AppState {
+        config: watcher.config(),
+        patients: Arc::new(PgPatientRepository::new(db.clone())),
+        hospital_resolver: Arc::new(PgHospitalResolver::new(db.clone())),
+        db,
+        jwt: Arc::new(jwt),
+        sessions: Arc::new(sessions),
+        login_attempts,
+        password_policy: Arc::new(password_policy),
+        password_hasher: Arc::new(password_hasher),
+        break_glass: Arc::new(break_glass),
+        delegations: Arc::new(delegations),
+        ip_allowlists: Arc::new(ip_allowlists),
+        url_signer: url_signer.map(Arc::new),
+        attachments: attachments.map(|storage| Arc::new(storage) as Arc<dyn ObjectStorage>),
+        hospital_cache,
+        events,
+        catchment_zones: catchment_zones.map(Arc::new),
+        reference_ranges: Arc::new(reference_ranges),
+        rate_limits,
+        idempotency,
+    };
+
+    let app = web::routes(state);
+
+    if let Some(tls) = tls {
+        let rustls = RustlsConfig::from_config(tls::server_config(&tls)?);
+        tls::spawn_reload(tls.clone(), rustls.clone());
+        info!("Listening on {} (HTTPS)", addr);
+        if tls.client_ca_path.is_some() {
+            info!("Client certificates are required");
+        }
+
+        axum_server::bind_rustls(addr, rustls)
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+            .context("Server error")?;
+        return Ok(());
+    }
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind {}", addr))?;
+    info!("Listening on {}", addr);
+
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .context("Server error")?;
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use serde_json::json;
+use tokio::task::JoinHandle;
+use tracing::{debug, info_span, warn, Instrument};
+
+use lib_auth::ctx::{RequestCtx, CORRELATION_ID_HEADER, REQUEST_ID_HEADER};
+use lib_core::store::{Db, OutboxMessage, OutboxRepository, RedisPool};
+use lib_types::errors::AppError;
+
+/// Messages claimed per round
+const BATCH_SIZE: i64 = 20;
+/// How long a claimed message is left to one dispatcher before another may
+/// deliver it again
+const LEASE: Duration = Duration::from_secs(300);
+/// Pause between rounds when the outbox is drained
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delivers outbox messages to Redis pub/sub (channel `events:<topic>`) and
+/// to the configured webhooks. A message is marked dispatched only after
+/// every target accepted it and is retried with backoff otherwise, so
+/// targets may see it more than once; the message id identifies repeats.
+/// Webhooks receive the request and correlation ids of the request that
+/// enqueued the message, for tracing it end to end.
+pub struct OutboxDispatcher {
+    outbox: OutboxRepository,
+    redis: RedisPool,
+    http: reqwest::Client,
+    webhooks: Vec<String>,
+}
+
+impl OutboxDispatcher {
+    pub fn new(db: Db, redis: RedisPool, webhooks: Vec<String>) -> anyhow::Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()?;
+        Ok(Self {
+            outbox: OutboxRepository::new(db),
+            redis,
+            http,
+            webhooks,
+        })
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let ctx = RequestCtx::system();
+            loop {
+                match self.dispatch_batch(&ctx).await {
+                    // More may be waiting
+                    Ok(claimed) if claimed == BATCH_SIZE as usize => continue,
+                    Ok(_) => {}
+                    Err(e) => warn!("Outbox dispatch failed: {}", e),
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+    }
+
+    /// Deliver one batch of due messages, returning how many were claimed
+    async fn dispatch_batch(&self, ctx: &RequestCtx) -> Result<usize, AppError> {
+        let messages = self.outbox.claim(ctx, BATCH_SIZE, LEASE).await?;
+        for message in &messages {
+            // Logged with the ids of the request that enqueued the message
+            let span = info_span!(
+                "outbox",
+                request_id = message.request_id.as_deref(),
+                correlation_id = message.correlation_id.as_deref()
+            );
+            self.dispatch(ctx, message).instrument(span).await?;
+        }
+        Ok(messages.len())
+    }
+
+    async fn dispatch(&self, ctx: &RequestCtx, message: &OutboxMessage) -> Result<(), AppError> {
+        match self.deliver(message).await {
+            Ok(()) => {
+                debug!("Dispatched {} {}", message.topic, message.id);
+                self.outbox.mark_dispatched(ctx, message.id).await
+            }
+            Err(error) => {
+                warn!(
+                    "Delivery of {} {} failed (attempt {}): {}",
+                    message.topic, message.id, message.attempts, error
+                );
+                self.outbox.mark_failed(ctx, message, &error).await
+            }
+        }
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), String> {
+        let event = json!({
+            "id": message.id,
+            "topic": message.topic,
+            "hospital_id": message.hospital_id,
+            "occurred_at": message.created_at,
+            "data": message.payload,
+        });
+        let body = event.to_string();
+
+        redis::cmd("PUBLISH")
+            .arg(format!("events:{}", message.topic))
+            .arg(&body)
+            .query_async::<()>(&mut self.redis.clone())
+            .await
+            .map_err(|e| format!("Redis publish failed: {}", e))?;
+
+        for url in &self.webhooks {
+            let mut request = self
+                .http
+                .post(url)
+                .header("Content-Type", "application/json")
+                .header("X-Event-Id", message.id.to_string())
+                .header("X-Event-Topic", &message.topic);
+            if let Some(request_id) = &message.request_id {
+                request = request.header(REQUEST_ID_HEADER, request_id);
+            }
+            if let Some(correlation_id) = &message.correlation_id {
+                request = request.header(CORRELATION_ID_HEADER, correlation_id);
+            }
+            request
+                .body(body.clone())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| format!("Webhook {} failed: {}", url, e))?;
+        }
+        Ok(())
+    }
+}
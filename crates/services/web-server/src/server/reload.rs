@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use lib_auth::lockout::LoginAttemptStore;
+use lib_core::config::AppConfig;
+use lib_core::store::Db;
+
+/// Handle for replacing the log filter installed in `main`
+pub type LogFilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Switch logging to the configured level, keeping the current filter if
+/// the level does not parse
+pub fn apply_log_level(log_filter: &LogFilterHandle, level: &str) {
+    match EnvFilter::try_new(level) {
+        Ok(filter) => {
+            if let Err(e) = log_filter.reload(filter) {
+                warn!("Failed to change the log level: {}", e);
+            }
+        }
+        Err(e) => warn!("Ignoring invalid log level {:?}: {}", level, e),
+    }
+}
+
+/// Push reloaded settings into the components that copied them at startup.
+/// Handlers and middleware read the shared configuration per request.
+pub fn spawn_subscribers(
+    mut changes: watch::Receiver<Arc<AppConfig>>,
+    log_filter: LogFilterHandle,
+    login_attempts: Arc<dyn LoginAttemptStore>,
+    db: Db,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut previous = changes.borrow_and_update().clone();
+        while changes.changed().await.is_ok() {
+            let config = changes.borrow_and_update().clone();
+
+            // New connections use a rotated password, open ones stay as they are
+            if config.database.password != previous.database.password {
+                match config.database.update_connect_options(&db) {
+                    Ok(()) => info!("Database password rotated"),
+                    Err(e) => warn!("Failed to apply the rotated database password: {:#}", e),
+                }
+            }
+            if config.database.slow_query_threshold_ms != previous.database.slow_query_threshold_ms
+            {
+                if let Err(e) = config.database.update_connect_options(&db) {
+                    warn!("Failed to apply the slow query threshold: {:#}", e);
+                }
+            }
+            if config.jwt.secret != previous.jwt.secret {
+                warn!("JWT secret rotated; tokens are signed with it after a restart");
+            }
+
+            apply_log_level(&log_filter, &config.logging.level);
+            login_attempts.set_policy(config.security.lockout_policy());
+            info!(
+                "Applied reloaded configuration (log level: {}, maintenance mode: {})",
+                config.logging.level, config.server.maintenance_mode
+            );
+            previous = config;
+        }
+    })
+}
@@ -0,0 +1,82 @@
+use chrono::Utc;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use lib_auth::ctx::RequestCtx;
+use lib_core::config::RetentionConfig;
+use lib_core::store::{Db, RetentionRepository};
+use lib_types::errors::AppError;
+
+/// Applies the retention policies every `interval_minutes`: archives
+/// patients whose visit ended and purges old vitals, a batch at a time. In
+/// dry-run mode it only logs how many rows are due. Every server instance
+/// runs the job; rows locked by one run are skipped by the others.
+pub struct RetentionJob {
+    retention: RetentionRepository,
+    config: RetentionConfig,
+}
+
+impl RetentionJob {
+    pub fn new(db: Db, config: RetentionConfig) -> Self {
+        Self {
+            retention: RetentionRepository::new(db),
+            config,
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let ctx = RequestCtx::system();
+            loop {
+                if let Err(e) = self.run(&ctx).await {
+                    warn!("Retention run failed: {}", e);
+                }
+                tokio::time::sleep(self.config.interval()).await;
+            }
+        })
+    }
+
+    async fn run(&self, ctx: &RequestCtx) -> Result<(), AppError> {
+        let now = Utc::now();
+        let batch = i64::from(self.config.batch_size);
+
+        if let Some(before) = self.config.patients.cutoff(now) {
+            if self.config.dry_run {
+                let due = self.retention.count_patients_due(ctx, before).await?;
+                info!("Retention dry run: {} patients would be archived", due);
+            } else {
+                let mut archived = 0;
+                loop {
+                    let count = self.retention.archive_patients(ctx, before, batch).await?;
+                    archived += count;
+                    if count < batch as u64 {
+                        break;
+                    }
+                }
+                if archived > 0 {
+                    info!("Retention archived {} patients", archived);
+                }
+            }
+        }
+
+        if let Some(before) = self.config.vitals.cutoff(now) {
+            if self.config.dry_run {
+                let due = self.retention.count_vitals_due(ctx, before).await?;
+                info!("Retention dry run: {} vitals would be purged", due);
+            } else {
+                let mut purged = 0;
+                loop {
+                    let count = self.retention.purge_vitals(ctx, before, batch).await?;
+                    purged += count;
+                    if count < batch as u64 {
+                        break;
+                    }
+                }
+                if purged > 0 {
+                    info!("Retention purged {} vitals", purged);
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use lib_auth::jwt::JwtService;
+use lib_auth::lockout::LoginAttemptStore;
+use lib_auth::middleware::{
+    AuthState, HospitalResolver, HospitalScope, IpAllowlists, ResourceKind,
+};
+use lib_auth::password::{PasswordHasher, PasswordPolicy};
+use lib_auth::rbac::{BreakGlassStore, DelegationStore};
+use lib_auth::session::SessionStore;
+use lib_auth::signed_url::UrlSigner;
+use lib_core::config::SharedConfig;
+use lib_core::storage::ObjectStorage;
+use lib_core::store::{
+    Db, EventBus, HospitalCache, HospitalRepository, IdempotencyStore, PatientRepository,
+};
+use lib_utils::location::ZoneRegistry;
+use lib_utils::rate_limit::TokenBuckets;
+use lib_utils::reference_ranges::ReferenceRanges;
+
+use crate::web::mw_rate_limit::RateLimiter;
+
+/// Shared application state available to every handler
+#[derive(Clone)]
+pub struct AppState {
+    pub config: SharedConfig, // Swapped when the configuration is reloaded
+    pub db: Db,
+    pub patients: Arc<dyn PatientRepository>, // In memory in handler tests
+    pub hospital_resolver: Arc<dyn HospitalResolver>, // Who owns the resource a route addresses
+    pub jwt: Arc<JwtService>,
+    pub sessions: Arc<dyn SessionStore>,
+    pub login_attempts: Arc<dyn LoginAttemptStore>,
+    pub password_policy: Arc<PasswordPolicy>,
+    pub password_hasher: Arc<PasswordHasher>,
+    pub break_glass: Arc<dyn BreakGlassStore>,
+    pub delegations: Arc<dyn DelegationStore>,
+    pub ip_allowlists: Arc<IpAllowlists>,
+    pub url_signer: Option<Arc<UrlSigner>>, // None when signed links are not configured
+    pub attachments: Option<Arc<dyn ObjectStorage>>, // None when no bucket is configured
+    pub hospital_cache: HospitalCache,
+    pub events: EventBus, // Live changes from every server instance
+    pub catchment_zones: Option<Arc<ZoneRegistry>>, // None when no zones file is configured
+    pub reference_ranges: Arc<ReferenceRanges>, // Vital sign thresholds, built in unless overridden
+    pub rate_limits: Arc<dyn TokenBuckets>, // Shared by every instance through Redis
+    pub idempotency: IdempotencyStore, // Responses replayed for retried requests
+}
+
+impl AppState {
+    /// State for `mw_require_auth`
+    pub fn auth(&self) -> AuthState {
+        AuthState {
+            jwt: self.jwt.clone(),
+            sessions: self.sessions.clone(),
+            delegations: self.delegations.clone(),
+        }
+    }
+
+    /// State for `mw_rate_limit`
+    pub fn rate_limiter(&self) -> RateLimiter {
+        RateLimiter {
+            buckets: self.rate_limits.clone(),
+            config: self.config.clone(),
+        }
+    }
+
+    /// Hospital-scope guard for routes addressing a resource of `kind` by `:id`
+    pub fn hospital_scope(&self, kind: ResourceKind) -> HospitalScope {
+        HospitalScope::new(self.hospital_resolver.clone(), kind)
+            .with_break_glass(self.break_glass.clone())
+    }
+
+    /// Hospital data access that reads through and invalidates the cache
+    pub fn hospitals(&self) -> HospitalRepository {
+        HospitalRepository::new(self.db.clone()).with_cache(self.hospital_cache.clone())
+    }
+}
@@ -0,0 +1,113 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use lib_core::config::TlsConfig;
+
+/// How often the certificate files are checked for rotation
+const RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Build the rustls configuration for `tls`. HTTP/2 is offered through
+/// ALPN with HTTP/1.1 as fallback; client certificates are required when a
+/// client CA is configured.
+pub fn server_config(tls: &TlsConfig) -> Result<Arc<ServerConfig>> {
+    let provider = Arc::new(ring::default_provider());
+    let certs = load_certs(&tls.cert_path)?;
+    let key = load_key(&tls.key_path)?;
+
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .context("Unsupported TLS protocol versions")?;
+    let builder = match &tls.client_ca_path {
+        Some(path) => builder.with_client_cert_verifier(client_verifier(path, provider)?),
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .context("TLS certificate does not match the private key")?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+/// Re-read the certificate files whenever they change, so rotated
+/// certificates are served without dropping connections. A broken
+/// certificate is logged and the previous one kept.
+pub fn spawn_reload(tls: TlsConfig, rustls: RustlsConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(RELOAD_INTERVAL);
+        let mut stamp = file_stamp(&tls);
+
+        loop {
+            interval.tick().await;
+            let latest = file_stamp(&tls);
+            if latest == stamp {
+                continue;
+            }
+            stamp = latest;
+
+            match server_config(&tls) {
+                Ok(config) => {
+                    rustls.reload_from_config(config);
+                    info!("Reloaded TLS certificate from {}", tls.cert_path);
+                }
+                Err(e) => warn!("Keeping the current TLS certificate: {:#}", e),
+            }
+        }
+    })
+}
+
+fn client_verifier(
+    path: &str,
+    provider: Arc<CryptoProvider>,
+) -> Result<Arc<dyn rustls::server::danger::ClientCertVerifier>> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        roots
+            .add(cert)
+            .with_context(|| format!("Invalid client CA certificate in {}", path))?;
+    }
+    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+        .build()
+        .context("Failed to build the client certificate verifier")
+}
+
+fn load_certs(path: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("Invalid PEM in {}", path))?;
+    if certs.is_empty() {
+        anyhow::bail!("No certificates found in {}", path);
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &str) -> Result<PrivateKeyDer<'static>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .with_context(|| format!("Invalid PEM in {}", path))?
+        .with_context(|| format!("No private key found in {}", path))
+}
+
+fn file_stamp(tls: &TlsConfig) -> Vec<Option<SystemTime>> {
+    [
+        Some(&tls.cert_path),
+        Some(&tls.key_path),
+        tls.client_ca_path.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+    .collect()
+}
@@ -1 +1,194 @@
 // pub mod web;
+
+pub mod mw_auth_audit;
+pub mod mw_idempotency;
+pub mod mw_maintenance;
+pub mod mw_rate_limit;
+pub mod mw_recent_auth;
+pub mod mw_signed_url;
+pub mod routes_admin;
+pub mod routes_ambulances;
+pub mod routes_attachments;
+pub mod routes_auth;
+pub mod routes_beds;
+pub mod routes_break_glass;
+pub mod routes_consents;
+pub mod routes_dashboard;
+pub mod routes_delegations;
+pub mod routes_devices;
+pub mod routes_handovers;
+pub mod routes_hospitals;
+pub mod routes_incidents;
+pub mod routes_jwks;
+pub mod routes_labs;
+pub mod routes_metrics;
+pub mod routes_patients;
+pub mod routes_service;
+pub mod routes_shared_links;
+pub mod routes_staff;
+pub mod routes_timeline;
+pub mod routes_transfers;
+pub mod routes_vitals;
+
+#[cfg(test)]
+mod test_support;
+
+use axum::http::{header, HeaderName, HeaderValue, Method};
+use axum::{middleware, Router};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use lib_auth::ctx::{CORRELATION_ID_HEADER, REQUEST_ID_HEADER};
+use lib_auth::middleware::{
+    mw_request_ctx, mw_require_auth, mw_require_hospital_scope, mw_require_ip_allowlist,
+    mw_require_service_auth, ResourceKind,
+};
+
+use crate::server::AppState;
+
+/// Build the application router
+pub fn routes(state: AppState) -> Router {
+    let public_routes = Router::new()
+        .merge(routes_jwks::routes())
+        .merge(routes_metrics::routes())
+        .merge(routes_auth::public_routes())
+        .merge(routes_shared_links::signed_routes(&state))
+        .route_layer(middleware::from_fn_with_state(
+            state.rate_limiter(),
+            mw_rate_limit::mw_rate_limit,
+        ));
+
+    // Sensitive operations also require the password to have been entered recently
+    let step_up_routes = Router::new()
+        .merge(routes_admin::routes())
+        .merge(routes_break_glass::routes())
+        .route_layer(middleware::from_fn_with_state(
+            state.config.clone(),
+            mw_recent_auth::mw_require_recent_auth,
+        ));
+
+    // Patients, beds and staff addressed by id are refused when another
+    // hospital owns them
+    let patient_routes = Router::new()
+        .merge(routes_attachments::routes())
+        .merge(routes_consents::routes())
+        .merge(routes_labs::routes())
+        .merge(routes_patients::routes())
+        .merge(routes_timeline::routes())
+        .merge(routes_vitals::routes())
+        .route_layer(middleware::from_fn_with_state(
+            state.hospital_scope(ResourceKind::Patient),
+            mw_require_hospital_scope,
+        ));
+    let hospital_scoped_routes = Router::new()
+        .merge(patient_routes)
+        .merge(
+            routes_beds::routes().route_layer(middleware::from_fn_with_state(
+                state.hospital_scope(ResourceKind::Bed),
+                mw_require_hospital_scope,
+            )),
+        )
+        .merge(
+            routes_staff::routes().route_layer(middleware::from_fn_with_state(
+                state.hospital_scope(ResourceKind::Staff),
+                mw_require_hospital_scope,
+            )),
+        );
+
+    // Everything else requires a valid access token backed by an active session,
+    // used from a network allowed for the caller's role, within the caller's request budget.
+    // POSTs carrying an Idempotency-Key run once; retries get the first response.
+    let api_routes = Router::new()
+        .merge(routes_ambulances::routes())
+        .merge(routes_auth::routes())
+        .merge(routes_beds::hospital_routes())
+        .merge(routes_dashboard::routes())
+        .merge(routes_delegations::routes())
+        .merge(routes_devices::routes())
+        .merge(routes_handovers::routes())
+        .merge(routes_hospitals::routes())
+        .merge(routes_incidents::routes())
+        .merge(routes_shared_links::routes())
+        .merge(routes_staff::hospital_routes())
+        .merge(routes_transfers::routes())
+        .merge(hospital_scoped_routes)
+        .merge(step_up_routes)
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            mw_idempotency::mw_idempotency,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.rate_limiter(),
+            mw_rate_limit::mw_rate_limit,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.config.clone(),
+            mw_maintenance::mw_maintenance,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.ip_allowlists.clone(),
+            mw_require_ip_allowlist,
+        ))
+        .route_layer(middleware::from_fn_with_state(state.auth(), mw_require_auth))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            mw_auth_audit::mw_audit_auth_failures,
+        ));
+
+    // Service accounts authenticate with their own tokens, checked for the
+    // scope each route needs
+    let service_routes = routes_service::routes()
+        .route_layer(middleware::from_fn_with_state(
+            state.rate_limiter(),
+            mw_rate_limit::mw_rate_limit,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.config.clone(),
+            mw_maintenance::mw_maintenance,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            state.jwt.clone(),
+            mw_require_service_auth,
+        ));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(api_routes)
+        .merge(service_routes)
+        .layer(middleware::from_fn(mw_request_ctx))
+        .layer(cors(&state))
+        .with_state(state)
+}
+
+/// CORS for the configured origins, read per request so reloads apply
+fn cors(state: &AppState) -> CorsLayer {
+    let config = state.config.clone();
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+            config
+                .load()
+                .server
+                .cors_origins
+                .iter()
+                .any(|allowed| origin == allowed.as_str())
+        }))
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            HeaderName::from_static(REQUEST_ID_HEADER),
+            HeaderName::from_static(CORRELATION_ID_HEADER),
+            HeaderName::from_static(mw_idempotency::IDEMPOTENCY_KEY_HEADER),
+        ])
+        .expose_headers([
+            header::RETRY_AFTER,
+            HeaderName::from_static(REQUEST_ID_HEADER),
+            HeaderName::from_static(CORRELATION_ID_HEADER),
+            HeaderName::from_static(mw_idempotency::IDEMPOTENT_REPLAYED_HEADER),
+        ])
+}
@@ -0,0 +1,105 @@
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::header::USER_AGENT;
+use axum::http::HeaderMap;
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::error;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_core::store::AuthAuditRepository;
+use lib_types::entities::AuthAuditEntry;
+use lib_types::errors::{AppError, AuthError};
+
+use crate::server::AppState;
+
+/// Client address and user agent recorded with audit entries
+#[derive(Debug, Clone, Default)]
+pub struct ClientInfo {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl ClientInfo {
+    pub fn new(addr: Option<SocketAddr>, headers: &HeaderMap) -> Self {
+        Self {
+            ip_address: addr.map(|addr| addr.ip().to_string()),
+            user_agent: headers
+                .get(USER_AGENT)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        }
+    }
+
+    /// Attach the client to an audit entry
+    pub fn apply(&self, entry: AuthAuditEntry) -> AuthAuditEntry {
+        entry.with_client(self.ip_address.clone(), self.user_agent.clone())
+    }
+}
+
+/// Append an entry to the auth audit log.
+///
+/// Audit failures are logged but never fail the request, so an unavailable
+/// audit table cannot lock staff out during an emergency.
+pub async fn record_auth_event(state: &AppState, req_ctx: &RequestCtx, entry: AuthAuditEntry) {
+    if let Err(e) = AuthAuditRepository::new(state.db.clone())
+        .record(req_ctx, &entry)
+        .await
+    {
+        error!("Failed to write auth audit entry {:?}: {}", entry.event, e);
+    }
+}
+
+/// Record a failed authentication attempt if the error is security-sensitive
+pub async fn record_auth_failure(
+    state: &AppState,
+    req_ctx: &RequestCtx,
+    error: &AppError,
+    username: &str,
+    client: &ClientInfo,
+) {
+    if let AppError::Auth(auth_error) = error {
+        if auth_error.is_security_sensitive() {
+            let entry = client.apply(AuthAuditEntry::failure(auth_error).with_username(username));
+            record_auth_event(state, req_ctx, entry).await;
+        }
+    }
+}
+
+/// Persist security-sensitive auth failures of protected routes (invalid
+/// tokens, permission denials, wrong passwords on re-authentication).
+///
+/// Must wrap `mw_require_auth` so the caller's `Ctx`, when known, is
+/// available on the response.
+pub async fn mw_audit_auth_failures(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let addr = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let client = ClientInfo::new(addr, req.headers());
+    let resource = format!("{} {}", req.method(), req.uri().path());
+    let req_ctx = req
+        .extensions()
+        .get::<RequestCtx>()
+        .cloned()
+        .unwrap_or_else(|| RequestCtx::from_headers(req.headers()));
+
+    let response = next.run(req).await;
+
+    if let Some(error) = response.extensions().get::<AuthError>() {
+        if error.is_security_sensitive() {
+            let mut entry = client.apply(AuthAuditEntry::failure(error).with_resource(resource));
+            if let Some(ctx) = response.extensions().get::<Ctx>() {
+                entry = entry.with_user(ctx.user_id());
+            }
+            record_auth_event(&state, &req_ctx, entry).await;
+        }
+    }
+
+    response
+}
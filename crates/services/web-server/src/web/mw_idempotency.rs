@@ -0,0 +1,175 @@
+use axum::body::{to_bytes, Body};
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::{info, warn};
+
+use lib_auth::ctx::Ctx;
+use lib_core::store::{ClaimToken, IdempotencyClaim, IdempotencyStore, StoredResponse};
+use lib_types::errors::AppError;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+/// Header naming a client-chosen key that identifies a request across retries
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Set on responses replayed from an earlier request with the same key
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LENGTH: usize = 255;
+/// Larger responses are not kept; a retry runs the request again
+const MAX_STORED_BODY: usize = 1024 * 1024;
+
+/// Answer retries of a POST carrying an `Idempotency-Key` with the response
+/// of the first attempt instead of running the handler again, so devices on
+/// flaky networks can retry without e.g. registering a patient twice.
+///
+/// Keys are scoped to the caller. Only successful responses are kept, for
+/// the configured TTL; a failed request releases its key and may be retried.
+/// Reusing a key for a different request is rejected, as is a retry while
+/// the first attempt is still running. When Redis is unreachable requests
+/// run as if they carried no key.
+pub async fn mw_idempotency(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> ApiResult<Response> {
+    let store = &state.idempotency;
+    if req.method() != Method::POST || !store.is_enabled() {
+        return Ok(next.run(req).await);
+    }
+    let (Some(key), Some(ctx)) = (
+        req.headers().get(IDEMPOTENCY_KEY_HEADER),
+        req.extensions().get::<Ctx>(),
+    ) else {
+        return Ok(next.run(req).await);
+    };
+    let key = match key.to_str().map(str::trim) {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => {
+            format!("{}:{}", ctx.user_id(), key)
+        }
+        _ => {
+            return Err(AppError::validation_error(
+                "Idempotency-Key",
+                format!("Must be 1 to {} visible ASCII characters", MAX_KEY_LENGTH),
+            )
+            .into())
+        }
+    };
+
+    let limit = state.config.load().server.max_request_size_mb * 1024 * 1024;
+    let (parts, body) = req.into_parts();
+    let body = to_bytes(body, limit)
+        .await
+        .map_err(|_| AppError::BadRequest {
+            message: "Request body is too large".to_string(),
+        })?;
+    let fingerprint =
+        IdempotencyStore::fingerprint(parts.method.as_str(), &parts.uri.to_string(), &body);
+    let req = Request::from_parts(parts, Body::from(body));
+
+    let claim = match store.claim(&key, &fingerprint).await {
+        Ok(IdempotencyClaim::Acquired(claim)) => claim,
+        Ok(IdempotencyClaim::Completed(stored)) => {
+            info!("Replaying the response for idempotency key {}", key);
+            return Ok(replay(stored));
+        }
+        Ok(IdempotencyClaim::InProgress) => {
+            return Err(AppError::Conflict {
+                message: "A request with this Idempotency-Key is still in progress".to_string(),
+            }
+            .into())
+        }
+        Ok(IdempotencyClaim::Mismatch) => {
+            return Err(AppError::validation_error(
+                "Idempotency-Key",
+                "Already used for a different request",
+            )
+            .into())
+        }
+        Err(e) => {
+            warn!(
+                "Idempotency store unavailable, running request anyway: {}",
+                e
+            );
+            return Ok(next.run(req).await);
+        }
+    };
+
+    let response = next.run(req).await;
+    if !response.status().is_success() {
+        release(store, &key, &claim).await;
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!(
+                "Failed to read the response for idempotency key {}: {}",
+                key, e
+            );
+            release(store, &key, &claim).await;
+            return Err(AppError::Internal.into());
+        }
+    };
+
+    let text = Some(&body)
+        .filter(|body| body.len() <= MAX_STORED_BODY)
+        .and_then(|body| String::from_utf8(body.to_vec()).ok());
+    match text {
+        Some(text) => {
+            let stored = StoredResponse {
+                status: parts.status.as_u16(),
+                content_type: header_string(&parts.headers, header::CONTENT_TYPE),
+                location: header_string(&parts.headers, header::LOCATION),
+                body: text,
+            };
+            match store.complete(&key, &claim, &fingerprint, stored).await {
+                Ok(true) => {}
+                Ok(false) => warn!(
+                    "Idempotency key {} was claimed again before its response was stored",
+                    key
+                ),
+                Err(e) => warn!(
+                    "Failed to store the response for idempotency key {}: {}",
+                    key, e
+                ),
+            }
+        }
+        None => release(store, &key, &claim).await,
+    }
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+async fn release(store: &IdempotencyStore, key: &str, claim: &ClaimToken) {
+    if let Err(e) = store.release(key, claim).await {
+        // The key frees itself once its claim expires
+        warn!("Failed to release idempotency key {}: {}", key, e);
+    }
+}
+
+fn header_string(headers: &HeaderMap, name: HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    let mut set = |name: HeaderName, value: Option<String>| {
+        if let Some(value) = value.and_then(|value| HeaderValue::from_str(&value).ok()) {
+            headers.insert(name, value);
+        }
+    };
+    set(header::CONTENT_TYPE, stored.content_type);
+    set(header::LOCATION, stored.location);
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
@@ -0,0 +1,26 @@
+use axum::extract::{Request, State};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use lib_core::config::SharedConfig;
+use lib_types::errors::AppError;
+
+use crate::responses::ApiResult;
+
+/// Reject requests that change data while maintenance mode is on.
+///
+/// Reads stay available so staff can still look up patients; the setting
+/// is reloadable, so maintenance can start and end without a restart.
+pub async fn mw_maintenance(
+    State(config): State<SharedConfig>,
+    req: Request,
+    next: Next,
+) -> ApiResult<Response> {
+    let read_only = matches!(*req.method(), Method::GET | Method::HEAD | Method::OPTIONS);
+    if config.load().server.maintenance_mode && !read_only {
+        return Err(AppError::Maintenance.into());
+    }
+
+    Ok(next.run(req).await)
+}
@@ -0,0 +1,97 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::extract::{ConnectInfo, MatchedPath, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::warn;
+
+use lib_auth::ctx::{Ctx, ServiceCtx};
+use lib_core::config::{RateLimitConfig, SharedConfig};
+use lib_types::errors::AppError;
+use lib_utils::rate_limit::{BucketConfig, TokenBuckets};
+
+use crate::responses::ApiResult;
+
+/// State for `mw_rate_limit`
+#[derive(Clone)]
+pub struct RateLimiter {
+    pub buckets: Arc<dyn TokenBuckets>,
+    pub config: SharedConfig,
+}
+
+/// Refuse callers that have used up their request budget with
+/// `AppError::RateLimit`, rendered as 429 with `Retry-After`.
+///
+/// Buckets live in Redis, so the budgets hold across every instance. Users
+/// are limited by role, API clients by client id and anonymous callers by
+/// IP address; a configured route also takes from a bucket of its own.
+/// When Redis is unreachable requests are let through rather than turning
+/// an outage of the limiter into an outage of the ER.
+pub async fn mw_rate_limit(
+    State(limiter): State<RateLimiter>,
+    req: Request,
+    next: Next,
+) -> ApiResult<Response> {
+    let config = limiter.config.load_full();
+    let limits = &config.rate_limit;
+    if !limits.enabled {
+        return Ok(next.run(req).await);
+    }
+    let Some((caller, per_minute)) = caller(&req, limits) else {
+        return Ok(next.run(req).await);
+    };
+
+    limiter.take(&caller, per_minute).await?;
+
+    let method = req.method().as_str();
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|path| limits.route_budget(method, path.as_str()));
+    if let Some(route) = route {
+        let key = format!("{}:{} {}", caller, method, route.path);
+        limiter.take(&key, route.per_minute).await?;
+    }
+
+    Ok(next.run(req).await)
+}
+
+impl RateLimiter {
+    async fn take(&self, key: &str, per_minute: u32) -> Result<(), AppError> {
+        let bucket = BucketConfig::per_minute(per_minute);
+        match self
+            .buckets
+            .try_take(&format!("rate_limit:{}", key), &bucket, 1.0)
+            .await
+        {
+            Ok(decision) if decision.allowed => Ok(()),
+            Ok(decision) => Err(AppError::RateLimit {
+                retry_after: (decision.retry_after.as_secs_f64().ceil() as u64).max(1),
+            }),
+            Err(e) => {
+                warn!("Rate limiting unavailable, letting request through: {}", e);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Who the request counts against and their budget per minute
+fn caller(req: &Request, limits: &RateLimitConfig) -> Option<(String, u32)> {
+    if let Some(ctx) = req.extensions().get::<Ctx>() {
+        return Some((
+            format!("user:{}", ctx.user_id()),
+            limits.role_budget(ctx.role()),
+        ));
+    }
+    if let Some(service) = req.extensions().get::<ServiceCtx>() {
+        return Some((
+            format!("client:{}", service.client_id()),
+            limits.service_per_minute,
+        ));
+    }
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| (format!("ip:{}", addr.ip()), limits.anonymous_per_minute))
+}
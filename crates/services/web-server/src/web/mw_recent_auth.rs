@@ -0,0 +1,26 @@
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use lib_auth::ctx::Ctx;
+use lib_auth::middleware::ensure_recent_auth;
+use lib_core::config::SharedConfig;
+
+use crate::responses::ApiResult;
+
+/// Step-up check for sensitive operations: answer with a 428 challenge when
+/// the last authentication is older than the configured maximum age.
+///
+/// Install after `mw_require_auth`; clients satisfy the challenge by
+/// re-authenticating and retrying with the new token. The maximum age is
+/// reloadable, so it is read on every request.
+pub async fn mw_require_recent_auth(
+    State(config): State<SharedConfig>,
+    ctx: Ctx,
+    req: Request,
+    next: Next,
+) -> ApiResult<Response> {
+    ensure_recent_auth(&ctx, config.load().security.step_up_max_age())?;
+
+    Ok(next.run(req).await)
+}
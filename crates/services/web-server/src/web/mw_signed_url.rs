@@ -0,0 +1,40 @@
+use axum::extract::{OriginalUri, Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::Utc;
+use tracing::warn;
+
+use lib_auth::middleware::AuthRejection;
+use lib_types::errors::AuthError;
+
+use crate::server::AppState;
+
+/// Admit requests carrying a valid, unexpired link signature instead of an
+/// access token.
+///
+/// Every request is rejected when no URL signing key is configured.
+pub async fn mw_require_signed_url(
+    State(state): State<AppState>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AuthRejection> {
+    let signer = state.url_signer.as_ref().ok_or(AuthError::InvalidToken)?;
+
+    // Nested services see a stripped path, links are signed for the full one
+    let uri = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|OriginalUri(uri)| uri.clone())
+        .unwrap_or_else(|| req.uri().clone());
+    let expires_at = signer.verify(uri.path(), uri.query(), Utc::now())?;
+
+    warn!(
+        target: "audit",
+        event = "signed_link_used",
+        path = %uri.path(),
+        expires_at = %expires_at,
+        "Shared document opened through a signed link"
+    );
+
+    Ok(next.run(req).await)
+}
@@ -0,0 +1,358 @@
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, patch, post};
+use axum::{Json, Router};
+use tracing::info;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::{ensure_hospital_access, ResourceKind};
+use lib_auth::password::generate_client_secret;
+use lib_core::store::{
+    schema_status, AuthAuditRepository, ServiceAccountRepository, UserRepository,
+};
+use lib_types::dtos::{
+    AuthAuditListResponse, AuthAuditQuery, CacheStats, CreateHospitalRequest,
+    CreateServiceAccountRequest, HospitalResponse, LogoutAllResponse, MigrationStatusResponse,
+    RegisterUserRequest, ServiceAccountCreatedResponse, UpdateHospitalRequest, UserProfileDto,
+};
+use lib_types::entities::{AuthAuditEntry, ServiceAccount, User};
+use lib_types::enums::{AuthEvent, AuthOutcome, UserRole};
+use lib_types::errors::{AppError, AuthError, Validate};
+use lib_types::ids::{HospitalId, UserId};
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+use super::mw_auth_audit::{record_auth_event, ClientInfo};
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/admin/users", post(register_user))
+        .route("/api/admin/users/:id/unlock", post(unlock_user))
+        .route("/api/admin/users/:id/logout-all", post(logout_user_everywhere))
+        .route("/api/admin/service-accounts", post(create_service_account))
+        .route("/api/admin/hospitals", post(create_hospital))
+        .route(
+            "/api/admin/hospitals/:id",
+            patch(update_hospital).delete(delete_hospital),
+        )
+        .route("/api/admin/hospitals/:id/restore", post(restore_hospital))
+        .route("/api/admin/auth-audit", get(search_auth_audit))
+        .route("/api/admin/migrations", get(get_migrations))
+        .route("/api/admin/cache/hospitals", get(get_hospital_cache_stats))
+}
+
+/// Create a staff account
+async fn register_user(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<RegisterUserRequest>,
+) -> ApiResult<(StatusCode, Json<UserProfileDto>)> {
+    if !ctx.role().is_admin() {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    // ER directors may only create non-admin accounts for their own hospital
+    if payload.role == UserRole::Admin && ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    ensure_hospital_access(&ctx, ResourceKind::Staff, payload.hospital_id)?;
+
+    payload.validate()?;
+
+    let username = payload.sanitized_username();
+    state.password_policy.validate(&payload.password, &username)?;
+
+    let user = User::new(
+        username,
+        payload.email.trim().to_lowercase(),
+        state.password_hasher.hash(&payload.password)?,
+        payload.role,
+        payload.hospital_id,
+        payload.first_name.trim().to_string(),
+        payload.last_name.trim().to_string(),
+        payload.phone_number,
+    );
+    UserRepository::new(state.db.clone())
+        .create(&req_ctx, &user)
+        .await?;
+
+    info!(
+        "User {} registered account {} ({})",
+        ctx.user_id(),
+        user.id,
+        user.role_display()
+    );
+
+    Ok((StatusCode::CREATED, Json(UserProfileDto::from_user(&user))))
+}
+
+/// Lift a failed-login lockout before it expires
+async fn unlock_user(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<UserId>,
+) -> ApiResult<StatusCode> {
+    if !ctx.role().is_admin() {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let user = UserRepository::new(state.db.clone())
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or_else(|| AppError::not_found("User"))?;
+
+    // ER directors may only unlock staff of their own hospital
+    ensure_hospital_access(&ctx, ResourceKind::Staff, user.hospital_id)?;
+
+    let was_locked = state
+        .login_attempts
+        .unlock(&user.username.trim().to_lowercase())
+        .await?;
+    info!(
+        "User {} unlocked account {} (was locked: {})",
+        ctx.user_id(),
+        user.id,
+        was_locked
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Terminate every session of a user, e.g. when a device is reported stolen
+async fn logout_user_everywhere(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<UserId>,
+) -> ApiResult<Json<LogoutAllResponse>> {
+    if !ctx.role().is_admin() {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let user = UserRepository::new(state.db.clone())
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or_else(|| AppError::not_found("User"))?;
+
+    // ER directors may only log out staff of their own hospital
+    ensure_hospital_access(&ctx, ResourceKind::Staff, user.hospital_id)?;
+
+    let terminated = state.sessions.delete_all_for_user(user.id).await?;
+
+    info!(
+        "User {} logged out account {} everywhere ({} session(s))",
+        ctx.user_id(),
+        user.id,
+        terminated.len()
+    );
+    let entry = AuthAuditEntry::new(AuthEvent::Logout, AuthOutcome::Success)
+        .with_user(user.id)
+        .with_username(&user.username)
+        .with_resource(format!("all sessions, by {}", ctx.user_id()));
+    record_auth_event(&state, &req_ctx, ClientInfo::new(Some(addr), &headers).apply(entry)).await;
+
+    Ok(Json(LogoutAllResponse {
+        user_id: user.id,
+        terminated_sessions: terminated.len(),
+    }))
+}
+
+/// Create a service account and return its client secret (shown only once)
+async fn create_service_account(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<CreateServiceAccountRequest>,
+) -> ApiResult<(StatusCode, Json<ServiceAccountCreatedResponse>)> {
+    // Service accounts are system integrations, managed by system admins only
+    if ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    payload.validate()?;
+
+    let client_secret = generate_client_secret();
+    let account = ServiceAccount::new(
+        payload.client_id.trim().to_string(),
+        state.password_hasher.hash(&client_secret)?,
+        payload.name.trim().to_string(),
+        payload.hospital_id,
+        &payload.scopes,
+    );
+    ServiceAccountRepository::new(state.db.clone())
+        .create(&req_ctx, &account)
+        .await?;
+
+    info!(
+        "User {} created service account {} ({})",
+        ctx.user_id(),
+        account.id,
+        account.client_id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ServiceAccountCreatedResponse::new(&account, client_secret)),
+    ))
+}
+
+/// Register a hospital in the network
+async fn create_hospital(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<CreateHospitalRequest>,
+) -> ApiResult<(StatusCode, Json<HospitalResponse>)> {
+    // Hospitals are the tenants themselves, managed by system admins only
+    if ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    payload.validate()?;
+
+    let hospital = payload.to_hospital();
+    state.hospitals().create(&req_ctx, &hospital).await?;
+
+    info!(
+        "User {} registered hospital {} ({})",
+        ctx.user_id(),
+        hospital.id,
+        hospital.license_number
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(HospitalResponse::from_hospital(&hospital)),
+    ))
+}
+
+/// Edit a hospital's details, capacity or status
+async fn update_hospital(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<HospitalId>,
+    Json(payload): Json<UpdateHospitalRequest>,
+) -> ApiResult<Json<HospitalResponse>> {
+    if ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    payload.validate()?;
+
+    let (hospital, changed) = state.hospitals().update(&req_ctx, id, &payload).await?;
+
+    if !changed.is_empty() {
+        info!(
+            "User {} updated hospital {}: {}",
+            ctx.user_id(),
+            hospital.id,
+            changed.join(", ")
+        );
+    }
+
+    Ok(Json(HospitalResponse::from_hospital(&hospital)))
+}
+
+/// Take a hospital out of service listings; the record is kept for
+/// restoring
+async fn delete_hospital(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<HospitalId>,
+) -> ApiResult<StatusCode> {
+    if ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let hospital = state.hospitals().soft_delete(&req_ctx, id).await?;
+
+    info!(
+        "User {} deleted hospital {} ({})",
+        ctx.user_id(),
+        hospital.id,
+        hospital.name
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Bring back a hospital deleted by mistake
+async fn restore_hospital(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<HospitalId>,
+) -> ApiResult<Json<HospitalResponse>> {
+    if ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let hospital = state.hospitals().restore(&req_ctx, id).await?;
+
+    info!(
+        "User {} restored hospital {} ({})",
+        ctx.user_id(),
+        hospital.id,
+        hospital.name
+    );
+
+    Ok(Json(HospitalResponse::from_hospital(&hospital)))
+}
+
+/// Search the authentication audit log for security review
+async fn search_auth_audit(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Query(query): Query<AuthAuditQuery>,
+) -> ApiResult<Json<AuthAuditListResponse>> {
+    // The log spans every hospital, so only system admins may read it
+    if ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    query.validate()?;
+
+    let entries = AuthAuditRepository::new(state.db.clone())
+        .search(&req_ctx, &query)
+        .await?;
+
+    Ok(Json(AuthAuditListResponse::new(entries)))
+}
+
+/// Show which schema migrations the database has applied and which of this
+/// build's migrations are still pending
+async fn get_migrations(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+) -> ApiResult<Json<MigrationStatusResponse>> {
+    if ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let status = schema_status(&req_ctx, &state.db).await?;
+
+    Ok(Json(status))
+}
+
+/// Show how often hospital lists were answered from the cache
+async fn get_hospital_cache_stats(
+    State(state): State<AppState>,
+    ctx: Ctx,
+) -> ApiResult<Json<CacheStats>> {
+    if ctx.role() != UserRole::Admin {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    Ok(Json(state.hospital_cache.stats()))
+}
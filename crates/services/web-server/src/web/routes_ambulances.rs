@@ -0,0 +1,152 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use tracing::info;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::{ensure_hospital_access, ResourceKind};
+use lib_auth::rbac::Permissions;
+use lib_core::model::{assign_ambulance, dispatch_ambulance};
+use lib_core::store::AmbulanceRepository;
+use lib_types::dtos::{
+    AmbulanceListResponse, AmbulanceResponse, AssignAmbulancePatientRequest,
+    DispatchAmbulanceRequest, UpdateAmbulanceLocationRequest, UpdateAmbulanceStatusRequest,
+};
+use lib_types::errors::{AppError, AuthError, Validate};
+use lib_types::ids::{AmbulanceId, HospitalId};
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/hospitals/:id/ambulances", get(list_ambulances))
+        .route("/api/ambulances/dispatch", post(dispatch))
+        .route("/api/ambulances/:id/patient", put(assign_patient))
+        .route("/api/ambulances/:id/status", put(update_status))
+        .route("/api/ambulances/:id/location", post(update_location))
+}
+
+/// The dispatch board: a hospital's fleet and how many units are free
+async fn list_ambulances(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(hospital_id): Path<HospitalId>,
+) -> ApiResult<Json<AmbulanceListResponse>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) && !ctx.has_permission(Permissions::DISPATCH)
+    {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    ensure_hospital_access(&ctx, ResourceKind::Ambulance, hospital_id)?;
+
+    let ambulances = AmbulanceRepository::new(state.db.clone())
+        .list_for_hospital(&req_ctx, hospital_id)
+        .await?;
+
+    Ok(Json(AmbulanceListResponse::from_ambulances(&ambulances)))
+}
+
+/// Send an available ambulance to a call
+async fn dispatch(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<DispatchAmbulanceRequest>,
+) -> ApiResult<(StatusCode, Json<AmbulanceResponse>)> {
+    require_dispatcher(&ctx)?;
+
+    payload.validate()?;
+
+    let (ambulance, patient) = dispatch_ambulance(&state.db, &req_ctx, &payload).await?;
+
+    info!(
+        "User {} dispatched ambulance {} to {}{}",
+        ctx.user_id(),
+        ambulance.call_sign,
+        payload.incident_location.trim(),
+        patient
+            .map(|patient| format!(" for patient {}", patient.patient_number))
+            .unwrap_or_default()
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AmbulanceResponse::from_ambulance(&ambulance)),
+    ))
+}
+
+/// Put the patient the crew is treating on board
+async fn assign_patient(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<AmbulanceId>,
+    Json(payload): Json<AssignAmbulancePatientRequest>,
+) -> ApiResult<Json<AmbulanceResponse>> {
+    require_dispatcher(&ctx)?;
+
+    let (ambulance, patient) =
+        assign_ambulance(&state.db, &req_ctx, id, payload.patient_id).await?;
+
+    info!(
+        "User {} put patient {} on ambulance {}",
+        ctx.user_id(),
+        patient.patient_number,
+        ambulance.call_sign
+    );
+
+    Ok(Json(AmbulanceResponse::from_ambulance(&ambulance)))
+}
+
+/// Move an ambulance along its mission: en route, at scene, transporting,
+/// and back to available
+async fn update_status(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<AmbulanceId>,
+    Json(payload): Json<UpdateAmbulanceStatusRequest>,
+) -> ApiResult<Json<AmbulanceResponse>> {
+    require_dispatcher(&ctx)?;
+
+    let ambulance = AmbulanceRepository::new(state.db.clone())
+        .update_status(&req_ctx, id, payload.status)
+        .await?;
+
+    info!(
+        "User {} set ambulance {} to {}",
+        ctx.user_id(),
+        ambulance.call_sign,
+        ambulance.status
+    );
+
+    Ok(Json(AmbulanceResponse::from_ambulance(&ambulance)))
+}
+
+/// GPS position reported by the ambulance's crew device
+async fn update_location(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<AmbulanceId>,
+    Json(payload): Json<UpdateAmbulanceLocationRequest>,
+) -> ApiResult<Json<AmbulanceResponse>> {
+    require_dispatcher(&ctx)?;
+
+    payload.validate()?;
+
+    let ambulance = AmbulanceRepository::new(state.db.clone())
+        .update_location(&req_ctx, id, payload.position())
+        .await?;
+
+    Ok(Json(AmbulanceResponse::from_ambulance(&ambulance)))
+}
+
+fn require_dispatcher(ctx: &Ctx) -> Result<(), AppError> {
+    if !ctx.has_permission(Permissions::DISPATCH) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::rbac::Permissions;
+use lib_core::storage::{self, ObjectStorage};
+use lib_core::store::AttachmentRepository;
+use lib_types::dtos::{AttachmentDownloadResponse, AttachmentResponse, UploadAttachmentRequest};
+use lib_types::entities::{Attachment, NewAttachment};
+use lib_types::errors::{AppError, AuthError, PatientError, Validate};
+use lib_types::ids::PatientId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/patients/:id/attachments",
+            get(list_attachments).post(upload_attachment),
+        )
+        .route(
+            "/api/patients/:id/attachments/:attachment_id",
+            get(download_attachment),
+        )
+}
+
+/// Attachment storage, if a bucket is configured
+fn attachment_storage(state: &AppState) -> Result<&Arc<dyn ObjectStorage>, AppError> {
+    state
+        .attachments
+        .as_ref()
+        .ok_or_else(|| AppError::NotImplemented {
+            feature: "Patient attachments".to_string(),
+        })
+}
+
+/// Attach a file such as a scene photo or a 12-lead ECG to a patient record
+async fn upload_attachment(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<UploadAttachmentRequest>,
+) -> ApiResult<(StatusCode, Json<AttachmentResponse>)> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+    let storage = attachment_storage(&state)?;
+
+    let content = payload.decode_content()?;
+    let checksum = storage::checksum(&content);
+    if !payload.checksum_matches(&checksum) {
+        return Err(AppError::validation_error(
+            "checksum",
+            "Content does not match the checksum; upload the file again",
+        )
+        .into());
+    }
+
+    let patient = state
+        .patients
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or(PatientError::NotFound { patient_id: id })?;
+
+    let attachment = Attachment::new(NewAttachment {
+        patient_id: patient.id,
+        hospital_id: patient.hospital_id,
+        kind: payload.kind,
+        file_name: payload.file_name(),
+        content_type: payload.content_type(),
+        size_bytes: content.len() as i64,
+        checksum,
+        uploaded_by: ctx.user_id(),
+    });
+    storage
+        .put(&attachment.storage_key, &attachment.content_type, content)
+        .await?;
+
+    if let Err(e) = AttachmentRepository::new(state.db.clone())
+        .create(&req_ctx, &attachment)
+        .await
+    {
+        // Do not leave content behind that no record points to
+        if let Err(cleanup) = storage.delete(&attachment.storage_key).await {
+            warn!(
+                "Failed to remove orphaned attachment {}: {}",
+                attachment.storage_key, cleanup
+            );
+        }
+        return Err(e.into());
+    }
+
+    info!(
+        "User {} attached {} {} to patient {}",
+        ctx.user_id(),
+        attachment.kind,
+        attachment.id,
+        patient.id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AttachmentResponse::from_attachment(&attachment)),
+    ))
+}
+
+async fn list_attachments(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+) -> ApiResult<Json<Vec<AttachmentResponse>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let attachments = AttachmentRepository::new(state.db.clone())
+        .list_for_patient(&req_ctx, id)
+        .await?;
+
+    Ok(Json(
+        attachments
+            .iter()
+            .map(AttachmentResponse::from_attachment)
+            .collect(),
+    ))
+}
+
+/// Download an attachment. The content is checked against the checksum
+/// taken at upload before it is handed out.
+async fn download_attachment(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path((id, attachment_id)): Path<(PatientId, Uuid)>,
+) -> ApiResult<Json<AttachmentDownloadResponse>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    let storage = attachment_storage(&state)?;
+
+    let not_found = || AppError::NotFound {
+        resource: "Attachment".to_string(),
+    };
+    let attachment = AttachmentRepository::new(state.db.clone())
+        .find(&req_ctx, id, attachment_id)
+        .await?
+        .ok_or_else(not_found)?;
+    let content = storage
+        .get(&attachment.storage_key)
+        .await?
+        .ok_or_else(not_found)?;
+
+    if storage::checksum(&content) != attachment.checksum {
+        return Err(AppError::external_service_error(
+            "Object storage",
+            format!("Attachment {} failed its integrity check", attachment.id),
+        )
+        .into());
+    }
+
+    info!(
+        "User {} downloaded attachment {} of patient {}",
+        ctx.user_id(),
+        attachment.id,
+        attachment.patient_id
+    );
+
+    Ok(Json(AttachmentDownloadResponse::new(&attachment, &content)))
+}
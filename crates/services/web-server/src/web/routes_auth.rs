@@ -0,0 +1,443 @@
+use std::net::SocketAddr;
+
+use axum::extract::{ConnectInfo, Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::device::DeviceFingerprint;
+use lib_auth::jwt::HealthcareClaims;
+use lib_auth::lockout::LoginFailure;
+use lib_auth::password::verify_password;
+use lib_auth::session::Session;
+use lib_core::store::{DeviceRepository, ServiceAccountRepository, UserRepository};
+use lib_types::dtos::{
+    ChangePasswordRequest, ClientCredentialsRequest, LoginRequest, LoginResponse,
+    LogoutAllResponse, ReauthenticateRequest, ServiceTokenResponse, SessionListResponse, SessionResponse,
+    UserProfileDto,
+};
+use lib_types::entities::{AuthAuditEntry, ServiceAccount, User};
+use lib_types::enums::{AuthEvent, AuthOutcome};
+use lib_types::errors::{AppError, AuthError, Validate};
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+use super::mw_auth_audit::{record_auth_event, record_auth_failure, ClientInfo};
+
+/// Routes that must be reachable without an access token
+pub fn public_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/auth/login", post(login))
+        .route("/api/auth/token", post(client_credentials_token))
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/auth/sessions", get(list_sessions))
+        .route("/api/auth/sessions/:id", delete(terminate_session))
+        .route("/api/auth/logout-all", post(logout_all))
+        .route("/api/auth/password", post(change_password))
+        .route("/api/auth/reauthenticate", post(reauthenticate))
+}
+
+/// Verify credentials, record the device, start a server-side session and
+/// issue a token bound to it
+async fn login(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    req_ctx: RequestCtx,
+    Json(payload): Json<LoginRequest>,
+) -> ApiResult<Json<LoginResponse>> {
+    payload.validate()?;
+
+    let username = payload.sanitized_username();
+    let client = ClientInfo::new(Some(addr), &headers);
+    let user = match authenticate(&state, &req_ctx, &username, &payload.password).await {
+        Ok(user) => user,
+        Err(e) => {
+            record_auth_failure(&state, &req_ctx, &e.0, &username, &client).await;
+            return Err(e);
+        }
+    };
+
+    let device = DeviceRepository::new(state.db.clone())
+        .record_login(
+            &req_ctx,
+            user.id,
+            DeviceFingerprint::from_headers(&headers).as_str(),
+            client.user_agent.as_deref(),
+            client.ip_address.as_deref(),
+        )
+        .await?;
+
+    let session = Session::new(user.id, user.role, user.hospital_id, state.sessions.timeout())
+        .with_client(client.ip_address.clone(), client.user_agent.clone())
+        .with_device(device.id);
+    state.sessions.save(&session).await?;
+
+    // The new session is the most recently used, so it is never the one evicted
+    let max_sessions = state.config.load().security.session_limits().for_role(user.role);
+    let evicted = state.sessions.evict_excess(user.id, max_sessions).await?;
+    if !evicted.is_empty() {
+        info!(
+            "Evicted {} session(s) of user {} over the {} limit of {}",
+            evicted.len(),
+            user.id,
+            user.role_display(),
+            max_sessions
+        );
+    }
+
+    let token = issue_staff_token(&state, &user, session.id).await?;
+
+    info!(
+        "User {} logged in (session {}, device {})",
+        user.id, session.id, device.id
+    );
+    let entry = AuthAuditEntry::new(AuthEvent::Login, AuthOutcome::Success)
+        .with_user(user.id)
+        .with_username(&user.username);
+    record_auth_event(&state, &req_ctx, client.apply(entry)).await;
+
+    Ok(Json(
+        LoginResponse::new(
+            token,
+            state.jwt.expiration_seconds(),
+            UserProfileDto::from_user(&user),
+        )
+        .with_device(device.id, device.is_trusted_at(Utc::now())),
+    ))
+}
+
+/// Issue an access token for a session, carrying any role currently delegated to the user
+async fn issue_staff_token(state: &AppState, user: &User, session_id: Uuid) -> ApiResult<String> {
+    let delegation = state.delegations.active_for_delegate(user.id).await?;
+
+    let claims =
+        HealthcareClaims::builder(user.id, &user.username, user.role, user.hospital_id, session_id)
+            .delegation(delegation.map(|delegation| delegation.claim()));
+
+    state.jwt.issue_token(claims).map_err(|e| {
+        error!("Failed to issue access token: {}", e);
+        AppError::Internal.into()
+    })
+}
+
+/// Check a staff member's password against the lockout and return the account
+async fn authenticate(
+    state: &AppState,
+    req_ctx: &RequestCtx,
+    username: &str,
+    password: &str,
+) -> ApiResult<User> {
+    // Attempts are tracked per username, whether or not the account exists,
+    // so lockouts do not reveal which usernames are valid
+    state.login_attempts.check(username).await?;
+
+    let users = UserRepository::new(state.db.clone());
+    let user = users.find_by_username(req_ctx, username).await?;
+    let verified = state
+        .password_hasher
+        .verify_or_dummy(password, user.as_ref().map(|user| user.password_hash.as_str()));
+
+    let Some(user) = user.filter(|_| verified) else {
+        return Err(record_failed_login(state, username).await?.into());
+    };
+    state.login_attempts.reset(username).await?;
+
+    // Upgrade legacy bcrypt and outdated Argon2 hashes while the plaintext is at hand
+    if state.password_hasher.needs_rehash(&user.password_hash) {
+        if let Err(e) = upgrade_password_hash(state, req_ctx, &users, &user, password).await {
+            warn!("Failed to upgrade password hash for user {}: {}", user.id, e);
+        }
+    }
+
+    if !user.is_active {
+        return Err(AuthError::AccountDisabled {
+            username: user.username,
+        }
+        .into());
+    }
+
+    Ok(user)
+}
+
+/// Issue a scoped token to a service account (OAuth 2.0 client-credentials grant)
+async fn client_credentials_token(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    req_ctx: RequestCtx,
+    Json(payload): Json<ClientCredentialsRequest>,
+) -> ApiResult<Json<ServiceTokenResponse>> {
+    payload.validate()?;
+    let requested_scopes = payload.requested_scopes()?;
+
+    let client_id = payload.client_id.trim();
+    let client = ClientInfo::new(Some(addr), &headers);
+    let account = match authenticate_client(&state, &req_ctx, client_id, &payload.client_secret)
+        .await
+    {
+        Ok(account) => account,
+        Err(e) => {
+            record_auth_failure(&state, &req_ctx, &e.0, client_id, &client).await;
+            return Err(e);
+        }
+    };
+
+    let scopes = account.grant_scopes(requested_scopes)?;
+    let token = state
+        .jwt
+        .issue_service_token(account.id, &account.client_id, account.hospital_id, scopes.clone())
+        .map_err(|e| {
+            error!("Failed to issue service token: {}", e);
+            AppError::Internal
+        })?;
+
+    if let Err(e) = ServiceAccountRepository::new(state.db.clone())
+        .touch_last_used(&req_ctx, account.id)
+        .await
+    {
+        warn!("Failed to record use of service account {}: {}", account.id, e);
+    }
+    info!("Issued service token to {}", account.client_id);
+    let entry = AuthAuditEntry::new(AuthEvent::ServiceToken, AuthOutcome::Success)
+        .with_username(&account.client_id);
+    record_auth_event(&state, &req_ctx, client.apply(entry)).await;
+
+    Ok(Json(ServiceTokenResponse::new(
+        token,
+        state.jwt.service_expiration_seconds(),
+        &scopes,
+    )))
+}
+
+/// Check a service account's client secret against the lockout and return the account
+async fn authenticate_client(
+    state: &AppState,
+    req_ctx: &RequestCtx,
+    client_id: &str,
+    client_secret: &str,
+) -> ApiResult<ServiceAccount> {
+    // Wrong secrets share the login lockout, keyed apart from staff usernames
+    let attempt_key = format!("client:{}", client_id);
+    state.login_attempts.check(&attempt_key).await?;
+
+    let account = ServiceAccountRepository::new(state.db.clone())
+        .find_by_client_id(req_ctx, client_id)
+        .await?;
+    let verified = state.password_hasher.verify_or_dummy(
+        client_secret,
+        account.as_ref().map(|account| account.client_secret_hash.as_str()),
+    );
+
+    let Some(account) = account.filter(|_| verified) else {
+        return Err(record_failed_login(state, &attempt_key).await?.into());
+    };
+    state.login_attempts.reset(&attempt_key).await?;
+
+    if !account.is_active {
+        return Err(AuthError::AccountDisabled {
+            username: account.client_id,
+        }
+        .into());
+    }
+
+    Ok(account)
+}
+
+/// Count a wrong password towards the lockout and pick the error to report
+async fn record_failed_login(state: &AppState, username: &str) -> Result<AuthError, AppError> {
+    Ok(match state.login_attempts.record_failure(username).await? {
+        LoginFailure::Locked => {
+            warn!("Account {} locked after repeated failed logins", username);
+            AuthError::AccountLocked
+        }
+        LoginFailure::Delayed { failures, .. } => {
+            warn!("Failed login for {} ({} consecutive)", username, failures);
+            AuthError::InvalidCredentials
+        }
+    })
+}
+
+/// Re-hash a just-verified password with the current Argon2 parameters
+async fn upgrade_password_hash(
+    state: &AppState,
+    req_ctx: &RequestCtx,
+    users: &UserRepository,
+    user: &User,
+    password: &str,
+) -> Result<(), AppError> {
+    let new_hash = state.password_hasher.hash(password)?;
+    users
+        .rehash_password(req_ctx, user.id, &user.password_hash, &new_hash)
+        .await
+}
+
+/// List the caller's active sessions across devices
+async fn list_sessions(
+    State(state): State<AppState>,
+    ctx: Ctx,
+) -> ApiResult<Json<SessionListResponse>> {
+    let sessions = state
+        .sessions
+        .list_for_user(ctx.user_id())
+        .await?
+        .into_iter()
+        .map(|session| SessionResponse {
+            is_current: session.id == ctx.session_id(),
+            id: session.id,
+            ip_address: session.ip_address,
+            user_agent: session.user_agent,
+            device_id: session.device_id,
+            created_at: session.created_at,
+            last_seen_at: session.last_seen_at,
+            expires_at: session.expires_at,
+        })
+        .collect();
+
+    Ok(Json(SessionListResponse::new(sessions)))
+}
+
+/// Terminate one of the caller's sessions, e.g. on a lost device
+async fn terminate_session(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    // Other users' sessions are reported as missing rather than forbidden
+    let owned = state
+        .sessions
+        .get(id)
+        .await?
+        .is_some_and(|session| session.user_id == ctx.user_id());
+    if !owned {
+        return Err(AppError::not_found("Session").into());
+    }
+
+    state.sessions.delete(id).await?;
+    info!("User {} terminated session {}", ctx.user_id(), id);
+    let entry = AuthAuditEntry::new(AuthEvent::Logout, AuthOutcome::Success)
+        .with_user(ctx.user_id())
+        .with_resource(format!("session {}", id));
+    record_auth_event(&state, &req_ctx, ClientInfo::new(Some(addr), &headers).apply(entry)).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Terminate every session of the caller, e.g. after losing a device; all of
+/// their access tokens stop working immediately
+async fn logout_all(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+) -> ApiResult<Json<LogoutAllResponse>> {
+    let terminated = state.sessions.delete_all_for_user(ctx.user_id()).await?;
+
+    info!(
+        "User {} logged out everywhere ({} session(s))",
+        ctx.user_id(),
+        terminated.len()
+    );
+    let entry = AuthAuditEntry::new(AuthEvent::Logout, AuthOutcome::Success)
+        .with_user(ctx.user_id())
+        .with_resource("all sessions");
+    record_auth_event(&state, &req_ctx, ClientInfo::new(Some(addr), &headers).apply(entry)).await;
+
+    Ok(Json(LogoutAllResponse {
+        user_id: ctx.user_id(),
+        terminated_sessions: terminated.len(),
+    }))
+}
+
+/// Change the caller's password, enforcing the password policy and history
+async fn change_password(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<ChangePasswordRequest>,
+) -> ApiResult<StatusCode> {
+    payload.validate()?;
+
+    let users = UserRepository::new(state.db.clone());
+    let user = users
+        .find_by_id(&req_ctx, ctx.user_id())
+        .await?
+        .ok_or(AuthError::SessionTerminated)?;
+
+    if !verify_password(&payload.current_password, &user.password_hash) {
+        return Err(AuthError::InvalidCredentials.into());
+    }
+
+    let policy = &state.password_policy;
+    policy.validate(&payload.new_password, &user.username)?;
+
+    let history = users
+        .password_history(&req_ctx, user.id, policy.history_size)
+        .await?;
+    let previous_hashes =
+        std::iter::once(user.password_hash.as_str()).chain(history.iter().map(String::as_str));
+    policy.check_reuse(&payload.new_password, previous_hashes)?;
+
+    let password_hash = state.password_hasher.hash(&payload.new_password)?;
+    users
+        .update_password(&req_ctx, user.id, &password_hash, policy.history_size)
+        .await?;
+
+    info!("User {} changed their password", user.id);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Confirm the caller's password and issue a token for the same session with a
+/// fresh authentication time, satisfying step-up checks
+async fn reauthenticate(
+    State(state): State<AppState>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<ReauthenticateRequest>,
+) -> ApiResult<Json<LoginResponse>> {
+    payload.validate()?;
+
+    let user = UserRepository::new(state.db.clone())
+        .find_by_id(&req_ctx, ctx.user_id())
+        .await?
+        .ok_or(AuthError::SessionTerminated)?;
+
+    // Wrong passwords count towards the same lockout as logins, so a stolen
+    // token cannot be used to guess the password
+    let username = user.sanitized_username();
+    state.login_attempts.check(&username).await?;
+
+    if !verify_password(&payload.password, &user.password_hash) {
+        return Err(record_failed_login(&state, &username).await?.into());
+    }
+    state.login_attempts.reset(&username).await?;
+
+    let token = issue_staff_token(&state, &user, ctx.session_id()).await?;
+
+    info!("User {} re-authenticated (session {})", user.id, ctx.session_id());
+    let entry = AuthAuditEntry::new(AuthEvent::TokenRefresh, AuthOutcome::Success)
+        .with_user(user.id)
+        .with_username(&user.username);
+    record_auth_event(&state, &req_ctx, ClientInfo::new(Some(addr), &headers).apply(entry)).await;
+
+    Ok(Json(LoginResponse::new(
+        token,
+        state.jwt.expiration_seconds(),
+        UserProfileDto::from_user(&user),
+    )))
+}
@@ -0,0 +1,122 @@
+use axum::extract::{Path, Query, State};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tracing::info;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::{ensure_hospital_access, ResourceKind};
+use lib_auth::rbac::Permissions;
+use lib_core::model::{admit_patient, release_bed};
+use lib_core::store::BedRepository;
+use lib_types::dtos::{AssignBedRequest, BedListQuery, BedOutOfServiceRequest, BedResponse};
+use lib_types::enums::BedStatus;
+use lib_types::errors::{AuthError, Validate};
+use lib_types::ids::{BedId, HospitalId};
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+/// Routes addressing a bed by `:id`
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/beds/:id/assign", post(assign_bed))
+        .route("/api/beds/:id/release", post(release))
+        .route("/api/beds/:id/out-of-service", post(take_out_of_service))
+}
+
+/// Routes addressing the hospital the beds belong to by `:id`
+pub fn hospital_routes() -> Router<AppState> {
+    Router::new().route("/api/hospitals/:id/beds", get(list_beds))
+}
+
+/// The bed board of a hospital, by ward, room and bed number
+async fn list_beds(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(hospital_id): Path<HospitalId>,
+    Query(query): Query<BedListQuery>,
+) -> ApiResult<Json<Vec<BedResponse>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS)
+        && !ctx.has_permission(Permissions::MANAGE_BEDS)
+    {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    ensure_hospital_access(&ctx, ResourceKind::Bed, hospital_id)?;
+
+    let beds = BedRepository::new(state.db.clone())
+        .list_for_hospital(&req_ctx, hospital_id, query.status)
+        .await?;
+
+    Ok(Json(beds.iter().map(BedResponse::from_bed).collect()))
+}
+
+/// Admit a patient to a bed, moving them out of any bed they held
+async fn assign_bed(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<BedId>,
+    Json(payload): Json<AssignBedRequest>,
+) -> ApiResult<Json<BedResponse>> {
+    if !ctx.has_permission(Permissions::MANAGE_BEDS | Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let (patient, bed) = admit_patient(&state.db, &req_ctx, payload.patient_id, id).await?;
+
+    info!(
+        "User {} admitted patient {} to bed {}",
+        ctx.user_id(),
+        patient.patient_number,
+        bed.label()
+    );
+
+    Ok(Json(BedResponse::from_bed(&bed)))
+}
+
+/// Free an occupied bed; it goes to cleaning
+async fn release(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<BedId>,
+) -> ApiResult<Json<BedResponse>> {
+    if !ctx.has_permission(Permissions::MANAGE_BEDS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let bed = release_bed(&state.db, &req_ctx, id).await?;
+
+    info!("User {} released bed {}", ctx.user_id(), bed.label());
+
+    Ok(Json(BedResponse::from_bed(&bed)))
+}
+
+/// Take a free bed out of service until it is cleaned or repaired
+async fn take_out_of_service(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<BedId>,
+    Json(payload): Json<BedOutOfServiceRequest>,
+) -> ApiResult<Json<BedResponse>> {
+    if !ctx.has_permission(Permissions::MANAGE_BEDS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    payload.validate()?;
+
+    let bed = BedRepository::new(state.db.clone())
+        .update_status(&req_ctx, id, BedStatus::OutOfService, payload.version)
+        .await?;
+
+    info!(
+        "User {} took bed {} out of service: {}",
+        ctx.user_id(),
+        bed.label(),
+        payload.reason.trim()
+    );
+
+    Ok(Json(BedResponse::from_bed(&bed)))
+}
@@ -0,0 +1,47 @@
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+
+use lib_auth::ctx::Ctx;
+use lib_auth::rbac::audit_break_glass_activated;
+use lib_types::dtos::{BreakGlassRequest, BreakGlassResponse};
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/api/break-glass", post(activate_break_glass))
+}
+
+/// Grant the caller time-boxed read access to another hospital's patients
+async fn activate_break_glass(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    Json(payload): Json<BreakGlassRequest>,
+) -> ApiResult<(StatusCode, Json<BreakGlassResponse>)> {
+    let duration = payload
+        .duration_minutes
+        .map(|minutes| Duration::from_secs(u64::from(minutes) * 60));
+
+    let grant = state.config.load().security.break_glass_policy().grant(
+        &ctx,
+        payload.hospital_id,
+        &payload.reason,
+        duration,
+    )?;
+    state.break_glass.activate(&grant).await?;
+    audit_break_glass_activated(&grant);
+
+    Ok((
+        StatusCode::CREATED,
+        Json(BreakGlassResponse {
+            hospital_id: grant.hospital_id,
+            reason: grant.reason,
+            granted_at: grant.granted_at,
+            expires_at: grant.expires_at,
+        }),
+    ))
+}
@@ -0,0 +1,110 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tracing::info;
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::rbac::Permissions;
+use lib_core::store::ConsentRepository;
+use lib_types::dtos::{ConsentResponse, RecordConsentRequest, WithdrawConsentRequest};
+use lib_types::errors::{AuthError, Validate};
+use lib_types::ids::PatientId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/patients/:id/consents",
+            get(list_consents).post(record_consent),
+        )
+        .route(
+            "/api/patients/:id/consents/:consent_id/withdraw",
+            post(withdraw_consent),
+        )
+}
+
+/// Record consent given by a patient or on their behalf
+async fn record_consent(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<RecordConsentRequest>,
+) -> ApiResult<(StatusCode, Json<ConsentResponse>)> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let consent = ConsentRepository::new(state.db.clone())
+        .record(&req_ctx, id, &payload, ctx.user_id())
+        .await?;
+
+    info!(
+        "User {} recorded {} consent {} for patient {}",
+        ctx.user_id(),
+        consent.consent_type,
+        consent.id,
+        consent.patient_id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ConsentResponse::from_consent(&consent)),
+    ))
+}
+
+async fn list_consents(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+) -> ApiResult<Json<Vec<ConsentResponse>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let consents = ConsentRepository::new(state.db.clone())
+        .list_for_patient(&req_ctx, id)
+        .await?;
+
+    Ok(Json(
+        consents.iter().map(ConsentResponse::from_consent).collect(),
+    ))
+}
+
+async fn withdraw_consent(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path((id, consent_id)): Path<(PatientId, Uuid)>,
+    Json(payload): Json<WithdrawConsentRequest>,
+) -> ApiResult<Json<ConsentResponse>> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let consent = ConsentRepository::new(state.db.clone())
+        .withdraw(
+            &req_ctx,
+            id,
+            consent_id,
+            ctx.user_id(),
+            payload.reason.trim().to_string(),
+        )
+        .await?;
+
+    info!(
+        "User {} withdrew consent {} for patient {}",
+        ctx.user_id(),
+        consent.id,
+        consent.patient_id
+    );
+
+    Ok(Json(ConsentResponse::from_consent(&consent)))
+}
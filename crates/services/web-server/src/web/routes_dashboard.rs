@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use serde::Serialize;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{info, warn};
+
+use lib_auth::ctx::Ctx;
+use lib_auth::middleware::{ensure_hospital_access, ResourceKind, WEBSOCKET_AUTH_PROTOCOL};
+use lib_auth::rbac::Permissions;
+use lib_types::dtos::{DashboardCommand, DashboardNotice, DashboardSubscription, LiveEvent};
+use lib_types::errors::AuthError;
+use lib_types::ids::HospitalId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+/// How often dashboards are pinged; one that has not been heard from for
+/// two intervals is disconnected
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(20);
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(40);
+/// A dashboard whose socket does not take a message within this time is
+/// too slow to keep up and is disconnected
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/ws/dashboard", get(dashboard))
+}
+
+/// Live ER dashboard: patient status, triage, bed and ambulance events of
+/// the hospitals the connection follows, starting with the caller's own
+async fn dashboard(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    ws: WebSocketUpgrade,
+) -> ApiResult<Response> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) && !ctx.has_permission(Permissions::DISPATCH)
+    {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let events = state.events.subscribe();
+    Ok(ws
+        .protocols([WEBSOCKET_AUTH_PROTOCOL])
+        .on_upgrade(move |socket| serve_dashboard(socket, ctx, events)))
+}
+
+async fn serve_dashboard(
+    mut socket: WebSocket,
+    ctx: Ctx,
+    mut events: broadcast::Receiver<LiveEvent>,
+) {
+    info!("User {} connected a live dashboard", ctx.user_id());
+
+    let mut subscription = DashboardSubscription::default();
+    let mut outgoing = Some(subscribe(&ctx, &mut subscription, ctx.hospital_id(), &[]));
+    let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+    heartbeat.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut last_seen = Instant::now();
+
+    loop {
+        if let Some(message) = outgoing.take() {
+            match tokio::time::timeout(SEND_TIMEOUT, socket.send(message)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(_)) => break,
+                Err(_) => {
+                    warn!(
+                        "Disconnecting live dashboard of user {}: too slow",
+                        ctx.user_id()
+                    );
+                    break;
+                }
+            }
+        }
+
+        outgoing = tokio::select! {
+            message = socket.recv() => {
+                let Some(Ok(message)) = message else { break };
+                last_seen = Instant::now();
+                match message {
+                    Message::Text(text) => Some(handle_command(&ctx, &mut subscription, &text)),
+                    Message::Close(_) => break,
+                    Message::Binary(_) => Some(notice(&DashboardNotice::Error {
+                        message: "Commands must be sent as JSON text".to_string(),
+                    })),
+                    // Pings are answered by axum; pongs only count as a sign of life
+                    Message::Ping(_) | Message::Pong(_) => None,
+                }
+            }
+            event = events.recv() => match event {
+                Ok(event) => subscription.matches(&event).then(|| notice(&event)),
+                // The bus dropped events this dashboard had not read yet
+                Err(RecvError::Lagged(missed)) => {
+                    Some(notice(&DashboardNotice::Resync { missed }))
+                }
+                Err(RecvError::Closed) => break,
+            },
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() > HEARTBEAT_TIMEOUT {
+                    info!("Live dashboard of user {} stopped responding", ctx.user_id());
+                    break;
+                }
+                Some(Message::Ping(Vec::new()))
+            }
+        };
+    }
+
+    info!("User {} disconnected a live dashboard", ctx.user_id());
+}
+
+fn handle_command(ctx: &Ctx, subscription: &mut DashboardSubscription, text: &str) -> Message {
+    match serde_json::from_str::<DashboardCommand>(text) {
+        Ok(DashboardCommand::Subscribe {
+            hospital_id,
+            channels,
+        }) => subscribe(ctx, subscription, hospital_id, &channels),
+        Ok(DashboardCommand::Unsubscribe { hospital_id }) => {
+            subscription.unsubscribe(hospital_id);
+            notice(&DashboardNotice::Unsubscribed { hospital_id })
+        }
+        Err(e) => notice(&DashboardNotice::Error {
+            message: format!("Invalid command: {}", e),
+        }),
+    }
+}
+
+/// Follow a hospital the caller may see patients of
+fn subscribe(
+    ctx: &Ctx,
+    subscription: &mut DashboardSubscription,
+    hospital_id: HospitalId,
+    channels: &[String],
+) -> Message {
+    if let Err(e) = ensure_hospital_access(ctx, ResourceKind::Patient, hospital_id) {
+        return notice(&DashboardNotice::Error {
+            message: e.to_string(),
+        });
+    }
+
+    match subscription.subscribe(hospital_id, channels) {
+        Ok(channels) => notice(&DashboardNotice::Subscribed {
+            hospital_id,
+            channels: channels.into_iter().map(String::from).collect(),
+        }),
+        Err(errors) => notice(&DashboardNotice::Error {
+            message: errors.to_string(),
+        }),
+    }
+}
+
+fn notice<T: Serialize>(message: &T) -> Message {
+    // Live events and notices always serialize
+    Message::Text(serde_json::to_string(message).unwrap_or_default())
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, post};
+use axum::{Json, Router};
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::ensure_recent_auth;
+use lib_auth::rbac::{audit_delegation_granted, audit_delegation_revoked, Delegation};
+use lib_core::store::UserRepository;
+use lib_types::dtos::{DelegateRoleRequest, DelegationResponse};
+use lib_types::enums::UserRole;
+use lib_types::errors::{AppError, AuthError};
+use lib_types::ids::UserId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/delegations", post(delegate_role))
+        .route("/api/delegations/:delegate_id", delete(revoke_delegation))
+}
+
+/// Hand the caller's role to a colleague for a limited time.
+///
+/// The delegate picks up the role with the next token they are issued
+/// (login or re-authentication).
+async fn delegate_role(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<DelegateRoleRequest>,
+) -> ApiResult<(StatusCode, Json<DelegationResponse>)> {
+    ensure_recent_auth(&ctx, state.config.load().security.step_up_max_age())?;
+
+    let delegate = UserRepository::new(state.db.clone())
+        .find_by_id(&req_ctx, payload.delegate_id)
+        .await?
+        .filter(|user| user.is_active)
+        .ok_or_else(|| AppError::not_found("User"))?;
+
+    let duration = payload
+        .duration_hours
+        .map(|hours| Duration::from_secs(u64::from(hours) * 3600));
+
+    let delegation = state.config.load().security.delegation_policy().grant(
+        &ctx,
+        delegate.id,
+        delegate.role,
+        delegate.hospital_id,
+        &payload.reason,
+        duration,
+    )?;
+    state.delegations.grant(&delegation).await?;
+    audit_delegation_granted(&delegation);
+
+    Ok((StatusCode::CREATED, Json(delegation_response(delegation))))
+}
+
+/// End a delegation early; allowed for the delegator, the delegate and admins
+async fn revoke_delegation(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    Path(delegate_id): Path<UserId>,
+) -> ApiResult<StatusCode> {
+    let delegation = state
+        .delegations
+        .active_for_delegate(delegate_id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Delegation"))?;
+
+    let allowed = ctx.user_id() == delegation.delegator_id
+        || ctx.user_id() == delegation.delegate_id
+        || ctx.own_role() == UserRole::Admin;
+    if !allowed {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    if let Some(revoked) = state.delegations.revoke(delegate_id).await? {
+        audit_delegation_revoked(&revoked, ctx.user_id());
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn delegation_response(delegation: Delegation) -> DelegationResponse {
+    DelegationResponse {
+        id: delegation.id,
+        delegator_id: delegation.delegator_id,
+        delegate_id: delegation.delegate_id,
+        role: delegation.role,
+        reason: delegation.reason,
+        granted_at: delegation.granted_at,
+        expires_at: delegation.expires_at,
+    }
+}
@@ -0,0 +1,115 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use tracing::info;
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::ensure_recent_auth;
+use lib_core::store::DeviceRepository;
+use lib_types::dtos::{DeviceListResponse, DeviceResponse, TrustDeviceRequest};
+use lib_types::errors::AppError;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/auth/devices", get(list_devices))
+        .route("/api/auth/devices/:id", delete(revoke_device))
+        .route("/api/auth/devices/:id/trust", post(trust_device))
+}
+
+/// List the devices the caller has logged in from
+async fn list_devices(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+) -> ApiResult<Json<DeviceListResponse>> {
+    let current_device = state
+        .sessions
+        .get(ctx.session_id())
+        .await?
+        .and_then(|session| session.device_id);
+
+    let devices = DeviceRepository::new(state.db.clone())
+        .list_for_user(&req_ctx, ctx.user_id())
+        .await?
+        .iter()
+        .map(|device| DeviceResponse::from_device(device, current_device == Some(device.id)))
+        .collect();
+
+    Ok(Json(DeviceListResponse::new(devices)))
+}
+
+/// Trust one of the caller's devices so it is exempt from MFA challenges
+async fn trust_device(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<TrustDeviceRequest>,
+) -> ApiResult<Json<DeviceResponse>> {
+    // Trust weakens later logins, so it needs the same proof as other sensitive changes
+    ensure_recent_auth(&ctx, state.config.load().security.step_up_max_age())?;
+
+    let trusted_until = Utc::now()
+        + chrono::Duration::from_std(state.config.load().security.trusted_device_duration())
+            .map_err(|_| AppError::Internal)?;
+    let device = DeviceRepository::new(state.db.clone())
+        .trust(
+            &req_ctx,
+            ctx.user_id(),
+            id,
+            payload.sanitized_name().as_deref(),
+            trusted_until,
+        )
+        .await?
+        .ok_or_else(|| AppError::not_found("Device"))?;
+
+    info!(
+        "User {} trusted device {} until {}",
+        ctx.user_id(),
+        device.id,
+        trusted_until
+    );
+
+    Ok(Json(DeviceResponse::from_device(&device, false)))
+}
+
+/// Forget one of the caller's devices and end every session started from it
+async fn revoke_device(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let removed = DeviceRepository::new(state.db.clone())
+        .delete(&req_ctx, ctx.user_id(), id)
+        .await?;
+    if !removed {
+        return Err(AppError::not_found("Device").into());
+    }
+
+    let sessions = state.sessions.list_for_user(ctx.user_id()).await?;
+    let mut terminated = 0;
+    for session in sessions
+        .iter()
+        .filter(|session| session.device_id == Some(id))
+    {
+        if state.sessions.delete(session.id).await? {
+            terminated += 1;
+        }
+    }
+
+    info!(
+        "User {} revoked device {} ({} sessions terminated)",
+        ctx.user_id(),
+        id,
+        terminated
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,126 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tracing::info;
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::rbac::Permissions;
+use lib_core::store::HandoverRepository;
+use lib_types::dtos::{CreateHandoverRequest, HandoverReportResponse};
+use lib_types::errors::{AuthError, Validate};
+use lib_types::ids::PatientId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/patients/:id/handovers",
+            get(list_handovers).post(create_handover),
+        )
+        .route("/api/handovers/pending", get(pending_handovers))
+        .route("/api/handovers/:id/acknowledge", post(acknowledge_handover))
+}
+
+/// Record the ambulance crew's ATMIST handover for a patient
+async fn create_handover(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<CreateHandoverRequest>,
+) -> ApiResult<(StatusCode, Json<HandoverReportResponse>)> {
+    if !ctx.has_permission(Permissions::DISPATCH) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let report = HandoverRepository::new(state.db.clone())
+        .create(&req_ctx, id, &payload, ctx.user_id())
+        .await?;
+
+    info!(
+        "User {} handed over patient {} from ambulance {}",
+        ctx.user_id(),
+        report.patient_id,
+        report.ambulance_id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(HandoverReportResponse::from_report(&report)),
+    ))
+}
+
+async fn list_handovers(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+) -> ApiResult<Json<Vec<HandoverReportResponse>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let reports = HandoverRepository::new(state.db.clone())
+        .list_for_patient(&req_ctx, id)
+        .await?;
+
+    Ok(Json(
+        reports
+            .iter()
+            .map(HandoverReportResponse::from_report)
+            .collect(),
+    ))
+}
+
+/// Handovers waiting for receiving staff, longest waiting first
+async fn pending_handovers(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+) -> ApiResult<Json<Vec<HandoverReportResponse>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let reports = HandoverRepository::new(state.db.clone())
+        .list_pending(&req_ctx)
+        .await?;
+
+    Ok(Json(
+        reports
+            .iter()
+            .map(HandoverReportResponse::from_report)
+            .collect(),
+    ))
+}
+
+/// Take over responsibility for a patient from the ambulance crew
+async fn acknowledge_handover(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<HandoverReportResponse>> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let report = HandoverRepository::new(state.db.clone())
+        .acknowledge(&req_ctx, id, ctx.user_id())
+        .await?;
+
+    info!(
+        "User {} acknowledged handover {} of patient {} after {} minutes",
+        ctx.user_id(),
+        report.id,
+        report.patient_id,
+        report.waiting_time(chrono::Utc::now()).num_minutes()
+    );
+
+    Ok(Json(HandoverReportResponse::from_report(&report)))
+}
@@ -0,0 +1,109 @@
+use axum::extract::{Path, Query, State};
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use chrono::Utc;
+use tracing::info;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::{ensure_hospital_access, ResourceKind};
+use lib_core::store::StaffRepository;
+use lib_types::dtos::{
+    CursorPage, HospitalDistanceQuery, HospitalFilter, HospitalResponse, HospitalSortField,
+    HospitalSummary, PageRequest, UpdateCapacityRequest,
+};
+use lib_types::enums::UserRole;
+use lib_types::errors::{AppError, AuthError, Validate};
+use lib_types::ids::HospitalId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/hospitals", get(list_hospitals))
+        .route("/api/hospitals/:id/capacity", put(update_capacity))
+}
+
+/// List the hospitals visible to the caller with how full each is, with the
+/// distance and travel time from `near` when it is given
+pub(crate) async fn list_hospitals(
+    State(state): State<AppState>,
+    req_ctx: RequestCtx,
+    Query(page): Query<PageRequest<HospitalSortField>>,
+    Query(filter): Query<HospitalFilter>,
+    Query(distance): Query<HospitalDistanceQuery>,
+) -> ApiResult<Json<CursorPage<HospitalSummary>>> {
+    page.validate()?;
+    filter.validate()?;
+    distance.validate()?;
+
+    let mut hospitals = state.hospitals().list(&req_ctx, &page, &filter).await?;
+    if let Some(near) = distance.near {
+        let eta = state.config.load().healthcare.eta;
+        let now = Utc::now();
+        hospitals.items = hospitals
+            .items
+            .into_iter()
+            .map(|hospital| hospital.with_distance_from(&near).with_eta(&eta, now))
+            .collect();
+    }
+
+    Ok(Json(hospitals))
+}
+
+/// Declare a hospital's beds and whether it is taking patients, e.g. going
+/// emergency-only while the ER is overwhelmed
+async fn update_capacity(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<HospitalId>,
+    Json(payload): Json<UpdateCapacityRequest>,
+) -> ApiResult<Json<HospitalResponse>> {
+    ensure_hospital_access(&ctx, ResourceKind::Hospital, id)?;
+    ensure_capacity_manager(&state, &ctx, &req_ctx).await?;
+
+    payload.validate()?;
+
+    let (hospital, changed) = state
+        .hospitals()
+        .update(&req_ctx, id, &payload.to_update())
+        .await?;
+
+    if !changed.is_empty() {
+        info!(
+            "User {} updated capacity of hospital {}: {} of {} beds available, {}",
+            ctx.user_id(),
+            hospital.id,
+            hospital.available_beds,
+            hospital.total_beds,
+            hospital.status
+        );
+    }
+
+    Ok(Json(HospitalResponse::from_hospital(&hospital)))
+}
+
+/// Capacity is declared by ER directors, including those acting for one,
+/// and by charge nurses: nurses of senior grade or above
+async fn ensure_capacity_manager(
+    state: &AppState,
+    ctx: &Ctx,
+    req_ctx: &RequestCtx,
+) -> Result<(), AppError> {
+    let acting_role = ctx.delegation().map(|delegation| delegation.role);
+    if ctx.role() == UserRole::ErDirector || acting_role == Some(UserRole::ErDirector) {
+        return Ok(());
+    }
+
+    if ctx.role() == UserRole::Nurse {
+        let staff = StaffRepository::new(state.db.clone())
+            .find_by_user(req_ctx, ctx.user_id())
+            .await?;
+        if staff.is_some_and(|staff| staff.seniority_level.is_senior()) {
+            return Ok(());
+        }
+    }
+
+    Err(AuthError::InsufficientPermissions.into())
+}
@@ -0,0 +1,253 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::rbac::Permissions;
+use lib_core::store::IncidentRepository;
+use lib_types::dtos::{
+    AssignCommanderRequest, CreateIncidentRequest, IncidentListQuery, IncidentListResponse,
+    IncidentResponse, LinkIncidentResourcesRequest, UpdateIncidentSeverityRequest,
+    UpdateIncidentStatusRequest,
+};
+use lib_types::entities::EmergencyIncident;
+use lib_types::errors::{AuthError, IncidentError, Validate};
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/incidents", get(list_incidents).post(create_incident))
+        .route("/api/incidents/:id", get(get_incident))
+        .route("/api/incidents/:id/status", put(update_status))
+        .route("/api/incidents/:id/severity", put(update_severity))
+        .route("/api/incidents/:id/commander", put(assign_commander))
+        .route("/api/incidents/:id/links", post(link_resources))
+}
+
+/// Incidents are visible to anyone who can see patients; changing them is
+/// part of dispatch
+fn require_permission(ctx: &Ctx, permission: Permissions) -> Result<(), AuthError> {
+    if ctx.has_permission(permission) {
+        Ok(())
+    } else {
+        Err(AuthError::InsufficientPermissions)
+    }
+}
+
+/// The response for `incident`, with its catchment zone when zones are
+/// configured
+fn incident_response(state: &AppState, incident: &EmergencyIncident) -> IncidentResponse {
+    let response = IncidentResponse::from_incident(incident);
+    match state.catchment_zones {
+        Some(ref zones) => {
+            let max_distance_km = state.config.load().healthcare.catchment.max_distance_km;
+            response.with_catchment_zone(zones, max_distance_km)
+        }
+        None => response,
+    }
+}
+
+/// Declare a new incident coordinated by the caller's hospital
+async fn create_incident(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<CreateIncidentRequest>,
+) -> ApiResult<(StatusCode, Json<IncidentResponse>)> {
+    require_permission(&ctx, Permissions::DISPATCH)?;
+    payload.validate()?;
+
+    let mut incident = EmergencyIncident::new(
+        payload.incident_type,
+        payload.severity,
+        payload.location.trim().to_string(),
+        ctx.hospital_id(),
+        ctx.user_id(),
+    );
+    if let (Some(latitude), Some(longitude)) = (payload.latitude, payload.longitude) {
+        incident.set_position(latitude, longitude)?;
+    }
+    incident.description = payload
+        .description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    incident.link(
+        &[],
+        &[],
+        payload.hospital_ids.as_deref().unwrap_or_default(),
+    )?;
+    if let Some(commander_id) = payload.commander_id {
+        incident.assign_commander(commander_id)?;
+    }
+
+    IncidentRepository::new(state.db.clone())
+        .create(&req_ctx, &incident)
+        .await?;
+
+    if incident.is_mass_casualty() {
+        warn!(
+            "User {} declared {} incident {} at {}",
+            ctx.user_id(),
+            incident.severity,
+            incident.id,
+            incident.location
+        );
+    } else {
+        info!(
+            "User {} reported {} incident {}",
+            ctx.user_id(),
+            incident.incident_type,
+            incident.id
+        );
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(incident_response(&state, &incident)),
+    ))
+}
+
+/// List incidents involving the caller's hospital
+async fn list_incidents(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Query(query): Query<IncidentListQuery>,
+) -> ApiResult<Json<IncidentListResponse>> {
+    require_permission(&ctx, Permissions::VIEW_PATIENTS)?;
+
+    let incidents = IncidentRepository::new(state.db.clone())
+        .list(&req_ctx, query.status)
+        .await?;
+
+    let mut response = IncidentListResponse::from_incidents(&incidents);
+    if let Some(ref zones) = state.catchment_zones {
+        let max_distance_km = state.config.load().healthcare.catchment.max_distance_km;
+        response = response.with_catchment_zones(zones, max_distance_km);
+    }
+
+    Ok(Json(response))
+}
+
+async fn get_incident(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<IncidentResponse>> {
+    require_permission(&ctx, Permissions::VIEW_PATIENTS)?;
+
+    let incident = IncidentRepository::new(state.db.clone())
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or(IncidentError::NotFound { incident_id: id })?;
+
+    Ok(Json(incident_response(&state, &incident)))
+}
+
+async fn update_status(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateIncidentStatusRequest>,
+) -> ApiResult<Json<IncidentResponse>> {
+    require_permission(&ctx, Permissions::DISPATCH)?;
+
+    let incident = IncidentRepository::new(state.db.clone())
+        .update_status(&req_ctx, id, payload.status)
+        .await?;
+
+    info!(
+        "User {} moved incident {} to {}",
+        ctx.user_id(),
+        incident.id,
+        incident.status
+    );
+
+    Ok(Json(incident_response(&state, &incident)))
+}
+
+async fn update_severity(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateIncidentSeverityRequest>,
+) -> ApiResult<Json<IncidentResponse>> {
+    require_permission(&ctx, Permissions::DISPATCH)?;
+
+    let incident = IncidentRepository::new(state.db.clone())
+        .update_severity(&req_ctx, id, payload.severity)
+        .await?;
+
+    warn!(
+        "User {} set severity of incident {} to {}",
+        ctx.user_id(),
+        incident.id,
+        incident.severity
+    );
+
+    Ok(Json(incident_response(&state, &incident)))
+}
+
+async fn assign_commander(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AssignCommanderRequest>,
+) -> ApiResult<Json<IncidentResponse>> {
+    require_permission(&ctx, Permissions::DISPATCH)?;
+
+    let incident = IncidentRepository::new(state.db.clone())
+        .assign_commander(&req_ctx, id, payload.commander_id)
+        .await?;
+
+    info!(
+        "User {} handed command of incident {} to {}",
+        ctx.user_id(),
+        incident.id,
+        payload.commander_id
+    );
+
+    Ok(Json(incident_response(&state, &incident)))
+}
+
+/// Link patients, ambulances and receiving hospitals to an incident
+async fn link_resources(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<LinkIncidentResourcesRequest>,
+) -> ApiResult<Json<IncidentResponse>> {
+    require_permission(&ctx, Permissions::DISPATCH)?;
+    payload.validate()?;
+
+    let incident = IncidentRepository::new(state.db.clone())
+        .link(
+            &req_ctx,
+            id,
+            &payload.patient_ids,
+            &payload.ambulance_ids,
+            &payload.hospital_ids,
+        )
+        .await?;
+
+    if incident.needs_escalation() {
+        warn!(
+            "Incident {} has {} patients but is declared {}",
+            incident.id,
+            incident.casualty_count(),
+            incident.severity
+        );
+    }
+
+    Ok(Json(incident_response(&state, &incident)))
+}
@@ -0,0 +1,196 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::rbac::Permissions;
+use lib_core::store::LabRepository;
+use lib_types::dtos::{
+    CreateLabOrderRequest, IngestLabResultsRequest, IngestLabResultsResponse, LabOrderResponse,
+    LabResultResponse,
+};
+use lib_types::errors::{AuthError, Validate};
+use lib_types::ids::PatientId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/patients/:id/lab-orders",
+            get(list_lab_orders).post(create_lab_order),
+        )
+        .route(
+            "/api/patients/:id/lab-orders/:order_id/cancel",
+            post(cancel_lab_order),
+        )
+        .route(
+            "/api/patients/:id/lab-results",
+            get(list_lab_results).post(ingest_lab_results),
+        )
+        .route(
+            "/api/patients/:id/lab-results/:result_id/acknowledge",
+            put(acknowledge_lab_result),
+        )
+}
+
+async fn create_lab_order(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<CreateLabOrderRequest>,
+) -> ApiResult<(StatusCode, Json<LabOrderResponse>)> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let order = LabRepository::new(state.db.clone())
+        .create_order(&req_ctx, id, &payload, ctx.user_id())
+        .await?;
+
+    info!(
+        "User {} ordered {} for patient {}",
+        ctx.user_id(),
+        order.panel,
+        order.patient_id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(LabOrderResponse::from_order(&order)),
+    ))
+}
+
+async fn list_lab_orders(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+) -> ApiResult<Json<Vec<LabOrderResponse>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let orders = LabRepository::new(state.db.clone())
+        .list_orders(&req_ctx, id)
+        .await?;
+
+    Ok(Json(
+        orders.iter().map(LabOrderResponse::from_order).collect(),
+    ))
+}
+
+async fn cancel_lab_order(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path((id, order_id)): Path<(PatientId, Uuid)>,
+) -> ApiResult<Json<LabOrderResponse>> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let order = LabRepository::new(state.db.clone())
+        .cancel_order(&req_ctx, id, order_id)
+        .await?;
+
+    info!(
+        "User {} cancelled lab order {} for patient {}",
+        ctx.user_id(),
+        order.id,
+        order.patient_id
+    );
+
+    Ok(Json(LabOrderResponse::from_order(&order)))
+}
+
+/// Receive results from the laboratory system. Critical values are
+/// returned separately and logged as clinical alerts.
+async fn ingest_lab_results(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<IngestLabResultsRequest>,
+) -> ApiResult<(StatusCode, Json<IngestLabResultsResponse>)> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let (order, results) = LabRepository::new(state.db.clone())
+        .record_results(&req_ctx, id, &payload)
+        .await?;
+
+    for result in results.iter().filter(|result| result.is_critical()) {
+        warn!(
+            target: "clinical_alert",
+            correlation_id = req_ctx.correlation_id(),
+            "Critical lab value for patient {}: {} (result {})",
+            result.patient_id,
+            result.display_value(),
+            result.id
+        );
+    }
+    info!(
+        "User {} recorded {} results for lab order {}",
+        ctx.user_id(),
+        results.len(),
+        order.id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(IngestLabResultsResponse::new(&order, &results)),
+    ))
+}
+
+async fn list_lab_results(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+) -> ApiResult<Json<Vec<LabResultResponse>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let results = LabRepository::new(state.db.clone())
+        .list_results(&req_ctx, id)
+        .await?;
+
+    Ok(Json(
+        results.iter().map(LabResultResponse::from_result).collect(),
+    ))
+}
+
+/// Record that the clinician has been told of a critical result
+async fn acknowledge_lab_result(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path((id, result_id)): Path<(PatientId, Uuid)>,
+) -> ApiResult<Json<LabResultResponse>> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let result = LabRepository::new(state.db.clone())
+        .acknowledge(&req_ctx, id, result_id, ctx.user_id())
+        .await?;
+
+    info!(
+        "User {} acknowledged lab result {} for patient {}",
+        ctx.user_id(),
+        result.id,
+        result.patient_id
+    );
+
+    Ok(Json(LabResultResponse::from_result(&result)))
+}
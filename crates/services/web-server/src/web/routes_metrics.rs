@@ -0,0 +1,24 @@
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/metrics", get(metrics))
+}
+
+/// Query timings in the Prometheus text format, for scraping from inside
+/// the network. Missing while `server.enable_metrics` is off.
+async fn metrics(State(state): State<AppState>) -> Response {
+    if !state.config.load().server.enable_metrics {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.db.metrics().render(),
+    )
+        .into_response()
+}
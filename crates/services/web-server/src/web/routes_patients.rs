@@ -0,0 +1,658 @@
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post, put};
+use axum::{Json, Router};
+use chrono::Utc;
+use futures::stream;
+use tracing::{info, warn};
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::ensure_recent_auth;
+use lib_auth::rbac::Permissions;
+use lib_core::store::DischargeRepository;
+use lib_types::dtos::{
+    CreatePatientRequest, CursorPage, DischargePatientRequest, DischargeSummaryResponse,
+    LookupPatientRequest, PatientExportQuery, PatientListQuery, PatientListResponse,
+    PatientResponse, PatientSearchRequest, PatientSummary, RecordDnrRequest, RetriageRequest,
+    UpdatePatientRequest, UpdatePatientStatusRequest, MAX_PAGE_SIZE,
+};
+use lib_types::errors::{AppError, AuthError, PatientError, Validate};
+use lib_types::ids::PatientId;
+use lib_utils::format::Humanizer;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/patients", get(list_patients).post(create_patient))
+        .route("/api/patients/search", post(search_patients))
+        .route("/api/patients/export", get(export_patients))
+        .route("/api/patients/lookup", post(lookup_patient))
+        .route("/api/patients/:id", get(get_patient).patch(update_patient))
+        .route("/api/patients/:id/status", put(update_status))
+        .route("/api/patients/:id/triage", post(retriage_patient))
+        .route("/api/patients/:id/dnr", put(record_dnr))
+        .route("/api/patients/:id/discharge", post(discharge_patient))
+        .route(
+            "/api/patients/:id/discharge-summaries",
+            get(list_discharge_summaries),
+        )
+}
+
+/// Describe each patient's wait in the caller's language
+fn set_waiting_times(req_ctx: &RequestCtx, summaries: &mut [PatientSummary]) {
+    let humanizer = Humanizer::new(req_ctx.message_locale());
+    let now = Utc::now();
+    for summary in summaries {
+        summary.set_waiting_time(&humanizer, now);
+    }
+}
+
+/// Page through the patients of the caller's hospital, newest first
+async fn list_patients(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Query(query): Query<PatientListQuery>,
+) -> ApiResult<Json<PatientListResponse>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    query.page().validate()?;
+
+    let mut patients = state.patients.list(&req_ctx, &query).await?;
+    set_waiting_times(&req_ctx, &mut patients.items);
+
+    Ok(Json(PatientListResponse::from_cursor_page(
+        patients,
+        query.limit,
+    )))
+}
+
+/// Register a patient, usually from the ambulance crew's first report. The
+/// patient number is assigned by the repository.
+async fn create_patient(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<CreatePatientRequest>,
+) -> ApiResult<(StatusCode, Json<PatientResponse>)> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let patient = state
+        .patients
+        .create(&req_ctx, &payload.to_patient())
+        .await?;
+
+    info!(
+        "User {} registered patient {} ({}) at {} triage",
+        ctx.user_id(),
+        patient.id,
+        patient.patient_number,
+        patient.triage_level
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(PatientResponse::from_patient(&patient)),
+    ))
+}
+
+async fn get_patient(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+) -> ApiResult<Json<PatientResponse>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let patient = state
+        .patients
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or(PatientError::NotFound { patient_id: id })?;
+
+    info!("User {} viewed patient {}", ctx.user_id(), patient.id);
+
+    Ok(Json(PatientResponse::from_patient(&patient)))
+}
+
+/// Search the patients of the caller's hospital. Filters travel in the body
+/// so identifiers stay out of URLs and access logs.
+async fn search_patients(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<PatientSearchRequest>,
+) -> ApiResult<Json<CursorPage<PatientSummary>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let mut patients = state.patients.search(&req_ctx, &payload).await?;
+    set_waiting_times(&req_ctx, &mut patients.items);
+
+    Ok(Json(patients))
+}
+
+/// Export the patients of the caller's hospital as CSV or NDJSON, newest
+/// first. Rows are streamed a page at a time, so the export is never held
+/// in memory whole; an error part way through ends the download early.
+async fn export_patients(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Query(query): Query<PatientExportQuery>,
+) -> ApiResult<Response> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    info!(
+        "User {} exported patients as {}",
+        ctx.user_id(),
+        query.format.extension()
+    );
+
+    patient_export(&state, req_ctx, &query)
+}
+
+/// Stream the patients visible to `req_ctx` in the requested format
+pub(crate) fn patient_export(
+    state: &AppState,
+    req_ctx: RequestCtx,
+    query: &PatientExportQuery,
+) -> ApiResult<Response> {
+    let mut writer = query.format.writer(Vec::new(), query.bom);
+    writer
+        .write_header::<PatientSummary>()
+        .map_err(|_| AppError::Internal)?;
+
+    let patients = state.patients.clone();
+    let pages = stream::try_unfold(Some((writer, None)), move |next| {
+        let patients = patients.clone();
+        let req_ctx = req_ctx.clone();
+        async move {
+            let Some((mut writer, cursor)) = next else {
+                return Ok(None);
+            };
+            let query = PatientListQuery {
+                cursor,
+                limit: MAX_PAGE_SIZE,
+            };
+            let page = patients.list(&req_ctx, &query).await.inspect_err(|e| {
+                warn!("Patient export ended early: {}", e);
+            })?;
+            for patient in &page.items {
+                writer
+                    .write_record(patient)
+                    .map_err(|_| AppError::Internal)?;
+            }
+
+            let chunk = std::mem::take(writer.get_mut());
+            let next = page.next_cursor.map(|cursor| (writer, Some(cursor)));
+            Ok::<_, AppError>(Some((chunk, next)))
+        }
+    });
+
+    let disposition = format!(
+        "attachment; filename=\"patients-{}.{}\"",
+        Utc::now().format("%Y%m%d"),
+        query.format.extension()
+    );
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        Body::from_stream(pages),
+    )
+        .into_response())
+}
+
+/// Find a patient by any of their identifiers: Emirates ID, passport, GCC
+/// ID, MRN or unidentified-patient alias
+async fn lookup_patient(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<LookupPatientRequest>,
+) -> ApiResult<Json<PatientResponse>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let patient = state
+        .patients
+        .find_by_identifier(&req_ctx, &payload)
+        .await?
+        .ok_or(PatientError::IdentifierNotFound {
+            scheme: payload.scheme,
+        })?;
+
+    info!(
+        "User {} looked up patient {} by {}",
+        ctx.user_id(),
+        patient.id,
+        payload.scheme
+    );
+
+    Ok(Json(PatientResponse::from_patient(&patient)))
+}
+
+/// Edit a patient's record. Only the names of the changed fields are
+/// logged, never their values.
+async fn update_patient(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<UpdatePatientRequest>,
+) -> ApiResult<Json<PatientResponse>> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let (patient, changed) = state.patients.update(&req_ctx, id, &payload).await?;
+
+    if !changed.is_empty() {
+        info!(
+            "User {} updated patient {}: {}",
+            ctx.user_id(),
+            patient.id,
+            changed.join(", ")
+        );
+    }
+
+    Ok(Json(PatientResponse::from_patient(&patient)))
+}
+
+/// Move a patient along the workflow, with the permissions the transition
+/// needs. Without an expected status the patient's current one is used.
+async fn update_status(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<UpdatePatientStatusRequest>,
+) -> ApiResult<Json<PatientResponse>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let expected = match payload.expected_status {
+        Some(status) => status,
+        None => {
+            state
+                .patients
+                .find_by_id(&req_ctx, id)
+                .await?
+                .ok_or(PatientError::NotFound { patient_id: id })?
+                .status
+        }
+    };
+    let required = Permissions::for_patient_transition(expected, payload.status).ok_or(
+        PatientError::InvalidStatusTransition {
+            current: expected,
+            requested: payload.status,
+        },
+    )?;
+    if !ctx.has_permission(required) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let patient = state
+        .patients
+        .transition_status(&req_ctx, id, expected, payload.status)
+        .await?;
+
+    info!(
+        "User {} moved patient {} from {} to {}",
+        ctx.user_id(),
+        patient.id,
+        expected,
+        patient.status
+    );
+
+    Ok(Json(PatientResponse::from_patient(&patient)))
+}
+
+/// Re-triage a patient; the assessment is added to their triage history
+async fn retriage_patient(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<RetriageRequest>,
+) -> ApiResult<Json<PatientResponse>> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    // Triage decides who is seen first, so it needs a recent password
+    ensure_recent_auth(&ctx, state.config.load().security.step_up_max_age())?;
+    payload.validate()?;
+
+    let (patient, assessment) = state
+        .patients
+        .retriage(&req_ctx, id, &payload, ctx.user_id())
+        .await?;
+
+    info!(
+        "User {} re-triaged patient {} to {}",
+        ctx.user_id(),
+        patient.id,
+        assessment.triage_level
+    );
+
+    Ok(Json(PatientResponse::from_patient(&patient)))
+}
+
+/// Record or withdraw a do-not-resuscitate order; the caller is kept as the
+/// recording clinician
+async fn record_dnr(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<RecordDnrRequest>,
+) -> ApiResult<Json<PatientResponse>> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS | Permissions::CLINICAL_SIGN_OFF) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let (patient, changed) = state
+        .patients
+        .record_dnr(&req_ctx, id, payload.dnr, ctx.user_id())
+        .await?;
+
+    if changed {
+        info!(
+            "User {} {} a DNR order for patient {}",
+            ctx.user_id(),
+            if patient.dnr { "recorded" } else { "withdrew" },
+            patient.id
+        );
+    }
+
+    Ok(Json(PatientResponse::from_patient(&patient)))
+}
+
+/// Discharge a patient and issue their discharge summary
+async fn discharge_patient(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<DischargePatientRequest>,
+) -> ApiResult<Json<DischargeSummaryResponse>> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS | Permissions::CLINICAL_SIGN_OFF) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    // Discharge closes the episode of care, so it needs a recent password
+    ensure_recent_auth(&ctx, state.config.load().security.step_up_max_age())?;
+    payload.validate()?;
+
+    let (summary, patient) = DischargeRepository::new(state.db.clone())
+        .discharge(&req_ctx, id, &payload, ctx.user_id())
+        .await?;
+
+    info!(
+        "User {} discharged patient {} ({})",
+        ctx.user_id(),
+        patient.id,
+        summary.disposition
+    );
+
+    Ok(Json(DischargeSummaryResponse::from_summary(&summary)))
+}
+
+async fn list_discharge_summaries(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+) -> ApiResult<Json<Vec<DischargeSummaryResponse>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let summaries = DischargeRepository::new(state.db.clone())
+        .list_for_patient(&req_ctx, id)
+        .await?;
+
+    Ok(Json(
+        summaries
+            .iter()
+            .map(DischargeSummaryResponse::from_summary)
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+    use lib_auth::middleware::ResourceKind;
+    use lib_auth::rbac::{BreakGlassGrant, BreakGlassStore};
+    use lib_types::enums::{TriageLevel, UserRole};
+    use lib_types::ids::HospitalId;
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::web::test_support::TestApp;
+
+    #[tokio::test]
+    async fn test_other_hospitals_patients_are_forbidden() {
+        let app = TestApp::new();
+        let hospital_id = HospitalId::new();
+        let own = app.add_patient(hospital_id).await;
+        let other = app.add_patient(HospitalId::new()).await;
+        let (_, token) = app.login(UserRole::Nurse, hospital_id).await;
+
+        let (status, body) = app
+            .send(Method::GET, &format!("/api/patients/{}", own.id), &token)
+            .await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+
+        let (status, body) = app
+            .send(Method::GET, &format!("/api/patients/{}", other.id), &token)
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{}", body);
+        let (status, _) = app
+            .send(
+                Method::PUT,
+                &format!("/api/patients/{}/dnr", other.id),
+                &token,
+            )
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Routes without a patient id are not affected
+        let (status, body) = app.send(Method::GET, "/api/patients", &token).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+    }
+
+    #[tokio::test]
+    async fn test_break_glass_grant_allows_reading_until_it_expires() {
+        let app = TestApp::new();
+        let other = app.add_patient(HospitalId::new()).await;
+        let (user_id, token) = app.login(UserRole::Specialist, HospitalId::new()).await;
+        let uri = format!("/api/patients/{}", other.id);
+
+        let now = Utc::now();
+        let mut grant = BreakGlassGrant {
+            user_id,
+            hospital_id: other.hospital_id,
+            reason: "Patient transferred unconscious without records".to_string(),
+            granted_at: now,
+            expires_at: now + chrono::Duration::minutes(30),
+        };
+        app.break_glass.activate(&grant).await.unwrap();
+
+        let (status, body) = app.send(Method::GET, &uri, &token).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert!(body.contains(&other.id.to_string()));
+        // The grant only covers reads
+        let (status, _) = app.send(Method::PUT, &format!("{}/dnr", uri), &token).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        grant.expires_at = now - chrono::Duration::seconds(1);
+        app.break_glass.activate(&grant).await.unwrap();
+        let (status, _) = app.send(Method::GET, &uri, &token).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_retriage_extends_the_history_and_updates_the_level() {
+        let app = TestApp::new();
+        let hospital_id = HospitalId::new();
+        let (_, token) = app.login(UserRole::Nurse, hospital_id).await;
+
+        let (status, body) = app
+            .send_json(
+                Method::POST,
+                "/api/patients",
+                &token,
+                json!({
+                    "first_name": "Test",
+                    "last_name": "Patient",
+                    "age": 40,
+                    "gender": "female",
+                    "national_id": null,
+                    "chief_complaint": "Chest pain",
+                    "triage_level": "medium",
+                    "hospital_id": hospital_id,
+                    "incident_location": null,
+                    "incident_time": null,
+                    "allergies": null,
+                    "medical_history": null,
+                    "insurance_info": null,
+                    "blood_type": null,
+                }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{}", body);
+        let created: Value = serde_json::from_str(&body).unwrap();
+        let id: PatientId = serde_json::from_value(created["id"].clone()).unwrap();
+        app.owners
+            .insert(ResourceKind::Patient, id.into(), hospital_id);
+        let uri = format!("/api/patients/{}/triage", id);
+
+        for (level, rationale) in [("high", "SpO2 dropping"), ("critical", "Unresponsive")] {
+            let (status, body) = app
+                .send_json(
+                    Method::POST,
+                    &uri,
+                    &token,
+                    json!({ "triage_level": level, "rationale": rationale }),
+                )
+                .await;
+            assert_eq!(status, StatusCode::OK, "{}", body);
+            let patient: Value = serde_json::from_str(&body).unwrap();
+            assert_eq!(patient["triage_level"], level);
+        }
+
+        let history = app
+            .state
+            .patients
+            .triage_history(&RequestCtx::system(), id)
+            .await
+            .unwrap();
+        let levels: Vec<_> = history.iter().map(|a| a.triage_level).collect();
+        assert_eq!(
+            levels,
+            [
+                TriageLevel::Medium,
+                TriageLevel::High,
+                TriageLevel::Critical
+            ]
+        );
+        assert_eq!(history[2].previous_level, Some(TriageLevel::High));
+
+        // A rationale is required
+        let (status, _) = app
+            .send_json(
+                Method::POST,
+                &uri,
+                &token,
+                json!({ "triage_level": "low", "rationale": " " }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_retriage_requires_a_recent_password() {
+        let app = TestApp::new();
+        let hospital_id = HospitalId::new();
+        let patient = app.add_patient(hospital_id).await;
+        let (_, token) = app
+            .login_at(
+                UserRole::Nurse,
+                hospital_id,
+                Utc::now() - chrono::Duration::hours(1),
+            )
+            .await;
+
+        let (status, body) = app
+            .send_json(
+                Method::POST,
+                &format!("/api/patients/{}/triage", patient.id),
+                &token,
+                json!({ "triage_level": "critical", "rationale": "Unresponsive" }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::PRECONDITION_REQUIRED, "{}", body);
+        let unchanged = app
+            .state
+            .patients
+            .find_by_id(&RequestCtx::system(), patient.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unchanged.triage_level, TriageLevel::High);
+    }
+
+    #[tokio::test]
+    async fn test_discharge_requires_a_recent_password() {
+        let app = TestApp::new();
+        let hospital_id = HospitalId::new();
+        let patient = app.add_patient(hospital_id).await;
+        let (_, token) = app
+            .login_at(
+                UserRole::Specialist,
+                hospital_id,
+                Utc::now() - chrono::Duration::hours(1),
+            )
+            .await;
+
+        let (status, body) = app
+            .send_json(
+                Method::POST,
+                &format!("/api/patients/{}/discharge", patient.id),
+                &token,
+                json!({
+                    "diagnosis": "Musculoskeletal chest pain",
+                    "disposition": "home",
+                    "instructions": "Rest and return if the pain comes back",
+                    "follow_up": null,
+                }),
+            )
+            .await;
+        assert_eq!(status, StatusCode::PRECONDITION_REQUIRED, "{}", body);
+    }
+}
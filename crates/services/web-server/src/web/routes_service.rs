@@ -0,0 +1,122 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::Response;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tracing::info;
+
+use lib_auth::ctx::{RequestCtx, ServiceCtx};
+use lib_core::model::dispatch_ambulance;
+use lib_types::dtos::{
+    AmbulanceResponse, CursorPage, DispatchAmbulanceRequest, HospitalDistanceQuery,
+    HospitalFilter, HospitalSortField, HospitalSummary, PageRequest, PatientExportQuery,
+};
+use lib_types::enums::ServiceScope;
+use lib_types::errors::Validate;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+use super::{routes_hospitals, routes_patients};
+
+/// Routes for service accounts (dispatch optimizer, reporting jobs), each
+/// limited to the scope its token must carry
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/service/hospitals", get(list_hospitals))
+        .route("/api/service/ambulances/dispatch", post(dispatch))
+        .route("/api/service/patients/export", get(export_patients))
+}
+
+/// Bed capacity of the hospitals the account may see
+async fn list_hospitals(
+    state: State<AppState>,
+    service: ServiceCtx,
+    req_ctx: RequestCtx,
+    page: Query<PageRequest<HospitalSortField>>,
+    filter: Query<HospitalFilter>,
+    distance: Query<HospitalDistanceQuery>,
+) -> ApiResult<Json<CursorPage<HospitalSummary>>> {
+    service.require_scope(ServiceScope::BedsRead)?;
+
+    routes_hospitals::list_hospitals(state, req_ctx, page, filter, distance).await
+}
+
+/// Send an ambulance to an incident
+async fn dispatch(
+    State(state): State<AppState>,
+    service: ServiceCtx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<DispatchAmbulanceRequest>,
+) -> ApiResult<(StatusCode, Json<AmbulanceResponse>)> {
+    service.require_scope(ServiceScope::DispatchWrite)?;
+
+    payload.validate()?;
+
+    let (ambulance, _) = dispatch_ambulance(&state.db, &req_ctx, &payload).await?;
+
+    info!(
+        "Service {} dispatched ambulance {} to {}",
+        service.client_id(),
+        ambulance.call_sign,
+        payload.incident_location.trim()
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AmbulanceResponse::from_ambulance(&ambulance)),
+    ))
+}
+
+/// Export the patients the account may see, for reporting
+async fn export_patients(
+    State(state): State<AppState>,
+    service: ServiceCtx,
+    req_ctx: RequestCtx,
+    Query(query): Query<PatientExportQuery>,
+) -> ApiResult<Response> {
+    service.require_scope(ServiceScope::ReportsRead)?;
+
+    info!(
+        "Service {} exported patients as {}",
+        service.client_id(),
+        query.format.extension()
+    );
+
+    routes_patients::patient_export(&state, req_ctx, &query)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+    use lib_types::enums::UserRole;
+    use lib_types::ids::HospitalId;
+
+    use super::*;
+    use crate::web::test_support::TestApp;
+
+    #[tokio::test]
+    async fn test_report_export_requires_reports_scope() {
+        let app = TestApp::new();
+        let hospital_id = HospitalId::new();
+        let own = app.add_patient(hospital_id).await;
+        let other = app.add_patient(HospitalId::new()).await;
+        let uri = "/api/service/patients/export";
+
+        let token = app.service_token(Some(hospital_id), vec![ServiceScope::ReportsRead]);
+        let (status, body) = app.send(Method::GET, uri, &token).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert!(body.contains(&own.id.to_string()));
+        // Accounts limited to a hospital only see its patients
+        assert!(!body.contains(&other.id.to_string()));
+
+        let token = app.service_token(Some(hospital_id), vec![ServiceScope::BedsRead]);
+        let (status, _) = app.send(Method::GET, uri, &token).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Staff tokens are not service tokens
+        let (_, token) = app.login(UserRole::Admin, hospital_id).await;
+        let (status, _) = app.send(Method::GET, uri, &token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,171 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{middleware, Json, Router};
+use chrono::Utc;
+use tower_http::services::ServeDir;
+use tracing::warn;
+
+use lib_auth::ctx::Ctx;
+use lib_auth::middleware::{ensure_hospital_access, ResourceKind};
+use lib_auth::rbac::Permissions;
+use lib_types::dtos::{CreateSharedLinkRequest, SharedLinkResponse};
+use lib_types::errors::{AppError, AuthError, PatientError};
+use lib_types::ids::PatientId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+use super::mw_signed_url::mw_require_signed_url;
+
+/// Documents under this prefix are served to holders of a signed link,
+/// filed by patient as `<prefix>/<patient id>/<file>`
+const SHARED_DOCUMENTS_PREFIX: &str = "/api/shared/documents";
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/api/shared-links", post(create_shared_link))
+}
+
+/// Serve the shared documents directory (discharge summaries, patient
+/// photos, one subdirectory per patient) to signed links only; nothing is
+/// mounted without a directory
+pub fn signed_routes(state: &AppState) -> Router<AppState> {
+    let config = state.config.load();
+    match config.healthcare.shared_documents_dir.as_deref() {
+        Some(dir) => Router::new()
+            .nest_service(SHARED_DOCUMENTS_PREFIX, ServeDir::new(dir))
+            .layer(middleware::from_fn_with_state(
+                state.clone(),
+                mw_require_signed_url,
+            )),
+        None => Router::new(),
+    }
+}
+
+/// Create a short-lived link to a shared document that opens without login,
+/// e.g. for the ambulance crew taking over a patient. Only documents of
+/// patients the caller's hospital treats can be shared.
+async fn create_shared_link(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    Json(payload): Json<CreateSharedLinkRequest>,
+) -> ApiResult<(StatusCode, Json<SharedLinkResponse>)> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let signer = state
+        .url_signer
+        .as_ref()
+        .ok_or_else(|| AppError::NotImplemented {
+            feature: "Signed document links".to_string(),
+        })?;
+    let Some(patient_id) = shared_document_patient(&payload.path) else {
+        return Err(AppError::validation_error(
+            "path",
+            format!("Only documents under {SHARED_DOCUMENTS_PREFIX}/<patient id>/ can be shared"),
+        )
+        .into());
+    };
+
+    let hospital_id = state
+        .hospital_resolver
+        .resolve_hospital(ResourceKind::Patient, patient_id.into())
+        .await?
+        .ok_or(PatientError::NotFound { patient_id })?;
+    ensure_hospital_access(&ctx, ResourceKind::Patient, hospital_id)?;
+
+    let link = signer.sign(&payload.path, Utc::now());
+    warn!(
+        target: "audit",
+        event = "signed_link_created",
+        user_id = %ctx.user_id(),
+        path = %payload.path,
+        expires_at = %link.expires_at,
+        "Signed document link created"
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SharedLinkResponse {
+            url: link.url,
+            expires_at: link.expires_at,
+        }),
+    ))
+}
+
+/// Get the patient a shared document is filed under, if the path points to a
+/// document inside the shared documents without traversal or a query
+fn shared_document_patient(path: &str) -> Option<PatientId> {
+    let rest = path
+        .strip_prefix(SHARED_DOCUMENTS_PREFIX)?
+        .strip_prefix('/')
+        .filter(|rest| !rest.contains(['?', '#', '\\', '%']))?;
+
+    let segments: Vec<&str> = rest.split('/').collect();
+    if segments.len() < 2
+        || segments
+            .iter()
+            .any(|segment| matches!(*segment, "" | "." | ".."))
+    {
+        return None;
+    }
+    segments[0].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+    use serde_json::json;
+
+    use lib_types::enums::UserRole;
+    use lib_types::ids::HospitalId;
+
+    use super::*;
+    use crate::web::test_support::TestApp;
+
+    #[test]
+    fn test_shared_document_patient() {
+        let patient_id = PatientId::new();
+        let path = format!("{SHARED_DOCUMENTS_PREFIX}/{patient_id}/discharge-summary.pdf");
+        assert_eq!(shared_document_patient(&path), Some(patient_id));
+
+        for path in [
+            format!("{SHARED_DOCUMENTS_PREFIX}/discharge-summary.pdf"),
+            format!("{SHARED_DOCUMENTS_PREFIX}/{patient_id}/"),
+            format!("{SHARED_DOCUMENTS_PREFIX}/{patient_id}/../other.pdf"),
+            format!("{SHARED_DOCUMENTS_PREFIX}/{patient_id}/photo.jpg?x=1"),
+            format!("/api/patients/{patient_id}/photo.jpg"),
+        ] {
+            assert_eq!(shared_document_patient(&path), None, "{}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_own_hospitals_documents_can_be_shared() {
+        let app = TestApp::new();
+        let hospital_id = HospitalId::new();
+        let own = app.add_patient(hospital_id).await;
+        let other = app.add_patient(HospitalId::new()).await;
+        let (_, token) = app.login(UserRole::Nurse, hospital_id).await;
+
+        let link_to = |patient_id: PatientId| {
+            json!({ "path": format!("{SHARED_DOCUMENTS_PREFIX}/{patient_id}/discharge-summary.pdf") })
+        };
+
+        let (status, body) = app
+            .send_json(Method::POST, "/api/shared-links", &token, link_to(own.id))
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{}", body);
+
+        let (status, _) = app
+            .send_json(Method::POST, "/api/shared-links", &token, link_to(other.id))
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, _) = app
+            .send_json(Method::POST, "/api/shared-links", &token, link_to(PatientId::new()))
+            .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
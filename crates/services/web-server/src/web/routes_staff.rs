@@ -0,0 +1,197 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get, post, put};
+use axum::{Json, Router};
+use tracing::info;
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::{ensure_hospital_access, ResourceKind};
+use lib_auth::rbac::Permissions;
+use lib_core::store::StaffRepository;
+use lib_types::dtos::{
+    CreateMedicalStaffRequest, CursorPage, PageRequest, StaffRosterEntry, StaffRosterQuery,
+    StaffRosterResponse, StaffSortField, UpdateAvailabilityRequest,
+};
+use lib_types::errors::{AppError, AuthError, Validate};
+use lib_types::ids::HospitalId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+/// Routes addressing a staff member by `:id`
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/staff", post(create_staff))
+        .route("/api/staff/:id", delete(delete_staff))
+        .route("/api/staff/:id/restore", post(restore_staff))
+        .route("/api/staff/:id/availability", put(update_availability))
+}
+
+/// Routes addressing the hospital the staff work at by `:id`
+pub fn hospital_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/hospitals/:id/roster", get(get_roster))
+        .route("/api/hospitals/:id/staff", get(list_staff))
+}
+
+/// Onboard a user account as clinical staff
+async fn create_staff(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Json(payload): Json<CreateMedicalStaffRequest>,
+) -> ApiResult<(StatusCode, Json<StaffRosterEntry>)> {
+    if !ctx.has_permission(Permissions::MANAGE_STAFF) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    ensure_hospital_access(&ctx, ResourceKind::Staff, payload.hospital_id)?;
+
+    payload.validate()?;
+
+    let staff = payload.to_medical_staff();
+    StaffRepository::new(state.db.clone())
+        .create(&req_ctx, &staff)
+        .await?;
+
+    info!(
+        "User {} onboarded staff {} ({}) at hospital {}",
+        ctx.user_id(),
+        staff.id,
+        staff.staff_id,
+        staff.hospital_id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(StaffRosterEntry::from_staff(&staff)),
+    ))
+}
+
+/// Change a staff member's availability; staff may always update their own
+async fn update_availability(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateAvailabilityRequest>,
+) -> ApiResult<Json<StaffRosterEntry>> {
+    let repository = StaffRepository::new(state.db.clone());
+    let staff = repository
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Staff member"))?;
+
+    if staff.user_id != ctx.user_id() && !ctx.has_permission(Permissions::MANAGE_STAFF) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let staff = repository
+        .update_availability(&req_ctx, id, payload.availability_status)
+        .await?;
+
+    info!(
+        "User {} set staff {} to {}",
+        ctx.user_id(),
+        staff.id,
+        staff.availability_status
+    );
+
+    Ok(Json(StaffRosterEntry::from_staff(&staff)))
+}
+
+/// Remove a staff member from rosters; the record is kept for restoring
+async fn delete_staff(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    if !ctx.has_permission(Permissions::MANAGE_STAFF) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let staff = StaffRepository::new(state.db.clone())
+        .soft_delete(&req_ctx, id)
+        .await?;
+
+    info!(
+        "User {} deleted staff {} ({})",
+        ctx.user_id(),
+        staff.id,
+        staff.staff_id
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Bring back a staff member deleted by mistake
+async fn restore_staff(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<StaffRosterEntry>> {
+    if !ctx.has_permission(Permissions::MANAGE_STAFF) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let staff = StaffRepository::new(state.db.clone())
+        .restore(&req_ctx, id)
+        .await?;
+
+    info!(
+        "User {} restored staff {} ({})",
+        ctx.user_id(),
+        staff.id,
+        staff.staff_id
+    );
+
+    Ok(Json(StaffRosterEntry::from_staff(&staff)))
+}
+
+/// List a hospital's staff, best candidates for an assignment first
+async fn get_roster(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(hospital_id): Path<HospitalId>,
+    Query(query): Query<StaffRosterQuery>,
+) -> ApiResult<Json<StaffRosterResponse>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS)
+        && !ctx.has_permission(Permissions::MANAGE_STAFF)
+    {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    ensure_hospital_access(&ctx, ResourceKind::Staff, hospital_id)?;
+
+    let staff = StaffRepository::new(state.db.clone())
+        .roster(&req_ctx, hospital_id, &query)
+        .await?;
+
+    Ok(Json(StaffRosterResponse::from_staff(hospital_id, &staff)))
+}
+
+/// Page through a hospital's staff in a stable order
+async fn list_staff(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(hospital_id): Path<HospitalId>,
+    Query(page): Query<PageRequest<StaffSortField>>,
+) -> ApiResult<Json<CursorPage<StaffRosterEntry>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS)
+        && !ctx.has_permission(Permissions::MANAGE_STAFF)
+    {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    ensure_hospital_access(&ctx, ResourceKind::Staff, hospital_id)?;
+
+    page.validate()?;
+
+    let staff = StaffRepository::new(state.db.clone())
+        .list(&req_ctx, hospital_id, &page)
+        .await?;
+
+    Ok(Json(staff))
+}
@@ -0,0 +1,222 @@
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::get;
+use axum::{Json, Router};
+use futures::stream::{self, Stream};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{Interval, MissedTickBehavior};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::rbac::Permissions;
+use lib_core::store::{
+    AttachmentRepository, DischargeRepository, HandoverRepository, LabRepository,
+    MedicationRepository, PatientEventRepository,
+};
+use lib_types::dtos::{build_timeline, LiveEvent, TimelineEntry};
+use lib_types::entities::PatientEvent;
+use lib_types::errors::{AppError, AuthError, PatientError};
+use lib_types::ids::PatientId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/patients/:id/timeline", get(patient_timeline))
+        .route("/api/patients/:id/events", get(patient_events))
+}
+
+/// How often a patient's event stream checks the log when no live event
+/// has woken it; assignments are only logged, not announced
+const EVENT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Events read from the log at a time
+const EVENT_BATCH: i64 = 100;
+
+/// Ambulance handover, triage, status and assignment changes, medications,
+/// labs, attachments and discharge for a patient in the order they happened
+async fn patient_timeline(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+) -> ApiResult<Json<Vec<TimelineEntry>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let handovers = HandoverRepository::new(state.db.clone());
+    let medications = MedicationRepository::new(state.db.clone());
+    let labs = LabRepository::new(state.db.clone());
+    let attachments = AttachmentRepository::new(state.db.clone());
+    let discharges = DischargeRepository::new(state.db.clone());
+    let events = PatientEventRepository::new(state.db.clone());
+    let (handovers, triage, medications, orders, results, attachments, discharges, events) = tokio::try_join!(
+        handovers.list_for_patient(&req_ctx, id),
+        state.patients.triage_history(&req_ctx, id),
+        medications.list_for_patient(&req_ctx, id),
+        labs.list_orders(&req_ctx, id),
+        labs.list_results(&req_ctx, id),
+        attachments.list_for_patient(&req_ctx, id),
+        discharges.list_for_patient(&req_ctx, id),
+        events.list_for_patient(&req_ctx, id),
+    )?;
+
+    let timeline = build_timeline(
+        handovers
+            .iter()
+            .map(TimelineEntry::from_handover)
+            .chain(triage.iter().map(TimelineEntry::from_triage))
+            .chain(medications.iter().map(TimelineEntry::from_medication))
+            .chain(orders.iter().map(TimelineEntry::from_lab_order))
+            .chain(results.iter().map(TimelineEntry::from_lab_result))
+            .chain(attachments.iter().map(TimelineEntry::from_attachment))
+            .chain(discharges.iter().map(TimelineEntry::from_discharge))
+            .chain(events.iter().filter_map(TimelineEntry::from_patient_event)),
+    );
+
+    Ok(Json(timeline))
+}
+
+/// Stream a patient's status changes, new vital signs and assignments as
+/// server-sent events, for clients behind proxies that block WebSockets.
+/// Each event's id is its id in the patient event log: a client
+/// reconnecting with `Last-Event-ID` first receives what it missed, while a
+/// new client only receives events from now on.
+async fn patient_events(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    headers: HeaderMap,
+) -> ApiResult<Sse<impl Stream<Item = Result<Event, Infallible>>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+
+    let last_event_id = match headers.get("last-event-id") {
+        Some(value) => Some(
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| Uuid::parse_str(value.trim()).ok())
+                .ok_or_else(|| {
+                    AppError::validation_error("Last-Event-ID", "Must be an event id")
+                })?,
+        ),
+        None => None,
+    };
+
+    state
+        .patients
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or(PatientError::NotFound { patient_id: id })?;
+
+    let events = PatientEventRepository::new(state.db.clone());
+    let cursor = match last_event_id {
+        Some(last_event_id) => Some(last_event_id),
+        None => events.latest_id(&req_ctx, id).await?,
+    };
+
+    info!(
+        "User {} is following events of patient {}",
+        ctx.user_id(),
+        id
+    );
+
+    let mut poll = tokio::time::interval(EVENT_POLL_INTERVAL);
+    poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let stream = PatientEventStream {
+        events,
+        req_ctx,
+        patient_id: id,
+        cursor,
+        pending: VecDeque::new(),
+        live: state.events.subscribe(),
+        poll,
+    };
+
+    Ok(Sse::new(stream::unfold(stream, PatientEventStream::next)).keep_alive(KeepAlive::default()))
+}
+
+/// Reads a patient's event log past `cursor`, checking again whenever a
+/// live event concerns the patient or the poll interval passes
+struct PatientEventStream {
+    events: PatientEventRepository,
+    req_ctx: RequestCtx,
+    patient_id: PatientId,
+    cursor: Option<Uuid>,
+    pending: VecDeque<PatientEvent>,
+    live: broadcast::Receiver<LiveEvent>,
+    poll: Interval,
+}
+
+impl PatientEventStream {
+    async fn next(mut self) -> Option<(Result<Event, Infallible>, Self)> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                self.cursor = Some(event.id);
+                let data = serde_json::to_string(&event).unwrap_or_default();
+                let event = Event::default()
+                    .id(event.id.to_string())
+                    .event(event.kind.as_str())
+                    .data(data);
+                return Some((Ok(event), self));
+            }
+
+            // The first tick is immediate, so missed events are sent at once
+            tokio::select! {
+                _ = self.poll.tick() => {}
+                event = self.live.recv() => match event {
+                    Ok(event) if event.patient_id() == Some(self.patient_id) => {}
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return None,
+                },
+            }
+
+            // The client reconnects with the last event it received
+            match self
+                .events
+                .list_after(&self.req_ctx, self.patient_id, self.cursor, EVENT_BATCH)
+                .await
+            {
+                Ok(events) => self.pending.extend(events),
+                Err(e) => {
+                    warn!("Event stream of patient {} ended: {}", self.patient_id, e);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{Method, StatusCode};
+
+    use lib_types::enums::UserRole;
+    use lib_types::ids::HospitalId;
+
+    use crate::web::test_support::TestApp;
+
+    #[tokio::test]
+    async fn test_other_hospitals_timelines_and_events_are_forbidden() {
+        let app = TestApp::new();
+        let other = app.add_patient(HospitalId::new()).await;
+        let (_, token) = app.login(UserRole::Nurse, HospitalId::new()).await;
+
+        for path in ["timeline", "events"] {
+            let uri = format!("/api/patients/{}/{}", other.id, path);
+            let (status, body) = app.send(Method::GET, &uri, &token).await;
+            assert_eq!(status, StatusCode::FORBIDDEN, "{}: {}", uri, body);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use tracing::info;
+use uuid::Uuid;
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::middleware::{ensure_hospital_access, ResourceKind};
+use lib_auth::rbac::Permissions;
+use lib_core::store::TransferRepository;
+use lib_types::dtos::{
+    AcceptTransferRequest, RejectTransferRequest, RequestTransferRequest, TransferListResponse,
+    TransferResponse,
+};
+use lib_types::entities::PatientTransfer;
+use lib_types::errors::{AppError, AuthError, Validate};
+use lib_types::ids::PatientId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/patients/:id/transfers",
+            get(list_transfers).post(request_transfer),
+        )
+        .route("/api/transfers/:id", get(get_transfer))
+        .route("/api/transfers/:id/accept", post(accept_transfer))
+        .route("/api/transfers/:id/reject", post(reject_transfer))
+        .route("/api/transfers/:id/complete", post(complete_transfer))
+}
+
+fn require_permission(ctx: &Ctx, permission: Permissions) -> Result<(), AuthError> {
+    if ctx.has_permission(permission) {
+        Ok(())
+    } else {
+        Err(AuthError::InsufficientPermissions)
+    }
+}
+
+/// Load a transfer and check the caller works at its destination, the only
+/// side that may answer or complete it
+async fn load_for_destination(
+    state: &AppState,
+    ctx: &Ctx,
+    req_ctx: &RequestCtx,
+    id: Uuid,
+) -> ApiResult<PatientTransfer> {
+    let transfer = TransferRepository::new(state.db.clone())
+        .find_by_id(req_ctx, id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Transfer"))?;
+    ensure_hospital_access(ctx, ResourceKind::Patient, transfer.destination_hospital_id)?;
+    Ok(transfer)
+}
+
+/// Ask another hospital to take over one of the caller's patients
+async fn request_transfer(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(patient_id): Path<PatientId>,
+    Json(payload): Json<RequestTransferRequest>,
+) -> ApiResult<(StatusCode, Json<TransferResponse>)> {
+    require_permission(&ctx, Permissions::CLINICAL_SIGN_OFF)?;
+    payload.validate()?;
+
+    let transfer = TransferRepository::new(state.db.clone())
+        .create(
+            &req_ctx,
+            patient_id,
+            payload.destination_hospital_id,
+            payload.reason.trim().to_string(),
+            ctx.user_id(),
+        )
+        .await?;
+
+    info!(
+        "User {} requested transfer {} of patient {} to hospital {}",
+        ctx.user_id(),
+        transfer.id,
+        patient_id,
+        transfer.destination_hospital_id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(TransferResponse::from_transfer(&transfer)),
+    ))
+}
+
+async fn list_transfers(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(patient_id): Path<PatientId>,
+) -> ApiResult<Json<TransferListResponse>> {
+    require_permission(&ctx, Permissions::VIEW_PATIENTS)?;
+
+    let transfers = TransferRepository::new(state.db.clone())
+        .list_for_patient(&req_ctx, patient_id)
+        .await?;
+
+    Ok(Json(TransferListResponse::from_transfers(&transfers)))
+}
+
+async fn get_transfer(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<TransferResponse>> {
+    require_permission(&ctx, Permissions::VIEW_PATIENTS)?;
+
+    let transfer = TransferRepository::new(state.db.clone())
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or_else(|| AppError::not_found("Transfer"))?;
+
+    Ok(Json(TransferResponse::from_transfer(&transfer)))
+}
+
+async fn accept_transfer(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AcceptTransferRequest>,
+) -> ApiResult<Json<TransferResponse>> {
+    require_permission(&ctx, Permissions::CLINICAL_SIGN_OFF)?;
+    load_for_destination(&state, &ctx, &req_ctx, id).await?;
+
+    let transfer = TransferRepository::new(state.db.clone())
+        .accept(&req_ctx, id, ctx.user_id(), payload.ambulance_id)
+        .await?;
+
+    info!("User {} accepted transfer {}", ctx.user_id(), transfer.id);
+
+    Ok(Json(TransferResponse::from_transfer(&transfer)))
+}
+
+async fn reject_transfer(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<RejectTransferRequest>,
+) -> ApiResult<Json<TransferResponse>> {
+    require_permission(&ctx, Permissions::CLINICAL_SIGN_OFF)?;
+    payload.validate()?;
+    load_for_destination(&state, &ctx, &req_ctx, id).await?;
+
+    let transfer = TransferRepository::new(state.db.clone())
+        .reject(
+            &req_ctx,
+            id,
+            ctx.user_id(),
+            payload.reason.trim().to_string(),
+        )
+        .await?;
+
+    info!("User {} rejected transfer {}", ctx.user_id(), transfer.id);
+
+    Ok(Json(TransferResponse::from_transfer(&transfer)))
+}
+
+/// Record the patient's arrival at the destination
+async fn complete_transfer(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<TransferResponse>> {
+    require_permission(&ctx, Permissions::EDIT_PATIENTS)?;
+    load_for_destination(&state, &ctx, &req_ctx, id).await?;
+
+    let (transfer, patient) = TransferRepository::new(state.db.clone())
+        .complete(&req_ctx, id)
+        .await?;
+
+    info!(
+        "User {} completed transfer {}: patient {} arrived at hospital {}",
+        ctx.user_id(),
+        transfer.id,
+        patient.id,
+        patient.hospital_id
+    );
+
+    Ok(Json(TransferResponse::from_transfer(&transfer)))
+}
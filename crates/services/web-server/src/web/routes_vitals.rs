@@ -0,0 +1,132 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use tracing::{info, warn};
+
+use lib_auth::ctx::{Ctx, RequestCtx};
+use lib_auth::rbac::Permissions;
+use lib_core::store::VitalsRepository;
+use lib_types::dtos::{
+    CursorPage, RecordVitalsRequest, RecordVitalsResponse, VitalsDto, VitalsHistoryQuery,
+};
+use lib_types::errors::{AuthError, PatientError, Validate};
+use lib_types::ids::PatientId;
+
+use crate::responses::ApiResult;
+use crate::server::AppState;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route(
+        "/api/patients/:id/vitals",
+        get(list_vitals).post(record_vitals),
+    )
+}
+
+/// Record a set of vital signs, checked against the reference ranges for
+/// the patient's age and sex. Signs of deterioration raise an alert to live
+/// dashboards and are returned with the reading.
+async fn record_vitals(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Json(payload): Json<RecordVitalsRequest>,
+) -> ApiResult<(StatusCode, Json<RecordVitalsResponse>)> {
+    if !ctx.has_permission(Permissions::EDIT_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    payload.validate()?;
+
+    let patient = state
+        .patients
+        .find_by_id(&req_ctx, id)
+        .await?
+        .ok_or(PatientError::NotFound { patient_id: id })?;
+    if patient.status.is_terminal() {
+        return Err(PatientError::InvalidData {
+            field: "status".to_string(),
+            reason: format!("patient is already {}", patient.status),
+        }
+        .into());
+    }
+
+    let vitals = payload.to_vitals(id, ctx.user_id());
+    let findings = vitals.deterioration(&patient.reference_ranges(&state.reference_ranges));
+    let vitals = VitalsRepository::new(state.db.clone())
+        .record(&req_ctx, &patient, &vitals, &findings)
+        .await?;
+
+    if !findings.is_empty() {
+        let summary: Vec<String> = findings.iter().map(ToString::to_string).collect();
+        warn!(
+            target: "clinical_alert",
+            correlation_id = req_ctx.correlation_id(),
+            "Patient {} deteriorating: {} (vitals {})",
+            patient.id,
+            summary.join(", "),
+            vitals.id
+        );
+    }
+    info!(
+        "User {} recorded vitals {} for patient {}",
+        ctx.user_id(),
+        vitals.id,
+        patient.id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(RecordVitalsResponse {
+            vitals: VitalsDto::from_vitals(&vitals),
+            deterioration: findings,
+        }),
+    ))
+}
+
+/// Page through a patient's vital signs, newest first
+async fn list_vitals(
+    State(state): State<AppState>,
+    ctx: Ctx,
+    req_ctx: RequestCtx,
+    Path(id): Path<PatientId>,
+    Query(query): Query<VitalsHistoryQuery>,
+) -> ApiResult<Json<CursorPage<VitalsDto>>> {
+    if !ctx.has_permission(Permissions::VIEW_PATIENTS) {
+        return Err(AuthError::InsufficientPermissions.into());
+    }
+    query.validate()?;
+
+    let vitals = VitalsRepository::new(state.db.clone())
+        .list_for_patient(&req_ctx, id, &query)
+        .await?;
+
+    Ok(Json(vitals))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+    use serde_json::json;
+
+    use lib_types::enums::UserRole;
+    use lib_types::ids::HospitalId;
+
+    use super::*;
+    use crate::web::test_support::TestApp;
+
+    #[tokio::test]
+    async fn test_other_hospitals_vitals_are_forbidden() {
+        let app = TestApp::new();
+        let other = app.add_patient(HospitalId::new()).await;
+        let (_, token) = app.login(UserRole::Nurse, HospitalId::new()).await;
+        let uri = format!("/api/patients/{}/vitals", other.id);
+
+        let (status, body) = app.send(Method::GET, &uri, &token).await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{}", body);
+        let (status, body) = app
+            .send_json(Method::POST, &uri, &token, json!({ "heart_rate": 120 }))
+            .await;
+        assert_eq!(status, StatusCode::FORBIDDEN, "{}", body);
+    }
+}
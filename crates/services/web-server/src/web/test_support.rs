@@ -0,0 +1,338 @@
+//! Application state for handler tests: patients in memory, Redis-backed
+//! stores replaced by maps, and a database that is never reached
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use axum::body::{to_bytes, Body};
+use axum::http::header::{AUTHORIZATION, CONTENT_TYPE};
+use axum::http::{Method, Request, StatusCode};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPoolOptions;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+use lib_auth::ctx::RequestCtx;
+use lib_auth::jwt::{HealthcareClaims, JwtKeyRing, JwtService};
+use lib_auth::lockout::{LockoutPolicy, LoginAttemptStore, LoginFailure};
+use lib_auth::middleware::{HospitalResolver, IpAllowlists, ResourceKind};
+use lib_auth::password::{Argon2Params, PasswordHasher, PasswordPolicy};
+use lib_auth::rbac::{BreakGlassGrant, BreakGlassStore, Delegation, DelegationStore};
+use lib_auth::session::{Session, SessionStore};
+use lib_auth::signed_url::UrlSigner;
+use lib_core::config::AppConfig;
+use lib_core::store::{
+    CircuitBreaker, Db, DbPolicy, EventBus, HospitalCache, IdempotencyStore,
+    MemoryPatientRepository, RetryPolicy,
+};
+use lib_types::entities::Patient;
+use lib_types::enums::{Gender, ServiceScope, TriageLevel, UserRole};
+use lib_types::errors::{AppError, AuthError};
+use lib_types::ids::{HospitalId, UserId};
+use lib_utils::rate_limit::MemoryTokenBuckets;
+use lib_utils::reference_ranges::ReferenceRanges;
+
+use crate::server::AppState;
+
+/// State and router for one handler test
+pub(crate) struct TestApp {
+    pub state: AppState,
+    pub owners: Arc<Owners>,
+    pub sessions: Arc<MemorySessions>,
+    pub break_glass: Arc<MemoryBreakGlass>,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        // Nothing listens here; audit writes fail at once and are only logged
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(100))
+            .connect_lazy("postgres://handler-tests@127.0.0.1:9/none")
+            .expect("lazy pool");
+        let db = Db::new(pool).with_policy(DbPolicy::new(
+            RetryPolicy {
+                max_retries: 0,
+                ..Default::default()
+            },
+            CircuitBreaker::new(0, Duration::ZERO),
+        ));
+
+        let config = AppConfig::default();
+        let jwt = JwtService::new(
+            JwtKeyRing::hmac(b"handler-test-secret-of-at-least-32-bytes"),
+            config.jwt.issuer.clone(),
+            config.jwt.audience.clone(),
+            config.jwt.expiration_seconds,
+        );
+        let owners = Arc::new(Owners::default());
+        let sessions = Arc::new(MemorySessions::default());
+        let break_glass = Arc::new(MemoryBreakGlass::default());
+
+        let state = AppState {
+            config: Arc::new(ArcSwap::from_pointee(config)),
+            db,
+            patients: Arc::new(MemoryPatientRepository::new()),
+            hospital_resolver: owners.clone(),
+            jwt: Arc::new(jwt),
+            sessions: sessions.clone(),
+            login_attempts: Arc::new(NoLockout),
+            password_policy: Arc::new(PasswordPolicy::default()),
+            password_hasher: Arc::new(
+                PasswordHasher::new(Argon2Params::default()).expect("argon2 params"),
+            ),
+            break_glass: break_glass.clone(),
+            delegations: Arc::new(NoDelegations),
+            ip_allowlists: Arc::new(IpAllowlists::default()),
+            url_signer: Some(Arc::new(UrlSigner::new(
+                b"handler-test-link-key",
+                Duration::from_secs(15 * 60),
+            ))),
+            attachments: None,
+            hospital_cache: HospitalCache::disabled(),
+            events: EventBus::new(),
+            catchment_zones: None,
+            reference_ranges: Arc::new(ReferenceRanges::builtin()),
+            rate_limits: Arc::new(MemoryTokenBuckets::new()),
+            idempotency: IdempotencyStore::disabled(),
+        };
+
+        Self {
+            state,
+            owners,
+            sessions,
+            break_glass,
+        }
+    }
+
+    /// Start a session for a new user and return their id and access token
+    pub async fn login(&self, role: UserRole, hospital_id: HospitalId) -> (UserId, String) {
+        self.login_at(role, hospital_id, Utc::now()).await
+    }
+
+    /// As `login`, for a user who last entered their password at `auth_time`
+    pub async fn login_at(
+        &self,
+        role: UserRole,
+        hospital_id: HospitalId,
+        auth_time: DateTime<Utc>,
+    ) -> (UserId, String) {
+        let user_id = UserId::new();
+        let session = Session::new(user_id, role, hospital_id, self.sessions.timeout());
+        self.sessions.save(&session).await.unwrap();
+
+        let claims =
+            HealthcareClaims::builder(user_id, "handler.test", role, hospital_id, session.id)
+                .auth_time(auth_time.timestamp());
+        (user_id, self.state.jwt.issue_token(claims).unwrap())
+    }
+
+    /// Issue a service account token with the given scopes
+    pub fn service_token(
+        &self,
+        hospital_id: Option<HospitalId>,
+        scopes: Vec<ServiceScope>,
+    ) -> String {
+        self.state
+            .jwt
+            .issue_service_token(Uuid::new_v4(), "handler-test-job", hospital_id, scopes)
+            .unwrap()
+    }
+
+    /// Register a patient at `hospital_id`, known to the hospital resolver
+    pub async fn add_patient(&self, hospital_id: HospitalId) -> Patient {
+        let patient = Patient::new(
+            String::new(),
+            None,
+            "Test".to_string(),
+            "Patient".to_string(),
+            40,
+            Gender::Female,
+            "Chest pain".to_string(),
+            TriageLevel::High,
+            hospital_id,
+            None,
+            None,
+        );
+        self.owners
+            .insert(ResourceKind::Patient, patient.id.into(), hospital_id);
+        self.state
+            .patients
+            .create(&RequestCtx::system(), &patient)
+            .await
+            .unwrap()
+    }
+
+    /// Send a request through the full router, returning the status and body
+    pub async fn send(&self, method: Method, uri: &str, token: &str) -> (StatusCode, String) {
+        self.send_body(method, uri, token, Body::empty()).await
+    }
+
+    /// Send a request with a JSON body through the full router
+    pub async fn send_json(
+        &self,
+        method: Method,
+        uri: &str,
+        token: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, String) {
+        self.send_body(method, uri, token, Body::from(body.to_string()))
+            .await
+    }
+
+    async fn send_body(
+        &self,
+        method: Method,
+        uri: &str,
+        token: &str,
+        body: Body,
+    ) -> (StatusCode, String) {
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .unwrap();
+        let response = super::routes(self.state.clone())
+            .oneshot(req)
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+}
+
+/// Resource ownership set up by the test
+#[derive(Default)]
+pub(crate) struct Owners(Mutex<HashMap<(ResourceKind, Uuid), HospitalId>>);
+
+impl Owners {
+    pub fn insert(&self, kind: ResourceKind, id: Uuid, hospital_id: HospitalId) {
+        self.0.lock().unwrap().insert((kind, id), hospital_id);
+    }
+}
+
+#[async_trait]
+impl HospitalResolver for Owners {
+    async fn resolve_hospital(
+        &self,
+        kind: ResourceKind,
+        id: Uuid,
+    ) -> Result<Option<HospitalId>, AuthError> {
+        Ok(self.0.lock().unwrap().get(&(kind, id)).copied())
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct MemorySessions(Mutex<HashMap<Uuid, Session>>);
+
+#[async_trait]
+impl SessionStore for MemorySessions {
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(30 * 60)
+    }
+
+    async fn save(&self, session: &Session) -> Result<(), AppError> {
+        self.0.lock().unwrap().insert(session.id, session.clone());
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> Result<Option<Session>, AppError> {
+        Ok(self.0.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn list_for_user(&self, user_id: UserId) -> Result<Vec<Session>, AppError> {
+        Ok(self
+            .0
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|session| session.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+        Ok(self.0.lock().unwrap().remove(&id).is_some())
+    }
+}
+
+/// Grants by user and hospital; expired ones are kept but never returned,
+/// as with the TTL in Redis
+#[derive(Default)]
+pub(crate) struct MemoryBreakGlass(Mutex<HashMap<(UserId, HospitalId), BreakGlassGrant>>);
+
+#[async_trait]
+impl BreakGlassStore for MemoryBreakGlass {
+    async fn activate(&self, grant: &BreakGlassGrant) -> Result<(), AppError> {
+        self.0
+            .lock()
+            .unwrap()
+            .insert((grant.user_id, grant.hospital_id), grant.clone());
+        Ok(())
+    }
+
+    async fn active_grant(
+        &self,
+        user_id: UserId,
+        hospital_id: HospitalId,
+    ) -> Result<Option<BreakGlassGrant>, AppError> {
+        Ok(self
+            .0
+            .lock()
+            .unwrap()
+            .get(&(user_id, hospital_id))
+            .filter(|grant| grant.is_active_at(Utc::now()))
+            .cloned())
+    }
+}
+
+struct NoDelegations;
+
+#[async_trait]
+impl DelegationStore for NoDelegations {
+    async fn grant(&self, _delegation: &Delegation) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn active_for_delegate(
+        &self,
+        _delegate_id: UserId,
+    ) -> Result<Option<Delegation>, AppError> {
+        Ok(None)
+    }
+
+    async fn revoke(&self, _delegate_id: UserId) -> Result<Option<Delegation>, AppError> {
+        Ok(None)
+    }
+}
+
+struct NoLockout;
+
+#[async_trait]
+impl LoginAttemptStore for NoLockout {
+    async fn check(&self, _username: &str) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn record_failure(&self, _username: &str) -> Result<LoginFailure, AppError> {
+        Ok(LoginFailure::Delayed {
+            failures: 1,
+            retry_after: Duration::ZERO,
+        })
+    }
+
+    async fn reset(&self, _username: &str) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn unlock(&self, _username: &str) -> Result<bool, AppError> {
+        Ok(false)
+    }
+
+    fn set_policy(&self, _policy: LockoutPolicy) {}
+}